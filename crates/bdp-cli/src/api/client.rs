@@ -2,10 +2,11 @@
 //!
 //! Provides methods to interact with the BDP backend API.
 
-use crate::api::{endpoints, types::*};
+use crate::api::{endpoints, events::JobEventStream, types::*};
 use crate::error::{CliError, Result};
 use crate::manifest::Manifest;
 use reqwest::Client;
+use std::path::Path;
 use std::time::Duration;
 
 // ============================================================================
@@ -60,33 +61,45 @@ impl ApiClient {
     }
 
     /// Resolve manifest dependencies
-    pub async fn resolve_manifest(&self, manifest: &Manifest) -> Result<ResolvedManifest> {
+    ///
+    /// `locked` lists specs already pinned in bdl.lock; these still resolve
+    /// if their version has since been yanked.
+    pub async fn resolve_manifest(
+        &self,
+        manifest: &Manifest,
+        locked: Vec<String>,
+    ) -> Result<ResolvedManifest> {
         let url = endpoints::resolve_url(&self.base_url);
 
         let request = ResolveRequest {
             sources: manifest.sources.clone(),
             tools: manifest.tools.clone(),
+            locked,
         };
 
-        let response = self
-            .client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await?
-            .error_for_status()?;
+        let response = self.client.post(&url).json(&request).send().await?;
+        Self::parse_response(response).await
+    }
 
-        let api_response: ApiResponse<ResolvedManifest> = response.json().await?;
+    /// Resolve a single source spec against the registry
+    ///
+    /// The spec is sent as locked so that a yanked version still resolves,
+    /// flagged as yanked, instead of failing the lookup.
+    pub async fn resolve_source(&self, spec: &str) -> Result<ResolvedSource> {
+        let url = endpoints::resolve_url(&self.base_url);
 
-        if !api_response.success {
-            return Err(CliError::api(
-                api_response.error.unwrap_or_else(|| {
-                    "Failed to resolve manifest dependencies. Check that all source specifications are valid and available.".to_string()
-                }),
-            ));
-        }
+        let request = ResolveRequest {
+            sources: vec![spec.to_string()],
+            tools: vec![],
+            locked: vec![spec.to_string()],
+        };
 
-        Ok(api_response.data)
+        let response = self.client.post(&url).json(&request).send().await?;
+        let mut resolved: ResolvedManifest = Self::parse_response(response).await?;
+        resolved
+            .sources
+            .remove(spec)
+            .ok_or_else(|| CliError::api(format!("Server did not resolve '{}'", spec)))
     }
 
     /// Download a file from the server
@@ -108,6 +121,45 @@ impl ApiClient {
         Ok(bytes)
     }
 
+    /// Download the dependency bundle of an aggregate data source
+    pub async fn download_bundle(
+        &self,
+        org: &str,
+        name: &str,
+        version: &str,
+        format: &str,
+    ) -> Result<Vec<u8>> {
+        let url = endpoints::data_source_bundle_url(&self.base_url, org, name, version, format);
+
+        let response = self.client.get(&url).send().await?.error_for_status()?;
+
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    /// Get the member index of a dependency bundle
+    pub async fn get_bundle_index(
+        &self,
+        org: &str,
+        name: &str,
+        version: &str,
+        format: &str,
+    ) -> Result<BundleIndex> {
+        let url =
+            endpoints::data_source_bundle_members_url(&self.base_url, org, name, version, format);
+
+        let response = self.client.get(&url).send().await?.error_for_status()?;
+
+        let api_response: ApiResponse<BundleIndex> = response.json().await?;
+
+        if !api_response.success {
+            return Err(CliError::api(api_response.error.unwrap_or_else(|| {
+                format!("Bundle of '{}/{}@{}' is unavailable", org, name, version)
+            })));
+        }
+
+        Ok(api_response.data)
+    }
+
     /// Get data source details
     pub async fn get_data_source(
         &self,
@@ -226,6 +278,284 @@ impl ApiClient {
         Ok(api_response.data)
     }
 
+    /// Get a data source by organization and slug
+    ///
+    /// Returns `None` if the data source does not exist yet.
+    pub async fn find_data_source(&self, org: &str, name: &str) -> Result<Option<EntryRef>> {
+        let url = endpoints::data_source_url(&self.base_url, org, name);
+
+        let response = self.client.get(&url).send().await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        let api_response: ApiResponse<EntryRef> = response.error_for_status()?.json().await?;
+        Ok(Some(api_response.data))
+    }
+
+    /// Get the versions of a data source, including yank state
+    pub async fn get_data_source_versions(
+        &self,
+        org: &str,
+        name: &str,
+    ) -> Result<DataSourceVersions> {
+        let url = endpoints::data_source_url(&self.base_url, org, name);
+
+        let response = self.client.get(&url).send().await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(CliError::SourceNotFound(format!("{}:{}", org, name)));
+        }
+
+        let api_response: ApiResponse<DataSourceVersions> =
+            response.error_for_status()?.json().await?;
+        Ok(api_response.data)
+    }
+
+    /// Get one page of the entry-level diff between two versions of a data source
+    #[allow(clippy::too_many_arguments)]
+    pub async fn diff_versions(
+        &self,
+        org: &str,
+        name: &str,
+        from: &str,
+        to: &str,
+        change: Option<&str>,
+        page: i64,
+        per_page: i64,
+    ) -> Result<VersionDiff> {
+        let url = endpoints::data_source_diff_url(
+            &self.base_url,
+            org,
+            name,
+            from,
+            to,
+            change,
+            page,
+            per_page,
+        );
+        let response = self.client.get(&url).send().await?;
+        Self::parse_response(response).await
+    }
+
+    /// Get the dependency graph of a version
+    pub async fn dependency_graph(
+        &self,
+        org: &str,
+        name: &str,
+        version: &str,
+        direction: &str,
+        depth: usize,
+    ) -> Result<DependencyGraph> {
+        let url = endpoints::version_graph_url(
+            &self.base_url,
+            org,
+            name,
+            version,
+            direction,
+            depth,
+            "json",
+        );
+        let response = self.client.get(&url).send().await?;
+        Self::parse_response(response).await
+    }
+
+    /// Get the dependency graph of a version rendered by the server as DOT or Mermaid
+    pub async fn dependency_graph_text(
+        &self,
+        org: &str,
+        name: &str,
+        version: &str,
+        direction: &str,
+        depth: usize,
+        format: &str,
+    ) -> Result<String> {
+        let url = endpoints::version_graph_url(
+            &self.base_url,
+            org,
+            name,
+            version,
+            direction,
+            depth,
+            format,
+        );
+        let response = self.client.get(&url).send().await?;
+        if !response.status().is_success() {
+            return Err(Self::response_error(response).await);
+        }
+        Ok(response.text().await?)
+    }
+
+    /// Get an organization's ID and slug
+    pub async fn get_organization_ref(&self, slug: &str) -> Result<EntryRef> {
+        let url = endpoints::organization_details_url(&self.base_url, slug);
+
+        let response = self.client.get(&url).send().await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(CliError::api(format!(
+                "Organization '{}' not found. Create it on the server before publishing.",
+                slug
+            )));
+        }
+
+        let api_response: ApiResponse<EntryRef> = response.error_for_status()?.json().await?;
+        Ok(api_response.data)
+    }
+
+    /// Create a data source
+    pub async fn create_data_source(&self, request: &CreateDataSourceRequest) -> Result<EntryRef> {
+        let url = endpoints::data_sources_url(&self.base_url);
+        self.post_json(&url, request).await
+    }
+
+    /// Publish a new version of a data source
+    pub async fn publish_version(
+        &self,
+        org: &str,
+        name: &str,
+        request: &PublishVersionRequest,
+    ) -> Result<PublishedVersion> {
+        let url = endpoints::data_source_versions_url(&self.base_url, org, name);
+        self.post_json(&url, request).await
+    }
+
+    /// Upload a file to the version's storage prefix, streaming it from disk
+    pub async fn upload_file(
+        &self,
+        org: &str,
+        name: &str,
+        version: &str,
+        filename: &str,
+        path: &Path,
+    ) -> Result<UploadedFile> {
+        let url = endpoints::file_upload_url(&self.base_url, org, name, version, filename);
+
+        let file = tokio::fs::File::open(path).await?;
+        let size = file.metadata().await?.len();
+        let part = reqwest::multipart::Part::stream_with_length(file, size)
+            .file_name(filename.to_string());
+        let form = reqwest::multipart::Form::new().part("file", part);
+
+        let response = self.client.post(&url).multipart(form).send().await?;
+        Self::parse_response(response).await
+    }
+
+    /// Register uploaded files against a version
+    pub async fn add_version_files(
+        &self,
+        org: &str,
+        name: &str,
+        version: &str,
+        files: Vec<VersionFileRequest>,
+    ) -> Result<()> {
+        let url = endpoints::version_files_url(&self.base_url, org, name, version);
+        let _: serde_json::Value = self
+            .post_json(&url, &serde_json::json!({ "files": files }))
+            .await?;
+        Ok(())
+    }
+
+    /// Start ingestions for a source organization (requires an admin token)
+    pub async fn trigger_ingestion(
+        &self,
+        admin_token: &str,
+        request: &TriggerIngestionRequest,
+    ) -> Result<TriggeredIngestion> {
+        let url = endpoints::jobs_url(&self.base_url);
+        let response = self
+            .client
+            .post(&url)
+            .bearer_auth(admin_token)
+            .json(request)
+            .send()
+            .await?;
+        Self::parse_response(response).await
+    }
+
+    /// Cancel or retry an ingestion job (requires an admin token)
+    pub async fn job_action(
+        &self,
+        admin_token: &str,
+        job_id: &str,
+        action: &str,
+    ) -> Result<JobCommandResult> {
+        let url = endpoints::job_action_url(&self.base_url, job_id, action);
+        let response = self
+            .client
+            .post(&url)
+            .bearer_auth(admin_token)
+            .send()
+            .await?;
+        Self::parse_response(response).await
+    }
+
+    /// List the records of an ingestion job that failed parsing or storage
+    pub async fn job_dead_letters(
+        &self,
+        job_id: &str,
+        stage: Option<&str>,
+        status: Option<&str>,
+        limit: Option<i64>,
+    ) -> Result<DeadLetterList> {
+        let url = endpoints::job_dead_letters_url(&self.base_url, job_id, stage, status, limit);
+        let response = self.client.get(&url).send().await?;
+        Self::parse_response(response).await
+    }
+
+    /// Open the event stream for one ingestion job, or for every active job
+    pub async fn job_events(&self, job_id: Option<&str>) -> Result<JobEventStream> {
+        let url = endpoints::job_events_url(&self.base_url, job_id);
+
+        // Streams outlive the request timeout, so they get a client without one
+        let client = Client::builder()
+            .connect_timeout(Duration::from_secs(30))
+            .build()?;
+        let response = client
+            .get(&url)
+            .header(reqwest::header::ACCEPT, "text/event-stream")
+            .send()
+            .await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(CliError::api(format!("job '{}' not found", job_id.unwrap_or_default())));
+        }
+
+        Ok(JobEventStream::new(response.error_for_status()?))
+    }
+
+    /// POST a JSON body and unwrap the `ApiResponse` envelope
+    async fn post_json<B, T>(&self, url: &str, body: &B) -> Result<T>
+    where
+        B: serde::Serialize + ?Sized,
+        T: serde::de::DeserializeOwned,
+    {
+        let response = self.client.post(url).json(body).send().await?;
+        Self::parse_response(response).await
+    }
+
+    /// Turn a response into its payload, surfacing server error messages
+    async fn parse_response<T: serde::de::DeserializeOwned>(
+        response: reqwest::Response,
+    ) -> Result<T> {
+        if !response.status().is_success() {
+            return Err(Self::response_error(response).await);
+        }
+
+        let api_response: ApiResponse<T> = response.json().await?;
+        Ok(api_response.data)
+    }
+
+    /// Error for a failed response, with the server's message when it sent one
+    async fn response_error(response: reqwest::Response) -> CliError {
+        let status = response.status();
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        let message = body
+            .pointer("/error/message")
+            .and_then(|m| m.as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("request failed with status {}", status));
+        CliError::api(message)
+    }
+
     /// Execute a SQL query
     pub async fn execute_query(&self, sql: String) -> Result<QueryResults> {
         let url = format!("{}/api/v1/query", self.base_url);
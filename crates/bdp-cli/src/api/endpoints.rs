@@ -21,6 +21,34 @@ pub fn data_source_download_url(
     )
 }
 
+/// Build URL of the dependency bundle of an aggregate data source
+pub fn data_source_bundle_url(
+    base_url: &str,
+    org: &str,
+    name: &str,
+    version: &str,
+    format: &str,
+) -> String {
+    format!(
+        "{}/api/v1/data-sources/{}/{}/{}/bundle?format={}",
+        base_url, org, name, version, format
+    )
+}
+
+/// Build URL of the member index of a dependency bundle
+pub fn data_source_bundle_members_url(
+    base_url: &str,
+    org: &str,
+    name: &str,
+    version: &str,
+    format: &str,
+) -> String {
+    format!(
+        "{}/api/v1/data-sources/{}/{}/{}/bundle/members?format={}",
+        base_url, org, name, version, format
+    )
+}
+
 /// Build data source details URL
 pub fn data_source_details_url(base_url: &str, org: &str, name: &str, version: &str) -> String {
     format!("{}/api/v1/data-sources/{}/{}/{}", base_url, org, name, version)
@@ -93,6 +121,139 @@ pub fn organization_details_url(base_url: &str, name: &str) -> String {
     format!("{}/api/v1/organizations/{}", base_url, name)
 }
 
+/// Build data source collection URL
+pub fn data_sources_url(base_url: &str) -> String {
+    format!("{}/api/v1/data-sources", base_url)
+}
+
+/// Build data source URL (without version)
+pub fn data_source_url(base_url: &str, org: &str, name: &str) -> String {
+    format!("{}/api/v1/data-sources/{}/{}", base_url, org, name)
+}
+
+/// Build version publish URL
+pub fn data_source_versions_url(base_url: &str, org: &str, name: &str) -> String {
+    format!("{}/api/v1/data-sources/{}/{}/versions", base_url, org, name)
+}
+
+/// Build version files registration URL
+pub fn version_files_url(base_url: &str, org: &str, name: &str, version: &str) -> String {
+    format!("{}/api/v1/data-sources/{}/{}/{}/files", base_url, org, name, version)
+}
+
+/// Build version dependencies URL
+pub fn version_dependencies_url(base_url: &str, org: &str, name: &str, version: &str) -> String {
+    format!("{}/api/v1/data-sources/{}/{}/{}/dependencies", base_url, org, name, version)
+}
+
+/// Build URL of the dependency graph of a version
+pub fn version_graph_url(
+    base_url: &str,
+    org: &str,
+    name: &str,
+    version: &str,
+    direction: &str,
+    depth: usize,
+    format: &str,
+) -> String {
+    format!(
+        "{}/api/v1/data-sources/{}/{}/{}/graph?direction={}&depth={}&format={}",
+        base_url, org, name, version, direction, depth, format
+    )
+}
+
+/// Build URL of the entry-level diff between two versions of a data source
+#[allow(clippy::too_many_arguments)]
+pub fn data_source_diff_url(
+    base_url: &str,
+    org: &str,
+    name: &str,
+    from: &str,
+    to: &str,
+    change: Option<&str>,
+    page: i64,
+    per_page: i64,
+) -> String {
+    let mut url = format!(
+        "{}/api/v1/data-sources/{}/{}/diff?from={}&to={}&page={}&per_page={}",
+        base_url,
+        org,
+        name,
+        urlencoding::encode(from),
+        urlencoding::encode(to),
+        page,
+        per_page
+    );
+    if let Some(change) = change {
+        url.push_str(&format!("&change={}", urlencoding::encode(change)));
+    }
+    url
+}
+
+/// Build file upload URL
+pub fn file_upload_url(
+    base_url: &str,
+    org: &str,
+    name: &str,
+    version: &str,
+    filename: &str,
+) -> String {
+    format!(
+        "{}/api/v1/files/{}/{}/{}/{}",
+        base_url,
+        org,
+        name,
+        version,
+        urlencoding::encode(filename)
+    )
+}
+
+/// Build ingestion job collection URL
+pub fn jobs_url(base_url: &str) -> String {
+    format!("{}/api/v1/jobs", base_url)
+}
+
+/// Build URL for an action on an ingestion job (`cancel`, `retry`,
+/// `dead-letters/reprocess`)
+pub fn job_action_url(base_url: &str, job_id: &str, action: &str) -> String {
+    format!("{}/api/v1/jobs/{}/{}", base_url, job_id, action)
+}
+
+/// Build job event stream URL, for one job or for every active job
+pub fn job_events_url(base_url: &str, job_id: Option<&str>) -> String {
+    match job_id {
+        Some(job_id) => format!("{}/api/v1/jobs/{}/events", base_url, job_id),
+        None => format!("{}/api/v1/jobs/events", base_url),
+    }
+}
+
+/// Build URL listing the dead letters of an ingestion job
+pub fn job_dead_letters_url(
+    base_url: &str,
+    job_id: &str,
+    stage: Option<&str>,
+    status: Option<&str>,
+    limit: Option<i64>,
+) -> String {
+    let mut params = Vec::new();
+    if let Some(stage) = stage {
+        params.push(format!("stage={}", urlencoding::encode(stage)));
+    }
+    if let Some(status) = status {
+        params.push(format!("status={}", urlencoding::encode(status)));
+    }
+    if let Some(limit) = limit {
+        params.push(format!("limit={}", limit));
+    }
+
+    let url = format!("{}/api/v1/jobs/{}/dead-letters", base_url, job_id);
+    if params.is_empty() {
+        url
+    } else {
+        format!("{}?{}", url, params.join("&"))
+    }
+}
+
 /// Build health check URL
 pub fn health_url(base_url: &str) -> String {
     format!("{}/health", base_url)
@@ -118,6 +279,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_version_graph_url() {
+        let url = version_graph_url(
+            "http://localhost:8000",
+            "ncbi",
+            "taxonomy",
+            "1.0",
+            "reverse",
+            2,
+            "dot",
+        );
+        assert_eq!(
+            url,
+            "http://localhost:8000/api/v1/data-sources/ncbi/taxonomy/1.0/graph?direction=reverse&depth=2&format=dot"
+        );
+    }
+
+    #[test]
+    fn test_data_source_bundle_urls() {
+        let url =
+            data_source_bundle_url("http://localhost:8000", "uniprot", "proteome", "1.0", "fasta");
+        assert_eq!(
+            url,
+            "http://localhost:8000/api/v1/data-sources/uniprot/proteome/1.0/bundle?format=fasta"
+        );
+
+        let url = data_source_bundle_members_url(
+            "http://localhost:8000",
+            "uniprot",
+            "proteome",
+            "1.0",
+            "fasta",
+        );
+        assert!(url.ends_with("/proteome/1.0/bundle/members?format=fasta"));
+    }
+
     #[test]
     fn test_data_source_details_url() {
         let url = data_source_details_url("http://localhost:8000", "uniprot", "P01308", "1.0");
@@ -142,6 +339,55 @@ mod tests {
         assert_eq!(url, "http://localhost:8000/api/v1/organizations");
     }
 
+    #[test]
+    fn test_publish_urls() {
+        let base = "http://localhost:8000";
+        assert_eq!(data_sources_url(base), "http://localhost:8000/api/v1/data-sources");
+        assert_eq!(
+            data_source_url(base, "my-lab", "proteome"),
+            "http://localhost:8000/api/v1/data-sources/my-lab/proteome"
+        );
+        assert_eq!(
+            data_source_versions_url(base, "my-lab", "proteome"),
+            "http://localhost:8000/api/v1/data-sources/my-lab/proteome/versions"
+        );
+        assert_eq!(
+            version_files_url(base, "my-lab", "proteome", "1.0"),
+            "http://localhost:8000/api/v1/data-sources/my-lab/proteome/1.0/files"
+        );
+        assert_eq!(
+            version_dependencies_url(base, "my-lab", "proteome", "1.0"),
+            "http://localhost:8000/api/v1/data-sources/my-lab/proteome/1.0/dependencies"
+        );
+        assert_eq!(
+            file_upload_url(base, "my-lab", "proteome", "1.0", "filtered.fasta"),
+            "http://localhost:8000/api/v1/files/my-lab/proteome/1.0/filtered.fasta"
+        );
+    }
+
+    #[test]
+    fn test_job_urls() {
+        let base = "http://localhost:8000";
+        assert_eq!(jobs_url(base), "http://localhost:8000/api/v1/jobs");
+        assert_eq!(
+            job_action_url(base, "abc", "retry"),
+            "http://localhost:8000/api/v1/jobs/abc/retry"
+        );
+        assert_eq!(job_events_url(base, None), "http://localhost:8000/api/v1/jobs/events");
+        assert_eq!(
+            job_events_url(base, Some("abc")),
+            "http://localhost:8000/api/v1/jobs/abc/events"
+        );
+        assert_eq!(
+            job_dead_letters_url(base, "abc", None, None, None),
+            "http://localhost:8000/api/v1/jobs/abc/dead-letters"
+        );
+        assert_eq!(
+            job_dead_letters_url(base, "abc", Some("parse"), Some("pending"), Some(20)),
+            "http://localhost:8000/api/v1/jobs/abc/dead-letters?stage=parse&status=pending&limit=20"
+        );
+    }
+
     #[test]
     fn test_health_url() {
         let url = health_url("http://localhost:8000");
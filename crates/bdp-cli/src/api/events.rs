@@ -0,0 +1,109 @@
+//! Server-Sent Events client
+//!
+//! Reads the job event streams served under `/api/v1/jobs`. Each event names
+//! its kind (`status` or `progress`) and carries a job progress snapshot as
+//! JSON data.
+
+use crate::api::types::JobProgressEvent;
+use crate::error::{CliError, Result};
+
+/// A status transition or progress tick of an ingestion job
+#[derive(Debug, Clone)]
+pub struct JobEvent {
+    /// Event kind, `status` or `progress`
+    pub kind: String,
+    pub job: JobProgressEvent,
+}
+
+/// Stream of job events read from an open SSE response
+pub struct JobEventStream {
+    response: reqwest::Response,
+    buffer: Vec<u8>,
+}
+
+impl JobEventStream {
+    pub(crate) fn new(response: reqwest::Response) -> Self {
+        Self {
+            response,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Wait for the next event, or `None` once the server closes the stream
+    pub async fn next(&mut self) -> Result<Option<JobEvent>> {
+        loop {
+            while let Some(frame) = take_frame(&mut self.buffer) {
+                if let Some(event) = parse_frame(&frame)? {
+                    return Ok(Some(event));
+                }
+            }
+
+            match self.response.chunk().await? {
+                Some(chunk) => self.buffer.extend_from_slice(&chunk),
+                None => return Ok(None),
+            }
+        }
+    }
+}
+
+/// Remove the first complete frame (terminated by a blank line) from the buffer
+fn take_frame(buffer: &mut Vec<u8>) -> Option<String> {
+    let end = buffer.windows(2).position(|w| w == b"\n\n")?;
+    let frame: Vec<u8> = buffer.drain(..end + 2).collect();
+    Some(String::from_utf8_lossy(&frame[..end]).into_owned())
+}
+
+/// Parse a frame into a job event, skipping keep-alive comments
+fn parse_frame(frame: &str) -> Result<Option<JobEvent>> {
+    let mut kind = "message".to_string();
+    let mut data = Vec::new();
+
+    for line in frame.lines() {
+        if let Some(value) = line.strip_prefix("event:") {
+            kind = value.trim().to_string();
+        } else if let Some(value) = line.strip_prefix("data:") {
+            data.push(value.strip_prefix(' ').unwrap_or(value));
+        }
+    }
+
+    if data.is_empty() {
+        return Ok(None);
+    }
+
+    let job = serde_json::from_str(&data.join("\n"))
+        .map_err(|e| CliError::api(format!("invalid job event: {}", e)))?;
+    Ok(Some(JobEvent { kind, job }))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_take_frame() {
+        let mut buffer = b"event: status\ndata: {}\n\nevent: prog".to_vec();
+
+        assert_eq!(take_frame(&mut buffer).unwrap(), "event: status\ndata: {}");
+        assert_eq!(buffer, b"event: prog");
+        assert!(take_frame(&mut buffer).is_none());
+    }
+
+    #[test]
+    fn test_parse_frame() {
+        let frame = r#"event: progress
+data: {"job_id":"abc","job_type":"uniprot_swissprot","external_version":"2025_01","status":"storing","total_records":200,"records_stored":50,"completion_percentage":25.0}"#;
+
+        let event = parse_frame(frame).unwrap().unwrap();
+        assert_eq!(event.kind, "progress");
+        assert_eq!(event.job.job_id, "abc");
+        assert_eq!(event.job.records_stored, 50);
+        assert_eq!(event.job.percentage(), 25.0);
+        assert!(!event.job.is_finished());
+    }
+
+    #[test]
+    fn test_parse_keep_alive() {
+        assert!(parse_frame(":").unwrap().is_none());
+    }
+}
@@ -4,7 +4,9 @@
 
 pub mod client;
 pub mod endpoints;
+pub mod events;
 pub mod types;
 
 pub use client::ApiClient;
+pub use events::{JobEvent, JobEventStream};
 pub use types::*;
@@ -19,6 +19,9 @@ pub struct ApiResponse<T> {
 pub struct ResolveRequest {
     pub sources: Vec<String>,
     pub tools: Vec<String>,
+    /// Specs already pinned in bdl.lock (allowed to resolve to yanked versions)
+    #[serde(default)]
+    pub locked: Vec<String>,
 }
 
 /// Response from resolve endpoint
@@ -44,14 +47,118 @@ pub struct ResolvedSource {
     pub size: i64,
 
     /// External version string
+    #[serde(default)]
     pub external_version: String,
 
     /// Download URL (presigned if from S3)
+    #[serde(default)]
     pub download_url: String,
 
     /// Number of dependencies
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dependency_count: Option<i32>,
+
+    /// Transitive dependencies, for aggregate sources
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dependencies: Option<Vec<DependencyInfo>>,
+
+    /// Bundle of the dependency files, preferred over pulling them one by one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bundle: Option<BundleInfo>,
+
+    /// Whether the locked version has been yanked
+    #[serde(default)]
+    pub yanked: bool,
+
+    /// Reason given when the version was yanked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub yanked_reason: Option<String>,
+
+    /// Entry (org:name) resolved instead of the one the spec names, when
+    /// that identifier was retired upstream or deprecated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replaced_by: Option<String>,
+
+    /// Retired or deprecated identifiers met while resolving the spec
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// A dependency of a resolved source
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DependencyInfo {
+    /// Dependency specification (org:name@version)
+    pub source: String,
+
+    /// Format of the dependency's file
+    pub format: String,
+
+    /// SHA-256 checksum
+    pub checksum: String,
+
+    /// File size in bytes
+    pub size: i64,
+}
+
+impl DependencyInfo {
+    /// Source specification with the format (org:name-format@version)
+    pub fn spec(&self) -> String {
+        match self.source.split_once('@') {
+            Some((name, version)) => format!("{}-{}@{}", name, self.format, version),
+            None => format!("{}-{}", self.source, self.format),
+        }
+    }
+}
+
+/// A built bundle of an aggregate source's dependencies
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BundleInfo {
+    /// Bundle layout (multifasta, jsonl, tar)
+    pub bundle_format: String,
+
+    /// SHA-256 checksum
+    pub checksum: String,
+
+    /// File size in bytes
+    pub size: i64,
+
+    /// Number of member files
+    pub member_count: i32,
+}
+
+/// Member index of a bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleIndex {
+    /// Member format (fasta, json, dat)
+    pub format: String,
+
+    /// Bundle layout (multifasta, jsonl, tar)
+    pub bundle_format: String,
+
+    /// SHA-256 checksum of the bundle
+    pub sha256: String,
+
+    /// Bundle size in bytes
+    pub size: i64,
+
+    /// Where each member sits inside the bundle
+    pub members: Vec<BundleMember>,
+}
+
+/// A member file inside a bundle
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BundleMember {
+    /// Member specification (org:name@version)
+    pub source: String,
+
+    /// Byte offset of the member inside the bundle
+    pub offset: u64,
+
+    /// Member size in bytes
+    pub size: u64,
+
+    /// SHA-256 checksum of the member's bytes
+    pub sha256: String,
 }
 
 /// A resolved tool entry
@@ -91,6 +198,28 @@ pub struct DataSource {
     pub updated_at: String,
 }
 
+/// Version list of a data source, as returned by the data source details endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataSourceVersions {
+    /// Latest non-yanked version
+    pub latest_version: Option<String>,
+    #[serde(default)]
+    pub versions: Vec<VersionSummary>,
+}
+
+/// A single version of a data source
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionSummary {
+    pub version: String,
+    /// Upstream release the version was built from (e.g., "2025_01")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_version: Option<String>,
+    #[serde(default)]
+    pub yanked: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub yanked_reason: Option<String>,
+}
+
 /// Organization details
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Organization {
@@ -122,6 +251,290 @@ pub struct SearchResult {
     pub description: Option<String>,
     pub format: String,
     pub entry_type: String,
+    /// Whether the entry has been deprecated
+    #[serde(default)]
+    pub deprecated: bool,
+    /// Entry that replaced a deprecated one (org:name)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub superseded_by: Option<String>,
+}
+
+/// Minimal registry reference returned by create/get endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntryRef {
+    pub id: String,
+    pub slug: String,
+}
+
+/// Request to create a data source
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateDataSourceRequest {
+    pub organization_id: String,
+    pub slug: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub source_type: String,
+}
+
+/// Request to publish a new version of a data source
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishVersionRequest {
+    pub data_source_id: String,
+    pub version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<i64>,
+    /// Uploaded files, registered together with the version
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<VersionFileRequest>,
+    /// Dependencies, recorded together with the version
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<DependencyRequest>,
+}
+
+/// Published version details
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishedVersion {
+    pub id: String,
+    pub version: String,
+}
+
+/// Result of uploading a file to storage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadedFile {
+    pub key: String,
+    pub checksum: String,
+    pub size: i64,
+}
+
+/// A file to register against a version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionFileRequest {
+    pub format: String,
+    pub s3_key: String,
+    pub checksum: String,
+    pub size_bytes: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compression: Option<String>,
+}
+
+/// A dependency to record against a version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyRequest {
+    pub source: String,
+}
+
+/// Request to start ingestions for a source organization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TriggerIngestionRequest {
+    pub organization_slug: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<serde_json::Value>,
+}
+
+/// Versions enqueued by an ingestion trigger
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TriggeredIngestion {
+    pub source: String,
+    pub enqueued: Vec<String>,
+    pub skipped: Vec<String>,
+}
+
+/// State of an ingestion job after cancel, retry or reprocessing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobCommandResult {
+    pub job_id: String,
+    pub status: String,
+    #[serde(default)]
+    pub work_units_cancelled: Option<i64>,
+    #[serde(default)]
+    pub work_units_retried: Option<i64>,
+    #[serde(default)]
+    pub dead_letters_pending: Option<i64>,
+}
+
+/// A record of an ingestion job that failed parsing or storage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetter {
+    pub id: String,
+    /// Stage the record failed in, `parse` or `store`
+    pub stage: String,
+    pub record_identifier: Option<String>,
+    pub source_file: Option<String>,
+    pub source_offset: Option<i64>,
+    pub raw_record: String,
+    pub error_message: String,
+    /// Error and its causes, outermost first
+    #[serde(default)]
+    pub error_chain: Vec<String>,
+    /// `pending` or `reprocessed`
+    pub status: String,
+    pub attempts: i32,
+}
+
+/// Dead letters of an ingestion job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetterList {
+    pub job_id: String,
+    pub dead_letters: Vec<DeadLetter>,
+    pub total: i64,
+}
+
+/// Progress of an ingestion job, as streamed by the job event routes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobProgressEvent {
+    pub job_id: String,
+    pub job_type: String,
+    pub external_version: String,
+    pub status: String,
+    #[serde(default)]
+    pub total_records: i64,
+    #[serde(default)]
+    pub records_processed: i64,
+    #[serde(default)]
+    pub records_stored: i64,
+    #[serde(default)]
+    pub records_failed: i64,
+    #[serde(default)]
+    pub records_skipped: i64,
+    #[serde(default)]
+    pub work_units_pending: i64,
+    #[serde(default)]
+    pub work_units_claimed: i64,
+    #[serde(default)]
+    pub work_units_processing: i64,
+    #[serde(default)]
+    pub work_units_completed: i64,
+    #[serde(default)]
+    pub work_units_failed: i64,
+    #[serde(default)]
+    pub completion_percentage: f64,
+    #[serde(default)]
+    pub work_unit_percentage: f64,
+}
+
+impl JobProgressEvent {
+    /// Whether the job finished and will not report again
+    pub fn is_finished(&self) -> bool {
+        matches!(self.status.as_str(), "completed" | "failed" | "cancelled")
+    }
+
+    /// Best available completion estimate, in percent
+    ///
+    /// Record counts are only known once the source has been parsed, so work
+    /// units stand in until then.
+    pub fn percentage(&self) -> f64 {
+        if self.total_records > 0 {
+            self.completion_percentage
+        } else {
+            self.work_unit_percentage
+        }
+    }
+}
+
+/// A field that differs between two versions of an entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub added: Vec<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed: Vec<serde_json::Value>,
+}
+
+/// An entry added, removed or modified between two versions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntryDiff {
+    pub organization_slug: String,
+    pub entry_slug: String,
+    pub entry_name: String,
+    /// "added", "removed" or "modified"
+    pub change: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_version: Option<String>,
+    #[serde(default)]
+    pub details_available: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<FieldChange>,
+}
+
+/// Number of changed entries by kind of change
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct DiffSummary {
+    pub added: i64,
+    pub removed: i64,
+    pub modified: i64,
+}
+
+/// Page position of a paginated response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Pagination {
+    pub page: i64,
+    pub per_page: i64,
+    pub total: i64,
+    pub pages: i64,
+    pub has_next: bool,
+}
+
+/// One page of the entry-level diff between two versions of a data source
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionDiff {
+    pub source: String,
+    pub from_version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_release: Option<String>,
+    pub to_version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_release: Option<String>,
+    pub summary: DiffSummary,
+    pub entries: Vec<EntryDiff>,
+    pub pagination: Pagination,
+}
+
+/// A data source version in a dependency graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphNode {
+    /// `org:slug@version`
+    pub id: String,
+    pub organization_slug: String,
+    pub entry_slug: String,
+    pub entry_name: String,
+    pub version: String,
+    pub depth: usize,
+    /// False for required versions that are not in the registry
+    pub resolved: bool,
+}
+
+/// A dependency, pointing from the dependent to its dependency
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphEdge {
+    pub from: String,
+    pub to: String,
+    pub required_version: String,
+    pub dependency_type: String,
+}
+
+/// Forward or reverse dependency graph of a data source version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyGraph {
+    pub root: String,
+    /// "forward" or "reverse"
+    pub direction: String,
+    pub depth: usize,
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+    #[serde(default)]
+    pub truncated: bool,
 }
 
 /// Query request payload
@@ -160,10 +573,23 @@ mod tests {
         let request = ResolveRequest {
             sources: vec!["uniprot:P01308-fasta@1.0".to_string()],
             tools: vec!["ncbi:blast@2.14.0".to_string()],
+            locked: vec![],
         };
 
         let json = serde_json::to_string(&request).unwrap();
         assert!(json.contains("uniprot:P01308-fasta@1.0"));
         assert!(json.contains("ncbi:blast@2.14.0"));
     }
+
+    #[test]
+    fn test_dependency_spec() {
+        let dependency = DependencyInfo {
+            source: "uniprot:P01308@1.0".to_string(),
+            format: "fasta".to_string(),
+            checksum: "abc123".to_string(),
+            size: 1024,
+        };
+
+        assert_eq!(dependency.spec(), "uniprot:P01308-fasta@1.0");
+    }
 }
@@ -0,0 +1,337 @@
+//! `bdp admin` command implementation
+//!
+//! Server administration. Commands that change ingestion jobs need the
+//! server's admin token, passed with `--admin-token` or the `BDP_ADMIN_TOKEN`
+//! environment variable. Watching jobs and listing their failed records only
+//! read public data.
+
+use crate::api::types::{DeadLetter, JobProgressEvent, TriggerIngestionRequest};
+use crate::api::ApiClient;
+use crate::error::{CliError, Result};
+use crate::progress::create_progress_bar;
+use crate::{AdminCommand, IngestCommand, JobsCommand};
+use colored::Colorize;
+
+/// Execute admin command
+pub async fn run(
+    command: &AdminCommand,
+    admin_token: Option<String>,
+    server_url: String,
+) -> Result<()> {
+    let client = ApiClient::new(server_url)?;
+
+    match command {
+        AdminCommand::Ingest { command } => {
+            let token = admin_token.filter(|t| !t.is_empty()).ok_or_else(|| {
+                CliError::config(
+                    "An admin token is required. Pass --admin-token or set BDP_ADMIN_TOKEN",
+                )
+            })?;
+            ingest(&client, &token, command).await
+        },
+        AdminCommand::Jobs { command } => jobs(&client, command).await,
+    }
+}
+
+async fn ingest(client: &ApiClient, token: &str, command: &IngestCommand) -> Result<()> {
+    match command {
+        IngestCommand::Start {
+            organization,
+            version,
+            from,
+            to,
+            include_existing,
+        } => {
+            let request = TriggerIngestionRequest {
+                organization_slug: organization.clone(),
+                external_version: version.clone(),
+                mode: ingestion_mode(from.as_deref(), to.as_deref(), *include_existing),
+            };
+            let triggered = client.trigger_ingestion(token, &request).await?;
+
+            if triggered.enqueued.is_empty() {
+                println!(
+                    "{} Nothing to ingest for {}, all matching versions are already ingested",
+                    "→".cyan(),
+                    triggered.source
+                );
+            }
+            for version in &triggered.enqueued {
+                println!("{} Enqueued {} {}", "✓".green(), triggered.source, version);
+            }
+            for version in &triggered.skipped {
+                println!("  {} {} already ingested", "-".dimmed(), version);
+            }
+            Ok(())
+        },
+        IngestCommand::Cancel { job_id } => {
+            let result = client.job_action(token, job_id, "cancel").await?;
+            println!(
+                "{} Cancelled job {} ({} pending work units dropped)",
+                "✓".green(),
+                result.job_id,
+                result.work_units_cancelled.unwrap_or(0)
+            );
+            Ok(())
+        },
+        IngestCommand::Retry { job_id } => {
+            let result = client.job_action(token, job_id, "retry").await?;
+            println!(
+                "{} Retrying {} failed work units of job {}",
+                "✓".green(),
+                result.work_units_retried.unwrap_or(0),
+                result.job_id
+            );
+            Ok(())
+        },
+        IngestCommand::Reprocess { job_id } => {
+            let result = client
+                .job_action(token, job_id, "dead-letters/reprocess")
+                .await?;
+            println!(
+                "{} Reprocessing {} failed records of job {}",
+                "✓".green(),
+                result.dead_letters_pending.unwrap_or(0),
+                result.job_id
+            );
+            Ok(())
+        },
+    }
+}
+
+async fn jobs(client: &ApiClient, command: &JobsCommand) -> Result<()> {
+    match command {
+        JobsCommand::Watch {
+            job_id: Some(job_id),
+        } => watch_job(client, job_id).await,
+        JobsCommand::Watch { job_id: None } => watch_all(client).await,
+        JobsCommand::Failures {
+            job_id,
+            stage,
+            all,
+            limit,
+            raw,
+        } => {
+            let status = (!*all).then_some("pending");
+            let list = client
+                .job_dead_letters(job_id, stage.as_deref(), status, Some(*limit))
+                .await?;
+
+            if list.dead_letters.is_empty() {
+                println!("{} No failed records for job {}", "✓".green(), list.job_id);
+                return Ok(());
+            }
+
+            println!("{} {} failed records for job {}", "→".cyan(), list.total, list.job_id);
+            for letter in &list.dead_letters {
+                println!("{} {}", "✗".red(), format_dead_letter(letter));
+                for cause in letter.error_chain.iter().skip(1) {
+                    println!("    {} {}", "caused by:".dimmed(), cause);
+                }
+                if *raw {
+                    for line in letter.raw_record.lines() {
+                        println!("    {}", line.dimmed());
+                    }
+                }
+            }
+            if list.total > list.dead_letters.len() as i64 {
+                println!(
+                    "  {} {} more not shown (use --limit)",
+                    "-".dimmed(),
+                    list.total - list.dead_letters.len() as i64
+                );
+            }
+            Ok(())
+        },
+    }
+}
+
+/// One-line summary of a failed record
+fn format_dead_letter(letter: &DeadLetter) -> String {
+    let mut line = format!(
+        "[{}] {}",
+        letter.stage,
+        letter
+            .record_identifier
+            .as_deref()
+            .unwrap_or("(unidentified)")
+    );
+    if let Some(offset) = letter.source_offset {
+        line.push_str(&format!(
+            " at {}:{}",
+            letter.source_file.as_deref().unwrap_or("offset"),
+            offset
+        ));
+    }
+    line.push_str(&format!(": {}", letter.error_chain.first().unwrap_or(&letter.error_message)));
+    if letter.status != "pending" {
+        line.push_str(&format!(" ({})", letter.status));
+    } else if letter.attempts > 1 {
+        line.push_str(&format!(" ({} attempts)", letter.attempts));
+    }
+    line
+}
+
+/// Render one job as a progress bar until it finishes
+async fn watch_job(client: &ApiClient, job_id: &str) -> Result<()> {
+    let mut events = client.job_events(Some(job_id)).await?;
+    let bar = create_progress_bar(100, &format!("Watching job {}", job_id));
+
+    let mut last = None;
+    while let Some(event) = events.next().await? {
+        bar.set_position(event.job.percentage().round() as u64);
+        bar.set_message(format_job_line(&event.job));
+        last = Some(event.job);
+    }
+
+    match last {
+        Some(job) if job.status == "completed" => {
+            bar.finish_with_message(format!("{} {}", "✓".green(), format_job_line(&job)))
+        },
+        Some(job) if job.is_finished() => {
+            bar.abandon_with_message(format!("{} {}", "✗".red(), format_job_line(&job)))
+        },
+        _ => bar.abandon_with_message("Event stream closed before the job finished".to_string()),
+    }
+    Ok(())
+}
+
+/// Print a line for every status change and progress tick of active jobs
+async fn watch_all(client: &ApiClient) -> Result<()> {
+    let mut events = client.job_events(None).await?;
+    println!("{} Watching active ingestion jobs (Ctrl+C to stop)", "→".cyan());
+
+    while let Some(event) = events.next().await? {
+        let marker = match event.job.status.as_str() {
+            "completed" => "✓".green(),
+            "failed" | "cancelled" => "✗".red(),
+            _ if event.kind == "status" => "→".cyan(),
+            _ => "·".dimmed(),
+        };
+        println!("{} {} {}", marker, short_id(&event.job.job_id), format_job_line(&event.job));
+    }
+    Ok(())
+}
+
+/// One-line summary of a job's progress
+fn format_job_line(job: &JobProgressEvent) -> String {
+    let units = job.work_units_pending
+        + job.work_units_claimed
+        + job.work_units_processing
+        + job.work_units_completed
+        + job.work_units_failed;
+
+    let mut line = format!(
+        "{} {} [{}] {:.1}%",
+        job.job_type,
+        job.external_version,
+        job.status,
+        job.percentage()
+    );
+    if job.total_records > 0 {
+        line.push_str(&format!(", {}/{} records stored", job.records_stored, job.total_records));
+    }
+    if units > 0 {
+        line.push_str(&format!(", {}/{} work units", job.work_units_completed, units));
+    }
+    if job.records_failed > 0 {
+        line.push_str(&format!(", {} failed", job.records_failed));
+    }
+    line
+}
+
+fn short_id(job_id: &str) -> &str {
+    job_id.get(..8).unwrap_or(job_id)
+}
+
+/// Build the server's ingestion mode from the backfill flags
+///
+/// Without `--from` the server defaults to ingesting the latest version.
+fn ingestion_mode(
+    from: Option<&str>,
+    to: Option<&str>,
+    include_existing: bool,
+) -> Option<serde_json::Value> {
+    from.map(|start| {
+        serde_json::json!({
+            "mode": "historical",
+            "start_version": start,
+            "end_version": to,
+            "skip_existing": !include_existing,
+        })
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ingestion_mode() {
+        assert!(ingestion_mode(None, None, false).is_none());
+
+        let mode = ingestion_mode(Some("2024_01"), Some("2024_06"), true).unwrap();
+        assert_eq!(mode["mode"], "historical");
+        assert_eq!(mode["start_version"], "2024_01");
+        assert_eq!(mode["end_version"], "2024_06");
+        assert_eq!(mode["skip_existing"], false);
+    }
+
+    #[test]
+    fn test_format_job_line() {
+        let job: JobProgressEvent = serde_json::from_value(serde_json::json!({
+            "job_id": "0f1e2d3c-0000-0000-0000-000000000000",
+            "job_type": "uniprot_trembl",
+            "external_version": "2025_01",
+            "status": "storing",
+            "total_records": 1000,
+            "records_stored": 250,
+            "records_failed": 3,
+            "work_units_completed": 1,
+            "work_units_processing": 3,
+            "completion_percentage": 25.0,
+        }))
+        .unwrap();
+
+        assert_eq!(
+            format_job_line(&job),
+            "uniprot_trembl 2025_01 [storing] 25.0%, 250/1000 records stored, 1/4 work units, 3 failed"
+        );
+        assert_eq!(short_id(&job.job_id), "0f1e2d3c");
+    }
+
+    #[test]
+    fn test_format_dead_letter() {
+        let letter: DeadLetter = serde_json::from_value(serde_json::json!({
+            "id": "5a1b",
+            "stage": "store",
+            "record_identifier": "P12345",
+            "source_file": "uniprot_sprot.dat.gz",
+            "source_offset": 2048,
+            "raw_record": "ID   TEST\n//\n",
+            "error_message": "Failed to store P12345: duplicate key value",
+            "error_chain": ["Failed to store P12345", "duplicate key value"],
+            "status": "pending",
+            "attempts": 2,
+        }))
+        .unwrap();
+
+        assert_eq!(
+            format_dead_letter(&letter),
+            "[store] P12345 at uniprot_sprot.dat.gz:2048: Failed to store P12345 (2 attempts)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_admin_requires_token() {
+        let command = AdminCommand::Ingest {
+            command: IngestCommand::Retry {
+                job_id: "00000000-0000-0000-0000-000000000000".to_string(),
+            },
+        };
+
+        let result = run(&command, None, "http://localhost:9999".to_string()).await;
+        assert!(matches!(result, Err(CliError::Config(_))));
+    }
+}
@@ -0,0 +1,227 @@
+//! `bdp diff` command implementation
+//!
+//! Lists the entries added, removed and modified between two versions of a
+//! data source, with the fields that changed in each modified entry. Versions
+//! can be given as internal versions or upstream releases.
+
+use crate::api::types::{EntryDiff, FieldChange, VersionDiff};
+use crate::api::ApiClient;
+use crate::error::{CliError, Result};
+use crate::manifest::parse_source_spec;
+use colored::Colorize;
+
+/// Source and the two versions to compare
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffTarget {
+    pub org: String,
+    pub name: String,
+    pub from: String,
+    pub to: String,
+}
+
+/// Parse `SPEC@A SPEC@B`, which must name the same data source
+pub fn diff_target(from_spec: &str, to_spec: &str) -> Result<DiffTarget> {
+    let (org, name, from, _) = parse_source_spec(from_spec)?;
+    let (to_org, to_name, to, _) = parse_source_spec(to_spec)?;
+
+    if org != to_org || name != to_name {
+        return Err(CliError::invalid_source_spec(format!(
+            "'{}' and '{}' are different data sources; diff compares two versions of one source",
+            from_spec, to_spec
+        )));
+    }
+
+    Ok(DiffTarget {
+        org,
+        name,
+        from,
+        to,
+    })
+}
+
+/// Render a JSON value without quoting strings
+fn display_value(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Null => "(none)".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// One line describing a changed field
+pub fn describe_field(change: &FieldChange) -> String {
+    if change.before.is_some() || change.after.is_some() {
+        let before = change
+            .before
+            .as_ref()
+            .map(display_value)
+            .unwrap_or_default();
+        let after = change.after.as_ref().map(display_value).unwrap_or_default();
+        return format!("{}: {} → {}", change.field, before, after);
+    }
+
+    let items: Vec<String> = change
+        .added
+        .iter()
+        .map(|v| format!("+{}", display_value(v)))
+        .chain(
+            change
+                .removed
+                .iter()
+                .map(|v| format!("-{}", display_value(v))),
+        )
+        .collect();
+    format!("{}: {}", change.field, items.join(" "))
+}
+
+fn version_label(version: &str, release: Option<&str>) -> String {
+    match release {
+        Some(release) if release != version => format!("{} ({})", version, release),
+        _ => version.to_string(),
+    }
+}
+
+fn print_entry(entry: &EntryDiff) {
+    let version = |v: &Option<String>| v.clone().unwrap_or_default();
+
+    match entry.change.as_str() {
+        "added" => println!(
+            "{} {} {}",
+            "+".green(),
+            entry.entry_slug.green(),
+            version(&entry.to_version).dimmed()
+        ),
+        "removed" => println!(
+            "{} {} {}",
+            "-".red(),
+            entry.entry_slug.red(),
+            version(&entry.from_version).dimmed()
+        ),
+        _ => {
+            println!(
+                "{} {} {}",
+                "~".yellow(),
+                entry.entry_slug.yellow(),
+                format!("{} → {}", version(&entry.from_version), version(&entry.to_version))
+                    .dimmed()
+            );
+            if !entry.details_available {
+                println!("    {}", "(no field detail recorded for these versions)".dimmed());
+            }
+            for field in &entry.fields {
+                println!("    {}", describe_field(field));
+            }
+        },
+    }
+}
+
+fn print_diff(spec: &str, diff: &VersionDiff) {
+    println!(
+        "{} {} → {}",
+        spec.bold(),
+        version_label(&diff.from_version, diff.from_release.as_deref()),
+        version_label(&diff.to_version, diff.to_release.as_deref())
+    );
+    println!(
+        "{} added, {} removed, {} modified",
+        diff.summary.added.to_string().green(),
+        diff.summary.removed.to_string().red(),
+        diff.summary.modified.to_string().yellow()
+    );
+    println!();
+
+    for entry in &diff.entries {
+        print_entry(entry);
+    }
+
+    if diff.pagination.pages > 1 {
+        println!();
+        println!(
+            "Page {} of {} ({} entries)",
+            diff.pagination.page, diff.pagination.pages, diff.pagination.total
+        );
+        if diff.pagination.has_next {
+            println!("Use --page {} to see more", diff.pagination.page + 1);
+        }
+    }
+}
+
+/// Show the entries that changed between two versions of a data source
+pub async fn run(
+    server_url: String,
+    from_spec: String,
+    to_spec: String,
+    change: Option<String>,
+    page: i64,
+    limit: i64,
+    format: String,
+) -> Result<()> {
+    let target = diff_target(&from_spec, &to_spec)?;
+    let api_client = ApiClient::new(server_url)?;
+
+    let diff = api_client
+        .diff_versions(
+            &target.org,
+            &target.name,
+            &target.from,
+            &target.to,
+            change.as_deref(),
+            page,
+            limit,
+        )
+        .await?;
+
+    match format.as_str() {
+        "json" => println!("{}", serde_json::to_string_pretty(&diff)?),
+        _ => print_diff(&format!("{}:{}", target.org, target.name), &diff),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_target() {
+        let target = diff_target("uniprot:all@2024_06", "uniprot:all@2025_01").unwrap();
+        assert_eq!(
+            target,
+            DiffTarget {
+                org: "uniprot".to_string(),
+                name: "all".to_string(),
+                from: "2024_06".to_string(),
+                to: "2025_01".to_string(),
+            }
+        );
+
+        assert!(matches!(
+            diff_target("uniprot:all@2024_06", "ncbi:taxonomy@2025_01"),
+            Err(CliError::InvalidSourceSpec(_))
+        ));
+    }
+
+    #[test]
+    fn test_describe_field() {
+        let scalar = FieldChange {
+            field: "sequence_length".to_string(),
+            before: Some(json!(110)),
+            after: Some(json!(111)),
+            added: vec![],
+            removed: vec![],
+        };
+        assert_eq!(describe_field(&scalar), "sequence_length: 110 → 111");
+
+        let list = FieldChange {
+            field: "go_terms".to_string(),
+            before: None,
+            after: None,
+            added: vec![json!("GO:0006006")],
+            removed: vec![json!("GO:0005615")],
+        };
+        assert_eq!(describe_field(&list), "go_terms: +GO:0006006 -GO:0005615");
+    }
+}
@@ -0,0 +1,205 @@
+//! `bdp graph` command implementation
+//!
+//! Shows the dependency graph of a data source version: what it is built on,
+//! or with `--reverse` which versions are built on it. The graph is printed
+//! as a tree, or as JSON, Graphviz DOT or Mermaid for use elsewhere.
+
+use std::collections::HashSet;
+
+use crate::api::types::DependencyGraph;
+use crate::api::ApiClient;
+use crate::error::{CliError, Result};
+use crate::manifest::parse_source_spec;
+use colored::Colorize;
+
+/// Render the graph as an indented tree rooted at the requested version
+///
+/// Nodes reached more than once are expanded only the first time.
+pub fn render_tree(graph: &DependencyGraph) -> String {
+    let mut out = String::new();
+    let mut expanded = HashSet::new();
+    render_node(graph, &graph.root, "", &mut expanded, &mut out);
+    out
+}
+
+fn node_label(graph: &DependencyGraph, id: &str) -> String {
+    match graph.nodes.iter().find(|n| n.id == id) {
+        Some(node) if !node.resolved => format!("{} (not in registry)", id),
+        _ => id.to_string(),
+    }
+}
+
+fn render_node(
+    graph: &DependencyGraph,
+    id: &str,
+    prefix: &str,
+    expanded: &mut HashSet<String>,
+    out: &mut String,
+) {
+    if prefix.is_empty() {
+        out.push_str(&node_label(graph, id));
+        out.push('\n');
+    }
+    if !expanded.insert(id.to_string()) {
+        return;
+    }
+
+    let reverse = graph.direction == "reverse";
+    let children: Vec<_> = graph
+        .edges
+        .iter()
+        .filter(|e| if reverse { e.to == id } else { e.from == id })
+        .collect();
+
+    for (i, edge) in children.iter().enumerate() {
+        let last = i + 1 == children.len();
+        let child = if reverse { &edge.from } else { &edge.to };
+
+        let mut line =
+            format!("{}{}{}", prefix, if last { "└── " } else { "├── " }, node_label(graph, child));
+        if edge.dependency_type != "required" {
+            line.push_str(&format!(" ({})", edge.dependency_type));
+        }
+        let seen = expanded.contains(child.as_str());
+        if seen {
+            line.push_str(" (see above)");
+        }
+        out.push_str(&line);
+        out.push('\n');
+
+        if !seen {
+            let child_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+            render_node(graph, child, &child_prefix, expanded, out);
+        }
+    }
+}
+
+/// Show the dependency graph of a data source version
+pub async fn run(
+    server_url: String,
+    spec: String,
+    reverse: bool,
+    depth: usize,
+    format: String,
+) -> Result<()> {
+    let (org, name, version, _) = parse_source_spec(&spec)?;
+    let direction = if reverse { "reverse" } else { "forward" };
+    let api_client = ApiClient::new(server_url)?;
+
+    match format.as_str() {
+        "dot" | "mermaid" => {
+            let rendered = api_client
+                .dependency_graph_text(&org, &name, &version, direction, depth, &format)
+                .await?;
+            print!("{}", rendered);
+        },
+        "json" | "text" => {
+            let graph = api_client
+                .dependency_graph(&org, &name, &version, direction, depth)
+                .await?;
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&graph)?);
+            } else {
+                print!("{}", render_tree(&graph));
+                if graph.truncated {
+                    println!(
+                        "{}",
+                        "Graph truncated; use a smaller --depth to see all of it".yellow()
+                    );
+                }
+            }
+        },
+        other => {
+            return Err(CliError::config(format!(
+                "Unknown graph format '{}'; use text, json, dot or mermaid",
+                other
+            )));
+        },
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::types::{GraphEdge, GraphNode};
+
+    fn node(id: &str, resolved: bool) -> GraphNode {
+        GraphNode {
+            id: id.to_string(),
+            organization_slug: String::new(),
+            entry_slug: String::new(),
+            entry_name: String::new(),
+            version: String::new(),
+            depth: 0,
+            resolved,
+        }
+    }
+
+    fn edge(from: &str, to: &str, dependency_type: &str) -> GraphEdge {
+        GraphEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            required_version: String::new(),
+            dependency_type: dependency_type.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_render_reverse_tree() {
+        let graph = DependencyGraph {
+            root: "ncbi:taxonomy@1.0".to_string(),
+            direction: "reverse".to_string(),
+            depth: 2,
+            nodes: vec![
+                node("ncbi:taxonomy@1.0", true),
+                node("ensembl:genes@4.0", true),
+                node("uniprot:swissprot@2.0", true),
+                node("uniprot:all@2.0", true),
+            ],
+            edges: vec![
+                edge("ensembl:genes@4.0", "ncbi:taxonomy@1.0", "optional"),
+                edge("uniprot:swissprot@2.0", "ncbi:taxonomy@1.0", "required"),
+                edge("uniprot:all@2.0", "uniprot:swissprot@2.0", "required"),
+            ],
+            truncated: false,
+        };
+
+        assert_eq!(
+            render_tree(&graph),
+            "ncbi:taxonomy@1.0\n\
+             ├── ensembl:genes@4.0 (optional)\n\
+             └── uniprot:swissprot@2.0\n    \
+             └── uniprot:all@2.0\n"
+        );
+    }
+
+    #[test]
+    fn test_render_forward_tree_with_shared_dependency() {
+        let graph = DependencyGraph {
+            root: "lab:panel@1.0".to_string(),
+            direction: "forward".to_string(),
+            depth: 2,
+            nodes: vec![
+                node("lab:panel@1.0", true),
+                node("uniprot:all@2.0", true),
+                node("ncbi:taxonomy@1.0", false),
+            ],
+            edges: vec![
+                edge("lab:panel@1.0", "ncbi:taxonomy@1.0", "required"),
+                edge("lab:panel@1.0", "uniprot:all@2.0", "required"),
+                edge("uniprot:all@2.0", "ncbi:taxonomy@1.0", "required"),
+            ],
+            truncated: false,
+        };
+
+        assert_eq!(
+            render_tree(&graph),
+            "lab:panel@1.0\n\
+             ├── ncbi:taxonomy@1.0 (not in registry)\n\
+             └── uniprot:all@2.0\n    \
+             └── ncbi:taxonomy@1.0 (not in registry) (see above)\n"
+        );
+    }
+}
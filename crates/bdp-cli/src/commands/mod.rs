@@ -2,10 +2,15 @@
 //!
 //! Each subcommand has its own module with a `run` function.
 
+pub mod admin;
 pub mod audit;
 pub mod clean;
 pub mod config;
+pub mod diff;
+pub mod graph;
 pub mod init;
+pub mod outdated;
+pub mod publish;
 pub mod pull;
 pub mod query;
 pub mod search;
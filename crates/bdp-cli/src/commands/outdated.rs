@@ -0,0 +1,182 @@
+//! `bdp outdated` command implementation
+//!
+//! Compares the versions pinned in the manifest with the latest versions in
+//! the registry. Pins may name the upstream release instead of the version.
+//! Yanked versions are never suggested as upgrades, and pinned versions that
+//! were yanked are flagged.
+
+use crate::api::types::DataSourceVersions;
+use crate::api::ApiClient;
+use crate::error::{CliError, Result};
+use crate::manifest::{parse_source_spec, Manifest};
+use colored::Colorize;
+
+/// Upgrade status of a single manifest source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SourceStatus {
+    UpToDate,
+    Outdated {
+        latest: String,
+    },
+    Yanked {
+        reason: Option<String>,
+        latest: Option<String>,
+    },
+    NoReleases,
+}
+
+/// Work out the status of `current` against the registry's version list
+///
+/// `current` is matched against version names first and upstream releases
+/// second, the same way the registry resolves a pin.
+pub fn source_status(current: &str, versions: &DataSourceVersions) -> SourceStatus {
+    let pinned = versions
+        .versions
+        .iter()
+        .find(|v| v.version == current)
+        .or_else(|| {
+            versions
+                .versions
+                .iter()
+                .find(|v| v.external_version.as_deref() == Some(current))
+        });
+    let current = pinned.map_or(current, |v| v.version.as_str());
+
+    if let Some(pinned) = pinned.filter(|v| v.yanked) {
+        return SourceStatus::Yanked {
+            reason: pinned.yanked_reason.clone(),
+            latest: versions.latest_version.clone(),
+        };
+    }
+
+    match &versions.latest_version {
+        Some(latest) if latest == current => SourceStatus::UpToDate,
+        Some(latest) => SourceStatus::Outdated {
+            latest: latest.clone(),
+        },
+        None => SourceStatus::NoReleases,
+    }
+}
+
+/// List manifest sources with newer (non-yanked) versions available
+pub async fn run(server_url: String) -> Result<()> {
+    let manifest = Manifest::load("bdp.yml").map_err(|_| {
+        CliError::NotInitialized(
+            "No bdp.yml found in current directory. Initialize a project with 'bdp init' first."
+                .to_string(),
+        )
+    })?;
+
+    if manifest.sources.is_empty() {
+        println!("No sources in manifest. Add sources with 'bdp source add'");
+        return Ok(());
+    }
+
+    let api_client = ApiClient::new(server_url)?;
+    let mut outdated = 0;
+
+    for spec in &manifest.sources {
+        let (org, name, version, _format) = parse_source_spec(spec)?;
+        let versions = api_client.get_data_source_versions(&org, &name).await?;
+
+        match source_status(&version, &versions) {
+            SourceStatus::UpToDate => {
+                println!("{} {} (up to date)", "✓".green(), spec);
+            },
+            SourceStatus::Outdated { latest } => {
+                outdated += 1;
+                println!("{} {} → {}", "↑".cyan(), spec, latest.cyan());
+            },
+            SourceStatus::Yanked { reason, latest } => {
+                outdated += 1;
+                println!(
+                    "{} {} is yanked: {}",
+                    "⚠".yellow(),
+                    spec,
+                    reason.as_deref().unwrap_or("no reason given")
+                );
+                if let Some(latest) = latest {
+                    println!("    latest available: {}", latest.cyan());
+                }
+            },
+            SourceStatus::NoReleases => {
+                println!("{} {} (no available versions)", "?".yellow(), spec);
+            },
+        }
+    }
+
+    println!();
+    if outdated == 0 {
+        println!("{} All sources are up to date", "✓".green().bold());
+    } else {
+        println!("{} source(s) can be updated", outdated);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::api::types::VersionSummary;
+
+    fn versions(latest: Option<&str>, list: &[(&str, bool)]) -> DataSourceVersions {
+        DataSourceVersions {
+            latest_version: latest.map(str::to_string),
+            versions: list
+                .iter()
+                .enumerate()
+                .map(|(i, (version, yanked))| VersionSummary {
+                    version: version.to_string(),
+                    external_version: Some(format!("2025_{:02}", list.len() - i)),
+                    yanked: *yanked,
+                    yanked_reason: yanked.then(|| "corrupt file".to_string()),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_source_status() {
+        let list = versions(Some("1.1"), &[("1.2", true), ("1.1", false), ("1.0", false)]);
+
+        assert_eq!(source_status("1.1", &list), SourceStatus::UpToDate);
+        assert_eq!(
+            source_status("1.0", &list),
+            SourceStatus::Outdated {
+                latest: "1.1".to_string()
+            }
+        );
+        assert_eq!(
+            source_status("1.2", &list),
+            SourceStatus::Yanked {
+                reason: Some("corrupt file".to_string()),
+                latest: Some("1.1".to_string())
+            }
+        );
+        assert_eq!(source_status("1.0", &versions(None, &[])), SourceStatus::NoReleases);
+    }
+
+    #[test]
+    fn test_source_status_of_release_pin() {
+        // 1.2 was built from 2025_03, 1.1 from 2025_02 and 1.0 from 2025_01
+        let list = versions(Some("1.1"), &[("1.2", true), ("1.1", false), ("1.0", false)]);
+
+        let (_, _, pinned, _) = parse_source_spec("uniprot:P01308-fasta@2025_02").unwrap();
+        assert_eq!(source_status(&pinned, &list), SourceStatus::UpToDate);
+        assert_eq!(
+            source_status("2025_01", &list),
+            SourceStatus::Outdated {
+                latest: "1.1".to_string()
+            }
+        );
+        assert_eq!(
+            source_status("2025_03", &list),
+            SourceStatus::Yanked {
+                reason: Some("corrupt file".to_string()),
+                latest: Some("1.1".to_string())
+            }
+        );
+    }
+}
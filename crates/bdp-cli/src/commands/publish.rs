@@ -0,0 +1,172 @@
+//! `bdp publish` command implementation
+//!
+//! Publishes a dataset version described by the `publish` section of bdp.yml.
+
+use crate::api::types::{
+    CreateDataSourceRequest, DependencyRequest, PublishVersionRequest, VersionFileRequest,
+};
+use crate::api::ApiClient;
+use crate::checksum;
+use crate::error::{CliError, Result};
+use crate::manifest::{Manifest, PublishFile};
+use crate::progress::format_bytes;
+use colored::Colorize;
+use std::path::Path;
+
+/// A local file hashed ahead of upload
+struct PreparedFile<'a> {
+    file: &'a PublishFile,
+    filename: String,
+    size: u64,
+    checksum: String,
+}
+
+/// Publish the dataset described in bdp.yml
+pub async fn run(server_url: String, dry_run: bool) -> Result<()> {
+    let manifest = Manifest::load("bdp.yml").map_err(|_| {
+        CliError::NotInitialized(
+            "No bdp.yml found in current directory. Initialize a project with 'bdp init' first."
+                .to_string(),
+        )
+    })?;
+
+    let config = manifest.publish.as_ref().ok_or_else(|| {
+        CliError::invalid_manifest(
+            "No 'publish' section found. Add one describing the organization, slug, version and files to publish",
+        )
+    })?;
+    config.validate()?;
+
+    // Hash everything up front so a missing file fails before any upload. Files
+    // are hashed and uploaded as streams, never held in memory as a whole.
+    let mut prepared = Vec::with_capacity(config.files.len());
+    for file in &config.files {
+        let path = Path::new(&file.path);
+        if !path.is_file() {
+            return Err(CliError::FileNotFound(file.path.clone()));
+        }
+
+        let filename = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .ok_or_else(|| {
+                CliError::invalid_manifest(format!("Invalid file path '{}'", file.path))
+            })?;
+        let size = std::fs::metadata(path)?.len();
+        let checksum = checksum::compute_file_checksum(path)?;
+
+        println!(
+            "{} {} ({}, sha256 {})",
+            "→".cyan(),
+            file.path,
+            format_bytes(size),
+            &checksum[..16]
+        );
+
+        prepared.push(PreparedFile {
+            file,
+            filename,
+            size,
+            checksum,
+        });
+    }
+
+    if dry_run {
+        println!("\n{} Dry run, nothing was uploaded. Would publish:", "✓".green());
+        for spec in config.published_specs() {
+            println!("  {}", spec);
+        }
+        return Ok(());
+    }
+
+    let api_client = ApiClient::new(server_url.clone())?;
+    if !api_client.health_check().await? {
+        return Err(CliError::api(format!(
+            "Cannot connect to BDP server at '{}'. Ensure the server is running or set BDP_SERVER_URL to the correct address.",
+            server_url
+        )));
+    }
+
+    let org = &config.organization;
+    let slug = &config.slug;
+    let version = &config.version;
+
+    let data_source = match api_client.find_data_source(org, slug).await? {
+        Some(existing) => existing,
+        None => {
+            let organization = api_client.get_organization_ref(org).await?;
+            let created = api_client
+                .create_data_source(&CreateDataSourceRequest {
+                    organization_id: organization.id,
+                    slug: slug.clone(),
+                    name: config.name.clone().unwrap_or_else(|| slug.clone()),
+                    description: config.description.clone(),
+                    source_type: config.source_type.clone(),
+                })
+                .await?;
+            println!("{} Created data source {}:{}", "✓".green(), org, slug);
+            created
+        },
+    };
+
+    // Upload before creating the version: the version, its files and its
+    // dependencies are registered together, so a failure leaves nothing published
+    let total_size: i64 = prepared.iter().map(|p| p.size as i64).sum();
+    let mut files = Vec::with_capacity(prepared.len());
+    for item in prepared {
+        let uploaded = api_client
+            .upload_file(org, slug, version, &item.filename, Path::new(&item.file.path))
+            .await?;
+
+        if uploaded.checksum != item.checksum {
+            return Err(CliError::checksum_mismatch(
+                item.file.path.clone(),
+                item.checksum,
+                uploaded.checksum,
+            ));
+        }
+
+        println!("{} Uploaded {}", "↑".cyan(), item.file.path);
+
+        files.push(VersionFileRequest {
+            format: item.file.format.clone(),
+            s3_key: uploaded.key,
+            checksum: item.checksum,
+            size_bytes: item.size as i64,
+            compression: item.file.compression.clone(),
+        });
+    }
+
+    api_client
+        .publish_version(
+            org,
+            slug,
+            &PublishVersionRequest {
+                data_source_id: data_source.id,
+                version: version.clone(),
+                external_version: config.external_version.clone(),
+                size_bytes: Some(total_size),
+                files,
+                dependencies: config
+                    .dependencies
+                    .iter()
+                    .map(|source| DependencyRequest {
+                        source: source.clone(),
+                    })
+                    .collect(),
+            },
+        )
+        .await?;
+    println!("{} Created version {}", "✓".green(), version);
+    if !config.dependencies.is_empty() {
+        println!("{} Recorded {} dependencies", "✓".green(), config.dependencies.len());
+    }
+
+    println!("\n{} Published {}:{}@{}", "✓".green().bold(), org, slug, version);
+    println!("Add it to a project with:");
+    for spec in config.published_specs() {
+        println!("  bdp source add {}", spec);
+    }
+
+    Ok(())
+}
@@ -1,18 +1,25 @@
 //! `bdp pull` command implementation
 //!
-//! Downloads and caches sources from the manifest.
+//! Downloads and caches sources from the manifest, together with the
+//! transitive dependencies of aggregate sources, and locks them all.
+//! Dependencies come as one bundle when the server offers one, and are
+//! otherwise pulled one by one.
 
-use crate::api::ApiClient;
+use crate::api::{ApiClient, BundleInfo, BundleMember, DependencyInfo, ResolvedSource};
 use crate::cache::CacheManager;
 use crate::checksum;
 use crate::error::{CliError, Result};
-use crate::lockfile::{Lockfile, SourceEntry};
+use crate::lockfile::{BundleEntry, DependencyEntry, Lockfile, SourceEntry};
 use crate::manifest::{parse_source_spec, Manifest};
 use crate::progress;
 use colored::Colorize;
+use std::collections::HashSet;
 
-/// Pull sources from manifest
-pub async fn run(server_url: String, force: bool) -> Result<()> {
+/// Pull sources from manifest, with their dependencies unless `no_deps` is set
+///
+/// With `split`, dependency bundles are also split back into their member
+/// files.
+pub async fn run(server_url: String, force: bool, no_deps: bool, split: bool) -> Result<()> {
     // Load manifest
     let manifest = Manifest::load("bdp.yml").map_err(|_| {
         CliError::NotInitialized(
@@ -39,86 +46,414 @@ pub async fn run(server_url: String, force: bool) -> Result<()> {
         )));
     }
 
+    // Specs already in the lockfile keep resolving even if their version was yanked
+    let locked = Lockfile::load("bdl.lock")
+        .map(|lockfile| lockfile.sources.into_keys().collect())
+        .unwrap_or_default();
+
     // Resolve manifest
-    let resolved = api_client.resolve_manifest(&manifest).await?;
+    let resolved = api_client.resolve_manifest(&manifest, locked).await?;
 
     println!("{} Found {} source(s)", "✓".green(), resolved.sources.len());
 
+    for (spec, resolved_source) in &resolved.sources {
+        for warning in &resolved_source.warnings {
+            println!("{} {}: {}", "⚠".yellow(), spec, warning);
+        }
+        if resolved_source.yanked {
+            println!(
+                "{} {} was yanked: {}",
+                "⚠".yellow(),
+                spec,
+                resolved_source
+                    .yanked_reason
+                    .as_deref()
+                    .unwrap_or("no reason given")
+            );
+        }
+    }
+
     // Initialize cache
     let cache = CacheManager::new().await?;
 
     // Create/update lockfile
     let mut lockfile = Lockfile::new();
 
+    // Dependencies shared by several sources are only fetched once
+    let mut pulled_dependencies = HashSet::new();
+
     // Download sources
     for (spec, resolved_source) in &resolved.sources {
-        // Check if cached and not forcing
-        if !force && cache.is_cached(spec).await? {
-            println!("{} {} (cached)", "✓".green(), spec);
-
-            // Add to lockfile
-            let entry = SourceEntry::new(
-                resolved_source.resolved.clone(),
-                resolved_source.format.clone(),
-                resolved_source.checksum.clone(),
-                resolved_source.size,
-                resolved_source.external_version.clone(),
-            );
-            lockfile.add_source(spec.clone(), entry);
+        pull_source(&api_client, &cache, spec, resolved_source, force).await?;
 
-            continue;
+        let dependencies = resolved_source.dependencies.as_deref().unwrap_or_default();
+        if !no_deps && !dependencies.is_empty() {
+            match &resolved_source.bundle {
+                Some(bundle) => {
+                    pull_bundle(
+                        &api_client,
+                        &cache,
+                        spec,
+                        resolved_source,
+                        bundle,
+                        force,
+                        split.then_some(&mut pulled_dependencies),
+                    )
+                    .await?
+                },
+                None => {
+                    pull_dependencies(
+                        &api_client,
+                        &cache,
+                        spec,
+                        dependencies,
+                        force,
+                        &mut pulled_dependencies,
+                    )
+                    .await?
+                },
+            }
         }
 
-        println!("{} Downloading {}...", "↓".cyan(), spec);
+        // Add to lockfile
+        lockfile.add_source(spec.clone(), lock_entry(resolved_source, !no_deps));
+    }
+
+    // Save lockfile
+    lockfile.save("bdl.lock")?;
+
+    if no_deps {
+        println!(
+            "\n{} All sources downloaded and verified (dependencies skipped)",
+            "✓".green().bold()
+        );
+    } else {
+        println!("\n{} All sources downloaded and verified", "✓".green().bold());
+    }
+    println!("Lockfile saved: bdl.lock");
+
+    Ok(())
+}
+
+/// Download, verify and cache a source unless it is already cached
+async fn pull_source(
+    api_client: &ApiClient,
+    cache: &CacheManager,
+    spec: &str,
+    resolved_source: &ResolvedSource,
+    force: bool,
+) -> Result<()> {
+    // Check if cached and not forcing
+    if !force && cache.is_cached(spec).await? {
+        println!("{} {} (cached)", "✓".green(), spec);
+        return Ok(());
+    }
+
+    println!("{} Downloading {}...", "↓".cyan(), spec);
+
+    // Parse spec to get components
+    let (org, name, version, format) = parse_source_spec(spec)?;
+    let (org, name) = resolved_entry(resolved_source, &org, &name);
+    let version = resolved_version(resolved_source, &version);
+    let format_str = format.as_deref().unwrap_or(&resolved_source.format);
 
-        // Parse spec to get components
-        let (org, name, version, format) = parse_source_spec(spec)?;
-        let format_str = format.as_deref().unwrap_or(&resolved_source.format);
+    // Create progress bar
+    let pb = progress::create_download_progress(resolved_source.size as u64, spec);
 
-        // Create progress bar
-        let pb = progress::create_download_progress(resolved_source.size as u64, spec);
+    // Download file
+    let bytes = api_client
+        .download_file(org, name, version, format_str)
+        .await?;
 
-        // Download file
+    pb.set_position(bytes.len() as u64);
+    pb.finish();
+
+    // Verify checksum
+    checksum::verify_checksum(&bytes, &resolved_source.checksum)?;
+
+    // Store in cache
+    cache
+        .store(spec, &resolved_source.resolved, format_str, bytes, &resolved_source.checksum)
+        .await?;
+
+    println!(
+        "{} {} ({}) verified",
+        "✓".green(),
+        spec,
+        progress::format_bytes(resolved_source.size as u64)
+    );
+
+    Ok(())
+}
+
+/// Download, verify and cache the dependencies of a source
+///
+/// Dependencies already cached with the locked checksum, or pulled earlier in
+/// this run, are skipped.
+async fn pull_dependencies(
+    api_client: &ApiClient,
+    cache: &CacheManager,
+    spec: &str,
+    dependencies: &[DependencyInfo],
+    force: bool,
+    pulled: &mut HashSet<String>,
+) -> Result<()> {
+    let pb = progress::create_progress_bar(
+        dependencies.len() as u64,
+        &format!("Dependencies of {}", spec),
+    );
+    let mut downloaded = 0;
+    let mut downloaded_bytes = 0;
+
+    for dependency in dependencies {
+        pb.inc(1);
+        let dependency_spec = dependency.spec();
+        if !pulled.insert(dependency_spec.clone()) {
+            continue;
+        }
+
+        let cached = cache.get_entry(&dependency_spec).await?;
+        if !force && cached.is_some_and(|entry| entry.checksum == dependency.checksum) {
+            continue;
+        }
+
+        let (org, name, version, format) = parse_source_spec(&dependency_spec)?;
+        let format = format.unwrap_or_else(|| dependency.format.clone());
         let bytes = api_client
-            .download_file(&org, &name, &version, format_str)
+            .download_file(&org, &name, &version, &format)
             .await?;
+        checksum::verify_checksum(&bytes, &dependency.checksum)?;
 
-        pb.set_position(bytes.len() as u64);
-        pb.finish();
+        downloaded += 1;
+        downloaded_bytes += bytes.len() as u64;
+        cache
+            .store(&dependency_spec, &dependency.source, &format, bytes, &dependency.checksum)
+            .await?;
+    }
+    pb.finish_and_clear();
+
+    println!(
+        "{} {} dependencies of {} verified ({} downloaded, {})",
+        "✓".green(),
+        dependencies.len(),
+        spec,
+        downloaded,
+        progress::format_bytes(downloaded_bytes)
+    );
+
+    Ok(())
+}
 
-        // Verify checksum
-        checksum::verify_checksum(&bytes, &resolved_source.checksum)?;
+/// Download, verify and cache the dependency bundle of a source
+///
+/// When `split_into` is given, the bundle is also split back into its
+/// members, which are checked against the member index and cached like
+/// dependencies pulled one by one.
+async fn pull_bundle(
+    api_client: &ApiClient,
+    cache: &CacheManager,
+    spec: &str,
+    resolved_source: &ResolvedSource,
+    bundle: &BundleInfo,
+    force: bool,
+    split_into: Option<&mut HashSet<String>>,
+) -> Result<()> {
+    let (org, name, version, format) = parse_source_spec(spec)?;
+    let (org, name) = resolved_entry(resolved_source, &org, &name);
+    let version = resolved_version(resolved_source, &version);
+    let format = format.unwrap_or_else(|| resolved_source.format.clone());
+    let bundle_spec = bundle_spec(org, name, version, &format);
 
-        // Store in cache
+    let cached = cache
+        .get_entry(&bundle_spec)
+        .await?
+        .filter(|entry| !force && entry.checksum == bundle.checksum);
+    let data = match cached {
+        Some(_) if split_into.is_none() => {
+            println!("{} {} (cached)", "✓".green(), bundle_spec);
+            return Ok(());
+        },
+        Some(entry) => std::fs::read(&entry.path)?,
+        None => {
+            println!("{} Downloading {}...", "↓".cyan(), bundle_spec);
+            let pb = progress::create_download_progress(bundle.size as u64, &bundle_spec);
+            let data = api_client
+                .download_bundle(org, name, version, &format)
+                .await?;
+            pb.set_position(data.len() as u64);
+            pb.finish();
+
+            checksum::verify_checksum(&data, &bundle.checksum)?;
+            cache
+                .store(
+                    &bundle_spec,
+                    &resolved_source.resolved,
+                    bundle_extension(&bundle.bundle_format),
+                    data.clone(),
+                    &bundle.checksum,
+                )
+                .await?;
+            data
+        },
+    };
+
+    println!(
+        "{} {} ({} members, {}) verified",
+        "✓".green(),
+        bundle_spec,
+        bundle.member_count,
+        progress::format_bytes(bundle.size as u64)
+    );
+
+    let Some(pulled) = split_into else {
+        return Ok(());
+    };
+
+    let index = api_client
+        .get_bundle_index(org, name, version, &format)
+        .await?;
+    if index.sha256 != bundle.checksum {
+        return Err(CliError::api(format!(
+            "Member index of {} describes a different bundle; it may have been rebuilt. Run 'bdp pull --force' to fetch it again.",
+            bundle_spec
+        )));
+    }
+
+    let members = split_bundle(&data, &index.members)?;
+    for (member, content) in &members {
+        let member_spec = DependencyInfo {
+            source: member.source.clone(),
+            format: index.format.clone(),
+            checksum: member.sha256.clone(),
+            size: member.size as i64,
+        }
+        .spec();
+        if !pulled.insert(member_spec.clone()) {
+            continue;
+        }
         cache
-            .store(spec, &resolved_source.resolved, format_str, bytes, &resolved_source.checksum)
+            .store(&member_spec, &member.source, &index.format, content.to_vec(), &member.sha256)
             .await?;
+    }
 
-        println!(
-            "{} {} ({}) verified",
-            "✓".green(),
-            spec,
-            progress::format_bytes(resolved_source.size as u64)
-        );
+    println!("{} Split {} into {} members", "✓".green(), bundle_spec, members.len());
 
-        // Add to lockfile
-        let entry = SourceEntry::new(
-            resolved_source.resolved.clone(),
-            resolved_source.format.clone(),
-            resolved_source.checksum.clone(),
-            resolved_source.size,
-            resolved_source.external_version.clone(),
-        );
-        lockfile.add_source(spec.clone(), entry);
+    Ok(())
+}
+
+/// Version a source resolved to
+///
+/// Specs may pin the upstream release (`@2025_01`); files are served under
+/// the internal version the server resolved it to.
+fn resolved_version<'a>(resolved_source: &'a ResolvedSource, pinned: &'a str) -> &'a str {
+    resolved_source
+        .resolved
+        .rsplit_once('@')
+        .map_or(pinned, |(_, version)| version)
+}
+
+/// Organization and name of the entry a source resolved to
+///
+/// They differ from the spec's when its identifier was retired upstream and
+/// the server resolved it to the entry that replaced it.
+fn resolved_entry<'a>(
+    resolved_source: &'a ResolvedSource,
+    org: &'a str,
+    name: &'a str,
+) -> (&'a str, &'a str) {
+    resolved_source
+        .resolved
+        .rsplit_once('@')
+        .and_then(|(entry, _)| entry.split_once(':'))
+        .unwrap_or((org, name))
+}
+
+/// Cache specification of the dependency bundle of a source
+fn bundle_spec(org: &str, name: &str, version: &str, format: &str) -> String {
+    format!("{}:{}-{}-bundle@{}", org, name, format, version)
+}
+
+/// File extension of a bundle layout
+fn bundle_extension(bundle_format: &str) -> &str {
+    match bundle_format {
+        "multifasta" => "fasta",
+        other => other,
     }
+}
 
-    // Save lockfile
-    lockfile.save("bdl.lock")?;
+/// Split a bundle into its members, verifying each against the member index
+fn split_bundle<'a>(
+    data: &'a [u8],
+    members: &'a [BundleMember],
+) -> Result<Vec<(&'a BundleMember, &'a [u8])>> {
+    members
+        .iter()
+        .map(|member| {
+            let content = usize::try_from(member.offset)
+                .ok()
+                .zip(usize::try_from(member.offset + member.size).ok())
+                .and_then(|(start, end)| data.get(start..end))
+                .ok_or_else(|| {
+                    CliError::api(format!(
+                        "Bundle member '{}' lies outside the bundle",
+                        member.source
+                    ))
+                })?;
 
-    println!("\n{} All sources downloaded and verified", "✓".green().bold());
-    println!("Lockfile saved: bdl.lock");
+            let actual = checksum::compute_checksum(content);
+            if actual != member.sha256 {
+                return Err(CliError::checksum_mismatch(
+                    member.source.clone(),
+                    member.sha256.clone(),
+                    actual,
+                ));
+            }
+            Ok((member, content))
+        })
+        .collect()
+}
 
-    Ok(())
+/// Lockfile entry of a resolved source
+///
+/// Dependencies and their bundle are only locked when `with_dependencies`
+/// is set, since the lockfile must not claim files that weren't pulled.
+fn lock_entry(resolved_source: &ResolvedSource, with_dependencies: bool) -> SourceEntry {
+    let entry = SourceEntry::new(
+        resolved_source.resolved.clone(),
+        resolved_source.format.clone(),
+        resolved_source.checksum.clone(),
+        resolved_source.size,
+        resolved_source.external_version.clone(),
+    );
+    if !with_dependencies {
+        return entry;
+    }
+
+    let entry = match &resolved_source.bundle {
+        Some(bundle) => entry.with_bundle(BundleEntry::new(
+            bundle.bundle_format.clone(),
+            bundle.checksum.clone(),
+            bundle.size,
+            bundle.member_count,
+        )),
+        None => entry,
+    };
+
+    match &resolved_source.dependencies {
+        Some(dependencies) => entry.with_locked_dependencies(
+            dependencies
+                .iter()
+                .map(|dependency| {
+                    DependencyEntry::new(
+                        dependency.spec(),
+                        dependency.checksum.clone(),
+                        dependency.size,
+                    )
+                })
+                .collect(),
+        ),
+        None => entry,
+    }
 }
 
 #[cfg(test)]
@@ -134,4 +469,162 @@ mod tests {
     async fn test_pull_command() {
         // This would test against a live server
     }
+
+    fn aggregate_source() -> ResolvedSource {
+        ResolvedSource {
+            resolved: "uniprot:proteome@1.0".to_string(),
+            format: "fasta".to_string(),
+            checksum: "abc123".to_string(),
+            size: 1024,
+            external_version: "2025_01".to_string(),
+            download_url: String::new(),
+            dependency_count: Some(1),
+            dependencies: Some(vec![DependencyInfo {
+                source: "uniprot:P01308@1.0".to_string(),
+                format: "fasta".to_string(),
+                checksum: "def456".to_string(),
+                size: 512,
+            }]),
+            bundle: Some(BundleInfo {
+                bundle_format: "multifasta".to_string(),
+                checksum: "789abc".to_string(),
+                size: 512,
+                member_count: 1,
+            }),
+            yanked: false,
+            yanked_reason: None,
+            replaced_by: None,
+            warnings: vec![],
+        }
+    }
+
+    #[test]
+    fn test_lock_entry_records_dependencies() {
+        let resolved_source = aggregate_source();
+
+        let entry = lock_entry(&resolved_source, true);
+        assert_eq!(entry.dependency_count, Some(1));
+        assert_eq!(
+            entry.dependencies,
+            vec![DependencyEntry::new(
+                "uniprot:P01308-fasta@1.0".to_string(),
+                "def456".to_string(),
+                512
+            )]
+        );
+
+        assert_eq!(entry.bundle.map(|bundle| bundle.member_count), Some(1));
+
+        let entry = lock_entry(
+            &ResolvedSource {
+                dependencies: None,
+                bundle: None,
+                ..resolved_source
+            },
+            true,
+        );
+        assert!(entry.dependencies.is_empty());
+        assert!(entry.bundle.is_none());
+    }
+
+    #[test]
+    fn test_lock_entry_without_dependencies() {
+        // Under --no-deps the dependencies weren't pulled, so none are locked
+        let entry = lock_entry(&aggregate_source(), false);
+        assert_eq!(entry.resolved, "uniprot:proteome@1.0");
+        assert_eq!(entry.checksum, "abc123");
+        assert_eq!(entry.dependency_count, None);
+        assert!(entry.dependencies.is_empty());
+        assert!(entry.bundle.is_none());
+    }
+
+    #[test]
+    fn test_resolved_version_of_release_pin() {
+        let resolved_source = ResolvedSource {
+            resolved: "uniprot:P01308@1.4.0".to_string(),
+            format: "fasta".to_string(),
+            checksum: "abc123".to_string(),
+            size: 1024,
+            external_version: "2025_01".to_string(),
+            download_url: String::new(),
+            dependency_count: None,
+            dependencies: None,
+            bundle: None,
+            yanked: false,
+            yanked_reason: None,
+            replaced_by: None,
+            warnings: vec![],
+        };
+        assert_eq!(resolved_version(&resolved_source, "2025_01"), "1.4.0");
+
+        let unresolved = ResolvedSource {
+            resolved: String::new(),
+            ..resolved_source
+        };
+        assert_eq!(resolved_version(&unresolved, "2025_01"), "2025_01");
+    }
+
+    #[test]
+    fn test_resolved_entry_of_replaced_source() {
+        let resolved_source = ResolvedSource {
+            resolved: "uniprot:P01308@1.0".to_string(),
+            format: "fasta".to_string(),
+            checksum: "abc123".to_string(),
+            size: 1024,
+            external_version: String::new(),
+            download_url: String::new(),
+            dependency_count: None,
+            dependencies: None,
+            bundle: None,
+            yanked: false,
+            yanked_reason: None,
+            replaced_by: Some("uniprot:P01308".to_string()),
+            warnings: vec![
+                "'uniprot:Q00001' is a previous accession of 'uniprot:P01308'".to_string()
+            ],
+        };
+        assert_eq!(resolved_entry(&resolved_source, "uniprot", "Q00001"), ("uniprot", "P01308"));
+
+        let unresolved = ResolvedSource {
+            resolved: String::new(),
+            ..resolved_source
+        };
+        assert_eq!(resolved_entry(&unresolved, "uniprot", "Q00001"), ("uniprot", "Q00001"));
+    }
+
+    #[test]
+    fn test_split_bundle() {
+        let data = b">P01308\nMALWMR\n>P01315\nMALWIR\n";
+        let member = |source: &str, offset: u64, content: &[u8]| BundleMember {
+            source: source.to_string(),
+            offset,
+            size: content.len() as u64,
+            sha256: checksum::compute_checksum(content),
+        };
+        let members = vec![
+            member("uniprot:P01308@1.0", 0, b">P01308\nMALWMR\n"),
+            member("uniprot:P01315@1.0", 15, b">P01315\nMALWIR\n"),
+        ];
+
+        let split = split_bundle(data, &members).unwrap();
+        assert_eq!(split[1].1, b">P01315\nMALWIR\n");
+
+        let mut corrupt = members.clone();
+        corrupt[1].sha256 = "0".repeat(64);
+        assert!(matches!(split_bundle(data, &corrupt), Err(CliError::ChecksumMismatch { .. })));
+
+        let mut truncated = members;
+        truncated[1].size = 100;
+        assert!(split_bundle(data, &truncated).is_err());
+    }
+
+    #[test]
+    fn test_bundle_spec() {
+        assert_eq!(
+            bundle_spec("uniprot", "proteome", "1.0", "fasta"),
+            "uniprot:proteome-fasta-bundle@1.0"
+        );
+        assert_eq!(bundle_extension("multifasta"), "fasta");
+        assert_eq!(bundle_extension("tar"), "tar");
+    }
 }
@@ -247,6 +247,12 @@ fn resolve_entity_alias(entity: &str) -> Result<(String, Vec<String>)> {
             "data_sources WHERE type = 'genome'".to_string(),
             vec![],
         )),
+        "structure" => Ok((
+            "data_sources".to_string(),
+            vec![
+                "LEFT JOIN structure_metadata sm ON sm.data_source_id = data_sources.id WHERE data_sources.source_type = 'structure'".to_string(),
+            ],
+        )),
         "transcriptome" => Ok((
             "data_sources WHERE type = 'transcriptome'".to_string(),
             vec![],
@@ -261,6 +267,8 @@ fn resolve_entity_alias(entity: &str) -> Result<(String, Vec<String>)> {
         "orgs" | "organizations" => Ok(("organizations".to_string(), vec![])),
         "protein_metadata" => Ok(("protein_metadata".to_string(), vec![])),
         "gene_metadata" => Ok(("gene_metadata".to_string(), vec![])),
+        "structure_metadata" => Ok(("structure_metadata".to_string(), vec![])),
+        "structure_chains" => Ok(("structure_chains".to_string(), vec![])),
         "organism_taxonomy" => Ok(("organism_taxonomy".to_string(), vec![])),
         "publication_refs" => Ok(("publication_refs".to_string(), vec![])),
 
@@ -268,7 +276,7 @@ fn resolve_entity_alias(entity: &str) -> Result<(String, Vec<String>)> {
         _ => Err(CliError::config(&format!(
             "Unknown entity: '{}'\n\nAvailable entities:\n  {}",
             entity,
-            "protein, gene, genome, transcriptome, proteome, tools, orgs,\n  protein_metadata, gene_metadata, organism_taxonomy, publication_refs"
+            "protein, gene, genome, structure, transcriptome, proteome, tools, orgs,\n  protein_metadata, gene_metadata, structure_metadata, structure_chains,\n  organism_taxonomy, publication_refs"
         ))),
     }
 }
@@ -516,6 +524,14 @@ mod tests {
         assert!(joins[0].contains("gene_metadata"));
     }
 
+    #[test]
+    fn test_resolve_entity_alias_structure() {
+        let (table, joins) = resolve_entity_alias("structure").unwrap();
+        assert!(table.contains("data_sources"));
+        assert_eq!(joins.len(), 1);
+        assert!(joins[0].contains("structure_metadata"));
+    }
+
     #[test]
     fn test_resolve_entity_alias_tools() {
         let (table, joins) = resolve_entity_alias("tools").unwrap();
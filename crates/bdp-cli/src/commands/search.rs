@@ -65,6 +65,7 @@ pub async fn run(
     let use_interactive = should_use_interactive(&format, no_interactive);
 
     // Parse filters
+    let (entry_type, source_type) = split_type_filters(entry_type, source_type);
     let type_filter = if entry_type.is_empty() {
         None
     } else {
@@ -122,6 +123,28 @@ pub async fn run(
     Ok(())
 }
 
+/// Registry entry types accepted by the server's type filter
+const ENTRY_TYPES: &[&str] = &["data_source", "tool", "organization"];
+
+/// Route `--type` values that aren't entry types to the source type filter
+///
+/// `--type structure` reads more naturally than `--source-type structure`,
+/// and the server rejects source types as entry types.
+fn split_type_filters(
+    entry_type: Vec<String>,
+    mut source_type: Vec<String>,
+) -> (Vec<String>, Vec<String>) {
+    let (entry_type, other): (Vec<String>, Vec<String>) = entry_type
+        .into_iter()
+        .partition(|t| ENTRY_TYPES.contains(&t.as_str()));
+    for t in other {
+        if !source_type.contains(&t) {
+            source_type.push(t);
+        }
+    }
+    (entry_type, source_type)
+}
+
 /// Determine if we should use interactive mode
 fn should_use_interactive(format: &str, no_interactive: bool) -> bool {
     if no_interactive {
@@ -329,7 +352,12 @@ async fn display_interactive(
                     .as_ref()
                     .map(|d| truncate_string(d, 60))
                     .unwrap_or_else(|| "No description".to_string());
-                format!("{} - {}", spec.cyan(), desc)
+                match deprecation_note(r) {
+                    Some(note) => {
+                        format!("{} - {} {}", spec.cyan(), desc, format!("({})", note).yellow())
+                    },
+                    None => format!("{} - {}", spec.cyan(), desc),
+                }
             })
             .collect();
 
@@ -500,6 +528,10 @@ fn display_result_details(result: &crate::api::types::SearchResult) -> Result<()
         table.add_row(vec!["Description", desc]);
     }
 
+    if let Some(note) = deprecation_note(result) {
+        table.add_row(vec!["Status", &note]);
+    }
+
     println!("{}", table);
     println!();
 
@@ -550,11 +582,16 @@ fn display_table(results: &crate::api::types::SearchResponse) -> Result<()> {
             .map(|d| truncate_string(d, 50))
             .unwrap_or_else(|| "-".to_string());
 
+        let entry_type = match deprecation_note(result) {
+            Some(note) => format!("{} ({})", result.entry_type, note),
+            None => result.entry_type.clone(),
+        };
+
         table.add_row(vec![
             source,
             result.name.clone(),
             result.format.clone(),
-            result.entry_type.clone(),
+            entry_type,
             description,
         ]);
     }
@@ -573,6 +610,17 @@ fn display_table(results: &crate::api::types::SearchResponse) -> Result<()> {
     Ok(())
 }
 
+/// Short note for a deprecated result, naming its replacement if any
+fn deprecation_note(result: &crate::api::types::SearchResult) -> Option<String> {
+    if !result.deprecated {
+        return None;
+    }
+    Some(match &result.superseded_by {
+        Some(replacement) => format!("deprecated, use {}", replacement),
+        None => "deprecated".to_string(),
+    })
+}
+
 /// Display results in JSON format
 fn display_json(results: &crate::api::types::SearchResponse) -> Result<()> {
     let json = serde_json::to_string_pretty(results)?;
@@ -662,6 +710,28 @@ mod tests {
         assert_eq!(truncate_string("hi", 5), "hi");
     }
 
+    #[test]
+    fn test_deprecation_note() {
+        let mut result = crate::api::types::SearchResult {
+            id: "1".to_string(),
+            organization: "uniprot".to_string(),
+            name: "Q00001".to_string(),
+            version: "1.0".to_string(),
+            description: None,
+            format: "fasta".to_string(),
+            entry_type: "data_source".to_string(),
+            deprecated: false,
+            superseded_by: None,
+        };
+        assert_eq!(deprecation_note(&result), None);
+
+        result.deprecated = true;
+        assert_eq!(deprecation_note(&result).as_deref(), Some("deprecated"));
+
+        result.superseded_by = Some("uniprot:P01308".to_string());
+        assert_eq!(deprecation_note(&result).as_deref(), Some("deprecated, use uniprot:P01308"));
+    }
+
     #[test]
     fn test_find_similar_terms() {
         let suggestions = find_similar_terms("insulinn");
@@ -671,6 +741,19 @@ mod tests {
         assert!(suggestions.contains(&"protein".to_string()));
     }
 
+    #[test]
+    fn test_split_type_filters() {
+        let (entry_type, source_type) =
+            split_type_filters(vec!["structure".to_string(), "data_source".to_string()], vec![]);
+        assert_eq!(entry_type, vec!["data_source"]);
+        assert_eq!(source_type, vec!["structure"]);
+
+        let (entry_type, source_type) =
+            split_type_filters(vec!["protein".to_string()], vec!["protein".to_string()]);
+        assert!(entry_type.is_empty());
+        assert_eq!(source_type, vec!["protein"]);
+    }
+
     #[test]
     fn test_should_use_interactive() {
         assert!(!should_use_interactive("table", false));
@@ -2,12 +2,28 @@
 //!
 //! Manages data sources in the manifest.
 
+use std::io::{self, IsTerminal};
+
+use crate::api::types::ResolvedSource;
+use crate::api::ApiClient;
 use crate::error::{CliError, Result};
-use crate::manifest::{validate_source_spec, Manifest};
+use crate::manifest::{parse_source_spec, validate_source_spec, Manifest};
 use colored::Colorize;
 
 /// Add a source to the manifest
-pub async fn add(source: String) -> Result<()> {
+///
+/// The spec is checked against the registry first. When it names an
+/// identifier retired upstream (a merged accession or taxon) or a deprecated
+/// entry with a successor, the spec of the replacement is offered instead;
+/// `use_replacement` takes it without asking. Specs whose version has been
+/// yanked are refused unless `allow_yanked` is set. Sources are still added
+/// when the registry can't be reached.
+pub async fn add(
+    server_url: String,
+    source: String,
+    use_replacement: bool,
+    allow_yanked: bool,
+) -> Result<()> {
     // Validate source specification
     validate_source_spec(&source)?;
 
@@ -18,6 +34,14 @@ pub async fn add(source: String) -> Result<()> {
         )
     })?;
 
+    let source = match check_registry(server_url, &source).await {
+        Some(resolved) => {
+            check_yanked(&source, &resolved, allow_yanked)?;
+            choose_spec(source, &resolved, use_replacement)?
+        },
+        None => source,
+    };
+
     // Check if already exists
     if manifest.has_source(&source) {
         println!("{} Source already exists: {}", "✓".green(), source);
@@ -35,6 +59,75 @@ pub async fn add(source: String) -> Result<()> {
     Ok(())
 }
 
+/// Resolve a spec, warning instead of failing when that isn't possible
+async fn check_registry(server_url: String, source: &str) -> Option<ResolvedSource> {
+    let result = match ApiClient::new(server_url) {
+        Ok(api_client) => api_client.resolve_source(source).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(resolved) => Some(resolved),
+        Err(e) => {
+            println!("{} Could not check {} against the registry: {}", "⚠".yellow(), source, e);
+            None
+        },
+    }
+}
+
+/// Refuse a spec whose version has been yanked, or warn when that's allowed
+fn check_yanked(source: &str, resolved: &ResolvedSource, allow_yanked: bool) -> Result<()> {
+    if !resolved.yanked {
+        return Ok(());
+    }
+
+    let reason = resolved
+        .yanked_reason
+        .clone()
+        .unwrap_or_else(|| "no reason given".to_string());
+    if !allow_yanked {
+        return Err(CliError::SourceYanked(source.to_string(), reason));
+    }
+
+    println!("{} {} has been yanked: {}", "⚠".yellow(), source, reason);
+    Ok(())
+}
+
+/// Spec of the entry that replaced the one a spec names, keeping its version pin
+fn replacement_spec(source: &str, resolved: &ResolvedSource) -> Result<Option<String>> {
+    let Some(replaced_by) = &resolved.replaced_by else {
+        return Ok(None);
+    };
+    let (_, _, version, format) = parse_source_spec(source)?;
+    let format = format.unwrap_or_else(|| resolved.format.clone());
+    Ok(Some(format!("{}-{}@{}", replaced_by, format, version)))
+}
+
+/// Report a spec's warnings and pick between it and its replacement
+fn choose_spec(source: String, resolved: &ResolvedSource, use_replacement: bool) -> Result<String> {
+    for warning in &resolved.warnings {
+        println!("{} {}", "⚠".yellow(), warning);
+    }
+
+    let Some(replacement) = replacement_spec(&source, resolved)? else {
+        return Ok(source);
+    };
+
+    let accepted = if use_replacement {
+        true
+    } else if io::stdin().is_terminal() {
+        inquire::Confirm::new(&format!("Add {} instead?", replacement))
+            .with_default(true)
+            .prompt()
+            .unwrap_or(false)
+    } else {
+        println!("  Use {} or pass --use-replacement to add it instead", replacement.cyan());
+        false
+    };
+
+    Ok(if accepted { replacement } else { source })
+}
+
 /// Remove a source from the manifest
 pub async fn remove(source: String) -> Result<()> {
     // Load manifest
@@ -96,6 +189,71 @@ mod tests {
         assert!(validate_source_spec("missing:version").is_err());
     }
 
+    fn resolved(replaced_by: Option<&str>) -> ResolvedSource {
+        ResolvedSource {
+            resolved: "uniprot:P01308@1.0".to_string(),
+            format: "fasta".to_string(),
+            checksum: "abc123".to_string(),
+            size: 1024,
+            external_version: String::new(),
+            download_url: String::new(),
+            dependency_count: None,
+            dependencies: None,
+            bundle: None,
+            yanked: false,
+            yanked_reason: None,
+            replaced_by: replaced_by.map(str::to_string),
+            warnings: vec![],
+        }
+    }
+
+    #[test]
+    fn test_replacement_spec() {
+        // The version pin is kept, even when it names an upstream release
+        assert_eq!(
+            replacement_spec("uniprot:Q00001-fasta@2025_01", &resolved(Some("uniprot:P01308")))
+                .unwrap()
+                .as_deref(),
+            Some("uniprot:P01308-fasta@2025_01")
+        );
+        assert_eq!(replacement_spec("uniprot:P01308-fasta@1.0", &resolved(None)).unwrap(), None);
+    }
+
+    #[test]
+    fn test_choose_spec_with_replacement() {
+        let chosen = choose_spec(
+            "uniprot:Q00001-fasta@1.0".to_string(),
+            &resolved(Some("uniprot:P01308")),
+            true,
+        )
+        .unwrap();
+        assert_eq!(chosen, "uniprot:P01308-fasta@1.0");
+
+        let chosen =
+            choose_spec("uniprot:P01308-fasta@1.0".to_string(), &resolved(None), true).unwrap();
+        assert_eq!(chosen, "uniprot:P01308-fasta@1.0");
+    }
+
+    #[test]
+    fn test_check_yanked() {
+        let spec = "uniprot:P01308-fasta@1.0";
+        assert!(check_yanked(spec, &resolved(None), false).is_ok());
+
+        let mut yanked = resolved(None);
+        yanked.yanked = true;
+        yanked.yanked_reason = Some("wrong organism".to_string());
+        match check_yanked(spec, &yanked, false) {
+            Err(CliError::SourceYanked(source, reason)) => {
+                assert_eq!(source, spec);
+                assert_eq!(reason, "wrong organism");
+            },
+            other => panic!("expected SourceYanked, got {:?}", other),
+        }
+
+        // Allowed yanks only warn
+        assert!(check_yanked(spec, &yanked, true).is_ok());
+    }
+
     /// Test manifest source operations
     #[tokio::test]
     async fn test_manifest_source_operations() {
@@ -95,6 +95,10 @@ pub enum CliError {
     #[error("Source '{0}' not found. Run 'bdp search' to find available sources.")]
     SourceNotFound(String),
 
+    /// Source's version has been yanked
+    #[error("Source '{0}' has been yanked: {1}. Pick a different version, or pass --allow-yanked to add it anyway.")]
+    SourceYanked(String, String),
+
     /// Generic anyhow error wrapper
     #[error(transparent)]
     Other(#[from] anyhow::Error),
@@ -10,10 +10,16 @@
 //! - **Project Management**: Initialize and configure BDP projects (`bdp init`)
 //! - **Source Management**: Add/remove data sources (`bdp source add/remove/list`)
 //! - **Dataset Installation**: Download and verify datasets (`bdp pull`)
+//! - **Publishing**: Upload custom dataset versions to the registry (`bdp publish`)
 //! - **Status Checking**: View cached datasets (`bdp status`)
+//! - **Upgrades**: List sources with newer versions available (`bdp outdated`)
+//! - **Diffs**: Show the entries that changed between two versions (`bdp diff`)
+//! - **Dependency Graphs**: Show what a version is built on, or what is built on it (`bdp graph`)
 //! - **Integrity Auditing**: Verify checksums (`bdp audit`)
 //! - **Cache Management**: Clean unused cache (`bdp clean`)
 //! - **Configuration**: Manage CLI settings (`bdp config`)
+//! - **Administration**: Trigger, cancel, retry and watch server ingestion jobs, and
+//!   reprocess records that failed ingestion (`bdp admin`)
 
 pub mod api;
 pub mod audit;
@@ -99,11 +105,77 @@ pub enum Commands {
         /// Force re-download even if cached
         #[arg(short, long)]
         force: bool,
+
+        /// Only pull the sources themselves, not the dependencies of aggregate sources
+        #[arg(long)]
+        no_deps: bool,
+
+        /// Split dependency bundles back into their member files
+        #[arg(long, conflicts_with = "no_deps")]
+        split: bool,
+    },
+
+    /// Publish the dataset described in the manifest's publish section
+    Publish {
+        /// Validate and hash files without uploading anything
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Show status of cached sources
     Status,
 
+    /// List manifest sources with newer versions available
+    Outdated,
+
+    /// Show the entries that changed between two versions of a data source
+    Diff {
+        /// Source at the old version or release (e.g. "uniprot:all@2024_06")
+        from: String,
+
+        /// Same source at the new version or release (e.g. "uniprot:all@2025_01")
+        to: String,
+
+        /// Only show entries with this kind of change
+        #[arg(long, value_parser = ["added", "removed", "modified"])]
+        change: Option<String>,
+
+        /// Page number
+        #[arg(short = 'p', long = "page", default_value = "1")]
+        page: i64,
+
+        /// Number of entries per page (1-1000)
+        #[arg(short = 'l', long = "limit", default_value = "100")]
+        limit: i64,
+
+        /// Output format (text or json)
+        #[arg(short = 'f', long = "format", default_value = "text")]
+        format: String,
+    },
+
+    /// Show the dependency graph of a data source version
+    Graph {
+        /// Source version (e.g. "ncbi:taxonomy@1.0")
+        spec: String,
+
+        /// Show the versions that depend on the source instead
+        #[arg(long)]
+        reverse: bool,
+
+        /// Number of dependency levels to follow (1-10)
+        #[arg(short = 'd', long = "depth", default_value = "3")]
+        depth: usize,
+
+        /// Output format
+        #[arg(
+            short = 'f',
+            long = "format",
+            default_value = "text",
+            value_parser = ["text", "json", "dot", "mermaid"]
+        )]
+        format: String,
+    },
+
     /// Audit trail management
     Audit {
         #[command(subcommand)]
@@ -138,13 +210,23 @@ pub enum Commands {
         purge: bool,
     },
 
+    /// Server administration
+    Admin {
+        /// Server admin token (required to change ingestion jobs)
+        #[arg(long, env = "BDP_ADMIN_TOKEN", hide_env_values = true, global = true)]
+        admin_token: Option<String>,
+
+        #[command(subcommand)]
+        command: AdminCommand,
+    },
+
     /// Search for data sources and tools in the registry
     Search {
         /// Search query (multiple words will be joined)
         #[arg(required = true)]
         query: Vec<String>,
 
-        /// Filter by entry type (can be repeated)
+        /// Filter by entry type or source type, e.g. "tool" or "structure" (can be repeated)
         #[arg(short = 't', long = "type")]
         entry_type: Vec<String>,
 
@@ -171,7 +253,7 @@ pub enum Commands {
 
     /// Advanced SQL-like querying of data sources and metadata
     Query {
-        /// Entity to query (protein, gene, genome, tools, orgs, etc.) or use --sql for raw SQL
+        /// Entity to query (protein, gene, genome, structure, tools, orgs, etc.) or use --sql for raw SQL
         entity: Option<String>,
 
         /// Select specific fields (comma-separated)
@@ -249,6 +331,14 @@ pub enum SourceCommand {
     Add {
         /// Source specification (e.g., "uniprot:P01308-fasta@1.0")
         source: String,
+
+        /// Add the replacement of a merged or deprecated source without asking
+        #[arg(long)]
+        use_replacement: bool,
+
+        /// Add the source even if its version has been yanked
+        #[arg(long)]
+        allow_yanked: bool,
     },
 
     /// Remove a source from the manifest
@@ -327,3 +417,95 @@ pub enum AuditCommand {
         project_version: Option<String>,
     },
 }
+
+/// Administration subcommands
+#[derive(Subcommand, Debug)]
+pub enum AdminCommand {
+    /// Manage ingestion jobs
+    Ingest {
+        #[command(subcommand)]
+        command: IngestCommand,
+    },
+
+    /// Follow ingestion jobs and inspect their failed records
+    Jobs {
+        #[command(subcommand)]
+        command: JobsCommand,
+    },
+}
+
+/// Job monitoring subcommands
+#[derive(Subcommand, Debug)]
+pub enum JobsCommand {
+    /// Stream live progress of one job until it finishes, or of every active job
+    Watch {
+        /// Ingestion job ID (omit to watch all active jobs)
+        job_id: Option<String>,
+    },
+
+    /// List the records of a job that failed parsing or storage
+    Failures {
+        /// Ingestion job ID
+        job_id: String,
+
+        /// Only show records that failed in this stage
+        #[arg(long, value_parser = ["parse", "store"])]
+        stage: Option<String>,
+
+        /// Include records that were already reprocessed
+        #[arg(long)]
+        all: bool,
+
+        /// Maximum number of records to show
+        #[arg(long, default_value = "20")]
+        limit: i64,
+
+        /// Print the raw text of each record
+        #[arg(long)]
+        raw: bool,
+    },
+}
+
+/// Ingestion job subcommands
+#[derive(Subcommand, Debug)]
+pub enum IngestCommand {
+    /// Start ingesting a source organization (latest version by default)
+    Start {
+        /// Organization the source ingests under (e.g., "uniprot", "ncbi")
+        organization: String,
+
+        /// Ingest this upstream version
+        #[arg(long, conflicts_with = "from")]
+        version: Option<String>,
+
+        /// Backfill upstream versions starting at this one
+        #[arg(long)]
+        from: Option<String>,
+
+        /// Last upstream version to backfill (defaults to the newest)
+        #[arg(long, requires = "from")]
+        to: Option<String>,
+
+        /// Re-ingest backfilled versions that were already ingested
+        #[arg(long, requires = "from")]
+        include_existing: bool,
+    },
+
+    /// Cancel a running ingestion job
+    Cancel {
+        /// Ingestion job ID
+        job_id: String,
+    },
+
+    /// Retry the failed work units of a finished ingestion job
+    Retry {
+        /// Ingestion job ID
+        job_id: String,
+    },
+
+    /// Re-run only the records of a finished job that failed parsing or storage
+    Reprocess {
+        /// Ingestion job ID
+        job_id: String,
+    },
+}
@@ -47,6 +47,43 @@ pub struct SourceEntry {
     /// Number of dependencies (for sources with dependencies)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dependency_count: Option<i32>,
+
+    /// Locked transitive dependencies, for aggregate sources
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<DependencyEntry>,
+
+    /// Locked bundle of the dependencies, when one was pulled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bundle: Option<BundleEntry>,
+}
+
+/// Entry for a locked dependency of a source
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DependencyEntry {
+    /// Source specification with the format (e.g., "uniprot:P01308-fasta@1.0")
+    pub spec: String,
+
+    /// SHA-256 checksum
+    pub checksum: String,
+
+    /// File size in bytes
+    pub size: i64,
+}
+
+/// Entry for the bundle of an aggregate source's dependencies
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BundleEntry {
+    /// Bundle layout (e.g., "multifasta", "jsonl", "tar")
+    pub bundle_format: String,
+
+    /// SHA-256 checksum
+    pub checksum: String,
+
+    /// File size in bytes
+    pub size: i64,
+
+    /// Number of member files in the bundle
+    pub member_count: i32,
 }
 
 /// Entry for a locked tool
@@ -159,6 +196,8 @@ impl SourceEntry {
             size,
             external_version,
             dependency_count: None,
+            dependencies: Vec::new(),
+            bundle: None,
         }
     }
 
@@ -178,6 +217,44 @@ impl SourceEntry {
             size,
             external_version,
             dependency_count: Some(dependency_count),
+            dependencies: Vec::new(),
+            bundle: None,
+        }
+    }
+
+    /// Lock the dependencies of the source, setting the dependency count
+    pub fn with_locked_dependencies(mut self, dependencies: Vec<DependencyEntry>) -> Self {
+        self.dependency_count = Some(dependencies.len() as i32);
+        self.dependencies = dependencies;
+        self
+    }
+
+    /// Lock the bundle the dependencies were pulled through
+    pub fn with_bundle(mut self, bundle: BundleEntry) -> Self {
+        self.bundle = Some(bundle);
+        self
+    }
+}
+
+impl BundleEntry {
+    /// Create a new bundle entry
+    pub fn new(bundle_format: String, checksum: String, size: i64, member_count: i32) -> Self {
+        Self {
+            bundle_format,
+            checksum,
+            size,
+            member_count,
+        }
+    }
+}
+
+impl DependencyEntry {
+    /// Create a new dependency entry
+    pub fn new(spec: String, checksum: String, size: i64) -> Self {
+        Self {
+            spec,
+            checksum,
+            size,
         }
     }
 }
@@ -313,6 +390,48 @@ mod tests {
         assert_eq!(entry.dependency_count, Some(5));
     }
 
+    #[test]
+    fn test_locked_dependencies_round_trip() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        let entry = SourceEntry::new(
+            "uniprot:proteome@1.0".to_string(),
+            "fasta".to_string(),
+            "abc123".to_string(),
+            1024,
+            "2025_01".to_string(),
+        )
+        .with_locked_dependencies(vec![DependencyEntry::new(
+            "uniprot:P01308-fasta@1.0".to_string(),
+            "def456".to_string(),
+            512,
+        )])
+        .with_bundle(BundleEntry::new(
+            "multifasta".to_string(),
+            "789abc".to_string(),
+            512,
+            1,
+        ));
+        assert_eq!(entry.dependency_count, Some(1));
+
+        let mut lockfile = Lockfile::new();
+        lockfile.add_source("uniprot:proteome-fasta@1.0".to_string(), entry.clone());
+        lockfile.save(path).unwrap();
+
+        let loaded = Lockfile::load(path).unwrap();
+        assert_eq!(loaded.get_source("uniprot:proteome-fasta@1.0"), Some(&entry));
+
+        // Lockfiles written before dependencies were locked still load
+        let legacy = r#"{"lockfile_version":1,"generated":"2025-01-01T00:00:00Z","sources":{"uniprot:P01308-fasta@1.0":{"resolved":"uniprot:P01308@1.0","format":"fasta","checksum":"abc123","size":1024,"external_version":"2025_01"}}}"#;
+        std::fs::write(path, legacy).unwrap();
+        let loaded = Lockfile::load(path).unwrap();
+        assert!(loaded.sources["uniprot:P01308-fasta@1.0"]
+            .dependencies
+            .is_empty());
+        assert!(loaded.sources["uniprot:P01308-fasta@1.0"].bundle.is_none());
+    }
+
     #[test]
     fn test_entry_count() {
         let mut lockfile = Lockfile::new();
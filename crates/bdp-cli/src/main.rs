@@ -85,19 +85,75 @@ async fn execute_command(cli: &Cli) -> bdp_cli::Result<()> {
         },
 
         Commands::Source { command } => match command {
-            SourceCommand::Add { source } => bdp_cli::commands::source::add(source.clone()).await,
+            SourceCommand::Add {
+                source,
+                use_replacement,
+                allow_yanked,
+            } => {
+                bdp_cli::commands::source::add(
+                    cli.server_url.clone(),
+                    source.clone(),
+                    *use_replacement,
+                    *allow_yanked,
+                )
+                .await
+            },
             SourceCommand::Remove { source } => {
                 bdp_cli::commands::source::remove(source.clone()).await
             },
             SourceCommand::List => bdp_cli::commands::source::list().await,
         },
 
-        Commands::Pull { force } => {
-            bdp_cli::commands::pull::run(cli.server_url.clone(), *force).await
+        Commands::Pull {
+            force,
+            no_deps,
+            split,
+        } => bdp_cli::commands::pull::run(cli.server_url.clone(), *force, *no_deps, *split).await,
+
+        Commands::Publish { dry_run } => {
+            bdp_cli::commands::publish::run(cli.server_url.clone(), *dry_run).await
         },
 
         Commands::Status => bdp_cli::commands::status::run().await,
 
+        Commands::Outdated => bdp_cli::commands::outdated::run(cli.server_url.clone()).await,
+
+        Commands::Diff {
+            from,
+            to,
+            change,
+            page,
+            limit,
+            format,
+        } => {
+            bdp_cli::commands::diff::run(
+                cli.server_url.clone(),
+                from.clone(),
+                to.clone(),
+                change.clone(),
+                *page,
+                *limit,
+                format.clone(),
+            )
+            .await
+        },
+
+        Commands::Graph {
+            spec,
+            reverse,
+            depth,
+            format,
+        } => {
+            bdp_cli::commands::graph::run(
+                cli.server_url.clone(),
+                spec.clone(),
+                *reverse,
+                *depth,
+                format.clone(),
+            )
+            .await
+        },
+
         Commands::Audit { command } => bdp_cli::commands::audit::run(command).await,
 
         Commands::Clean { all, search_cache } => {
@@ -115,6 +171,14 @@ async fn execute_command(cli: &Cli) -> bdp_cli::Result<()> {
 
         Commands::Uninstall { yes, purge } => bdp_cli::commands::uninstall::run(*yes, *purge).await,
 
+        Commands::Admin {
+            admin_token,
+            command,
+        } => {
+            bdp_cli::commands::admin::run(command, admin_token.clone(), cli.server_url.clone())
+                .await
+        },
+
         Commands::Search {
             query,
             entry_type,
@@ -19,6 +19,10 @@ pub struct Manifest {
     /// Tool dependencies (e.g., "ncbi:blast@2.14.0")
     #[serde(default)]
     pub tools: Vec<String>,
+
+    /// Publishing configuration for `bdp publish`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publish: Option<PublishConfig>,
 }
 
 /// Project metadata section
@@ -35,6 +39,118 @@ pub struct ProjectMetadata {
     pub description: Option<String>,
 }
 
+/// Publish section describing a dataset version to upload to the registry
+///
+/// ```yaml
+/// publish:
+///   organization: my-lab
+///   slug: filtered-proteome
+///   source_type: protein
+///   version: "1.0"
+///   files:
+///     - path: output/filtered.fasta
+///       format: fasta
+///   dependencies:
+///     - uniprot:all@2025_01
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PublishConfig {
+    /// Organization slug the data source belongs to
+    pub organization: String,
+
+    /// Data source slug (created on first publish)
+    pub slug: String,
+
+    /// Display name (defaults to the slug)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Optional data source description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Source type (protein, genome, annotation, structure, other)
+    pub source_type: String,
+
+    /// Version to publish
+    pub version: String,
+
+    /// Upstream version string, if the dataset tracks one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_version: Option<String>,
+
+    /// Files that make up the version
+    pub files: Vec<PublishFile>,
+
+    /// Registry sources this version was derived from ("org:slug@version")
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+}
+
+/// A local file to upload as part of a published version
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PublishFile {
+    /// Path relative to the project directory
+    pub path: String,
+
+    /// File format (fasta, tsv, gff3, ...)
+    pub format: String,
+
+    /// Compression (gzip, bzip2, none)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compression: Option<String>,
+}
+
+impl PublishConfig {
+    /// Validate the publish section
+    pub fn validate(&self) -> Result<()> {
+        if self.organization.is_empty() {
+            return Err(CliError::invalid_manifest("publish.organization cannot be empty"));
+        }
+
+        if self.slug.is_empty() {
+            return Err(CliError::invalid_manifest("publish.slug cannot be empty"));
+        }
+
+        if self.version.is_empty() {
+            return Err(CliError::invalid_manifest("publish.version cannot be empty"));
+        }
+
+        if self.files.is_empty() {
+            return Err(CliError::invalid_manifest("publish.files must list at least one file"));
+        }
+
+        let mut formats = std::collections::HashSet::new();
+        for file in &self.files {
+            if file.path.is_empty() || file.format.is_empty() {
+                return Err(CliError::invalid_manifest(
+                    "Each publish file needs both 'path' and 'format'",
+                ));
+            }
+            if !formats.insert(file.format.as_str()) {
+                return Err(CliError::invalid_manifest(format!(
+                    "publish.files contains format '{}' more than once",
+                    file.format
+                )));
+            }
+        }
+
+        for dependency in &self.dependencies {
+            validate_source_spec(dependency)?;
+        }
+
+        Ok(())
+    }
+
+    /// Source specs that consumers can add to their manifests, one per file
+    pub fn published_specs(&self) -> Vec<String> {
+        self.files
+            .iter()
+            .map(|f| format!("{}:{}-{}@{}", self.organization, self.slug, f.format, self.version))
+            .collect()
+    }
+}
+
 impl Manifest {
     /// Create a new manifest with the given project metadata
     pub fn new(name: String, version: String) -> Self {
@@ -46,6 +162,7 @@ impl Manifest {
             },
             sources: Vec::new(),
             tools: Vec::new(),
+            publish: None,
         }
     }
 
@@ -59,6 +176,7 @@ impl Manifest {
             },
             sources: Vec::new(),
             tools: Vec::new(),
+            publish: None,
         }
     }
 
@@ -151,6 +269,10 @@ impl Manifest {
             validate_source_spec(tool)?; // Tools use same format
         }
 
+        if let Some(publish) = &self.publish {
+            publish.validate()?;
+        }
+
         Ok(())
     }
 }
@@ -337,6 +459,72 @@ mod tests {
         assert_eq!(format, None);
     }
 
+    fn publish_config() -> PublishConfig {
+        PublishConfig {
+            organization: "my-lab".to_string(),
+            slug: "filtered-proteome".to_string(),
+            name: None,
+            description: None,
+            source_type: "protein".to_string(),
+            version: "1.0".to_string(),
+            external_version: None,
+            files: vec![PublishFile {
+                path: "output/filtered.fasta".to_string(),
+                format: "fasta".to_string(),
+                compression: None,
+            }],
+            dependencies: vec!["uniprot:all@2025_01".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_publish_section_roundtrip() {
+        let yaml = r#"
+project:
+  name: test
+  version: "1.0"
+publish:
+  organization: my-lab
+  slug: filtered-proteome
+  source_type: protein
+  version: "1.0"
+  files:
+    - path: output/filtered.fasta
+      format: fasta
+  dependencies:
+    - uniprot:all@2025_01
+"#;
+        let manifest: Manifest = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(manifest.publish, Some(publish_config()));
+        assert!(manifest.validate().is_ok());
+
+        // Manifests without a publish section don't serialize one
+        let plain = serde_yaml::to_string(&Manifest::default()).unwrap();
+        assert!(!plain.contains("publish"));
+    }
+
+    #[test]
+    fn test_publish_validate() {
+        assert!(publish_config().validate().is_ok());
+
+        let mut config = publish_config();
+        config.files.clear();
+        assert!(config.validate().is_err());
+
+        let mut config = publish_config();
+        config.files.push(config.files[0].clone());
+        assert!(config.validate().is_err());
+
+        let mut config = publish_config();
+        config.dependencies.push("not-a-spec".to_string());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_published_specs() {
+        assert_eq!(publish_config().published_specs(), vec!["my-lab:filtered-proteome-fasta@1.0"]);
+    }
+
     #[test]
     fn test_manifest_validate() {
         let mut manifest = Manifest::new("test".to_string(), "1.0".to_string());
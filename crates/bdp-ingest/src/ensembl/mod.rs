@@ -1,33 +1,149 @@
 //! Ensembl data source ingestion module
 //!
-//! This module handles ingestion of biological data from Ensembl sources including:
-//! - Genome assemblies and annotations
-//! - Gene and transcript models
-//! - Comparative genomics data
-//! - Variation data
+//! Downloads the gene sets of an Ensembl release into a local mirror with the
+//! FTP site's layout, releases included under `pub/`. The server ingests
+//! releases from such a mirror when `INGEST_ENSEMBL_LOCAL_MIRROR` points at
+//! the output directory:
+//!
+//! ```text
+//! pub/release-110/species_EnsemblVertebrates.txt
+//! pub/release-110/gtf/homo_sapiens/Homo_sapiens.GRCh38.110.gtf.gz
+//! pub/release-110/gff3/homo_sapiens/Homo_sapiens.GRCh38.110.gff3.gz
+//! pub/release-110/fasta/homo_sapiens/cdna/Homo_sapiens.GRCh38.cdna.all.fa.gz
+//! pub/release-110/fasta/homo_sapiens/pep/Homo_sapiens.GRCh38.pep.all.fa.gz
+//! ```
+
+use anyhow::{Context, Result};
+use scraper::{Html, Selector};
+use std::path::PathBuf;
+use tracing::{info, warn};
+
+const ENSEMBL_FTP_URL: &str = "https://ftp.ensembl.org/pub";
 
-use anyhow::Result;
+/// Directory of the mirror holding the releases, as on the FTP site
+const RELEASES_DIR: &str = "pub";
 
 /// Ensembl data ingestion functionality
 pub struct EnsemblIngester {
-    // Configuration and state will be added here
+    base_url: String,
+    output_dir: PathBuf,
+    species: Vec<String>,
+    client: reqwest::Client,
 }
 
 impl EnsemblIngester {
-    /// Creates a new Ensembl ingester instance
-    pub fn new() -> Self {
-        Self {}
+    /// Creates a new Ensembl ingester writing its mirror to `output_dir`
+    pub fn new(output_dir: impl Into<PathBuf>, species: Vec<String>) -> Self {
+        Self {
+            base_url: ENSEMBL_FTP_URL.to_string(),
+            output_dir: output_dir.into(),
+            species,
+            client: reqwest::Client::new(),
+        }
     }
 
-    /// Ingests data from Ensembl sources
-    pub async fn ingest(&self) -> Result<()> {
-        // Implementation will be added
-        todo!("Ensembl ingestion not yet implemented")
+    /// Read from another Ensembl FTP mirror, given the URL of its releases
+    /// directory (e.g. `https://ftp.ensembl.org/pub`)
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
     }
-}
 
-impl Default for EnsemblIngester {
-    fn default() -> Self {
-        Self::new()
+    /// Mirrors the gene sets of a release, or of the newest release
+    ///
+    /// Returns the release number that was mirrored.
+    pub async fn ingest(&self, release: Option<u32>) -> Result<u32> {
+        let release = match release {
+            Some(release) => release,
+            None => self.latest_release().await?,
+        };
+        let release_dir = format!("release-{}", release);
+        info!("Mirroring Ensembl release {} to {}", release, self.output_dir.display());
+
+        self.download(&format!("{}/species_EnsemblVertebrates.txt", release_dir))
+            .await?;
+
+        for species in &self.species {
+            let dirs = [
+                (format!("{}/gtf/{}", release_dir, species), format!(".{}.gtf.gz", release)),
+                (format!("{}/gff3/{}", release_dir, species), format!(".{}.gff3.gz", release)),
+                (format!("{}/fasta/{}/cdna", release_dir, species), ".cdna.all.fa.gz".to_string()),
+                (format!("{}/fasta/{}/pep", release_dir, species), ".pep.all.fa.gz".to_string()),
+            ];
+
+            for (dir, suffix) in dirs {
+                let files = self.list(&dir).await?;
+                let Some(file) = files.iter().find(|name| is_primary_file(name, &suffix)) else {
+                    warn!("No {} file in {}", suffix, dir);
+                    continue;
+                };
+                self.download(&format!("{}/{}", dir, file)).await?;
+            }
+        }
+
+        Ok(release)
     }
+
+    /// Newest release on the FTP site
+    async fn latest_release(&self) -> Result<u32> {
+        self.list("")
+            .await?
+            .iter()
+            .filter_map(|name| name.strip_prefix("release-")?.parse().ok())
+            .max()
+            .context("No Ensembl releases found")
+    }
+
+    /// Names linked from a directory listing
+    async fn list(&self, dir: &str) -> Result<Vec<String>> {
+        let url = format!("{}/{}/", self.base_url, dir.trim_matches('/'));
+        let response = self.client.get(&url).send().await?;
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to list {}: {}", url, response.status());
+        }
+        let html = Html::parse_document(&response.text().await?);
+
+        let links = Selector::parse("a[href]").expect("valid selector");
+        Ok(html
+            .select(&links)
+            .filter_map(|link| link.value().attr("href"))
+            .filter(|href| !href.starts_with(['?', '/', '.']) && !href.contains("://"))
+            .map(|href| href.trim_end_matches('/').to_string())
+            .collect())
+    }
+
+    /// Download a file to its path on the FTP site under the output directory
+    async fn download(&self, path: &str) -> Result<()> {
+        let url = format!("{}/{}", self.base_url, path);
+        let output_file = self.output_dir.join(RELEASES_DIR).join(path);
+        if let Some(parent) = output_file.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        info!("Downloading {} ...", path);
+        let response = self.client.get(&url).send().await?;
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to download {}: {}", url, response.status());
+        }
+
+        let mut file = std::fs::File::create(&output_file)?;
+        let mut stream = response.bytes_stream();
+
+        use futures::StreamExt;
+        while let Some(chunk) = stream.next().await {
+            std::io::Write::write_all(&mut file, &chunk?)?;
+        }
+
+        info!("Downloaded {}", output_file.display());
+        Ok(())
+    }
+}
+
+/// Whether a file is the primary file of its directory, rather than one of the
+/// per-chromosome, ab initio or patch/haplotype variants next to it
+fn is_primary_file(name: &str, suffix: &str) -> bool {
+    name.ends_with(suffix)
+        && ![".chr.", ".abinitio.", ".chr_patch_hapl_scaff."]
+            .iter()
+            .any(|variant| name.contains(variant))
 }
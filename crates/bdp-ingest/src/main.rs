@@ -2,7 +2,7 @@
 
 use anyhow::Result;
 use bdp_common::logging::{init_logging, LogConfig, LogLevel};
-use bdp_ingest::{uniprot, version_mapping};
+use bdp_ingest::{ensembl::EnsemblIngester, uniprot, version_mapping};
 use clap::Parser;
 use tracing::info;
 
@@ -32,6 +32,26 @@ enum Source {
         version: Option<String>,
     },
 
+    /// Mirror Ensembl gene sets
+    Ensembl {
+        /// Output directory
+        #[arg(short, long, default_value = "./data/ensembl")]
+        output: String,
+
+        /// Ensembl release number (defaults to the newest release)
+        #[arg(short, long)]
+        release: Option<u32>,
+
+        /// Species production names
+        #[arg(
+            short,
+            long,
+            value_delimiter = ',',
+            default_value = "homo_sapiens,mus_musculus"
+        )]
+        species: Vec<String>,
+    },
+
     /// Generate version mapping
     VersionMapping {
         /// Input directory
@@ -70,6 +90,17 @@ async fn main() -> Result<()> {
             info!("Ingesting UniProt data");
             uniprot::ingest(&output, version.as_deref()).await?;
         },
+        Source::Ensembl {
+            output,
+            release,
+            species,
+        } => {
+            info!("Mirroring Ensembl gene sets");
+            let release = EnsemblIngester::new(output, species)
+                .ingest(release)
+                .await?;
+            info!("Mirrored Ensembl release {}", release);
+        },
         Source::VersionMapping { input, output } => {
             info!("Generating version mapping");
             version_mapping::generate(&input, &output).await?;
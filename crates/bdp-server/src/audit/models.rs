@@ -60,6 +60,12 @@ pub enum AuditAction {
     Grant,
     Revoke,
     Ingest,
+    /// A write to a sealed version was refused
+    #[serde(rename = "overwrite_rejected")]
+    OverwriteRejected,
+    /// An administrator overrode the seal on a version
+    #[serde(rename = "seal_override")]
+    SealOverride,
     Other,
 }
 
@@ -81,6 +87,8 @@ impl AuditAction {
             Self::Grant => "grant",
             Self::Revoke => "revoke",
             Self::Ingest => "ingest",
+            Self::OverwriteRejected => "overwrite_rejected",
+            Self::SealOverride => "seal_override",
             Self::Other => "other",
         }
     }
@@ -337,6 +345,9 @@ mod tests {
 
         let action: AuditAction = serde_json::from_str(r#""update""#).unwrap();
         assert_eq!(action, AuditAction::Update);
+
+        let json = serde_json::to_string(&AuditAction::OverwriteRejected).unwrap();
+        assert_eq!(json, format!(r#""{}""#, AuditAction::OverwriteRejected.as_str()));
     }
 
     #[test]
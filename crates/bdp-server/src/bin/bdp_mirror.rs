@@ -0,0 +1,95 @@
+//! Registry Mirror
+//!
+//! Syncs organizations, data sources and versions from an upstream BDP
+//! registry into the local database and storage. Safe to re-run: only
+//! versions that are new or incomplete locally are transferred.
+//!
+//! Usage:
+//!   MIRROR_UPSTREAM_URL=https://bdp.example.org cargo run --bin bdp_mirror -- \
+//!     uniprot:swissprot@latest ncbi:taxonomy@>=2025.01 my-lab
+//!
+//! Each argument is a selector: `org`, `org:slug` or `org:slug@range`, where a
+//! range is `*`, `latest`, an exact version or comparisons such as `>=1.0,<2.0`.
+//!
+//! Environment variables:
+//!   MIRROR_UPSTREAM_URL (required) - Base URL of the upstream registry
+//!   DATABASE_URL (required) - PostgreSQL connection string
+//!   STORAGE_* / AWS_* - Local storage configuration
+//!   DRY_RUN (optional, default: false) - Print the parsed selectors and exit
+//!   VERBOSE (optional, default: false) - Enable verbose logging
+
+use anyhow::{bail, Context, Result};
+use bdp_server::mirror::{Mirror, MirrorSelector, UpstreamClient};
+use bdp_server::storage::{config::StorageConfig, Storage};
+use sqlx::postgres::PgPoolOptions;
+use std::env;
+use tracing::{info, Level};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let upstream_url = env::var("MIRROR_UPSTREAM_URL")
+        .context("MIRROR_UPSTREAM_URL environment variable must be set")?;
+
+    let dry_run = env::var("DRY_RUN")
+        .map(|v| v.to_lowercase() == "true" || v == "1")
+        .unwrap_or(false);
+
+    let verbose = env::var("VERBOSE")
+        .map(|v| v.to_lowercase() == "true" || v == "1")
+        .unwrap_or(false);
+
+    let log_level = if verbose { Level::DEBUG } else { Level::INFO };
+
+    tracing_subscriber::fmt()
+        .with_max_level(log_level)
+        .with_target(false)
+        .init();
+
+    let selectors = env::args()
+        .skip(1)
+        .map(|arg| arg.parse::<MirrorSelector>())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if selectors.is_empty() {
+        bail!("Usage: bdp_mirror <org[:slug][@range]>...");
+    }
+
+    info!(upstream = %upstream_url, "Registry mirror");
+    for selector in &selectors {
+        info!(selector = %selector, "Selector");
+    }
+
+    if dry_run {
+        info!("Dry run mode - nothing mirrored");
+        return Ok(());
+    }
+
+    let database_url =
+        env::var("DATABASE_URL").context("DATABASE_URL environment variable must be set")?;
+
+    let db = PgPoolOptions::new()
+        .max_connections(5)
+        .connect(&database_url)
+        .await
+        .context("Failed to connect to database")?;
+
+    let storage = Storage::new(StorageConfig::from_env()?).await?;
+    let client = UpstreamClient::new(upstream_url)?;
+
+    let report = Mirror::new(db, storage, client).run(&selectors).await?;
+
+    info!(
+        run_id = %report.run_id,
+        organizations = report.organizations,
+        data_sources = report.data_sources,
+        versions_mirrored = report.versions_mirrored,
+        versions_unchanged = report.versions_unchanged,
+        files = report.files_mirrored,
+        bytes = report.bytes_mirrored,
+        dependencies_linked = report.dependencies_linked,
+        dependencies_missing = report.dependencies_missing,
+        "Mirror run completed"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,542 @@
+//! Add dependencies command
+//!
+//! Records which registry sources a published version depends on. Each
+//! dependency is pinned to an exact version so that lockfiles stay
+//! reproducible.
+//!
+//! Dependencies are normally sent with the publish request. Once a version is
+//! sealed its pins can no longer change: re-recording an existing pin is a
+//! no-op, anything else is refused and audited unless an administrator
+//! overrides the seal.
+
+use mediator::Request;
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::features::version_files::sealing;
+
+/// Command to add dependencies to a data source version
+///
+/// The path segments (`organization_slug`, `data_source_slug`, `version`) are
+/// filled in from the route; the request body only carries `dependencies`.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use bdp_server::features::data_sources::commands::{AddDependenciesCommand, DependencyInput};
+///
+/// let command = AddDependenciesCommand {
+///     organization_slug: "my-lab".to_string(),
+///     data_source_slug: "filtered-proteome".to_string(),
+///     version: "1.0".to_string(),
+///     dependencies: vec![DependencyInput {
+///         source: "uniprot:P01308@1.0".to_string(),
+///         dependency_type: None,
+///     }],
+///     override_reason: None,
+/// };
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddDependenciesCommand {
+    #[serde(default)]
+    pub organization_slug: String,
+    #[serde(default)]
+    pub data_source_slug: String,
+    #[serde(default)]
+    pub version: String,
+    pub dependencies: Vec<DependencyInput>,
+    /// Admin-provided reason for changing the dependencies of a sealed version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub override_reason: Option<String>,
+}
+
+/// A single dependency in `org:slug@version` form
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyInput {
+    pub source: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dependency_type: Option<String>,
+}
+
+/// Response from adding dependencies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddDependenciesResponse {
+    pub version_id: Uuid,
+    pub dependencies_added: usize,
+    pub dependency_count: i32,
+}
+
+/// Errors that can occur when adding dependencies
+#[derive(Debug, thiserror::Error)]
+pub enum AddDependenciesError {
+    #[error("Organization slug is required and cannot be empty")]
+    OrganizationSlugRequired,
+    #[error("Data source slug is required and cannot be empty")]
+    DataSourceSlugRequired,
+    #[error("Version is required and cannot be empty")]
+    VersionRequired,
+    #[error("Dependencies list cannot be empty")]
+    DependenciesRequired,
+    #[error("Invalid dependency '{0}'. Expected format: 'org:slug@version'")]
+    InvalidSpec(String),
+    #[error("Dependency type must be 'required' or 'optional', got '{0}'")]
+    InvalidDependencyType(String),
+    #[error("Version '{2}' for data source '{0}/{1}' not found")]
+    VersionNotFound(String, String, String),
+    #[error("Dependency '{0}' not found in registry")]
+    DependencyNotFound(String),
+    #[error("A version cannot depend on its own data source ('{0}')")]
+    SelfDependency(String),
+    #[error("Version '{0}' is sealed; its dependencies cannot be changed ({1})")]
+    VersionSealed(Uuid, String),
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl Request<Result<AddDependenciesResponse, AddDependenciesError>> for AddDependenciesCommand {}
+
+impl crate::cqrs::middleware::Command for AddDependenciesCommand {}
+
+impl DependencyInput {
+    /// Splits the dependency spec into `(org, slug, version)`
+    pub fn parse(&self) -> Result<(&str, &str, &str), AddDependenciesError> {
+        let invalid = || AddDependenciesError::InvalidSpec(self.source.clone());
+
+        let (org, rest) = self.source.split_once(':').ok_or_else(invalid)?;
+        let (slug, version) = rest.split_once('@').ok_or_else(invalid)?;
+
+        if org.is_empty() || slug.is_empty() || version.is_empty() || version.contains('@') {
+            return Err(invalid());
+        }
+
+        Ok((org, slug, version))
+    }
+
+    fn dependency_type(&self) -> &str {
+        self.dependency_type.as_deref().unwrap_or("required")
+    }
+}
+
+impl AddDependenciesCommand {
+    pub fn validate(&self) -> Result<(), AddDependenciesError> {
+        if self.organization_slug.is_empty() {
+            return Err(AddDependenciesError::OrganizationSlugRequired);
+        }
+        if self.data_source_slug.is_empty() {
+            return Err(AddDependenciesError::DataSourceSlugRequired);
+        }
+        if self.version.is_empty() {
+            return Err(AddDependenciesError::VersionRequired);
+        }
+        if self.dependencies.is_empty() {
+            return Err(AddDependenciesError::DependenciesRequired);
+        }
+        validate_dependencies(&self.dependencies)
+    }
+}
+
+/// Validate the spec and type of each dependency
+pub(crate) fn validate_dependencies(
+    dependencies: &[DependencyInput],
+) -> Result<(), AddDependenciesError> {
+    for dependency in dependencies {
+        dependency.parse()?;
+        let dependency_type = dependency.dependency_type();
+        if dependency_type != "required" && dependency_type != "optional" {
+            return Err(AddDependenciesError::InvalidDependencyType(dependency_type.to_string()));
+        }
+    }
+    Ok(())
+}
+
+/// A dependency resolved to the registry entry and version it pins
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ResolvedDependency {
+    pub source: String,
+    pub entry_id: Uuid,
+    pub version: String,
+    pub version_id: Uuid,
+    pub dependency_type: String,
+}
+
+/// Handles the add dependencies command
+///
+/// Resolves each dependency to a pinned version, upserts it into the
+/// `dependencies` table and refreshes `versions.dependency_count`, all in a
+/// single transaction. Changes to a sealed version require an override
+/// reason; refusals and overrides are audited.
+///
+/// # Errors
+///
+/// - Validation errors if command parameters are invalid
+/// - `VersionNotFound` - The target version doesn't exist
+/// - `DependencyNotFound` - A dependency source or version doesn't exist
+/// - `SelfDependency` - A dependency points back at the same data source
+/// - `VersionSealed` - The version is sealed and no override was given
+/// - `Database` - A database error occurred
+#[tracing::instrument(skip(pool))]
+pub async fn handle(
+    pool: PgPool,
+    command: AddDependenciesCommand,
+) -> Result<AddDependenciesResponse, AddDependenciesError> {
+    command.validate()?;
+
+    let target = sqlx::query!(
+        r#"
+        SELECT v.id, v.entry_id, v.sealed_at
+        FROM versions v
+        JOIN registry_entries re ON v.entry_id = re.id
+        JOIN organizations o ON re.organization_id = o.id
+        WHERE LOWER(o.slug) = LOWER($1) AND LOWER(re.slug) = LOWER($2) AND v.version = $3
+        "#,
+        command.organization_slug,
+        command.data_source_slug,
+        command.version
+    )
+    .fetch_optional(&pool)
+    .await?
+    .ok_or_else(|| {
+        AddDependenciesError::VersionNotFound(
+            command.organization_slug.clone(),
+            command.data_source_slug.clone(),
+            command.version.clone(),
+        )
+    })?;
+
+    let mut tx = pool.begin().await?;
+    let mut resolved =
+        resolve_dependencies(&mut tx, target.entry_id, &command.dependencies).await?;
+    let mut override_audit = None;
+
+    if target.sealed_at.is_some() {
+        let existing = sqlx::query!(
+            r#"
+            SELECT depends_on_entry_id, depends_on_version, dependency_type
+            FROM dependencies
+            WHERE version_id = $1
+            "#,
+            target.id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        // Re-recording exactly the pins that are already there is harmless
+        resolved.retain(|dependency| {
+            !existing.iter().any(|e| {
+                e.depends_on_entry_id == dependency.entry_id
+                    && e.depends_on_version == dependency.version
+                    && e.dependency_type.as_deref() == Some(dependency.dependency_type.as_str())
+            })
+        });
+
+        if !resolved.is_empty() {
+            let details = serde_json::json!({
+                "attempted_dependencies": resolved,
+                "existing_dependencies": existing
+                    .iter()
+                    .map(|e| serde_json::json!({
+                        "entry_id": e.depends_on_entry_id,
+                        "version": e.depends_on_version,
+                        "dependency_type": e.dependency_type,
+                    }))
+                    .collect::<Vec<_>>(),
+            });
+
+            match command.override_reason {
+                Some(reason) => override_audit = Some((reason, details)),
+                None => {
+                    drop(tx);
+                    sealing::record_rejected_write(&pool, target.id, details).await;
+                    let sources = resolved
+                        .iter()
+                        .map(|d| d.source.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    return Err(AddDependenciesError::VersionSealed(target.id, sources));
+                },
+            }
+        }
+    }
+
+    let dependency_count = insert_dependencies(&mut tx, target.id, &resolved).await?;
+    tx.commit().await?;
+
+    if let Some((reason, details)) = override_audit {
+        sealing::record_override(&pool, target.id, &reason, details).await;
+    }
+
+    Ok(AddDependenciesResponse {
+        version_id: target.id,
+        dependencies_added: resolved.len(),
+        dependency_count,
+    })
+}
+
+/// Resolve each dependency to the registry entry and version it pins
+///
+/// `entry_id` is the data source the dependencies belong to; depending on it
+/// is refused.
+pub(crate) async fn resolve_dependencies(
+    tx: &mut Transaction<'_, Postgres>,
+    entry_id: Uuid,
+    dependencies: &[DependencyInput],
+) -> Result<Vec<ResolvedDependency>, AddDependenciesError> {
+    let mut resolved = Vec::with_capacity(dependencies.len());
+
+    for dependency in dependencies {
+        let (org, slug, version) = dependency.parse()?;
+
+        let row = sqlx::query!(
+            r#"
+            SELECT re.id as entry_id, v.id as version_id
+            FROM registry_entries re
+            JOIN organizations o ON re.organization_id = o.id
+            JOIN versions v ON v.entry_id = re.id
+            WHERE LOWER(o.slug) = LOWER($1) AND LOWER(re.slug) = LOWER($2) AND v.version = $3
+            "#,
+            org,
+            slug,
+            version
+        )
+        .fetch_optional(&mut **tx)
+        .await?
+        .ok_or_else(|| AddDependenciesError::DependencyNotFound(dependency.source.clone()))?;
+
+        if row.entry_id == entry_id {
+            return Err(AddDependenciesError::SelfDependency(dependency.source.clone()));
+        }
+
+        resolved.push(ResolvedDependency {
+            source: dependency.source.clone(),
+            entry_id: row.entry_id,
+            version: version.to_string(),
+            version_id: row.version_id,
+            dependency_type: dependency.dependency_type().to_string(),
+        });
+    }
+
+    Ok(resolved)
+}
+
+/// Upsert resolved dependencies of a version and refresh its dependency count
+///
+/// Returns the new `versions.dependency_count`.
+pub(crate) async fn insert_dependencies(
+    tx: &mut Transaction<'_, Postgres>,
+    version_id: Uuid,
+    dependencies: &[ResolvedDependency],
+) -> Result<i32, sqlx::Error> {
+    for dependency in dependencies {
+        sqlx::query!(
+            r#"
+            INSERT INTO dependencies (
+                version_id, depends_on_entry_id, depends_on_version,
+                dependency_version_id, dependency_type
+            )
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (version_id, depends_on_entry_id) DO UPDATE SET
+                depends_on_version = EXCLUDED.depends_on_version,
+                dependency_version_id = EXCLUDED.dependency_version_id,
+                dependency_type = EXCLUDED.dependency_type
+            "#,
+            version_id,
+            dependency.entry_id,
+            dependency.version,
+            dependency.version_id,
+            dependency.dependency_type
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    sqlx::query_scalar!(
+        r#"
+        UPDATE versions
+        SET dependency_count = (SELECT COUNT(*) FROM dependencies WHERE version_id = $1),
+            updated_at = NOW()
+        WHERE id = $1
+        RETURNING dependency_count as "dependency_count!"
+        "#,
+        version_id
+    )
+    .fetch_one(&mut **tx)
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(dependencies: Vec<DependencyInput>) -> AddDependenciesCommand {
+        AddDependenciesCommand {
+            organization_slug: "my-lab".to_string(),
+            data_source_slug: "filtered-proteome".to_string(),
+            version: "1.0".to_string(),
+            dependencies,
+            override_reason: None,
+        }
+    }
+
+    fn dep(source: &str) -> DependencyInput {
+        DependencyInput {
+            source: source.to_string(),
+            dependency_type: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_dependency_spec() {
+        let input = dep("uniprot:P01308@1.0");
+        assert_eq!(input.parse().unwrap(), ("uniprot", "P01308", "1.0"));
+
+        assert!(dep("uniprot:P01308").parse().is_err());
+        assert!(dep("P01308@1.0").parse().is_err());
+        assert!(dep("uniprot:@1.0").parse().is_err());
+        assert!(dep("uniprot:P01308@1.0@2.0").parse().is_err());
+    }
+
+    #[test]
+    fn test_validation_success() {
+        let cmd = command(vec![dep("uniprot:P01308@1.0")]);
+        assert!(cmd.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validation_empty_dependencies() {
+        let cmd = command(vec![]);
+        assert!(matches!(cmd.validate(), Err(AddDependenciesError::DependenciesRequired)));
+    }
+
+    #[test]
+    fn test_validation_invalid_dependency_type() {
+        let cmd = command(vec![DependencyInput {
+            source: "uniprot:P01308@1.0".to_string(),
+            dependency_type: Some("recommended".to_string()),
+        }]);
+        assert!(matches!(cmd.validate(), Err(AddDependenciesError::InvalidDependencyType(_))));
+    }
+
+    async fn insert_source(
+        pool: &PgPool,
+        org_id: Uuid,
+        slug: &str,
+        version: &str,
+    ) -> sqlx::Result<Uuid> {
+        let entry_id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO registry_entries (organization_id, slug, name, entry_type)
+            VALUES ($1, $2, $2, 'data_source')
+            RETURNING id
+            "#,
+            org_id,
+            slug
+        )
+        .fetch_one(pool)
+        .await?;
+
+        sqlx::query!("INSERT INTO data_sources (id, source_type) VALUES ($1, 'protein')", entry_id)
+            .execute(pool)
+            .await?;
+
+        sqlx::query!("INSERT INTO versions (entry_id, version) VALUES ($1, $2)", entry_id, version)
+            .execute(pool)
+            .await?;
+
+        Ok(entry_id)
+    }
+
+    #[sqlx::test]
+    async fn test_handle_adds_dependencies(pool: PgPool) -> sqlx::Result<()> {
+        let org_id = Uuid::new_v4();
+        sqlx::query!(
+            "INSERT INTO organizations (id, slug, name, is_system) VALUES ($1, $2, $3, $4)",
+            org_id,
+            "my-lab",
+            "My Lab",
+            false
+        )
+        .execute(&pool)
+        .await?;
+
+        insert_source(&pool, org_id, "filtered-proteome", "1.0").await?;
+        insert_source(&pool, org_id, "insulin", "2.0").await?;
+
+        let result = handle(pool.clone(), command(vec![dep("my-lab:insulin@2.0")]))
+            .await
+            .unwrap();
+        assert_eq!(result.dependencies_added, 1);
+        assert_eq!(result.dependency_count, 1);
+
+        // Re-adding the same dependency is idempotent
+        let result = handle(pool.clone(), command(vec![dep("my-lab:insulin@2.0")]))
+            .await
+            .unwrap();
+        assert_eq!(result.dependency_count, 1);
+
+        let missing = handle(pool.clone(), command(vec![dep("my-lab:insulin@9.9")])).await;
+        assert!(matches!(missing, Err(AddDependenciesError::DependencyNotFound(_))));
+
+        let self_dep =
+            handle(pool.clone(), command(vec![dep("my-lab:filtered-proteome@1.0")])).await;
+        assert!(matches!(self_dep, Err(AddDependenciesError::SelfDependency(_))));
+        Ok(())
+    }
+    #[sqlx::test]
+    async fn test_handle_sealed_version(pool: PgPool) -> sqlx::Result<()> {
+        let org_id = Uuid::new_v4();
+        sqlx::query!(
+            "INSERT INTO organizations (id, slug, name, is_system) VALUES ($1, $2, $3, $4)",
+            org_id,
+            "my-lab",
+            "My Lab",
+            false
+        )
+        .execute(&pool)
+        .await?;
+
+        let target = insert_source(&pool, org_id, "filtered-proteome", "1.0").await?;
+        let insulin = insert_source(&pool, org_id, "insulin", "2.0").await?;
+        sqlx::query!("INSERT INTO versions (entry_id, version) VALUES ($1, '3.0')", insulin)
+            .execute(&pool)
+            .await?;
+
+        handle(pool.clone(), command(vec![dep("my-lab:insulin@2.0")]))
+            .await
+            .unwrap();
+        sqlx::query!("UPDATE versions SET sealed_at = NOW() WHERE entry_id = $1", target)
+            .execute(&pool)
+            .await?;
+
+        // Re-recording the existing pin is a no-op
+        let result = handle(pool.clone(), command(vec![dep("my-lab:insulin@2.0")]))
+            .await
+            .unwrap();
+        assert_eq!(result.dependencies_added, 0);
+
+        let repin = handle(pool.clone(), command(vec![dep("my-lab:insulin@3.0")])).await;
+        assert!(matches!(repin, Err(AddDependenciesError::VersionSealed(_, _))));
+
+        let rejected = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!" FROM audit_log WHERE action = 'overwrite_rejected'"#
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(rejected, 1);
+
+        let mut overridden = command(vec![dep("my-lab:insulin@3.0")]);
+        overridden.override_reason = Some("pinned the wrong release".to_string());
+        let result = handle(pool.clone(), overridden).await.unwrap();
+        assert_eq!(result.dependencies_added, 1);
+        assert_eq!(result.dependency_count, 1);
+
+        let pinned = sqlx::query_scalar!(
+            "SELECT depends_on_version FROM dependencies WHERE version_id = $1",
+            result.version_id
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(pinned, "3.0");
+        Ok(())
+    }
+}
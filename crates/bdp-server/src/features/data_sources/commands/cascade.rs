@@ -0,0 +1,175 @@
+//! Cascade version command
+//!
+//! Publishes the cascade of a version's bump: every dependent, recursively,
+//! gets a new version with a changelog pointing at the upstream update.
+//! Dependents left out after reviewing the dry run keep their current
+//! version, and nothing is cascaded through them.
+
+use std::collections::HashSet;
+
+use mediator::Request;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::features::data_sources::queries::preview_cascade::{
+    load_cascade_source, validate_cascade_request, PreviewCascadeError, DEFAULT_CASCADE_DEPTH,
+};
+use crate::ingest::versioning::{cascade_recursive_excluding, CascadeResult};
+
+/// Command to cascade a version's bump to its dependents
+///
+/// Takes the same body as the dry run, so a reviewed preview can be
+/// published as is.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CascadeVersionCommand {
+    #[serde(default)]
+    pub organization_slug: String,
+    #[serde(default)]
+    pub data_source_slug: String,
+    #[serde(default)]
+    pub version: String,
+    /// Registry entry IDs of dependents to leave out
+    #[serde(default)]
+    pub exclude: Vec<Uuid>,
+    #[serde(default)]
+    pub max_depth: Option<usize>,
+}
+
+/// Versions created by a cascade
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CascadeVersionResponse {
+    pub source_version_id: Uuid,
+    pub version: String,
+    pub excluded: Vec<Uuid>,
+    pub cascaded: Vec<CascadeResult>,
+}
+
+pub type CascadeVersionError = PreviewCascadeError;
+
+impl Request<Result<CascadeVersionResponse, CascadeVersionError>> for CascadeVersionCommand {}
+
+impl crate::cqrs::middleware::Command for CascadeVersionCommand {}
+
+impl CascadeVersionCommand {
+    pub fn validate(&self) -> Result<(), CascadeVersionError> {
+        validate_cascade_request(
+            &self.organization_slug,
+            &self.data_source_slug,
+            &self.version,
+            self.max_depth,
+        )
+    }
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn handle(
+    pool: PgPool,
+    command: CascadeVersionCommand,
+) -> Result<CascadeVersionResponse, CascadeVersionError> {
+    command.validate()?;
+
+    let source = load_cascade_source(
+        &pool,
+        &command.organization_slug,
+        &command.data_source_slug,
+        &command.version,
+    )
+    .await?;
+
+    let excluded: HashSet<Uuid> = command.exclude.iter().copied().collect();
+    let cascaded = cascade_recursive_excluding(
+        &pool,
+        source.version_id,
+        &source.changelog,
+        command.max_depth.unwrap_or(DEFAULT_CASCADE_DEPTH),
+        &excluded,
+    )
+    .await
+    .map_err(|e| PreviewCascadeError::Cascade(e.to_string()))?;
+
+    Ok(CascadeVersionResponse {
+        source_version_id: source.version_id,
+        version: command.version,
+        excluded: command.exclude,
+        cascaded,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::shared::test_helpers::{TestDataSource, TestOrganization, TestVersion};
+    use crate::ingest::versioning::ChangelogSummary;
+
+    #[sqlx::test]
+    async fn test_handle_skips_excluded_dependents(pool: PgPool) -> sqlx::Result<()> {
+        let org = TestOrganization::new("ncbi", "NCBI").insert(&pool).await?;
+        let taxonomy = TestDataSource::new(&org, "taxonomy", "Taxonomy")
+            .insert(&pool)
+            .await?;
+        let source = TestVersion::new(&taxonomy, "1.2.0").insert(&pool).await?;
+
+        let mut dependents = Vec::new();
+        for slug in ["genomes", "proteomes"] {
+            let ds = TestDataSource::new(&org, slug, slug).insert(&pool).await?;
+            let version = TestVersion::new(&ds, "1.0.0").insert(&pool).await?;
+            sqlx::query!(
+                r#"
+                INSERT INTO dependencies (version_id, depends_on_entry_id, depends_on_version)
+                VALUES ($1, $2, '1.2.0')
+                "#,
+                version.id,
+                taxonomy.id
+            )
+            .execute(&pool)
+            .await?;
+            dependents.push(ds);
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO version_changelogs (version_id, bump_type, entries, summary, summary_text)
+            VALUES ($1, 'minor', '[]', $2, 'New taxa')
+            "#,
+            source.id,
+            serde_json::to_value(ChangelogSummary::default()).unwrap()
+        )
+        .execute(&pool)
+        .await?;
+
+        let response = handle(
+            pool.clone(),
+            CascadeVersionCommand {
+                organization_slug: "ncbi".to_string(),
+                data_source_slug: "taxonomy".to_string(),
+                version: "1.2.0".to_string(),
+                exclude: vec![dependents[0].id],
+                max_depth: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.cascaded.len(), 1);
+        assert_eq!(response.cascaded[0].entry_slug, "proteomes");
+        assert_eq!(response.cascaded[0].new_version, "1.1");
+
+        let genomes_versions = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!" FROM versions WHERE entry_id = $1"#,
+            dependents[0].id
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(genomes_versions, 1);
+
+        let triggered_by = sqlx::query_scalar!(
+            "SELECT triggered_by_version_id FROM version_changelogs WHERE id = $1",
+            response.cascaded[0].changelog_id
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(triggered_by, Some(source.id));
+        Ok(())
+    }
+}
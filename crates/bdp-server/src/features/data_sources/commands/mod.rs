@@ -1,9 +1,19 @@
+pub mod add_dependencies;
+pub mod cascade;
 pub mod create;
 pub mod delete;
 pub mod publish;
+pub mod unyank;
 pub mod update;
+pub mod yank;
 
+pub use add_dependencies::{
+    AddDependenciesCommand, AddDependenciesError, AddDependenciesResponse, DependencyInput,
+};
+pub use cascade::{CascadeVersionCommand, CascadeVersionError, CascadeVersionResponse};
 pub use create::{CreateDataSourceCommand, CreateDataSourceError, CreateDataSourceResponse};
 pub use delete::{DeleteDataSourceCommand, DeleteDataSourceError, DeleteDataSourceResponse};
 pub use publish::{PublishVersionCommand, PublishVersionError, PublishVersionResponse};
+pub use unyank::{UnyankVersionCommand, UnyankVersionError};
 pub use update::{UpdateDataSourceCommand, UpdateDataSourceError, UpdateDataSourceResponse};
+pub use yank::{YankVersionCommand, YankVersionError, YankVersionResponse};
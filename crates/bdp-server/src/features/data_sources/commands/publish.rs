@@ -4,6 +4,11 @@ use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use uuid::Uuid;
 
+use super::add_dependencies::{self, AddDependenciesError, DependencyInput};
+use crate::features::version_files::commands::add_batch::{
+    self, AddVersionFilesError, VersionFileInput,
+};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PublishVersionCommand {
     pub data_source_id: Uuid,
@@ -16,6 +21,14 @@ pub struct PublishVersionCommand {
     pub size_bytes: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub additional_metadata: Option<serde_json::Value>,
+    /// Files registered with the version in the same transaction, so a
+    /// version is never published without them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<VersionFileInput>,
+    /// Dependencies recorded with the version in the same transaction, so
+    /// they are pinned before the version is sealed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<DependencyInput>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,6 +58,10 @@ pub enum PublishVersionError {
     DuplicateVersion(Uuid, String),
     #[error("Size bytes must be non-negative")]
     InvalidSize,
+    #[error("Invalid file: {0}")]
+    InvalidFile(AddVersionFilesError),
+    #[error("Invalid dependency: {0}")]
+    InvalidDependency(AddDependenciesError),
     #[error("Database error: {0}")]
     Database(#[from] sqlx::Error),
 }
@@ -66,6 +83,9 @@ impl PublishVersionCommand {
                 return Err(PublishVersionError::InvalidSize);
             }
         }
+        add_batch::validate_files(&self.files).map_err(PublishVersionError::InvalidFile)?;
+        add_dependencies::validate_dependencies(&self.dependencies)
+            .map_err(PublishVersionError::InvalidDependency)?;
         Ok(())
     }
 }
@@ -95,6 +115,8 @@ pub async fn handle(
         return Err(PublishVersionError::DataSourceNotFound(command.data_source_id));
     }
 
+    let mut tx = pool.begin().await?;
+
     let result = sqlx::query_as!(
         VersionRecord,
         r#"
@@ -114,7 +136,7 @@ pub async fn handle(
         command.size_bytes,
         command.additional_metadata
     )
-    .fetch_one(&pool)
+    .fetch_one(&mut *tx)
     .await
     .map_err(|e| {
         if let sqlx::Error::Database(ref db_err) = e {
@@ -128,6 +150,17 @@ pub async fn handle(
         PublishVersionError::Database(e)
     })?;
 
+    let dependencies =
+        add_dependencies::resolve_dependencies(&mut tx, result.entry_id, &command.dependencies)
+            .await
+            .map_err(|e| match e {
+                AddDependenciesError::Database(e) => PublishVersionError::Database(e),
+                e => PublishVersionError::InvalidDependency(e),
+            })?;
+    add_dependencies::insert_dependencies(&mut tx, result.id, &dependencies).await?;
+    add_batch::insert_files(&mut tx, result.id, &command.files).await?;
+    tx.commit().await?;
+
     Ok(PublishVersionResponse {
         id: result.id,
         entry_id: result.entry_id,
@@ -167,6 +200,8 @@ mod tests {
             release_date: Some(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()),
             size_bytes: Some(1024),
             additional_metadata: None,
+            files: Vec::new(),
+            dependencies: Vec::new(),
         };
         assert!(cmd.validate().is_ok());
     }
@@ -180,6 +215,8 @@ mod tests {
             release_date: None,
             size_bytes: None,
             additional_metadata: None,
+            files: Vec::new(),
+            dependencies: Vec::new(),
         };
         assert!(matches!(cmd.validate(), Err(PublishVersionError::VersionRequired)));
     }
@@ -193,6 +230,8 @@ mod tests {
             release_date: None,
             size_bytes: Some(-100),
             additional_metadata: None,
+            files: Vec::new(),
+            dependencies: Vec::new(),
         };
         assert!(matches!(cmd.validate(), Err(PublishVersionError::InvalidSize)));
     }
@@ -238,6 +277,8 @@ mod tests {
             release_date: Some(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()),
             size_bytes: Some(2048),
             additional_metadata: None,
+            files: Vec::new(),
+            dependencies: Vec::new(),
         };
 
         let result = handle(pool.clone(), cmd).await;
@@ -249,6 +290,147 @@ mod tests {
         Ok(())
     }
 
+    #[sqlx::test]
+    async fn test_handle_publishes_version_with_files_and_dependencies(
+        pool: PgPool,
+    ) -> sqlx::Result<()> {
+        let org_id = Uuid::new_v4();
+        sqlx::query!(
+            "INSERT INTO organizations (id, slug, name, is_system) VALUES ($1, $2, $3, $4)",
+            org_id,
+            "test-org",
+            "Test Org",
+            false
+        )
+        .execute(&pool)
+        .await?;
+
+        let entry_id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO registry_entries (organization_id, slug, name, entry_type)
+            VALUES ($1, $2, $3, 'data_source')
+            RETURNING id
+            "#,
+            org_id,
+            "test-protein",
+            "Test Protein"
+        )
+        .fetch_one(&pool)
+        .await?;
+
+        sqlx::query!(
+            "INSERT INTO data_sources (id, source_type) VALUES ($1, $2)",
+            entry_id,
+            "protein"
+        )
+        .execute(&pool)
+        .await?;
+
+        let file = VersionFileInput {
+            format: "fasta".to_string(),
+            s3_key: "test-org/test-protein/1.0/proteins.fasta".to_string(),
+            checksum: "a".repeat(64),
+            size_bytes: 2048,
+            compression: None,
+            md5: None,
+        };
+
+        // An invalid file is refused before anything is written
+        let invalid = PublishVersionCommand {
+            data_source_id: entry_id,
+            version: "1.0".to_string(),
+            external_version: None,
+            release_date: None,
+            size_bytes: None,
+            additional_metadata: None,
+            files: vec![file.clone(), file.clone()],
+            dependencies: Vec::new(),
+        };
+        let result = handle(pool.clone(), invalid).await;
+        assert!(matches!(result, Err(PublishVersionError::InvalidFile(_))));
+
+        let dependency_id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO registry_entries (organization_id, slug, name, entry_type)
+            VALUES ($1, 'uniprot-reference', 'UniProt Reference', 'data_source')
+            RETURNING id
+            "#,
+            org_id
+        )
+        .fetch_one(&pool)
+        .await?;
+        sqlx::query!(
+            "INSERT INTO data_sources (id, source_type) VALUES ($1, 'protein')",
+            dependency_id
+        )
+        .execute(&pool)
+        .await?;
+        sqlx::query!("INSERT INTO versions (entry_id, version) VALUES ($1, '2.0')", dependency_id)
+            .execute(&pool)
+            .await?;
+
+        let dependency = |source: &str| DependencyInput {
+            source: source.to_string(),
+            dependency_type: None,
+        };
+
+        // An unknown dependency rolls back the whole publish
+        let missing = PublishVersionCommand {
+            data_source_id: entry_id,
+            version: "1.0".to_string(),
+            external_version: None,
+            release_date: None,
+            size_bytes: None,
+            additional_metadata: None,
+            files: vec![file.clone()],
+            dependencies: vec![dependency("test-org:uniprot-reference@9.9")],
+        };
+        let result = handle(pool.clone(), missing).await;
+        assert!(matches!(
+            result,
+            Err(PublishVersionError::InvalidDependency(
+                AddDependenciesError::DependencyNotFound(_)
+            ))
+        ));
+
+        let cmd = PublishVersionCommand {
+            data_source_id: entry_id,
+            version: "1.0".to_string(),
+            external_version: None,
+            release_date: None,
+            size_bytes: Some(2048),
+            additional_metadata: None,
+            files: vec![file],
+            dependencies: vec![dependency("test-org:uniprot-reference@2.0")],
+        };
+        let response = handle(pool.clone(), cmd).await.unwrap();
+
+        let pinned = sqlx::query_scalar!(
+            "SELECT depends_on_version FROM dependencies WHERE version_id = $1",
+            response.id
+        )
+        .fetch_all(&pool)
+        .await?;
+        assert_eq!(pinned, vec!["2.0".to_string()]);
+
+        let sealed = sqlx::query_scalar!(
+            "SELECT sealed_at IS NOT NULL AS \"sealed!\" FROM versions WHERE id = $1",
+            response.id
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert!(sealed);
+
+        let formats = sqlx::query_scalar!(
+            "SELECT format FROM version_files WHERE version_id = $1",
+            response.id
+        )
+        .fetch_all(&pool)
+        .await?;
+        assert_eq!(formats, vec!["fasta".to_string()]);
+        Ok(())
+    }
+
     #[sqlx::test]
     async fn test_handle_duplicate_version(pool: PgPool) -> sqlx::Result<()> {
         let org_id = Uuid::new_v4();
@@ -290,6 +472,8 @@ mod tests {
             release_date: None,
             size_bytes: None,
             additional_metadata: None,
+            files: Vec::new(),
+            dependencies: Vec::new(),
         };
         let _ = handle(pool.clone(), cmd1).await.unwrap();
 
@@ -300,6 +484,8 @@ mod tests {
             release_date: None,
             size_bytes: None,
             additional_metadata: None,
+            files: Vec::new(),
+            dependencies: Vec::new(),
         };
         let result = handle(pool.clone(), cmd2).await;
         assert!(matches!(result, Err(PublishVersionError::DuplicateVersion(_, _))));
@@ -315,6 +501,8 @@ mod tests {
             release_date: None,
             size_bytes: None,
             additional_metadata: None,
+            files: Vec::new(),
+            dependencies: Vec::new(),
         };
 
         let result = handle(pool.clone(), cmd).await;
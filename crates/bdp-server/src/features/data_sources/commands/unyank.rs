@@ -0,0 +1,106 @@
+//! Unyank version command
+//!
+//! Reverses a yank so the version is resolvable again.
+
+use mediator::Request;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+
+use super::yank::YankVersionResponse;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnyankVersionCommand {
+    pub organization_slug: String,
+    pub data_source_slug: String,
+    pub version: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum UnyankVersionError {
+    #[error("Organization slug is required and cannot be empty")]
+    OrganizationSlugRequired,
+    #[error("Data source slug is required and cannot be empty")]
+    DataSourceSlugRequired,
+    #[error("Version is required and cannot be empty")]
+    VersionRequired,
+    #[error("Version '{2}' for data source '{0}/{1}' not found")]
+    NotFound(String, String, String),
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl Request<Result<YankVersionResponse, UnyankVersionError>> for UnyankVersionCommand {}
+
+impl crate::cqrs::middleware::Command for UnyankVersionCommand {}
+
+impl UnyankVersionCommand {
+    pub fn validate(&self) -> Result<(), UnyankVersionError> {
+        if self.organization_slug.is_empty() {
+            return Err(UnyankVersionError::OrganizationSlugRequired);
+        }
+        if self.data_source_slug.is_empty() {
+            return Err(UnyankVersionError::DataSourceSlugRequired);
+        }
+        if self.version.is_empty() {
+            return Err(UnyankVersionError::VersionRequired);
+        }
+        Ok(())
+    }
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn handle(
+    pool: PgPool,
+    command: UnyankVersionCommand,
+) -> Result<YankVersionResponse, UnyankVersionError> {
+    command.validate()?;
+
+    let result = sqlx::query!(
+        r#"
+        UPDATE versions v
+        SET yanked_at = NULL,
+            yanked_reason = NULL,
+            updated_at = NOW()
+        FROM registry_entries re
+        JOIN organizations o ON re.organization_id = o.id
+        WHERE v.entry_id = re.id
+          AND LOWER(o.slug) = LOWER($1) AND LOWER(re.slug) = LOWER($2) AND v.version = $3
+        RETURNING v.id, v.version
+        "#,
+        command.organization_slug,
+        command.data_source_slug,
+        command.version
+    )
+    .fetch_optional(&pool)
+    .await?
+    .ok_or_else(|| {
+        UnyankVersionError::NotFound(
+            command.organization_slug.clone(),
+            command.data_source_slug.clone(),
+            command.version.clone(),
+        )
+    })?;
+
+    Ok(YankVersionResponse {
+        version_id: result.id,
+        version: result.version,
+        yanked: false,
+        yanked_at: None,
+        yanked_reason: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation() {
+        let cmd = UnyankVersionCommand {
+            organization_slug: "my-lab".to_string(),
+            data_source_slug: "proteome".to_string(),
+            version: String::new(),
+        };
+        assert!(matches!(cmd.validate(), Err(UnyankVersionError::VersionRequired)));
+    }
+}
@@ -0,0 +1,198 @@
+//! Yank version command
+//!
+//! Retracts a published version without deleting it. Yanked versions keep
+//! their files, so existing lockfiles can still download them, but they are
+//! no longer resolved for new specs or reported as the latest version.
+
+use chrono::{DateTime, Utc};
+use mediator::Request;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Command to yank a published version
+///
+/// The path segments (`organization_slug`, `data_source_slug`, `version`) are
+/// filled in from the route; the request body only carries `reason`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YankVersionCommand {
+    #[serde(default)]
+    pub organization_slug: String,
+    #[serde(default)]
+    pub data_source_slug: String,
+    #[serde(default)]
+    pub version: String,
+    pub reason: String,
+}
+
+/// Yank state of a version after a yank or unyank
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YankVersionResponse {
+    pub version_id: Uuid,
+    pub version: String,
+    pub yanked: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub yanked_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub yanked_reason: Option<String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum YankVersionError {
+    #[error("Organization slug is required and cannot be empty")]
+    OrganizationSlugRequired,
+    #[error("Data source slug is required and cannot be empty")]
+    DataSourceSlugRequired,
+    #[error("Version is required and cannot be empty")]
+    VersionRequired,
+    #[error("A reason is required when yanking a version")]
+    ReasonRequired,
+    #[error("Reason must not exceed 1000 characters")]
+    ReasonTooLong,
+    #[error("Version '{2}' for data source '{0}/{1}' not found")]
+    NotFound(String, String, String),
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl Request<Result<YankVersionResponse, YankVersionError>> for YankVersionCommand {}
+
+impl crate::cqrs::middleware::Command for YankVersionCommand {}
+
+impl YankVersionCommand {
+    pub fn validate(&self) -> Result<(), YankVersionError> {
+        if self.organization_slug.is_empty() {
+            return Err(YankVersionError::OrganizationSlugRequired);
+        }
+        if self.data_source_slug.is_empty() {
+            return Err(YankVersionError::DataSourceSlugRequired);
+        }
+        if self.version.is_empty() {
+            return Err(YankVersionError::VersionRequired);
+        }
+        if self.reason.trim().is_empty() {
+            return Err(YankVersionError::ReasonRequired);
+        }
+        if self.reason.len() > 1000 {
+            return Err(YankVersionError::ReasonTooLong);
+        }
+        Ok(())
+    }
+}
+
+/// Handles the yank version command
+///
+/// Yanking an already yanked version updates the reason but keeps the
+/// original `yanked_at` timestamp.
+#[tracing::instrument(skip(pool))]
+pub async fn handle(
+    pool: PgPool,
+    command: YankVersionCommand,
+) -> Result<YankVersionResponse, YankVersionError> {
+    command.validate()?;
+
+    let result = sqlx::query!(
+        r#"
+        UPDATE versions v
+        SET yanked_at = COALESCE(v.yanked_at, NOW()),
+            yanked_reason = $4,
+            updated_at = NOW()
+        FROM registry_entries re
+        JOIN organizations o ON re.organization_id = o.id
+        WHERE v.entry_id = re.id
+          AND LOWER(o.slug) = LOWER($1) AND LOWER(re.slug) = LOWER($2) AND v.version = $3
+        RETURNING v.id, v.version, v.yanked_at, v.yanked_reason
+        "#,
+        command.organization_slug,
+        command.data_source_slug,
+        command.version,
+        command.reason.trim()
+    )
+    .fetch_optional(&pool)
+    .await?
+    .ok_or_else(|| {
+        YankVersionError::NotFound(
+            command.organization_slug.clone(),
+            command.data_source_slug.clone(),
+            command.version.clone(),
+        )
+    })?;
+
+    Ok(YankVersionResponse {
+        version_id: result.id,
+        version: result.version,
+        yanked: true,
+        yanked_at: result.yanked_at,
+        yanked_reason: result.yanked_reason,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(reason: &str) -> YankVersionCommand {
+        YankVersionCommand {
+            organization_slug: "my-lab".to_string(),
+            data_source_slug: "proteome".to_string(),
+            version: "1.0".to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(command("corrupt FASTA file").validate().is_ok());
+        assert!(matches!(command("  ").validate(), Err(YankVersionError::ReasonRequired)));
+        assert!(matches!(
+            command(&"x".repeat(1001)).validate(),
+            Err(YankVersionError::ReasonTooLong)
+        ));
+    }
+
+    #[sqlx::test]
+    async fn test_handle_yanks_version(pool: PgPool) -> sqlx::Result<()> {
+        let org_id = sqlx::query_scalar!(
+            "INSERT INTO organizations (slug, name, is_system) VALUES ('my-lab', 'My Lab', false) RETURNING id"
+        )
+        .fetch_one(&pool)
+        .await?;
+        let entry_id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO registry_entries (organization_id, slug, name, entry_type)
+            VALUES ($1, 'proteome', 'Proteome', 'data_source')
+            RETURNING id
+            "#,
+            org_id
+        )
+        .fetch_one(&pool)
+        .await?;
+        sqlx::query!("INSERT INTO data_sources (id, source_type) VALUES ($1, 'protein')", entry_id)
+            .execute(&pool)
+            .await?;
+        sqlx::query!("INSERT INTO versions (entry_id, version) VALUES ($1, '1.0')", entry_id)
+            .execute(&pool)
+            .await?;
+
+        let first = handle(pool.clone(), command("wrong organism"))
+            .await
+            .unwrap();
+        assert!(first.yanked);
+        assert_eq!(first.yanked_reason.as_deref(), Some("wrong organism"));
+
+        // Re-yanking keeps the original timestamp
+        let second = handle(pool.clone(), command("wrong organism, see #12"))
+            .await
+            .unwrap();
+        assert_eq!(second.yanked_at, first.yanked_at);
+        assert_eq!(second.yanked_reason.as_deref(), Some("wrong organism, see #12"));
+
+        let mut missing = command("x");
+        missing.version = "9.9".to_string();
+        assert!(matches!(
+            handle(pool.clone(), missing).await,
+            Err(YankVersionError::NotFound(..))
+        ));
+        Ok(())
+    }
+}
@@ -4,18 +4,24 @@ pub mod routes;
 pub mod types;
 
 pub use commands::{
-    CreateDataSourceCommand, CreateDataSourceError, CreateDataSourceResponse,
-    DeleteDataSourceCommand, DeleteDataSourceError, DeleteDataSourceResponse,
-    PublishVersionCommand, PublishVersionError, PublishVersionResponse, UpdateDataSourceCommand,
-    UpdateDataSourceError, UpdateDataSourceResponse,
+    AddDependenciesCommand, AddDependenciesError, AddDependenciesResponse, CascadeVersionCommand,
+    CascadeVersionError, CascadeVersionResponse, CreateDataSourceCommand, CreateDataSourceError,
+    CreateDataSourceResponse, DeleteDataSourceCommand, DeleteDataSourceError,
+    DeleteDataSourceResponse, PublishVersionCommand, PublishVersionError, PublishVersionResponse,
+    UnyankVersionCommand, UnyankVersionError, UpdateDataSourceCommand, UpdateDataSourceError,
+    UpdateDataSourceResponse, YankVersionCommand, YankVersionError, YankVersionResponse,
 };
 
 pub use queries::{
-    CitationInfo, DataSourceListItem, DependencyItem, FileInfo, GetDataSourceError,
-    GetDataSourceQuery, GetDataSourceResponse, GetVersionError, GetVersionQuery,
+    CitationInfo, DataSourceListItem, DependencyGraphError, DependencyGraphQuery,
+    DependencyGraphResponse, DependencyItem, DiffVersionsError, DiffVersionsQuery,
+    DiffVersionsResponse, FileInfo, GetDataSourceError, GetDataSourceQuery, GetDataSourceResponse,
+    GetVersionError, GetVersionMappingError, GetVersionMappingQuery, GetVersionQuery,
     GetVersionResponse, ListDataSourcesError, ListDataSourcesQuery, ListDataSourcesResponse,
-    ListDependenciesError, ListDependenciesQuery, ListDependenciesResponse, OrganismInfo,
-    OrganizationInfo, PaginationMetadata, VersionInfo,
+    ListDependenciesError, ListDependenciesQuery, ListDependenciesResponse,
+    ListVersionMappingsError, ListVersionMappingsQuery, OrganismInfo, OrganizationInfo,
+    PaginationMetadata, PreviewCascadeError, PreviewCascadeQuery, PreviewCascadeResponse,
+    VersionInfo,
 };
 
 pub use routes::data_sources_routes;
@@ -0,0 +1,587 @@
+//! Dependency graph query
+//!
+//! Walks the dependencies of a data source version to a chosen depth, either
+//! forward (what the version is built on) or in reverse (which versions are
+//! built on it). The graph can be returned as JSON or rendered as Graphviz
+//! DOT or Mermaid.
+//!
+//! A reverse edge exists when a version depends on the root's data source at
+//! the root's version or upstream release; forward edges follow the required
+//! version of each dependency to its stored version where there is one.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+use mediator::Request;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Depth used when a request does not set `depth`
+pub const DEFAULT_GRAPH_DEPTH: usize = 3;
+
+/// Deepest graph a request may ask for
+pub const MAX_GRAPH_DEPTH: usize = 10;
+
+/// Nodes returned before the walk stops and the graph is marked truncated
+pub const MAX_GRAPH_NODES: usize = 500;
+
+/// Edges returned before the walk stops and the graph is marked truncated
+pub const MAX_GRAPH_EDGES: usize = 2000;
+
+/// Which way to follow dependency edges from the root
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphDirection {
+    /// Dependencies of the root, and theirs
+    #[default]
+    Forward,
+    /// Versions that depend on the root, and theirs
+    Reverse,
+}
+
+/// Representation of the graph returned by the endpoint
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphFormat {
+    #[default]
+    Json,
+    Dot,
+    Mermaid,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DependencyGraphQuery {
+    #[serde(default)]
+    pub organization_slug: String,
+    #[serde(default)]
+    pub data_source_slug: String,
+    #[serde(default)]
+    pub version: String,
+    #[serde(default)]
+    pub direction: GraphDirection,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depth: Option<usize>,
+    #[serde(default)]
+    pub format: GraphFormat,
+}
+
+/// A data source version in the graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphNode {
+    /// `org:slug@version`, unique within the graph
+    pub id: String,
+    pub organization_slug: String,
+    pub entry_slug: String,
+    pub entry_name: String,
+    pub version: String,
+    /// Distance from the root
+    pub depth: usize,
+    /// Whether the version exists in the registry; forward edges can point at
+    /// required versions that were never ingested
+    pub resolved: bool,
+}
+
+/// A dependency, always pointing from the dependent to its dependency
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphEdge {
+    pub from: String,
+    pub to: String,
+    pub required_version: String,
+    pub dependency_type: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyGraphResponse {
+    pub root: String,
+    pub direction: GraphDirection,
+    pub depth: usize,
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+    /// Set when the walk stopped at [`MAX_GRAPH_NODES`] or [`MAX_GRAPH_EDGES`]
+    pub truncated: bool,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DependencyGraphError {
+    #[error("Organization slug is required and cannot be empty")]
+    OrganizationSlugRequired,
+    #[error("Data source slug is required and cannot be empty")]
+    DataSourceSlugRequired,
+    #[error("Version is required and cannot be empty")]
+    VersionRequired,
+    #[error("Depth must be between 1 and {MAX_GRAPH_DEPTH}")]
+    InvalidDepth,
+    #[error("Version '{2}' for data source '{0}/{1}' not found")]
+    NotFound(String, String, String),
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl Request<Result<DependencyGraphResponse, DependencyGraphError>> for DependencyGraphQuery {}
+
+impl crate::cqrs::middleware::Query for DependencyGraphQuery {}
+
+impl DependencyGraphQuery {
+    pub fn validate(&self) -> Result<(), DependencyGraphError> {
+        if self.organization_slug.is_empty() {
+            return Err(DependencyGraphError::OrganizationSlugRequired);
+        }
+        if self.data_source_slug.is_empty() {
+            return Err(DependencyGraphError::DataSourceSlugRequired);
+        }
+        if self.version.is_empty() {
+            return Err(DependencyGraphError::VersionRequired);
+        }
+        if let Some(depth) = self.depth {
+            if depth == 0 || depth > MAX_GRAPH_DEPTH {
+                return Err(DependencyGraphError::InvalidDepth);
+            }
+        }
+        Ok(())
+    }
+
+    fn depth(&self) -> usize {
+        self.depth.unwrap_or(DEFAULT_GRAPH_DEPTH)
+    }
+}
+
+fn node_id(organization_slug: &str, entry_slug: &str, version: &str) -> String {
+    format!("{}:{}@{}", organization_slug, entry_slug, version)
+}
+
+impl DependencyGraphResponse {
+    /// Render as a Graphviz digraph
+    pub fn to_dot(&self) -> String {
+        let escape = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+        let quote = |s: &str| format!("\"{}\"", escape(s));
+
+        let mut out = String::new();
+        let _ = writeln!(out, "digraph {} {{", quote(&self.root));
+        let _ = writeln!(out, "  rankdir=LR;");
+        let _ = writeln!(out, "  node [shape=box];");
+        for node in &self.nodes {
+            let mut attrs =
+                format!("label=\"{}\\n{}\"", escape(&node.id), escape(&node.entry_name));
+            if node.id == self.root {
+                attrs.push_str(", style=bold");
+            }
+            if !node.resolved {
+                attrs.push_str(", style=dashed");
+            }
+            let _ = writeln!(out, "  {} [{}];", quote(&node.id), attrs);
+        }
+        for edge in &self.edges {
+            let style = if edge.dependency_type == "optional" {
+                " [style=dashed]"
+            } else {
+                ""
+            };
+            let _ = writeln!(out, "  {} -> {}{};", quote(&edge.from), quote(&edge.to), style);
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Render as a Mermaid flowchart
+    ///
+    /// Mermaid node IDs cannot contain `:` or `@`, so nodes are numbered and
+    /// labelled with their `org:slug@version`.
+    pub fn to_mermaid(&self) -> String {
+        let ids: HashMap<&str, String> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.id.as_str(), format!("n{}", i)))
+            .collect();
+
+        let mut out = String::from("graph LR\n");
+        for node in &self.nodes {
+            let label = node.id.replace('"', "#quot;");
+            let _ = writeln!(out, "  {}[\"{}\"]", ids[node.id.as_str()], label);
+        }
+        for edge in &self.edges {
+            let arrow = if edge.dependency_type == "optional" {
+                "-.->"
+            } else {
+                "-->"
+            };
+            let _ =
+                writeln!(out, "  {} {} {}", ids[edge.from.as_str()], arrow, ids[edge.to.as_str()]);
+        }
+        out
+    }
+}
+
+/// One dependency edge found while expanding a level of the graph
+#[derive(Debug)]
+struct EdgeRecord {
+    /// Version on the frontier the edge was found from
+    frontier_id: Uuid,
+    /// Version at the other end, if it exists in the registry
+    version_id: Option<Uuid>,
+    version: String,
+    organization_slug: String,
+    entry_slug: String,
+    entry_name: String,
+    required_version: String,
+    dependency_type: String,
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn handle(
+    pool: PgPool,
+    query: DependencyGraphQuery,
+) -> Result<DependencyGraphResponse, DependencyGraphError> {
+    query.validate()?;
+
+    let root = sqlx::query!(
+        r#"
+        SELECT v.id, o.slug AS organization_slug, re.slug AS entry_slug, re.name AS entry_name,
+               v.version
+        FROM versions v
+        JOIN registry_entries re ON v.entry_id = re.id
+        JOIN organizations o ON re.organization_id = o.id
+        WHERE LOWER(o.slug) = LOWER($1) AND LOWER(re.slug) = LOWER($2) AND v.version = $3
+        "#,
+        query.organization_slug,
+        query.data_source_slug,
+        query.version
+    )
+    .fetch_optional(&pool)
+    .await?
+    .ok_or_else(|| {
+        DependencyGraphError::NotFound(
+            query.organization_slug.clone(),
+            query.data_source_slug.clone(),
+            query.version.clone(),
+        )
+    })?;
+
+    let root_key = node_id(&root.organization_slug, &root.entry_slug, &root.version);
+    let mut keys: HashMap<Uuid, String> = HashMap::from([(root.id, root_key.clone())]);
+    let mut seen: HashSet<String> = HashSet::from([root_key.clone()]);
+    let mut nodes = vec![GraphNode {
+        id: root_key.clone(),
+        organization_slug: root.organization_slug,
+        entry_slug: root.entry_slug,
+        entry_name: root.entry_name,
+        version: root.version,
+        depth: 0,
+        resolved: true,
+    }];
+    let mut edges = Vec::new();
+    let mut truncated = false;
+
+    let mut frontier = vec![root.id];
+    for depth in 1..=query.depth() {
+        if frontier.is_empty() || truncated {
+            break;
+        }
+
+        // One row past the remaining budget tells us the graph is cut short
+        let budget = MAX_GRAPH_EDGES - edges.len();
+        let limit = (budget + 1) as i64;
+        let mut records = match query.direction {
+            GraphDirection::Forward => {
+                sqlx::query_as!(
+                    EdgeRecord,
+                    r#"
+                    SELECT
+                        d.version_id AS frontier_id,
+                        dv.id AS "version_id?",
+                        COALESCE(dv.version, d.depends_on_version) AS "version!",
+                        o.slug AS organization_slug,
+                        re.slug AS entry_slug,
+                        re.name AS entry_name,
+                        d.depends_on_version AS required_version,
+                        COALESCE(d.dependency_type, 'required') AS "dependency_type!"
+                    FROM dependencies d
+                    JOIN registry_entries re ON re.id = d.depends_on_entry_id
+                    JOIN organizations o ON o.id = re.organization_id
+                    LEFT JOIN LATERAL (
+                        SELECT v.id, v.version
+                        FROM versions v
+                        WHERE v.entry_id = re.id
+                          AND d.depends_on_version IN (v.version, v.external_version)
+                        ORDER BY (v.version = d.depends_on_version) DESC
+                        LIMIT 1
+                    ) dv ON true
+                    WHERE d.version_id = ANY($1)
+                    ORDER BY o.slug, re.slug
+                    LIMIT $2
+                    "#,
+                    &frontier,
+                    limit
+                )
+                .fetch_all(&pool)
+                .await?
+            },
+            GraphDirection::Reverse => {
+                sqlx::query_as!(
+                    EdgeRecord,
+                    r#"
+                    SELECT
+                        t.id AS frontier_id,
+                        v.id AS "version_id?",
+                        v.version,
+                        o.slug AS organization_slug,
+                        re.slug AS entry_slug,
+                        re.name AS entry_name,
+                        d.depends_on_version AS required_version,
+                        COALESCE(d.dependency_type, 'required') AS "dependency_type!"
+                    FROM versions t
+                    JOIN dependencies d
+                        ON d.depends_on_entry_id = t.entry_id
+                       AND d.depends_on_version IN (t.version, t.external_version)
+                    JOIN versions v ON v.id = d.version_id
+                    JOIN registry_entries re ON re.id = v.entry_id
+                    JOIN organizations o ON o.id = re.organization_id
+                    WHERE t.id = ANY($1)
+                    ORDER BY o.slug, re.slug, v.version_major, v.version_minor, v.version_patch
+                    LIMIT $2
+                    "#,
+                    &frontier,
+                    limit
+                )
+                .fetch_all(&pool)
+                .await?
+            },
+        };
+        if records.len() > budget {
+            records.truncate(budget);
+            truncated = true;
+        }
+
+        let mut next = Vec::new();
+        for record in records {
+            let key = node_id(&record.organization_slug, &record.entry_slug, &record.version);
+            let frontier_key = keys[&record.frontier_id].clone();
+
+            if seen.insert(key.clone()) {
+                if nodes.len() >= MAX_GRAPH_NODES {
+                    seen.remove(&key);
+                    truncated = true;
+                    break;
+                }
+                nodes.push(GraphNode {
+                    id: key.clone(),
+                    organization_slug: record.organization_slug,
+                    entry_slug: record.entry_slug,
+                    entry_name: record.entry_name,
+                    version: record.version,
+                    depth,
+                    resolved: record.version_id.is_some(),
+                });
+                if let Some(version_id) = record.version_id {
+                    keys.insert(version_id, key.clone());
+                    next.push(version_id);
+                }
+            }
+
+            let (from, to) = match query.direction {
+                GraphDirection::Forward => (frontier_key, key),
+                GraphDirection::Reverse => (key, frontier_key),
+            };
+            edges.push(GraphEdge {
+                from,
+                to,
+                required_version: record.required_version,
+                dependency_type: record.dependency_type,
+            });
+        }
+
+        frontier = next;
+    }
+
+    Ok(DependencyGraphResponse {
+        root: root_key,
+        direction: query.direction,
+        depth: query.depth(),
+        nodes,
+        edges,
+        truncated,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::shared::test_helpers::{TestDataSource, TestOrganization, TestVersion};
+
+    /// ncbi:taxonomy@1.0 <- uniprot:swissprot@2.0 <- uniprot:all@2.0
+    ///                   <- ensembl:genes@4.0 (optional)
+    async fn setup_graph(pool: &PgPool) -> sqlx::Result<()> {
+        let ncbi = TestOrganization::new("ncbi", "NCBI").insert(pool).await?;
+        let uniprot = TestOrganization::new("uniprot", "UniProt")
+            .insert(pool)
+            .await?;
+        let ensembl = TestOrganization::new("ensembl", "Ensembl")
+            .insert(pool)
+            .await?;
+
+        let taxonomy = TestDataSource::new(&ncbi, "taxonomy", "Taxonomy")
+            .insert(pool)
+            .await?;
+        let swissprot = TestDataSource::new(&uniprot, "swissprot", "Swiss-Prot")
+            .insert(pool)
+            .await?;
+        let all = TestDataSource::new(&uniprot, "all", "UniProt")
+            .insert(pool)
+            .await?;
+        let genes = TestDataSource::new(&ensembl, "genes", "Genes")
+            .insert(pool)
+            .await?;
+
+        TestVersion::new(&taxonomy, "1.0")
+            .with_external_version("2025-01-01")
+            .insert(pool)
+            .await?;
+        TestVersion::new(&taxonomy, "1.1").insert(pool).await?;
+        let swissprot_v = TestVersion::new(&swissprot, "2.0").insert(pool).await?;
+        let all_v = TestVersion::new(&all, "2.0").insert(pool).await?;
+        let genes_v = TestVersion::new(&genes, "4.0").insert(pool).await?;
+
+        for (version_id, depends_on, required, kind) in [
+            (swissprot_v.id, taxonomy.id, "2025-01-01", "required"),
+            (genes_v.id, taxonomy.id, "1.0", "optional"),
+            (all_v.id, swissprot.id, "2.0", "required"),
+        ] {
+            sqlx::query!(
+                r#"
+                INSERT INTO dependencies (version_id, depends_on_entry_id, depends_on_version, dependency_type)
+                VALUES ($1, $2, $3, $4)
+                "#,
+                version_id,
+                depends_on,
+                required,
+                kind
+            )
+            .execute(pool)
+            .await?;
+        }
+        Ok(())
+    }
+
+    fn query(org: &str, slug: &str, version: &str) -> DependencyGraphQuery {
+        DependencyGraphQuery {
+            organization_slug: org.to_string(),
+            data_source_slug: slug.to_string(),
+            version: version.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(query("ncbi", "taxonomy", "1.0").validate().is_ok());
+
+        let mut q = query("ncbi", "taxonomy", "1.0");
+        q.depth = Some(MAX_GRAPH_DEPTH + 1);
+        assert!(matches!(q.validate(), Err(DependencyGraphError::InvalidDepth)));
+
+        assert!(matches!(
+            query("ncbi", "", "1.0").validate(),
+            Err(DependencyGraphError::DataSourceSlugRequired)
+        ));
+    }
+
+    #[test]
+    fn test_render() {
+        let node = |id: &str, resolved| GraphNode {
+            id: id.to_string(),
+            organization_slug: String::new(),
+            entry_slug: String::new(),
+            entry_name: "Name".to_string(),
+            version: String::new(),
+            depth: 0,
+            resolved,
+        };
+        let graph = DependencyGraphResponse {
+            root: "uniprot:all@2.0".to_string(),
+            direction: GraphDirection::Forward,
+            depth: 1,
+            nodes: vec![node("uniprot:all@2.0", true), node("ncbi:taxonomy@1.0", false)],
+            edges: vec![GraphEdge {
+                from: "uniprot:all@2.0".to_string(),
+                to: "ncbi:taxonomy@1.0".to_string(),
+                required_version: "1.0".to_string(),
+                dependency_type: "optional".to_string(),
+            }],
+            truncated: false,
+        };
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph \"uniprot:all@2.0\" {"));
+        assert!(dot
+            .contains("\"ncbi:taxonomy@1.0\" [label=\"ncbi:taxonomy@1.0\\nName\", style=dashed];"));
+        assert!(dot.contains("\"uniprot:all@2.0\" -> \"ncbi:taxonomy@1.0\" [style=dashed];"));
+
+        assert_eq!(
+            graph.to_mermaid(),
+            "graph LR\n  n0[\"uniprot:all@2.0\"]\n  n1[\"ncbi:taxonomy@1.0\"]\n  n0 -.-> n1\n"
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_handle_reverse(pool: PgPool) -> sqlx::Result<()> {
+        setup_graph(&pool).await?;
+
+        let mut q = query("ncbi", "taxonomy", "1.0");
+        q.direction = GraphDirection::Reverse;
+        let graph = handle(pool.clone(), q.clone()).await.unwrap();
+
+        let ids: Vec<_> = graph
+            .nodes
+            .iter()
+            .map(|n| (n.id.as_str(), n.depth))
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                ("ncbi:taxonomy@1.0", 0),
+                ("ensembl:genes@4.0", 1),
+                ("uniprot:swissprot@2.0", 1),
+                ("uniprot:all@2.0", 2),
+            ]
+        );
+        assert!(graph.edges.iter().any(|e| e.from == "uniprot:swissprot@2.0"
+            && e.to == "ncbi:taxonomy@1.0"
+            && e.required_version == "2025-01-01"));
+        assert!(!graph.truncated);
+
+        q.depth = Some(1);
+        let graph = handle(pool.clone(), q).await.unwrap();
+        assert_eq!(graph.nodes.len(), 3);
+
+        // Nothing was built on the newer taxonomy release
+        let mut q = query("ncbi", "taxonomy", "1.1");
+        q.direction = GraphDirection::Reverse;
+        let graph = handle(pool, q).await.unwrap();
+        assert_eq!(graph.nodes.len(), 1);
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_handle_forward(pool: PgPool) -> sqlx::Result<()> {
+        setup_graph(&pool).await?;
+
+        let graph = handle(pool.clone(), query("uniprot", "all", "2.0"))
+            .await
+            .unwrap();
+
+        let ids: Vec<_> = graph.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["uniprot:all@2.0", "uniprot:swissprot@2.0", "ncbi:taxonomy@1.0"]);
+        assert_eq!(graph.edges.len(), 2);
+        assert_eq!(graph.edges[1].from, "uniprot:swissprot@2.0");
+        assert_eq!(graph.edges[1].to, "ncbi:taxonomy@1.0");
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_handle_not_found(pool: PgPool) -> sqlx::Result<()> {
+        let result = handle(pool, query("ncbi", "taxonomy", "9.9")).await;
+        assert!(matches!(result, Err(DependencyGraphError::NotFound(..))));
+        Ok(())
+    }
+}
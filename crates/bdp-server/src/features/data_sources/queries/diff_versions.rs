@@ -0,0 +1,630 @@
+//! Entry-level diff between two versions of a data source
+//!
+//! The entries of a version are its dependencies, so an aggregate source such
+//! as `uniprot:all` diffs per protein; a version without dependencies is its
+//! own single entry. Entries present in only one version are added or
+//! removed, and entries pinned at different versions are modified. For
+//! modified entries the snapshots pipelines keep in each version's
+//! `additional_metadata` are compared field by field.
+
+use mediator::Request;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::features::shared::pagination::PaginationMetadata;
+use crate::ingest::version_mapping::VersionMapper;
+
+/// How an entry differs between the two versions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryChange {
+    Added,
+    Removed,
+    Modified,
+}
+
+impl EntryChange {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EntryChange::Added => "added",
+            EntryChange::Removed => "removed",
+            EntryChange::Modified => "modified",
+        }
+    }
+
+    fn from_db_str(s: &str) -> Option<Self> {
+        match s {
+            "added" => Some(EntryChange::Added),
+            "removed" => Some(EntryChange::Removed),
+            "modified" => Some(EntryChange::Modified),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffVersionsQuery {
+    #[serde(default)]
+    pub organization_slug: String,
+    #[serde(default)]
+    pub data_source_slug: String,
+    /// Internal version or upstream release to diff from
+    #[serde(default)]
+    pub from: String,
+    /// Internal version or upstream release to diff to
+    #[serde(default)]
+    pub to: String,
+    /// Only return entries with this kind of change
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub change: Option<EntryChange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub per_page: Option<i64>,
+}
+
+/// A field that differs between two snapshots of an entry
+///
+/// List fields report the items added and removed; other fields report the
+/// value before and after.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub added: Vec<Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed: Vec<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntryDiff {
+    pub organization_slug: String,
+    pub entry_slug: String,
+    pub entry_name: String,
+    pub change: EntryChange,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_version: Option<String>,
+    /// Whether both versions of a modified entry recorded a snapshot; older
+    /// versions ingested before snapshots were kept have no field detail
+    pub details_available: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<FieldChange>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiffSummary {
+    pub added: i64,
+    pub removed: i64,
+    pub modified: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffVersionsResponse {
+    pub source: String,
+    pub from_version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_release: Option<String>,
+    pub to_version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_release: Option<String>,
+    /// Counts over every changed entry, regardless of the `change` filter
+    pub summary: DiffSummary,
+    pub entries: Vec<EntryDiff>,
+    pub pagination: PaginationMetadata,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DiffVersionsError {
+    #[error("Organization slug is required and cannot be empty")]
+    OrganizationSlugRequired,
+    #[error("Data source slug is required and cannot be empty")]
+    DataSourceSlugRequired,
+    #[error("Both 'from' and 'to' versions are required")]
+    VersionRequired,
+    #[error("Page must be greater than 0")]
+    InvalidPage,
+    #[error("Per page must be between 1 and 1000")]
+    InvalidPerPage,
+    #[error("Version or release '{2}' for data source '{0}/{1}' not found")]
+    NotFound(String, String, String),
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl Request<Result<DiffVersionsResponse, DiffVersionsError>> for DiffVersionsQuery {}
+
+impl crate::cqrs::middleware::Query for DiffVersionsQuery {}
+
+impl DiffVersionsQuery {
+    pub fn validate(&self) -> Result<(), DiffVersionsError> {
+        if self.organization_slug.is_empty() {
+            return Err(DiffVersionsError::OrganizationSlugRequired);
+        }
+        if self.data_source_slug.is_empty() {
+            return Err(DiffVersionsError::DataSourceSlugRequired);
+        }
+        if self.from.is_empty() || self.to.is_empty() {
+            return Err(DiffVersionsError::VersionRequired);
+        }
+        if let Some(page) = self.page {
+            if page < 1 {
+                return Err(DiffVersionsError::InvalidPage);
+            }
+        }
+        if let Some(per_page) = self.per_page {
+            if !(1..=1000).contains(&per_page) {
+                return Err(DiffVersionsError::InvalidPerPage);
+            }
+        }
+        Ok(())
+    }
+
+    fn page(&self) -> i64 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    fn per_page(&self) -> i64 {
+        self.per_page.unwrap_or(100).clamp(1, 1000)
+    }
+}
+
+/// Compare two entry snapshots field by field
+///
+/// Fields missing on one side compare as `null`. Returns no changes when
+/// either snapshot is not a JSON object.
+pub fn diff_snapshots(before: &Value, after: &Value) -> Vec<FieldChange> {
+    let (Some(before), Some(after)) = (before.as_object(), after.as_object()) else {
+        return Vec::new();
+    };
+
+    let mut fields: Vec<&String> = before.keys().chain(after.keys()).collect();
+    fields.sort();
+    fields.dedup();
+
+    fields
+        .into_iter()
+        .filter_map(|field| {
+            let old = before.get(field).unwrap_or(&Value::Null);
+            let new = after.get(field).unwrap_or(&Value::Null);
+            if old == new {
+                return None;
+            }
+
+            let change = match (old, new) {
+                (Value::Array(old_items), Value::Array(new_items)) => FieldChange {
+                    field: field.clone(),
+                    before: None,
+                    after: None,
+                    added: new_items
+                        .iter()
+                        .filter(|item| !old_items.contains(item))
+                        .cloned()
+                        .collect(),
+                    removed: old_items
+                        .iter()
+                        .filter(|item| !new_items.contains(item))
+                        .cloned()
+                        .collect(),
+                },
+                _ => FieldChange {
+                    field: field.clone(),
+                    before: Some(old.clone()),
+                    after: Some(new.clone()),
+                    added: Vec::new(),
+                    removed: Vec::new(),
+                },
+            };
+
+            // Reordered lists are not a change
+            let reordered =
+                change.before.is_none() && change.added.is_empty() && change.removed.is_empty();
+            (!reordered).then_some(change)
+        })
+        .collect()
+}
+
+/// Version id, internal version and upstream release of a version given in
+/// either form
+async fn resolve_version(
+    pool: &PgPool,
+    organization_slug: &str,
+    data_source_slug: &str,
+    version: &str,
+) -> Result<Option<(Uuid, String, Option<String>)>, DiffVersionsError> {
+    let lookup = |internal: String| async move {
+        sqlx::query!(
+            r#"
+            SELECT v.id, v.version, v.external_version
+            FROM versions v
+            JOIN registry_entries re ON v.entry_id = re.id
+            JOIN organizations o ON re.organization_id = o.id
+            WHERE LOWER(o.slug) = LOWER($1) AND LOWER(re.slug) = LOWER($2) AND v.version = $3
+            "#,
+            organization_slug,
+            data_source_slug,
+            internal
+        )
+        .fetch_optional(pool)
+        .await
+        .map(|row| row.map(|row| (row.id, row.version, row.external_version)))
+    };
+
+    if let Some(found) = lookup(version.to_string()).await? {
+        return Ok(Some(found));
+    }
+
+    let mapping = VersionMapper::new(pool.clone())
+        .resolve(organization_slug, Some(data_source_slug), version)
+        .await?;
+    match mapping {
+        Some(mapping) => Ok(lookup(mapping.internal_version).await?),
+        None => Ok(None),
+    }
+}
+
+struct ChangeRecord {
+    organization_slug: String,
+    entry_slug: String,
+    entry_name: String,
+    change: String,
+    from_version: Option<String>,
+    to_version: Option<String>,
+    from_snapshot: Option<Value>,
+    to_snapshot: Option<Value>,
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn handle(
+    pool: PgPool,
+    query: DiffVersionsQuery,
+) -> Result<DiffVersionsResponse, DiffVersionsError> {
+    query.validate()?;
+
+    let not_found = |version: &str| {
+        DiffVersionsError::NotFound(
+            query.organization_slug.clone(),
+            query.data_source_slug.clone(),
+            version.to_string(),
+        )
+    };
+    let (from_id, from_version, from_release) =
+        resolve_version(&pool, &query.organization_slug, &query.data_source_slug, &query.from)
+            .await?
+            .ok_or_else(|| not_found(&query.from))?;
+    let (to_id, to_version, to_release) =
+        resolve_version(&pool, &query.organization_slug, &query.data_source_slug, &query.to)
+            .await?
+            .ok_or_else(|| not_found(&query.to))?;
+
+    // The entries of each version, then those that differ between them
+    let counts = sqlx::query!(
+        r#"
+        WITH a AS (
+            SELECT d.depends_on_entry_id AS entry_id, d.depends_on_version AS version
+            FROM dependencies d WHERE d.version_id = $1
+            UNION ALL
+            SELECT v.entry_id, v.version FROM versions v
+            WHERE v.id = $1 AND NOT EXISTS (SELECT 1 FROM dependencies d WHERE d.version_id = $1)
+        ),
+        b AS (
+            SELECT d.depends_on_entry_id AS entry_id, d.depends_on_version AS version
+            FROM dependencies d WHERE d.version_id = $2
+            UNION ALL
+            SELECT v.entry_id, v.version FROM versions v
+            WHERE v.id = $2 AND NOT EXISTS (SELECT 1 FROM dependencies d WHERE d.version_id = $2)
+        ),
+        changes AS (
+            SELECT COALESCE(a.entry_id, b.entry_id) AS entry_id,
+                   a.version AS from_version, b.version AS to_version,
+                   CASE WHEN a.entry_id IS NULL THEN 'added'
+                        WHEN b.entry_id IS NULL THEN 'removed'
+                        ELSE 'modified' END AS change
+            FROM a FULL OUTER JOIN b ON a.entry_id = b.entry_id
+            WHERE a.version IS DISTINCT FROM b.version
+        )
+        SELECT change AS "change!", COUNT(*) AS "count!"
+        FROM changes
+        GROUP BY change
+        "#,
+        from_id,
+        to_id
+    )
+    .fetch_all(&pool)
+    .await?;
+
+    let mut summary = DiffSummary::default();
+    for row in counts {
+        match EntryChange::from_db_str(&row.change) {
+            Some(EntryChange::Added) => summary.added = row.count,
+            Some(EntryChange::Removed) => summary.removed = row.count,
+            Some(EntryChange::Modified) => summary.modified = row.count,
+            None => {},
+        }
+    }
+    let total = match query.change {
+        Some(EntryChange::Added) => summary.added,
+        Some(EntryChange::Removed) => summary.removed,
+        Some(EntryChange::Modified) => summary.modified,
+        None => summary.added + summary.removed + summary.modified,
+    };
+
+    let page = query.page();
+    let per_page = query.per_page();
+
+    let records = sqlx::query_as!(
+        ChangeRecord,
+        r#"
+        WITH a AS (
+            SELECT d.depends_on_entry_id AS entry_id, d.depends_on_version AS version
+            FROM dependencies d WHERE d.version_id = $1
+            UNION ALL
+            SELECT v.entry_id, v.version FROM versions v
+            WHERE v.id = $1 AND NOT EXISTS (SELECT 1 FROM dependencies d WHERE d.version_id = $1)
+        ),
+        b AS (
+            SELECT d.depends_on_entry_id AS entry_id, d.depends_on_version AS version
+            FROM dependencies d WHERE d.version_id = $2
+            UNION ALL
+            SELECT v.entry_id, v.version FROM versions v
+            WHERE v.id = $2 AND NOT EXISTS (SELECT 1 FROM dependencies d WHERE d.version_id = $2)
+        ),
+        changes AS (
+            SELECT COALESCE(a.entry_id, b.entry_id) AS entry_id,
+                   a.version AS from_version, b.version AS to_version,
+                   CASE WHEN a.entry_id IS NULL THEN 'added'
+                        WHEN b.entry_id IS NULL THEN 'removed'
+                        ELSE 'modified' END AS change
+            FROM a FULL OUTER JOIN b ON a.entry_id = b.entry_id
+            WHERE a.version IS DISTINCT FROM b.version
+        )
+        SELECT o.slug AS organization_slug, re.slug AS entry_slug, re.name AS entry_name,
+               c.change AS "change!", c.from_version, c.to_version,
+               va.additional_metadata AS "from_snapshot?", vb.additional_metadata AS "to_snapshot?"
+        FROM changes c
+        JOIN registry_entries re ON re.id = c.entry_id
+        JOIN organizations o ON o.id = re.organization_id
+        LEFT JOIN versions va ON va.entry_id = c.entry_id AND va.version = c.from_version
+        LEFT JOIN versions vb ON vb.entry_id = c.entry_id AND vb.version = c.to_version
+        WHERE $3::text IS NULL OR c.change = $3
+        ORDER BY o.slug, re.slug
+        LIMIT $4 OFFSET $5
+        "#,
+        from_id,
+        to_id,
+        query.change.map(|c| c.as_str()),
+        per_page,
+        (page - 1) * per_page
+    )
+    .fetch_all(&pool)
+    .await?;
+
+    let entries = records
+        .into_iter()
+        .filter_map(|r| {
+            let change = EntryChange::from_db_str(&r.change)?;
+            let snapshots = match (&r.from_snapshot, &r.to_snapshot) {
+                (Some(before), Some(after)) if before.is_object() && after.is_object() => {
+                    Some((before, after))
+                },
+                _ => None,
+            };
+            let details_available = change == EntryChange::Modified && snapshots.is_some();
+            let fields = match snapshots {
+                Some((before, after)) if details_available => diff_snapshots(before, after),
+                _ => Vec::new(),
+            };
+
+            Some(EntryDiff {
+                organization_slug: r.organization_slug,
+                entry_slug: r.entry_slug,
+                entry_name: r.entry_name,
+                change,
+                from_version: r.from_version,
+                to_version: r.to_version,
+                details_available,
+                fields,
+            })
+        })
+        .collect();
+
+    Ok(DiffVersionsResponse {
+        source: format!("{}/{}", query.organization_slug, query.data_source_slug),
+        from_version,
+        from_release,
+        to_version,
+        to_release,
+        summary,
+        entries,
+        pagination: PaginationMetadata::new(page, per_page, total),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_validation_requires_both_versions() {
+        let query = DiffVersionsQuery {
+            organization_slug: "uniprot".to_string(),
+            data_source_slug: "all".to_string(),
+            from: "2024_06".to_string(),
+            to: String::new(),
+            change: None,
+            page: None,
+            per_page: None,
+        };
+        assert!(matches!(query.validate(), Err(DiffVersionsError::VersionRequired)));
+    }
+
+    #[test]
+    fn test_diff_snapshots() {
+        let before = json!({
+            "protein_name": "Insulin",
+            "sequence_length": 110,
+            "go_terms": ["GO:0005179", "GO:0005615"],
+            "keywords": ["Hormone", "Diabetes mellitus"],
+        });
+        let after = json!({
+            "protein_name": "Insulin",
+            "sequence_length": 111,
+            "go_terms": ["GO:0005179", "GO:0006006"],
+            "keywords": ["Diabetes mellitus", "Hormone"],
+            "gene_name": "INS",
+        });
+
+        let changes = diff_snapshots(&before, &after);
+        let fields: Vec<&str> = changes.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(fields, vec!["gene_name", "go_terms", "sequence_length"]);
+
+        assert_eq!(changes[0].before, Some(Value::Null));
+        assert_eq!(changes[0].after, Some(json!("INS")));
+        assert_eq!(changes[1].added, vec![json!("GO:0006006")]);
+        assert_eq!(changes[1].removed, vec![json!("GO:0005615")]);
+        assert_eq!(changes[2].before, Some(json!(110)));
+
+        assert!(diff_snapshots(&json!("a"), &json!({})).is_empty());
+    }
+
+    async fn insert_entry(pool: &PgPool, org_id: Uuid, slug: &str) -> sqlx::Result<Uuid> {
+        sqlx::query_scalar(
+            "INSERT INTO registry_entries (organization_id, slug, name, entry_type) \
+             VALUES ($1, $2, $2, 'data_source') RETURNING id",
+        )
+        .bind(org_id)
+        .bind(slug)
+        .fetch_one(pool)
+        .await
+    }
+
+    async fn insert_version(
+        pool: &PgPool,
+        entry_id: Uuid,
+        version: &str,
+        release: Option<&str>,
+        snapshot: Option<Value>,
+    ) -> sqlx::Result<Uuid> {
+        sqlx::query_scalar(
+            "INSERT INTO versions (entry_id, version, external_version, additional_metadata) \
+             VALUES ($1, $2, $3, $4) RETURNING id",
+        )
+        .bind(entry_id)
+        .bind(version)
+        .bind(release)
+        .bind(snapshot)
+        .fetch_one(pool)
+        .await
+    }
+
+    async fn depend(pool: &PgPool, version_id: Uuid, entry_id: Uuid, on: &str) -> sqlx::Result<()> {
+        sqlx::query(
+            "INSERT INTO dependencies (version_id, depends_on_entry_id, depends_on_version) \
+             VALUES ($1, $2, $3)",
+        )
+        .bind(version_id)
+        .bind(entry_id)
+        .bind(on)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_handle_diffs_aggregate_members(pool: PgPool) -> sqlx::Result<()> {
+        let org_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO organizations (slug, name, is_system) VALUES ('uniprot', 'UniProt', true) RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await?;
+
+        let all = insert_entry(&pool, org_id, "all").await?;
+        let kept = insert_entry(&pool, org_id, "p01308").await?;
+        let changed = insert_entry(&pool, org_id, "p68871").await?;
+        let dropped = insert_entry(&pool, org_id, "q9y6k9").await?;
+        let new = insert_entry(&pool, org_id, "a0a0b4").await?;
+
+        insert_version(&pool, kept, "1.0", Some("2024_06"), None).await?;
+        insert_version(&pool, changed, "1.0", Some("2024_06"), Some(json!({"go_terms": ["GO:1"]})))
+            .await?;
+        insert_version(&pool, changed, "1.1", Some("2025_01"), Some(json!({"go_terms": ["GO:2"]})))
+            .await?;
+        insert_version(&pool, dropped, "1.0", Some("2024_06"), None).await?;
+        insert_version(&pool, new, "1.0", Some("2025_01"), None).await?;
+
+        let old = insert_version(&pool, all, "1.0", Some("2024_06"), None).await?;
+        depend(&pool, old, kept, "1.0").await?;
+        depend(&pool, old, changed, "1.0").await?;
+        depend(&pool, old, dropped, "1.0").await?;
+        let current = insert_version(&pool, all, "2.0", Some("2025_01"), None).await?;
+        depend(&pool, current, kept, "1.0").await?;
+        depend(&pool, current, changed, "1.1").await?;
+        depend(&pool, current, new, "1.0").await?;
+
+        let query = DiffVersionsQuery {
+            organization_slug: "uniprot".to_string(),
+            data_source_slug: "all".to_string(),
+            from: "2024_06".to_string(),
+            to: "2.0".to_string(),
+            change: None,
+            page: None,
+            per_page: None,
+        };
+        let response = handle(pool.clone(), query.clone()).await.unwrap();
+
+        assert_eq!(response.from_version, "1.0");
+        assert_eq!(response.to_release.as_deref(), Some("2025_01"));
+        assert_eq!(
+            response.summary,
+            DiffSummary {
+                added: 1,
+                removed: 1,
+                modified: 1
+            }
+        );
+        let slugs: Vec<&str> = response
+            .entries
+            .iter()
+            .map(|e| e.entry_slug.as_str())
+            .collect();
+        assert_eq!(slugs, vec!["a0a0b4", "p68871", "q9y6k9"]);
+
+        let modified = &response.entries[1];
+        assert_eq!(modified.change, EntryChange::Modified);
+        assert!(modified.details_available);
+        assert_eq!(modified.fields[0].added, vec![json!("GO:2")]);
+
+        let filtered = handle(
+            pool.clone(),
+            DiffVersionsQuery {
+                change: Some(EntryChange::Removed),
+                per_page: Some(1),
+                ..query.clone()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(filtered.entries.len(), 1);
+        assert_eq!(filtered.entries[0].change, EntryChange::Removed);
+        assert_eq!(filtered.pagination.total, 1);
+
+        let missing = handle(
+            pool,
+            DiffVersionsQuery {
+                to: "2026_01".to_string(),
+                ..query
+            },
+        )
+        .await;
+        assert!(matches!(missing, Err(DiffVersionsError::NotFound(..))));
+        Ok(())
+    }
+}
@@ -143,6 +143,10 @@ pub struct VersionInfo {
     pub size_bytes: Option<i64>,
     pub download_count: i64,
     pub published_at: DateTime<Utc>,
+    /// Whether the version has been yanked
+    pub yanked: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub yanked_reason: Option<String>,
 }
 
 /// Errors that can occur when getting a data source
@@ -299,7 +303,8 @@ pub async fn handle(
         VersionRecord,
         r#"
         SELECT id, version, external_version, release_date, size_bytes,
-               download_count as "download_count!", published_at as "published_at!"
+               download_count as "download_count!", published_at as "published_at!",
+               yanked_at, yanked_reason
         FROM versions
         WHERE entry_id = $1
         ORDER BY published_at DESC
@@ -311,7 +316,11 @@ pub async fn handle(
 
     let total_downloads: i64 = versions.iter().map(|v| v.download_count).sum();
 
-    let latest_version = versions.first().map(|v| v.version.clone());
+    // Yanked versions are never reported as the latest
+    let latest_version = versions
+        .iter()
+        .find(|v| v.yanked_at.is_none())
+        .map(|v| v.version.clone());
 
     let tags = sqlx::query_scalar!(
         r#"
@@ -389,6 +398,8 @@ pub async fn handle(
                 size_bytes: v.size_bytes,
                 download_count: v.download_count,
                 published_at: v.published_at,
+                yanked: v.yanked_at.is_some(),
+                yanked_reason: v.yanked_reason,
             })
             .collect(),
         latest_version,
@@ -446,6 +457,8 @@ struct VersionRecord {
     size_bytes: Option<i64>,
     download_count: i64,
     published_at: DateTime<Utc>,
+    yanked_at: Option<DateTime<Utc>>,
+    yanked_reason: Option<String>,
 }
 
 #[cfg(test)]
@@ -30,6 +30,15 @@ pub struct GetVersionResponse {
     pub has_dependencies: bool,
     pub dependency_count: i32,
     pub published_at: DateTime<Utc>,
+    /// Yanked versions remain downloadable but are not resolved for new specs
+    pub yanked: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub yanked_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub yanked_reason: Option<String>,
+    /// Files of a sealed version can no longer be replaced
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sealed_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,6 +50,10 @@ pub struct FileInfo {
     pub size_bytes: i64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub compression: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub md5: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -114,6 +127,9 @@ pub async fn handle(
             v.download_count as "download_count!",
             v.dependency_count as "dependency_count!",
             v.published_at as "published_at!",
+            v.yanked_at,
+            v.yanked_reason,
+            v.sealed_at,
             re.slug as data_source_slug,
             re.name as data_source_name,
             o.slug as organization_slug
@@ -139,7 +155,7 @@ pub async fn handle(
     let files = sqlx::query_as!(
         FileRecord,
         r#"
-        SELECT id, format, s3_key, checksum, size_bytes, compression
+        SELECT id, format, s3_key, checksum, size_bytes, compression, sha256, md5
         FROM version_files
         WHERE version_id = $1
         ORDER BY format
@@ -183,6 +199,8 @@ pub async fn handle(
                 checksum: f.checksum,
                 size_bytes: f.size_bytes,
                 compression: f.compression,
+                sha256: f.sha256,
+                md5: f.md5,
             })
             .collect(),
         citations: citations
@@ -201,6 +219,10 @@ pub async fn handle(
         has_dependencies,
         dependency_count: version_record.dependency_count,
         published_at: version_record.published_at,
+        yanked: version_record.yanked_at.is_some(),
+        yanked_at: version_record.yanked_at,
+        yanked_reason: version_record.yanked_reason,
+        sealed_at: version_record.sealed_at,
     })
 }
 
@@ -214,6 +236,9 @@ struct VersionRecord {
     download_count: i64,
     dependency_count: i32,
     published_at: DateTime<Utc>,
+    yanked_at: Option<DateTime<Utc>>,
+    yanked_reason: Option<String>,
+    sealed_at: Option<DateTime<Utc>>,
     data_source_slug: String,
     data_source_name: String,
     organization_slug: String,
@@ -227,6 +252,8 @@ struct FileRecord {
     checksum: String,
     size_bytes: i64,
     compression: Option<String>,
+    sha256: Option<String>,
+    md5: Option<String>,
 }
 
 #[derive(Debug)]
@@ -0,0 +1,127 @@
+//! Get version mapping query
+//!
+//! Looks up a version of a data source given either as its internal version
+//! (`1.4.0`) or as the upstream release it was ingested from (`2025_01`), and
+//! returns both forms.
+
+use mediator::Request;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+
+use crate::ingest::version_mapping::{VersionMapper, VersionMapping};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetVersionMappingQuery {
+    pub organization_slug: String,
+    pub data_source_slug: String,
+    /// Internal version or upstream release
+    pub version: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GetVersionMappingError {
+    #[error("Organization slug is required and cannot be empty")]
+    OrganizationSlugRequired,
+    #[error("Data source slug is required and cannot be empty")]
+    DataSourceSlugRequired,
+    #[error("Version is required and cannot be empty")]
+    VersionRequired,
+    #[error("No version or release '{2}' mapped for data source '{0}/{1}'")]
+    NotFound(String, String, String),
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl Request<Result<VersionMapping, GetVersionMappingError>> for GetVersionMappingQuery {}
+
+impl crate::cqrs::middleware::Query for GetVersionMappingQuery {}
+
+impl GetVersionMappingQuery {
+    pub fn validate(&self) -> Result<(), GetVersionMappingError> {
+        if self.organization_slug.is_empty() {
+            return Err(GetVersionMappingError::OrganizationSlugRequired);
+        }
+        if self.data_source_slug.is_empty() {
+            return Err(GetVersionMappingError::DataSourceSlugRequired);
+        }
+        if self.version.is_empty() {
+            return Err(GetVersionMappingError::VersionRequired);
+        }
+        Ok(())
+    }
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn handle(
+    pool: PgPool,
+    query: GetVersionMappingQuery,
+) -> Result<VersionMapping, GetVersionMappingError> {
+    query.validate()?;
+
+    VersionMapper::new(pool)
+        .resolve(&query.organization_slug, Some(&query.data_source_slug), &query.version)
+        .await?
+        .ok_or(GetVersionMappingError::NotFound(
+            query.organization_slug,
+            query.data_source_slug,
+            query.version,
+        ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_validation_empty_version() {
+        let query = GetVersionMappingQuery {
+            organization_slug: "go".to_string(),
+            data_source_slug: "go".to_string(),
+            version: String::new(),
+        };
+        assert!(matches!(query.validate(), Err(GetVersionMappingError::VersionRequired)));
+    }
+
+    #[sqlx::test]
+    async fn test_handle_resolves_both_forms(pool: PgPool) -> sqlx::Result<()> {
+        let org_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO organizations (slug, name, is_system) VALUES ('go', 'Gene Ontology', true) RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        sqlx::query(
+            r#"
+            WITH entry AS (
+                INSERT INTO registry_entries (organization_id, slug, name, entry_type)
+                VALUES ($1, 'go', 'Gene Ontology', 'data_source')
+                RETURNING id
+            )
+            INSERT INTO versions (entry_id, version, external_version)
+            SELECT id, '1.2.0', '2025-03-16' FROM entry
+            "#,
+        )
+        .bind(org_id)
+        .execute(&pool)
+        .await?;
+
+        for version in ["1.2.0", "2025-03-16"] {
+            let query = GetVersionMappingQuery {
+                organization_slug: "go".to_string(),
+                data_source_slug: "go".to_string(),
+                version: version.to_string(),
+            };
+            let mapping = handle(pool.clone(), query).await.unwrap();
+            assert_eq!(mapping.internal_version, "1.2.0");
+            assert_eq!(mapping.external_version, "2025-03-16");
+        }
+
+        let query = GetVersionMappingQuery {
+            organization_slug: "go".to_string(),
+            data_source_slug: "go".to_string(),
+            version: "2024-01-01".to_string(),
+        };
+        assert!(matches!(handle(pool, query).await, Err(GetVersionMappingError::NotFound(..))));
+        Ok(())
+    }
+}
@@ -160,7 +160,7 @@ pub async fn handle(
             (
                 SELECT v.version
                 FROM versions v
-                WHERE v.entry_id = re.id
+                WHERE v.entry_id = re.id AND v.yanked_at IS NULL
                 ORDER BY v.published_at DESC
                 LIMIT 1
             ) as latest_version,
@@ -0,0 +1,71 @@
+//! List version mappings query
+//!
+//! Lists every upstream release of a data source with the internal version
+//! it is published under, newest first.
+
+use mediator::Request;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+
+use crate::ingest::version_mapping::{VersionMapper, VersionMapping};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListVersionMappingsQuery {
+    pub organization_slug: String,
+    pub data_source_slug: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ListVersionMappingsError {
+    #[error("Organization slug is required and cannot be empty")]
+    OrganizationSlugRequired,
+    #[error("Data source slug is required and cannot be empty")]
+    DataSourceSlugRequired,
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl Request<Result<Vec<VersionMapping>, ListVersionMappingsError>> for ListVersionMappingsQuery {}
+
+impl crate::cqrs::middleware::Query for ListVersionMappingsQuery {}
+
+impl ListVersionMappingsQuery {
+    pub fn validate(&self) -> Result<(), ListVersionMappingsError> {
+        if self.organization_slug.is_empty() {
+            return Err(ListVersionMappingsError::OrganizationSlugRequired);
+        }
+        if self.data_source_slug.is_empty() {
+            return Err(ListVersionMappingsError::DataSourceSlugRequired);
+        }
+        Ok(())
+    }
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn handle(
+    pool: PgPool,
+    query: ListVersionMappingsQuery,
+) -> Result<Vec<VersionMapping>, ListVersionMappingsError> {
+    query.validate()?;
+
+    Ok(VersionMapper::new(pool)
+        .list(&query.organization_slug, Some(&query.data_source_slug))
+        .await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation_empty_data_source_slug() {
+        let query = ListVersionMappingsQuery {
+            organization_slug: "interpro".to_string(),
+            data_source_slug: String::new(),
+        };
+        assert!(matches!(
+            query.validate(),
+            Err(ListVersionMappingsError::DataSourceSlugRequired)
+        ));
+    }
+}
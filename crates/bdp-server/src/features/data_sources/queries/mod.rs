@@ -1,9 +1,22 @@
+pub mod dependency_graph;
+pub mod diff_versions;
 pub mod get;
 pub mod get_protein_metadata;
 pub mod get_version;
+pub mod get_version_mapping;
 pub mod list;
 pub mod list_dependencies;
+pub mod list_version_mappings;
+pub mod preview_cascade;
 
+pub use dependency_graph::{
+    DependencyGraphError, DependencyGraphQuery, DependencyGraphResponse, GraphDirection, GraphEdge,
+    GraphFormat, GraphNode,
+};
+pub use diff_versions::{
+    DiffSummary, DiffVersionsError, DiffVersionsQuery, DiffVersionsResponse, EntryChange,
+    EntryDiff, FieldChange,
+};
 pub use get::{
     GetDataSourceError, GetDataSourceQuery, GetDataSourceResponse, OrganismInfo, OrganizationInfo,
     VersionInfo,
@@ -11,6 +24,7 @@ pub use get::{
 pub use get_version::{
     CitationInfo, FileInfo, GetVersionError, GetVersionQuery, GetVersionResponse,
 };
+pub use get_version_mapping::{GetVersionMappingError, GetVersionMappingQuery};
 pub use list::{
     DataSourceListItem, ListDataSourcesError, ListDataSourcesQuery, ListDataSourcesResponse,
 };
@@ -19,3 +33,5 @@ pub use crate::features::shared::pagination::PaginationMetadata;
 pub use list_dependencies::{
     DependencyItem, ListDependenciesError, ListDependenciesQuery, ListDependenciesResponse,
 };
+pub use list_version_mappings::{ListVersionMappingsError, ListVersionMappingsQuery};
+pub use preview_cascade::{PreviewCascadeError, PreviewCascadeQuery, PreviewCascadeResponse};
@@ -0,0 +1,378 @@
+//! Version cascade dry run
+//!
+//! Shows which dependents a version's bump would cascade to before anything
+//! is written: the tree of data sources that would get a new version, the
+//! version numbers they would get and the changelogs saved with them.
+//! Dependents can be excluded to see the cascade that would be published
+//! without them.
+
+use std::collections::HashSet;
+
+use mediator::Request;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::ingest::versioning::{get_changelog, preview_cascade, BumpType, CascadePreview};
+
+/// Depth used when a request does not set `max_depth`
+pub const DEFAULT_CASCADE_DEPTH: usize = 5;
+
+/// Deepest cascade a request may ask for
+pub const MAX_CASCADE_DEPTH: usize = 20;
+
+/// Query to preview the cascade of a version's bump
+///
+/// The path segments (`organization_slug`, `data_source_slug`, `version`) are
+/// filled in from the route; the request body carries `exclude` and
+/// `max_depth`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PreviewCascadeQuery {
+    #[serde(default)]
+    pub organization_slug: String,
+    #[serde(default)]
+    pub data_source_slug: String,
+    #[serde(default)]
+    pub version: String,
+    /// Registry entry IDs of dependents to leave out
+    #[serde(default)]
+    pub exclude: Vec<Uuid>,
+    #[serde(default)]
+    pub max_depth: Option<usize>,
+}
+
+/// Cascade that publishing a version's bump would perform
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviewCascadeResponse {
+    pub source_version_id: Uuid,
+    pub organization_slug: String,
+    pub data_source_slug: String,
+    pub version: String,
+    pub bump_type: BumpType,
+    pub max_depth: usize,
+    /// Number of versions the cascade would create
+    pub total_versions: usize,
+    pub excluded: Vec<Uuid>,
+    pub dependents: Vec<CascadePreview>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PreviewCascadeError {
+    #[error("Organization slug is required and cannot be empty")]
+    OrganizationSlugRequired,
+    #[error("Data source slug is required and cannot be empty")]
+    DataSourceSlugRequired,
+    #[error("Version is required and cannot be empty")]
+    VersionRequired,
+    #[error("max_depth must be between 1 and {MAX_CASCADE_DEPTH}")]
+    InvalidMaxDepth,
+    #[error("Version '{2}' for data source '{0}/{1}' not found")]
+    NotFound(String, String, String),
+    #[error("Version '{2}' for data source '{0}/{1}' has no changelog to cascade")]
+    NoChangelog(String, String, String),
+    #[error("Cascade failed: {0}")]
+    Cascade(String),
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl Request<Result<PreviewCascadeResponse, PreviewCascadeError>> for PreviewCascadeQuery {}
+
+impl crate::cqrs::middleware::Query for PreviewCascadeQuery {}
+
+impl PreviewCascadeQuery {
+    pub fn validate(&self) -> Result<(), PreviewCascadeError> {
+        validate_cascade_request(
+            &self.organization_slug,
+            &self.data_source_slug,
+            &self.version,
+            self.max_depth,
+        )
+    }
+
+    fn max_depth(&self) -> usize {
+        self.max_depth.unwrap_or(DEFAULT_CASCADE_DEPTH)
+    }
+}
+
+/// Validation shared with the cascade command, which takes the same request
+pub(crate) fn validate_cascade_request(
+    organization_slug: &str,
+    data_source_slug: &str,
+    version: &str,
+    max_depth: Option<usize>,
+) -> Result<(), PreviewCascadeError> {
+    if organization_slug.is_empty() {
+        return Err(PreviewCascadeError::OrganizationSlugRequired);
+    }
+    if data_source_slug.is_empty() {
+        return Err(PreviewCascadeError::DataSourceSlugRequired);
+    }
+    if version.is_empty() {
+        return Err(PreviewCascadeError::VersionRequired);
+    }
+    if let Some(depth) = max_depth {
+        if depth == 0 || depth > MAX_CASCADE_DEPTH {
+            return Err(PreviewCascadeError::InvalidMaxDepth);
+        }
+    }
+    Ok(())
+}
+
+/// Version whose bump triggers a cascade, with its stored changelog
+pub(crate) struct CascadeSource {
+    pub version_id: Uuid,
+    pub changelog: crate::ingest::versioning::VersionChangelog,
+}
+
+/// Look up a version and the changelog its cascade is derived from
+pub(crate) async fn load_cascade_source(
+    pool: &PgPool,
+    organization_slug: &str,
+    data_source_slug: &str,
+    version: &str,
+) -> Result<CascadeSource, PreviewCascadeError> {
+    let version_id = sqlx::query_scalar!(
+        r#"
+        SELECT v.id
+        FROM versions v
+        JOIN registry_entries re ON re.id = v.entry_id
+        JOIN organizations o ON o.id = re.organization_id
+        WHERE LOWER(o.slug) = LOWER($1) AND LOWER(re.slug) = LOWER($2) AND v.version = $3
+        "#,
+        organization_slug,
+        data_source_slug,
+        version
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let version_id = version_id.ok_or_else(|| {
+        PreviewCascadeError::NotFound(
+            organization_slug.to_string(),
+            data_source_slug.to_string(),
+            version.to_string(),
+        )
+    })?;
+
+    let changelog = get_changelog(pool, version_id)
+        .await
+        .map_err(|e| PreviewCascadeError::Cascade(e.to_string()))?
+        .ok_or_else(|| {
+            PreviewCascadeError::NoChangelog(
+                organization_slug.to_string(),
+                data_source_slug.to_string(),
+                version.to_string(),
+            )
+        })?;
+
+    Ok(CascadeSource {
+        version_id,
+        changelog,
+    })
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn handle(
+    pool: PgPool,
+    query: PreviewCascadeQuery,
+) -> Result<PreviewCascadeResponse, PreviewCascadeError> {
+    query.validate()?;
+
+    let source = load_cascade_source(
+        &pool,
+        &query.organization_slug,
+        &query.data_source_slug,
+        &query.version,
+    )
+    .await?;
+
+    let excluded: HashSet<Uuid> = query.exclude.iter().copied().collect();
+    let dependents =
+        preview_cascade(&pool, source.version_id, &source.changelog, query.max_depth(), &excluded)
+            .await
+            .map_err(|e| PreviewCascadeError::Cascade(e.to_string()))?;
+
+    Ok(PreviewCascadeResponse {
+        source_version_id: source.version_id,
+        organization_slug: query.organization_slug.clone(),
+        data_source_slug: query.data_source_slug.clone(),
+        version: query.version.clone(),
+        bump_type: source.changelog.bump_type,
+        max_depth: query.max_depth(),
+        total_versions: dependents.iter().map(CascadePreview::total).sum(),
+        excluded: query.exclude,
+        dependents,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::shared::test_helpers::{TestDataSource, TestOrganization, TestVersion};
+    use crate::ingest::versioning::{ChangelogEntry, ChangelogSummary, VersionChangelog};
+
+    /// uniprot:swissprot 1.0.0 <- ncbi:proteome 2.3.0 <- ncbi:annotations 1.4.0
+    ///                         <- ensembl:genes 3.0.0
+    async fn setup_cascade(pool: &PgPool) -> anyhow::Result<Uuid> {
+        let uniprot = TestOrganization::new("uniprot", "UniProt")
+            .insert(pool)
+            .await?;
+        let ncbi = TestOrganization::new("ncbi", "NCBI").insert(pool).await?;
+        let ensembl = TestOrganization::new("ensembl", "Ensembl")
+            .insert(pool)
+            .await?;
+
+        let swissprot = TestDataSource::new(&uniprot, "swissprot", "Swiss-Prot")
+            .insert(pool)
+            .await?;
+        let proteome = TestDataSource::new(&ncbi, "proteome", "Proteome")
+            .insert(pool)
+            .await?;
+        let annotations = TestDataSource::new(&ncbi, "annotations", "Annotations")
+            .insert(pool)
+            .await?;
+        let genes = TestDataSource::new(&ensembl, "genes", "Genes")
+            .insert(pool)
+            .await?;
+
+        let source = TestVersion::new(&swissprot, "1.0.0").insert(pool).await?;
+        let proteome_v = TestVersion::new(&proteome, "2.3.0").insert(pool).await?;
+        let annotations_v = TestVersion::new(&annotations, "1.4.0").insert(pool).await?;
+        let genes_v = TestVersion::new(&genes, "3.0.0").insert(pool).await?;
+
+        for (version_id, depends_on) in [
+            (proteome_v.id, swissprot.id),
+            (genes_v.id, swissprot.id),
+            (annotations_v.id, proteome.id),
+        ] {
+            sqlx::query!(
+                r#"
+                INSERT INTO dependencies (version_id, depends_on_entry_id, depends_on_version)
+                VALUES ($1, $2, '1.0.0')
+                "#,
+                version_id,
+                depends_on
+            )
+            .execute(pool)
+            .await?;
+        }
+
+        let changelog = VersionChangelog::new(
+            BumpType::Major,
+            vec![ChangelogEntry::removed("proteins", 12, "Proteins removed")],
+            ChangelogSummary::default(),
+            "Proteins removed",
+        );
+        sqlx::query!(
+            r#"
+            INSERT INTO version_changelogs (version_id, bump_type, entries, summary, summary_text)
+            VALUES ($1, 'major', $2, $3, $4)
+            "#,
+            source.id,
+            serde_json::to_value(&changelog.entries)?,
+            serde_json::to_value(&changelog.summary)?,
+            changelog.summary_text
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(source.id)
+    }
+
+    fn query() -> PreviewCascadeQuery {
+        PreviewCascadeQuery {
+            organization_slug: "uniprot".to_string(),
+            data_source_slug: "swissprot".to_string(),
+            version: "1.0.0".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(query().validate().is_ok());
+
+        let mut q = query();
+        q.version = String::new();
+        assert!(matches!(q.validate(), Err(PreviewCascadeError::VersionRequired)));
+
+        let mut q = query();
+        q.max_depth = Some(0);
+        assert!(matches!(q.validate(), Err(PreviewCascadeError::InvalidMaxDepth)));
+
+        q.max_depth = Some(MAX_CASCADE_DEPTH + 1);
+        assert!(matches!(q.validate(), Err(PreviewCascadeError::InvalidMaxDepth)));
+    }
+
+    #[sqlx::test]
+    async fn test_handle_returns_dependent_tree(pool: PgPool) -> sqlx::Result<()> {
+        let source_id = setup_cascade(&pool).await.unwrap();
+
+        let response = handle(pool.clone(), query()).await.unwrap();
+
+        assert_eq!(response.source_version_id, source_id);
+        assert_eq!(response.bump_type, BumpType::Major);
+        assert_eq!(response.total_versions, 3);
+
+        let slugs: Vec<_> = response
+            .dependents
+            .iter()
+            .map(|d| d.entry_slug.as_str())
+            .collect();
+        assert_eq!(slugs, vec!["genes", "proteome"]);
+
+        let proteome = &response.dependents[1];
+        assert_eq!(proteome.current_version, "2.3.0");
+        assert_eq!(proteome.new_version, "3.0");
+        assert_eq!(proteome.changelog.triggered_by_version_id, Some(source_id));
+        assert!(proteome.changelog.entries[0]
+            .description
+            .contains("swissprot to version 1.0.0"));
+
+        let annotations = &proteome.dependents[0];
+        assert_eq!(annotations.entry_slug, "annotations");
+        assert_eq!(annotations.new_version, "2.0");
+        assert!(annotations.changelog.triggered_by_version_id.is_none());
+        assert!(annotations.changelog.entries[0]
+            .description
+            .contains("proteome to version 3.0"));
+
+        // Nothing is written by a dry run
+        let versions = sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM versions"#)
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(versions, 4);
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_handle_excludes_dependents(pool: PgPool) -> sqlx::Result<()> {
+        setup_cascade(&pool).await.unwrap();
+
+        let proteome_id =
+            sqlx::query_scalar!("SELECT id FROM registry_entries WHERE slug = 'proteome'")
+                .fetch_one(&pool)
+                .await?;
+
+        let mut q = query();
+        q.exclude = vec![proteome_id];
+        let response = handle(pool.clone(), q).await.unwrap();
+
+        assert_eq!(response.total_versions, 1);
+        assert_eq!(response.dependents[0].entry_slug, "genes");
+
+        let mut q = query();
+        q.max_depth = Some(1);
+        let response = handle(pool.clone(), q).await.unwrap();
+        assert_eq!(response.total_versions, 2);
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_handle_not_found(pool: PgPool) -> sqlx::Result<()> {
+        let result = handle(pool, query()).await;
+        assert!(matches!(result, Err(PreviewCascadeError::NotFound(..))));
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use crate::api::response::{ApiResponse, ErrorResponse};
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{header, StatusCode},
     response::{IntoResponse, Response},
     routing::{delete, get, post, put},
     Json, Router,
@@ -11,12 +11,20 @@ use sqlx::PgPool;
 
 use super::{
     commands::{
+        AddDependenciesCommand, AddDependenciesError, CascadeVersionCommand,
         CreateDataSourceCommand, CreateDataSourceError, DeleteDataSourceCommand,
-        DeleteDataSourceError, PublishVersionCommand, PublishVersionError, UpdateDataSourceCommand,
-        UpdateDataSourceError,
+        DeleteDataSourceError, PublishVersionCommand, PublishVersionError, UnyankVersionCommand,
+        UnyankVersionError, UpdateDataSourceCommand, UpdateDataSourceError, YankVersionCommand,
+        YankVersionError,
+    },
+    queries::{
+        DependencyGraphQuery, DiffVersionsQuery, GetDataSourceQuery, GetVersionMappingQuery,
+        GetVersionQuery, GraphDirection, GraphFormat, ListDataSourcesQuery, ListDependenciesQuery,
+        ListVersionMappingsQuery, PreviewCascadeError, PreviewCascadeQuery,
     },
-    queries::{GetDataSourceQuery, GetVersionQuery, ListDataSourcesQuery, ListDependenciesQuery},
 };
+use crate::features::version_files::sealing::{OverrideNotAllowed, SealOverrideParams};
+use crate::middleware::admin::AdminAuth;
 
 pub fn data_sources_routes() -> Router<PgPool> {
     Router::new()
@@ -27,12 +35,23 @@ pub fn data_sources_routes() -> Router<PgPool> {
         .route("/:org/:slug", put(update_data_source))
         .route("/:org/:slug", delete(delete_data_source))
         .route("/:org/:slug/versions", post(publish_version))
+        .route("/:org/:slug/version-mappings", get(list_version_mappings))
+        .route("/:org/:slug/version-mappings/:version", get(get_version_mapping))
+        .route("/:org/:slug/diff", get(diff_versions))
         .route("/:org/:slug/:version", get(get_version))
         .route(
             "/:org/:slug/:version/protein-metadata",
             get(super::queries::get_protein_metadata::get_protein_metadata),
         )
-        .route("/:org/:slug/:version/dependencies", get(list_dependencies))
+        .route(
+            "/:org/:slug/:version/dependencies",
+            get(list_dependencies).post(add_dependencies),
+        )
+        .route("/:org/:slug/:version/graph", get(dependency_graph))
+        .route("/:org/:slug/:version/dependents", get(list_dependents))
+        .route("/:org/:slug/:version/yank", post(yank_version).delete(unyank_version))
+        .route("/:org/:slug/:version/cascade", post(cascade_version))
+        .route("/:org/:slug/:version/cascade/dry-run", post(preview_cascade))
 }
 
 #[tracing::instrument(skip(pool, command), fields(slug = %command.slug, name = %command.name))]
@@ -190,6 +209,75 @@ async fn get_version(
     Ok((StatusCode::OK, Json(ApiResponse::success(response))).into_response())
 }
 
+#[tracing::instrument(skip(pool), fields(org = %org, slug = %slug))]
+async fn list_version_mappings(
+    State(pool): State<PgPool>,
+    Path((org, slug)): Path<(String, String)>,
+) -> Result<Response, DataSourceApiError> {
+    let query = ListVersionMappingsQuery {
+        organization_slug: org,
+        data_source_slug: slug,
+    };
+
+    let response = super::queries::list_version_mappings::handle(pool, query).await?;
+
+    tracing::debug!(count = response.len(), "Version mappings listed via API");
+
+    Ok((StatusCode::OK, Json(ApiResponse::success(response))).into_response())
+}
+
+/// Entry-level diff between two versions or releases
+///
+/// GET /:org/:slug/diff?from=2024_06&to=2025_01&change=modified&page=1&per_page=100
+#[tracing::instrument(skip(pool, query), fields(org = %org, slug = %slug))]
+async fn diff_versions(
+    State(pool): State<PgPool>,
+    Path((org, slug)): Path<(String, String)>,
+    Query(mut query): Query<DiffVersionsQuery>,
+) -> Result<Response, DataSourceApiError> {
+    query.organization_slug = org;
+    query.data_source_slug = slug;
+
+    let response = super::queries::diff_versions::handle(pool, query).await?;
+
+    tracing::debug!(
+        from = %response.from_version,
+        to = %response.to_version,
+        added = response.summary.added,
+        removed = response.summary.removed,
+        modified = response.summary.modified,
+        "Versions diffed via API"
+    );
+
+    let meta = json!({
+        "pagination": response.pagination
+    });
+
+    Ok((StatusCode::OK, Json(ApiResponse::success_with_meta(response, meta))).into_response())
+}
+
+#[tracing::instrument(skip(pool), fields(org = %org, slug = %slug, version = %version))]
+async fn get_version_mapping(
+    State(pool): State<PgPool>,
+    Path((org, slug, version)): Path<(String, String, String)>,
+) -> Result<Response, DataSourceApiError> {
+    let query = GetVersionMappingQuery {
+        organization_slug: org,
+        data_source_slug: slug,
+        version,
+    };
+
+    let response = super::queries::get_version_mapping::handle(pool, query).await?;
+
+    tracing::debug!(
+        internal_version = %response.internal_version,
+        external_version = %response.external_version,
+        "Version mapping retrieved via API"
+    );
+
+    Ok((StatusCode::OK, Json(ApiResponse::success(response))).into_response())
+}
+
 #[tracing::instrument(skip(pool, query), fields(org = %org, slug = %slug, version = %version))]
 async fn list_dependencies(
     State(pool): State<PgPool>,
@@ -211,6 +299,198 @@ async fn list_dependencies(
     Ok((StatusCode::OK, Json(ApiResponse::success_with_meta(response, meta))).into_response())
 }
 
+/// Forward or reverse dependency graph of a version
+///
+/// GET /:org/:slug/:version/graph?direction=reverse&depth=3&format=dot
+#[tracing::instrument(skip(pool, query), fields(org = %org, slug = %slug, version = %version))]
+async fn dependency_graph(
+    State(pool): State<PgPool>,
+    Path((org, slug, version)): Path<(String, String, String)>,
+    Query(mut query): Query<DependencyGraphQuery>,
+) -> Result<Response, DataSourceApiError> {
+    query.organization_slug = org;
+    query.data_source_slug = slug;
+    query.version = version;
+
+    render_graph(pool, query).await
+}
+
+/// Versions that depend on a version ("used by")
+///
+/// GET /:org/:slug/:version/dependents?depth=1&format=json
+///
+/// Same as the reverse graph, but only direct dependents unless `depth` is given.
+#[tracing::instrument(skip(pool, query), fields(org = %org, slug = %slug, version = %version))]
+async fn list_dependents(
+    State(pool): State<PgPool>,
+    Path((org, slug, version)): Path<(String, String, String)>,
+    Query(mut query): Query<DependencyGraphQuery>,
+) -> Result<Response, DataSourceApiError> {
+    query.organization_slug = org;
+    query.data_source_slug = slug;
+    query.version = version;
+    query.direction = GraphDirection::Reverse;
+    query.depth = query.depth.or(Some(1));
+
+    render_graph(pool, query).await
+}
+
+async fn render_graph(
+    pool: PgPool,
+    query: DependencyGraphQuery,
+) -> Result<Response, DataSourceApiError> {
+    let format = query.format;
+    let graph = super::queries::dependency_graph::handle(pool, query).await?;
+
+    tracing::debug!(
+        root = %graph.root,
+        direction = ?graph.direction,
+        nodes = graph.nodes.len(),
+        edges = graph.edges.len(),
+        truncated = graph.truncated,
+        "Dependency graph built via API"
+    );
+
+    let response = match format {
+        GraphFormat::Json => (StatusCode::OK, Json(ApiResponse::success(graph))).into_response(),
+        GraphFormat::Dot => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "text/vnd.graphviz; charset=utf-8")],
+            graph.to_dot(),
+        )
+            .into_response(),
+        GraphFormat::Mermaid => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+            graph.to_mermaid(),
+        )
+            .into_response(),
+    };
+    Ok(response)
+}
+
+/// Record dependencies of a version
+///
+/// Dependencies of a sealed version can only be changed by an administrator
+/// passing `?override_reason=...`.
+#[tracing::instrument(skip(pool, admin, command), fields(org = %org, slug = %slug, version = %version))]
+async fn add_dependencies(
+    State(pool): State<PgPool>,
+    Path((org, slug, version)): Path<(String, String, String)>,
+    Query(params): Query<SealOverrideParams>,
+    admin: Option<AdminAuth>,
+    Json(mut command): Json<AddDependenciesCommand>,
+) -> Result<Response, DataSourceApiError> {
+    command.organization_slug = org;
+    command.data_source_slug = slug;
+    command.version = version;
+    command.override_reason = params.authorize(admin)?;
+
+    let response = super::commands::add_dependencies::handle(pool, command).await?;
+
+    tracing::info!(
+        version_id = %response.version_id,
+        dependencies_added = response.dependencies_added,
+        "Dependencies added via API"
+    );
+
+    Ok((StatusCode::CREATED, Json(ApiResponse::success(response))).into_response())
+}
+
+/// Yank a published version (admin only)
+#[tracing::instrument(skip(pool, _admin, command), fields(org = %org, slug = %slug, version = %version))]
+async fn yank_version(
+    State(pool): State<PgPool>,
+    _admin: AdminAuth,
+    Path((org, slug, version)): Path<(String, String, String)>,
+    Json(mut command): Json<YankVersionCommand>,
+) -> Result<Response, DataSourceApiError> {
+    command.organization_slug = org;
+    command.data_source_slug = slug;
+    command.version = version;
+
+    let response = super::commands::yank::handle(pool, command).await?;
+
+    tracing::info!(
+        version_id = %response.version_id,
+        reason = ?response.yanked_reason,
+        "Version yanked via API"
+    );
+
+    Ok((StatusCode::OK, Json(ApiResponse::success(response))).into_response())
+}
+
+/// Restore a yanked version (admin only)
+#[tracing::instrument(skip(pool, _admin), fields(org = %org, slug = %slug, version = %version))]
+async fn unyank_version(
+    State(pool): State<PgPool>,
+    _admin: AdminAuth,
+    Path((org, slug, version)): Path<(String, String, String)>,
+) -> Result<Response, DataSourceApiError> {
+    let command = UnyankVersionCommand {
+        organization_slug: org,
+        data_source_slug: slug,
+        version,
+    };
+
+    let response = super::commands::unyank::handle(pool, command).await?;
+
+    tracing::info!(version_id = %response.version_id, "Version unyanked via API");
+
+    Ok((StatusCode::OK, Json(ApiResponse::success(response))).into_response())
+}
+
+/// Preview the dependents a version's bump would cascade to (admin only)
+///
+/// POST /:org/:slug/:version/cascade/dry-run with `{"exclude": [<entry id>], "max_depth": 5}`
+#[tracing::instrument(skip(pool, _admin, query), fields(org = %org, slug = %slug, version = %version))]
+async fn preview_cascade(
+    State(pool): State<PgPool>,
+    _admin: AdminAuth,
+    Path((org, slug, version)): Path<(String, String, String)>,
+    Json(mut query): Json<PreviewCascadeQuery>,
+) -> Result<Response, DataSourceApiError> {
+    query.organization_slug = org;
+    query.data_source_slug = slug;
+    query.version = version;
+
+    let response = super::queries::preview_cascade::handle(pool, query).await?;
+
+    tracing::debug!(
+        total_versions = response.total_versions,
+        excluded = response.excluded.len(),
+        "Cascade previewed via API"
+    );
+
+    Ok((StatusCode::OK, Json(ApiResponse::success(response))).into_response())
+}
+
+/// Cascade a version's bump to its dependents (admin only)
+///
+/// POST /:org/:slug/:version/cascade with the body of a reviewed dry run
+#[tracing::instrument(skip(pool, _admin, command), fields(org = %org, slug = %slug, version = %version))]
+async fn cascade_version(
+    State(pool): State<PgPool>,
+    _admin: AdminAuth,
+    Path((org, slug, version)): Path<(String, String, String)>,
+    Json(mut command): Json<CascadeVersionCommand>,
+) -> Result<Response, DataSourceApiError> {
+    command.organization_slug = org;
+    command.data_source_slug = slug;
+    command.version = version;
+
+    let response = super::commands::cascade::handle(pool, command).await?;
+
+    tracing::info!(
+        source_version_id = %response.source_version_id,
+        cascaded = response.cascaded.len(),
+        excluded = response.excluded.len(),
+        "Version cascaded via API"
+    );
+
+    Ok((StatusCode::OK, Json(ApiResponse::success(response))).into_response())
+}
+
 #[derive(Debug)]
 #[allow(clippy::enum_variant_names)]
 enum DataSourceApiError {
@@ -218,10 +498,19 @@ enum DataSourceApiError {
     UpdateError(UpdateDataSourceError),
     DeleteError(DeleteDataSourceError),
     PublishError(PublishVersionError),
+    AddDependenciesError(AddDependenciesError),
+    OverrideNotAllowed(OverrideNotAllowed),
+    YankError(YankVersionError),
+    UnyankError(UnyankVersionError),
     GetError(super::queries::GetDataSourceError),
     ListError(super::queries::ListDataSourcesError),
     GetVersionError(super::queries::GetVersionError),
     ListDependenciesError(super::queries::ListDependenciesError),
+    GetVersionMappingError(super::queries::GetVersionMappingError),
+    ListVersionMappingsError(super::queries::ListVersionMappingsError),
+    DiffVersionsError(super::queries::DiffVersionsError),
+    DependencyGraphError(super::queries::DependencyGraphError),
+    CascadeError(PreviewCascadeError),
 }
 
 impl From<CreateDataSourceError> for DataSourceApiError {
@@ -248,6 +537,30 @@ impl From<PublishVersionError> for DataSourceApiError {
     }
 }
 
+impl From<AddDependenciesError> for DataSourceApiError {
+    fn from(err: AddDependenciesError) -> Self {
+        Self::AddDependenciesError(err)
+    }
+}
+
+impl From<OverrideNotAllowed> for DataSourceApiError {
+    fn from(err: OverrideNotAllowed) -> Self {
+        Self::OverrideNotAllowed(err)
+    }
+}
+
+impl From<YankVersionError> for DataSourceApiError {
+    fn from(err: YankVersionError) -> Self {
+        Self::YankError(err)
+    }
+}
+
+impl From<UnyankVersionError> for DataSourceApiError {
+    fn from(err: UnyankVersionError) -> Self {
+        Self::UnyankError(err)
+    }
+}
+
 impl From<super::queries::GetDataSourceError> for DataSourceApiError {
     fn from(err: super::queries::GetDataSourceError) -> Self {
         Self::GetError(err)
@@ -272,6 +585,36 @@ impl From<super::queries::ListDependenciesError> for DataSourceApiError {
     }
 }
 
+impl From<super::queries::GetVersionMappingError> for DataSourceApiError {
+    fn from(err: super::queries::GetVersionMappingError) -> Self {
+        Self::GetVersionMappingError(err)
+    }
+}
+
+impl From<super::queries::DiffVersionsError> for DataSourceApiError {
+    fn from(err: super::queries::DiffVersionsError) -> Self {
+        Self::DiffVersionsError(err)
+    }
+}
+
+impl From<super::queries::DependencyGraphError> for DataSourceApiError {
+    fn from(err: super::queries::DependencyGraphError) -> Self {
+        Self::DependencyGraphError(err)
+    }
+}
+
+impl From<PreviewCascadeError> for DataSourceApiError {
+    fn from(err: PreviewCascadeError) -> Self {
+        Self::CascadeError(err)
+    }
+}
+
+impl From<super::queries::ListVersionMappingsError> for DataSourceApiError {
+    fn from(err: super::queries::ListVersionMappingsError) -> Self {
+        Self::ListVersionMappingsError(err)
+    }
+}
+
 impl IntoResponse for DataSourceApiError {
     fn into_response(self) -> Response {
         match self {
@@ -329,7 +672,9 @@ impl IntoResponse for DataSourceApiError {
 
             DataSourceApiError::PublishError(PublishVersionError::VersionRequired)
             | DataSourceApiError::PublishError(PublishVersionError::VersionLength)
-            | DataSourceApiError::PublishError(PublishVersionError::InvalidSize) => {
+            | DataSourceApiError::PublishError(PublishVersionError::InvalidSize)
+            | DataSourceApiError::PublishError(PublishVersionError::InvalidFile(_))
+            | DataSourceApiError::PublishError(PublishVersionError::InvalidDependency(_)) => {
                 let error = ErrorResponse::new("VALIDATION_ERROR", self.to_string());
                 (StatusCode::BAD_REQUEST, Json(error)).into_response()
             },
@@ -347,6 +692,65 @@ impl IntoResponse for DataSourceApiError {
                 (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
             },
 
+            DataSourceApiError::AddDependenciesError(
+                AddDependenciesError::OrganizationSlugRequired,
+            )
+            | DataSourceApiError::AddDependenciesError(
+                AddDependenciesError::DataSourceSlugRequired,
+            )
+            | DataSourceApiError::AddDependenciesError(AddDependenciesError::VersionRequired)
+            | DataSourceApiError::AddDependenciesError(
+                AddDependenciesError::DependenciesRequired,
+            )
+            | DataSourceApiError::AddDependenciesError(AddDependenciesError::InvalidSpec(_))
+            | DataSourceApiError::AddDependenciesError(
+                AddDependenciesError::InvalidDependencyType(_),
+            )
+            | DataSourceApiError::AddDependenciesError(AddDependenciesError::SelfDependency(_)) => {
+                let error = ErrorResponse::new("VALIDATION_ERROR", self.to_string());
+                (StatusCode::BAD_REQUEST, Json(error)).into_response()
+            },
+            DataSourceApiError::AddDependenciesError(AddDependenciesError::VersionNotFound(
+                _,
+                _,
+                _,
+            ))
+            | DataSourceApiError::AddDependenciesError(AddDependenciesError::DependencyNotFound(
+                _,
+            )) => {
+                let error = ErrorResponse::new("NOT_FOUND", self.to_string());
+                (StatusCode::NOT_FOUND, Json(error)).into_response()
+            },
+            DataSourceApiError::AddDependenciesError(AddDependenciesError::VersionSealed(..)) => {
+                let error = ErrorResponse::new("VERSION_SEALED", self.to_string());
+                (StatusCode::CONFLICT, Json(error)).into_response()
+            },
+            DataSourceApiError::OverrideNotAllowed(_) => {
+                let error = ErrorResponse::new("FORBIDDEN", self.to_string());
+                (StatusCode::FORBIDDEN, Json(error)).into_response()
+            },
+            DataSourceApiError::AddDependenciesError(AddDependenciesError::Database(_)) => {
+                tracing::error!("Database error while adding dependencies: {}", self);
+                let error = ErrorResponse::new("INTERNAL_ERROR", "A database error occurred");
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            },
+
+            DataSourceApiError::YankError(YankVersionError::NotFound(_, _, _))
+            | DataSourceApiError::UnyankError(UnyankVersionError::NotFound(_, _, _)) => {
+                let error = ErrorResponse::new("NOT_FOUND", self.to_string());
+                (StatusCode::NOT_FOUND, Json(error)).into_response()
+            },
+            DataSourceApiError::YankError(YankVersionError::Database(_))
+            | DataSourceApiError::UnyankError(UnyankVersionError::Database(_)) => {
+                tracing::error!("Database error while changing yank state: {}", self);
+                let error = ErrorResponse::new("INTERNAL_ERROR", "A database error occurred");
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            },
+            DataSourceApiError::YankError(_) | DataSourceApiError::UnyankError(_) => {
+                let error = ErrorResponse::new("VALIDATION_ERROR", self.to_string());
+                (StatusCode::BAD_REQUEST, Json(error)).into_response()
+            },
+
             DataSourceApiError::GetError(
                 super::queries::GetDataSourceError::OrganizationSlugRequired,
             )
@@ -433,6 +837,114 @@ impl IntoResponse for DataSourceApiError {
                 let error = ErrorResponse::new("INTERNAL_ERROR", "A database error occurred");
                 (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
             },
+
+            DataSourceApiError::GetVersionMappingError(
+                super::queries::GetVersionMappingError::OrganizationSlugRequired,
+            )
+            | DataSourceApiError::GetVersionMappingError(
+                super::queries::GetVersionMappingError::DataSourceSlugRequired,
+            )
+            | DataSourceApiError::GetVersionMappingError(
+                super::queries::GetVersionMappingError::VersionRequired,
+            )
+            | DataSourceApiError::ListVersionMappingsError(
+                super::queries::ListVersionMappingsError::OrganizationSlugRequired,
+            )
+            | DataSourceApiError::ListVersionMappingsError(
+                super::queries::ListVersionMappingsError::DataSourceSlugRequired,
+            ) => {
+                let error = ErrorResponse::new("VALIDATION_ERROR", self.to_string());
+                (StatusCode::BAD_REQUEST, Json(error)).into_response()
+            },
+            DataSourceApiError::GetVersionMappingError(
+                super::queries::GetVersionMappingError::NotFound(_, _, _),
+            ) => {
+                let error = ErrorResponse::new("NOT_FOUND", self.to_string());
+                (StatusCode::NOT_FOUND, Json(error)).into_response()
+            },
+            DataSourceApiError::GetVersionMappingError(
+                super::queries::GetVersionMappingError::Database(_),
+            )
+            | DataSourceApiError::ListVersionMappingsError(
+                super::queries::ListVersionMappingsError::Database(_),
+            ) => {
+                tracing::error!("Database error during version mapping lookup: {}", self);
+                let error = ErrorResponse::new("INTERNAL_ERROR", "A database error occurred");
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            },
+
+            DataSourceApiError::DiffVersionsError(
+                super::queries::DiffVersionsError::OrganizationSlugRequired
+                | super::queries::DiffVersionsError::DataSourceSlugRequired
+                | super::queries::DiffVersionsError::VersionRequired
+                | super::queries::DiffVersionsError::InvalidPage
+                | super::queries::DiffVersionsError::InvalidPerPage,
+            ) => {
+                let error = ErrorResponse::new("VALIDATION_ERROR", self.to_string());
+                (StatusCode::BAD_REQUEST, Json(error)).into_response()
+            },
+            DataSourceApiError::DiffVersionsError(super::queries::DiffVersionsError::NotFound(
+                _,
+                _,
+                _,
+            )) => {
+                let error = ErrorResponse::new("NOT_FOUND", self.to_string());
+                (StatusCode::NOT_FOUND, Json(error)).into_response()
+            },
+            DataSourceApiError::DiffVersionsError(super::queries::DiffVersionsError::Database(
+                _,
+            )) => {
+                tracing::error!("Database error during version diff: {}", self);
+                let error = ErrorResponse::new("INTERNAL_ERROR", "A database error occurred");
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            },
+
+            DataSourceApiError::DependencyGraphError(
+                super::queries::DependencyGraphError::OrganizationSlugRequired
+                | super::queries::DependencyGraphError::DataSourceSlugRequired
+                | super::queries::DependencyGraphError::VersionRequired
+                | super::queries::DependencyGraphError::InvalidDepth,
+            ) => {
+                let error = ErrorResponse::new("VALIDATION_ERROR", self.to_string());
+                (StatusCode::BAD_REQUEST, Json(error)).into_response()
+            },
+            DataSourceApiError::DependencyGraphError(
+                super::queries::DependencyGraphError::NotFound(_, _, _),
+            ) => {
+                let error = ErrorResponse::new("NOT_FOUND", self.to_string());
+                (StatusCode::NOT_FOUND, Json(error)).into_response()
+            },
+            DataSourceApiError::DependencyGraphError(
+                super::queries::DependencyGraphError::Database(_),
+            ) => {
+                tracing::error!("Database error while building dependency graph: {}", self);
+                let error = ErrorResponse::new("INTERNAL_ERROR", "A database error occurred");
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            },
+            DataSourceApiError::CascadeError(
+                PreviewCascadeError::OrganizationSlugRequired
+                | PreviewCascadeError::DataSourceSlugRequired
+                | PreviewCascadeError::VersionRequired
+                | PreviewCascadeError::InvalidMaxDepth,
+            ) => {
+                let error = ErrorResponse::new("VALIDATION_ERROR", self.to_string());
+                (StatusCode::BAD_REQUEST, Json(error)).into_response()
+            },
+            DataSourceApiError::CascadeError(PreviewCascadeError::NotFound(_, _, _)) => {
+                let error = ErrorResponse::new("NOT_FOUND", self.to_string());
+                (StatusCode::NOT_FOUND, Json(error)).into_response()
+            },
+            DataSourceApiError::CascadeError(PreviewCascadeError::NoChangelog(_, _, _)) => {
+                let error = ErrorResponse::new("CONFLICT", self.to_string());
+                (StatusCode::CONFLICT, Json(error)).into_response()
+            },
+            DataSourceApiError::CascadeError(
+                PreviewCascadeError::Cascade(_) | PreviewCascadeError::Database(_),
+            ) => {
+                tracing::error!("Error during version cascade: {}", self);
+                let error = ErrorResponse::new("INTERNAL_ERROR", "Failed to compute the cascade");
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            },
         }
     }
 }
@@ -444,10 +956,19 @@ impl std::fmt::Display for DataSourceApiError {
             Self::UpdateError(e) => write!(f, "{}", e),
             Self::DeleteError(e) => write!(f, "{}", e),
             Self::PublishError(e) => write!(f, "{}", e),
+            Self::AddDependenciesError(e) => write!(f, "{}", e),
+            Self::OverrideNotAllowed(e) => write!(f, "{}", e),
+            Self::YankError(e) => write!(f, "{}", e),
+            Self::UnyankError(e) => write!(f, "{}", e),
             Self::GetError(e) => write!(f, "{}", e),
             Self::ListError(e) => write!(f, "{}", e),
             Self::GetVersionError(e) => write!(f, "{}", e),
             Self::ListDependenciesError(e) => write!(f, "{}", e),
+            Self::GetVersionMappingError(e) => write!(f, "{}", e),
+            Self::ListVersionMappingsError(e) => write!(f, "{}", e),
+            Self::DiffVersionsError(e) => write!(f, "{}", e),
+            Self::DependencyGraphError(e) => write!(f, "{}", e),
+            Self::CascadeError(e) => write!(f, "{}", e),
         }
     }
 }
@@ -43,7 +43,7 @@ pub struct UploadFileCommand {
 pub struct UploadFileResponse {
     /// S3 object key
     pub key: String,
-    /// MD5 checksum of the uploaded content
+    /// SHA-256 checksum of the uploaded content
     pub checksum: String,
     /// Size of the uploaded file in bytes
     pub size: i64,
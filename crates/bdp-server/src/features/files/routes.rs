@@ -1,7 +1,14 @@
+//! File routes
+//!
+//! Uploads are refused for versions that are already sealed; administrators
+//! can override this with `?override_reason=...` and an admin token.
+
 use crate::api::response::{ApiResponse, ErrorResponse};
-use crate::storage::Storage;
+use crate::features::version_files::sealing::{self, OverrideNotAllowed, SealOverrideParams};
+use crate::features::FeatureState;
+use crate::middleware::admin::AdminAuth;
 use axum::{
-    extract::{Multipart, Path, State},
+    extract::{Multipart, Path, Query, State},
     http::StatusCode,
     response::{IntoResponse, Response},
     routing::post,
@@ -13,16 +20,33 @@ use super::{
     queries::{DownloadFileError, DownloadFileQuery},
 };
 
-pub fn files_routes() -> Router<Storage> {
+pub fn files_routes() -> Router<FeatureState> {
     Router::new().route("/:org/:name/:version/:filename", post(upload_file).get(download_file))
 }
 
-#[tracing::instrument(skip(storage, multipart), fields(org = %org, name = %name, version = %version, filename = %filename))]
+#[tracing::instrument(skip(state, admin, multipart), fields(org = %org, name = %name, version = %version, filename = %filename))]
 async fn upload_file(
-    State(storage): State<Storage>,
+    State(state): State<FeatureState>,
     Path((org, name, version, filename)): Path<(String, String, String, String)>,
+    Query(params): Query<SealOverrideParams>,
+    admin: Option<AdminAuth>,
     mut multipart: Multipart,
 ) -> Result<Response, FileApiError> {
+    let override_reason = params.authorize(admin)?;
+    let sealed = sealing::find_sealed_version(&state.db, &org, &name, &version).await?;
+
+    if let Some(sealed) = &sealed {
+        if override_reason.is_none() {
+            sealing::record_rejected_write(
+                &state.db,
+                sealed.id,
+                serde_json::json!({ "upload": filename }),
+            )
+            .await;
+            return Err(FileApiError::VersionSealed(format!("{}/{}@{}", org, name, version)));
+        }
+    }
+
     let mut content: Option<Vec<u8>> = None;
     let mut content_type: Option<String> = None;
 
@@ -61,7 +85,17 @@ async fn upload_file(
         content_type,
     };
 
-    let response = super::commands::upload::handle(storage, command).await?;
+    let response = super::commands::upload::handle(state.storage, command).await?;
+
+    if let (Some(sealed), Some(reason)) = (sealed, override_reason) {
+        sealing::record_override(
+            &state.db,
+            sealed.id,
+            &reason,
+            serde_json::json!({ "upload": response.key, "checksum": response.checksum }),
+        )
+        .await;
+    }
 
     tracing::info!(
         key = %response.key,
@@ -73,9 +107,9 @@ async fn upload_file(
     Ok((StatusCode::CREATED, Json(ApiResponse::success(response))).into_response())
 }
 
-#[tracing::instrument(skip(storage), fields(org = %org, name = %name, version = %version, filename = %filename))]
+#[tracing::instrument(skip(state), fields(org = %org, name = %name, version = %version, filename = %filename))]
 async fn download_file(
-    State(storage): State<Storage>,
+    State(state): State<FeatureState>,
     Path((org, name, version, filename)): Path<(String, String, String, String)>,
 ) -> Result<Response, FileApiError> {
     let query = DownloadFileQuery {
@@ -85,7 +119,7 @@ async fn download_file(
         filename,
     };
 
-    let response = super::queries::download::handle(storage, query).await?;
+    let response = super::queries::download::handle(state.storage, query).await?;
 
     tracing::debug!(
         presigned_url = %response.presigned_url,
@@ -98,8 +132,23 @@ async fn download_file(
 
 #[derive(Debug)]
 enum FileApiError {
+    VersionSealed(String),
+    OverrideNotAllowed(OverrideNotAllowed),
     UploadError(UploadFileError),
     DownloadError(DownloadFileError),
+    Database(sqlx::Error),
+}
+
+impl From<sqlx::Error> for FileApiError {
+    fn from(err: sqlx::Error) -> Self {
+        Self::Database(err)
+    }
+}
+
+impl From<OverrideNotAllowed> for FileApiError {
+    fn from(err: OverrideNotAllowed) -> Self {
+        Self::OverrideNotAllowed(err)
+    }
 }
 
 impl From<UploadFileError> for FileApiError {
@@ -117,6 +166,19 @@ impl From<DownloadFileError> for FileApiError {
 impl IntoResponse for FileApiError {
     fn into_response(self) -> Response {
         match self {
+            FileApiError::VersionSealed(_) => {
+                let error = ErrorResponse::new("VERSION_SEALED", self.to_string());
+                (StatusCode::CONFLICT, Json(error)).into_response()
+            },
+            FileApiError::OverrideNotAllowed(_) => {
+                let error = ErrorResponse::new("FORBIDDEN", self.to_string());
+                (StatusCode::FORBIDDEN, Json(error)).into_response()
+            },
+            FileApiError::Database(_) => {
+                tracing::error!("Database error during file upload: {}", self);
+                let error = ErrorResponse::new("INTERNAL_ERROR", "A database error occurred");
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            },
             FileApiError::UploadError(UploadFileError::OrgRequired)
             | FileApiError::UploadError(UploadFileError::NameRequired)
             | FileApiError::UploadError(UploadFileError::VersionRequired)
@@ -155,8 +217,13 @@ impl IntoResponse for FileApiError {
 impl std::fmt::Display for FileApiError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Self::VersionSealed(spec) => {
+                write!(f, "Version '{}' is sealed; its files cannot be replaced", spec)
+            },
+            Self::OverrideNotAllowed(e) => write!(f, "{}", e),
             Self::UploadError(e) => write!(f, "{}", e),
             Self::DownloadError(e) => write!(f, "{}", e),
+            Self::Database(e) => write!(f, "Database error: {}", e),
         }
     }
 }
@@ -169,6 +236,9 @@ mod tests {
     fn test_error_display() {
         let err = FileApiError::UploadError(UploadFileError::FilenameRequired);
         assert!(err.to_string().contains("Filename is required"));
+
+        let err = FileApiError::VersionSealed("my-lab/proteome@1.0".to_string());
+        assert!(err.to_string().contains("is sealed"));
     }
 
     #[test]
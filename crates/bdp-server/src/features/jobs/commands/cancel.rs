@@ -0,0 +1,192 @@
+//! Cancel job command
+//!
+//! Marks a running ingestion job as cancelled. Its pending work units are
+//! cancelled too, so workers stop claiming them; units already being processed
+//! finish, and the pipeline stops before its next phase.
+
+use mediator::Request;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::audit::{create_audit_entry, AuditAction, CreateAuditEntry, ResourceType};
+
+/// Command to cancel an ingestion job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CancelJobCommand {
+    pub job_id: Uuid,
+}
+
+/// State of a job after cancellation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CancelJobResponse {
+    pub job_id: Uuid,
+    pub status: String,
+    /// Work units that will no longer be claimed
+    pub work_units_cancelled: i64,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CancelJobError {
+    #[error("Job '{0}' not found")]
+    NotFound(Uuid),
+    #[error("Job '{0}' is already {1} and cannot be cancelled")]
+    AlreadyFinished(Uuid, String),
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl Request<Result<CancelJobResponse, CancelJobError>> for CancelJobCommand {}
+
+impl crate::cqrs::middleware::Command for CancelJobCommand {}
+
+/// Handles the cancel job command
+#[tracing::instrument(skip(pool))]
+pub async fn handle(
+    pool: PgPool,
+    command: CancelJobCommand,
+) -> Result<CancelJobResponse, CancelJobError> {
+    let mut tx = pool.begin().await?;
+
+    let status = sqlx::query_scalar!(
+        "SELECT status FROM ingestion_jobs WHERE id = $1 FOR UPDATE",
+        command.job_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(CancelJobError::NotFound(command.job_id))?;
+
+    if matches!(status.as_str(), "completed" | "failed" | "cancelled") {
+        return Err(CancelJobError::AlreadyFinished(command.job_id, status));
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE ingestion_jobs
+        SET status = 'cancelled', completed_at = NOW()
+        WHERE id = $1
+        "#,
+        command.job_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let work_units_cancelled = sqlx::query!(
+        r#"
+        UPDATE ingestion_work_units
+        SET status = 'cancelled'
+        WHERE job_id = $1 AND status IN ('pending', 'claimed')
+        "#,
+        command.job_id
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected() as i64;
+
+    tx.commit().await?;
+
+    if let Err(e) = create_audit_entry(
+        &pool,
+        CreateAuditEntry::builder()
+            .action(AuditAction::Ingest)
+            .resource_type(ResourceType::IngestionJob)
+            .resource_id(Some(command.job_id))
+            .metadata(serde_json::json!({
+                "operation": "cancel",
+                "previous_status": status,
+                "work_units_cancelled": work_units_cancelled,
+            }))
+            .build(),
+    )
+    .await
+    {
+        tracing::warn!(error = %e, "Failed to audit job cancellation");
+    }
+
+    Ok(CancelJobResponse {
+        job_id: command.job_id,
+        status: "cancelled".to_string(),
+        work_units_cancelled,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn insert_job(pool: &PgPool, status: &str) -> sqlx::Result<Uuid> {
+        let org_id = sqlx::query_scalar!(
+            "INSERT INTO organizations (slug, name, is_system) VALUES ('uniprot', 'UniProt', true) RETURNING id"
+        )
+        .fetch_one(pool)
+        .await?;
+
+        sqlx::query_scalar!(
+            r#"
+            INSERT INTO ingestion_jobs (organization_id, job_type, external_version, internal_version, status)
+            VALUES ($1, 'uniprot_swissprot', '2025_01', '1.0', $2)
+            RETURNING id
+            "#,
+            org_id,
+            status
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    async fn insert_work_unit(
+        pool: &PgPool,
+        job_id: Uuid,
+        batch_number: i32,
+        status: &str,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO ingestion_work_units (job_id, unit_type, batch_number, start_offset, end_offset, status)
+            VALUES ($1, 'parse_store', $2, 0, 10, $3)
+            "#,
+            job_id,
+            batch_number,
+            status
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_cancel_stops_pending_work_units(pool: PgPool) -> sqlx::Result<()> {
+        let job_id = insert_job(&pool, "storing").await?;
+        insert_work_unit(&pool, job_id, 0, "completed").await?;
+        insert_work_unit(&pool, job_id, 1, "processing").await?;
+        insert_work_unit(&pool, job_id, 2, "pending").await?;
+
+        let response = handle(pool.clone(), CancelJobCommand { job_id })
+            .await
+            .unwrap();
+        assert_eq!(response.work_units_cancelled, 1);
+
+        let claimable = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!" FROM ingestion_work_units WHERE job_id = $1 AND status = 'pending'"#,
+            job_id
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(claimable, 0);
+
+        assert!(matches!(
+            handle(pool.clone(), CancelJobCommand { job_id }).await,
+            Err(CancelJobError::AlreadyFinished(..))
+        ));
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_cancel_unknown_job(pool: PgPool) {
+        let job_id = Uuid::new_v4();
+        assert!(matches!(
+            handle(pool, CancelJobCommand { job_id }).await,
+            Err(CancelJobError::NotFound(_))
+        ));
+    }
+}
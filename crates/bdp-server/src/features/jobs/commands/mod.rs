@@ -0,0 +1,15 @@
+//! Job commands
+//!
+//! Administrative operations on ingestion jobs. Routes require an admin token.
+
+pub mod cancel;
+pub mod reprocess;
+pub mod retry;
+pub mod trigger;
+
+pub use cancel::{CancelJobCommand, CancelJobError, CancelJobResponse};
+pub use reprocess::{
+    ReprocessDeadLettersCommand, ReprocessDeadLettersError, ReprocessDeadLettersResponse,
+};
+pub use retry::{RetryJobCommand, RetryJobError, RetryJobResponse};
+pub use trigger::{TriggerIngestionCommand, TriggerIngestionError, TriggerIngestionResponse};
@@ -0,0 +1,183 @@
+//! Reprocess dead letters command
+//!
+//! Re-runs only the records of a finished ingestion job that failed parsing or
+//! storage, typically after a parser fix. An ingest job is enqueued that
+//! parses and stores the job's pending dead letters from their raw text.
+
+use apalis::prelude::TaskSink;
+use apalis_postgres::PostgresStorage;
+use mediator::Request;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::audit::{create_audit_entry, AuditAction, CreateAuditEntry, ResourceType};
+use crate::ingest::sources::IngestSource;
+use crate::ingest::SourceIngestJob;
+
+/// Command to reprocess the pending dead letters of an ingestion job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReprocessDeadLettersCommand {
+    pub job_id: Uuid,
+}
+
+/// Dead letters queued for reprocessing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReprocessDeadLettersResponse {
+    pub job_id: Uuid,
+    pub status: String,
+    pub dead_letters_pending: i64,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ReprocessDeadLettersError {
+    #[error("Job '{0}' not found")]
+    NotFound(Uuid),
+    #[error("Job '{0}' is still {1}; only finished jobs can be reprocessed")]
+    NotFinished(Uuid, String),
+    #[error("Job '{0}' has no pending dead letters to reprocess")]
+    NothingToReprocess(Uuid),
+    #[error("No ingestion pipeline for organization '{0}'")]
+    UnknownSource(String),
+    #[error("Failed to enqueue ingestion job: {0}")]
+    Queue(String),
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl Request<Result<ReprocessDeadLettersResponse, ReprocessDeadLettersError>>
+    for ReprocessDeadLettersCommand
+{
+}
+
+impl crate::cqrs::middleware::Command for ReprocessDeadLettersCommand {}
+
+/// Handles the reprocess dead letters command
+#[tracing::instrument(skip(pool))]
+pub async fn handle(
+    pool: PgPool,
+    command: ReprocessDeadLettersCommand,
+) -> Result<ReprocessDeadLettersResponse, ReprocessDeadLettersError> {
+    let job = sqlx::query!(
+        r#"
+        SELECT j.status, j.organization_id, j.external_version, o.slug as organization_slug
+        FROM ingestion_jobs j
+        JOIN organizations o ON j.organization_id = o.id
+        WHERE j.id = $1
+        "#,
+        command.job_id
+    )
+    .fetch_optional(&pool)
+    .await?
+    .ok_or(ReprocessDeadLettersError::NotFound(command.job_id))?;
+
+    if !matches!(job.status.as_str(), "completed" | "failed" | "cancelled") {
+        return Err(ReprocessDeadLettersError::NotFinished(command.job_id, job.status));
+    }
+
+    let source = IngestSource::from_organization_slug(&job.organization_slug)
+        .ok_or_else(|| ReprocessDeadLettersError::UnknownSource(job.organization_slug.clone()))?;
+
+    let dead_letters_pending = sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(*) as "count!"
+        FROM ingestion_dead_letters
+        WHERE job_id = $1 AND status = 'pending'
+        "#,
+        command.job_id
+    )
+    .fetch_one(&pool)
+    .await?;
+
+    if dead_letters_pending == 0 {
+        return Err(ReprocessDeadLettersError::NothingToReprocess(command.job_id));
+    }
+
+    PostgresStorage::<SourceIngestJob>::new(&pool)
+        .push(
+            SourceIngestJob::new(source, job.organization_id, job.external_version)
+                .with_dead_letters_of(command.job_id),
+        )
+        .await
+        .map_err(|e| ReprocessDeadLettersError::Queue(format!("{:?}", e)))?;
+
+    if let Err(e) = create_audit_entry(
+        &pool,
+        CreateAuditEntry::builder()
+            .action(AuditAction::Ingest)
+            .resource_type(ResourceType::IngestionJob)
+            .resource_id(Some(command.job_id))
+            .metadata(serde_json::json!({
+                "operation": "reprocess_dead_letters",
+                "dead_letters_pending": dead_letters_pending,
+            }))
+            .build(),
+    )
+    .await
+    {
+        tracing::warn!(error = %e, "Failed to audit dead letter reprocessing");
+    }
+
+    Ok(ReprocessDeadLettersResponse {
+        job_id: command.job_id,
+        status: job.status,
+        dead_letters_pending,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn insert_job(pool: &PgPool, status: &str) -> sqlx::Result<Uuid> {
+        let org_id = sqlx::query_scalar::<_, Uuid>(
+            "INSERT INTO organizations (slug, name, is_system) VALUES ('uniprot', 'UniProt', true) RETURNING id",
+        )
+        .fetch_one(pool)
+        .await?;
+
+        sqlx::query_scalar::<_, Uuid>(
+            r#"
+            INSERT INTO ingestion_jobs (organization_id, job_type, external_version, internal_version, status)
+            VALUES ($1, 'uniprot_swissprot', '2025_01', '1.0', $2)
+            RETURNING id
+            "#,
+        )
+        .bind(org_id)
+        .bind(status)
+        .fetch_one(pool)
+        .await
+    }
+
+    #[sqlx::test]
+    async fn test_reprocess_requires_finished_job(pool: PgPool) -> sqlx::Result<()> {
+        let job_id = insert_job(&pool, "storing").await?;
+
+        assert!(matches!(
+            handle(pool.clone(), ReprocessDeadLettersCommand { job_id }).await,
+            Err(ReprocessDeadLettersError::NotFinished(..))
+        ));
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_reprocess_without_dead_letters(pool: PgPool) -> sqlx::Result<()> {
+        let job_id = insert_job(&pool, "completed").await?;
+
+        assert!(matches!(
+            handle(pool.clone(), ReprocessDeadLettersCommand { job_id }).await,
+            Err(ReprocessDeadLettersError::NothingToReprocess(_))
+        ));
+        assert!(matches!(
+            handle(
+                pool.clone(),
+                ReprocessDeadLettersCommand {
+                    job_id: Uuid::new_v4()
+                }
+            )
+            .await,
+            Err(ReprocessDeadLettersError::NotFound(_))
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,335 @@
+//! Retry job command
+//!
+//! Re-runs only the failed work units of a finished ingestion job, and the
+//! cancelled ones of a cancelled job. They are reset to pending with a fresh
+//! retry budget and an ingest job is enqueued that resumes the existing
+//! ingestion job instead of starting over. If the ingest job can't be
+//! enqueued, the job and its units are put back as they were.
+
+use apalis::prelude::TaskSink;
+use apalis_postgres::PostgresStorage;
+use mediator::Request;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::audit::{create_audit_entry, AuditAction, CreateAuditEntry, ResourceType};
+use crate::ingest::sources::IngestSource;
+use crate::ingest::SourceIngestJob;
+
+/// Command to retry the failed work units of an ingestion job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetryJobCommand {
+    pub job_id: Uuid,
+}
+
+/// State of a job after its failed work units were requeued
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetryJobResponse {
+    pub job_id: Uuid,
+    pub status: String,
+    pub work_units_retried: i64,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RetryJobError {
+    #[error("Job '{0}' not found")]
+    NotFound(Uuid),
+    #[error("Job '{0}' is still {1}; only finished jobs can be retried")]
+    NotFinished(Uuid, String),
+    #[error("Job '{0}' has no failed or cancelled work units to retry")]
+    NothingToRetry(Uuid),
+    #[error("No ingestion pipeline for organization '{0}'")]
+    UnknownSource(String),
+    #[error("Failed to enqueue ingestion job: {0}")]
+    Queue(String),
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl Request<Result<RetryJobResponse, RetryJobError>> for RetryJobCommand {}
+
+impl crate::cqrs::middleware::Command for RetryJobCommand {}
+
+/// Handles the retry job command
+#[tracing::instrument(skip(pool))]
+pub async fn handle(
+    pool: PgPool,
+    command: RetryJobCommand,
+) -> Result<RetryJobResponse, RetryJobError> {
+    let mut tx = pool.begin().await?;
+
+    let job = sqlx::query!(
+        r#"
+        SELECT j.status, j.organization_id, j.external_version, j.completed_at, j.records_failed,
+               o.slug as organization_slug
+        FROM ingestion_jobs j
+        JOIN organizations o ON j.organization_id = o.id
+        WHERE j.id = $1
+        FOR UPDATE OF j
+        "#,
+        command.job_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(RetryJobError::NotFound(command.job_id))?;
+
+    if !matches!(job.status.as_str(), "completed" | "failed" | "cancelled") {
+        return Err(RetryJobError::NotFinished(command.job_id, job.status));
+    }
+
+    let source = IngestSource::from_organization_slug(&job.organization_slug)
+        .ok_or_else(|| RetryJobError::UnknownSource(job.organization_slug.clone()))?;
+
+    // A cancelled job also never processed the units cancel.rs cancelled
+    let units = sqlx::query!(
+        r#"
+        UPDATE ingestion_work_units u
+        SET status = 'pending', retry_count = 0, worker_id = NULL,
+            claimed_at = NULL, heartbeat_at = NULL, completed_at = NULL
+        FROM ingestion_work_units previous
+        WHERE previous.id = u.id AND u.job_id = $1
+          AND (u.status = 'failed' OR ($2 AND u.status = 'cancelled'))
+        RETURNING u.id, previous.status as previous_status, u.record_count
+        "#,
+        command.job_id,
+        job.status == "cancelled"
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    let work_units_retried = units.len() as i64;
+
+    if work_units_retried == 0 {
+        return Err(RetryJobError::NothingToRetry(command.job_id));
+    }
+
+    // The records of failed units were counted as failed; they are pending again
+    let failed_records: i64 = units
+        .iter()
+        .filter(|u| u.previous_status == "failed")
+        .map(|u| i64::from(u.record_count.unwrap_or(0)))
+        .sum();
+
+    sqlx::query!(
+        r#"
+        UPDATE ingestion_jobs
+        SET status = 'pending', completed_at = NULL,
+            records_failed = GREATEST(COALESCE(records_failed, 0) - $2, 0)
+        WHERE id = $1
+        "#,
+        command.job_id,
+        failed_records
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    let pushed = PostgresStorage::<SourceIngestJob>::new(&pool)
+        .push(
+            SourceIngestJob::new(source, job.organization_id, job.external_version)
+                .with_retry_of(command.job_id),
+        )
+        .await;
+
+    // Nothing will run the pending units, so the job goes back to its
+    // finished state and can be retried again
+    if let Err(e) = pushed {
+        let unit_ids: Vec<Uuid> = units.iter().map(|u| u.id).collect();
+        let unit_statuses: Vec<String> = units.into_iter().map(|u| u.previous_status).collect();
+
+        let mut tx = pool.begin().await?;
+        sqlx::query!(
+            r#"
+            UPDATE ingestion_work_units u
+            SET status = previous.status
+            FROM UNNEST($1::uuid[], $2::text[]) AS previous(id, status)
+            WHERE u.id = previous.id
+            "#,
+            &unit_ids,
+            &unit_statuses
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            r#"
+            UPDATE ingestion_jobs
+            SET status = $2, completed_at = $3, records_failed = $4
+            WHERE id = $1
+            "#,
+            command.job_id,
+            job.status,
+            job.completed_at,
+            job.records_failed
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        return Err(RetryJobError::Queue(format!("{:?}", e)));
+    }
+
+    if let Err(e) = create_audit_entry(
+        &pool,
+        CreateAuditEntry::builder()
+            .action(AuditAction::Ingest)
+            .resource_type(ResourceType::IngestionJob)
+            .resource_id(Some(command.job_id))
+            .metadata(serde_json::json!({
+                "operation": "retry",
+                "previous_status": job.status,
+                "work_units_retried": work_units_retried,
+            }))
+            .build(),
+    )
+    .await
+    {
+        tracing::warn!(error = %e, "Failed to audit job retry");
+    }
+
+    Ok(RetryJobResponse {
+        job_id: command.job_id,
+        status: "pending".to_string(),
+        work_units_retried,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn insert_job(pool: &PgPool, status: &str) -> sqlx::Result<Uuid> {
+        let org_id = sqlx::query_scalar!(
+            "INSERT INTO organizations (slug, name, is_system) VALUES ('uniprot', 'UniProt', true) RETURNING id"
+        )
+        .fetch_one(pool)
+        .await?;
+
+        sqlx::query_scalar!(
+            r#"
+            INSERT INTO ingestion_jobs (organization_id, job_type, external_version, internal_version, status, records_failed)
+            VALUES ($1, 'uniprot_swissprot', '2025_01', '1.0', $2, 23)
+            RETURNING id
+            "#,
+            org_id,
+            status
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    async fn insert_work_unit(
+        pool: &PgPool,
+        job_id: Uuid,
+        batch_number: i32,
+        status: &str,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO ingestion_work_units (job_id, unit_type, batch_number, start_offset, end_offset, record_count, status, retry_count)
+            VALUES ($1, 'parse_store', $2, 0, 10, 10, $3, 3)
+            "#,
+            job_id,
+            batch_number,
+            status
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_retry_requeues_only_failed_units(pool: PgPool) -> sqlx::Result<()> {
+        let job_id = insert_job(&pool, "completed").await?;
+        insert_work_unit(&pool, job_id, 0, "completed").await?;
+        insert_work_unit(&pool, job_id, 1, "failed").await?;
+        insert_work_unit(&pool, job_id, 2, "failed").await?;
+
+        let response = handle(pool.clone(), RetryJobCommand { job_id })
+            .await
+            .unwrap();
+        assert_eq!(response.work_units_retried, 2);
+
+        let units = sqlx::query!(
+            "SELECT status, retry_count FROM ingestion_work_units WHERE job_id = $1 ORDER BY batch_number",
+            job_id
+        )
+        .fetch_all(&pool)
+        .await?;
+        assert_eq!(units[0].status, "completed");
+        assert!(units[1..]
+            .iter()
+            .all(|u| u.status == "pending" && u.retry_count == Some(0)));
+
+        let job =
+            sqlx::query!("SELECT status, records_failed FROM ingestion_jobs WHERE id = $1", job_id)
+                .fetch_one(&pool)
+                .await?;
+        // 3 dead-lettered records plus the 10 records of each failed unit
+        // were failed; only the units' records are pending again
+        assert_eq!(job.status, "pending");
+        assert_eq!(job.records_failed, Some(3));
+
+        // The job is running again, so it can't be retried twice
+        assert!(matches!(
+            handle(pool.clone(), RetryJobCommand { job_id }).await,
+            Err(RetryJobError::NotFinished(..))
+        ));
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_retry_requeues_cancelled_units_of_cancelled_job(
+        pool: PgPool,
+    ) -> sqlx::Result<()> {
+        let job_id = insert_job(&pool, "cancelled").await?;
+        insert_work_unit(&pool, job_id, 0, "completed").await?;
+        insert_work_unit(&pool, job_id, 1, "failed").await?;
+        insert_work_unit(&pool, job_id, 2, "cancelled").await?;
+
+        let response = handle(pool.clone(), RetryJobCommand { job_id })
+            .await
+            .unwrap();
+        assert_eq!(response.work_units_retried, 2);
+
+        let statuses = sqlx::query_scalar!(
+            "SELECT status FROM ingestion_work_units WHERE job_id = $1 ORDER BY batch_number",
+            job_id
+        )
+        .fetch_all(&pool)
+        .await?;
+        assert_eq!(statuses, ["completed", "pending", "pending"]);
+
+        // Only the failed unit's records had been counted as failed
+        let records_failed =
+            sqlx::query_scalar!("SELECT records_failed FROM ingestion_jobs WHERE id = $1", job_id)
+                .fetch_one(&pool)
+                .await?;
+        assert_eq!(records_failed, Some(13));
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_retry_leaves_cancelled_units_of_finished_job(pool: PgPool) -> sqlx::Result<()> {
+        let job_id = insert_job(&pool, "failed").await?;
+        insert_work_unit(&pool, job_id, 0, "cancelled").await?;
+
+        assert!(matches!(
+            handle(pool.clone(), RetryJobCommand { job_id }).await,
+            Err(RetryJobError::NothingToRetry(_))
+        ));
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_retry_without_failed_units(pool: PgPool) -> sqlx::Result<()> {
+        let job_id = insert_job(&pool, "failed").await?;
+        insert_work_unit(&pool, job_id, 0, "completed").await?;
+
+        assert!(matches!(
+            handle(pool.clone(), RetryJobCommand { job_id }).await,
+            Err(RetryJobError::NothingToRetry(_))
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,373 @@
+//! Trigger ingestion command
+//!
+//! Enqueues ingestion jobs for a source organization: either one explicit
+//! upstream version, the newest one (`latest` mode) or a range of releases
+//! (`historical` mode backfill).
+
+use apalis::prelude::TaskSink;
+use apalis_postgres::PostgresStorage;
+use mediator::Request;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::cmp::Ordering;
+use uuid::Uuid;
+
+use crate::audit::{create_audit_entry, AuditAction, CreateAuditEntry, ResourceType};
+use crate::ingest::config::{IngestConfig, IngestionMode};
+use crate::ingest::sources::{self, IngestSource};
+use crate::ingest::SourceIngestJob;
+
+/// Command to start ingestions for a source organization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TriggerIngestionCommand {
+    /// Slug of the organization the source ingests under (e.g. `uniprot`)
+    pub organization_slug: String,
+    /// Upstream version to ingest; takes precedence over `mode`
+    #[serde(default)]
+    pub external_version: Option<String>,
+    /// Which upstream versions to ingest (defaults to latest)
+    #[serde(default)]
+    pub mode: Option<IngestionMode>,
+}
+
+/// Versions enqueued by a trigger
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TriggerIngestionResponse {
+    pub source: IngestSource,
+    pub organization_id: Uuid,
+    /// Versions a job was enqueued for
+    pub enqueued: Vec<String>,
+    /// Versions skipped because they were already ingested
+    pub skipped: Vec<String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TriggerIngestionError {
+    #[error("Organization slug is required and cannot be empty")]
+    OrganizationSlugRequired,
+    #[error("External version cannot be empty")]
+    ExternalVersionEmpty,
+    #[error("Historical start version is required")]
+    StartVersionRequired,
+    #[error("No ingestion pipeline for organization '{0}'")]
+    UnknownSource(String),
+    #[error("No upstream versions of {0} match the request")]
+    NoVersions(IngestSource),
+    #[error("Failed to discover {0} versions: {1}")]
+    Discovery(IngestSource, String),
+    #[error("Failed to enqueue ingestion job: {0}")]
+    Queue(String),
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl Request<Result<TriggerIngestionResponse, TriggerIngestionError>> for TriggerIngestionCommand {}
+
+impl crate::cqrs::middleware::Command for TriggerIngestionCommand {}
+
+impl TriggerIngestionCommand {
+    pub fn validate(&self) -> Result<IngestSource, TriggerIngestionError> {
+        if self.organization_slug.trim().is_empty() {
+            return Err(TriggerIngestionError::OrganizationSlugRequired);
+        }
+        if matches!(&self.external_version, Some(v) if v.trim().is_empty()) {
+            return Err(TriggerIngestionError::ExternalVersionEmpty);
+        }
+        if let Some(IngestionMode::Historical(historical)) = &self.mode {
+            if historical.start_version.trim().is_empty() {
+                return Err(TriggerIngestionError::StartVersionRequired);
+            }
+        }
+
+        IngestSource::from_organization_slug(self.organization_slug.trim())
+            .ok_or_else(|| TriggerIngestionError::UnknownSource(self.organization_slug.clone()))
+    }
+}
+
+/// Pick the versions a mode asks for from the available ones (oldest first)
+///
+/// Bounds are placed by their position among the available versions, whose
+/// order comes from each source's version discovery, so releases such as
+/// InterPro `98.0` and `100.0` are never compared as text.
+pub fn select_versions(available: Vec<String>, mode: &IngestionMode) -> Vec<String> {
+    match mode {
+        IngestionMode::Latest(latest) => {
+            let first = latest
+                .ignore_before
+                .as_deref()
+                .map_or(0, |min| lower_bound(&available, min));
+            available
+                .into_iter()
+                .skip(first)
+                .last()
+                .into_iter()
+                .collect()
+        },
+        IngestionMode::Historical(historical) => {
+            let first = lower_bound(&available, &historical.start_version);
+            let end = historical
+                .end_version
+                .as_deref()
+                .map_or(available.len(), |max| upper_bound(&available, max));
+            available.into_iter().take(end).skip(first).collect()
+        },
+    }
+}
+
+/// Index of the first available version at or after `bound`
+fn lower_bound(available: &[String], bound: &str) -> usize {
+    available
+        .iter()
+        .position(|v| v == bound)
+        .unwrap_or_else(|| {
+            available.partition_point(|v| compare_versions(v, bound) == Ordering::Less)
+        })
+}
+
+/// Index just past the last available version at or before `bound`
+fn upper_bound(available: &[String], bound: &str) -> usize {
+    available
+        .iter()
+        .position(|v| v == bound)
+        .map(|i| i + 1)
+        .unwrap_or_else(|| {
+            available.partition_point(|v| compare_versions(v, bound) != Ordering::Greater)
+        })
+}
+
+/// Order version strings that aren't available releases, comparing runs of
+/// digits numerically (`99` < `113`, `98.0` < `100.0`, `2024_02` < `2024_10`)
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    fn segments(version: &str) -> Vec<Result<u64, &str>> {
+        let mut segments = Vec::new();
+        let mut rest = version;
+        while let Some(c) = rest.chars().next() {
+            let digit = c.is_ascii_digit();
+            let len = rest
+                .find(|c: char| c.is_ascii_digit() != digit)
+                .unwrap_or(rest.len());
+            let (segment, tail) = rest.split_at(len);
+            segments.push(match segment.parse() {
+                Ok(n) if digit => Ok(n),
+                _ => Err(segment),
+            });
+            rest = tail;
+        }
+        segments
+    }
+
+    segments(a).cmp(&segments(b))
+}
+
+/// Handles the trigger ingestion command
+///
+/// Versions that are already ingested are skipped, except for an explicitly
+/// requested version or a historical backfill with `skip_existing: false`.
+#[tracing::instrument(skip(pool))]
+pub async fn handle(
+    pool: PgPool,
+    command: TriggerIngestionCommand,
+) -> Result<TriggerIngestionResponse, TriggerIngestionError> {
+    let source = command.validate()?;
+    let organization_id = sources::ensure_organization(&pool, source)
+        .await
+        .map_err(|e| TriggerIngestionError::Discovery(source, e.to_string()))?;
+
+    let (versions, skip_existing) = match &command.external_version {
+        Some(version) => (vec![version.trim().to_string()], false),
+        None => {
+            let mode = command.mode.clone().unwrap_or_default();
+            // The upstream transports are configured like the scheduler's
+            let config = IngestConfig::from_env()
+                .map_err(|e| TriggerIngestionError::Discovery(source, format!("{:#}", e)))?;
+            let available = sources::available_versions(&pool, &config, source)
+                .await
+                .map_err(|e| TriggerIngestionError::Discovery(source, format!("{:#}", e)))?;
+            let skip_existing = match &mode {
+                IngestionMode::Latest(_) => true,
+                IngestionMode::Historical(historical) => historical.skip_existing,
+            };
+            (select_versions(available, &mode), skip_existing)
+        },
+    };
+
+    if versions.is_empty() {
+        return Err(TriggerIngestionError::NoVersions(source));
+    }
+
+    let mut queue = PostgresStorage::<SourceIngestJob>::new(&pool);
+    let mut enqueued = Vec::new();
+    let mut skipped = Vec::new();
+
+    for version in versions {
+        if skip_existing
+            && sources::is_ingested(&pool, organization_id, &version)
+                .await
+                .map_err(|e| TriggerIngestionError::Discovery(source, e.to_string()))?
+        {
+            skipped.push(version);
+            continue;
+        }
+
+        // Without skip_existing the worker re-ingests versions it already has
+        queue
+            .push(
+                SourceIngestJob::new(source, organization_id, version.clone())
+                    .with_force(!skip_existing),
+            )
+            .await
+            .map_err(|e| TriggerIngestionError::Queue(format!("{:?}", e)))?;
+
+        // Keep the scheduler from enqueueing the same version again
+        sqlx::query!(
+            r#"
+            INSERT INTO scheduled_ingestions (source, external_version)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+            "#,
+            source.as_str(),
+            version
+        )
+        .execute(&pool)
+        .await?;
+
+        enqueued.push(version);
+    }
+
+    if let Err(e) = create_audit_entry(
+        &pool,
+        CreateAuditEntry::builder()
+            .action(AuditAction::Ingest)
+            .resource_type(ResourceType::Organization)
+            .resource_id(Some(organization_id))
+            .metadata(serde_json::json!({
+                "operation": "trigger",
+                "source": source,
+                "enqueued": enqueued,
+                "skipped": skipped,
+            }))
+            .build(),
+    )
+    .await
+    {
+        tracing::warn!(error = %e, "Failed to audit ingestion trigger");
+    }
+
+    Ok(TriggerIngestionResponse {
+        source,
+        organization_id,
+        enqueued,
+        skipped,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::config::{HistoricalConfig, LatestConfig};
+
+    fn versions(v: &[&str]) -> Vec<String> {
+        v.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn command(org: &str) -> TriggerIngestionCommand {
+        TriggerIngestionCommand {
+            organization_slug: org.to_string(),
+            external_version: None,
+            mode: None,
+        }
+    }
+
+    #[test]
+    fn test_validation() {
+        assert_eq!(command("uniprot").validate().unwrap(), IngestSource::Uniprot);
+        assert!(matches!(
+            command("").validate(),
+            Err(TriggerIngestionError::OrganizationSlugRequired)
+        ));
+        assert!(matches!(
+            command("my-lab").validate(),
+            Err(TriggerIngestionError::UnknownSource(_))
+        ));
+
+        let mut empty_version = command("uniprot");
+        empty_version.external_version = Some(" ".to_string());
+        assert!(matches!(
+            empty_version.validate(),
+            Err(TriggerIngestionError::ExternalVersionEmpty)
+        ));
+    }
+
+    #[test]
+    fn test_mode_deserializes_from_tagged_json() {
+        let command: TriggerIngestionCommand = serde_json::from_value(serde_json::json!({
+            "organization_slug": "uniprot",
+            "mode": { "mode": "historical", "start_version": "2024_01" }
+        }))
+        .unwrap();
+
+        assert!(matches!(
+            command.mode,
+            Some(IngestionMode::Historical(HistoricalConfig { ref start_version, .. }))
+                if start_version == "2024_01"
+        ));
+    }
+
+    #[test]
+    fn test_select_latest() {
+        let available = versions(&["2024_01", "2024_02", "2024_03"]);
+        let mode = IngestionMode::Latest(LatestConfig::default());
+        assert_eq!(select_versions(available.clone(), &mode), versions(&["2024_03"]));
+
+        let mode = IngestionMode::Latest(LatestConfig {
+            ignore_before: Some("2025_01".to_string()),
+            ..LatestConfig::default()
+        });
+        assert!(select_versions(available, &mode).is_empty());
+    }
+
+    #[test]
+    fn test_select_historical_range() {
+        let available = versions(&["2023_12", "2024_01", "2024_02", "2024_03"]);
+        let mode = IngestionMode::Historical(HistoricalConfig {
+            start_version: "2024_01".to_string(),
+            end_version: Some("2024_02".to_string()),
+            ..HistoricalConfig::default()
+        });
+
+        assert_eq!(select_versions(available, &mode), versions(&["2024_01", "2024_02"]));
+    }
+
+    #[test]
+    fn test_select_across_digit_boundary() {
+        // InterPro and Ensembl releases, oldest first as discovered
+        let interpro = versions(&["98.0", "99.0", "100.0", "101.0"]);
+        let mode = IngestionMode::Historical(HistoricalConfig {
+            start_version: "99.0".to_string(),
+            end_version: Some("100.0".to_string()),
+            ..HistoricalConfig::default()
+        });
+        assert_eq!(select_versions(interpro.clone(), &mode), versions(&["99.0", "100.0"]));
+
+        let mode = IngestionMode::Latest(LatestConfig {
+            ignore_before: Some("99.0".to_string()),
+            ..LatestConfig::default()
+        });
+        assert_eq!(select_versions(interpro, &mode), versions(&["101.0"]));
+
+        // Bounds that aren't releases are placed numerically
+        let ensembl = versions(&["98", "99", "112", "113"]);
+        let mode = IngestionMode::Historical(HistoricalConfig {
+            start_version: "100".to_string(),
+            end_version: Some("112".to_string()),
+            ..HistoricalConfig::default()
+        });
+        assert_eq!(select_versions(ensembl.clone(), &mode), versions(&["112"]));
+
+        let mode = IngestionMode::Latest(LatestConfig {
+            ignore_before: Some("114".to_string()),
+            ..LatestConfig::default()
+        });
+        assert!(select_versions(ensembl, &mode).is_empty());
+    }
+}
@@ -0,0 +1,215 @@
+//! Job event streams
+//!
+//! Turns periodic job progress snapshots into `status` and `progress` events
+//! for the Server-Sent Events routes. Workers only write progress to the
+//! database, so polling it is what lets every server instance stream jobs run
+//! by any other instance.
+
+use futures::{stream, Stream, StreamExt};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::ingest::framework::JobProgress;
+
+use super::queries::job_progress::{handle as handle_job_progress, JobProgressQuery};
+use super::queries::JobProgressSnapshot;
+
+/// How often job progress is re-read for open streams
+pub const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Kind of change a job event reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobEventKind {
+    /// The job was seen for the first time or changed status
+    Status,
+    /// Records or work units moved without a status change
+    Progress,
+}
+
+impl JobEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobEventKind::Status => "status",
+            JobEventKind::Progress => "progress",
+        }
+    }
+}
+
+/// A change in a job's progress
+#[derive(Debug, Clone, Serialize)]
+pub struct JobEvent {
+    pub kind: JobEventKind,
+    pub job: JobProgressSnapshot,
+}
+
+/// Remembers the last progress of each job to diff new snapshots against
+#[derive(Debug, Default)]
+pub struct JobEventTracker {
+    last: HashMap<Uuid, JobProgress>,
+}
+
+impl JobEventTracker {
+    /// Jobs that have been seen and haven't finished yet
+    pub fn tracked(&self) -> Vec<Uuid> {
+        self.last.keys().copied().collect()
+    }
+
+    /// Whether a job has been seen and hasn't finished yet
+    pub fn is_tracking(&self, job_id: Uuid) -> bool {
+        self.last.contains_key(&job_id)
+    }
+
+    /// Compare snapshots with the previous ones and return what changed
+    ///
+    /// Finished jobs emit their final status and are then forgotten.
+    pub fn observe(&mut self, snapshots: Vec<JobProgressSnapshot>) -> Vec<JobEvent> {
+        let mut events = Vec::new();
+
+        for job in snapshots {
+            let kind = match self.last.get(&job.job_id) {
+                None => Some(JobEventKind::Status),
+                Some(previous) if previous.status != job.progress.status => {
+                    Some(JobEventKind::Status)
+                },
+                Some(previous) if *previous != job.progress => Some(JobEventKind::Progress),
+                Some(_) => None,
+            };
+
+            if job.progress.status.is_terminal() {
+                self.last.remove(&job.job_id);
+            } else {
+                self.last.insert(job.job_id, job.progress.clone());
+            }
+
+            if let Some(kind) = kind {
+                events.push(JobEvent { kind, job });
+            }
+        }
+
+        events
+    }
+}
+
+struct StreamState {
+    pool: PgPool,
+    job_id: Option<Uuid>,
+    tracker: JobEventTracker,
+    interval: tokio::time::Interval,
+    finished: bool,
+}
+
+/// Stream events for one job until it finishes, or for every active job
+pub fn job_events(pool: PgPool, job_id: Option<Uuid>) -> impl Stream<Item = JobEvent> + Send {
+    let state = StreamState {
+        pool,
+        job_id,
+        tracker: JobEventTracker::default(),
+        interval: tokio::time::interval(POLL_INTERVAL),
+        finished: false,
+    };
+
+    stream::unfold(state, |mut state| async move {
+        if state.finished {
+            return None;
+        }
+        state.interval.tick().await;
+
+        // Tracked jobs are queried by ID so their final status is reported
+        // even though they no longer count as active
+        let query = match state.job_id {
+            Some(job_id) => JobProgressQuery {
+                job_ids: vec![job_id],
+                include_active: false,
+            },
+            None => JobProgressQuery {
+                job_ids: state.tracker.tracked(),
+                include_active: true,
+            },
+        };
+
+        let events = match handle_job_progress(state.pool.clone(), query).await {
+            Ok(snapshots) => {
+                let events = state.tracker.observe(snapshots);
+                // A single job stream ends once the job finished or vanished
+                if let Some(job_id) = state.job_id {
+                    state.finished = !state.tracker.is_tracking(job_id);
+                }
+                events
+            },
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to read job progress");
+                Vec::new()
+            },
+        };
+
+        Some((events, state))
+    })
+    .flat_map(stream::iter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::framework::JobStatus;
+
+    fn snapshot(job_id: Uuid, status: JobStatus, records_stored: i64) -> JobProgressSnapshot {
+        JobProgressSnapshot {
+            job_id,
+            job_type: "uniprot_swissprot".to_string(),
+            external_version: "2025_01".to_string(),
+            progress: JobProgress {
+                status,
+                total_records: 100,
+                records_processed: records_stored,
+                records_stored,
+                records_failed: 0,
+                records_skipped: 0,
+                work_units_pending: 0,
+                work_units_claimed: 0,
+                work_units_processing: 0,
+                work_units_completed: 0,
+                work_units_failed: 0,
+            },
+            completion_percentage: records_stored as f64,
+            work_unit_percentage: 0.0,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_tracker_emits_changes_only() {
+        let job_id = Uuid::new_v4();
+        let mut tracker = JobEventTracker::default();
+
+        let events = tracker.observe(vec![snapshot(job_id, JobStatus::Storing, 10)]);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, JobEventKind::Status);
+
+        assert!(tracker
+            .observe(vec![snapshot(job_id, JobStatus::Storing, 10)])
+            .is_empty());
+
+        let events = tracker.observe(vec![snapshot(job_id, JobStatus::Storing, 40)]);
+        assert_eq!(events[0].kind, JobEventKind::Progress);
+        assert_eq!(events[0].job.progress.records_stored, 40);
+    }
+
+    #[test]
+    fn test_tracker_forgets_finished_jobs() {
+        let job_id = Uuid::new_v4();
+        let mut tracker = JobEventTracker::default();
+
+        tracker.observe(vec![snapshot(job_id, JobStatus::Storing, 90)]);
+        assert!(tracker.is_tracking(job_id));
+
+        let events = tracker.observe(vec![snapshot(job_id, JobStatus::Completed, 100)]);
+        assert_eq!(events[0].kind, JobEventKind::Status);
+        assert_eq!(events[0].job.progress.status, JobStatus::Completed);
+        assert!(!tracker.is_tracking(job_id));
+        assert!(tracker.tracked().is_empty());
+    }
+}
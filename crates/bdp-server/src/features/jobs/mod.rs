@@ -1,8 +1,11 @@
 //! Jobs feature module
 //!
-//! Provides public read-only access to job status and sync progress.
-//! NO authentication required, NO job triggers allowed.
+//! Provides public read-only access to job status and sync progress, and
+//! admin-only commands to trigger, cancel and retry ingestion jobs. Job
+//! progress is also streamed live over Server-Sent Events.
 
+pub mod commands;
+pub mod events;
 pub mod queries;
 pub mod routes;
 
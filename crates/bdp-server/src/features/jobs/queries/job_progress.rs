@@ -0,0 +1,209 @@
+//! Job progress query
+//!
+//! Snapshots the progress of ingestion jobs, including their work unit
+//! breakdown. Used by the job event streams to detect status transitions and
+//! progress ticks.
+
+use chrono::{DateTime, Utc};
+use mediator::Request;
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::ingest::framework::{JobProgress, JobStatus};
+
+/// Query to snapshot job progress
+#[derive(Debug, Clone, Default)]
+pub struct JobProgressQuery {
+    /// Jobs to include regardless of their status
+    pub job_ids: Vec<Uuid>,
+    /// Also include every job that hasn't finished yet
+    pub include_active: bool,
+}
+
+/// Progress of a single ingestion job
+#[derive(Debug, Clone, Serialize)]
+pub struct JobProgressSnapshot {
+    pub job_id: Uuid,
+    pub job_type: String,
+    pub external_version: String,
+    #[serde(flatten)]
+    pub progress: JobProgress,
+    pub completion_percentage: f64,
+    pub work_unit_percentage: f64,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug)]
+struct JobProgressRow {
+    id: Uuid,
+    job_type: String,
+    external_version: String,
+    status: String,
+    total_records: Option<i64>,
+    records_processed: Option<i64>,
+    records_stored: Option<i64>,
+    records_failed: Option<i64>,
+    records_skipped: Option<i64>,
+    work_units_pending: i64,
+    work_units_claimed: i64,
+    work_units_processing: i64,
+    work_units_completed: i64,
+    work_units_failed: i64,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<JobProgressRow> for JobProgressSnapshot {
+    fn from(row: JobProgressRow) -> Self {
+        let progress = JobProgress {
+            status: JobStatus::from(row.status),
+            total_records: row.total_records.unwrap_or(0),
+            records_processed: row.records_processed.unwrap_or(0),
+            records_stored: row.records_stored.unwrap_or(0),
+            records_failed: row.records_failed.unwrap_or(0),
+            records_skipped: row.records_skipped.unwrap_or(0),
+            work_units_pending: row.work_units_pending,
+            work_units_claimed: row.work_units_claimed,
+            work_units_processing: row.work_units_processing,
+            work_units_completed: row.work_units_completed,
+            work_units_failed: row.work_units_failed,
+        };
+
+        Self {
+            job_id: row.id,
+            job_type: row.job_type,
+            external_version: row.external_version,
+            completion_percentage: progress.completion_percentage(),
+            work_unit_percentage: progress.work_unit_percentage(),
+            progress,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+/// Error type for job progress query
+#[derive(Debug, thiserror::Error)]
+pub enum JobProgressError {
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl Request<Result<Vec<JobProgressSnapshot>, JobProgressError>> for JobProgressQuery {}
+
+impl crate::cqrs::middleware::Query for JobProgressQuery {}
+
+pub async fn handle(
+    pool: PgPool,
+    query: JobProgressQuery,
+) -> Result<Vec<JobProgressSnapshot>, JobProgressError> {
+    // updated_at also moves when a worker heartbeats or finishes a unit, so
+    // clients can tell a stalled job from a busy one
+    let rows = sqlx::query_as!(
+        JobProgressRow,
+        r#"
+        SELECT j.id, j.job_type, j.external_version, j.status, j.total_records,
+               j.records_processed, j.records_stored, j.records_failed, j.records_skipped,
+               COUNT(w.id) FILTER (WHERE w.status = 'pending') AS "work_units_pending!",
+               COUNT(w.id) FILTER (WHERE w.status = 'claimed') AS "work_units_claimed!",
+               COUNT(w.id) FILTER (WHERE w.status = 'processing') AS "work_units_processing!",
+               COUNT(w.id) FILTER (WHERE w.status = 'completed') AS "work_units_completed!",
+               COUNT(w.id) FILTER (WHERE w.status = 'failed') AS "work_units_failed!",
+               GREATEST(j.updated_at, MAX(w.updated_at)) AS updated_at
+        FROM ingestion_jobs j
+        LEFT JOIN ingestion_work_units w ON w.job_id = j.id
+        WHERE j.id = ANY($1)
+           OR ($2::bool AND j.status NOT IN ('completed', 'failed', 'cancelled'))
+        GROUP BY j.id
+        ORDER BY j.created_at
+        "#,
+        &query.job_ids,
+        query.include_active
+    )
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(rows.into_iter().map(JobProgressSnapshot::from).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn insert_job(pool: &PgPool, version: &str, status: &str) -> sqlx::Result<Uuid> {
+        let org_id = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            INSERT INTO organizations (slug, name, is_system) VALUES ('uniprot', 'UniProt', true)
+            ON CONFLICT (slug) DO UPDATE SET name = EXCLUDED.name
+            RETURNING id
+            "#,
+        )
+        .fetch_one(pool)
+        .await?;
+
+        sqlx::query_scalar::<_, Uuid>(
+            r#"
+            INSERT INTO ingestion_jobs (organization_id, job_type, external_version, internal_version, status, total_records, records_stored)
+            VALUES ($1, 'uniprot_swissprot', $2, '1.0', $3, 100, 25)
+            RETURNING id
+            "#,
+        )
+        .bind(org_id)
+        .bind(version)
+        .bind(status)
+        .fetch_one(pool)
+        .await
+    }
+
+    #[sqlx::test]
+    async fn test_job_progress_snapshot(pool: PgPool) -> sqlx::Result<()> {
+        let running = insert_job(&pool, "2025_01", "storing").await?;
+        let finished = insert_job(&pool, "2024_06", "completed").await?;
+
+        for (batch, status) in [(0, "completed"), (1, "processing"), (2, "pending"), (3, "failed")]
+        {
+            sqlx::query(
+                r#"
+                INSERT INTO ingestion_work_units (job_id, unit_type, batch_number, start_offset, end_offset, status)
+                VALUES ($1, 'parse_store', $2, 0, 10, $3)
+                "#,
+            )
+            .bind(running)
+            .bind(batch)
+            .bind(status)
+            .execute(&pool)
+            .await?;
+        }
+
+        let active = handle(
+            pool.clone(),
+            JobProgressQuery {
+                include_active: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(active.len(), 1);
+        let snapshot = &active[0];
+        assert_eq!(snapshot.job_id, running);
+        assert_eq!(snapshot.progress.status, JobStatus::Storing);
+        assert_eq!(snapshot.progress.work_units_completed, 1);
+        assert_eq!(snapshot.progress.work_units_failed, 1);
+        assert_eq!(snapshot.completion_percentage, 25.0);
+        assert_eq!(snapshot.work_unit_percentage, 25.0);
+
+        // Finished jobs are only returned when asked for by ID
+        let finished_only = handle(
+            pool.clone(),
+            JobProgressQuery {
+                job_ids: vec![finished],
+                include_active: false,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(finished_only.len(), 1);
+        assert!(finished_only[0].progress.status.is_terminal());
+        Ok(())
+    }
+}
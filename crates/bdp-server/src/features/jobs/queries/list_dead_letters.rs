@@ -0,0 +1,253 @@
+//! List dead letters query
+//!
+//! Lists the records of an ingestion job that failed parsing or storage,
+//! with their raw text and error chain.
+
+use chrono::{DateTime, Utc};
+use mediator::Request;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::ingest::framework::DeadLetterStage;
+
+/// Query to list the dead letters of a job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListDeadLettersQuery {
+    pub job_id: Uuid,
+    /// Filter by stage ("parse" or "store")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stage: Option<String>,
+    /// Filter by status ("pending" or "reprocessed")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// Limit number of results
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+    /// Offset for pagination
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<i64>,
+}
+
+/// A record that failed ingestion
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetterItem {
+    pub id: Uuid,
+    pub work_unit_id: Option<Uuid>,
+    pub stage: String,
+    pub record_identifier: Option<String>,
+    pub source_file: Option<String>,
+    pub source_offset: Option<i64>,
+    pub raw_record: String,
+    pub error_message: String,
+    pub error_chain: serde_json::Value,
+    pub status: String,
+    pub attempts: i32,
+    pub reprocessed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Response for list dead letters query
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListDeadLettersResponse {
+    pub job_id: Uuid,
+    pub dead_letters: Vec<DeadLetterItem>,
+    pub total: i64,
+}
+
+/// Error type for list dead letters query
+#[derive(Debug, thiserror::Error)]
+pub enum ListDeadLettersError {
+    #[error("Job '{0}' not found")]
+    NotFound(Uuid),
+    #[error("Invalid stage '{0}'; expected 'parse' or 'store'")]
+    InvalidStage(String),
+    #[error("Invalid status '{0}'; expected 'pending' or 'reprocessed'")]
+    InvalidStatus(String),
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl Request<Result<ListDeadLettersResponse, ListDeadLettersError>> for ListDeadLettersQuery {}
+
+impl crate::cqrs::middleware::Query for ListDeadLettersQuery {}
+
+pub async fn handle(
+    pool: PgPool,
+    query: ListDeadLettersQuery,
+) -> Result<ListDeadLettersResponse, ListDeadLettersError> {
+    if let Some(stage) = &query.stage {
+        stage
+            .parse::<DeadLetterStage>()
+            .map_err(|_| ListDeadLettersError::InvalidStage(stage.clone()))?;
+    }
+    if let Some(status) = &query.status {
+        if !matches!(status.as_str(), "pending" | "reprocessed") {
+            return Err(ListDeadLettersError::InvalidStatus(status.clone()));
+        }
+    }
+
+    let limit = query.limit.unwrap_or(100).clamp(1, 1000);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let job_exists = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM ingestion_jobs WHERE id = $1) as "exists!""#,
+        query.job_id
+    )
+    .fetch_one(&pool)
+    .await?;
+    if !job_exists {
+        return Err(ListDeadLettersError::NotFound(query.job_id));
+    }
+
+    let dead_letters = sqlx::query_as!(
+        DeadLetterItem,
+        r#"
+        SELECT id, work_unit_id, stage, record_identifier, source_file, source_offset,
+               raw_record, error_message, error_chain, status, attempts, reprocessed_at,
+               created_at
+        FROM ingestion_dead_letters
+        WHERE job_id = $1
+          AND ($2::text IS NULL OR stage = $2)
+          AND ($3::text IS NULL OR status = $3)
+        ORDER BY source_offset NULLS LAST, created_at
+        LIMIT $4 OFFSET $5
+        "#,
+        query.job_id,
+        query.stage.as_deref(),
+        query.status.as_deref(),
+        limit,
+        offset
+    )
+    .fetch_all(&pool)
+    .await?;
+
+    let total = sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(*) as "count!"
+        FROM ingestion_dead_letters
+        WHERE job_id = $1
+          AND ($2::text IS NULL OR stage = $2)
+          AND ($3::text IS NULL OR status = $3)
+        "#,
+        query.job_id,
+        query.stage.as_deref(),
+        query.status.as_deref()
+    )
+    .fetch_one(&pool)
+    .await?;
+
+    Ok(ListDeadLettersResponse {
+        job_id: query.job_id,
+        dead_letters,
+        total,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::framework::{DeadLetter, DeadLetterStore};
+    use std::sync::Arc;
+
+    async fn insert_job(pool: &PgPool) -> sqlx::Result<Uuid> {
+        let org_id = sqlx::query_scalar::<_, Uuid>(
+            "INSERT INTO organizations (slug, name, is_system) VALUES ('uniprot', 'UniProt', true) RETURNING id",
+        )
+        .fetch_one(pool)
+        .await?;
+
+        sqlx::query_scalar::<_, Uuid>(
+            r#"
+            INSERT INTO ingestion_jobs (organization_id, job_type, external_version, internal_version, status)
+            VALUES ($1, 'uniprot_swissprot', '2025_01', '1.0', 'completed')
+            RETURNING id
+            "#,
+        )
+        .bind(org_id)
+        .fetch_one(pool)
+        .await
+    }
+
+    fn query(job_id: Uuid) -> ListDeadLettersQuery {
+        ListDeadLettersQuery {
+            job_id,
+            stage: None,
+            status: None,
+            limit: None,
+            offset: None,
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_list_dead_letters(pool: PgPool) -> sqlx::Result<()> {
+        let job_id = insert_job(&pool).await?;
+        let store = DeadLetterStore::new(Arc::new(pool.clone()));
+
+        let parse_error =
+            anyhow::anyhow!("Invalid date: 31-FEB-2020").context("Failed to parse line");
+        let store_error = anyhow::anyhow!("duplicate key value");
+        store
+            .record(
+                job_id,
+                None,
+                &[
+                    DeadLetter::new(
+                        DeadLetterStage::Parse,
+                        "ID   A\n//\n".to_string(),
+                        &parse_error,
+                    )
+                    .with_source(Some("uniprot_sprot.dat.gz"), 0),
+                    DeadLetter::new(
+                        DeadLetterStage::Store,
+                        "ID   B\n//\n".to_string(),
+                        &store_error,
+                    )
+                    .with_identifier("P12345")
+                    .with_source(Some("uniprot_sprot.dat.gz"), 11),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let response = handle(pool.clone(), query(job_id)).await.unwrap();
+        assert_eq!(response.total, 2);
+        let parse = &response.dead_letters[0];
+        assert_eq!(parse.stage, "parse");
+        assert_eq!(parse.raw_record, "ID   A\n//\n");
+        assert_eq!(
+            parse.error_chain,
+            serde_json::json!(["Failed to parse line", "Invalid date: 31-FEB-2020"])
+        );
+        assert_eq!(response.dead_letters[1].record_identifier.as_deref(), Some("P12345"));
+
+        let stored_only = handle(
+            pool.clone(),
+            ListDeadLettersQuery {
+                stage: Some("store".to_string()),
+                ..query(job_id)
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(stored_only.total, 1);
+        assert_eq!(stored_only.dead_letters[0].source_offset, Some(11));
+
+        assert!(matches!(
+            handle(
+                pool.clone(),
+                ListDeadLettersQuery {
+                    stage: Some("download".to_string()),
+                    ..query(job_id)
+                }
+            )
+            .await,
+            Err(ListDeadLettersError::InvalidStage(_))
+        ));
+        assert!(matches!(
+            handle(pool.clone(), query(Uuid::new_v4())).await,
+            Err(ListDeadLettersError::NotFound(_))
+        ));
+        Ok(())
+    }
+}
@@ -2,6 +2,8 @@
 
 pub mod get_job;
 pub mod get_sync_status;
+pub mod job_progress;
+pub mod list_dead_letters;
 pub mod list_jobs;
 
 pub use get_job::{GetJobError, GetJobQuery, JobDetails};
@@ -9,4 +11,8 @@ pub use get_sync_status::{
     GetSyncStatusQuery, ListSyncStatusQuery, ListSyncStatusResponse, SyncStatusError,
     SyncStatusItem,
 };
+pub use job_progress::{JobProgressError, JobProgressQuery, JobProgressSnapshot};
+pub use list_dead_letters::{
+    DeadLetterItem, ListDeadLettersError, ListDeadLettersQuery, ListDeadLettersResponse,
+};
 pub use list_jobs::{JobListItem, ListJobsError, ListJobsQuery, ListJobsResponse};
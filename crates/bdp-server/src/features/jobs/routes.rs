@@ -1,33 +1,62 @@
 //! Job routes
 //!
-//! Public read-only routes for querying job status and sync progress.
-//! These endpoints do NOT require authentication and do NOT allow triggering jobs.
+//! Public read-only routes for querying job status and sync progress, plus
+//! admin-only routes to trigger, cancel and retry ingestion jobs. Reads need
+//! no authentication; every write requires the admin token.
+//!
+//! Records that failed parsing or storage can be browsed per job
+//! (`/:job_id/dead-letters`) and re-run once the cause is fixed.
+//!
+//! Job progress can also be followed live over Server-Sent Events, either for
+//! a single job (`/:job_id/events`) or for every active job (`/events`).
 
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
-    response::{IntoResponse, Response},
-    routing::get,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    routing::{get, post},
     Json, Router,
 };
+use futures::{Stream, StreamExt};
+use serde::Deserialize;
 use serde_json::json;
 use sqlx::PgPool;
 use uuid::Uuid;
 
+use crate::api::response::{ApiResponse, ErrorResponse};
+use crate::middleware::admin::AdminAuth;
+
+use super::commands::{
+    CancelJobCommand, CancelJobError, ReprocessDeadLettersCommand, ReprocessDeadLettersError,
+    RetryJobCommand, RetryJobError, TriggerIngestionCommand, TriggerIngestionError,
+};
+use super::events::{job_events, JobEvent};
 use super::queries::{
     get_job::handle as handle_get_job,
     get_sync_status::{
         handle_get as handle_get_sync_status, handle_list as handle_list_sync_status,
     },
+    job_progress::handle as handle_job_progress,
+    list_dead_letters::handle as handle_list_dead_letters,
     list_jobs::handle as handle_list_jobs,
-    GetJobQuery, GetSyncStatusQuery, ListJobsQuery, ListSyncStatusQuery,
+    GetJobQuery, GetSyncStatusQuery, JobProgressQuery, ListDeadLettersError, ListDeadLettersQuery,
+    ListJobsQuery, ListSyncStatusQuery,
 };
 
 /// Create job routes
 pub fn jobs_routes() -> Router<PgPool> {
     Router::new()
-        .route("/", get(list_jobs))
+        .route("/", get(list_jobs).post(trigger_ingestion))
+        .route("/events", get(watch_jobs))
         .route("/:job_id", get(get_job))
+        .route("/:job_id/events", get(watch_job))
+        .route("/:job_id/cancel", post(cancel_job))
+        .route("/:job_id/retry", post(retry_job))
+        .route("/:job_id/dead-letters", get(list_dead_letters))
+        .route("/:job_id/dead-letters/reprocess", post(reprocess_dead_letters))
 }
 
 /// Create sync status routes
@@ -71,6 +100,159 @@ async fn get_job(
     }
 }
 
+/// Stream status and progress events for every active job
+///
+/// GET /events
+async fn watch_jobs(
+    State(db): State<PgPool>,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    sse_response(job_events(db, None))
+}
+
+/// Stream status and progress events for one job until it finishes
+///
+/// GET /:job_id/events
+async fn watch_job(
+    State(db): State<PgPool>,
+    Path(job_id): Path<Uuid>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, StatusCode> {
+    let query = JobProgressQuery {
+        job_ids: vec![job_id],
+        include_active: false,
+    };
+
+    match handle_job_progress(db.clone(), query).await {
+        Ok(jobs) if jobs.is_empty() => Err(StatusCode::NOT_FOUND),
+        Ok(_) => Ok(sse_response(job_events(db, Some(job_id)))),
+        Err(e) => {
+            tracing::error!("Failed to read job progress: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        },
+    }
+}
+
+/// Encode job events as SSE events named after their kind
+fn sse_response(
+    events: impl Stream<Item = JobEvent> + Send + 'static,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    let events = events.map(|event| {
+        Event::default()
+            .event(event.kind.as_str())
+            .json_data(&event.job)
+    });
+
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+/// Trigger ingestion for a source organization (admin only)
+///
+/// POST / with `{"organization_slug": "uniprot", "external_version": "2025_01"}`
+/// or `{"organization_slug": "uniprot", "mode": {"mode": "historical", "start_version": "2024_01"}}`
+#[tracing::instrument(skip(db, _admin))]
+async fn trigger_ingestion(
+    State(db): State<PgPool>,
+    _admin: AdminAuth,
+    Json(command): Json<TriggerIngestionCommand>,
+) -> Result<Response, JobsApiError> {
+    let response = super::commands::trigger::handle(db, command).await?;
+
+    tracing::info!(
+        source = %response.source,
+        enqueued = ?response.enqueued,
+        skipped = ?response.skipped,
+        "Ingestion triggered via API"
+    );
+
+    Ok((StatusCode::ACCEPTED, Json(ApiResponse::success(response))).into_response())
+}
+
+/// Cancel a running job (admin only)
+///
+/// POST /:job_id/cancel
+#[tracing::instrument(skip(db, _admin))]
+async fn cancel_job(
+    State(db): State<PgPool>,
+    _admin: AdminAuth,
+    Path(job_id): Path<Uuid>,
+) -> Result<Response, JobsApiError> {
+    let response = super::commands::cancel::handle(db, CancelJobCommand { job_id }).await?;
+
+    tracing::info!(job_id = %job_id, "Job cancelled via API");
+
+    Ok((StatusCode::OK, Json(ApiResponse::success(response))).into_response())
+}
+
+/// Retry the failed work units of a finished job (admin only)
+///
+/// POST /:job_id/retry
+#[tracing::instrument(skip(db, _admin))]
+async fn retry_job(
+    State(db): State<PgPool>,
+    _admin: AdminAuth,
+    Path(job_id): Path<Uuid>,
+) -> Result<Response, JobsApiError> {
+    let response = super::commands::retry::handle(db, RetryJobCommand { job_id }).await?;
+
+    tracing::info!(
+        job_id = %job_id,
+        work_units = response.work_units_retried,
+        "Job retry enqueued via API"
+    );
+
+    Ok((StatusCode::ACCEPTED, Json(ApiResponse::success(response))).into_response())
+}
+
+/// Filters for listing dead letters
+#[derive(Debug, Deserialize)]
+struct DeadLetterParams {
+    stage: Option<String>,
+    status: Option<String>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
+
+/// List the records of a job that failed parsing or storage
+///
+/// GET /:job_id/dead-letters?stage=parse&status=pending&limit=100&offset=0
+async fn list_dead_letters(
+    State(db): State<PgPool>,
+    Path(job_id): Path<Uuid>,
+    Query(params): Query<DeadLetterParams>,
+) -> Result<Response, JobsApiError> {
+    let query = ListDeadLettersQuery {
+        job_id,
+        stage: params.stage,
+        status: params.status,
+        limit: params.limit,
+        offset: params.offset,
+    };
+
+    let response = handle_list_dead_letters(db, query).await?;
+
+    Ok((StatusCode::OK, Json(ApiResponse::success(response))).into_response())
+}
+
+/// Reprocess the pending dead letters of a finished job (admin only)
+///
+/// POST /:job_id/dead-letters/reprocess
+#[tracing::instrument(skip(db, _admin))]
+async fn reprocess_dead_letters(
+    State(db): State<PgPool>,
+    _admin: AdminAuth,
+    Path(job_id): Path<Uuid>,
+) -> Result<Response, JobsApiError> {
+    let response =
+        super::commands::reprocess::handle(db, ReprocessDeadLettersCommand { job_id }).await?;
+
+    tracing::info!(
+        job_id = %job_id,
+        dead_letters = response.dead_letters_pending,
+        "Dead letter reprocessing enqueued via API"
+    );
+
+    Ok((StatusCode::ACCEPTED, Json(ApiResponse::success(response))).into_response())
+}
+
 /// List all sync statuses
 ///
 /// GET /sync-status?organization_id=<uuid>&status=running
@@ -105,6 +287,68 @@ async fn get_sync_status(
     }
 }
 
+#[derive(Debug, thiserror::Error)]
+enum JobsApiError {
+    #[error(transparent)]
+    Trigger(#[from] TriggerIngestionError),
+    #[error(transparent)]
+    Cancel(#[from] CancelJobError),
+    #[error(transparent)]
+    Retry(#[from] RetryJobError),
+    #[error(transparent)]
+    Reprocess(#[from] ReprocessDeadLettersError),
+    #[error(transparent)]
+    DeadLetters(#[from] ListDeadLettersError),
+}
+
+impl IntoResponse for JobsApiError {
+    fn into_response(self) -> Response {
+        let (status, code) = match &self {
+            JobsApiError::Trigger(TriggerIngestionError::OrganizationSlugRequired)
+            | JobsApiError::Trigger(TriggerIngestionError::ExternalVersionEmpty)
+            | JobsApiError::Trigger(TriggerIngestionError::StartVersionRequired)
+            | JobsApiError::DeadLetters(ListDeadLettersError::InvalidStage(_))
+            | JobsApiError::DeadLetters(ListDeadLettersError::InvalidStatus(_)) => {
+                (StatusCode::BAD_REQUEST, "VALIDATION_ERROR")
+            },
+            JobsApiError::Trigger(TriggerIngestionError::UnknownSource(_))
+            | JobsApiError::Trigger(TriggerIngestionError::NoVersions(_))
+            | JobsApiError::Cancel(CancelJobError::NotFound(_))
+            | JobsApiError::Retry(RetryJobError::NotFound(_))
+            | JobsApiError::Retry(RetryJobError::UnknownSource(_))
+            | JobsApiError::Reprocess(ReprocessDeadLettersError::NotFound(_))
+            | JobsApiError::Reprocess(ReprocessDeadLettersError::UnknownSource(_))
+            | JobsApiError::DeadLetters(ListDeadLettersError::NotFound(_)) => {
+                (StatusCode::NOT_FOUND, "NOT_FOUND")
+            },
+            JobsApiError::Cancel(CancelJobError::AlreadyFinished(..))
+            | JobsApiError::Retry(RetryJobError::NotFinished(..))
+            | JobsApiError::Retry(RetryJobError::NothingToRetry(_))
+            | JobsApiError::Reprocess(ReprocessDeadLettersError::NotFinished(..))
+            | JobsApiError::Reprocess(ReprocessDeadLettersError::NothingToReprocess(_)) => {
+                (StatusCode::CONFLICT, "CONFLICT")
+            },
+            JobsApiError::Trigger(TriggerIngestionError::Discovery(..)) => {
+                (StatusCode::BAD_GATEWAY, "UPSTREAM_ERROR")
+            },
+            JobsApiError::Trigger(TriggerIngestionError::Queue(_))
+            | JobsApiError::Trigger(TriggerIngestionError::Database(_))
+            | JobsApiError::Cancel(CancelJobError::Database(_))
+            | JobsApiError::Retry(RetryJobError::Queue(_))
+            | JobsApiError::Retry(RetryJobError::Database(_))
+            | JobsApiError::Reprocess(ReprocessDeadLettersError::Queue(_))
+            | JobsApiError::Reprocess(ReprocessDeadLettersError::Database(_))
+            | JobsApiError::DeadLetters(ListDeadLettersError::Database(_)) => {
+                tracing::error!("Job command failed: {}", self);
+                let error = ErrorResponse::new("INTERNAL_ERROR", "An internal error occurred");
+                return (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response();
+            },
+        };
+
+        (status, Json(ErrorResponse::new(code, self.to_string()))).into_response()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -178,4 +178,114 @@ mod tests {
         assert_eq!(json["status"], "idle");
         assert_eq!(json["total_entries"], 1000);
     }
+
+    #[sqlx::test]
+    async fn test_admin_job_routes_require_token(pool: PgPool) {
+        let app = create_test_router(pool);
+        let job_id = Uuid::new_v4();
+
+        for (uri, body) in [
+            ("/".to_string(), json!({ "organization_slug": "uniprot" })),
+            (format!("/{}/cancel", job_id), json!({})),
+            (format!("/{}/retry", job_id), json!({})),
+            (format!("/{}/dead-letters/reprocess", job_id), json!({})),
+        ] {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .method("POST")
+                        .uri(&uri)
+                        .header("content-type", "application/json")
+                        .body(Body::from(body.to_string()))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert!(
+                matches!(response.status(), StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN),
+                "{} was not refused",
+                uri
+            );
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_dead_letters_unknown_job(pool: PgPool) {
+        let app = create_test_router(pool);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/{}/dead-letters?stage=parse", Uuid::new_v4()))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[sqlx::test]
+    async fn test_watch_unknown_job(pool: PgPool) {
+        let app = create_test_router(pool);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/{}/events", Uuid::new_v4()))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[sqlx::test]
+    async fn test_watch_finished_job_ends_stream(pool: PgPool) {
+        let org_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO organizations (slug, name, is_system) VALUES ('uniprot', 'UniProt', true) RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let job_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO ingestion_jobs (organization_id, job_type, external_version, internal_version, status)
+            VALUES ($1, 'uniprot_swissprot', '2025_01', '1.0', 'completed')
+            RETURNING id
+            "#,
+        )
+        .bind(org_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let app = create_test_router(pool);
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/{}/events", job_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], "text/event-stream");
+
+        // The job already finished, so the stream reports its status and closes
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.starts_with("event: status\n"));
+        assert!(body.contains(&job_id.to_string()));
+        assert!(body.contains("\"status\":\"completed\""));
+    }
 }
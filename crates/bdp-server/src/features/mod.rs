@@ -14,7 +14,7 @@
 //! - **protein_metadata**: Protein-specific metadata operations
 //! - **resolve**: Manifest resolution for CLI dependency resolution
 //! - **search**: Full-text search and autocomplete suggestions
-//! - **version_files**: Version-specific file management
+//! - **version_files**: Version-specific file registration (mounted under `/data-sources`)
 //!
 //! # Architecture
 //!
@@ -81,12 +81,17 @@ pub fn router(state: FeatureState) -> Router<()> {
         )
         .nest(
             "/data-sources",
-            data_sources::data_sources_routes().with_state(state.db.clone()),
+            data_sources::data_sources_routes()
+                .merge(version_files::version_files_routes())
+                .with_state(state.db.clone())
+                .merge(
+                    version_files::routes::version_file_download_routes().with_state(state.clone()),
+                ),
         )
         .nest("/search", search::search_routes().with_state(state.db.clone()))
         .nest("/resolve", resolve::resolve_routes().with_state(state.db.clone()))
         .nest("/jobs", jobs::jobs_routes().with_state(state.db.clone()))
         .nest("/sync-status", jobs::sync_status_routes().with_state(state.db.clone()))
-        .nest("/files", files::files_routes().with_state(state.storage.clone()))
+        .nest("/files", files::files_routes().with_state(state.clone()))
         .nest("/query", query::query_routes().with_state(state.db.clone()))
 }
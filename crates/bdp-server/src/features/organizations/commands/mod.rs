@@ -1,7 +1,11 @@
 pub mod create;
 pub mod delete;
 pub mod update;
+pub mod update_versioning_strategy;
 
 pub use create::{CreateOrganizationCommand, CreateOrganizationError, CreateOrganizationResponse};
 pub use delete::{DeleteOrganizationCommand, DeleteOrganizationError, DeleteOrganizationResponse};
 pub use update::{UpdateOrganizationCommand, UpdateOrganizationError, UpdateOrganizationResponse};
+pub use update_versioning_strategy::{
+    UpdateVersioningStrategyCommand, UpdateVersioningStrategyError,
+};
@@ -0,0 +1,266 @@
+//! Update organization versioning strategy command
+//!
+//! Replaces the versioning strategy stored on an organization, or clears it so
+//! the detector defaults apply again. Ingestion reads the strategy at the
+//! start of every run, so a change takes effect at the next ingestion. Every
+//! change is audited with the strategy it replaced.
+
+use mediator::Request;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+
+use crate::audit::{create_audit_entry, AuditAction, CreateAuditEntry, ResourceType};
+use crate::features::organizations::queries::VersioningStrategyResponse;
+use crate::ingest::versioning::{StrategyValidationError, VersioningStrategy};
+
+/// Command to replace an organization's versioning strategy
+///
+/// `strategy` is taken as raw JSON so that unknown change types and malformed
+/// triggers are reported as validation errors rather than request rejections.
+/// `null` clears the stored strategy.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let command = UpdateVersioningStrategyCommand {
+///     slug: "go".to_string(),
+///     strategy: serde_json::json!({
+///         "major_triggers": [
+///             {"change_type": "removed", "category": "terms", "description": "Terms obsoleted"}
+///         ],
+///         "minor_triggers": [
+///             {"change_type": "added", "category": "*", "description": "Anything added"}
+///         ],
+///         "cascade_on_minor": false
+///     }),
+/// };
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateVersioningStrategyCommand {
+    #[serde(default)]
+    pub slug: String,
+    pub strategy: serde_json::Value,
+}
+
+/// Errors that can occur when updating a versioning strategy
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateVersioningStrategyError {
+    #[error("Organization slug is required")]
+    SlugRequired,
+    #[error("Invalid versioning strategy: {0}")]
+    Malformed(String),
+    #[error("Invalid versioning strategy: {0}")]
+    Invalid(#[from] StrategyValidationError),
+    #[error("Organization '{0}' not found")]
+    NotFound(String),
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl Request<Result<VersioningStrategyResponse, UpdateVersioningStrategyError>>
+    for UpdateVersioningStrategyCommand
+{
+}
+
+impl crate::cqrs::middleware::Command for UpdateVersioningStrategyCommand {}
+
+impl UpdateVersioningStrategyCommand {
+    /// Validate the command and parse the proposed strategy
+    ///
+    /// Returns `None` when the command clears the stored strategy.
+    pub fn validate(&self) -> Result<Option<VersioningStrategy>, UpdateVersioningStrategyError> {
+        if self.slug.trim().is_empty() {
+            return Err(UpdateVersioningStrategyError::SlugRequired);
+        }
+        if self.strategy.is_null() {
+            return Ok(None);
+        }
+        let strategy = parse_strategy(&self.strategy)?;
+        Ok(Some(strategy))
+    }
+}
+
+/// Parse and validate a strategy submitted as JSON
+///
+/// Shared with the dry-run query so both reject the same strategies.
+pub(crate) fn parse_strategy(
+    value: &serde_json::Value,
+) -> Result<VersioningStrategy, UpdateVersioningStrategyError> {
+    let strategy: VersioningStrategy = serde_json::from_value(value.clone())
+        .map_err(|e| UpdateVersioningStrategyError::Malformed(e.to_string()))?;
+    strategy.validate()?;
+    Ok(strategy)
+}
+
+#[tracing::instrument(skip(pool, command), fields(slug = %command.slug))]
+pub async fn handle(
+    pool: PgPool,
+    command: UpdateVersioningStrategyCommand,
+) -> Result<VersioningStrategyResponse, UpdateVersioningStrategyError> {
+    let strategy = command.validate()?;
+
+    let stored = strategy
+        .as_ref()
+        .map(serde_json::to_value)
+        .transpose()
+        .map_err(|e| UpdateVersioningStrategyError::Malformed(e.to_string()))?;
+
+    let org = sqlx::query!(
+        r#"
+        UPDATE organizations o
+        SET versioning_strategy = $2
+        FROM organizations previous
+        WHERE previous.id = o.id AND o.slug = $1
+        RETURNING o.id, o.slug, o.updated_at as "updated_at!",
+                  previous.versioning_strategy as previous_strategy
+        "#,
+        command.slug,
+        stored
+    )
+    .fetch_optional(&pool)
+    .await?
+    .ok_or_else(|| UpdateVersioningStrategyError::NotFound(command.slug.clone()))?;
+
+    if let Err(e) = create_audit_entry(
+        &pool,
+        CreateAuditEntry::builder()
+            .action(AuditAction::Update)
+            .resource_type(ResourceType::Organization)
+            .resource_id(Some(org.id))
+            .metadata(serde_json::json!({
+                "operation": "update_versioning_strategy",
+                "previous_strategy": org.previous_strategy,
+                "strategy": stored,
+            }))
+            .build(),
+    )
+    .await
+    {
+        tracing::warn!(error = %e, "Failed to audit versioning strategy update");
+    }
+
+    tracing::info!(
+        organization = %org.slug,
+        cleared = strategy.is_none(),
+        "Versioning strategy updated"
+    );
+
+    Ok(VersioningStrategyResponse {
+        organization_slug: org.slug,
+        uses_detector_defaults: strategy.is_none(),
+        strategy,
+        updated_at: org.updated_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::shared::test_helpers::{create_test_data_source, create_test_org};
+    use crate::ingest::versioning::get_organization_versioning_strategy;
+    use serde_json::json;
+
+    fn command(strategy: serde_json::Value) -> UpdateVersioningStrategyCommand {
+        UpdateVersioningStrategyCommand {
+            slug: "go".to_string(),
+            strategy,
+        }
+    }
+
+    #[test]
+    fn test_validation_rejects_unknown_change_type() {
+        let cmd = command(json!({
+            "major_triggers": [
+                {"change_type": "renamed", "category": "terms", "description": "Terms renamed"}
+            ],
+            "minor_triggers": []
+        }));
+        let err = cmd.validate().unwrap_err();
+        assert!(matches!(err, UpdateVersioningStrategyError::Malformed(_)));
+        assert!(err.to_string().contains("renamed"));
+        assert!(err.to_string().contains("dependency"));
+    }
+
+    #[test]
+    fn test_validation_rejects_conflicting_triggers() {
+        let cmd = command(json!({
+            "major_triggers": [
+                {"change_type": "modified", "category": "terms", "description": "Terms changed"}
+            ],
+            "minor_triggers": [
+                {"change_type": "modified", "category": "terms", "description": "Terms changed"}
+            ]
+        }));
+        assert!(matches!(
+            cmd.validate(),
+            Err(UpdateVersioningStrategyError::Invalid(
+                StrategyValidationError::ConflictingTrigger { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_validation_accepts_null_and_defaults() {
+        assert!(command(serde_json::Value::Null)
+            .validate()
+            .unwrap()
+            .is_none());
+
+        let strategy = command(json!({"major_triggers": [], "minor_triggers": []}))
+            .validate()
+            .unwrap()
+            .unwrap();
+        assert!(strategy.cascade_on_major);
+        assert!(strategy.cascade_on_minor);
+    }
+
+    #[sqlx::test]
+    async fn test_handle_is_picked_up_by_ingestion(pool: PgPool) -> sqlx::Result<()> {
+        let org_id = create_test_org(&pool, "go", "Gene Ontology").await?;
+        let ds_id = create_test_data_source(&pool, org_id, "go-basic", "GO", "go_term").await?;
+
+        let proposed = VersioningStrategy::gene_ontology();
+        let response = handle(pool.clone(), command(serde_json::to_value(&proposed).unwrap()))
+            .await
+            .unwrap();
+        assert_eq!(response.strategy.as_ref(), Some(&proposed));
+
+        let used = get_organization_versioning_strategy(&pool, ds_id)
+            .await
+            .unwrap();
+        assert_eq!(used.as_ref(), Some(&proposed));
+
+        let cleared = handle(pool.clone(), command(serde_json::Value::Null))
+            .await
+            .unwrap();
+        assert!(cleared.uses_detector_defaults);
+        assert!(get_organization_versioning_strategy(&pool, ds_id)
+            .await
+            .unwrap()
+            .is_none());
+
+        let audited = sqlx::query!(
+            r#"
+            SELECT action, metadata->'previous_strategy' AS previous_strategy
+            FROM audit_log
+            WHERE resource_id = $1
+            ORDER BY timestamp
+            "#,
+            org_id
+        )
+        .fetch_all(&pool)
+        .await?;
+        assert_eq!(audited.len(), 2);
+        assert!(audited.iter().all(|entry| entry.action == "update"));
+        assert_eq!(audited[0].previous_strategy, Some(serde_json::Value::Null));
+        assert_eq!(audited[1].previous_strategy, Some(serde_json::to_value(&proposed).unwrap()));
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_handle_not_found(pool: PgPool) -> sqlx::Result<()> {
+        let result = handle(pool, command(serde_json::Value::Null)).await;
+        assert!(matches!(result, Err(UpdateVersioningStrategyError::NotFound(_))));
+        Ok(())
+    }
+}
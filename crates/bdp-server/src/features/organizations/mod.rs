@@ -6,11 +6,15 @@ pub use commands::{
     CreateOrganizationCommand, CreateOrganizationError, CreateOrganizationResponse,
     DeleteOrganizationCommand, DeleteOrganizationError, DeleteOrganizationResponse,
     UpdateOrganizationCommand, UpdateOrganizationError, UpdateOrganizationResponse,
+    UpdateVersioningStrategyCommand, UpdateVersioningStrategyError,
 };
 
 pub use queries::{
-    GetOrganizationError, GetOrganizationQuery, GetOrganizationResponse, ListOrganizationsError,
+    GetOrganizationError, GetOrganizationQuery, GetOrganizationResponse,
+    GetVersioningStrategyError, GetVersioningStrategyQuery, ListOrganizationsError,
     ListOrganizationsQuery, ListOrganizationsResponse, OrganizationListItem, PaginationMetadata,
+    PreviewVersioningStrategyError, PreviewVersioningStrategyQuery,
+    PreviewVersioningStrategyResponse, VersioningStrategyResponse,
 };
 
 pub use routes::organizations_routes;
@@ -0,0 +1,167 @@
+//! Get organization versioning strategy query
+//!
+//! Returns the versioning strategy stored on an organization. When none is
+//! stored, ingestion falls back to the per-source detector defaults.
+
+use chrono::{DateTime, Utc};
+use mediator::Request;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+
+use crate::ingest::versioning::VersioningStrategy;
+
+/// Query to retrieve an organization's versioning strategy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetVersioningStrategyQuery {
+    pub slug: String,
+}
+
+/// An organization's versioning strategy
+///
+/// `strategy` is `None` when the organization has no stored strategy and its
+/// data sources are bumped by the detector defaults.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersioningStrategyResponse {
+    pub organization_slug: String,
+    pub strategy: Option<VersioningStrategy>,
+    pub uses_detector_defaults: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Errors that can occur when getting a versioning strategy
+#[derive(Debug, thiserror::Error)]
+pub enum GetVersioningStrategyError {
+    #[error("Organization slug is required")]
+    SlugRequired,
+    #[error("Organization '{0}' not found")]
+    NotFound(String),
+    #[error("Stored versioning strategy for '{slug}' is invalid: {reason}")]
+    Corrupt { slug: String, reason: String },
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl Request<Result<VersioningStrategyResponse, GetVersioningStrategyError>>
+    for GetVersioningStrategyQuery
+{
+}
+
+impl crate::cqrs::middleware::Query for GetVersioningStrategyQuery {}
+
+impl GetVersioningStrategyQuery {
+    pub fn validate(&self) -> Result<(), GetVersioningStrategyError> {
+        if self.slug.trim().is_empty() {
+            return Err(GetVersioningStrategyError::SlugRequired);
+        }
+        Ok(())
+    }
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn handle(
+    pool: PgPool,
+    query: GetVersioningStrategyQuery,
+) -> Result<VersioningStrategyResponse, GetVersioningStrategyError> {
+    query.validate()?;
+
+    let org = sqlx::query!(
+        r#"
+        SELECT slug, versioning_strategy, updated_at as "updated_at!"
+        FROM organizations
+        WHERE slug = $1
+        "#,
+        query.slug
+    )
+    .fetch_optional(&pool)
+    .await?
+    .ok_or_else(|| GetVersioningStrategyError::NotFound(query.slug.clone()))?;
+
+    let strategy = org
+        .versioning_strategy
+        .map(serde_json::from_value::<VersioningStrategy>)
+        .transpose()
+        .map_err(|e| GetVersioningStrategyError::Corrupt {
+            slug: org.slug.clone(),
+            reason: e.to_string(),
+        })?;
+
+    Ok(VersioningStrategyResponse {
+        organization_slug: org.slug,
+        uses_detector_defaults: strategy.is_none(),
+        strategy,
+        updated_at: org.updated_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::shared::test_helpers::TestOrganization;
+
+    #[test]
+    fn test_validation_requires_slug() {
+        let query = GetVersioningStrategyQuery {
+            slug: " ".to_string(),
+        };
+        assert!(matches!(query.validate(), Err(GetVersioningStrategyError::SlugRequired)));
+    }
+
+    #[sqlx::test]
+    async fn test_handle_returns_stored_strategy(pool: PgPool) -> sqlx::Result<()> {
+        TestOrganization::new("strategy-org", "Strategy Org")
+            .insert(&pool)
+            .await?;
+        sqlx::query!(
+            "UPDATE organizations SET versioning_strategy = $1 WHERE slug = 'strategy-org'",
+            serde_json::to_value(VersioningStrategy::gene_ontology()).unwrap()
+        )
+        .execute(&pool)
+        .await?;
+
+        let response = handle(
+            pool.clone(),
+            GetVersioningStrategyQuery {
+                slug: "strategy-org".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+        assert!(!response.uses_detector_defaults);
+        assert_eq!(response.strategy, Some(VersioningStrategy::gene_ontology()));
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_handle_without_strategy(pool: PgPool) -> sqlx::Result<()> {
+        TestOrganization::new("plain-org", "Plain Org")
+            .insert(&pool)
+            .await?;
+
+        let response = handle(
+            pool.clone(),
+            GetVersioningStrategyQuery {
+                slug: "plain-org".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+        assert!(response.uses_detector_defaults);
+        assert!(response.strategy.is_none());
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_handle_not_found(pool: PgPool) -> sqlx::Result<()> {
+        let result = handle(
+            pool,
+            GetVersioningStrategyQuery {
+                slug: "missing".to_string(),
+            },
+        )
+        .await;
+        assert!(matches!(result, Err(GetVersioningStrategyError::NotFound(_))));
+        Ok(())
+    }
+}
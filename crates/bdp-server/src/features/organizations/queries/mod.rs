@@ -1,9 +1,18 @@
 pub mod get;
+pub mod get_versioning_strategy;
 pub mod list;
+pub mod preview_versioning_strategy;
 
 pub use get::{GetOrganizationError, GetOrganizationQuery, GetOrganizationResponse};
+pub use get_versioning_strategy::{
+    GetVersioningStrategyError, GetVersioningStrategyQuery, VersioningStrategyResponse,
+};
 pub use list::{
     ListOrganizationsError, ListOrganizationsQuery, ListOrganizationsResponse, OrganizationListItem,
 };
+pub use preview_versioning_strategy::{
+    EntryBumpPreview, PreviewVersioningStrategyError, PreviewVersioningStrategyQuery,
+    PreviewVersioningStrategyResponse,
+};
 // Re-export from shared module to avoid privacy issues
 pub use crate::features::shared::pagination::PaginationMetadata;
@@ -0,0 +1,263 @@
+//! Versioning strategy dry run
+//!
+//! Replays a stored changelog against a proposed versioning strategy without
+//! saving anything, showing which bump each entry would get and whether the
+//! resulting version would cascade to dependents.
+
+use mediator::Request;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::features::organizations::commands::update_versioning_strategy::{
+    parse_strategy, UpdateVersioningStrategyError,
+};
+use crate::ingest::versioning::{BumpType, ChangeType, ChangelogEntry, VersionTrigger};
+
+/// Query to preview a proposed strategy against a stored changelog
+///
+/// The changelog must belong to a data source of the organization.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviewVersioningStrategyQuery {
+    #[serde(default)]
+    pub slug: String,
+    pub changelog_id: Uuid,
+    pub strategy: serde_json::Value,
+}
+
+/// How one changelog entry bumps under the proposed strategy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntryBumpPreview {
+    pub change_type: ChangeType,
+    pub category: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<i64>,
+    pub is_breaking: bool,
+    pub bump: BumpType,
+    /// Trigger that decided the bump; `None` for breaking entries and entries
+    /// that fall through to the default bump
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matched_trigger: Option<VersionTrigger>,
+}
+
+/// Result of a versioning strategy dry run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviewVersioningStrategyResponse {
+    pub changelog_id: Uuid,
+    pub data_source: String,
+    pub version: String,
+    pub current_bump: BumpType,
+    pub proposed_bump: BumpType,
+    pub bump_changed: bool,
+    pub would_cascade: bool,
+    pub entries: Vec<EntryBumpPreview>,
+}
+
+/// Errors that can occur during a dry run
+#[derive(Debug, thiserror::Error)]
+pub enum PreviewVersioningStrategyError {
+    #[error("Organization slug is required")]
+    SlugRequired,
+    #[error(transparent)]
+    Strategy(#[from] UpdateVersioningStrategyError),
+    #[error("Changelog '{changelog_id}' not found for organization '{slug}'")]
+    ChangelogNotFound { slug: String, changelog_id: Uuid },
+    #[error("Stored changelog '{0}' could not be read: {1}")]
+    CorruptChangelog(Uuid, String),
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl Request<Result<PreviewVersioningStrategyResponse, PreviewVersioningStrategyError>>
+    for PreviewVersioningStrategyQuery
+{
+}
+
+impl crate::cqrs::middleware::Query for PreviewVersioningStrategyQuery {}
+
+impl PreviewVersioningStrategyQuery {
+    pub fn validate(&self) -> Result<(), PreviewVersioningStrategyError> {
+        if self.slug.trim().is_empty() {
+            return Err(PreviewVersioningStrategyError::SlugRequired);
+        }
+        Ok(())
+    }
+}
+
+#[tracing::instrument(skip(pool, query), fields(slug = %query.slug, changelog_id = %query.changelog_id))]
+pub async fn handle(
+    pool: PgPool,
+    query: PreviewVersioningStrategyQuery,
+) -> Result<PreviewVersioningStrategyResponse, PreviewVersioningStrategyError> {
+    query.validate()?;
+    let strategy = parse_strategy(&query.strategy)?;
+
+    let changelog = sqlx::query!(
+        r#"
+        SELECT re.slug as data_source, v.version, vc.bump_type::text as "bump_type!", vc.entries
+        FROM version_changelogs vc
+        JOIN versions v ON v.id = vc.version_id
+        JOIN registry_entries re ON re.id = v.entry_id
+        JOIN organizations o ON o.id = re.organization_id
+        WHERE vc.id = $1 AND LOWER(o.slug) = LOWER($2)
+        "#,
+        query.changelog_id,
+        query.slug
+    )
+    .fetch_optional(&pool)
+    .await?
+    .ok_or_else(|| PreviewVersioningStrategyError::ChangelogNotFound {
+        slug: query.slug.clone(),
+        changelog_id: query.changelog_id,
+    })?;
+
+    let current_bump = BumpType::from_db_str(&changelog.bump_type).ok_or_else(|| {
+        PreviewVersioningStrategyError::CorruptChangelog(
+            query.changelog_id,
+            format!("unknown bump type '{}'", changelog.bump_type),
+        )
+    })?;
+    let entries: Vec<ChangelogEntry> = serde_json::from_value(changelog.entries).map_err(|e| {
+        PreviewVersioningStrategyError::CorruptChangelog(query.changelog_id, e.to_string())
+    })?;
+
+    let previews = entries
+        .iter()
+        .map(|entry| {
+            let matched = strategy.matching_trigger(&entry.change_type, &entry.category);
+            let bump = if entry.is_breaking {
+                BumpType::Major
+            } else {
+                matched
+                    .map(|(bump, _)| bump)
+                    .unwrap_or(strategy.default_bump)
+            };
+            EntryBumpPreview {
+                change_type: entry.change_type,
+                category: entry.category.clone(),
+                count: entry.count,
+                is_breaking: entry.is_breaking,
+                bump,
+                matched_trigger: matched
+                    .filter(|_| !entry.is_breaking)
+                    .map(|(_, trigger)| trigger.clone()),
+            }
+        })
+        .collect();
+
+    let proposed_bump = strategy.determine_bump_from_entries(&entries);
+
+    Ok(PreviewVersioningStrategyResponse {
+        changelog_id: query.changelog_id,
+        data_source: changelog.data_source,
+        version: changelog.version,
+        current_bump,
+        proposed_bump,
+        bump_changed: current_bump != proposed_bump,
+        would_cascade: strategy.should_cascade(proposed_bump),
+        entries: previews,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::shared::test_helpers::{TestDataSource, TestOrganization, TestVersion};
+    use crate::ingest::versioning::{VersionChangeType, VersioningStrategy};
+
+    async fn setup(pool: &PgPool) -> anyhow::Result<Uuid> {
+        let org = TestOrganization::new("ncbi", "NCBI").insert(pool).await?;
+        let ds = TestDataSource::new(&org, "taxonomy", "Taxonomy")
+            .insert(pool)
+            .await?;
+        let version = TestVersion::new(&ds, "1.1.0").insert(pool).await?;
+
+        let entries = vec![
+            ChangelogEntry::added("taxa", 120, "New taxa"),
+            ChangelogEntry::with_count(ChangeType::Modified, "taxa", 4, "Taxa reclassified", false),
+        ];
+        let changelog_id = sqlx::query_scalar(
+            r#"
+            INSERT INTO version_changelogs (version_id, bump_type, entries, summary_text)
+            VALUES ($1, 'minor', $2, 'Routine release')
+            RETURNING id
+            "#,
+        )
+        .bind(version.id)
+        .bind(serde_json::to_value(&entries)?)
+        .fetch_one(pool)
+        .await?;
+        Ok(changelog_id)
+    }
+
+    fn query(changelog_id: Uuid, strategy: &VersioningStrategy) -> PreviewVersioningStrategyQuery {
+        PreviewVersioningStrategyQuery {
+            slug: "ncbi".to_string(),
+            changelog_id,
+            strategy: serde_json::to_value(strategy).unwrap(),
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_handle_reports_stricter_bump(pool: PgPool) -> sqlx::Result<()> {
+        let changelog_id = setup(&pool).await.unwrap();
+
+        let mut strategy = VersioningStrategy::new(
+            vec![VersionTrigger::new(VersionChangeType::Modified, "taxa", "Reclassification")],
+            vec![VersionTrigger::new(VersionChangeType::Added, "*", "Anything added")],
+        );
+        strategy.cascade_on_major = false;
+
+        let response = handle(pool.clone(), query(changelog_id, &strategy))
+            .await
+            .unwrap();
+
+        assert_eq!(response.data_source, "taxonomy");
+        assert_eq!(response.current_bump, BumpType::Minor);
+        assert_eq!(response.proposed_bump, BumpType::Major);
+        assert!(response.bump_changed);
+        assert!(!response.would_cascade);
+        assert_eq!(response.entries[0].bump, BumpType::Minor);
+        assert_eq!(
+            response.entries[0]
+                .matched_trigger
+                .as_ref()
+                .unwrap()
+                .category,
+            "*"
+        );
+        assert_eq!(response.entries[1].bump, BumpType::Major);
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_handle_matches_slug_case_insensitively(pool: PgPool) -> sqlx::Result<()> {
+        let changelog_id = setup(&pool).await.unwrap();
+
+        let mut q = query(changelog_id, &VersioningStrategy::default());
+        q.slug = "NCBI".to_string();
+        let response = handle(pool.clone(), q).await.unwrap();
+        assert_eq!(response.data_source, "taxonomy");
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_handle_rejects_other_organizations(pool: PgPool) -> sqlx::Result<()> {
+        let changelog_id = setup(&pool).await.unwrap();
+
+        let mut q = query(changelog_id, &VersioningStrategy::default());
+        q.slug = "uniprot".to_string();
+        let result = handle(pool.clone(), q).await;
+        assert!(matches!(result, Err(PreviewVersioningStrategyError::ChangelogNotFound { .. })));
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_handle_rejects_invalid_strategy(pool: PgPool) -> sqlx::Result<()> {
+        let mut q = query(Uuid::new_v4(), &VersioningStrategy::default());
+        q.strategy = serde_json::json!({"major_triggers": "removed"});
+        let result = handle(pool, q).await;
+        assert!(matches!(result, Err(PreviewVersioningStrategyError::Strategy(_))));
+        Ok(())
+    }
+}
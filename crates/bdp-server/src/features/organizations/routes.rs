@@ -10,6 +10,9 @@
 //! - `GET /api/v1/organizations/:slug` - Get a single organization by slug
 //! - `PUT /api/v1/organizations/:slug` - Update an organization
 //! - `DELETE /api/v1/organizations/:slug` - Delete an organization
+//! - `GET /api/v1/organizations/:slug/versioning-strategy` - Get the versioning strategy (admin only)
+//! - `PUT /api/v1/organizations/:slug/versioning-strategy` - Replace the versioning strategy (admin only)
+//! - `POST /api/v1/organizations/:slug/versioning-strategy/dry-run` - Preview a strategy against a stored changelog (admin only)
 //!
 //! # Examples
 //!
@@ -25,6 +28,7 @@
 //! ```
 
 use crate::api::response::{ApiResponse, ErrorResponse};
+use crate::middleware::admin::AdminAuth;
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
@@ -39,8 +43,12 @@ use super::{
     commands::{
         CreateOrganizationCommand, CreateOrganizationError, DeleteOrganizationCommand,
         DeleteOrganizationError, UpdateOrganizationCommand, UpdateOrganizationError,
+        UpdateVersioningStrategyCommand, UpdateVersioningStrategyError,
+    },
+    queries::{
+        GetOrganizationQuery, GetVersioningStrategyError, GetVersioningStrategyQuery,
+        ListOrganizationsQuery, PreviewVersioningStrategyError, PreviewVersioningStrategyQuery,
     },
-    queries::{GetOrganizationQuery, ListOrganizationsQuery},
 };
 
 // ============================================================================
@@ -66,6 +74,9 @@ pub fn organizations_routes() -> Router<PgPool> {
         .route("/:slug", get(get_organization))
         .route("/:slug", put(update_organization))
         .route("/:slug", delete(delete_organization))
+        .route("/:slug/versioning-strategy", get(get_versioning_strategy))
+        .route("/:slug/versioning-strategy", put(update_versioning_strategy))
+        .route("/:slug/versioning-strategy/dry-run", post(preview_versioning_strategy))
 }
 
 // ============================================================================
@@ -193,6 +204,60 @@ async fn delete_organization(
     Ok((StatusCode::OK, Json(ApiResponse::success(response))).into_response())
 }
 
+/// Replace an organization's versioning strategy (admin only)
+///
+/// # Endpoint
+///
+/// `PUT /api/v1/organizations/:slug/versioning-strategy`
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///   "strategy": {
+///     "major_triggers": [
+///       {"change_type": "removed", "category": "taxa", "description": "Taxa removed"}
+///     ],
+///     "minor_triggers": [
+///       {"change_type": "added", "category": "*", "description": "Anything added"}
+///     ],
+///     "default_bump": "minor",
+///     "cascade_on_major": true,
+///     "cascade_on_minor": false
+///   }
+/// }
+/// ```
+///
+/// `"strategy": null` clears the stored strategy so the detector defaults apply.
+/// The new strategy is used from the next ingestion run.
+///
+/// # Response
+///
+/// - `200 OK` - Strategy stored
+/// - `400 Bad Request` - Unknown change type or inconsistent triggers
+/// - `401 Unauthorized` - Missing or invalid admin token
+/// - `404 Not Found` - Organization not found
+/// - `500 Internal Server Error` - Database error
+#[tracing::instrument(skip(pool, _admin, command), fields(slug = %slug))]
+async fn update_versioning_strategy(
+    State(pool): State<PgPool>,
+    _admin: AdminAuth,
+    Path(slug): Path<String>,
+    Json(mut command): Json<UpdateVersioningStrategyCommand>,
+) -> Result<Response, OrganizationApiError> {
+    command.slug = slug;
+
+    let response = super::commands::update_versioning_strategy::handle(pool, command).await?;
+
+    tracing::info!(
+        org_slug = %response.organization_slug,
+        uses_detector_defaults = response.uses_detector_defaults,
+        "Versioning strategy updated via API"
+    );
+
+    Ok((StatusCode::OK, Json(ApiResponse::success(response))).into_response())
+}
+
 // ============================================================================
 // Query Handlers (Read Operations)
 // ============================================================================
@@ -280,6 +345,76 @@ async fn list_organizations(
     )
 }
 
+/// Get an organization's versioning strategy (admin only)
+///
+/// # Endpoint
+///
+/// `GET /api/v1/organizations/:slug/versioning-strategy`
+///
+/// # Response
+///
+/// - `200 OK` - Stored strategy, or `null` with `uses_detector_defaults: true`
+/// - `401 Unauthorized` - Missing or invalid admin token
+/// - `404 Not Found` - Organization not found
+/// - `500 Internal Server Error` - Database error or unreadable stored strategy
+#[tracing::instrument(skip(pool, _admin), fields(slug = %slug))]
+async fn get_versioning_strategy(
+    State(pool): State<PgPool>,
+    _admin: AdminAuth,
+    Path(slug): Path<String>,
+) -> Result<Response, OrganizationApiError> {
+    let response =
+        super::queries::get_versioning_strategy::handle(pool, GetVersioningStrategyQuery { slug })
+            .await?;
+
+    Ok((StatusCode::OK, Json(ApiResponse::success(response))).into_response())
+}
+
+/// Preview how a stored changelog would bump under a proposed strategy (admin only)
+///
+/// # Endpoint
+///
+/// `POST /api/v1/organizations/:slug/versioning-strategy/dry-run`
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///   "changelog_id": "550e8400-e29b-41d4-a716-446655440000",
+///   "strategy": { "major_triggers": [], "minor_triggers": [] }
+/// }
+/// ```
+///
+/// Nothing is stored.
+///
+/// # Response
+///
+/// - `200 OK` - Current and proposed bump, per-entry bumps and cascade decision
+/// - `400 Bad Request` - Unknown change type or inconsistent triggers
+/// - `401 Unauthorized` - Missing or invalid admin token
+/// - `404 Not Found` - Changelog not found for this organization
+/// - `500 Internal Server Error` - Database error
+#[tracing::instrument(skip(pool, _admin, query), fields(slug = %slug))]
+async fn preview_versioning_strategy(
+    State(pool): State<PgPool>,
+    _admin: AdminAuth,
+    Path(slug): Path<String>,
+    Json(mut query): Json<PreviewVersioningStrategyQuery>,
+) -> Result<Response, OrganizationApiError> {
+    query.slug = slug;
+
+    let response = super::queries::preview_versioning_strategy::handle(pool, query).await?;
+
+    tracing::debug!(
+        changelog_id = %response.changelog_id,
+        current_bump = %response.current_bump,
+        proposed_bump = %response.proposed_bump,
+        "Versioning strategy dry run via API"
+    );
+
+    Ok((StatusCode::OK, Json(ApiResponse::success(response))).into_response())
+}
+
 // ============================================================================
 // Error Handling
 // ============================================================================
@@ -293,6 +428,9 @@ enum OrganizationApiError {
     DeleteError(DeleteOrganizationError),
     GetError(super::queries::GetOrganizationError),
     ListError(super::queries::ListOrganizationsError),
+    StrategyUpdateError(UpdateVersioningStrategyError),
+    StrategyGetError(GetVersioningStrategyError),
+    StrategyPreviewError(PreviewVersioningStrategyError),
 }
 
 impl From<CreateOrganizationError> for OrganizationApiError {
@@ -325,6 +463,24 @@ impl From<super::queries::ListOrganizationsError> for OrganizationApiError {
     }
 }
 
+impl From<UpdateVersioningStrategyError> for OrganizationApiError {
+    fn from(err: UpdateVersioningStrategyError) -> Self {
+        Self::StrategyUpdateError(err)
+    }
+}
+
+impl From<GetVersioningStrategyError> for OrganizationApiError {
+    fn from(err: GetVersioningStrategyError) -> Self {
+        Self::StrategyGetError(err)
+    }
+}
+
+impl From<PreviewVersioningStrategyError> for OrganizationApiError {
+    fn from(err: PreviewVersioningStrategyError) -> Self {
+        Self::StrategyPreviewError(err)
+    }
+}
+
 impl IntoResponse for OrganizationApiError {
     fn into_response(self) -> Response {
         match self {
@@ -433,6 +589,46 @@ impl IntoResponse for OrganizationApiError {
                 let error = ErrorResponse::new("INTERNAL_ERROR", "A database error occurred");
                 (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
             },
+
+            // Versioning strategy errors
+            OrganizationApiError::StrategyUpdateError(
+                UpdateVersioningStrategyError::SlugRequired
+                | UpdateVersioningStrategyError::Malformed(_)
+                | UpdateVersioningStrategyError::Invalid(_),
+            )
+            | OrganizationApiError::StrategyGetError(GetVersioningStrategyError::SlugRequired)
+            | OrganizationApiError::StrategyPreviewError(
+                PreviewVersioningStrategyError::SlugRequired
+                | PreviewVersioningStrategyError::Strategy(_),
+            ) => {
+                let error = ErrorResponse::new("VALIDATION_ERROR", self.to_string());
+                (StatusCode::BAD_REQUEST, Json(error)).into_response()
+            },
+            OrganizationApiError::StrategyUpdateError(UpdateVersioningStrategyError::NotFound(
+                _,
+            ))
+            | OrganizationApiError::StrategyGetError(GetVersioningStrategyError::NotFound(_))
+            | OrganizationApiError::StrategyPreviewError(
+                PreviewVersioningStrategyError::ChangelogNotFound { .. },
+            ) => {
+                let error = ErrorResponse::new("NOT_FOUND", self.to_string());
+                (StatusCode::NOT_FOUND, Json(error)).into_response()
+            },
+            OrganizationApiError::StrategyUpdateError(UpdateVersioningStrategyError::Database(
+                _,
+            ))
+            | OrganizationApiError::StrategyGetError(
+                GetVersioningStrategyError::Database(_)
+                | GetVersioningStrategyError::Corrupt { .. },
+            )
+            | OrganizationApiError::StrategyPreviewError(
+                PreviewVersioningStrategyError::Database(_)
+                | PreviewVersioningStrategyError::CorruptChangelog(..),
+            ) => {
+                tracing::error!("Error handling versioning strategy request: {}", self);
+                let error = ErrorResponse::new("INTERNAL_ERROR", "A database error occurred");
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            },
         }
     }
 }
@@ -445,6 +641,9 @@ impl std::fmt::Display for OrganizationApiError {
             Self::DeleteError(e) => write!(f, "{}", e),
             Self::GetError(e) => write!(f, "{}", e),
             Self::ListError(e) => write!(f, "{}", e),
+            Self::StrategyUpdateError(e) => write!(f, "{}", e),
+            Self::StrategyGetError(e) => write!(f, "{}", e),
+            Self::StrategyPreviewError(e) => write!(f, "{}", e),
         }
     }
 }
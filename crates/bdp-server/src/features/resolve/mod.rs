@@ -2,8 +2,8 @@ pub mod queries;
 pub mod routes;
 
 pub use queries::{
-    DependencyInfo, ResolveManifestError, ResolveManifestQuery, ResolveManifestResponse,
-    ResolvedSource, ResolvedTool, SourceSpec, ToolSpec,
+    BundleInfo, DependencyInfo, ResolveManifestError, ResolveManifestQuery,
+    ResolveManifestResponse, ResolvedSource, ResolvedTool, SourceSpec, ToolSpec,
 };
 
 pub use routes::resolve_routes;
@@ -4,6 +4,8 @@ use sqlx::PgPool;
 use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
+use crate::ingest::version_mapping::VersionMapper;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SourceSpec {
     pub organization: String,
@@ -117,6 +119,10 @@ pub struct ResolveManifestQuery {
     pub sources: Vec<String>,
     #[serde(default)]
     pub tools: Vec<String>,
+    /// Source specs already pinned in the client's lockfile. These still
+    /// resolve when their version has been yanked; new specs do not.
+    #[serde(default)]
+    pub locked: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -138,6 +144,22 @@ pub struct ResolvedSource {
     pub dependency_count: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dependencies: Option<Vec<DependencyInfo>>,
+    /// Bundle of the dependency files in this format, the preferred download
+    /// for aggregates once it has been built
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bundle: Option<BundleInfo>,
+    /// Set when a locked spec points at a yanked version
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub yanked: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub yanked_reason: Option<String>,
+    /// Entry (`org:name`) the spec resolved to instead of the one it names,
+    /// when that identifier was retired upstream or deprecated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replaced_by: Option<String>,
+    /// Retired or deprecated identifiers met while resolving the spec
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -157,6 +179,14 @@ pub struct DependencyInfo {
     pub size: i64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleInfo {
+    pub bundle_format: String,
+    pub checksum: String,
+    pub size: i64,
+    pub member_count: i32,
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ResolveManifestError {
     #[error("Invalid source specification: '{0}'. Expected format: 'registry:identifier-format@version' (e.g., 'uniprot:P01308-fasta@1.0').")]
@@ -171,6 +201,8 @@ pub enum ResolveManifestError {
         "Version '{0}' not available. Use the API to list available versions for this source."
     )]
     VersionNotFound(String),
+    #[error("Version '{0}' has been yanked: {1}. Pick a different version for new specs.")]
+    VersionYanked(String, String),
     #[error("Format '{0}' not available for this data source. Check available formats with the data source details endpoint.")]
     FormatNotAvailable(String),
     #[error("Dependency conflict: {0}. Two sources require incompatible versions of the same dependency.")]
@@ -210,7 +242,8 @@ pub async fn handle(
         let spec =
             SourceSpec::parse(source_spec_str).map_err(ResolveManifestError::InvalidSourceSpec)?;
 
-        let resolved = resolve_source(&pool, &spec).await?;
+        let allow_yanked = query.locked.contains(source_spec_str);
+        let resolved = resolve_source(&pool, &spec, allow_yanked).await?;
         resolved_sources.insert(source_spec_str.clone(), resolved);
     }
 
@@ -232,41 +265,49 @@ pub async fn handle(
 async fn resolve_source(
     pool: &PgPool,
     spec: &SourceSpec,
+    allow_yanked: bool,
 ) -> Result<ResolvedSource, ResolveManifestError> {
-    let entry = sqlx::query!(
-        r#"
-        SELECT re.id, re.slug
-        FROM registry_entries re
-        JOIN organizations o ON o.id = re.organization_id
-        WHERE LOWER(o.slug) = LOWER($1) AND LOWER(re.slug) = LOWER($2) AND re.entry_type = 'data_source'
-        "#,
-        spec.organization,
-        spec.name
-    )
-    .fetch_optional(pool)
-    .await?
-    .ok_or_else(|| {
-        ResolveManifestError::SourceNotFound(format!("{}:{}", spec.organization, spec.name))
-    })?;
+    let (entry, warnings) = find_source_entry(pool, spec).await?;
+    let source_name = format!("{}:{}", entry.organization_slug, entry.slug);
+    let requested = format!("{}:{}", spec.organization, spec.name);
+    let replaced_by = (!source_name.eq_ignore_ascii_case(&requested)).then(|| source_name.clone());
+
+    // Specs may pin the upstream release (`@2025_01`) instead of the internal
+    // version (`@1.4.0`); a published internal version of the same name wins
+    let mapped_version = VersionMapper::new(pool.clone())
+        .external_to_internal(&entry.organization_slug, Some(&entry.slug), &spec.version)
+        .await?
+        .unwrap_or_else(|| spec.version.clone());
 
     let version = sqlx::query!(
         r#"
-        SELECT id, version, external_version, dependency_count
+        SELECT id, version, external_version, dependency_count, yanked_at, yanked_reason
         FROM versions
-        WHERE entry_id = $1 AND version = $2
+        WHERE entry_id = $1 AND version IN ($2, $3)
+        ORDER BY version = $2 DESC
+        LIMIT 1
         "#,
         entry.id,
-        spec.version
+        spec.version,
+        mapped_version
     )
     .fetch_optional(pool)
     .await?
     .ok_or_else(|| {
-        ResolveManifestError::VersionNotFound(format!(
-            "{}:{}@{}",
-            spec.organization, spec.name, spec.version
-        ))
+        ResolveManifestError::VersionNotFound(format!("{}@{}", source_name, spec.version))
     })?;
 
+    let yanked = version.yanked_at.is_some();
+    if yanked && !allow_yanked {
+        return Err(ResolveManifestError::VersionYanked(
+            spec.to_source(),
+            version
+                .yanked_reason
+                .clone()
+                .unwrap_or_else(|| "no reason given".to_string()),
+        ));
+    }
+
     let file = sqlx::query!(
         r#"
         SELECT checksum, size_bytes
@@ -280,21 +321,24 @@ async fn resolve_source(
     .await?
     .ok_or_else(|| {
         ResolveManifestError::FormatNotAvailable(format!(
-            "Format '{}' not available for {}:{}@{}",
-            spec.format, spec.organization, spec.name, spec.version
+            "Format '{}' not available for {}@{}",
+            spec.format, source_name, spec.version
         ))
     })?;
 
     let has_dependencies = version.dependency_count.unwrap_or(0) > 0;
 
-    let dependencies = if has_dependencies {
-        Some(fetch_dependencies(pool, version.id).await?)
+    let (dependencies, bundle) = if has_dependencies {
+        (
+            Some(fetch_dependencies(pool, version.id, &spec.format).await?),
+            fetch_bundle(pool, version.id, &spec.format).await?,
+        )
     } else {
-        None
+        (None, None)
     };
 
     Ok(ResolvedSource {
-        resolved: spec.to_source(),
+        resolved: format!("{}@{}", source_name, version.version),
         format: spec.format.clone(),
         checksum: file.checksum,
         size: file.size_bytes,
@@ -302,32 +346,209 @@ async fn resolve_source(
         has_dependencies,
         dependency_count: version.dependency_count,
         dependencies,
+        bundle,
+        yanked,
+        yanked_reason: version.yanked_reason,
+        replaced_by,
+        warnings,
     })
 }
 
+/// Longest chain of successors followed from a deprecated entry
+const MAX_SUCCESSION_DEPTH: usize = 10;
+
+/// Registry entry of a data source, with its deprecation
+#[derive(Debug)]
+struct SourceEntry {
+    id: Uuid,
+    organization_slug: String,
+    slug: String,
+    deprecated: bool,
+    deprecated_reason: Option<String>,
+    superseded_by_id: Option<Uuid>,
+}
+
+/// Find the data source a spec names
+///
+/// Identifiers retired upstream still resolve: a previous accession or
+/// synonym through its alias, and a deprecated entry through the entries
+/// that superseded it. Each step is reported in the returned warnings.
+async fn find_source_entry(
+    pool: &PgPool,
+    spec: &SourceSpec,
+) -> Result<(SourceEntry, Vec<String>), ResolveManifestError> {
+    let requested = format!("{}:{}", spec.organization, spec.name);
+    let mut warnings = Vec::new();
+
+    let by_slug = sqlx::query_as!(
+        SourceEntry,
+        r#"
+        SELECT re.id, o.slug AS organization_slug, re.slug,
+               COALESCE(re.deprecated, FALSE) AS "deprecated!", re.deprecated_reason,
+               re.superseded_by_id
+        FROM registry_entries re
+        JOIN organizations o ON o.id = re.organization_id
+        WHERE LOWER(o.slug) = LOWER($1) AND LOWER(re.slug) = LOWER($2) AND re.entry_type = 'data_source'
+        "#,
+        spec.organization,
+        spec.name
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let mut entry = match by_slug {
+        Some(entry) => entry,
+        None => {
+            let (entry, alias_type) = find_aliased_entry(pool, spec)
+                .await?
+                .ok_or_else(|| ResolveManifestError::SourceNotFound(requested.clone()))?;
+            let kind = match alias_type.as_str() {
+                "previous_accession" => "a previous accession",
+                "synonym" => "a synonym",
+                _ => "an alias",
+            };
+            warnings.push(format!(
+                "'{}' is {} of '{}:{}'",
+                requested, kind, entry.organization_slug, entry.slug
+            ));
+            entry
+        },
+    };
+
+    let mut followed = HashSet::from([entry.id]);
+    while entry.deprecated {
+        let name = format!("{}:{}", entry.organization_slug, entry.slug);
+        let reason = entry
+            .deprecated_reason
+            .clone()
+            .unwrap_or_else(|| "no reason given".to_string());
+
+        let successor = match entry.superseded_by_id {
+            Some(id) if followed.len() <= MAX_SUCCESSION_DEPTH && followed.insert(id) => {
+                sqlx::query_as!(
+                    SourceEntry,
+                    r#"
+                    SELECT re.id, o.slug AS organization_slug, re.slug,
+                           COALESCE(re.deprecated, FALSE) AS "deprecated!", re.deprecated_reason,
+                           re.superseded_by_id
+                    FROM registry_entries re
+                    JOIN organizations o ON o.id = re.organization_id
+                    WHERE re.id = $1 AND re.entry_type = 'data_source'
+                    "#,
+                    id
+                )
+                .fetch_optional(pool)
+                .await?
+            },
+            _ => None,
+        };
+
+        match successor {
+            Some(successor) => {
+                warnings.push(format!(
+                    "'{}' is deprecated ({}); using '{}:{}' instead",
+                    name, reason, successor.organization_slug, successor.slug
+                ));
+                entry = successor;
+            },
+            None => {
+                warnings.push(format!("'{}' is deprecated: {}", name, reason));
+                break;
+            },
+        }
+    }
+
+    Ok((entry, warnings))
+}
+
+/// Find the data source a spec's identifier is an alias of, with the alias type
+async fn find_aliased_entry(
+    pool: &PgPool,
+    spec: &SourceSpec,
+) -> Result<Option<(SourceEntry, String)>, ResolveManifestError> {
+    let Some(alias) = sqlx::query!(
+        r#"
+        SELECT a.data_source_id, a.alias_type
+        FROM data_source_aliases a
+        JOIN registry_entries re ON re.id = a.data_source_id
+        JOIN organizations o ON o.id = re.organization_id
+        WHERE LOWER(o.slug) = LOWER($1) AND LOWER(a.alias) = LOWER($2)
+          AND re.entry_type = 'data_source'
+        ORDER BY a.alias_type = 'previous_accession' DESC, a.created_at DESC
+        LIMIT 1
+        "#,
+        spec.organization,
+        spec.name
+    )
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let entry = sqlx::query_as!(
+        SourceEntry,
+        r#"
+        SELECT re.id, o.slug AS organization_slug, re.slug,
+               COALESCE(re.deprecated, FALSE) AS "deprecated!", re.deprecated_reason,
+               re.superseded_by_id
+        FROM registry_entries re
+        JOIN organizations o ON o.id = re.organization_id
+        WHERE re.id = $1
+        "#,
+        alias.data_source_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(Some((entry, alias.alias_type)))
+}
+
+/// Fetch the transitive dependency closure of a version
+///
+/// Every dependency appears once, with a single file: the one in the
+/// requested format when it has one, otherwise its first format. A cycle
+/// back to the version itself doesn't list it as its own dependency.
 async fn fetch_dependencies(
     pool: &PgPool,
     version_id: Uuid,
+    format: &str,
 ) -> Result<Vec<DependencyInfo>, ResolveManifestError> {
     let deps = sqlx::query!(
         r#"
+        WITH RECURSIVE closure (entry_id, version) AS (
+            SELECT d.depends_on_entry_id, d.depends_on_version
+            FROM dependencies d
+            WHERE d.version_id = $1
+            UNION
+            SELECT d.depends_on_entry_id, d.depends_on_version
+            FROM closure c
+            JOIN versions v ON v.entry_id = c.entry_id AND v.version = c.version
+            JOIN dependencies d ON d.version_id = v.id
+        )
         SELECT
-            o.slug as org_slug,
-            re.slug as entry_slug,
-            d.depends_on_version,
-            vf.format,
-            vf.checksum,
-            vf.size_bytes
-        FROM dependencies d
-        JOIN registry_entries re ON re.id = d.depends_on_entry_id
+            o.slug as "org_slug!",
+            re.slug as "entry_slug!",
+            c.version as "version!",
+            vf.format as "format!",
+            vf.checksum as "checksum!",
+            vf.size_bytes as "size_bytes!"
+        FROM closure c
+        JOIN registry_entries re ON re.id = c.entry_id
         JOIN organizations o ON o.id = re.organization_id
-        JOIN versions v ON v.entry_id = re.id AND v.version = d.depends_on_version
-        JOIN version_files vf ON vf.version_id = v.id
-        WHERE d.version_id = $1
-        ORDER BY re.slug
-        LIMIT 100
+        JOIN versions v ON v.entry_id = c.entry_id AND v.version = c.version
+        JOIN LATERAL (
+            SELECT format, checksum, size_bytes
+            FROM version_files
+            WHERE version_id = v.id
+            ORDER BY format = $2 DESC, format
+            LIMIT 1
+        ) vf ON true
+        WHERE v.id <> $1
+        ORDER BY o.slug, re.slug, c.version
         "#,
-        version_id
+        version_id,
+        format
     )
     .fetch_all(pool)
     .await?;
@@ -335,7 +556,7 @@ async fn fetch_dependencies(
     Ok(deps
         .into_iter()
         .map(|dep| DependencyInfo {
-            source: format!("{}:{}@{}", dep.org_slug, dep.entry_slug, dep.depends_on_version),
+            source: format!("{}:{}@{}", dep.org_slug, dep.entry_slug, dep.version),
             format: dep.format,
             checksum: dep.checksum,
             size: dep.size_bytes,
@@ -343,6 +564,28 @@ async fn fetch_dependencies(
         .collect())
 }
 
+/// The built bundle of a version in a format, if any
+async fn fetch_bundle(
+    pool: &PgPool,
+    version_id: Uuid,
+    format: &str,
+) -> Result<Option<BundleInfo>, ResolveManifestError> {
+    let bundle = sqlx::query_as!(
+        BundleInfo,
+        r#"
+        SELECT bundle_format, sha256 AS checksum, size_bytes AS size, member_count
+        FROM version_bundles
+        WHERE version_id = $1 AND format = $2
+        "#,
+        version_id,
+        format
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(bundle)
+}
+
 async fn resolve_tool(
     pool: &PgPool,
     spec: &ToolSpec,
@@ -531,6 +774,7 @@ mod tests {
         let query = ResolveManifestQuery {
             sources: vec![],
             tools: vec![],
+            locked: vec![],
         };
         assert!(query.validate().is_err());
     }
@@ -540,6 +784,7 @@ mod tests {
         let query = ResolveManifestQuery {
             sources: vec!["uniprot:P01308-fasta@1.0".to_string()],
             tools: vec![],
+            locked: vec![],
         };
         assert!(query.validate().is_ok());
     }
@@ -549,6 +794,7 @@ mod tests {
         let query = ResolveManifestQuery {
             sources: vec![],
             tools: vec!["ncbi:blast@2.14.0".to_string()],
+            locked: vec![],
         };
         assert!(query.validate().is_ok());
     }
@@ -616,6 +862,7 @@ mod tests {
         let query = ResolveManifestQuery {
             sources: vec!["uniprot:P01308-fasta@1.0".to_string()],
             tools: vec![],
+            locked: vec![],
         };
 
         let result = handle(pool.clone(), query).await;
@@ -627,6 +874,127 @@ mod tests {
         assert_eq!(source.format, "fasta");
         assert_eq!(source.checksum, "abc123");
         assert_eq!(source.size, 1024);
+        assert!(!source.yanked);
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_handle_resolve_source_by_external_version(pool: PgPool) -> sqlx::Result<()> {
+        let org_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO organizations (slug, name, is_system) VALUES ('uniprot', 'UniProt', true) RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        sqlx::query(
+            r#"
+            WITH entry AS (
+                INSERT INTO registry_entries (organization_id, slug, name, entry_type)
+                VALUES ($1, 'P01308', 'Insulin', 'data_source')
+                RETURNING id
+            ), version AS (
+                INSERT INTO versions (entry_id, version, external_version)
+                SELECT id, v.version, v.external_version
+                FROM entry, (VALUES ('1.0', '2025_01'), ('1.1', '2025_02')) AS v (version, external_version)
+                RETURNING id, version
+            )
+            INSERT INTO version_files (version_id, format, s3_key, checksum, size_bytes)
+            SELECT id, 'fasta', 'test/P01308_' || version || '.fasta', 'sum-' || version, 1024
+            FROM version
+            "#,
+        )
+        .bind(org_id)
+        .execute(&pool)
+        .await?;
+
+        let query = ResolveManifestQuery {
+            sources: vec![
+                "uniprot:P01308-fasta@2025_02".to_string(),
+                "uniprot:P01308-fasta@1.1".to_string(),
+            ],
+            tools: vec![],
+            locked: vec![],
+        };
+        // Both forms pin the same version, so they don't conflict
+        let response = handle(pool.clone(), query).await.unwrap();
+
+        let pinned_release = &response.sources["uniprot:P01308-fasta@2025_02"];
+        assert_eq!(pinned_release.resolved, "uniprot:P01308@1.1");
+        assert_eq!(pinned_release.checksum, "sum-1.1");
+        assert_eq!(pinned_release.external_version.as_deref(), Some("2025_02"));
+
+        let pinned_internal = &response.sources["uniprot:P01308-fasta@1.1"];
+        assert_eq!(pinned_internal.resolved, pinned_release.resolved);
+
+        let query = ResolveManifestQuery {
+            sources: vec!["uniprot:P01308-fasta@2024_01".to_string()],
+            tools: vec![],
+            locked: vec![],
+        };
+        assert!(matches!(
+            handle(pool, query).await,
+            Err(ResolveManifestError::VersionNotFound(_))
+        ));
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_handle_yanked_version(pool: PgPool) -> sqlx::Result<()> {
+        let org_id = sqlx::query_scalar!(
+            "INSERT INTO organizations (slug, name, is_system) VALUES ('uniprot', 'UniProt', true) RETURNING id"
+        )
+        .fetch_one(&pool)
+        .await?;
+        let entry_id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO registry_entries (organization_id, slug, name, entry_type)
+            VALUES ($1, 'P01308', 'Insulin', 'data_source')
+            RETURNING id
+            "#,
+            org_id
+        )
+        .fetch_one(&pool)
+        .await?;
+        let version_id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO versions (entry_id, version, yanked_at, yanked_reason)
+            VALUES ($1, '1.0', NOW(), 'wrong organism')
+            RETURNING id
+            "#,
+            entry_id
+        )
+        .fetch_one(&pool)
+        .await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO version_files (version_id, format, s3_key, checksum, size_bytes)
+            VALUES ($1, 'fasta', 'test/P01308.fasta', 'abc123', 1024)
+            "#,
+            version_id
+        )
+        .execute(&pool)
+        .await?;
+
+        let spec = "uniprot:P01308-fasta@1.0".to_string();
+
+        // New specs cannot resolve to a yanked version
+        let query = ResolveManifestQuery {
+            sources: vec![spec.clone()],
+            tools: vec![],
+            locked: vec![],
+        };
+        let result = handle(pool.clone(), query).await;
+        assert!(matches!(result, Err(ResolveManifestError::VersionYanked(_, _))));
+
+        // Locked specs still resolve, flagged as yanked
+        let query = ResolveManifestQuery {
+            sources: vec![spec.clone()],
+            tools: vec![],
+            locked: vec![spec.clone()],
+        };
+        let response = handle(pool.clone(), query).await.unwrap();
+        let source = response.sources.get(&spec).unwrap();
+        assert!(source.yanked);
+        assert_eq!(source.yanked_reason.as_deref(), Some("wrong organism"));
         Ok(())
     }
 
@@ -635,6 +1003,7 @@ mod tests {
         let query = ResolveManifestQuery {
             sources: vec!["nonexistent:source-fasta@1.0".to_string()],
             tools: vec![],
+            locked: vec![],
         };
 
         let result = handle(pool.clone(), query).await;
@@ -758,6 +1127,7 @@ mod tests {
         let query = ResolveManifestQuery {
             sources: vec!["uniprot:all-fasta@1.0".to_string()],
             tools: vec![],
+            locked: vec![],
         };
 
         let result = handle(pool.clone(), query).await;
@@ -773,4 +1143,208 @@ mod tests {
         assert_eq!(deps[0].source, "uniprot:P01308@1.0");
         Ok(())
     }
+
+    #[sqlx::test]
+    async fn test_handle_resolve_transitive_dependencies(pool: PgPool) -> sqlx::Result<()> {
+        let org_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO organizations (slug, name, is_system) VALUES ('uniprot', 'UniProt', true) RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await?;
+
+        // proteome -> {P01308, P01315}, P01308 -> P01315, P01315 -> proteome (a cycle)
+        let mut versions = HashMap::new();
+        for (slug, formats) in [
+            ("proteome", &["fasta"][..]),
+            ("P01308", &["json", "fasta"][..]),
+            ("P01315", &["json"][..]),
+        ] {
+            let version_id: Uuid = sqlx::query_scalar(
+                r#"
+                WITH entry AS (
+                    INSERT INTO registry_entries (organization_id, slug, name, entry_type)
+                    VALUES ($1, $2, $2, 'data_source')
+                    RETURNING id
+                )
+                INSERT INTO versions (entry_id, version, dependency_count)
+                SELECT id, '1.0', 1 FROM entry
+                RETURNING id
+                "#,
+            )
+            .bind(org_id)
+            .bind(slug)
+            .fetch_one(&pool)
+            .await?;
+            for format in formats {
+                sqlx::query(
+                    "INSERT INTO version_files (version_id, format, s3_key, checksum, size_bytes) \
+                     VALUES ($1, $2, $3, $4, 10)",
+                )
+                .bind(version_id)
+                .bind(format)
+                .bind(format!("test/{}.{}", slug, format))
+                .bind(format!("{}-{}", slug, format))
+                .execute(&pool)
+                .await?;
+            }
+            versions.insert(slug, version_id);
+        }
+        for (from, to) in [
+            ("proteome", "P01308"),
+            ("proteome", "P01315"),
+            ("P01308", "P01315"),
+            ("P01315", "proteome"),
+        ] {
+            sqlx::query(
+                "INSERT INTO dependencies (version_id, depends_on_entry_id, depends_on_version) \
+                 SELECT $1, entry_id, '1.0' FROM versions WHERE id = $2",
+            )
+            .bind(versions[from])
+            .bind(versions[to])
+            .execute(&pool)
+            .await?;
+        }
+
+        let query = ResolveManifestQuery {
+            sources: vec!["uniprot:proteome-fasta@1.0".to_string()],
+            tools: vec![],
+            locked: vec![],
+        };
+        let response = handle(pool.clone(), query).await.unwrap();
+        let deps = response.sources["uniprot:proteome-fasta@1.0"]
+            .dependencies
+            .clone()
+            .unwrap();
+
+        let resolved: Vec<(&str, &str, &str)> = deps
+            .iter()
+            .map(|d| (d.source.as_str(), d.format.as_str(), d.checksum.as_str()))
+            .collect();
+        assert_eq!(
+            resolved,
+            vec![
+                ("uniprot:P01308@1.0", "fasta", "P01308-fasta"),
+                ("uniprot:P01315@1.0", "json", "P01315-json"),
+            ]
+        );
+        Ok(())
+    }
+
+    /// Insert `uniprot` proteins with a `1.0` FASTA file, returning their entry IDs
+    async fn insert_proteins(pool: &PgPool, slugs: &[&str]) -> sqlx::Result<Vec<Uuid>> {
+        let org_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO organizations (slug, name, is_system) VALUES ('uniprot', 'UniProt', true) RETURNING id",
+        )
+        .fetch_one(pool)
+        .await?;
+
+        let mut ids = Vec::new();
+        for slug in slugs {
+            let id: Uuid = sqlx::query_scalar(
+                r#"
+                WITH entry AS (
+                    INSERT INTO registry_entries (organization_id, slug, name, entry_type)
+                    VALUES ($1, $2, $2, 'data_source')
+                    RETURNING id
+                ), source AS (
+                    INSERT INTO data_sources (id, source_type) SELECT id, 'protein' FROM entry
+                ), version AS (
+                    INSERT INTO versions (entry_id, version) SELECT id, '1.0' FROM entry
+                    RETURNING id
+                ), file AS (
+                    INSERT INTO version_files (version_id, format, s3_key, checksum, size_bytes)
+                    SELECT id, 'fasta', 'test/' || $2 || '.fasta', 'sum-' || $2, 1024 FROM version
+                )
+                SELECT id FROM entry
+                "#,
+            )
+            .bind(org_id)
+            .bind(slug)
+            .fetch_one(pool)
+            .await?;
+            ids.push(id);
+        }
+        Ok(ids)
+    }
+
+    #[sqlx::test]
+    async fn test_handle_resolve_previous_accession(pool: PgPool) -> sqlx::Result<()> {
+        let ids = insert_proteins(&pool, &["P01308"]).await?;
+        sqlx::query(
+            "INSERT INTO data_source_aliases (data_source_id, alias, alias_type) \
+             VALUES ($1, 'Q00001', 'previous_accession')",
+        )
+        .bind(ids[0])
+        .execute(&pool)
+        .await?;
+
+        let spec = "uniprot:Q00001-fasta@1.0".to_string();
+        let query = ResolveManifestQuery {
+            sources: vec![spec.clone()],
+            tools: vec![],
+            locked: vec![],
+        };
+        let response = handle(pool.clone(), query).await.unwrap();
+
+        let source = &response.sources[&spec];
+        assert_eq!(source.resolved, "uniprot:P01308@1.0");
+        assert_eq!(source.checksum, "sum-P01308");
+        assert_eq!(source.replaced_by.as_deref(), Some("uniprot:P01308"));
+        assert_eq!(
+            source.warnings,
+            vec!["'uniprot:Q00001' is a previous accession of 'uniprot:P01308'"]
+        );
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_handle_resolve_deprecated_entries(pool: PgPool) -> sqlx::Result<()> {
+        let ids = insert_proteins(&pool, &["P00001", "P00002", "P00003", "P00004"]).await?;
+        // P00001 was merged into P00002, which was merged into P00003
+        for (old, new) in [(0, 1), (1, 2)] {
+            sqlx::query(
+                "UPDATE registry_entries SET deprecated = TRUE, deprecated_reason = 'Merged', \
+                 superseded_by_id = $2 WHERE id = $1",
+            )
+            .bind(ids[old])
+            .bind(ids[new])
+            .execute(&pool)
+            .await?;
+        }
+        sqlx::query(
+            "UPDATE registry_entries SET deprecated = TRUE, deprecated_reason = 'Withdrawn' \
+             WHERE id = $1",
+        )
+        .bind(ids[3])
+        .execute(&pool)
+        .await?;
+
+        let query = ResolveManifestQuery {
+            sources: vec![
+                "uniprot:P00001-fasta@1.0".to_string(),
+                "uniprot:P00004-fasta@1.0".to_string(),
+            ],
+            tools: vec![],
+            locked: vec![],
+        };
+        let response = handle(pool.clone(), query).await.unwrap();
+
+        let merged = &response.sources["uniprot:P00001-fasta@1.0"];
+        assert_eq!(merged.resolved, "uniprot:P00003@1.0");
+        assert_eq!(merged.replaced_by.as_deref(), Some("uniprot:P00003"));
+        assert_eq!(
+            merged.warnings,
+            vec![
+                "'uniprot:P00001' is deprecated (Merged); using 'uniprot:P00002' instead",
+                "'uniprot:P00002' is deprecated (Merged); using 'uniprot:P00003' instead",
+            ]
+        );
+
+        // Without a successor the entry itself still resolves
+        let withdrawn = &response.sources["uniprot:P00004-fasta@1.0"];
+        assert_eq!(withdrawn.resolved, "uniprot:P00004@1.0");
+        assert!(withdrawn.replaced_by.is_none());
+        assert_eq!(withdrawn.warnings, vec!["'uniprot:P00004' is deprecated: Withdrawn"]);
+        Ok(())
+    }
 }
@@ -61,6 +61,10 @@ impl IntoResponse for ResolveApiError {
                 let error = ErrorResponse::new("CONFLICT", msg);
                 (StatusCode::CONFLICT, Json(error)).into_response()
             },
+            ResolveApiError::ResolveError(ResolveManifestError::VersionYanked(_, _)) => {
+                let error = ErrorResponse::new("VERSION_YANKED", self.to_string());
+                (StatusCode::GONE, Json(error)).into_response()
+            },
             ResolveApiError::ResolveError(ResolveManifestError::Database(_)) => {
                 tracing::error!("Database error during manifest resolution: {}", self);
                 let error = ErrorResponse::new("INTERNAL_ERROR", "A database error occurred");
@@ -6,6 +6,7 @@
 use mediator::Request;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
+use std::collections::HashMap;
 use uuid::Uuid;
 
 use crate::features::shared::pagination::{PaginationMetadata, PaginationParams};
@@ -95,6 +96,15 @@ pub struct SearchResultItem {
     pub external_id: Option<String>,
     /// Search relevance rank (higher is more relevant)
     pub rank: f32,
+    /// Whether the entry has been deprecated
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deprecated: bool,
+    /// Why the entry was deprecated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated_reason: Option<String>,
+    /// Entry that replaced a deprecated one (`org:slug`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub superseded_by: Option<String>,
 }
 
 /// Organism information in search results
@@ -344,6 +354,9 @@ async fn search_organizations(
             total_downloads: 0,
             external_id: None,
             rank: r.rank,
+            deprecated: false,
+            deprecated_reason: None,
+            superseded_by: None,
         })
         .collect())
 }
@@ -420,7 +433,7 @@ async fn search_registry_entries(
     .fetch_all(pool)
     .await?;
 
-    Ok(records
+    let mut items: Vec<SearchResultItem> = records
         .into_iter()
         .map(|r| SearchResultItem {
             id: r.id,
@@ -442,8 +455,47 @@ async fn search_registry_entries(
             total_downloads: r.total_downloads,
             external_id: r.external_id,
             rank: r.rank,
+            deprecated: false,
+            deprecated_reason: None,
+            superseded_by: None,
         })
-        .collect())
+        .collect();
+
+    flag_deprecated(pool, &mut items).await?;
+    Ok(items)
+}
+
+/// Flag deprecated entries among the results, with their replacement
+async fn flag_deprecated(pool: &PgPool, items: &mut [SearchResultItem]) -> Result<(), sqlx::Error> {
+    if items.is_empty() {
+        return Ok(());
+    }
+
+    let ids: Vec<Uuid> = items.iter().map(|item| item.id).collect();
+    let deprecated: HashMap<Uuid, (Option<String>, Option<String>)> = sqlx::query!(
+        r#"
+        SELECT re.id, re.deprecated_reason, so.slug || ':' || s.slug as superseded_by
+        FROM registry_entries re
+        LEFT JOIN registry_entries s ON s.id = re.superseded_by_id
+        LEFT JOIN organizations so ON so.id = s.organization_id
+        WHERE re.id = ANY($1) AND re.deprecated
+        "#,
+        &ids
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|r| (r.id, (r.deprecated_reason, r.superseded_by)))
+    .collect();
+
+    for item in items.iter_mut() {
+        if let Some((reason, superseded_by)) = deprecated.get(&item.id) {
+            item.deprecated = true;
+            item.deprecated_reason = reason.clone();
+            item.superseded_by = superseded_by.clone();
+        }
+    }
+    Ok(())
 }
 
 async fn count_search_results(
@@ -709,6 +761,56 @@ mod tests {
         Ok(())
     }
 
+    #[sqlx::test]
+    async fn test_handle_flags_deprecated_entries(pool: PgPool) -> sqlx::Result<()> {
+        let org_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO organizations (slug, name, is_system) VALUES ('uniprot', 'UniProt', true) RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            WITH successor AS (
+                INSERT INTO registry_entries (organization_id, slug, name, entry_type)
+                VALUES ($1, 'P01308', 'Insulin', 'data_source')
+                RETURNING id
+            )
+            INSERT INTO registry_entries
+                (organization_id, slug, name, entry_type, deprecated, deprecated_reason, superseded_by_id)
+            SELECT $1, 'Q00001', 'Insulin fragment', 'data_source', TRUE, 'Merged into P01308', id
+            FROM successor
+            "#,
+        )
+        .bind(org_id)
+        .execute(&pool)
+        .await?;
+        sqlx::query("REFRESH MATERIALIZED VIEW search_registry_entries_mv")
+            .execute(&pool)
+            .await?;
+
+        let query = UnifiedSearchQuery {
+            query: "insulin".to_string(),
+            type_filter: Some(vec!["data_source".to_string()]),
+            source_type_filter: None,
+            organism: None,
+            format: None,
+            pagination: PaginationParams::new(Some(1), Some(10)),
+        };
+        let response = handle(pool.clone(), query).await.unwrap();
+        assert_eq!(response.items.len(), 2);
+
+        let current = response.items.iter().find(|i| i.slug == "P01308").unwrap();
+        assert!(!current.deprecated);
+        assert!(current.superseded_by.is_none());
+
+        let merged = response.items.iter().find(|i| i.slug == "Q00001").unwrap();
+        assert!(merged.deprecated);
+        assert_eq!(merged.deprecated_reason.as_deref(), Some("Merged into P01308"));
+        assert_eq!(merged.superseded_by.as_deref(), Some("uniprot:P01308"));
+        Ok(())
+    }
+
     #[sqlx::test]
     async fn test_handle_unified_search(pool: PgPool) -> sqlx::Result<()> {
         sqlx::query!(
@@ -0,0 +1,64 @@
+//! Bundle build worker
+//!
+//! Processes the [`BuildBundleJob`]s queued by the build bundle command from
+//! the apalis queue. Bundles are built on request, so the worker runs whether
+//! or not ingestion is enabled.
+
+use anyhow::Result;
+use apalis::prelude::*;
+use apalis_postgres::PostgresStorage;
+use sqlx::PgPool;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use super::commands::build_bundle::{self, BuildBundleJob};
+use crate::storage::Storage;
+
+/// State shared by bundle jobs
+#[derive(Clone)]
+struct BundleWorkerContext {
+    db: PgPool,
+    storage: Storage,
+}
+
+/// Start the worker that builds queued bundles
+pub fn start(db: PgPool, storage: Storage) -> JoinHandle<()> {
+    let backend = PostgresStorage::<BuildBundleJob>::new(&db);
+    let ctx = BundleWorkerContext { db, storage };
+
+    tokio::spawn(async move {
+        info!("Bundle worker started");
+        if let Err(e) = Monitor::new()
+            .register(move |_index| {
+                WorkerBuilder::new("bdp-bundle-worker")
+                    .backend(backend.clone())
+                    .data(ctx.clone())
+                    .build(process_bundle_job)
+            })
+            .run()
+            .await
+        {
+            error!("Bundle worker error: {:?}", e);
+        }
+        info!("Bundle worker stopped");
+    })
+}
+
+/// Build a queued bundle
+///
+/// The outcome is recorded in `bundle_builds` before the job finishes.
+async fn process_bundle_job(job: BuildBundleJob, ctx: Data<BundleWorkerContext>) -> Result<()> {
+    if let Err(e) = build_bundle::run_job(&ctx.db, &ctx.storage, &job).await {
+        warn!(
+            build_id = %job.build_id,
+            version_id = %job.version_id,
+            format = %job.command.format,
+            "Bundle build failed: {}",
+            e
+        );
+        return Err(e.into());
+    }
+
+    info!(build_id = %job.build_id, "Bundle build completed");
+    Ok(())
+}
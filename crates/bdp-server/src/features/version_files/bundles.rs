@@ -0,0 +1,310 @@
+//! Aggregate bundle assembly
+//!
+//! A bundle concatenates the files of every member of an aggregate version
+//! into one artifact, so a client pulls one object instead of thousands:
+//!
+//! - `fasta` members become a multi-FASTA file
+//! - `json` members become JSON Lines, one compacted document per line
+//! - any other format (`dat`) becomes a tar archive with one entry per member
+//!
+//! The member index records the byte range and SHA-256 of each member inside
+//! the bundle. FASTA and tar members are stored verbatim, so their digest
+//! matches the member file; JSON members are compacted onto a single line
+//! unless they already fit on one.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::Write;
+
+const TAR_BLOCK: usize = 512;
+
+/// Layout of a bundle, chosen by the member format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BundleFormat {
+    MultiFasta,
+    Jsonl,
+    Tar,
+}
+
+impl BundleFormat {
+    pub fn for_member_format(format: &str) -> Self {
+        match format {
+            "fasta" => Self::MultiFasta,
+            "json" => Self::Jsonl,
+            _ => Self::Tar,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MultiFasta => "multifasta",
+            Self::Jsonl => "jsonl",
+            Self::Tar => "tar",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::MultiFasta => "fasta",
+            Self::Jsonl => "jsonl",
+            Self::Tar => "tar",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::MultiFasta => "text/plain",
+            Self::Jsonl => "application/jsonl",
+            Self::Tar => "application/x-tar",
+        }
+    }
+}
+
+/// Storage filename of the bundle of a version in a member format
+pub fn bundle_filename(slug: &str, version: &str, format: &str) -> String {
+    let bundle_format = BundleFormat::for_member_format(format);
+    format!("{}_{}.{}-bundle.{}", slug, version, format, bundle_format.extension())
+}
+
+/// Where a member's bytes sit inside a bundle
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleMember {
+    pub source: String,
+    pub offset: u64,
+    pub size: u64,
+    pub sha256: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BundleError {
+    #[error("Member '{0}' is not a valid JSON document: {1}")]
+    InvalidJson(String, String),
+    #[error("Member '{0}' cannot be stored in a tar archive: {1}")]
+    InvalidTarEntry(String, String),
+    #[error("Failed to write bundle: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// An assembled bundle, without its content
+#[derive(Debug, Clone)]
+pub struct Bundle {
+    pub format: BundleFormat,
+    pub size: u64,
+    pub sha256: String,
+    pub members: Vec<BundleMember>,
+}
+
+/// Appends member files to a bundle written to `out`, tracking the member
+/// index and the bundle digest as it goes
+///
+/// Only the member being appended is held in memory, so a bundle of any size
+/// can be spooled to a file.
+#[derive(Debug)]
+pub struct BundleWriter<W> {
+    format: BundleFormat,
+    out: W,
+    size: u64,
+    hasher: Sha256,
+    members: Vec<BundleMember>,
+}
+
+impl<W: Write> BundleWriter<W> {
+    pub fn new(format: BundleFormat, out: W) -> Self {
+        Self {
+            format,
+            out,
+            size: 0,
+            hasher: Sha256::new(),
+            members: Vec::new(),
+        }
+    }
+
+    /// Append a member file
+    ///
+    /// `source` is the member's `org:slug@version` spec and `format` its file
+    /// format, which names the tar entry.
+    pub fn append(
+        &mut self,
+        source: &str,
+        format: &str,
+        content: &[u8],
+    ) -> Result<(), BundleError> {
+        match self.format {
+            BundleFormat::MultiFasta => {
+                self.push_member(source, content)?;
+                if !content.ends_with(b"\n") {
+                    self.write(b"\n")?;
+                }
+            },
+            BundleFormat::Jsonl => {
+                let line = jsonl_line(source, content)?;
+                self.push_member(source, &line)?;
+                self.write(b"\n")?;
+            },
+            BundleFormat::Tar => {
+                let header = tar_header(source, format, content.len() as u64)?;
+                self.write(header.as_bytes())?;
+                self.push_member(source, content)?;
+                let padding = (TAR_BLOCK - content.len() % TAR_BLOCK) % TAR_BLOCK;
+                self.write(&[0; TAR_BLOCK][..padding])?;
+            },
+        }
+        Ok(())
+    }
+
+    /// Complete the bundle, returning the writer it was written to
+    pub fn finish(mut self) -> Result<(W, Bundle), BundleError> {
+        if self.format == BundleFormat::Tar {
+            // End-of-archive marker: two zero blocks
+            self.write(&[0; 2 * TAR_BLOCK])?;
+        }
+        self.out.flush()?;
+
+        let bundle = Bundle {
+            format: self.format,
+            size: self.size,
+            sha256: format!("{:x}", self.hasher.finalize()),
+            members: self.members,
+        };
+        Ok((self.out, bundle))
+    }
+
+    fn push_member(&mut self, source: &str, content: &[u8]) -> Result<(), BundleError> {
+        self.members.push(BundleMember {
+            source: source.to_string(),
+            offset: self.size,
+            size: content.len() as u64,
+            sha256: format!("{:x}", Sha256::digest(content)),
+        });
+        self.write(content)
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> Result<(), BundleError> {
+        self.out.write_all(bytes)?;
+        self.hasher.update(bytes);
+        self.size += bytes.len() as u64;
+        Ok(())
+    }
+}
+
+/// A JSON member as a single line, without its trailing newline
+fn jsonl_line(source: &str, content: &[u8]) -> Result<Vec<u8>, BundleError> {
+    let value: serde_json::Value = serde_json::from_slice(content)
+        .map_err(|e| BundleError::InvalidJson(source.to_string(), e.to_string()))?;
+
+    let trimmed = content.strip_suffix(b"\n").unwrap_or(content);
+    if !trimmed.contains(&b'\n') {
+        return Ok(trimmed.to_vec());
+    }
+    serde_json::to_vec(&value)
+        .map_err(|e| BundleError::InvalidJson(source.to_string(), e.to_string()))
+}
+
+/// Tar entries are named `{org}/{slug}_{version}.{format}`
+fn tar_header(source: &str, format: &str, size: u64) -> Result<tar::Header, BundleError> {
+    let (org, rest) = source.split_once(':').unwrap_or(("", source));
+    let (slug, version) = rest.split_once('@').unwrap_or((rest, ""));
+    let path = if org.is_empty() {
+        format!("{}_{}.{}", slug, version, format)
+    } else {
+        format!("{}/{}_{}.{}", org, slug, version, format)
+    };
+
+    let mut header = tar::Header::new_ustar();
+    header
+        .set_path(&path)
+        .map_err(|e| BundleError::InvalidTarEntry(source.to_string(), e.to_string()))?;
+    header.set_size(size);
+    header.set_mode(0o644);
+    header.set_entry_type(tar::EntryType::Regular);
+    header.set_cksum();
+    Ok(header)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slice<'a>(data: &'a [u8], member: &BundleMember) -> &'a [u8] {
+        &data[member.offset as usize..(member.offset + member.size) as usize]
+    }
+
+    #[test]
+    fn test_bundle_format_for_member_format() {
+        assert_eq!(BundleFormat::for_member_format("fasta"), BundleFormat::MultiFasta);
+        assert_eq!(BundleFormat::for_member_format("json"), BundleFormat::Jsonl);
+        assert_eq!(BundleFormat::for_member_format("dat"), BundleFormat::Tar);
+        assert_eq!(bundle_filename("proteome", "1.0", "dat"), "proteome_1.0.dat-bundle.tar");
+    }
+
+    #[test]
+    fn test_multi_fasta_bundle() {
+        let mut writer = BundleWriter::new(BundleFormat::MultiFasta, Vec::new());
+        writer
+            .append("uniprot:P01308@1.0", "fasta", b">P01308\nMALWMR\n")
+            .unwrap();
+        writer
+            .append("uniprot:P01315@1.0", "fasta", b">P01315\nMALWIR")
+            .unwrap();
+        let (data, bundle) = writer.finish().unwrap();
+
+        assert_eq!(data, b">P01308\nMALWMR\n>P01315\nMALWIR\n");
+        assert_eq!(slice(&data, &bundle.members[1]), b">P01315\nMALWIR");
+        assert_eq!(bundle.members[0].sha256, format!("{:x}", Sha256::digest(b">P01308\nMALWMR\n")));
+        assert_eq!(bundle.size, data.len() as u64);
+        assert_eq!(bundle.sha256, format!("{:x}", Sha256::digest(&data)));
+    }
+
+    #[test]
+    fn test_jsonl_bundle_compacts_multiline_members() {
+        let mut writer = BundleWriter::new(BundleFormat::Jsonl, Vec::new());
+        writer
+            .append("uniprot:P01308@1.0", "json", b"{\n  \"id\": \"P01308\"\n}\n")
+            .unwrap();
+        writer
+            .append("uniprot:P01315@1.0", "json", b"{\"id\": \"P01315\"}\n")
+            .unwrap();
+        let (data, bundle) = writer.finish().unwrap();
+
+        assert_eq!(data, b"{\"id\":\"P01308\"}\n{\"id\": \"P01315\"}\n");
+        assert_eq!(slice(&data, &bundle.members[1]), b"{\"id\": \"P01315\"}");
+
+        let mut writer = BundleWriter::new(BundleFormat::Jsonl, Vec::new());
+        let err = writer.append("uniprot:P01308@1.0", "json", b"not json");
+        assert!(matches!(err, Err(BundleError::InvalidJson(..))));
+    }
+
+    #[test]
+    fn test_tar_bundle_is_a_readable_archive() {
+        let mut writer = BundleWriter::new(BundleFormat::Tar, Vec::new());
+        writer
+            .append("uniprot:P01308@1.0", "dat", b"ID   INS_HUMAN\n//\n")
+            .unwrap();
+        writer
+            .append("uniprot:P01315@1.0", "dat", b"ID   INS_PIG\n//\n")
+            .unwrap();
+        let (data, bundle) = writer.finish().unwrap();
+
+        assert_eq!(data.len() % TAR_BLOCK, 0);
+        assert_eq!(slice(&data, &bundle.members[1]), b"ID   INS_PIG\n//\n");
+
+        let mut archive = tar::Archive::new(data.as_slice());
+        let entries: Vec<(String, u64)> = archive
+            .entries()
+            .unwrap()
+            .map(|entry| {
+                let entry = entry.unwrap();
+                let path = entry.path().unwrap().display().to_string();
+                (path, entry.raw_file_position())
+            })
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                ("uniprot/P01308_1.0.dat".to_string(), bundle.members[0].offset),
+                ("uniprot/P01315_1.0.dat".to_string(), bundle.members[1].offset),
+            ]
+        );
+    }
+}
@@ -0,0 +1,106 @@
+//! SHA256SUMS manifests
+//!
+//! Each version gets a manifest in the format produced by `sha256sum`
+//! (`<sha256>  <filename>` per line, sorted by filename) so clients and mirrors
+//! can verify every file of a version with a single request. The manifest is
+//! stored on the version when its files are published; versions written
+//! directly by ingestion pipelines get one generated on demand.
+
+use sqlx::{PgExecutor, Postgres, Transaction};
+use uuid::Uuid;
+
+/// Filename under which the manifest is served
+pub const MANIFEST_FILENAME: &str = "SHA256SUMS";
+
+/// Whether `s` is a hex-encoded SHA-256 digest
+pub fn is_sha256(s: &str) -> bool {
+    s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Whether `s` is a hex-encoded MD5 digest
+pub fn is_md5(s: &str) -> bool {
+    s.len() == 32 && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Render manifest lines for `(filename, sha256)` pairs
+pub fn format_manifest(entries: impl IntoIterator<Item = (String, String)>) -> String {
+    let mut entries: Vec<_> = entries.into_iter().collect();
+    entries.sort();
+
+    entries
+        .into_iter()
+        .map(|(filename, sha256)| format!("{}  {}\n", sha256, filename))
+        .collect()
+}
+
+/// Filename part of a storage key
+fn file_name(s3_key: &str) -> &str {
+    s3_key.rsplit('/').next().unwrap_or(s3_key)
+}
+
+/// Build the manifest for a version from its registered files
+///
+/// Files without a known SHA-256 are left out.
+pub async fn generate_manifest<'e, E>(executor: E, version_id: Uuid) -> Result<String, sqlx::Error>
+where
+    E: PgExecutor<'e>,
+{
+    let files = sqlx::query!(
+        "SELECT s3_key, sha256 FROM version_files WHERE version_id = $1 AND sha256 IS NOT NULL",
+        version_id
+    )
+    .fetch_all(executor)
+    .await?;
+
+    Ok(format_manifest(files.into_iter().filter_map(|f| {
+        f.sha256
+            .map(|sha256| (file_name(&f.s3_key).to_string(), sha256))
+    })))
+}
+
+/// Regenerate and store the manifest for a version
+pub async fn store_manifest(
+    tx: &mut Transaction<'_, Postgres>,
+    version_id: Uuid,
+) -> Result<(), sqlx::Error> {
+    let manifest = generate_manifest(&mut **tx, version_id).await?;
+
+    sqlx::query!("UPDATE versions SET checksum_manifest = $2 WHERE id = $1", version_id, manifest)
+        .execute(&mut **tx)
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digest_detection() {
+        assert!(is_sha256(&"a".repeat(64)));
+        assert!(!is_sha256(&"g".repeat(64)));
+        assert!(!is_sha256("abc123"));
+        assert!(is_md5("6cd3556deb0da54bca060b4c39479839"));
+        assert!(!is_md5(&"a".repeat(64)));
+    }
+
+    #[test]
+    fn test_format_manifest() {
+        let manifest = format_manifest(vec![
+            ("proteome.json".to_string(), "b".repeat(64)),
+            ("proteome.fasta".to_string(), "a".repeat(64)),
+        ]);
+        assert_eq!(
+            manifest,
+            format!("{}  proteome.fasta\n{}  proteome.json\n", "a".repeat(64), "b".repeat(64))
+        );
+        assert_eq!(format_manifest(Vec::new()), "");
+    }
+
+    #[test]
+    fn test_file_name() {
+        assert_eq!(file_name("data-sources/my-lab/proteome/1.0/proteome.fasta"), "proteome.fasta");
+        assert_eq!(file_name("proteome.fasta"), "proteome.fasta");
+    }
+}
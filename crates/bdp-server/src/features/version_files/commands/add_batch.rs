@@ -1,8 +1,17 @@
+//! Register files for a version
+//!
+//! The first registration seals the version. Afterwards identical
+//! re-registrations are accepted as no-ops, while anything that would change
+//! or add a file is refused and audited unless an administrator overrides
+//! the seal.
+
 use mediator::Request;
 use serde::{Deserialize, Serialize};
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, Transaction};
 use uuid::Uuid;
 
+use crate::features::version_files::{checksums, sealing};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VersionFileInput {
     pub format: String,
@@ -11,12 +20,18 @@ pub struct VersionFileInput {
     pub size_bytes: i64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub compression: Option<String>,
+    /// MD5 published upstream, where known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub md5: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AddVersionFilesCommand {
     pub version_id: Uuid,
     pub files: Vec<VersionFileInput>,
+    /// Admin-provided reason for writing to a sealed version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub override_reason: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,7 +46,7 @@ pub enum AddVersionFilesError {
     FilesRequired,
     #[error("Format is required and cannot be empty")]
     FormatRequired,
-    #[error("Invalid format: {0}. Must be one of: fasta, json, xml, dat, tar.gz")]
+    #[error("Invalid format: {0}. Must be one of: fasta, json, xml, dat, tar.gz, tsv, csv, gff3, gtf, bed, obo")]
     InvalidFormat(String),
     #[error("S3 key is required and cannot be empty")]
     S3KeyRequired,
@@ -45,8 +60,14 @@ pub enum AddVersionFilesError {
     InvalidSize,
     #[error("Invalid compression format: {0}. Must be one of: gzip, bzip2, none")]
     InvalidCompression(String),
+    #[error("Invalid MD5 digest: {0}. Must be 32 hex characters")]
+    InvalidMd5(String),
+    #[error("Format '{0}' is listed more than once")]
+    DuplicateFormat(String),
     #[error("Version with ID '{0}' not found")]
     VersionNotFound(Uuid),
+    #[error("Version '{0}' is sealed; its {1} file(s) cannot be changed")]
+    VersionSealed(Uuid, String),
     #[error("Database error: {0}")]
     Database(#[from] sqlx::Error),
 }
@@ -55,7 +76,9 @@ impl Request<Result<AddVersionFilesResponse, AddVersionFilesError>> for AddVersi
 
 impl crate::cqrs::middleware::Command for AddVersionFilesCommand {}
 
-const VALID_FORMATS: &[&str] = &["fasta", "json", "xml", "dat", "tar.gz"];
+const VALID_FORMATS: &[&str] = &[
+    "fasta", "json", "xml", "dat", "tar.gz", "tsv", "csv", "gff3", "gtf", "bed", "obo",
+];
 const VALID_COMPRESSIONS: &[&str] = &["gzip", "bzip2", "none"];
 
 impl VersionFileInput {
@@ -86,8 +109,18 @@ impl VersionFileInput {
                 return Err(AddVersionFilesError::InvalidCompression(compression.clone()));
             }
         }
+        if let Some(ref md5) = self.md5 {
+            if !checksums::is_md5(md5) {
+                return Err(AddVersionFilesError::InvalidMd5(md5.clone()));
+            }
+        }
         Ok(())
     }
+
+    /// The checksum as a SHA-256 digest, if it is one
+    pub fn sha256(&self) -> Option<String> {
+        checksums::is_sha256(&self.checksum).then(|| self.checksum.to_lowercase())
+    }
 }
 
 impl AddVersionFilesCommand {
@@ -95,11 +128,19 @@ impl AddVersionFilesCommand {
         if self.files.is_empty() {
             return Err(AddVersionFilesError::FilesRequired);
         }
-        for file in &self.files {
-            file.validate()?;
+        validate_files(&self.files)
+    }
+}
+
+/// Validate each file and reject formats listed more than once
+pub(crate) fn validate_files(files: &[VersionFileInput]) -> Result<(), AddVersionFilesError> {
+    for (i, file) in files.iter().enumerate() {
+        file.validate()?;
+        if files[..i].iter().any(|f| f.format == file.format) {
+            return Err(AddVersionFilesError::DuplicateFormat(file.format.clone()));
         }
-        Ok(())
     }
+    Ok(())
 }
 
 #[tracing::instrument(skip(pool))]
@@ -109,50 +150,126 @@ pub async fn handle(
 ) -> Result<AddVersionFilesResponse, AddVersionFilesError> {
     command.validate()?;
 
-    // Check if version exists
-    let version_exists: bool = sqlx::query_scalar!(
-        "SELECT EXISTS(SELECT 1 FROM versions WHERE id = $1)",
-        command.version_id
-    )
-    .fetch_one(&pool)
-    .await?
-    .unwrap_or(false);
-
-    if !version_exists {
-        return Err(AddVersionFilesError::VersionNotFound(command.version_id));
+    let sealed_at =
+        sqlx::query_scalar!("SELECT sealed_at FROM versions WHERE id = $1", command.version_id)
+            .fetch_optional(&pool)
+            .await?
+            .ok_or(AddVersionFilesError::VersionNotFound(command.version_id))?;
+
+    let mut files = command.files;
+    let mut override_audit = None;
+
+    if sealed_at.is_some() {
+        let existing = sqlx::query!(
+            "SELECT format, s3_key, checksum, size_bytes, md5 FROM version_files WHERE version_id = $1",
+            command.version_id
+        )
+        .fetch_all(&pool)
+        .await?;
+
+        // Re-registering exactly what is already there is harmless
+        files.retain(|file| {
+            !existing.iter().any(|e| {
+                e.format == file.format
+                    && e.s3_key == file.s3_key
+                    && e.checksum == file.checksum
+                    && e.size_bytes == file.size_bytes
+                    && (file.md5.is_none() || e.md5 == file.md5.as_ref().map(|m| m.to_lowercase()))
+            })
+        });
+
+        if !files.is_empty() {
+            let details = serde_json::json!({
+                "attempted": files,
+                "existing": existing
+                    .iter()
+                    .map(|e| serde_json::json!({
+                        "format": e.format,
+                        "s3_key": e.s3_key,
+                        "checksum": e.checksum,
+                        "size_bytes": e.size_bytes,
+                        "md5": e.md5,
+                    }))
+                    .collect::<Vec<_>>(),
+            });
+
+            match command.override_reason {
+                Some(reason) => override_audit = Some((reason, details)),
+                None => {
+                    sealing::record_rejected_write(&pool, command.version_id, details).await;
+                    let formats = files
+                        .iter()
+                        .map(|f| f.format.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    return Err(AddVersionFilesError::VersionSealed(command.version_id, formats));
+                },
+            }
+        }
+    }
+
+    let mut tx = pool.begin().await?;
+    if override_audit.is_some() {
+        sealing::allow_sealed_overwrite(&mut tx).await?;
+    }
+
+    insert_files(&mut tx, command.version_id, &files).await?;
+
+    tx.commit().await?;
+
+    if let Some((reason, details)) = override_audit {
+        sealing::record_override(&pool, command.version_id, &reason, details).await;
     }
 
-    let mut files_added = 0;
+    let files_added = files.len();
 
-    for file in command.files {
+    Ok(AddVersionFilesResponse {
+        version_id: command.version_id,
+        files_added,
+    })
+}
+
+/// Insert or replace files of a version and refresh its checksum manifest
+///
+/// Inserting the first file seals the version.
+pub(crate) async fn insert_files(
+    tx: &mut Transaction<'_, Postgres>,
+    version_id: Uuid,
+    files: &[VersionFileInput],
+) -> Result<(), sqlx::Error> {
+    for file in files {
         sqlx::query!(
             r#"
-            INSERT INTO version_files (version_id, format, s3_key, checksum, size_bytes, compression)
-            VALUES ($1, $2, $3, $4, $5, $6)
+            INSERT INTO version_files
+                (version_id, format, s3_key, checksum, size_bytes, compression, sha256, md5)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
             ON CONFLICT (version_id, format)
             DO UPDATE SET
                 s3_key = EXCLUDED.s3_key,
                 checksum = EXCLUDED.checksum,
                 size_bytes = EXCLUDED.size_bytes,
-                compression = EXCLUDED.compression
+                compression = EXCLUDED.compression,
+                sha256 = EXCLUDED.sha256,
+                md5 = EXCLUDED.md5
             "#,
-            command.version_id,
+            version_id,
             file.format,
             file.s3_key,
             file.checksum,
             file.size_bytes,
-            file.compression
+            file.compression,
+            file.sha256(),
+            file.md5.as_ref().map(|m| m.to_lowercase())
         )
-        .execute(&pool)
+        .execute(&mut **tx)
         .await?;
+    }
 
-        files_added += 1;
+    if !files.is_empty() {
+        checksums::store_manifest(tx, version_id).await?;
     }
 
-    Ok(AddVersionFilesResponse {
-        version_id: command.version_id,
-        files_added,
-    })
+    Ok(())
 }
 
 #[cfg(test)]
@@ -167,6 +284,7 @@ mod tests {
             checksum: "abc123".to_string(),
             size_bytes: 1024,
             compression: Some("gzip".to_string()),
+            md5: None,
         };
         assert!(file.validate().is_ok());
     }
@@ -179,6 +297,7 @@ mod tests {
             checksum: "a".to_string(),
             size_bytes: 1,
             compression: None,
+            md5: None,
         };
         assert!(file.validate().is_ok());
     }
@@ -192,6 +311,7 @@ mod tests {
                 checksum: "abc123".to_string(),
                 size_bytes: 100,
                 compression: None,
+                md5: None,
             };
             assert!(file.validate().is_ok(), "Format {} should be valid", format);
         }
@@ -205,6 +325,7 @@ mod tests {
             checksum: "abc123".to_string(),
             size_bytes: 100,
             compression: None,
+            md5: None,
         };
         assert!(matches!(file.validate(), Err(AddVersionFilesError::FormatRequired)));
     }
@@ -217,6 +338,7 @@ mod tests {
             checksum: "abc123".to_string(),
             size_bytes: 100,
             compression: None,
+            md5: None,
         };
         assert!(matches!(file.validate(), Err(AddVersionFilesError::InvalidFormat(_))));
     }
@@ -229,6 +351,7 @@ mod tests {
             checksum: "abc123".to_string(),
             size_bytes: 100,
             compression: None,
+            md5: None,
         };
         assert!(matches!(file.validate(), Err(AddVersionFilesError::S3KeyRequired)));
     }
@@ -241,6 +364,7 @@ mod tests {
             checksum: "abc123".to_string(),
             size_bytes: 100,
             compression: None,
+            md5: None,
         };
         assert!(matches!(file.validate(), Err(AddVersionFilesError::S3KeyTooLong)));
     }
@@ -253,6 +377,7 @@ mod tests {
             checksum: "".to_string(),
             size_bytes: 100,
             compression: None,
+            md5: None,
         };
         assert!(matches!(file.validate(), Err(AddVersionFilesError::ChecksumRequired)));
     }
@@ -265,6 +390,7 @@ mod tests {
             checksum: "a".repeat(65),
             size_bytes: 100,
             compression: None,
+            md5: None,
         };
         assert!(matches!(file.validate(), Err(AddVersionFilesError::ChecksumLength)));
     }
@@ -277,6 +403,7 @@ mod tests {
             checksum: "abc123".to_string(),
             size_bytes: 0,
             compression: None,
+            md5: None,
         };
         assert!(matches!(file.validate(), Err(AddVersionFilesError::InvalidSize)));
     }
@@ -289,6 +416,7 @@ mod tests {
             checksum: "abc123".to_string(),
             size_bytes: -1,
             compression: None,
+            md5: None,
         };
         assert!(matches!(file.validate(), Err(AddVersionFilesError::InvalidSize)));
     }
@@ -301,6 +429,7 @@ mod tests {
             checksum: "abc123".to_string(),
             size_bytes: 100,
             compression: Some("invalid".to_string()),
+            md5: None,
         };
         assert!(matches!(file.validate(), Err(AddVersionFilesError::InvalidCompression(_))));
     }
@@ -314,6 +443,7 @@ mod tests {
                 checksum: "abc123".to_string(),
                 size_bytes: 100,
                 compression: Some(compression.to_string()),
+                md5: None,
             };
             assert!(file.validate().is_ok(), "Compression {} should be valid", compression);
         }
@@ -324,6 +454,7 @@ mod tests {
         let cmd = AddVersionFilesCommand {
             version_id: Uuid::new_v4(),
             files: vec![],
+            override_reason: None,
         };
         assert!(matches!(cmd.validate(), Err(AddVersionFilesError::FilesRequired)));
     }
@@ -338,11 +469,48 @@ mod tests {
                 checksum: "abc123".to_string(),
                 size_bytes: 100,
                 compression: None,
+                md5: None,
             }],
+            override_reason: None,
         };
         assert!(cmd.validate().is_ok());
     }
 
+    #[test]
+    fn test_version_file_input_digests() {
+        let mut file = VersionFileInput {
+            format: "fasta".to_string(),
+            s3_key: "test.fasta".to_string(),
+            checksum: "abc123".to_string(),
+            size_bytes: 100,
+            compression: None,
+            md5: Some("not-an-md5".to_string()),
+        };
+        assert!(matches!(file.validate(), Err(AddVersionFilesError::InvalidMd5(_))));
+        assert_eq!(file.sha256(), None);
+
+        file.checksum = "F".repeat(64);
+        assert_eq!(file.sha256(), Some("f".repeat(64)));
+    }
+
+    #[test]
+    fn test_command_validation_duplicate_format() {
+        let file = VersionFileInput {
+            format: "fasta".to_string(),
+            s3_key: "test.fasta".to_string(),
+            checksum: "abc123".to_string(),
+            size_bytes: 100,
+            compression: None,
+            md5: None,
+        };
+        let cmd = AddVersionFilesCommand {
+            version_id: Uuid::new_v4(),
+            files: vec![file.clone(), file],
+            override_reason: None,
+        };
+        assert!(matches!(cmd.validate(), Err(AddVersionFilesError::DuplicateFormat(_))));
+    }
+
     #[sqlx::test]
     async fn test_handle_adds_version_files(pool: PgPool) -> sqlx::Result<()> {
         // Setup: Create organization, registry entry, data source, and version
@@ -400,7 +568,9 @@ mod tests {
                 checksum: "abc123".to_string(),
                 size_bytes: 1024,
                 compression: Some("gzip".to_string()),
+                md5: None,
             }],
+            override_reason: None,
         };
 
         let result = handle(pool.clone(), cmd).await;
@@ -468,6 +638,7 @@ mod tests {
                     checksum: "abc123".to_string(),
                     size_bytes: 1024,
                     compression: Some("gzip".to_string()),
+                    md5: None,
                 },
                 VersionFileInput {
                     format: "json".to_string(),
@@ -475,8 +646,10 @@ mod tests {
                     checksum: "def456".to_string(),
                     size_bytes: 2048,
                     compression: None,
+                    md5: None,
                 },
             ],
+            override_reason: None,
         };
 
         let result = handle(pool.clone(), cmd).await;
@@ -487,7 +660,7 @@ mod tests {
     }
 
     #[sqlx::test]
-    async fn test_handle_upsert_updates_existing_file(pool: PgPool) -> sqlx::Result<()> {
+    async fn test_handle_stores_digests_and_manifest(pool: PgPool) -> sqlx::Result<()> {
         let org_id = Uuid::new_v4();
         sqlx::query!(
             "INSERT INTO organizations (id, slug, name, is_system) VALUES ($1, $2, $3, $4)",
@@ -534,45 +707,154 @@ mod tests {
         .execute(&pool)
         .await?;
 
-        // First insert
-        let cmd1 = AddVersionFilesCommand {
+        let sha = "A".repeat(64);
+        let cmd = AddVersionFilesCommand {
             version_id,
             files: vec![VersionFileInput {
                 format: "fasta".to_string(),
-                s3_key: "old.fasta".to_string(),
-                checksum: "old123".to_string(),
-                size_bytes: 500,
+                s3_key: "data-sources/test-org/test-ds/1.0/test.fasta".to_string(),
+                checksum: sha.clone(),
+                size_bytes: 100,
                 compression: None,
+                md5: Some("6CD3556DEB0DA54BCA060B4C39479839".to_string()),
             }],
+            override_reason: None,
         };
-        handle(pool.clone(), cmd1).await.unwrap();
+        handle(pool.clone(), cmd).await.unwrap();
+
+        let file =
+            sqlx::query!("SELECT sha256, md5 FROM version_files WHERE version_id = $1", version_id)
+                .fetch_one(&pool)
+                .await?;
+        assert_eq!(file.sha256, Some(sha.to_lowercase()));
+        assert_eq!(file.md5.as_deref(), Some("6cd3556deb0da54bca060b4c39479839"));
+
+        let manifest =
+            sqlx::query_scalar!("SELECT checksum_manifest FROM versions WHERE id = $1", version_id)
+                .fetch_one(&pool)
+                .await?;
+        assert_eq!(manifest, Some(format!("{}  test.fasta\n", sha.to_lowercase())));
+        Ok(())
+    }
 
-        // Update with same format
-        let cmd2 = AddVersionFilesCommand {
+    #[sqlx::test]
+    async fn test_handle_sealed_version(pool: PgPool) -> sqlx::Result<()> {
+        let org_id = Uuid::new_v4();
+        sqlx::query!(
+            "INSERT INTO organizations (id, slug, name, is_system) VALUES ($1, $2, $3, $4)",
+            org_id,
+            "test-org",
+            "Test Org",
+            false
+        )
+        .execute(&pool)
+        .await?;
+
+        let entry_id = Uuid::new_v4();
+        sqlx::query!(
+            r#"
+            INSERT INTO registry_entries (id, organization_id, slug, name, entry_type)
+            VALUES ($1, $2, $3, $4, 'data_source')
+            "#,
+            entry_id,
+            org_id,
+            "test-ds",
+            "Test DS"
+        )
+        .execute(&pool)
+        .await?;
+
+        sqlx::query!(
+            "INSERT INTO data_sources (id, source_type) VALUES ($1, $2)",
+            entry_id,
+            "protein"
+        )
+        .execute(&pool)
+        .await?;
+
+        let version_id = Uuid::new_v4();
+        sqlx::query!(
+            r#"
+            INSERT INTO versions (id, entry_id, version)
+            VALUES ($1, $2, $3)
+            "#,
             version_id,
-            files: vec![VersionFileInput {
-                format: "fasta".to_string(),
-                s3_key: "new.fasta".to_string(),
-                checksum: "new456".to_string(),
-                size_bytes: 1000,
-                compression: Some("gzip".to_string()),
-            }],
+            entry_id,
+            "1.0"
+        )
+        .execute(&pool)
+        .await?;
+
+        let file = |s3_key: &str, checksum: &str| VersionFileInput {
+            format: "fasta".to_string(),
+            s3_key: s3_key.to_string(),
+            checksum: checksum.to_string(),
+            size_bytes: 500,
+            compression: None,
+            md5: None,
         };
-        let result = handle(pool.clone(), cmd2).await;
-        assert!(result.is_ok());
+        let cmd =
+            |files: Vec<VersionFileInput>, override_reason: Option<&str>| AddVersionFilesCommand {
+                version_id,
+                files,
+                override_reason: override_reason.map(str::to_string),
+            };
 
-        // Verify update
-        let file = sqlx::query!(
-            "SELECT s3_key, checksum, size_bytes FROM version_files WHERE version_id = $1 AND format = $2",
-            version_id,
-            "fasta"
+        // First registration seals the version
+        handle(pool.clone(), cmd(vec![file("v1.fasta", "old123")], None))
+            .await
+            .unwrap();
+        let sealed_at =
+            sqlx::query_scalar!("SELECT sealed_at FROM versions WHERE id = $1", version_id)
+                .fetch_one(&pool)
+                .await?;
+        assert!(sealed_at.is_some());
+
+        // Identical re-registration is a no-op
+        let response = handle(pool.clone(), cmd(vec![file("v1.fasta", "old123")], None))
+            .await
+            .unwrap();
+        assert_eq!(response.files_added, 0);
+
+        // Changing the checksum is refused and audited
+        let result = handle(pool.clone(), cmd(vec![file("v1.fasta", "new456")], None)).await;
+        assert!(matches!(result, Err(AddVersionFilesError::VersionSealed(..))));
+        let checksum = sqlx::query_scalar!(
+            "SELECT checksum FROM version_files WHERE version_id = $1 AND format = 'fasta'",
+            version_id
         )
         .fetch_one(&pool)
         .await?;
+        assert_eq!(checksum, "old123");
 
-        assert_eq!(file.s3_key, "new.fasta");
-        assert_eq!(file.checksum, "new456");
-        assert_eq!(file.size_bytes, 1000);
+        // The database refuses direct updates too
+        let direct = sqlx::query!(
+            "UPDATE version_files SET checksum = 'tampered' WHERE version_id = $1",
+            version_id
+        )
+        .execute(&pool)
+        .await;
+        assert!(direct.is_err());
+
+        // An admin override goes through and is audited
+        handle(pool.clone(), cmd(vec![file("v1.fasta", "new456")], Some("truncated upload")))
+            .await
+            .unwrap();
+        let checksum = sqlx::query_scalar!(
+            "SELECT checksum FROM version_files WHERE version_id = $1 AND format = 'fasta'",
+            version_id
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(checksum, "new456");
+
+        let actions = sqlx::query_scalar!(
+            "SELECT action FROM audit_log WHERE resource_id = $1 ORDER BY timestamp",
+            version_id
+        )
+        .fetch_all(&pool)
+        .await?;
+        assert_eq!(actions, vec!["overwrite_rejected", "seal_override"]);
         Ok(())
     }
 
@@ -586,7 +868,9 @@ mod tests {
                 checksum: "abc123".to_string(),
                 size_bytes: 100,
                 compression: None,
+                md5: None,
             }],
+            override_reason: None,
         };
 
         let result = handle(pool.clone(), cmd).await;
@@ -0,0 +1,607 @@
+//! Build bundle command
+//!
+//! Assembles the bundle of an aggregate version in one member format from
+//! the files of its transitive dependency closure and records it with its
+//! member index. The command checks that every member has a file in the
+//! format, records a pending build in `bundle_builds` and queues a
+//! [`BuildBundleJob`]. The bundle worker then spools the bundle to a
+//! temporary file one member at a time and streams it to storage next to
+//! the version's own files.
+//!
+//! Lockfiles pin bundle checksums like those of any other file, so the
+//! bundle of a sealed version is never rebuilt; refusals are audited.
+//! Bundles of versions that are still being assembled can be rebuilt,
+//! replacing the previous one.
+
+use anyhow::Context;
+use apalis::prelude::TaskSink;
+use apalis_postgres::PostgresStorage;
+use aws_sdk_s3::primitives::ByteStream;
+use mediator::Request;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::io::BufWriter;
+use std::path::Path;
+use uuid::Uuid;
+
+use crate::features::shared::error_helpers::is_unique_violation;
+use crate::features::version_files::bundles::{
+    bundle_filename, Bundle, BundleError, BundleFormat, BundleMember, BundleWriter,
+};
+use crate::features::version_files::sealing;
+use crate::storage::Storage;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildBundleCommand {
+    pub organization_slug: String,
+    pub data_source_slug: String,
+    pub version: String,
+    pub format: String,
+}
+
+/// A bundle build that has been queued
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedBundle {
+    pub build_id: Uuid,
+    pub format: String,
+    pub bundle_format: BundleFormat,
+    pub member_count: usize,
+}
+
+/// Job that builds one queued bundle
+///
+/// Enqueued by [`handle`] and processed by the bundle worker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildBundleJob {
+    /// The `bundle_builds` row tracking this build
+    pub build_id: Uuid,
+    pub version_id: Uuid,
+    pub command: BuildBundleCommand,
+}
+
+/// A stored bundle and its member index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionBundle {
+    pub format: String,
+    pub bundle_format: BundleFormat,
+    #[serde(skip)]
+    pub s3_key: String,
+    pub filename: String,
+    pub sha256: String,
+    pub size: i64,
+    pub member_count: i32,
+    pub members: Vec<BundleMember>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BuildBundleError {
+    #[error("Organization slug is required and cannot be empty")]
+    OrganizationSlugRequired,
+    #[error("Data source slug is required and cannot be empty")]
+    DataSourceSlugRequired,
+    #[error("Version is required and cannot be empty")]
+    VersionRequired,
+    #[error("Format is required and cannot be empty")]
+    FormatRequired,
+    #[error("Version '{0}' not found")]
+    VersionNotFound(String),
+    #[error("Version '{0}' has no dependencies to bundle")]
+    NotAggregate(String),
+    #[error("Dependency '{0}' has no '{1}' file to bundle")]
+    MemberFormatMissing(String, String),
+    #[error("Version '{0}' is sealed; its '{1}' bundle cannot be rebuilt")]
+    BundleSealed(String, String),
+    #[error("A '{1}' bundle of '{0}' is already being built")]
+    BuildInProgress(String, String),
+    #[error("Failed to queue bundle build: {0}")]
+    Queue(String),
+    #[error(transparent)]
+    Bundle(#[from] BundleError),
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error("Storage error: {0}")]
+    Storage(#[from] anyhow::Error),
+}
+
+impl Request<Result<QueuedBundle, BuildBundleError>> for BuildBundleCommand {}
+
+impl crate::cqrs::middleware::Command for BuildBundleCommand {}
+
+impl BuildBundleCommand {
+    pub fn validate(&self) -> Result<(), BuildBundleError> {
+        if self.organization_slug.is_empty() {
+            return Err(BuildBundleError::OrganizationSlugRequired);
+        }
+        if self.data_source_slug.is_empty() {
+            return Err(BuildBundleError::DataSourceSlugRequired);
+        }
+        if self.version.is_empty() {
+            return Err(BuildBundleError::VersionRequired);
+        }
+        if self.format.is_empty() {
+            return Err(BuildBundleError::FormatRequired);
+        }
+        Ok(())
+    }
+
+    fn spec(&self) -> String {
+        format!("{}:{}@{}", self.organization_slug, self.data_source_slug, self.version)
+    }
+}
+
+/// A dependency in the closure of an aggregate, with its file in the bundle
+/// format if it has one
+#[derive(Debug, Clone)]
+struct BundleCandidate {
+    source: String,
+    s3_key: Option<String>,
+    is_aggregate: bool,
+}
+
+/// Queue a build of the bundle of a version
+///
+/// # Errors
+///
+/// - `VersionNotFound` - The version doesn't exist
+/// - `BundleSealed` - The version is sealed and the bundle was already built
+/// - `NotAggregate` / `MemberFormatMissing` - There is nothing to bundle
+/// - `BuildInProgress` - A build of the same bundle is pending or running
+/// - `Queue` - The job could not be enqueued
+#[tracing::instrument(skip(pool))]
+pub async fn handle(
+    pool: PgPool,
+    command: BuildBundleCommand,
+) -> Result<QueuedBundle, BuildBundleError> {
+    command.validate()?;
+
+    let version_id = sqlx::query_scalar!(
+        r#"
+        SELECT v.id
+        FROM versions v
+        JOIN registry_entries re ON v.entry_id = re.id
+        JOIN organizations o ON re.organization_id = o.id
+        WHERE LOWER(o.slug) = LOWER($1) AND LOWER(re.slug) = LOWER($2) AND v.version = $3
+        "#,
+        command.organization_slug,
+        command.data_source_slug,
+        command.version
+    )
+    .fetch_optional(&pool)
+    .await?
+    .ok_or_else(|| BuildBundleError::VersionNotFound(command.spec()))?;
+
+    ensure_rebuildable(&pool, version_id, &command).await?;
+
+    let members = fetch_members(&pool, version_id, &command.format).await?;
+    if members.is_empty() {
+        return Err(BuildBundleError::NotAggregate(command.spec()));
+    }
+
+    let member_count = members.len();
+    let build_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO bundle_builds (version_id, format, member_count)
+        VALUES ($1, $2, $3)
+        RETURNING id
+        "#,
+        version_id,
+        command.format,
+        member_count as i32
+    )
+    .fetch_one(&pool)
+    .await
+    .map_err(|e| {
+        if is_unique_violation(&e) {
+            BuildBundleError::BuildInProgress(command.spec(), command.format.clone())
+        } else {
+            BuildBundleError::Database(e)
+        }
+    })?;
+
+    let queued = QueuedBundle {
+        build_id,
+        format: command.format.clone(),
+        bundle_format: BundleFormat::for_member_format(&command.format),
+        member_count,
+    };
+
+    let job = BuildBundleJob {
+        build_id,
+        version_id,
+        command,
+    };
+    if let Err(e) = PostgresStorage::<BuildBundleJob>::new(&pool)
+        .push(job)
+        .await
+    {
+        let error = format!("{:?}", e);
+        record_outcome(&pool, build_id, Some(&error)).await?;
+        return Err(BuildBundleError::Queue(error));
+    }
+
+    Ok(queued)
+}
+
+/// Run a queued bundle build and record its outcome in `bundle_builds`
+pub async fn run_job(
+    pool: &PgPool,
+    storage: &Storage,
+    job: &BuildBundleJob,
+) -> Result<(), BuildBundleError> {
+    sqlx::query!(
+        r#"
+        UPDATE bundle_builds
+        SET status = 'running', started_at = NOW(), error_message = NULL
+        WHERE id = $1
+        "#,
+        job.build_id
+    )
+    .execute(pool)
+    .await?;
+
+    let result = async {
+        // The bundle may have been built and sealed since the job was queued
+        ensure_rebuildable(pool, job.version_id, &job.command).await?;
+
+        let members = fetch_members(pool, job.version_id, &job.command.format).await?;
+        if members.is_empty() {
+            return Err(BuildBundleError::NotAggregate(job.command.spec()));
+        }
+        build(pool, storage, job.version_id, &job.command, &members).await
+    }
+    .await;
+
+    let error = result.as_ref().err().map(ToString::to_string);
+    record_outcome(pool, job.build_id, error.as_deref()).await?;
+    result
+}
+
+/// Mark a build completed, or failed with `error`
+async fn record_outcome(
+    pool: &PgPool,
+    build_id: Uuid,
+    error: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE bundle_builds
+        SET status = CASE WHEN $2::text IS NULL THEN 'completed' ELSE 'failed' END,
+            error_message = $2,
+            completed_at = NOW()
+        WHERE id = $1
+        "#,
+        build_id,
+        error
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Refuse to replace the bundle of a sealed version
+async fn ensure_rebuildable(
+    pool: &PgPool,
+    version_id: Uuid,
+    command: &BuildBundleCommand,
+) -> Result<(), BuildBundleError> {
+    let existing = sqlx::query!(
+        r#"
+        SELECT vb.s3_key, vb.sha256
+        FROM version_bundles vb
+        JOIN versions v ON v.id = vb.version_id
+        WHERE vb.version_id = $1 AND vb.format = $2 AND v.sealed_at IS NOT NULL
+        "#,
+        version_id,
+        command.format
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    if let Some(existing) = existing {
+        let details = serde_json::json!({
+            "bundle": command.format,
+            "existing": { "s3_key": existing.s3_key, "sha256": existing.sha256 },
+        });
+        sealing::record_rejected_write(pool, version_id, details).await;
+        return Err(BuildBundleError::BundleSealed(command.spec(), command.format.clone()));
+    }
+    Ok(())
+}
+
+/// Build the bundle of a version, upload it and record its member index
+async fn build(
+    pool: &PgPool,
+    storage: &Storage,
+    version_id: Uuid,
+    command: &BuildBundleCommand,
+    members: &[(String, String)],
+) -> Result<(), BuildBundleError> {
+    let filename = bundle_filename(&command.data_source_slug, &command.version, &command.format);
+    let s3_key = storage.build_key(
+        &command.organization_slug,
+        &command.data_source_slug,
+        &command.version,
+        &filename,
+    );
+
+    let spool = std::env::temp_dir().join(format!("bdp-bundle-{}-{}", Uuid::new_v4(), filename));
+    let uploaded = upload_bundle(storage, &spool, &s3_key, &command.format, members).await;
+    if let Err(e) = std::fs::remove_file(&spool) {
+        tracing::warn!(path = %spool.display(), error = %e, "Failed to remove spooled bundle");
+    }
+    let bundle = uploaded?;
+
+    let size = bundle.size as i64;
+    let member_count = bundle.members.len() as i32;
+    sqlx::query!(
+        r#"
+        INSERT INTO version_bundles
+            (version_id, format, bundle_format, s3_key, sha256, size_bytes, member_count, members)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        ON CONFLICT (version_id, format) DO UPDATE SET
+            bundle_format = EXCLUDED.bundle_format,
+            s3_key = EXCLUDED.s3_key,
+            sha256 = EXCLUDED.sha256,
+            size_bytes = EXCLUDED.size_bytes,
+            member_count = EXCLUDED.member_count,
+            members = EXCLUDED.members,
+            created_at = NOW()
+        "#,
+        version_id,
+        command.format,
+        bundle.format.as_str(),
+        s3_key,
+        bundle.sha256,
+        size,
+        member_count,
+        sqlx::types::Json(&bundle.members) as _
+    )
+    .execute(pool)
+    .await?;
+
+    tracing::info!(
+        version_id = %version_id,
+        format = %command.format,
+        member_count,
+        size,
+        "Built aggregate bundle"
+    );
+    Ok(())
+}
+
+/// Spool the bundle to `spool`, one member at a time, and stream it to storage
+async fn upload_bundle(
+    storage: &Storage,
+    spool: &Path,
+    s3_key: &str,
+    format: &str,
+    members: &[(String, String)],
+) -> Result<Bundle, BuildBundleError> {
+    let file = std::fs::File::create(spool).map_err(BundleError::from)?;
+    let mut writer =
+        BundleWriter::new(BundleFormat::for_member_format(format), BufWriter::new(file));
+    for (source, member_key) in members {
+        let content = storage.download(member_key).await?;
+        writer.append(source, format, &content)?;
+    }
+    let (_, bundle) = writer.finish()?;
+
+    let stream = ByteStream::from_path(spool)
+        .await
+        .context("Failed to read spooled bundle")?;
+    storage
+        .upload_stream(
+            s3_key,
+            stream,
+            Some(bundle.format.content_type().to_string()),
+            Some(bundle.size as i64),
+        )
+        .await?;
+    Ok(bundle)
+}
+
+/// Member files of the bundle of a version, as `(source, s3_key)` pairs
+///
+/// Members are the dependencies in the transitive closure that have a file in
+/// `format`. Nested aggregates without one are skipped since their own
+/// dependencies are members; any other dependency lacking the format fails,
+/// as the bundle would silently miss it.
+pub async fn fetch_members(
+    pool: &PgPool,
+    version_id: Uuid,
+    format: &str,
+) -> Result<Vec<(String, String)>, BuildBundleError> {
+    let candidates = sqlx::query_as!(
+        BundleCandidate,
+        r#"
+        WITH RECURSIVE closure (entry_id, version) AS (
+            SELECT d.depends_on_entry_id, d.depends_on_version
+            FROM dependencies d
+            WHERE d.version_id = $1
+            UNION
+            SELECT d.depends_on_entry_id, d.depends_on_version
+            FROM closure c
+            JOIN versions v ON v.entry_id = c.entry_id AND v.version = c.version
+            JOIN dependencies d ON d.version_id = v.id
+        )
+        SELECT
+            o.slug || ':' || re.slug || '@' || c.version AS "source!",
+            vf.s3_key AS "s3_key?",
+            EXISTS (SELECT 1 FROM dependencies d WHERE d.version_id = v.id) AS "is_aggregate!"
+        FROM closure c
+        JOIN registry_entries re ON re.id = c.entry_id
+        JOIN organizations o ON o.id = re.organization_id
+        JOIN versions v ON v.entry_id = c.entry_id AND v.version = c.version
+        LEFT JOIN version_files vf ON vf.version_id = v.id AND vf.format = $2
+        WHERE v.id <> $1
+        ORDER BY o.slug, re.slug, c.version
+        "#,
+        version_id,
+        format
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut members = Vec::with_capacity(candidates.len());
+    for candidate in candidates {
+        match candidate.s3_key {
+            Some(s3_key) => members.push((candidate.source, s3_key)),
+            None if candidate.is_aggregate => {},
+            None => {
+                return Err(BuildBundleError::MemberFormatMissing(
+                    candidate.source,
+                    format.to_string(),
+                ))
+            },
+        }
+    }
+    Ok(members)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation() {
+        let command = BuildBundleCommand {
+            organization_slug: "uniprot".to_string(),
+            data_source_slug: "proteome".to_string(),
+            version: "1.0".to_string(),
+            format: String::new(),
+        };
+        assert!(matches!(command.validate(), Err(BuildBundleError::FormatRequired)));
+    }
+
+    async fn insert_version(
+        pool: &PgPool,
+        org_id: Uuid,
+        slug: &str,
+        formats: &[&str],
+    ) -> sqlx::Result<Uuid> {
+        let version_id: Uuid = sqlx::query_scalar(
+            r#"
+            WITH entry AS (
+                INSERT INTO registry_entries (organization_id, slug, name, entry_type)
+                VALUES ($1, $2, $2, 'data_source')
+                RETURNING id
+            )
+            INSERT INTO versions (entry_id, version)
+            SELECT id, '1.0' FROM entry
+            RETURNING id
+            "#,
+        )
+        .bind(org_id)
+        .bind(slug)
+        .fetch_one(pool)
+        .await?;
+        for format in formats {
+            sqlx::query(
+                "INSERT INTO version_files (version_id, format, s3_key, checksum, size_bytes) \
+                 VALUES ($1, $2, $3, 'abc', 10)",
+            )
+            .bind(version_id)
+            .bind(format)
+            .bind(format!("test/{}.{}", slug, format))
+            .execute(pool)
+            .await?;
+        }
+        Ok(version_id)
+    }
+
+    async fn depend(pool: &PgPool, from: Uuid, to: Uuid) -> sqlx::Result<()> {
+        sqlx::query(
+            "INSERT INTO dependencies (version_id, depends_on_entry_id, depends_on_version) \
+             SELECT $1, entry_id, '1.0' FROM versions WHERE id = $2",
+        )
+        .bind(from)
+        .bind(to)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_fetch_members(pool: PgPool) -> sqlx::Result<()> {
+        let org_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO organizations (slug, name, is_system) VALUES ('uniprot', 'UniProt', true) RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await?;
+
+        // proteome -> {UP000005640, P01315}, UP000005640 -> P01308; UP000005640 has no fasta file
+        let proteome = insert_version(&pool, org_id, "proteome", &["fasta"]).await?;
+        let organism = insert_version(&pool, org_id, "UP000005640", &["json"]).await?;
+        let p01308 = insert_version(&pool, org_id, "P01308", &["fasta", "json"]).await?;
+        let p01315 = insert_version(&pool, org_id, "P01315", &["json"]).await?;
+        depend(&pool, proteome, organism).await?;
+        depend(&pool, proteome, p01315).await?;
+        depend(&pool, organism, p01308).await?;
+
+        let members = fetch_members(&pool, proteome, "json").await.unwrap();
+        let sources: Vec<&str> = members.iter().map(|(s, _)| s.as_str()).collect();
+        assert_eq!(
+            sources,
+            vec!["uniprot:P01308@1.0", "uniprot:P01315@1.0", "uniprot:UP000005640@1.0"]
+        );
+
+        let err = fetch_members(&pool, proteome, "fasta").await.unwrap_err();
+        assert!(matches!(
+            err,
+            BuildBundleError::MemberFormatMissing(source, _) if source == "uniprot:P01315@1.0"
+        ));
+
+        assert!(fetch_members(&pool, p01308, "fasta")
+            .await
+            .unwrap()
+            .is_empty());
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_handle_queues_build(pool: PgPool) -> sqlx::Result<()> {
+        let org_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO organizations (slug, name, is_system) VALUES ('uniprot', 'UniProt', true) RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await?;
+
+        // Registering its fasta file seals the proteome
+        let proteome = insert_version(&pool, org_id, "proteome", &["fasta"]).await?;
+        let p01308 = insert_version(&pool, org_id, "P01308", &["json"]).await?;
+        depend(&pool, proteome, p01308).await?;
+
+        let command = BuildBundleCommand {
+            organization_slug: "uniprot".to_string(),
+            data_source_slug: "proteome".to_string(),
+            version: "1.0".to_string(),
+            format: "json".to_string(),
+        };
+
+        let queued = handle(pool.clone(), command.clone()).await.unwrap();
+        assert_eq!(queued.member_count, 1);
+        let status: String = sqlx::query_scalar("SELECT status FROM bundle_builds WHERE id = $1")
+            .bind(queued.build_id)
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(status, "pending");
+
+        let err = handle(pool.clone(), command.clone()).await.unwrap_err();
+        assert!(matches!(err, BuildBundleError::BuildInProgress(..)));
+
+        // Once built, the bundle of the sealed proteome is final
+        record_outcome(&pool, queued.build_id, None).await?;
+        sqlx::query(
+            "INSERT INTO version_bundles \
+             (version_id, format, bundle_format, s3_key, sha256, size_bytes, member_count) \
+             VALUES ($1, 'json', 'jsonl', 'test/proteome.jsonl', 'abc', 10, 1)",
+        )
+        .bind(proteome)
+        .execute(&pool)
+        .await?;
+
+        let err = handle(pool.clone(), command).await.unwrap_err();
+        assert!(matches!(err, BuildBundleError::BundleSealed(..)));
+        Ok(())
+    }
+}
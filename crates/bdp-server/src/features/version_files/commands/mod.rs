@@ -1,3 +1,7 @@
 pub mod add_batch;
+pub mod build_bundle;
 
 pub use add_batch::{AddVersionFilesCommand, AddVersionFilesError, AddVersionFilesResponse};
+pub use build_bundle::{
+    BuildBundleCommand, BuildBundleError, BuildBundleJob, QueuedBundle, VersionBundle,
+};
@@ -1,5 +1,15 @@
+pub mod bundle_worker;
+pub mod bundles;
+pub mod checksums;
 pub mod commands;
+pub mod queries;
+pub mod routes;
+pub mod sealing;
 
 pub use commands::add_batch::{
     AddVersionFilesCommand, AddVersionFilesError, AddVersionFilesResponse,
 };
+pub use queries::{
+    GetChecksumManifestError, GetChecksumManifestQuery, GetChecksumManifestResponse,
+};
+pub use routes::version_files_routes;
@@ -0,0 +1,139 @@
+//! Download version file query
+//!
+//! Serves a registered file of a version by format, so clients and mirrors
+//! can fetch content through the API without direct storage access.
+
+use mediator::Request;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::storage::Storage;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadVersionFileQuery {
+    pub organization_slug: String,
+    pub data_source_slug: String,
+    pub version: String,
+    pub format: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct DownloadVersionFileResponse {
+    pub file_id: Uuid,
+    pub filename: String,
+    pub checksum: String,
+    pub sha256: Option<String>,
+    pub content: Vec<u8>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DownloadVersionFileError {
+    #[error("Organization slug is required and cannot be empty")]
+    OrganizationSlugRequired,
+    #[error("Data source slug is required and cannot be empty")]
+    DataSourceSlugRequired,
+    #[error("Version is required and cannot be empty")]
+    VersionRequired,
+    #[error("Format is required and cannot be empty")]
+    FormatRequired,
+    #[error("No '{3}' file for version '{2}' of data source '{0}/{1}'")]
+    NotFound(String, String, String, String),
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error("Storage error: {0}")]
+    Storage(#[from] anyhow::Error),
+}
+
+impl Request<Result<DownloadVersionFileResponse, DownloadVersionFileError>>
+    for DownloadVersionFileQuery
+{
+}
+
+impl crate::cqrs::middleware::Query for DownloadVersionFileQuery {}
+
+impl DownloadVersionFileQuery {
+    pub fn validate(&self) -> Result<(), DownloadVersionFileError> {
+        if self.organization_slug.is_empty() {
+            return Err(DownloadVersionFileError::OrganizationSlugRequired);
+        }
+        if self.data_source_slug.is_empty() {
+            return Err(DownloadVersionFileError::DataSourceSlugRequired);
+        }
+        if self.version.is_empty() {
+            return Err(DownloadVersionFileError::VersionRequired);
+        }
+        if self.format.is_empty() {
+            return Err(DownloadVersionFileError::FormatRequired);
+        }
+        Ok(())
+    }
+}
+
+#[tracing::instrument(skip(pool, storage))]
+pub async fn handle(
+    pool: PgPool,
+    storage: Storage,
+    query: DownloadVersionFileQuery,
+) -> Result<DownloadVersionFileResponse, DownloadVersionFileError> {
+    query.validate()?;
+
+    let file = sqlx::query!(
+        r#"
+        SELECT vf.id, vf.s3_key, vf.checksum, vf.sha256
+        FROM version_files vf
+        JOIN versions v ON vf.version_id = v.id
+        JOIN registry_entries re ON v.entry_id = re.id
+        JOIN organizations o ON re.organization_id = o.id
+        WHERE LOWER(o.slug) = LOWER($1) AND LOWER(re.slug) = LOWER($2)
+          AND v.version = $3 AND vf.format = $4
+        "#,
+        query.organization_slug,
+        query.data_source_slug,
+        query.version,
+        query.format
+    )
+    .fetch_optional(&pool)
+    .await?
+    .ok_or_else(|| {
+        DownloadVersionFileError::NotFound(
+            query.organization_slug.clone(),
+            query.data_source_slug.clone(),
+            query.version.clone(),
+            query.format.clone(),
+        )
+    })?;
+
+    let content = storage.download(&file.s3_key).await?;
+
+    let filename = file
+        .s3_key
+        .rsplit('/')
+        .next()
+        .unwrap_or(&file.s3_key)
+        .to_string();
+
+    Ok(DownloadVersionFileResponse {
+        file_id: file.id,
+        filename,
+        checksum: file.checksum,
+        sha256: file.sha256,
+        content,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation() {
+        let query = DownloadVersionFileQuery {
+            organization_slug: "my-lab".to_string(),
+            data_source_slug: "proteome".to_string(),
+            version: "1.0".to_string(),
+            format: String::new(),
+        };
+        assert!(matches!(query.validate(), Err(DownloadVersionFileError::FormatRequired)));
+    }
+}
@@ -0,0 +1,160 @@
+//! Get bundle query
+//!
+//! Looks up the stored bundle of an aggregate version in a member format,
+//! with its member index. Returns `None` when it hasn't been built yet.
+
+use mediator::Request;
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, PgPool};
+
+use crate::features::version_files::bundles::{BundleFormat, BundleMember};
+use crate::features::version_files::commands::VersionBundle;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetBundleQuery {
+    pub organization_slug: String,
+    pub data_source_slug: String,
+    pub version: String,
+    pub format: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GetBundleError {
+    #[error("Organization slug is required and cannot be empty")]
+    OrganizationSlugRequired,
+    #[error("Data source slug is required and cannot be empty")]
+    DataSourceSlugRequired,
+    #[error("Version is required and cannot be empty")]
+    VersionRequired,
+    #[error("Format is required and cannot be empty")]
+    FormatRequired,
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl Request<Result<Option<VersionBundle>, GetBundleError>> for GetBundleQuery {}
+
+impl crate::cqrs::middleware::Query for GetBundleQuery {}
+
+impl GetBundleQuery {
+    pub fn validate(&self) -> Result<(), GetBundleError> {
+        if self.organization_slug.is_empty() {
+            return Err(GetBundleError::OrganizationSlugRequired);
+        }
+        if self.data_source_slug.is_empty() {
+            return Err(GetBundleError::DataSourceSlugRequired);
+        }
+        if self.version.is_empty() {
+            return Err(GetBundleError::VersionRequired);
+        }
+        if self.format.is_empty() {
+            return Err(GetBundleError::FormatRequired);
+        }
+        Ok(())
+    }
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn handle(
+    pool: PgPool,
+    query: GetBundleQuery,
+) -> Result<Option<VersionBundle>, GetBundleError> {
+    query.validate()?;
+
+    let row = sqlx::query!(
+        r#"
+        SELECT vb.s3_key, vb.sha256, vb.size_bytes, vb.member_count,
+               vb.members AS "members: Json<Vec<BundleMember>>"
+        FROM version_bundles vb
+        JOIN versions v ON vb.version_id = v.id
+        JOIN registry_entries re ON v.entry_id = re.id
+        JOIN organizations o ON re.organization_id = o.id
+        WHERE LOWER(o.slug) = LOWER($1) AND LOWER(re.slug) = LOWER($2)
+          AND v.version = $3 AND vb.format = $4
+        "#,
+        query.organization_slug,
+        query.data_source_slug,
+        query.version,
+        query.format
+    )
+    .fetch_optional(&pool)
+    .await?;
+
+    Ok(row.map(|row| {
+        let filename = row
+            .s3_key
+            .rsplit('/')
+            .next()
+            .unwrap_or(&row.s3_key)
+            .to_string();
+        VersionBundle {
+            bundle_format: BundleFormat::for_member_format(&query.format),
+            format: query.format,
+            s3_key: row.s3_key,
+            filename,
+            sha256: row.sha256,
+            size: row.size_bytes,
+            member_count: row.member_count,
+            members: row.members.0,
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[sqlx::test]
+    async fn test_handle_returns_stored_bundle(pool: PgPool) -> sqlx::Result<()> {
+        let version_id: Uuid = sqlx::query_scalar(
+            r#"
+            WITH org AS (
+                INSERT INTO organizations (slug, name, is_system)
+                VALUES ('uniprot', 'UniProt', true)
+                RETURNING id
+            ), entry AS (
+                INSERT INTO registry_entries (organization_id, slug, name, entry_type)
+                SELECT id, 'proteome', 'Proteome', 'data_source' FROM org
+                RETURNING id
+            )
+            INSERT INTO versions (entry_id, version)
+            SELECT id, '1.0' FROM entry
+            RETURNING id
+            "#,
+        )
+        .fetch_one(&pool)
+        .await?;
+
+        let query = GetBundleQuery {
+            organization_slug: "uniprot".to_string(),
+            data_source_slug: "proteome".to_string(),
+            version: "1.0".to_string(),
+            format: "fasta".to_string(),
+        };
+        assert!(handle(pool.clone(), query.clone()).await.unwrap().is_none());
+
+        let members = vec![BundleMember {
+            source: "uniprot:P01308@1.0".to_string(),
+            offset: 0,
+            size: 15,
+            sha256: "a".repeat(64),
+        }];
+        sqlx::query(
+            "INSERT INTO version_bundles \
+             (version_id, format, bundle_format, s3_key, sha256, size_bytes, member_count, members) \
+             VALUES ($1, 'fasta', 'multifasta', 'data-sources/uniprot/proteome/1.0/proteome_1.0.fasta-bundle.fasta', $2, 15, 1, $3)",
+        )
+        .bind(version_id)
+        .bind("b".repeat(64))
+        .bind(Json(&members))
+        .execute(&pool)
+        .await?;
+
+        let bundle = handle(pool, query).await.unwrap().unwrap();
+        assert_eq!(bundle.bundle_format, BundleFormat::MultiFasta);
+        assert_eq!(bundle.filename, "proteome_1.0.fasta-bundle.fasta");
+        assert_eq!(bundle.members, members);
+        Ok(())
+    }
+}
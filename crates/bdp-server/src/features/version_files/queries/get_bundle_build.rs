@@ -0,0 +1,73 @@
+//! Get bundle build query
+//!
+//! Looks up a queued bundle build of a version with its status. Returns
+//! `None` when the build doesn't exist or belongs to another version.
+
+use chrono::{DateTime, Utc};
+use mediator::Request;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetBundleBuildQuery {
+    pub organization_slug: String,
+    pub data_source_slug: String,
+    pub version: String,
+    pub build_id: Uuid,
+}
+
+/// A bundle build and its status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleBuild {
+    pub id: Uuid,
+    pub format: String,
+    /// `pending`, `running`, `completed` or `failed`
+    pub status: String,
+    pub member_count: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_message: Option<String>,
+    pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GetBundleBuildError {
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl Request<Result<Option<BundleBuild>, GetBundleBuildError>> for GetBundleBuildQuery {}
+
+impl crate::cqrs::middleware::Query for GetBundleBuildQuery {}
+
+#[tracing::instrument(skip(pool))]
+pub async fn handle(
+    pool: PgPool,
+    query: GetBundleBuildQuery,
+) -> Result<Option<BundleBuild>, GetBundleBuildError> {
+    let build = sqlx::query_as!(
+        BundleBuild,
+        r#"
+        SELECT bb.id, bb.format, bb.status, bb.member_count, bb.error_message,
+               bb.created_at, bb.started_at, bb.completed_at
+        FROM bundle_builds bb
+        JOIN versions v ON bb.version_id = v.id
+        JOIN registry_entries re ON v.entry_id = re.id
+        JOIN organizations o ON re.organization_id = o.id
+        WHERE bb.id = $1 AND LOWER(o.slug) = LOWER($2) AND LOWER(re.slug) = LOWER($3)
+          AND v.version = $4
+        "#,
+        query.build_id,
+        query.organization_slug,
+        query.data_source_slug,
+        query.version
+    )
+    .fetch_optional(&pool)
+    .await?;
+
+    Ok(build)
+}
@@ -0,0 +1,118 @@
+//! Get checksum manifest query
+//!
+//! Returns the SHA256SUMS manifest of a version.
+
+use mediator::Request;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::features::version_files::checksums;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetChecksumManifestQuery {
+    pub organization_slug: String,
+    pub data_source_slug: String,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetChecksumManifestResponse {
+    pub version_id: Uuid,
+    /// Manifest text in `sha256sum` format
+    pub manifest: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GetChecksumManifestError {
+    #[error("Organization slug is required and cannot be empty")]
+    OrganizationSlugRequired,
+    #[error("Data source slug is required and cannot be empty")]
+    DataSourceSlugRequired,
+    #[error("Version is required and cannot be empty")]
+    VersionRequired,
+    #[error("Version '{2}' for data source '{0}/{1}' not found")]
+    NotFound(String, String, String),
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl Request<Result<GetChecksumManifestResponse, GetChecksumManifestError>>
+    for GetChecksumManifestQuery
+{
+}
+
+impl crate::cqrs::middleware::Query for GetChecksumManifestQuery {}
+
+impl GetChecksumManifestQuery {
+    pub fn validate(&self) -> Result<(), GetChecksumManifestError> {
+        if self.organization_slug.is_empty() {
+            return Err(GetChecksumManifestError::OrganizationSlugRequired);
+        }
+        if self.data_source_slug.is_empty() {
+            return Err(GetChecksumManifestError::DataSourceSlugRequired);
+        }
+        if self.version.is_empty() {
+            return Err(GetChecksumManifestError::VersionRequired);
+        }
+        Ok(())
+    }
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn handle(
+    pool: PgPool,
+    query: GetChecksumManifestQuery,
+) -> Result<GetChecksumManifestResponse, GetChecksumManifestError> {
+    query.validate()?;
+
+    let version = sqlx::query!(
+        r#"
+        SELECT v.id, v.checksum_manifest
+        FROM versions v
+        JOIN registry_entries re ON v.entry_id = re.id
+        JOIN organizations o ON re.organization_id = o.id
+        WHERE LOWER(o.slug) = LOWER($1) AND LOWER(re.slug) = LOWER($2) AND v.version = $3
+        "#,
+        query.organization_slug,
+        query.data_source_slug,
+        query.version
+    )
+    .fetch_optional(&pool)
+    .await?
+    .ok_or_else(|| {
+        GetChecksumManifestError::NotFound(
+            query.organization_slug.clone(),
+            query.data_source_slug.clone(),
+            query.version.clone(),
+        )
+    })?;
+
+    let manifest = match version.checksum_manifest {
+        Some(manifest) => manifest,
+        None => checksums::generate_manifest(&pool, version.id).await?,
+    };
+
+    Ok(GetChecksumManifestResponse {
+        version_id: version.id,
+        manifest,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation() {
+        let query = GetChecksumManifestQuery {
+            organization_slug: "my-lab".to_string(),
+            data_source_slug: String::new(),
+            version: "1.0".to_string(),
+        };
+        assert!(matches!(
+            query.validate(),
+            Err(GetChecksumManifestError::DataSourceSlugRequired)
+        ));
+    }
+}
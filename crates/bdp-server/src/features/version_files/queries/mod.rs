@@ -0,0 +1,13 @@
+pub mod download;
+pub mod get_bundle;
+pub mod get_bundle_build;
+pub mod get_checksums;
+
+pub use download::{
+    DownloadVersionFileError, DownloadVersionFileQuery, DownloadVersionFileResponse,
+};
+pub use get_bundle::{GetBundleError, GetBundleQuery};
+pub use get_bundle_build::{BundleBuild, GetBundleBuildError, GetBundleBuildQuery};
+pub use get_checksums::{
+    GetChecksumManifestError, GetChecksumManifestQuery, GetChecksumManifestResponse,
+};
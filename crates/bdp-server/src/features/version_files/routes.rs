@@ -0,0 +1,485 @@
+//! Version file routes
+//!
+//! Registers uploaded files (format, storage key, checksum, size) against an
+//! existing data source version. These routes are merged into the
+//! `/data-sources` router so they share its path structure.
+//!
+//! - `POST /api/v1/data-sources/:org/:slug/:version/files` - Register files for a version
+//! - `GET /api/v1/data-sources/:org/:slug/:version/SHA256SUMS` - Checksum manifest (text/plain)
+//! - `GET /api/v1/data-sources/:org/:slug/:version/download?format=` - File content
+//! - `POST /api/v1/data-sources/:org/:slug/:version/bundle?format=` - Queue a (re)build of an aggregate bundle (admin only)
+//! - `GET /api/v1/data-sources/:org/:slug/:version/bundle?format=` - Bundle content
+//! - `GET /api/v1/data-sources/:org/:slug/:version/bundle/members?format=` - Bundle member index
+//! - `GET /api/v1/data-sources/:org/:slug/:version/bundle/builds/:build_id` - Status of a queued bundle build
+//!
+//! Bundles are built by a background worker; the bundle routes return 404
+//! until the build has finished. The bundle of a sealed version is never
+//! rebuilt.
+//!
+//! Once registered, a version is sealed. Administrators can replace sealed
+//! files by passing `?override_reason=...` with an admin token.
+
+use crate::api::response::{ApiResponse, ErrorResponse};
+use crate::features::FeatureState;
+use crate::middleware::admin::AdminAuth;
+use axum::{
+    body::Body,
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use tokio_util::io::ReaderStream;
+
+use super::checksums::MANIFEST_FILENAME;
+use super::commands::{
+    add_batch::VersionFileInput, AddVersionFilesCommand, AddVersionFilesError, BuildBundleCommand,
+    BuildBundleError, VersionBundle,
+};
+use super::queries::{
+    DownloadVersionFileError, DownloadVersionFileQuery, GetBundleBuildError, GetBundleBuildQuery,
+    GetBundleError, GetBundleQuery, GetChecksumManifestError, GetChecksumManifestQuery,
+};
+
+/// Response header carrying the SHA-256 of a downloaded file
+pub const SHA256_HEADER: &str = "x-checksum-sha256";
+use super::sealing::{OverrideNotAllowed, SealOverrideParams};
+
+/// Request body for registering version files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddVersionFilesRequest {
+    pub files: Vec<VersionFileInput>,
+}
+
+/// Query parameters for file downloads
+#[derive(Debug, Clone, Deserialize)]
+pub struct DownloadParams {
+    pub format: String,
+}
+
+/// Creates the version files router
+pub fn version_files_routes() -> Router<PgPool> {
+    Router::new()
+        .route("/:org/:slug/:version/files", post(add_version_files))
+        .route(
+            &format!("/:org/:slug/:version/{}", MANIFEST_FILENAME),
+            get(get_checksum_manifest),
+        )
+}
+
+/// Creates the router for downloading version files, which needs storage
+pub fn version_file_download_routes() -> Router<FeatureState> {
+    Router::new()
+        .route("/:org/:slug/:version/download", get(download_version_file))
+        .route("/:org/:slug/:version/bundle", get(download_bundle).post(build_bundle))
+        .route("/:org/:slug/:version/bundle/members", get(get_bundle_members))
+        .route("/:org/:slug/:version/bundle/builds/:build_id", get(get_bundle_build))
+}
+
+#[tracing::instrument(skip(pool, request), fields(org = %org, slug = %slug, version = %version))]
+async fn add_version_files(
+    State(pool): State<PgPool>,
+    Path((org, slug, version)): Path<(String, String, String)>,
+    Query(params): Query<SealOverrideParams>,
+    admin: Option<AdminAuth>,
+    Json(request): Json<AddVersionFilesRequest>,
+) -> Result<Response, VersionFileApiError> {
+    let override_reason = params.authorize(admin)?;
+
+    let version_id = sqlx::query_scalar!(
+        r#"
+        SELECT v.id
+        FROM versions v
+        JOIN registry_entries re ON v.entry_id = re.id
+        JOIN organizations o ON re.organization_id = o.id
+        WHERE LOWER(o.slug) = LOWER($1) AND LOWER(re.slug) = LOWER($2) AND v.version = $3
+        "#,
+        org,
+        slug,
+        version
+    )
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| VersionFileApiError::AddError(AddVersionFilesError::Database(e)))?
+    .ok_or_else(|| VersionFileApiError::VersionNotFound(format!("{}/{}@{}", org, slug, version)))?;
+
+    let command = AddVersionFilesCommand {
+        version_id,
+        files: request.files,
+        override_reason,
+    };
+
+    let response = super::commands::add_batch::handle(pool, command).await?;
+
+    tracing::info!(
+        version_id = %response.version_id,
+        files_added = response.files_added,
+        "Version files registered via API"
+    );
+
+    Ok((StatusCode::CREATED, Json(ApiResponse::success(response))).into_response())
+}
+
+#[tracing::instrument(skip(pool), fields(org = %org, slug = %slug, version = %version))]
+async fn get_checksum_manifest(
+    State(pool): State<PgPool>,
+    Path((org, slug, version)): Path<(String, String, String)>,
+) -> Result<Response, VersionFileApiError> {
+    let query = GetChecksumManifestQuery {
+        organization_slug: org,
+        data_source_slug: slug,
+        version,
+    };
+
+    let response = super::queries::get_checksums::handle(pool, query).await?;
+
+    Ok((
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+        response.manifest,
+    )
+        .into_response())
+}
+
+#[tracing::instrument(skip(state), fields(org = %org, slug = %slug, version = %version))]
+async fn download_version_file(
+    State(state): State<FeatureState>,
+    Path((org, slug, version)): Path<(String, String, String)>,
+    Query(params): Query<DownloadParams>,
+) -> Result<Response, VersionFileApiError> {
+    let query = DownloadVersionFileQuery {
+        organization_slug: org,
+        data_source_slug: slug,
+        version,
+        format: params.format,
+    };
+
+    let response = super::queries::download::handle(state.db, state.storage, query).await?;
+    let sha256 = response.sha256.unwrap_or(response.checksum);
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "application/octet-stream".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", response.filename),
+            ),
+            (header::HeaderName::from_static(SHA256_HEADER), sha256),
+        ],
+        response.content,
+    )
+        .into_response())
+}
+
+/// Queue a (re)build of the bundle of an aggregate version (admin only)
+#[tracing::instrument(skip(state, _admin), fields(org = %org, slug = %slug, version = %version))]
+async fn build_bundle(
+    State(state): State<FeatureState>,
+    _admin: AdminAuth,
+    Path((org, slug, version)): Path<(String, String, String)>,
+    Query(params): Query<DownloadParams>,
+) -> Result<Response, VersionFileApiError> {
+    let command = BuildBundleCommand {
+        organization_slug: org,
+        data_source_slug: slug,
+        version,
+        format: params.format,
+    };
+
+    let queued = super::commands::build_bundle::handle(state.db, command).await?;
+
+    Ok((StatusCode::ACCEPTED, Json(ApiResponse::success(queued))).into_response())
+}
+
+#[tracing::instrument(skip(state), fields(org = %org, slug = %slug, version = %version))]
+async fn get_bundle_build(
+    State(state): State<FeatureState>,
+    Path((org, slug, version, build_id)): Path<(String, String, String, uuid::Uuid)>,
+) -> Result<Response, VersionFileApiError> {
+    let query = GetBundleBuildQuery {
+        organization_slug: org,
+        data_source_slug: slug,
+        version,
+        build_id,
+    };
+
+    let build = super::queries::get_bundle_build::handle(state.db, query)
+        .await?
+        .ok_or(VersionFileApiError::BuildNotFound(build_id))?;
+
+    Ok((StatusCode::OK, Json(ApiResponse::success(build))).into_response())
+}
+
+#[tracing::instrument(skip(state), fields(org = %org, slug = %slug, version = %version))]
+async fn download_bundle(
+    State(state): State<FeatureState>,
+    Path((org, slug, version)): Path<(String, String, String)>,
+    Query(params): Query<DownloadParams>,
+) -> Result<Response, VersionFileApiError> {
+    let bundle = find_bundle(&state.db, org, slug, version, params.format).await?;
+    let content = state
+        .storage
+        .download_stream(&bundle.s3_key)
+        .await
+        .map_err(BuildBundleError::Storage)?;
+    let body = Body::from_stream(ReaderStream::new(content.into_async_read()));
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, bundle.bundle_format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", bundle.filename),
+            ),
+            (header::HeaderName::from_static(SHA256_HEADER), bundle.sha256),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+#[tracing::instrument(skip(state), fields(org = %org, slug = %slug, version = %version))]
+async fn get_bundle_members(
+    State(state): State<FeatureState>,
+    Path((org, slug, version)): Path<(String, String, String)>,
+    Query(params): Query<DownloadParams>,
+) -> Result<Response, VersionFileApiError> {
+    let bundle = find_bundle(&state.db, org, slug, version, params.format).await?;
+
+    Ok((StatusCode::OK, Json(ApiResponse::success(bundle))).into_response())
+}
+
+async fn find_bundle(
+    pool: &PgPool,
+    org: String,
+    slug: String,
+    version: String,
+    format: String,
+) -> Result<VersionBundle, VersionFileApiError> {
+    let query = GetBundleQuery {
+        organization_slug: org,
+        data_source_slug: slug,
+        version,
+        format,
+    };
+    let spec = format!("{}/{}@{}", query.organization_slug, query.data_source_slug, query.version);
+    let format = query.format.clone();
+
+    super::queries::get_bundle::handle(pool.clone(), query)
+        .await?
+        .ok_or(VersionFileApiError::BundleNotBuilt(spec, format))
+}
+
+#[derive(Debug)]
+enum VersionFileApiError {
+    VersionNotFound(String),
+    BundleNotBuilt(String, String),
+    BuildNotFound(uuid::Uuid),
+    OverrideNotAllowed(OverrideNotAllowed),
+    AddError(AddVersionFilesError),
+    ManifestError(GetChecksumManifestError),
+    DownloadError(DownloadVersionFileError),
+    BundleError(BuildBundleError),
+    BundleLookupError(GetBundleError),
+    BuildLookupError(GetBundleBuildError),
+}
+
+impl From<BuildBundleError> for VersionFileApiError {
+    fn from(err: BuildBundleError) -> Self {
+        Self::BundleError(err)
+    }
+}
+
+impl From<GetBundleBuildError> for VersionFileApiError {
+    fn from(err: GetBundleBuildError) -> Self {
+        Self::BuildLookupError(err)
+    }
+}
+
+impl From<GetBundleError> for VersionFileApiError {
+    fn from(err: GetBundleError) -> Self {
+        Self::BundleLookupError(err)
+    }
+}
+
+impl From<DownloadVersionFileError> for VersionFileApiError {
+    fn from(err: DownloadVersionFileError) -> Self {
+        Self::DownloadError(err)
+    }
+}
+
+impl From<GetChecksumManifestError> for VersionFileApiError {
+    fn from(err: GetChecksumManifestError) -> Self {
+        Self::ManifestError(err)
+    }
+}
+
+impl From<OverrideNotAllowed> for VersionFileApiError {
+    fn from(err: OverrideNotAllowed) -> Self {
+        Self::OverrideNotAllowed(err)
+    }
+}
+
+impl From<AddVersionFilesError> for VersionFileApiError {
+    fn from(err: AddVersionFilesError) -> Self {
+        Self::AddError(err)
+    }
+}
+
+impl IntoResponse for VersionFileApiError {
+    fn into_response(self) -> Response {
+        match self {
+            VersionFileApiError::VersionNotFound(_)
+            | VersionFileApiError::BundleNotBuilt(..)
+            | VersionFileApiError::BuildNotFound(_)
+            | VersionFileApiError::AddError(AddVersionFilesError::VersionNotFound(_)) => {
+                let error = ErrorResponse::new("NOT_FOUND", self.to_string());
+                (StatusCode::NOT_FOUND, Json(error)).into_response()
+            },
+            VersionFileApiError::OverrideNotAllowed(_) => {
+                let error = ErrorResponse::new("FORBIDDEN", self.to_string());
+                (StatusCode::FORBIDDEN, Json(error)).into_response()
+            },
+            VersionFileApiError::AddError(AddVersionFilesError::VersionSealed(..)) => {
+                let error = ErrorResponse::new("VERSION_SEALED", self.to_string());
+                (StatusCode::CONFLICT, Json(error)).into_response()
+            },
+            VersionFileApiError::AddError(AddVersionFilesError::Database(_)) => {
+                tracing::error!("Database error while registering version files: {}", self);
+                let error = ErrorResponse::new("INTERNAL_ERROR", "A database error occurred");
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            },
+            VersionFileApiError::AddError(_) => {
+                let error = ErrorResponse::new("VALIDATION_ERROR", self.to_string());
+                (StatusCode::BAD_REQUEST, Json(error)).into_response()
+            },
+            VersionFileApiError::ManifestError(GetChecksumManifestError::NotFound(..)) => {
+                let error = ErrorResponse::new("NOT_FOUND", self.to_string());
+                (StatusCode::NOT_FOUND, Json(error)).into_response()
+            },
+            VersionFileApiError::ManifestError(GetChecksumManifestError::Database(_)) => {
+                tracing::error!("Database error while loading checksum manifest: {}", self);
+                let error = ErrorResponse::new("INTERNAL_ERROR", "A database error occurred");
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            },
+            VersionFileApiError::ManifestError(_) => {
+                let error = ErrorResponse::new("VALIDATION_ERROR", self.to_string());
+                (StatusCode::BAD_REQUEST, Json(error)).into_response()
+            },
+            VersionFileApiError::DownloadError(DownloadVersionFileError::NotFound(..)) => {
+                let error = ErrorResponse::new("NOT_FOUND", self.to_string());
+                (StatusCode::NOT_FOUND, Json(error)).into_response()
+            },
+            VersionFileApiError::DownloadError(DownloadVersionFileError::Database(_)) => {
+                tracing::error!("Database error while downloading version file: {}", self);
+                let error = ErrorResponse::new("INTERNAL_ERROR", "A database error occurred");
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            },
+            VersionFileApiError::DownloadError(DownloadVersionFileError::Storage(_)) => {
+                tracing::error!("Storage error while downloading version file: {}", self);
+                let error = ErrorResponse::new("STORAGE_ERROR", "A storage error occurred");
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            },
+            VersionFileApiError::DownloadError(_) => {
+                let error = ErrorResponse::new("VALIDATION_ERROR", self.to_string());
+                (StatusCode::BAD_REQUEST, Json(error)).into_response()
+            },
+            VersionFileApiError::BundleError(BuildBundleError::VersionNotFound(_)) => {
+                let error = ErrorResponse::new("NOT_FOUND", self.to_string());
+                (StatusCode::NOT_FOUND, Json(error)).into_response()
+            },
+            VersionFileApiError::BundleError(BuildBundleError::BundleSealed(..)) => {
+                let error = ErrorResponse::new("VERSION_SEALED", self.to_string());
+                (StatusCode::CONFLICT, Json(error)).into_response()
+            },
+            VersionFileApiError::BundleError(BuildBundleError::BuildInProgress(..)) => {
+                let error = ErrorResponse::new("CONFLICT", self.to_string());
+                (StatusCode::CONFLICT, Json(error)).into_response()
+            },
+            VersionFileApiError::BundleError(
+                BuildBundleError::NotAggregate(_)
+                | BuildBundleError::MemberFormatMissing(..)
+                | BuildBundleError::Bundle(_),
+            ) => {
+                let error = ErrorResponse::new("BUNDLE_UNAVAILABLE", self.to_string());
+                (StatusCode::UNPROCESSABLE_ENTITY, Json(error)).into_response()
+            },
+            VersionFileApiError::BundleError(BuildBundleError::Database(_))
+            | VersionFileApiError::BundleError(BuildBundleError::Queue(_))
+            | VersionFileApiError::BundleLookupError(GetBundleError::Database(_))
+            | VersionFileApiError::BuildLookupError(GetBundleBuildError::Database(_)) => {
+                tracing::error!("Database error while serving bundle: {}", self);
+                let error = ErrorResponse::new("INTERNAL_ERROR", "A database error occurred");
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            },
+            VersionFileApiError::BundleError(BuildBundleError::Storage(_)) => {
+                tracing::error!("Storage error while serving bundle: {}", self);
+                let error = ErrorResponse::new("STORAGE_ERROR", "A storage error occurred");
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            },
+            VersionFileApiError::BundleError(_) | VersionFileApiError::BundleLookupError(_) => {
+                let error = ErrorResponse::new("VALIDATION_ERROR", self.to_string());
+                (StatusCode::BAD_REQUEST, Json(error)).into_response()
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for VersionFileApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::VersionNotFound(spec) => write!(f, "Version '{}' not found", spec),
+            Self::BundleNotBuilt(spec, format) => {
+                write!(f, "No '{}' bundle of '{}' has been built", format, spec)
+            },
+            Self::BuildNotFound(id) => write!(f, "Bundle build '{}' not found", id),
+            Self::OverrideNotAllowed(e) => write!(f, "{}", e),
+            Self::AddError(e) => write!(f, "{}", e),
+            Self::ManifestError(e) => write!(f, "{}", e),
+            Self::DownloadError(e) => write!(f, "{}", e),
+            Self::BundleError(e) => write!(f, "{}", e),
+            Self::BundleLookupError(e) => write!(f, "{}", e),
+            Self::BuildLookupError(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_display() {
+        let err = VersionFileApiError::AddError(AddVersionFilesError::FilesRequired);
+        assert!(err.to_string().contains("cannot be empty"));
+
+        let err = VersionFileApiError::VersionNotFound("my-lab/proteome@1.0".to_string());
+        assert!(err.to_string().contains("my-lab/proteome@1.0"));
+
+        let err = VersionFileApiError::BundleNotBuilt(
+            "uniprot/proteome@1.0".to_string(),
+            "fasta".to_string(),
+        );
+        assert_eq!(err.into_response().status(), StatusCode::NOT_FOUND);
+
+        let err = VersionFileApiError::BundleError(BuildBundleError::MemberFormatMissing(
+            "uniprot:P01308@1.0".to_string(),
+            "dat".to_string(),
+        ));
+        assert!(err.to_string().contains("no 'dat' file"));
+    }
+
+    #[test]
+    fn test_routes_structure() {
+        let router = version_files_routes();
+        assert!(format!("{:?}", router).contains("Router"));
+
+        let router = version_file_download_routes();
+        assert!(format!("{:?}", router).contains("Router"));
+    }
+}
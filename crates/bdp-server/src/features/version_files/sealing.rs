@@ -0,0 +1,146 @@
+//! Sealed version helpers
+//!
+//! A version is sealed once its files are registered (see the
+//! `seal_version_artifacts` migration). Sealed files cannot be replaced in
+//! storage or in `version_files`; administrators may override the seal with a
+//! reason, and both refusals and overrides are written to the audit log.
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::audit::{create_audit_entry, AuditAction, CreateAuditEntry, ResourceType};
+use crate::middleware::admin::AdminAuth;
+
+/// Query parameters accepted by routes that write to a version
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SealOverrideParams {
+    /// Why the seal is being overridden; requires an admin token
+    pub override_reason: Option<String>,
+}
+
+/// The override was requested without admin credentials
+#[derive(Debug, thiserror::Error)]
+#[error("Overriding a sealed version requires an admin token")]
+pub struct OverrideNotAllowed;
+
+impl SealOverrideParams {
+    /// The override reason, provided the caller is an administrator
+    pub fn authorize(self, admin: Option<AdminAuth>) -> Result<Option<String>, OverrideNotAllowed> {
+        match self.override_reason.filter(|r| !r.trim().is_empty()) {
+            Some(_) if admin.is_none() => Err(OverrideNotAllowed),
+            reason => Ok(reason),
+        }
+    }
+}
+
+/// A version whose files are sealed
+#[derive(Debug, Clone)]
+pub struct SealedVersion {
+    pub id: Uuid,
+    pub sealed_at: DateTime<Utc>,
+}
+
+/// Look up `org/slug@version` and return it only if it is sealed
+pub async fn find_sealed_version(
+    pool: &PgPool,
+    org: &str,
+    slug: &str,
+    version: &str,
+) -> Result<Option<SealedVersion>, sqlx::Error> {
+    let row = sqlx::query!(
+        r#"
+        SELECT v.id, v.sealed_at
+        FROM versions v
+        JOIN registry_entries re ON v.entry_id = re.id
+        JOIN organizations o ON re.organization_id = o.id
+        WHERE LOWER(o.slug) = LOWER($1) AND LOWER(re.slug) = LOWER($2) AND v.version = $3
+        "#,
+        org,
+        slug,
+        version
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.and_then(|r| {
+        r.sealed_at.map(|sealed_at| SealedVersion {
+            id: r.id,
+            sealed_at,
+        })
+    }))
+}
+
+/// Lift the database seal guard for the rest of the transaction
+pub async fn allow_sealed_overwrite(tx: &mut Transaction<'_, Postgres>) -> Result<(), sqlx::Error> {
+    sqlx::query("SELECT set_config('bdp.allow_sealed_overwrite', 'on', true)")
+        .execute(&mut **tx)
+        .await?;
+    Ok(())
+}
+
+/// Audit a write that was refused because the version is sealed
+pub async fn record_rejected_write(pool: &PgPool, version_id: Uuid, details: JsonValue) {
+    tracing::warn!(version_id = %version_id, details = %details, "Rejected write to sealed version");
+
+    if let Err(e) = create_audit_entry(
+        pool,
+        CreateAuditEntry::builder()
+            .action(AuditAction::OverwriteRejected)
+            .resource_type(ResourceType::Version)
+            .resource_id(Some(version_id))
+            .changes(details)
+            .build(),
+    )
+    .await
+    {
+        tracing::warn!(error = %e, "Failed to create audit log for rejected sealed write");
+    }
+}
+
+/// Audit an administrator override of a sealed version
+pub async fn record_override(pool: &PgPool, version_id: Uuid, reason: &str, details: JsonValue) {
+    tracing::warn!(version_id = %version_id, reason, "Sealed version overridden by admin");
+
+    if let Err(e) = create_audit_entry(
+        pool,
+        CreateAuditEntry::builder()
+            .action(AuditAction::SealOverride)
+            .resource_type(ResourceType::Version)
+            .resource_id(Some(version_id))
+            .changes(details)
+            .metadata(serde_json::json!({ "reason": reason }))
+            .build(),
+    )
+    .await
+    {
+        tracing::warn!(error = %e, "Failed to create audit log for sealed version override");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_authorize_override() {
+        let none = SealOverrideParams::default();
+        assert!(none.clone().authorize(None).unwrap().is_none());
+
+        let params = SealOverrideParams {
+            override_reason: Some("re-upload truncated file".to_string()),
+        };
+        assert!(params.clone().authorize(None).is_err());
+        assert_eq!(
+            params.authorize(Some(AdminAuth)).unwrap().as_deref(),
+            Some("re-upload truncated file")
+        );
+
+        let blank = SealOverrideParams {
+            override_reason: Some("  ".to_string()),
+        };
+        assert!(blank.authorize(None).unwrap().is_none());
+    }
+}
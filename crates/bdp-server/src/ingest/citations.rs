@@ -203,6 +203,108 @@ pub fn ncbi_taxonomy_policy(
     }
 }
 
+/// Ensembl citation policy
+pub fn ensembl_policy(organization_id: Uuid, license_id: Option<Uuid>) -> CitationPolicyConfig {
+    CitationPolicyConfig {
+        organization_id,
+        policy_url: "https://www.ensembl.org/info/about/publications.html".to_string(),
+        license_id,
+        requires_version_citation: true, // Gene sets change between releases
+        requires_accession_citation: false,
+        citation_instructions: "When citing Ensembl, cite the most recent Ensembl paper and state the Ensembl release number (e.g., Ensembl release 110) of the data used.".to_string(),
+        required_citations: vec![RequiredCitation {
+            citation: CitationData {
+                doi: Some("10.1093/nar/gkad1049".to_string()),
+                pubmed_id: Some("37953337".to_string()),
+                title: "Ensembl 2024".to_string(),
+                journal: Some("Nucleic Acids Research".to_string()),
+                publication_date: chrono::NaiveDate::from_ymd_opt(2024, 1, 5),
+                volume: Some("52".to_string()),
+                pages: Some("D891-D899".to_string()),
+                authors: "Harrison, P. W., Amode, M. R., Austine-Orimoloye, O., et al.".to_string(),
+                bibtex: None,
+            },
+            requirement_type: CitationRequirementType::Required,
+            display_order: 1,
+            context: Some("Primary database paper".to_string()),
+        }],
+    }
+}
+
+/// PDB citation policy
+pub fn pdb_policy(organization_id: Uuid, license_id: Option<Uuid>) -> CitationPolicyConfig {
+    CitationPolicyConfig {
+        organization_id,
+        policy_url: "https://www.rcsb.org/pages/policies".to_string(),
+        license_id,
+        requires_version_citation: false,
+        requires_accession_citation: true, // Structures are cited by PDB ID
+        citation_instructions: "When using PDB data, cite the PDB ID of each structure together with its primary citation, and the Protein Data Bank paper.".to_string(),
+        required_citations: vec![RequiredCitation {
+            citation: CitationData {
+                doi: Some("10.1093/nar/28.1.235".to_string()),
+                pubmed_id: Some("10592235".to_string()),
+                title: "The Protein Data Bank".to_string(),
+                journal: Some("Nucleic Acids Research".to_string()),
+                publication_date: chrono::NaiveDate::from_ymd_opt(2000, 1, 1),
+                volume: Some("28".to_string()),
+                pages: Some("235-242".to_string()),
+                authors: "Berman, H. M., Westbrook, J., Feng, Z., et al.".to_string(),
+                bibtex: None,
+            },
+            requirement_type: CitationRequirementType::Required,
+            display_order: 1,
+            context: Some("Primary database paper".to_string()),
+        }],
+    }
+}
+
+/// AlphaFold DB citation policy
+pub fn alphafold_policy(organization_id: Uuid, license_id: Option<Uuid>) -> CitationPolicyConfig {
+    CitationPolicyConfig {
+        organization_id,
+        policy_url: "https://alphafold.ebi.ac.uk/faq".to_string(),
+        license_id,
+        requires_version_citation: true, // Models change between model versions
+        requires_accession_citation: false,
+        citation_instructions: "When using AlphaFold DB models, cite both the AlphaFold method paper and the AlphaFold Protein Structure Database paper, and state the model version (e.g., v4).".to_string(),
+        required_citations: vec![
+            RequiredCitation {
+                citation: CitationData {
+                    doi: Some("10.1038/s41586-021-03819-2".to_string()),
+                    pubmed_id: Some("34265844".to_string()),
+                    title: "Highly accurate protein structure prediction with AlphaFold".to_string(),
+                    journal: Some("Nature".to_string()),
+                    publication_date: chrono::NaiveDate::from_ymd_opt(2021, 7, 15),
+                    volume: Some("596".to_string()),
+                    pages: Some("583-589".to_string()),
+                    authors: "Jumper, J., Evans, R., Pritzel, A., et al.".to_string(),
+                    bibtex: None,
+                },
+                requirement_type: CitationRequirementType::Required,
+                display_order: 1,
+                context: Some("AlphaFold method".to_string()),
+            },
+            RequiredCitation {
+                citation: CitationData {
+                    doi: Some("10.1093/nar/gkad1011".to_string()),
+                    pubmed_id: Some("37933859".to_string()),
+                    title: "AlphaFold Protein Structure Database in 2024: providing structure coverage for over 214 million protein sequences".to_string(),
+                    journal: Some("Nucleic Acids Research".to_string()),
+                    publication_date: chrono::NaiveDate::from_ymd_opt(2024, 1, 5),
+                    volume: Some("52".to_string()),
+                    pages: Some("D368-D375".to_string()),
+                    authors: "Varadi, M., Bertoni, D., Magana, P., et al.".to_string(),
+                    bibtex: None,
+                },
+                requirement_type: CitationRequirementType::Required,
+                display_order: 2,
+                context: Some("Primary database paper".to_string()),
+            },
+        ],
+    }
+}
+
 /// Set up citation policy for an organization
 pub async fn setup_citation_policy(db: &PgPool, config: &CitationPolicyConfig) -> Result<Uuid> {
     let mut tx = db.begin().await.context("Failed to begin transaction")?;
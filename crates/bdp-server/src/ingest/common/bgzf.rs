@@ -0,0 +1,292 @@
+//! Seekable reader for BGZF (blocked gzip) files
+//!
+//! BGZF files are concatenated gzip members of at most 64 KiB each, with the
+//! compressed size of every member in its header. They decompress with any
+//! gzip reader, and can also be read from the start of any block. Positions
+//! are virtual offsets: the compressed offset of a block shifted left by 16
+//! bits, plus the offset within the block's decompressed data.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use bdp_server::ingest::common::bgzf::BgzfReader;
+//!
+//! let mut reader = BgzfReader::new(BufReader::new(File::open("genes.gff3.gz")?));
+//! reader.seek_virtual(offset)?;
+//! ```
+
+use flate2::read::DeflateDecoder;
+use flate2::Crc;
+use std::io::{self, BufRead, Read, Seek, SeekFrom};
+
+/// Length of the fixed part of a gzip member header
+const GZIP_HEADER_LEN: usize = 12;
+
+/// Whether data starts with a BGZF block header
+///
+/// Checks for a gzip header with the extra field whose first subfield is
+/// BGZF's block size (`BC`), as samtools does.
+pub fn is_bgzf(header: &[u8]) -> bool {
+    header.len() >= 18
+        && header[..4] == [0x1f, 0x8b, 8, 4]
+        && header[12..14] == *b"BC"
+        && header[14..16] == [2, 0]
+}
+
+/// Sequential and random access to the decompressed data of a BGZF file
+pub struct BgzfReader<R> {
+    inner: R,
+    /// Compressed offset of the current block
+    block_offset: u64,
+    /// Compressed size of the current block
+    block_size: u64,
+    /// Decompressed data of the current block
+    block: Vec<u8>,
+    /// Read position in `block`
+    position: usize,
+    eof: bool,
+}
+
+impl<R: Read + Seek> BgzfReader<R> {
+    /// Read from the start of the file
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            block_offset: 0,
+            block_size: 0,
+            block: Vec::new(),
+            position: 0,
+            eof: false,
+        }
+    }
+
+    /// Virtual offset of the next byte to be read
+    ///
+    /// At the end of a block this is the start of the next block, so every
+    /// position has a single virtual offset.
+    pub fn virtual_offset(&self) -> u64 {
+        if self.position == self.block.len() {
+            (self.block_offset + self.block_size) << 16
+        } else {
+            (self.block_offset << 16) | self.position as u64
+        }
+    }
+
+    /// Continue reading at a virtual offset
+    pub fn seek_virtual(&mut self, virtual_offset: u64) -> io::Result<()> {
+        let block_offset = virtual_offset >> 16;
+        let within_block = (virtual_offset & 0xffff) as usize;
+
+        self.load_block(block_offset)?;
+        if within_block > self.block.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "Virtual offset {} is past the end of the block at {}",
+                    virtual_offset, block_offset
+                ),
+            ));
+        }
+        self.position = within_block;
+        Ok(())
+    }
+
+    /// Read and decompress the block at a compressed offset
+    ///
+    /// Past the last block, the block is empty and `eof` is set.
+    fn load_block(&mut self, block_offset: u64) -> io::Result<()> {
+        self.inner.seek(SeekFrom::Start(block_offset))?;
+        self.block_offset = block_offset;
+        self.block.clear();
+        self.position = 0;
+
+        let mut header = [0u8; GZIP_HEADER_LEN];
+        if !read_exact_or_eof(&mut self.inner, &mut header)? {
+            self.block_size = 0;
+            self.eof = true;
+            return Ok(());
+        }
+        self.eof = false;
+        if header[..4] != [0x1f, 0x8b, 8, 4] {
+            return Err(invalid_data(block_offset, "not a BGZF block header"));
+        }
+
+        let extra_len = u16::from_le_bytes([header[10], header[11]]) as usize;
+        let mut extra = vec![0u8; extra_len];
+        self.inner.read_exact(&mut extra)?;
+        let block_size = block_size(&extra)
+            .ok_or_else(|| invalid_data(block_offset, "no BC subfield with the block size"))?;
+
+        let data_len = block_size
+            .checked_sub(GZIP_HEADER_LEN + extra_len + 8)
+            .ok_or_else(|| invalid_data(block_offset, "block size is too small"))?;
+        let mut compressed = vec![0u8; data_len + 8];
+        self.inner.read_exact(&mut compressed)?;
+        let (data, trailer) = compressed.split_at(data_len);
+
+        DeflateDecoder::new(data).read_to_end(&mut self.block)?;
+
+        let expected_crc = u32::from_le_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]);
+        let expected_len = u32::from_le_bytes([trailer[4], trailer[5], trailer[6], trailer[7]]);
+        let mut crc = Crc::new();
+        crc.update(&self.block);
+        if crc.sum() != expected_crc || self.block.len() as u32 != expected_len {
+            return Err(invalid_data(block_offset, "checksum mismatch"));
+        }
+
+        self.block_size = block_size as u64;
+        Ok(())
+    }
+}
+
+impl<R: Read + Seek> Read for BgzfReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let len = available.len().min(buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+impl<R: Read + Seek> BufRead for BgzfReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        // Skip empty blocks, such as the end-of-file marker block
+        while self.position == self.block.len() && !self.eof {
+            self.load_block(self.block_offset + self.block_size)?;
+        }
+        Ok(&self.block[self.position..])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.position = (self.position + amount).min(self.block.len());
+    }
+}
+
+/// Total size of a block, from the `BC` subfield of its extra field
+fn block_size(extra: &[u8]) -> Option<usize> {
+    let mut rest = extra;
+    while rest.len() >= 4 {
+        let subfield_len = u16::from_le_bytes([rest[2], rest[3]]) as usize;
+        let (subfield, next) = rest[4..].split_at_checked(subfield_len)?;
+        if rest[..2] == *b"BC" && subfield_len == 2 {
+            return Some(u16::from_le_bytes([subfield[0], subfield[1]]) as usize + 1);
+        }
+        rest = next;
+    }
+    None
+}
+
+/// Fill `buf`, or return false at the end of the input
+fn read_exact_or_eof<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<bool> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) if filled == 0 => return Ok(false),
+            Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {},
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(true)
+}
+
+fn invalid_data(block_offset: u64, reason: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("Invalid BGZF block at offset {}: {}", block_offset, reason),
+    )
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use flate2::write::DeflateEncoder;
+    use flate2::Compression;
+    use std::io::{Cursor, Write};
+
+    /// Compress data into BGZF blocks of at most `block_len` bytes, with the
+    /// end-of-file marker block
+    pub(crate) fn bgzf_compress(data: &[u8], block_len: usize) -> Vec<u8> {
+        let mut output = Vec::new();
+        for chunk in data.chunks(block_len).chain(std::iter::once(&[][..])) {
+            let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(chunk).unwrap();
+            let compressed = encoder.finish().unwrap();
+            let mut crc = Crc::new();
+            crc.update(chunk);
+
+            let block_size = (GZIP_HEADER_LEN + 6 + compressed.len() + 8 - 1) as u16;
+            output.extend_from_slice(&[0x1f, 0x8b, 8, 4, 0, 0, 0, 0, 0, 0xff, 6, 0]);
+            output.extend_from_slice(b"BC");
+            output.extend_from_slice(&[2, 0]);
+            output.extend_from_slice(&block_size.to_le_bytes());
+            output.extend_from_slice(&compressed);
+            output.extend_from_slice(&crc.sum().to_le_bytes());
+            output.extend_from_slice(&(chunk.len() as u32).to_le_bytes());
+        }
+        output
+    }
+
+    #[test]
+    fn test_sequential_read() {
+        let data: Vec<u8> = (0..1000u32)
+            .flat_map(|i| i.to_string().into_bytes())
+            .collect();
+        let compressed = bgzf_compress(&data, 100);
+        assert!(is_bgzf(&compressed));
+        assert!(!is_bgzf(b"ID   001R_FRG3G"));
+
+        let mut decompressed = Vec::new();
+        BgzfReader::new(Cursor::new(&compressed))
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, data);
+
+        // BGZF files are valid multi-member gzip files
+        let mut gunzipped = Vec::new();
+        flate2::read::MultiGzDecoder::new(&compressed[..])
+            .read_to_end(&mut gunzipped)
+            .unwrap();
+        assert_eq!(gunzipped, data);
+    }
+
+    #[test]
+    fn test_seek_virtual() {
+        let data = b"line one\nline two\nline three\n".repeat(20);
+        let compressed = bgzf_compress(&data, 64);
+        let mut reader = BgzfReader::new(Cursor::new(&compressed));
+
+        // Remember the virtual offset of every line while reading through
+        let mut offsets = Vec::new();
+        let mut line = String::new();
+        loop {
+            let offset = reader.virtual_offset();
+            line.clear();
+            if reader.read_line(&mut line).unwrap() == 0 {
+                break;
+            }
+            offsets.push((offset, line.clone()));
+        }
+        assert_eq!(offsets.len(), 60);
+
+        for (offset, expected) in offsets.iter().rev() {
+            reader.seek_virtual(*offset).unwrap();
+            line.clear();
+            reader.read_line(&mut line).unwrap();
+            assert_eq!(&line, expected);
+        }
+
+        assert!(reader.seek_virtual((1 << 16) | 0xffff).is_err());
+    }
+
+    #[test]
+    fn test_rejects_corrupt_block() {
+        let mut compressed = bgzf_compress(b"some data", 64);
+        let crc_offset = compressed.len() - 28 - 8;
+        compressed[crc_offset] ^= 0xff;
+        let mut reader = BgzfReader::new(Cursor::new(&compressed));
+        assert!(reader.read_to_end(&mut Vec::new()).is_err());
+    }
+}
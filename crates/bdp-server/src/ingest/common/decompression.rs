@@ -9,6 +9,10 @@
 //! - **Tar.gz** (.tar.gz, .tgz): Combined gzip + tar
 //! - **Zip** (.zip): Using zip crate
 //!
+//! Files too large to hold in memory are decompressed from a reader to disk
+//! with [`decompress_gzip_to_file`] and [`extract_tar_gz_to_dir`]. Gzip files
+//! with several members, BGZF files included, are read to the end.
+//!
 //! # Examples
 //!
 //! ```rust,ignore
@@ -22,9 +26,11 @@
 //! ```
 
 use anyhow::{Context, Result};
-use flate2::read::GzDecoder;
+use flate2::read::{GzDecoder, MultiGzDecoder};
 use std::collections::HashMap;
-use std::io::{Cursor, Read};
+use std::fs::File;
+use std::io::{BufWriter, Cursor, Read, Write};
+use std::path::{Path, PathBuf};
 use tracing::debug;
 
 /// Decompress gzip-compressed data
@@ -153,6 +159,87 @@ pub fn extract_zip(data: &[u8], filenames: &[&str]) -> Result<HashMap<String, Ve
     Ok(result)
 }
 
+/// Decompress a gzip stream to a file without buffering it in memory
+///
+/// # Arguments
+/// * `reader` - Gzip-compressed stream
+/// * `dest` - File to write the decompressed data to
+///
+/// # Returns
+/// Number of decompressed bytes written
+pub fn decompress_gzip_to_file<R: Read>(reader: R, dest: &Path) -> Result<u64> {
+    let file =
+        File::create(dest).with_context(|| format!("Failed to create {}", dest.display()))?;
+    let mut writer = BufWriter::new(file);
+    let written = std::io::copy(&mut MultiGzDecoder::new(reader), &mut writer)
+        .context("Failed to decompress gzip stream")?;
+    writer
+        .flush()
+        .with_context(|| format!("Failed to write {}", dest.display()))?;
+    debug!("Decompressed {} bytes to {}", written, dest.display());
+    Ok(written)
+}
+
+/// Extract files from a tar.gz stream into a directory
+///
+/// # Arguments
+/// * `reader` - Gzip-compressed tar archive stream
+/// * `filenames` - List of filenames to extract (empty = extract all)
+/// * `dest_dir` - Directory to write the files to, under their filenames
+///
+/// # Returns
+/// HashMap of filename -> extracted file path
+pub fn extract_tar_gz_to_dir<R: Read>(
+    reader: R,
+    filenames: &[&str],
+    dest_dir: &Path,
+) -> Result<HashMap<String, PathBuf>> {
+    let mut archive = tar::Archive::new(MultiGzDecoder::new(reader));
+    let mut result = HashMap::new();
+
+    let extract_all = filenames.is_empty();
+    let filenames_set: std::collections::HashSet<&str> = filenames.iter().copied().collect();
+
+    for entry_result in archive.entries().context("Failed to read tar entries")? {
+        let mut entry = entry_result.context("Failed to read tar entry")?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry
+            .path()
+            .context("Failed to get entry path")?
+            .to_string_lossy()
+            .to_string();
+
+        // Extract the filename part only, which also keeps entries inside dest_dir
+        let filename = path.split('/').next_back().unwrap_or(&path).to_string();
+        if filename.is_empty() || !(extract_all || filenames_set.contains(filename.as_str())) {
+            continue;
+        }
+
+        let dest = dest_dir.join(&filename);
+        let mut file = BufWriter::new(
+            File::create(&dest).with_context(|| format!("Failed to create {}", dest.display()))?,
+        );
+        let written = std::io::copy(&mut entry, &mut file)
+            .with_context(|| format!("Failed to read tar entry: {}", filename))?;
+        file.flush()
+            .with_context(|| format!("Failed to write {}", dest.display()))?;
+        debug!("Extracted {} ({} bytes) to {}", filename, written, dest.display());
+        result.insert(filename, dest);
+    }
+
+    if !extract_all && result.len() < filenames.len() {
+        let missing: Vec<_> = filenames
+            .iter()
+            .filter(|f| !result.contains_key(**f))
+            .collect();
+        anyhow::bail!("Missing files in tar archive: {:?}", missing);
+    }
+
+    Ok(result)
+}
+
 /// Extract a single file from a tar.gz archive
 ///
 /// # Arguments
@@ -213,4 +300,36 @@ mod tests {
         let invalid = b"not gzip data";
         assert!(decompress_gzip(invalid).is_err());
     }
+
+    #[test]
+    fn test_decompress_gzip_to_file_reads_all_members() {
+        let mut compressed = create_gzip_data(b"first member\n");
+        compressed.extend(create_gzip_data(b"second member\n"));
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("data.txt");
+
+        let written = decompress_gzip_to_file(&compressed[..], &dest).unwrap();
+        assert_eq!(written, 27);
+        assert_eq!(std::fs::read(&dest).unwrap(), b"first member\nsecond member\n");
+    }
+
+    #[test]
+    fn test_extract_tar_gz_to_dir() {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (name, content) in [("dump/names.dmp", &b"names"[..]), ("dump/nodes.dmp", b"nodes")] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_cksum();
+            builder.append_data(&mut header, name, content).unwrap();
+        }
+        let archive = create_gzip_data(&builder.into_inner().unwrap());
+        let dir = tempfile::tempdir().unwrap();
+
+        let files = extract_tar_gz_to_dir(&archive[..], &["nodes.dmp"], dir.path()).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(std::fs::read(&files["nodes.dmp"]).unwrap(), b"nodes");
+        assert!(!dir.path().join("names.dmp").exists());
+
+        assert!(extract_tar_gz_to_dir(&archive[..], &["missing.dmp"], dir.path()).is_err());
+    }
 }
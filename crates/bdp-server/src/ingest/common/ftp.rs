@@ -19,11 +19,17 @@
 //! ```
 
 use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use std::fs::OpenOptions;
 use std::io::Read;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use suppaftp::FtpStream;
 use tracing::{debug, info, warn};
 
+use super::decompression::decompress_gzip_to_file;
+use super::transport::is_not_found;
+
 /// Maximum number of retry attempts for FTP operations
 pub const MAX_RETRIES: u32 = 3;
 
@@ -58,13 +64,16 @@ impl Default for FtpConfig {
     }
 }
 
-/// Result type for FTP download operations with timestamp
+/// Result type for download operations with timestamp
 pub struct DownloadResult {
     /// Downloaded file data
     pub data: Vec<u8>,
 
-    /// File modification timestamp from FTP server (if available)
-    pub timestamp: Option<String>,
+    /// File modification time reported by the server (if available)
+    pub timestamp: Option<DateTime<Utc>>,
+
+    /// Entity tag of the file, for conditional requests (HTTPS only)
+    pub etag: Option<String>,
 }
 
 /// Generic FTP client with retry logic
@@ -81,6 +90,11 @@ impl FtpClient {
         Self { config }
     }
 
+    /// Host and port of the FTP server
+    pub fn address(&self) -> String {
+        format!("{}:{}", self.config.host, self.config.port)
+    }
+
     /// Download a file from the FTP server with retry logic
     ///
     /// # Arguments
@@ -101,44 +115,65 @@ impl FtpClient {
     /// # Returns
     /// DownloadResult containing file data and optional timestamp
     pub async fn download_file_with_metadata(&self, path: &str) -> Result<DownloadResult> {
-        let config = self.config.clone();
-        let path = path.to_string();
-
-        for attempt in 1..=MAX_RETRIES {
-            debug!("Download attempt {}/{} for: {}", attempt, MAX_RETRIES, path);
+        self.download_file_if_modified(path, None)
+            .await?
+            .with_context(|| format!("{} was not downloaded", path))
+    }
 
-            match tokio::task::spawn_blocking({
-                let config = config.clone();
-                let path = path.clone();
-                move || Self::download_file_sync(&config, &path)
+    /// Download a file unless it hasn't changed since `modified_since`
+    ///
+    /// Compares the MDTM modification time with `modified_since` before
+    /// transferring anything. Returns `None` when the file is unchanged.
+    pub async fn download_file_if_modified(
+        &self,
+        path: &str,
+        modified_since: Option<DateTime<Utc>>,
+    ) -> Result<Option<DownloadResult>> {
+        let result = self
+            .with_retries("Download", path, move |config, path| {
+                Self::download_file_sync(config, path, modified_since)
             })
-            .await
-            {
-                Ok(Ok(result)) => {
-                    info!("Successfully downloaded {} ({} bytes)", path, result.data.len());
-                    return Ok(result);
-                },
-                Ok(Err(e)) => {
-                    if attempt < MAX_RETRIES {
-                        let delay = RETRY_DELAY_SECS * attempt as u64;
-                        warn!(
-                            "Download attempt {}/{} failed: {}. Retrying in {}s...",
-                            attempt, MAX_RETRIES, e, delay
-                        );
-                        tokio::time::sleep(Duration::from_secs(delay)).await;
-                    } else {
-                        return Err(e).with_context(|| {
-                            format!("Failed to download {} after {} attempts", path, MAX_RETRIES)
-                        });
-                    }
-                },
-                Err(e) => {
-                    return Err(anyhow::anyhow!("FTP download task panicked: {}", e));
-                },
-            }
+            .await?;
+        match &result {
+            Some(result) => {
+                info!("Successfully downloaded {} ({} bytes)", path, result.data.len())
+            },
+            None => info!("{} not modified since {:?}", path, modified_since),
         }
+        Ok(result)
+    }
 
-        unreachable!("Retry loop should always return")
+    /// Download a file to `dest`, resuming an interrupted transfer
+    ///
+    /// The file is written to `dest` with a `.part` suffix first; a partial
+    /// file left by an earlier attempt is continued with `REST`.
+    ///
+    /// # Returns
+    /// Size of the downloaded file
+    pub async fn download_to_file(&self, path: &str, dest: &Path) -> Result<u64> {
+        let dest = dest.to_path_buf();
+        let written = self
+            .with_retries("Download", path, move |config, path| {
+                Self::download_to_file_sync(config, path, &dest)
+            })
+            .await?;
+        info!("Successfully downloaded {} ({} bytes)", path, written);
+        Ok(written)
+    }
+
+    /// Download a gzipped file, decompressing it to `dest` while it streams in
+    ///
+    /// # Returns
+    /// Size of the decompressed file
+    pub async fn download_gzip_to_file(&self, path: &str, dest: &Path) -> Result<u64> {
+        let dest = dest.to_path_buf();
+        let written = self
+            .with_retries("Download", path, move |config, path| {
+                Self::download_gzip_to_file_sync(config, path, &dest)
+            })
+            .await?;
+        info!("Successfully downloaded {} ({} bytes decompressed)", path, written);
+        Ok(written)
     }
 
     /// List directory contents on the FTP server
@@ -149,39 +184,71 @@ impl FtpClient {
     /// # Returns
     /// Vector of parsed directory entries
     pub async fn list_directory(&self, path: &str) -> Result<Vec<FtpEntry>> {
-        let config = self.config.clone();
-        let path = path.to_string();
+        let entries = self
+            .with_retries("LIST", path, |config, path| Self::list_directory_sync(config, path))
+            .await?;
+        info!("Successfully listed {} ({} entries)", path, entries.len());
+        Ok(entries)
+    }
 
+    /// List only directories in a path
+    pub async fn list_directories(&self, path: &str) -> Result<Vec<String>> {
+        let entries = self.list_directory(path).await?;
+        Ok(entries
+            .into_iter()
+            .filter(|e| e.is_directory)
+            .map(|e| e.name)
+            .collect())
+    }
+
+    /// List only files in a path
+    pub async fn list_files(&self, path: &str) -> Result<Vec<String>> {
+        let entries = self.list_directory(path).await?;
+        Ok(entries
+            .into_iter()
+            .filter(|e| !e.is_directory)
+            .map(|e| e.name)
+            .collect())
+    }
+
+    /// Run a synchronous FTP operation on a blocking thread, retrying failures
+    async fn with_retries<T, F>(&self, operation: &str, path: &str, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: Fn(&FtpConfig, &str) -> Result<T> + Clone + Send + 'static,
+    {
         for attempt in 1..=MAX_RETRIES {
-            debug!("LIST attempt {}/{} for: {}", attempt, MAX_RETRIES, path);
+            debug!("{} attempt {}/{} for: {}", operation, attempt, MAX_RETRIES, path);
 
             match tokio::task::spawn_blocking({
-                let config = config.clone();
-                let path = path.clone();
-                move || Self::list_directory_sync(&config, &path)
+                let config = self.config.clone();
+                let path = path.to_string();
+                let f = f.clone();
+                move || f(&config, &path)
             })
             .await
             {
-                Ok(Ok(entries)) => {
-                    info!("Successfully listed {} ({} entries)", path, entries.len());
-                    return Ok(entries);
-                },
+                Ok(Ok(result)) => return Ok(result),
                 Ok(Err(e)) => {
-                    if attempt < MAX_RETRIES {
+                    // A missing file stays missing
+                    if attempt < MAX_RETRIES && !is_not_found(&e) {
                         let delay = RETRY_DELAY_SECS * attempt as u64;
                         warn!(
-                            "LIST attempt {}/{} failed: {}. Retrying in {}s...",
-                            attempt, MAX_RETRIES, e, delay
+                            "{} attempt {}/{} failed: {}. Retrying in {}s...",
+                            operation, attempt, MAX_RETRIES, e, delay
                         );
                         tokio::time::sleep(Duration::from_secs(delay)).await;
                     } else {
                         return Err(e).with_context(|| {
-                            format!("Failed to list {} after {} attempts", path, MAX_RETRIES)
+                            format!(
+                                "{} of {} failed after {} attempts",
+                                operation, path, MAX_RETRIES
+                            )
                         });
                     }
                 },
                 Err(e) => {
-                    return Err(anyhow::anyhow!("FTP LIST task panicked: {}", e));
+                    return Err(anyhow::anyhow!("FTP {} task panicked: {}", operation, e));
                 },
             }
         }
@@ -189,28 +256,8 @@ impl FtpClient {
         unreachable!("Retry loop should always return")
     }
 
-    /// List only directories in a path
-    pub async fn list_directories(&self, path: &str) -> Result<Vec<String>> {
-        let entries = self.list_directory(path).await?;
-        Ok(entries
-            .into_iter()
-            .filter(|e| e.is_directory)
-            .map(|e| e.name)
-            .collect())
-    }
-
-    /// List only files in a path
-    pub async fn list_files(&self, path: &str) -> Result<Vec<String>> {
-        let entries = self.list_directory(path).await?;
-        Ok(entries
-            .into_iter()
-            .filter(|e| !e.is_directory)
-            .map(|e| e.name)
-            .collect())
-    }
-
-    /// Synchronous FTP download implementation
-    fn download_file_sync(config: &FtpConfig, path: &str) -> Result<DownloadResult> {
+    /// Connect, log in and switch to binary transfers
+    fn connect(config: &FtpConfig) -> Result<FtpStream> {
         debug!("Connecting to FTP server: {}:{}", config.host, config.port);
 
         let mut ftp_stream = FtpStream::connect(format!("{}:{}", config.host, config.port))
@@ -228,8 +275,25 @@ impl FtpClient {
             .transfer_type(suppaftp::types::FileType::Binary)
             .context("Failed to set binary mode")?;
 
+        Ok(ftp_stream)
+    }
+
+    /// Synchronous FTP download implementation
+    fn download_file_sync(
+        config: &FtpConfig,
+        path: &str,
+        modified_since: Option<DateTime<Utc>>,
+    ) -> Result<Option<DownloadResult>> {
+        let mut ftp_stream = Self::connect(config)?;
+
         // Try to get modification time
-        let timestamp = ftp_stream.mdtm(path).ok().map(|dt| dt.to_string());
+        let timestamp = ftp_stream.mdtm(path).ok().map(|dt| dt.and_utc());
+        if let (Some(timestamp), Some(since)) = (timestamp, modified_since) {
+            if timestamp <= since {
+                let _ = ftp_stream.quit();
+                return Ok(None);
+            }
+        }
 
         debug!("Downloading file: {}", path);
         let mut reader = ftp_stream
@@ -247,22 +311,72 @@ impl FtpClient {
             warn!("Failed to quit FTP session gracefully: {}", e);
         }
 
-        Ok(DownloadResult { data, timestamp })
+        Ok(Some(DownloadResult {
+            data,
+            timestamp,
+            etag: None,
+        }))
     }
 
-    /// Synchronous FTP directory listing
-    fn list_directory_sync(config: &FtpConfig, path: &str) -> Result<Vec<FtpEntry>> {
-        debug!("Connecting to FTP server: {}:{}", config.host, config.port);
+    /// Synchronous resumable download into a file
+    fn download_to_file_sync(config: &FtpConfig, path: &str, dest: &Path) -> Result<u64> {
+        let partial = partial_path(dest);
+        let offset = std::fs::metadata(&partial).map(|m| m.len()).unwrap_or(0);
+
+        let mut ftp_stream = Self::connect(config)?;
+        if offset > 0 {
+            debug!("Resuming {} at byte {}", path, offset);
+            ftp_stream
+                .resume_transfer(offset as usize)
+                .with_context(|| format!("Failed to resume {} at byte {}", path, offset))?;
+        }
 
-        let mut ftp_stream = FtpStream::connect(format!("{}:{}", config.host, config.port))
-            .context("Failed to connect to FTP server")?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&partial)
+            .with_context(|| format!("Failed to open {}", partial.display()))?;
+        let mut stream = ftp_stream
+            .retr_as_stream(path)
+            .with_context(|| format!("Failed to download file: {}", path))?;
+        std::io::copy(&mut stream, &mut file)
+            .with_context(|| format!("Failed to write {}", partial.display()))?;
+        ftp_stream
+            .finalize_retr_stream(stream)
+            .with_context(|| format!("Failed to finish downloading {}", path))?;
 
-        ftp_stream.set_mode(suppaftp::Mode::ExtendedPassive);
+        if let Err(e) = ftp_stream.quit() {
+            warn!("Failed to quit FTP session gracefully: {}", e);
+        }
 
-        debug!("Logging in as: {}", config.username);
+        complete_partial(&partial, dest)
+    }
+
+    /// Synchronous streaming download, decompressing into a file
+    fn download_gzip_to_file_sync(config: &FtpConfig, path: &str, dest: &Path) -> Result<u64> {
+        let mut ftp_stream = Self::connect(config)?;
+
+        debug!("Streaming file: {} -> {}", path, dest.display());
+
+        let mut stream = ftp_stream
+            .retr_as_stream(path)
+            .with_context(|| format!("Failed to download file: {}", path))?;
+        let written = decompress_gzip_to_file(&mut stream, dest)
+            .with_context(|| format!("Failed to decompress {}", path))?;
         ftp_stream
-            .login(&config.username, &config.password)
-            .context("FTP login failed")?;
+            .finalize_retr_stream(stream)
+            .with_context(|| format!("Failed to finish downloading {}", path))?;
+
+        if let Err(e) = ftp_stream.quit() {
+            warn!("Failed to quit FTP session gracefully: {}", e);
+        }
+
+        Ok(written)
+    }
+
+    /// Synchronous FTP directory listing
+    fn list_directory_sync(config: &FtpConfig, path: &str) -> Result<Vec<FtpEntry>> {
+        let mut ftp_stream = Self::connect(config)?;
 
         debug!("Listing directory: {}", path);
         let entries = ftp_stream
@@ -282,6 +396,23 @@ impl FtpClient {
     }
 }
 
+/// Where a download to `dest` is written until it completes
+pub fn partial_path(dest: &Path) -> PathBuf {
+    let mut name = dest.as_os_str().to_owned();
+    name.push(".part");
+    PathBuf::from(name)
+}
+
+/// Move a completed download into place, returning its size
+pub fn complete_partial(partial: &Path, dest: &Path) -> Result<u64> {
+    let size = std::fs::metadata(partial)
+        .with_context(|| format!("Failed to read {}", partial.display()))?
+        .len();
+    std::fs::rename(partial, dest)
+        .with_context(|| format!("Failed to move download to {}", dest.display()))?;
+    Ok(size)
+}
+
 /// Parsed FTP directory entry
 #[derive(Debug, Clone)]
 pub struct FtpEntry {
@@ -293,6 +424,9 @@ pub struct FtpEntry {
 
     /// File size in bytes (if available)
     pub size: Option<u64>,
+
+    /// Last modification date (if available)
+    pub modified: Option<NaiveDate>,
 }
 
 impl FtpEntry {
@@ -317,14 +451,39 @@ impl FtpEntry {
             None
         };
 
+        // Month, day and year or time precede the name
+        let modified = if parts.len() >= 9 {
+            parse_list_date(parts[5], parts[6], parts[7])
+        } else {
+            None
+        };
+
         Some(Self {
             name,
             is_directory,
             size,
+            modified,
         })
     }
 }
 
+/// Date of a LIST line: `Jan 15 12:00` within the last six months, `Jan 15 2023`
+/// before that
+fn parse_list_date(month: &str, day: &str, year_or_time: &str) -> Option<NaiveDate> {
+    let parse = |year: i32| {
+        NaiveDate::parse_from_str(&format!("{} {} {}", year, month, day), "%Y %b %d").ok()
+    };
+    if !year_or_time.contains(':') {
+        return parse(year_or_time.parse().ok()?);
+    }
+
+    let today = Utc::now().date_naive();
+    match parse(today.year()) {
+        Some(date) if date <= today => Some(date),
+        _ => parse(today.year() - 1),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -345,6 +504,16 @@ mod tests {
         assert_eq!(entry.name, "data.txt");
         assert!(!entry.is_directory);
         assert_eq!(entry.size, Some(123456));
+        assert!(entry
+            .modified
+            .is_some_and(|date| date <= Utc::now().date_naive()));
+    }
+
+    #[test]
+    fn test_parse_entry_date() {
+        let entry =
+            FtpEntry::parse("drwxr-xr-x   2 ftp ftp  4096 Jul  4  2023 release-110").unwrap();
+        assert_eq!(entry.modified, NaiveDate::from_ymd_opt(2023, 7, 4));
     }
 
     #[test]
@@ -353,6 +522,12 @@ mod tests {
         assert!(FtpEntry::parse("   ").is_none());
     }
 
+    #[test]
+    fn test_partial_download_path() {
+        let partial = partial_path(Path::new("/tmp/cache/new_taxdump.tar.gz"));
+        assert_eq!(partial, PathBuf::from("/tmp/cache/new_taxdump.tar.gz.part"));
+    }
+
     #[test]
     fn test_ftp_config_default() {
         let config = FtpConfig::default();
@@ -2,10 +2,14 @@
 //!
 //! This module provides reusable components for data ingestion:
 //!
+//! - **bgzf**: Seekable reader for blocked gzip (BGZF) files
 //! - **ftp**: Shared FTP download utilities with retry logic
 //! - **decompression**: Common decompression helpers for gzip, tar, zip
+//! - **transport**: FTP, HTTPS and local-mirror access to upstream files
 //! - **version_discovery**: Generic version discovery trait and utilities
 
+pub mod bgzf;
 pub mod decompression;
 pub mod ftp;
+pub mod transport;
 pub mod version_discovery;
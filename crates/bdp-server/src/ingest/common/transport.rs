@@ -0,0 +1,775 @@
+//! Download transports for upstream sources
+//!
+//! Pipelines reach upstream files through a [`Transport`], so the same version
+//! discovery and download code runs against a provider's FTP server, its HTTPS
+//! site or a pre-staged directory on an air-gapped host:
+//!
+//! - [`FtpTransport`]: FTP with EPSV and retries, through [`FtpClient`]
+//! - [`HttpsTransport`]: HTTP(S) with conditional requests and resumable downloads
+//! - [`LocalTransport`]: a `file://` directory mirroring the upstream layout
+//!
+//! Paths are always upstream paths (`/pub/taxonomy/new_taxdump/...`). HTTPS and
+//! local transports resolve them against their base URL or root directory, so
+//! a mirror only has to keep the provider's directory layout.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use bdp_server::ingest::common::transport::TransportConfig;
+//!
+//! let transport = TransportConfig::parse("file:///srv/mirror/ncbi")?.build();
+//! let versions = transport.list_files("/pub/taxonomy/taxdump_archive").await?;
+//! ```
+
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use reqwest::{header, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::future::Future;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tracing::{debug, info, warn};
+
+use super::decompression::decompress_gzip_to_file;
+use super::ftp::{
+    complete_partial, partial_path, DownloadResult, FtpClient, FtpConfig, FtpEntry, MAX_RETRIES,
+    RETRY_DELAY_SECS,
+};
+
+/// Entry of a remote directory listing
+pub type RemoteEntry = FtpEntry;
+
+/// Validators of a previously downloaded copy of a file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FetchConditions {
+    /// Entity tag of the cached copy (HTTPS only)
+    pub etag: Option<String>,
+    /// Modification time of the cached copy
+    pub modified_since: Option<DateTime<Utc>>,
+}
+
+/// Outcome of a conditional fetch
+pub enum FetchOutcome {
+    /// The file changed, or no conditions were given
+    Fetched(DownloadResult),
+    /// The cached copy is still current
+    NotModified,
+}
+
+/// Access to an upstream file tree
+#[async_trait]
+pub trait Transport: Send + Sync {
+    /// Where the transport reads from, for logs
+    fn location(&self) -> String;
+
+    /// List a directory
+    async fn list_directory(&self, path: &str) -> Result<Vec<RemoteEntry>>;
+
+    /// Download a file into memory unless it matches `conditions`
+    async fn fetch(&self, path: &str, conditions: &FetchConditions) -> Result<FetchOutcome>;
+
+    /// Download a file to `dest`, resuming an interrupted download where supported
+    ///
+    /// Returns the size of the file.
+    async fn download_to(&self, path: &str, dest: &Path) -> Result<u64>;
+
+    /// Download a gzipped file and decompress it to `dest`
+    ///
+    /// Returns the size of the decompressed file. The default implementation
+    /// downloads the compressed file next to `dest` first.
+    async fn download_gzip_to(&self, path: &str, dest: &Path) -> Result<u64> {
+        let mut compressed = dest.as_os_str().to_owned();
+        compressed.push(".gz");
+        let compressed = PathBuf::from(compressed);
+
+        self.download_to(path, &compressed).await?;
+
+        let dest = dest.to_path_buf();
+        tokio::task::spawn_blocking(move || {
+            let file = File::open(&compressed)
+                .with_context(|| format!("Failed to open {}", compressed.display()))?;
+            let written = decompress_gzip_to_file(file, &dest)?;
+            std::fs::remove_file(&compressed)
+                .with_context(|| format!("Failed to remove {}", compressed.display()))?;
+            Ok(written)
+        })
+        .await
+        .context("Decompression task panicked")?
+    }
+
+    /// Download a file into memory
+    async fn download(&self, path: &str) -> Result<DownloadResult> {
+        match self.fetch(path, &FetchConditions::default()).await? {
+            FetchOutcome::Fetched(result) => Ok(result),
+            FetchOutcome::NotModified => {
+                bail!("{} returned no content for {}", self.location(), path)
+            },
+        }
+    }
+
+    /// Download a file into memory, or `None` when it doesn't exist
+    async fn download_if_exists(&self, path: &str) -> Result<Option<DownloadResult>> {
+        match self.download(path).await {
+            Ok(result) => Ok(Some(result)),
+            Err(e) if is_not_found(&e) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Names of the subdirectories of a directory
+    async fn list_directories(&self, path: &str) -> Result<Vec<String>> {
+        Ok(self
+            .list_directory(path)
+            .await?
+            .into_iter()
+            .filter(|e| e.is_directory)
+            .map(|e| e.name)
+            .collect())
+    }
+
+    /// Names of the files in a directory
+    async fn list_files(&self, path: &str) -> Result<Vec<String>> {
+        Ok(self
+            .list_directory(path)
+            .await?
+            .into_iter()
+            .filter(|e| !e.is_directory)
+            .map(|e| e.name)
+            .collect())
+    }
+}
+
+/// Transport selected for a source
+///
+/// Parsed from a URL: `ftp://host[:port]`, `https://host/base` (or `http://`)
+/// and `file:///path/to/mirror`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TransportConfig {
+    Ftp { host: String, port: u16 },
+    Https { base_url: String },
+    Local { root: PathBuf },
+}
+
+impl TransportConfig {
+    /// Parse a transport URL
+    pub fn parse(url: &str) -> Result<Self> {
+        let url = url.trim();
+        if let Some(address) = url.strip_prefix("ftp://") {
+            let address = address.trim_end_matches('/');
+            let (host, port) = match address.rsplit_once(':') {
+                Some((host, port)) => (
+                    host,
+                    port.parse()
+                        .with_context(|| format!("Invalid FTP port in '{}'", url))?,
+                ),
+                None => (address, 21),
+            };
+            if host.is_empty() || host.contains('/') {
+                bail!("Invalid FTP transport '{}': expected ftp://host[:port]", url);
+            }
+            return Ok(Self::Ftp {
+                host: host.to_string(),
+                port,
+            });
+        }
+        if url.starts_with("https://") || url.starts_with("http://") {
+            return Ok(Self::Https {
+                base_url: url.trim_end_matches('/').to_string(),
+            });
+        }
+        if let Some(root) = url.strip_prefix("file://") {
+            if !root.starts_with('/') {
+                bail!("Invalid local transport '{}': expected file:///absolute/path", url);
+            }
+            return Ok(Self::Local {
+                root: PathBuf::from(root),
+            });
+        }
+        bail!("Unsupported transport '{}': expected an ftp://, https:// or file:// URL", url)
+    }
+
+    /// Create the transport, logging in to FTP servers anonymously
+    pub fn build(&self) -> Arc<dyn Transport> {
+        match self {
+            Self::Ftp { host, port } => Arc::new(FtpTransport::new(FtpConfig {
+                host: host.clone(),
+                port: *port,
+                ..FtpConfig::default()
+            })),
+            Self::Https { base_url } => Arc::new(HttpsTransport::new(base_url.clone())),
+            Self::Local { root } => Arc::new(LocalTransport::new(root.clone())),
+        }
+    }
+}
+
+// ============================================================================
+// FTP
+// ============================================================================
+
+/// Transport over FTP
+pub struct FtpTransport {
+    client: FtpClient,
+}
+
+impl FtpTransport {
+    pub fn new(config: FtpConfig) -> Self {
+        Self {
+            client: FtpClient::new(config),
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for FtpTransport {
+    fn location(&self) -> String {
+        format!("ftp://{}", self.client.address())
+    }
+
+    async fn list_directory(&self, path: &str) -> Result<Vec<RemoteEntry>> {
+        self.client.list_directory(path).await
+    }
+
+    /// FTP has no entity tags; only the modification time is compared
+    async fn fetch(&self, path: &str, conditions: &FetchConditions) -> Result<FetchOutcome> {
+        Ok(
+            match self
+                .client
+                .download_file_if_modified(path, conditions.modified_since)
+                .await?
+            {
+                Some(result) => FetchOutcome::Fetched(result),
+                None => FetchOutcome::NotModified,
+            },
+        )
+    }
+
+    async fn download_to(&self, path: &str, dest: &Path) -> Result<u64> {
+        self.client.download_to_file(path, dest).await
+    }
+
+    async fn download_gzip_to(&self, path: &str, dest: &Path) -> Result<u64> {
+        self.client.download_gzip_to_file(path, dest).await
+    }
+}
+
+// ============================================================================
+// HTTPS
+// ============================================================================
+
+/// Transport over HTTP(S)
+///
+/// Directory listings are read from the server's HTML index pages, as served
+/// by the FTP-style HTTPS sites of NCBI, EBI and UniProt.
+pub struct HttpsTransport {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl HttpsTransport {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .user_agent(concat!("bdp-ingest/", env!("CARGO_PKG_VERSION")))
+                .connect_timeout(Duration::from_secs(30))
+                .build()
+                .unwrap_or_default(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.base_url, path.trim_start_matches('/'))
+    }
+
+    async fn fetch_once(&self, url: &str, conditions: &FetchConditions) -> Result<FetchOutcome> {
+        let mut request = self.client.get(url);
+        if let Some(etag) = &conditions.etag {
+            request = request.header(header::IF_NONE_MATCH, etag);
+        }
+        if let Some(since) = conditions.modified_since {
+            request = request.header(header::IF_MODIFIED_SINCE, http_date(since));
+        }
+
+        let response = request.send().await?;
+        if response.status() == StatusCode::NOT_MODIFIED {
+            return Ok(FetchOutcome::NotModified);
+        }
+        let response = response.error_for_status()?;
+
+        let etag = header_value(&response, header::ETAG);
+        let timestamp = header_value(&response, header::LAST_MODIFIED)
+            .and_then(|value| DateTime::parse_from_rfc2822(&value).ok())
+            .map(|dt| dt.with_timezone(&Utc));
+        let data = response.bytes().await?.to_vec();
+
+        Ok(FetchOutcome::Fetched(DownloadResult {
+            data,
+            timestamp,
+            etag,
+        }))
+    }
+
+    async fn download_to_once(&self, url: &str, dest: &Path) -> Result<u64> {
+        let partial = partial_path(dest);
+        let offset = tokio::fs::metadata(&partial)
+            .await
+            .map(|m| m.len())
+            .unwrap_or(0);
+
+        let mut request = self.client.get(url);
+        if offset > 0 {
+            debug!("Resuming {} at byte {}", url, offset);
+            request = request.header(header::RANGE, format!("bytes={}-", offset));
+        }
+
+        let mut response = request.send().await?;
+        // The partial file already holds the whole file
+        if offset > 0 && response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+            return complete_partial(&partial, dest);
+        }
+        response = response.error_for_status()?;
+
+        // Servers that ignore the range send the whole file again
+        let resumed = response.status() == StatusCode::PARTIAL_CONTENT;
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(resumed)
+            .truncate(!resumed)
+            .open(&partial)
+            .await
+            .with_context(|| format!("Failed to open {}", partial.display()))?;
+        while let Some(chunk) = response.chunk().await? {
+            file.write_all(&chunk).await?;
+        }
+        file.flush().await?;
+
+        complete_partial(&partial, dest)
+    }
+}
+
+#[async_trait]
+impl Transport for HttpsTransport {
+    fn location(&self) -> String {
+        self.base_url.clone()
+    }
+
+    async fn list_directory(&self, path: &str) -> Result<Vec<RemoteEntry>> {
+        let url = format!("{}/", self.url(path).trim_end_matches('/'));
+        let html = with_retries("LIST", &url, || async {
+            Ok(self
+                .client
+                .get(&url)
+                .send()
+                .await?
+                .error_for_status()?
+                .text()
+                .await?)
+        })
+        .await?;
+
+        let entries = parse_index_page(&html);
+        info!("Successfully listed {} ({} entries)", url, entries.len());
+        Ok(entries)
+    }
+
+    async fn fetch(&self, path: &str, conditions: &FetchConditions) -> Result<FetchOutcome> {
+        let url = self.url(path);
+        let outcome = with_retries("Download", &url, || self.fetch_once(&url, conditions)).await?;
+        if let FetchOutcome::Fetched(result) = &outcome {
+            info!("Successfully downloaded {} ({} bytes)", url, result.data.len());
+        }
+        Ok(outcome)
+    }
+
+    async fn download_to(&self, path: &str, dest: &Path) -> Result<u64> {
+        let url = self.url(path);
+        let written = with_retries("Download", &url, || self.download_to_once(&url, dest)).await?;
+        info!("Successfully downloaded {} ({} bytes)", url, written);
+        Ok(written)
+    }
+}
+
+/// Retry an HTTP operation, giving up at once on client errors such as 404
+async fn with_retries<T, F, Fut>(operation: &str, url: &str, f: F) -> Result<T>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    for attempt in 1..=MAX_RETRIES {
+        debug!("{} attempt {}/{} for: {}", operation, attempt, MAX_RETRIES, url);
+
+        match f().await {
+            Ok(result) => return Ok(result),
+            Err(e) if attempt < MAX_RETRIES && !is_client_error(&e) => {
+                let delay = RETRY_DELAY_SECS * attempt as u64;
+                warn!(
+                    "{} attempt {}/{} failed: {}. Retrying in {}s...",
+                    operation, attempt, MAX_RETRIES, e, delay
+                );
+                tokio::time::sleep(Duration::from_secs(delay)).await;
+            },
+            Err(e) => {
+                return Err(e).with_context(|| format!("{} of {} failed", operation, url));
+            },
+        }
+    }
+
+    unreachable!("Retry loop should always return")
+}
+
+/// Whether an error means the requested file doesn't exist
+///
+/// Recognises HTTP 404 and 410, FTP 550 and missing local files.
+pub fn is_not_found(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+            return e.status().is_some_and(|status| {
+                status == StatusCode::NOT_FOUND || status == StatusCode::GONE
+            });
+        }
+        if let Some(suppaftp::FtpError::UnexpectedResponse(response)) =
+            cause.downcast_ref::<suppaftp::FtpError>()
+        {
+            return response.status == suppaftp::Status::FileUnavailable;
+        }
+        cause
+            .downcast_ref::<std::io::Error>()
+            .is_some_and(|e| e.kind() == std::io::ErrorKind::NotFound)
+    })
+}
+
+fn is_client_error(error: &anyhow::Error) -> bool {
+    error
+        .chain()
+        .filter_map(|cause| cause.downcast_ref::<reqwest::Error>())
+        .any(|e| e.status().is_some_and(|status| status.is_client_error()))
+}
+
+fn header_value(response: &reqwest::Response, name: header::HeaderName) -> Option<String> {
+    response
+        .headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+/// Format a time as an HTTP date (`Wed, 15 Jan 2025 12:00:00 GMT`)
+fn http_date(time: DateTime<Utc>) -> String {
+    time.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+/// Parse the entries of an HTML directory index
+///
+/// Keeps relative links only, which skips sort links (`?C=M;O=A`), the parent
+/// directory and links off the index. Directories end with a slash. The date
+/// after a link is read as its modification date.
+pub fn parse_index_page(html: &str) -> Vec<RemoteEntry> {
+    let mut entries: Vec<RemoteEntry> = Vec::new();
+    for fragment in html.split("href=").skip(1) {
+        let Some(quote) = fragment.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+            continue;
+        };
+        let Some(link) = fragment[1..].split(quote).next() else {
+            continue;
+        };
+        if link.is_empty()
+            || link.starts_with(['?', '/', '#'])
+            || link.starts_with("..")
+            || link.contains("://")
+        {
+            continue;
+        }
+
+        let is_directory = link.ends_with('/');
+        let name = link.trim_end_matches('/').replace("%20", " ");
+        if name.contains('/') || entries.iter().any(|e| e.name == name) {
+            continue;
+        }
+        entries.push(RemoteEntry {
+            name,
+            is_directory,
+            size: None,
+            modified: fragment
+                .split_once("</a>")
+                .and_then(|(_, row)| row_date(row)),
+        });
+    }
+    entries
+}
+
+/// First date on the rest of an index row, as `2023-07-04 12:19` (Apache) or
+/// `04-Jul-2023 12:19` (nginx)
+fn row_date(row: &str) -> Option<NaiveDate> {
+    row.lines()
+        .next()?
+        .split(|c: char| c.is_whitespace() || c == '<' || c == '>')
+        .find_map(|word| {
+            NaiveDate::parse_from_str(word, "%Y-%m-%d")
+                .or_else(|_| NaiveDate::parse_from_str(word, "%d-%b-%Y"))
+                .ok()
+        })
+}
+
+// ============================================================================
+// Local directory
+// ============================================================================
+
+/// Transport reading a local mirror of the upstream file tree
+pub struct LocalTransport {
+    root: PathBuf,
+}
+
+impl LocalTransport {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Resolve an upstream path under the mirror root
+    fn resolve(&self, path: &str) -> Result<PathBuf> {
+        let relative = Path::new(path.trim_start_matches('/'));
+        if relative
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+        {
+            bail!("Path '{}' escapes the mirror at {}", path, self.root.display());
+        }
+        Ok(self.root.join(relative))
+    }
+}
+
+#[async_trait]
+impl Transport for LocalTransport {
+    fn location(&self) -> String {
+        format!("file://{}", self.root.display())
+    }
+
+    async fn list_directory(&self, path: &str) -> Result<Vec<RemoteEntry>> {
+        let dir = self.resolve(path)?;
+        let mut read_dir = tokio::fs::read_dir(&dir)
+            .await
+            .with_context(|| format!("Failed to list directory: {}", dir.display()))?;
+
+        let mut entries = Vec::new();
+        while let Some(entry) = read_dir.next_entry().await? {
+            let metadata = entry.metadata().await?;
+            entries.push(RemoteEntry {
+                name: entry.file_name().to_string_lossy().into_owned(),
+                is_directory: metadata.is_dir(),
+                size: metadata.is_file().then(|| metadata.len()),
+                modified: metadata
+                    .modified()
+                    .ok()
+                    .map(|time| DateTime::<Utc>::from(time).date_naive()),
+            });
+        }
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(entries)
+    }
+
+    /// Mirrors have no entity tags; only the modification time is compared
+    async fn fetch(&self, path: &str, conditions: &FetchConditions) -> Result<FetchOutcome> {
+        let file = self.resolve(path)?;
+        let timestamp: Option<DateTime<Utc>> = tokio::fs::metadata(&file)
+            .await
+            .with_context(|| format!("Failed to read file: {}", file.display()))?
+            .modified()
+            .ok()
+            .map(DateTime::from);
+
+        if let (Some(timestamp), Some(since)) = (timestamp, conditions.modified_since) {
+            if timestamp <= since {
+                return Ok(FetchOutcome::NotModified);
+            }
+        }
+
+        let data = tokio::fs::read(&file)
+            .await
+            .with_context(|| format!("Failed to read file: {}", file.display()))?;
+        Ok(FetchOutcome::Fetched(DownloadResult {
+            data,
+            timestamp,
+            etag: None,
+        }))
+    }
+
+    async fn download_to(&self, path: &str, dest: &Path) -> Result<u64> {
+        let file = self.resolve(path)?;
+        tokio::fs::copy(&file, dest)
+            .await
+            .with_context(|| format!("Failed to copy {} to {}", file.display(), dest.display()))
+    }
+
+    async fn download_gzip_to(&self, path: &str, dest: &Path) -> Result<u64> {
+        let file = self.resolve(path)?;
+        let dest = dest.to_path_buf();
+        tokio::task::spawn_blocking(move || {
+            let reader =
+                File::open(&file).with_context(|| format!("Failed to open {}", file.display()))?;
+            decompress_gzip_to_file(reader, &dest)
+        })
+        .await
+        .context("Decompression task panicked")?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{write::GzEncoder, Compression};
+    use std::io::Write;
+
+    #[test]
+    fn test_parse_transport_urls() {
+        assert_eq!(
+            TransportConfig::parse("ftp://ftp.ncbi.nlm.nih.gov").unwrap(),
+            TransportConfig::Ftp {
+                host: "ftp.ncbi.nlm.nih.gov".to_string(),
+                port: 21
+            }
+        );
+        assert_eq!(
+            TransportConfig::parse("ftp://localhost:2121/").unwrap(),
+            TransportConfig::Ftp {
+                host: "localhost".to_string(),
+                port: 2121
+            }
+        );
+        assert_eq!(
+            TransportConfig::parse("https://ftp.uniprot.org/").unwrap(),
+            TransportConfig::Https {
+                base_url: "https://ftp.uniprot.org".to_string()
+            }
+        );
+        assert_eq!(
+            TransportConfig::parse("file:///srv/mirror").unwrap(),
+            TransportConfig::Local {
+                root: PathBuf::from("/srv/mirror")
+            }
+        );
+        assert!(TransportConfig::parse("file://relative/path").is_err());
+        assert!(TransportConfig::parse("s3://bucket").is_err());
+    }
+
+    #[test]
+    fn test_parse_index_page() {
+        let html = r#"
+            <a href="?C=N;O=D">Name</a>
+            <a href="/pub/">Parent Directory</a>
+            <a href="../">../</a>
+            <a href="release-2024_01/">release-2024_01/</a>      2024-01-24 10:02    -
+            <a href='new_taxdump_2024-01-01.zip'>new_taxdump_2024-01-01.zip</a> 01-Jan-2024 04:10  140M
+            <a href="https://www.ncbi.nlm.nih.gov/">NCBI</a>
+            <a href="release-2024_01/">release-2024_01/</a>
+            <tr><td><a href="current/">current/</a></td><td align="right">2024-02-01 09:12</td></tr>
+        "#;
+
+        let entries = parse_index_page(html);
+        let names: Vec<(&str, bool)> = entries
+            .iter()
+            .map(|e| (e.name.as_str(), e.is_directory))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("release-2024_01", true),
+                ("new_taxdump_2024-01-01.zip", false),
+                ("current", true)
+            ]
+        );
+
+        let dates: Vec<_> = entries.iter().map(|e| e.modified).collect();
+        assert_eq!(
+            dates,
+            vec![
+                NaiveDate::from_ymd_opt(2024, 1, 24),
+                NaiveDate::from_ymd_opt(2024, 1, 1),
+                NaiveDate::from_ymd_opt(2024, 2, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_not_found_errors() {
+        let missing = std::fs::read("/nonexistent/file").unwrap_err();
+        assert!(is_not_found(&anyhow::Error::from(missing).context("Failed to read file")));
+
+        let unavailable = suppaftp::FtpError::UnexpectedResponse(suppaftp::types::Response::new(
+            suppaftp::Status::FileUnavailable,
+            b"550 No such file".to_vec(),
+        ));
+        assert!(is_not_found(&anyhow::Error::from(unavailable)));
+
+        assert!(!is_not_found(&anyhow::anyhow!("connection reset")));
+    }
+
+    #[test]
+    fn test_http_date() {
+        let time = DateTime::parse_from_rfc3339("2025-01-15T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(http_date(time), "Wed, 15 Jan 2025 12:00:00 GMT");
+    }
+
+    #[tokio::test]
+    async fn test_local_transport() {
+        let mirror = tempfile::tempdir().unwrap();
+        let dir = mirror.path().join("pub/taxonomy/taxdump_archive");
+        std::fs::create_dir_all(dir.join("old")).unwrap();
+        std::fs::write(dir.join("new_taxdump_2024-01-01.zip"), b"zip").unwrap();
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"rankedlineage").unwrap();
+        std::fs::write(dir.join("data.gz"), encoder.finish().unwrap()).unwrap();
+
+        let transport = TransportConfig::parse(&format!("file://{}", mirror.path().display()))
+            .unwrap()
+            .build();
+
+        let files = transport
+            .list_files("/pub/taxonomy/taxdump_archive")
+            .await
+            .unwrap();
+        assert_eq!(files, vec!["data.gz", "new_taxdump_2024-01-01.zip"]);
+        assert_eq!(
+            transport
+                .list_directories("/pub/taxonomy/taxdump_archive")
+                .await
+                .unwrap(),
+            vec!["old"]
+        );
+
+        let path = "/pub/taxonomy/taxdump_archive/new_taxdump_2024-01-01.zip";
+        let result = transport.download(path).await.unwrap();
+        assert_eq!(result.data, b"zip");
+
+        let conditions = FetchConditions {
+            etag: None,
+            modified_since: result.timestamp,
+        };
+        assert!(matches!(
+            transport.fetch(path, &conditions).await.unwrap(),
+            FetchOutcome::NotModified
+        ));
+
+        let dest = mirror.path().join("rankedlineage.dmp");
+        let written = transport
+            .download_gzip_to("/pub/taxonomy/taxdump_archive/data.gz", &dest)
+            .await
+            .unwrap();
+        assert_eq!(written, 13);
+        assert_eq!(std::fs::read(&dest).unwrap(), b"rankedlineage");
+
+        assert!(transport
+            .download_if_exists("/pub/taxonomy/missing.zip")
+            .await
+            .unwrap()
+            .is_none());
+        assert!(transport.download("/pub/../../etc/passwd").await.is_err());
+    }
+}
@@ -5,6 +5,9 @@
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
+use super::common::transport::TransportConfig;
+use super::sources::IngestSource;
+
 // ============================================================================
 // Ingestion Configuration Constants
 // ============================================================================
@@ -135,6 +138,122 @@ pub struct IngestConfig {
     pub job_timeout_secs: u64,
     /// UniProt-specific configuration
     pub uniprot: UniProtConfig,
+    /// Cron schedule of each source pipeline
+    pub schedules: Vec<SourceScheduleConfig>,
+    /// Transport overrides; sources without one use their upstream server
+    pub transports: Vec<SourceTransportConfig>,
+}
+
+/// Transport for one source's upstream files
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SourceTransportConfig {
+    pub source: IngestSource,
+    pub transport: TransportConfig,
+}
+
+impl SourceTransportConfig {
+    /// Load from `INGEST_<SOURCE>_TRANSPORT`, an `ftp://host[:port]`,
+    /// `https://host/base` or `file:///path/to/mirror` URL
+    ///
+    /// The local mirror settings of Ensembl, RefSeq assemblies and structures
+    /// are read as `file://` transports when no transport is set.
+    pub fn from_env(source: IngestSource) -> anyhow::Result<Option<Self>> {
+        let transport_var = format!("INGEST_{}_TRANSPORT", source.env_prefix());
+        let mirror = local_mirror_var(source)
+            .and_then(|var| Some((var.to_string(), format!("file://{}", env_value(var)?))));
+        let Some((var, url)) = env_value(&transport_var)
+            .map(|url| (transport_var, url))
+            .or(mirror)
+        else {
+            return Ok(None);
+        };
+
+        let transport = TransportConfig::parse(&url)
+            .map_err(|e| anyhow::anyhow!("{} is invalid: {}", var, e))?;
+        Ok(Some(Self { source, transport }))
+    }
+}
+
+/// Local mirror setting of a source
+fn local_mirror_var(source: IngestSource) -> Option<&'static str> {
+    match source {
+        IngestSource::Ensembl => Some("INGEST_ENSEMBL_LOCAL_MIRROR"),
+        IngestSource::Refseq => Some("INGEST_REFSEQ_ASSEMBLY_LOCAL_MIRROR"),
+        IngestSource::Pdb | IngestSource::Alphafold => Some("INGEST_STRUCTURE_LOCAL_MIRROR"),
+        _ => None,
+    }
+}
+
+fn env_value(var: &str) -> Option<String> {
+    std::env::var(var)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+}
+
+/// Cron schedule for one source pipeline
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SourceScheduleConfig {
+    pub source: IngestSource,
+    /// Whether the scheduler runs this source
+    pub enabled: bool,
+    /// Cron expression, with or without a leading seconds field
+    pub schedule: String,
+    /// Minimum time between two scheduled runs in seconds (0 = cron only)
+    pub check_interval_secs: u64,
+}
+
+impl SourceScheduleConfig {
+    /// Disabled schedule with the default cron expression
+    pub fn new(source: IngestSource) -> Self {
+        Self {
+            source,
+            enabled: false,
+            schedule: DEFAULT_INGESTION_SCHEDULE.to_string(),
+            check_interval_secs: 0,
+        }
+    }
+
+    /// Load from `INGEST_<SOURCE>_AUTO_ENABLED`, `INGEST_<SOURCE>_SCHEDULE`
+    /// and `INGEST_<SOURCE>_CHECK_INTERVAL_SECS`
+    ///
+    /// UniProt keeps its existing settings: `INGEST_UNIPROT_AUTO_ENABLED`,
+    /// `INGEST_UNIPROT_SCHEDULE` and, in latest mode, the check interval of
+    /// [`LatestConfig`].
+    pub fn from_env(source: IngestSource, uniprot: &UniProtConfig) -> Self {
+        if source == IngestSource::Uniprot {
+            let check_interval_secs = match &uniprot.ingestion_mode {
+                IngestionMode::Latest(latest) => latest.check_interval_secs,
+                IngestionMode::Historical(_) => 0,
+            };
+            return Self {
+                source,
+                enabled: uniprot.auto_ingest_enabled,
+                schedule: uniprot.ingestion_schedule.clone(),
+                check_interval_secs,
+            };
+        }
+
+        let prefix = source.env_prefix();
+        let defaults = Self::new(source);
+        Self {
+            source,
+            enabled: std::env::var(format!("INGEST_{}_AUTO_ENABLED", prefix))
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.enabled),
+            schedule: std::env::var(format!("INGEST_{}_SCHEDULE", prefix))
+                .unwrap_or(defaults.schedule),
+            check_interval_secs: std::env::var(format!("INGEST_{}_CHECK_INTERVAL_SECS", prefix))
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.check_interval_secs),
+        }
+    }
+
+    /// Minimum time between two scheduled runs
+    pub fn check_interval(&self) -> Duration {
+        Duration::from_secs(self.check_interval_secs)
+    }
 }
 
 /// UniProt-specific ingestion configuration
@@ -168,6 +287,16 @@ pub struct UniProtConfig {
 impl IngestConfig {
     /// Load ingestion configuration from environment variables
     pub fn from_env() -> anyhow::Result<Self> {
+        let uniprot = UniProtConfig::from_env()?;
+        let schedules = IngestSource::ALL
+            .into_iter()
+            .map(|source| SourceScheduleConfig::from_env(source, &uniprot))
+            .collect();
+        let mut transports = Vec::new();
+        for source in IngestSource::ALL {
+            transports.extend(SourceTransportConfig::from_env(source)?);
+        }
+
         let config = Self {
             enabled: std::env::var("INGEST_ENABLED")
                 .unwrap_or_else(|_| "false".to_string())
@@ -185,7 +314,9 @@ impl IngestConfig {
                 .ok()
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(DEFAULT_JOB_TIMEOUT_SECS),
-            uniprot: UniProtConfig::from_env()?,
+            uniprot,
+            schedules,
+            transports,
         };
 
         config.validate()?;
@@ -203,9 +334,31 @@ impl IngestConfig {
             }
         }
         self.uniprot.validate()?;
+        for schedule in self.schedules.iter().filter(|s| s.enabled) {
+            super::scheduler::parse_schedule(&schedule.schedule).map_err(|e| {
+                anyhow::anyhow!(
+                    "INGEST_{}_SCHEDULE is not a valid cron expression: {}",
+                    schedule.source.env_prefix(),
+                    e
+                )
+            })?;
+        }
         Ok(())
     }
 
+    /// Schedules of the sources the scheduler should run
+    pub fn enabled_schedules(&self) -> impl Iterator<Item = &SourceScheduleConfig> {
+        self.schedules.iter().filter(|s| s.enabled)
+    }
+
+    /// Transport configured for a source, if it doesn't use its default
+    pub fn transport(&self, source: IngestSource) -> Option<&TransportConfig> {
+        self.transports
+            .iter()
+            .find(|t| t.source == source)
+            .map(|t| &t.transport)
+    }
+
     /// Get job timeout as Duration
     pub fn job_timeout(&self) -> Duration {
         Duration::from_secs(self.job_timeout_secs)
@@ -354,6 +507,11 @@ impl Default for IngestConfig {
             max_retries: DEFAULT_MAX_RETRIES,
             job_timeout_secs: DEFAULT_JOB_TIMEOUT_SECS,
             uniprot: UniProtConfig::default(),
+            schedules: IngestSource::ALL
+                .into_iter()
+                .map(SourceScheduleConfig::new)
+                .collect(),
+            transports: Vec::new(),
         }
     }
 }
@@ -451,6 +609,73 @@ mod tests {
         assert_eq!(config.job_timeout(), Duration::from_secs(1800));
     }
 
+    #[test]
+    fn test_default_schedules() {
+        let config = IngestConfig::default();
+        assert_eq!(config.schedules.len(), IngestSource::ALL.len());
+        assert_eq!(config.enabled_schedules().count(), 0);
+        assert!(config
+            .schedules
+            .iter()
+            .all(|s| s.schedule == DEFAULT_INGESTION_SCHEDULE));
+    }
+
+    #[test]
+    fn test_uniprot_schedule_honours_check_interval() {
+        let uniprot = UniProtConfig {
+            auto_ingest_enabled: true,
+            ingestion_schedule: "0 */6 * * *".to_string(),
+            ingestion_mode: IngestionMode::Latest(LatestConfig {
+                check_interval_secs: 43200,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let schedule = SourceScheduleConfig::from_env(IngestSource::Uniprot, &uniprot);
+        assert!(schedule.enabled);
+        assert_eq!(schedule.schedule, "0 */6 * * *");
+        assert_eq!(schedule.check_interval(), Duration::from_secs(43200));
+    }
+
+    #[test]
+    fn test_ingest_config_validation_invalid_schedule() {
+        let mut config = IngestConfig::default();
+        config.schedules[1].enabled = true;
+        config.schedules[1].schedule = "every day".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_source_transports() {
+        let mut config = IngestConfig::default();
+        assert!(config.transport(IngestSource::NcbiTaxonomy).is_none());
+
+        config.transports.push(SourceTransportConfig {
+            source: IngestSource::NcbiTaxonomy,
+            transport: TransportConfig::parse("file:///srv/mirror/ncbi").unwrap(),
+        });
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.transport(IngestSource::NcbiTaxonomy),
+            Some(&TransportConfig::Local {
+                root: "/srv/mirror/ncbi".into()
+            })
+        );
+
+        config.transports.push(SourceTransportConfig {
+            source: IngestSource::Genbank,
+            transport: TransportConfig::parse("https://ftp.ncbi.nlm.nih.gov").unwrap(),
+        });
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.transport(IngestSource::Genbank),
+            Some(&TransportConfig::Https {
+                base_url: "https://ftp.ncbi.nlm.nih.gov".to_string()
+            })
+        );
+        assert!(config.transport(IngestSource::Ensembl).is_none());
+    }
+
     #[test]
     fn test_ftp_timeout_duration() {
         let config = UniProtConfig {
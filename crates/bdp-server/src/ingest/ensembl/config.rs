@@ -0,0 +1,233 @@
+// Ensembl configuration
+//
+// Environment-based configuration for Ensembl ingestion. Files are read from
+// the Ensembl FTP site over HTTPS, or through the configured transport with
+// the same directory layout (/pub/release-N/gtf/<species>/..., /pub/release-N/fasta/...).
+
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::sync::Arc;
+
+use crate::ingest::common::transport::{HttpsTransport, Transport, TransportConfig};
+
+/// Configuration for Ensembl downloads and ingestion
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnsemblConfig {
+    /// Base URL of the Ensembl FTP site (default: https://ftp.ensembl.org)
+    pub base_url: String,
+
+    /// Transport to read upstream files through (None for HTTPS to `base_url`)
+    #[serde(default)]
+    pub transport: Option<TransportConfig>,
+
+    /// Species to ingest, by Ensembl production name (e.g., "homo_sapiens")
+    pub species: Vec<String>,
+
+    /// Batch size for database operations
+    pub batch_size: usize,
+
+    /// Parse limit for testing (None = parse all genes)
+    pub parse_limit: Option<usize>,
+}
+
+impl Default for EnsemblConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "https://ftp.ensembl.org".to_string(),
+            transport: None,
+            species: vec!["homo_sapiens".to_string(), "mus_musculus".to_string()],
+            batch_size: 500,
+            parse_limit: None,
+        }
+    }
+}
+
+impl EnsemblConfig {
+    /// Create new configuration with defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load configuration from environment variables
+    ///
+    /// Environment variables:
+    /// - INGEST_ENSEMBL_BASE_URL
+    /// - INGEST_ENSEMBL_SPECIES (comma-separated production names)
+    /// - INGEST_ENSEMBL_BATCH_SIZE
+    ///
+    /// The transport (including `INGEST_ENSEMBL_LOCAL_MIRROR`) is read by
+    /// [`IngestConfig`](crate::ingest::config::IngestConfig).
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+
+        Self {
+            base_url: env::var("INGEST_ENSEMBL_BASE_URL").unwrap_or(defaults.base_url),
+
+            transport: None,
+
+            species: env::var("INGEST_ENSEMBL_SPECIES")
+                .ok()
+                .map(|list| parse_species_list(&list))
+                .filter(|species| !species.is_empty())
+                .unwrap_or(defaults.species),
+
+            batch_size: env::var("INGEST_ENSEMBL_BATCH_SIZE")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.batch_size),
+
+            parse_limit: None,
+        }
+    }
+
+    /// Read upstream files through another transport
+    pub fn with_transport(mut self, transport: TransportConfig) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Transport for upstream files
+    pub fn transport(&self) -> Arc<dyn Transport> {
+        match &self.transport {
+            Some(transport) => transport.build(),
+            None => Arc::new(HttpsTransport::new(self.base_url.clone())),
+        }
+    }
+
+    /// Set the species to ingest
+    pub fn with_species<S: Into<String>>(mut self, species: impl IntoIterator<Item = S>) -> Self {
+        self.species = species.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set parse limit for testing
+    pub fn with_parse_limit(mut self, limit: usize) -> Self {
+        self.parse_limit = Some(limit);
+        self
+    }
+
+    /// Set batch size
+    pub fn with_batch_size(mut self, size: usize) -> Self {
+        self.batch_size = size;
+        self
+    }
+
+    /// Directory holding the releases
+    pub const RELEASES_DIR: &'static str = "/pub";
+
+    /// Directory of a release (e.g., "/pub/release-110")
+    pub fn release_dir(release: u32) -> String {
+        format!("{}/release-{}", Self::RELEASES_DIR, release)
+    }
+
+    /// Species list of a release, with taxonomy IDs and assemblies
+    pub fn species_list_path(release: u32) -> String {
+        format!("{}/species_EnsemblVertebrates.txt", Self::release_dir(release))
+    }
+
+    /// Directory holding a species' GTF gene set
+    pub fn gtf_dir(release: u32, species: &str) -> String {
+        format!("{}/gtf/{}", Self::release_dir(release), species)
+    }
+
+    /// Directory holding a species' GFF3 gene set
+    pub fn gff3_dir(release: u32, species: &str) -> String {
+        format!("{}/gff3/{}", Self::release_dir(release), species)
+    }
+
+    /// Directory holding a species' cDNA FASTA
+    pub fn cdna_dir(release: u32, species: &str) -> String {
+        format!("{}/fasta/{}/cdna", Self::release_dir(release), species)
+    }
+
+    /// Directory holding a species' peptide FASTA
+    pub fn pep_dir(release: u32, species: &str) -> String {
+        format!("{}/fasta/{}/pep", Self::release_dir(release), species)
+    }
+
+    /// Validate configuration
+    pub fn validate(&self) -> Result<(), String> {
+        if self.transport.is_none() && self.base_url.is_empty() {
+            return Err("Base URL cannot be empty without a transport".to_string());
+        }
+
+        if self.species.is_empty() {
+            return Err("At least one species must be configured".to_string());
+        }
+
+        if self.batch_size == 0 {
+            return Err("Batch size must be greater than 0".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_species_list(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(|species| species.trim().to_lowercase())
+        .filter(|species| !species.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_release_paths() {
+        assert_eq!(
+            EnsemblConfig::species_list_path(110),
+            "/pub/release-110/species_EnsemblVertebrates.txt"
+        );
+        assert_eq!(
+            EnsemblConfig::gtf_dir(110, "homo_sapiens"),
+            "/pub/release-110/gtf/homo_sapiens"
+        );
+        assert_eq!(
+            EnsemblConfig::gff3_dir(110, "homo_sapiens"),
+            "/pub/release-110/gff3/homo_sapiens"
+        );
+        assert_eq!(
+            EnsemblConfig::cdna_dir(110, "homo_sapiens"),
+            "/pub/release-110/fasta/homo_sapiens/cdna"
+        );
+        assert_eq!(
+            EnsemblConfig::pep_dir(110, "homo_sapiens"),
+            "/pub/release-110/fasta/homo_sapiens/pep"
+        );
+    }
+
+    #[test]
+    fn test_parse_species_list() {
+        assert_eq!(
+            parse_species_list(" Homo_sapiens, mus_musculus,,"),
+            vec!["homo_sapiens", "mus_musculus"]
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(EnsemblConfig::default().validate().is_ok());
+        assert!(EnsemblConfig::default()
+            .with_species(Vec::<String>::new())
+            .validate()
+            .is_err());
+        assert!(EnsemblConfig::default()
+            .with_batch_size(0)
+            .validate()
+            .is_err());
+
+        let config = EnsemblConfig {
+            base_url: String::new(),
+            ..EnsemblConfig::default()
+        };
+        assert!(config.validate().is_err());
+        assert!(config
+            .with_transport(TransportConfig::Local {
+                root: "/srv/mirror/ensembl".into(),
+            })
+            .validate()
+            .is_ok());
+    }
+}
@@ -0,0 +1,19 @@
+// Ensembl ingestion module
+//
+// This module handles ingestion of Ensembl gene sets, one data source per species.
+// Each Ensembl release becomes a version of the species gene set.
+// Storage: S3 for release files (GTF, GFF3, FASTA), PostgreSQL for genes and transcripts
+
+pub mod config;
+pub mod models;
+pub mod parser;
+pub mod pipeline;
+pub mod storage;
+pub mod version_discovery;
+
+pub use config::EnsemblConfig;
+pub use models::{EnsemblSpecies, GeneModel, GeneModelFormat, Transcript};
+pub use parser::{GeneModelParser, GeneSet};
+pub use pipeline::EnsemblPipeline;
+pub use storage::EnsemblStorage;
+pub use version_discovery::{DiscoveredVersion, VersionDiscovery};
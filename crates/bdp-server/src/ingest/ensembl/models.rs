@@ -0,0 +1,243 @@
+// Data models for Ensembl gene sets
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// A species of an Ensembl release, from `species_EnsemblVertebrates.txt`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnsemblSpecies {
+    /// Production name (e.g., "homo_sapiens")
+    pub name: String,
+    /// Display name (e.g., "Human")
+    pub display_name: String,
+    /// Ensembl division (e.g., "EnsemblVertebrates")
+    pub division: String,
+    /// NCBI Taxonomy ID
+    pub taxonomy_id: i32,
+    /// Assembly name (e.g., "GRCh38.p14")
+    pub assembly: String,
+    /// INSDC assembly accession (e.g., "GCA_000001405.29")
+    pub assembly_accession: Option<String>,
+    /// Gene build identifier
+    pub genebuild: Option<String>,
+}
+
+impl EnsemblSpecies {
+    /// Scientific name derived from the production name ("homo_sapiens" → "Homo sapiens")
+    pub fn scientific_name(&self) -> String {
+        let name = self.name.replace('_', " ");
+        let mut chars = name.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => name,
+        }
+    }
+
+    /// Registry slug of the species gene set
+    pub fn slug(&self) -> String {
+        format!("ensembl-{}", self.name.replace('_', "-"))
+    }
+
+    /// Parse the tab-separated species list of a release
+    ///
+    /// Columns: name, species, division, taxonomy_id, assembly,
+    /// assembly_accession, genebuild, then flags that are ignored.
+    pub fn parse_list(content: &str) -> Result<Vec<EnsemblSpecies>> {
+        content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+            .map(|(index, line)| {
+                let fields: Vec<&str> = line.split('\t').collect();
+                if fields.len() < 5 {
+                    anyhow::bail!(
+                        "Line {}: expected at least 5 columns, found {}",
+                        index + 1,
+                        fields.len()
+                    );
+                }
+                let optional = |i: usize| {
+                    fields
+                        .get(i)
+                        .map(|value| value.trim())
+                        .filter(|value| !value.is_empty())
+                        .map(str::to_string)
+                };
+
+                Ok(EnsemblSpecies {
+                    display_name: fields[0].trim().to_string(),
+                    name: fields[1].trim().to_string(),
+                    division: fields[2].trim().to_string(),
+                    taxonomy_id: fields[3].trim().parse().with_context(|| {
+                        format!("Line {}: invalid taxonomy ID '{}'", index + 1, fields[3])
+                    })?,
+                    assembly: fields[4].trim().to_string(),
+                    assembly_accession: optional(5),
+                    genebuild: optional(6),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Gene set file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GeneModelFormat {
+    Gtf,
+    Gff3,
+}
+
+impl GeneModelFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GeneModelFormat::Gtf => "gtf",
+            GeneModelFormat::Gff3 => "gff3",
+        }
+    }
+}
+
+/// A gene and its transcripts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GeneModel {
+    /// Stable ID (e.g., "ENSG00000139618")
+    pub gene_id: String,
+    pub gene_version: Option<i32>,
+    pub gene_name: Option<String>,
+    pub biotype: Option<String>,
+    /// Sequence region (chromosome or scaffold)
+    pub seqid: String,
+    /// 1-based inclusive start
+    pub start: i64,
+    /// 1-based inclusive end
+    pub end: i64,
+    /// '+', '-' or '.'
+    pub strand: char,
+    pub transcripts: Vec<Transcript>,
+}
+
+/// A transcript of a gene
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Transcript {
+    /// Stable ID (e.g., "ENST00000380152")
+    pub transcript_id: String,
+    pub transcript_version: Option<i32>,
+    pub transcript_name: Option<String>,
+    pub biotype: Option<String>,
+    pub start: i64,
+    pub end: i64,
+    /// Exon coordinates in file order
+    pub exons: Vec<(i64, i64)>,
+    /// Translation stable ID for coding transcripts
+    pub protein_id: Option<String>,
+}
+
+impl GeneModel {
+    /// SHA-256 of the gene and transcript coordinates
+    ///
+    /// Changes when a gene moves or its transcript structure changes.
+    pub fn location_checksum(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(format!("{}:{}-{}:{}", self.seqid, self.start, self.end, self.strand));
+        for transcript in self.sorted_transcripts() {
+            hasher.update(format!(
+                "|{}:{}-{}",
+                transcript.transcript_id, transcript.start, transcript.end
+            ));
+            let mut exons = transcript.exons.clone();
+            exons.sort_unstable();
+            for (start, end) in exons {
+                hasher.update(format!(",{}-{}", start, end));
+            }
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// SHA-256 of the gene and transcript names, biotypes and versions
+    pub fn annotation_checksum(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(format!("{:?}|{:?}|{:?}", self.gene_version, self.gene_name, self.biotype));
+        for transcript in self.sorted_transcripts() {
+            hasher.update(format!(
+                "|{}|{:?}|{:?}|{:?}|{:?}",
+                transcript.transcript_id,
+                transcript.transcript_version,
+                transcript.transcript_name,
+                transcript.biotype,
+                transcript.protein_id
+            ));
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
+    fn sorted_transcripts(&self) -> Vec<&Transcript> {
+        let mut transcripts: Vec<_> = self.transcripts.iter().collect();
+        transcripts.sort_by(|a, b| a.transcript_id.cmp(&b.transcript_id));
+        transcripts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPECIES_LIST: &str = "#name\tspecies\tdivision\ttaxonomy_id\tassembly\tassembly_accession\tgenebuild\tvariation\n\
+Human\thomo_sapiens\tEnsemblVertebrates\t9606\tGRCh38.p14\tGCA_000001405.29\t2014-01-Ensembl/2023-03\tY\n\
+Mouse\tmus_musculus\tEnsemblVertebrates\t10090\tGRCm39\t\t\tY\n";
+
+    fn gene() -> GeneModel {
+        GeneModel {
+            gene_id: "ENSG00000139618".to_string(),
+            gene_version: Some(17),
+            gene_name: Some("BRCA2".to_string()),
+            biotype: Some("protein_coding".to_string()),
+            seqid: "13".to_string(),
+            start: 32315508,
+            end: 32400268,
+            strand: '+',
+            transcripts: vec![Transcript {
+                transcript_id: "ENST00000380152".to_string(),
+                transcript_version: Some(8),
+                transcript_name: Some("BRCA2-201".to_string()),
+                biotype: Some("protein_coding".to_string()),
+                start: 32315508,
+                end: 32400268,
+                exons: vec![(32315508, 32315667), (32316422, 32316527)],
+                protein_id: Some("ENSP00000369497".to_string()),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_parse_species_list() {
+        let species = EnsemblSpecies::parse_list(SPECIES_LIST).unwrap();
+        assert_eq!(species.len(), 2);
+        assert_eq!(species[0].name, "homo_sapiens");
+        assert_eq!(species[0].taxonomy_id, 9606);
+        assert_eq!(species[0].assembly_accession.as_deref(), Some("GCA_000001405.29"));
+        assert_eq!(species[0].scientific_name(), "Homo sapiens");
+        assert_eq!(species[0].slug(), "ensembl-homo-sapiens");
+        assert_eq!(species[1].assembly_accession, None);
+
+        assert!(EnsemblSpecies::parse_list(
+            "Human\thomo_sapiens\tEnsemblVertebrates\thuman\tGRCh38\n"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_checksums_separate_location_and_annotation() {
+        let original = gene();
+
+        let mut renamed = gene();
+        renamed.gene_name = Some("FANCD1".to_string());
+        assert_eq!(renamed.location_checksum(), original.location_checksum());
+        assert_ne!(renamed.annotation_checksum(), original.annotation_checksum());
+
+        let mut moved = gene();
+        moved.transcripts[0].exons[1].1 += 3;
+        assert_ne!(moved.location_checksum(), original.location_checksum());
+        assert_eq!(moved.annotation_checksum(), original.annotation_checksum());
+    }
+}
@@ -0,0 +1,366 @@
+// Ensembl GTF/GFF3 gene set parser
+//
+// Assembles gene, transcript, exon and CDS lines into genes with their
+// transcripts. Features other than those are skipped.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+
+use super::models::{GeneModel, GeneModelFormat, Transcript};
+use crate::ingest::framework::gff::{GffDirective, GffReader};
+use crate::ingest::framework::{GffFeature, GffFormat};
+
+/// Genes parsed from a gene set file
+#[derive(Debug, Clone, Default)]
+pub struct GeneSet {
+    /// Assembly from the `#!genome-build` header
+    pub genome_build: Option<String>,
+    pub genes: Vec<GeneModel>,
+}
+
+impl GeneSet {
+    /// Total number of transcripts
+    pub fn transcript_count(&self) -> usize {
+        self.genes.iter().map(|gene| gene.transcripts.len()).sum()
+    }
+}
+
+/// Parser for Ensembl GTF and GFF3 gene sets
+pub struct GeneModelParser {
+    parse_limit: Option<usize>,
+}
+
+impl GeneModelParser {
+    pub fn new() -> Self {
+        Self { parse_limit: None }
+    }
+
+    /// Keep only the first `limit` genes
+    pub fn with_limit(limit: usize) -> Self {
+        Self {
+            parse_limit: Some(limit),
+        }
+    }
+
+    /// Parse a decompressed gene set file
+    pub fn parse(&self, content: &str, format: GeneModelFormat) -> Result<GeneSet> {
+        let mut builder = GeneSetBuilder::default();
+        let gff_format = match format {
+            GeneModelFormat::Gtf => GffFormat::Gtf,
+            GeneModelFormat::Gff3 => GffFormat::Gff3,
+        };
+        let mut reader = GffReader::new(content.as_bytes(), gff_format);
+
+        while let Some((line_number, feature)) = reader.next_feature()? {
+            let result = match format {
+                GeneModelFormat::Gtf => builder.add_gtf_feature(&feature),
+                GeneModelFormat::Gff3 => builder.add_gff3_feature(&feature),
+            };
+            result.with_context(|| {
+                format!(
+                    "Line {}: {} {}:{}-{}",
+                    line_number, feature.feature_type, feature.seqid, feature.start, feature.end
+                )
+            })?;
+        }
+
+        builder.genome_build = reader
+            .directives()
+            .iter()
+            .find_map(|directive| match directive {
+                GffDirective::GenomeBuild(build) => Some(build.clone()),
+                _ => None,
+            });
+        let mut gene_set = builder.build();
+        if let Some(limit) = self.parse_limit {
+            gene_set.genes.truncate(limit);
+        }
+        Ok(gene_set)
+    }
+}
+
+impl Default for GeneModelParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Location of a feature as stored on genes and transcripts
+fn location(feature: &GffFeature) -> (i64, i64, char) {
+    let strand = match feature.strand {
+        '?' => '.',
+        strand => strand,
+    };
+    (feature.start as i64, feature.end as i64, strand)
+}
+
+#[derive(Default)]
+struct GeneSetBuilder {
+    genome_build: Option<String>,
+    genes: Vec<GeneModel>,
+    gene_index: HashMap<String, usize>,
+    /// transcript ID → (gene index, transcript index)
+    transcript_index: HashMap<String, (usize, usize)>,
+}
+
+impl GeneSetBuilder {
+    fn add_gtf_feature(&mut self, feature: &GffFeature) -> Result<()> {
+        let get = |key: &str| feature.first_attribute(key);
+        let (start, end, strand) = location(feature);
+
+        match feature.feature_type.as_str() {
+            "gene" => {
+                let gene_id = get("gene_id").context("Gene without gene_id")?;
+                self.add_gene(GeneModel {
+                    gene_id,
+                    gene_version: parse_version(get("gene_version"))?,
+                    gene_name: get("gene_name"),
+                    biotype: get("gene_biotype").or_else(|| get("gene_type")),
+                    seqid: feature.seqid.clone(),
+                    start,
+                    end,
+                    strand,
+                    transcripts: Vec::new(),
+                })
+            },
+            "transcript" => {
+                let gene_id = get("gene_id").context("Transcript without gene_id")?;
+                let transcript_id =
+                    get("transcript_id").context("Transcript without transcript_id")?;
+                self.add_transcript(
+                    &gene_id,
+                    Transcript {
+                        transcript_id,
+                        transcript_version: parse_version(get("transcript_version"))?,
+                        transcript_name: get("transcript_name"),
+                        biotype: get("transcript_biotype").or_else(|| get("transcript_type")),
+                        start,
+                        end,
+                        exons: Vec::new(),
+                        protein_id: None,
+                    },
+                )
+            },
+            "exon" => {
+                let transcript_id = get("transcript_id").context("Exon without transcript_id")?;
+                self.transcript_mut(&transcript_id)?
+                    .exons
+                    .push((start, end));
+                Ok(())
+            },
+            "CDS" => {
+                let transcript_id = get("transcript_id").context("CDS without transcript_id")?;
+                if let Some(protein_id) = get("protein_id") {
+                    self.transcript_mut(&transcript_id)?.protein_id = Some(protein_id);
+                }
+                Ok(())
+            },
+            _ => Ok(()),
+        }
+    }
+
+    fn add_gff3_feature(&mut self, feature: &GffFeature) -> Result<()> {
+        let get = |key: &str| feature.first_attribute(key);
+        let (start, end, strand) = location(feature);
+        let id = feature.id.as_deref();
+
+        if let Some(gene_id) = id.and_then(|id| id.strip_prefix("gene:")) {
+            return self.add_gene(GeneModel {
+                gene_id: gene_id.to_string(),
+                gene_version: parse_version(get("version"))?,
+                gene_name: feature.name.clone(),
+                biotype: get("biotype"),
+                seqid: feature.seqid.clone(),
+                start,
+                end,
+                strand,
+                transcripts: Vec::new(),
+            });
+        }
+
+        if let Some(transcript_id) = id.and_then(|id| id.strip_prefix("transcript:")) {
+            let parent = feature
+                .parents
+                .first()
+                .context("Transcript without Parent")?;
+            let gene_id = parent
+                .strip_prefix("gene:")
+                .with_context(|| format!("Transcript parent '{}' is not a gene", parent))?;
+            return self.add_transcript(
+                gene_id,
+                Transcript {
+                    transcript_id: transcript_id.to_string(),
+                    transcript_version: parse_version(get("version"))?,
+                    transcript_name: feature.name.clone(),
+                    biotype: get("biotype"),
+                    start,
+                    end,
+                    exons: Vec::new(),
+                    protein_id: None,
+                },
+            );
+        }
+
+        match feature.feature_type.as_str() {
+            "exon" | "CDS" => {
+                let parent = feature
+                    .parents
+                    .first()
+                    .with_context(|| format!("{} without Parent", feature.feature_type))?;
+                let transcript_id = parent.strip_prefix("transcript:").with_context(|| {
+                    format!("{} parent '{}' is not a transcript", feature.feature_type, parent)
+                })?;
+                let transcript = self.transcript_mut(transcript_id)?;
+                if feature.feature_type == "exon" {
+                    transcript.exons.push((start, end));
+                } else if let Some(protein_id) = get("protein_id") {
+                    transcript.protein_id = Some(protein_id);
+                }
+                Ok(())
+            },
+            _ => Ok(()),
+        }
+    }
+
+    fn add_gene(&mut self, gene: GeneModel) -> Result<()> {
+        if self.gene_index.contains_key(&gene.gene_id) {
+            anyhow::bail!("Duplicate gene {}", gene.gene_id);
+        }
+        self.gene_index
+            .insert(gene.gene_id.clone(), self.genes.len());
+        self.genes.push(gene);
+        Ok(())
+    }
+
+    fn add_transcript(&mut self, gene_id: &str, transcript: Transcript) -> Result<()> {
+        if self
+            .transcript_index
+            .contains_key(&transcript.transcript_id)
+        {
+            anyhow::bail!("Duplicate transcript {}", transcript.transcript_id);
+        }
+        let gene_index = *self.gene_index.get(gene_id).with_context(|| {
+            format!("Transcript {} of unknown gene {}", transcript.transcript_id, gene_id)
+        })?;
+        let transcripts = &mut self.genes[gene_index].transcripts;
+        self.transcript_index
+            .insert(transcript.transcript_id.clone(), (gene_index, transcripts.len()));
+        transcripts.push(transcript);
+        Ok(())
+    }
+
+    fn transcript_mut(&mut self, transcript_id: &str) -> Result<&mut Transcript> {
+        let (gene_index, transcript_index) = *self
+            .transcript_index
+            .get(transcript_id)
+            .with_context(|| format!("Feature of unknown transcript {}", transcript_id))?;
+        Ok(&mut self.genes[gene_index].transcripts[transcript_index])
+    }
+
+    fn build(self) -> GeneSet {
+        GeneSet {
+            genome_build: self.genome_build,
+            genes: self.genes,
+        }
+    }
+}
+
+fn parse_version(value: Option<String>) -> Result<Option<i32>> {
+    value
+        .map(|v| {
+            v.parse()
+                .with_context(|| format!("Invalid version '{}'", v))
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GTF: &str = "#!genome-build GRCh38.p14
+#!genome-version GRCh38
+13\tensembl_havana\tgene\t32315508\t32400268\t.\t+\t.\tgene_id \"ENSG00000139618\"; gene_version \"17\"; gene_name \"BRCA2\"; gene_source \"ensembl_havana\"; gene_biotype \"protein_coding\";
+13\tensembl_havana\ttranscript\t32315508\t32400268\t.\t+\t.\tgene_id \"ENSG00000139618\"; gene_version \"17\"; transcript_id \"ENST00000380152\"; transcript_version \"8\"; gene_name \"BRCA2\"; transcript_name \"BRCA2-201\"; transcript_biotype \"protein_coding\"; tag \"basic\"; tag \"Ensembl_canonical\";
+13\tensembl_havana\texon\t32315508\t32315667\t.\t+\t.\tgene_id \"ENSG00000139618\"; transcript_id \"ENST00000380152\"; exon_number \"1\";
+13\tensembl_havana\tCDS\t32316461\t32316527\t.\t+\t0\tgene_id \"ENSG00000139618\"; transcript_id \"ENST00000380152\"; protein_id \"ENSP00000369497\"; protein_version \"3\";
+13\tensembl_havana\texon\t32316422\t32316527\t.\t+\t.\tgene_id \"ENSG00000139618\"; transcript_id \"ENST00000380152\"; exon_number \"2\";
+13\tensembl_havana\tstart_codon\t32316461\t32316463\t.\t+\t0\tgene_id \"ENSG00000139618\"; transcript_id \"ENST00000380152\";
+";
+
+    const GFF3: &str = "##gff-version 3
+##sequence-region   13 1 114364328
+#!genome-build GRCh38.p14
+13\tGRCh38\tchromosome\t1\t114364328\t.\t.\t.\tID=chromosome:13;Alias=CM000675.2
+13\tensembl_havana\tgene\t32315508\t32400268\t.\t+\t.\tID=gene:ENSG00000139618;Name=BRCA2;biotype=protein_coding;description=BRCA2 DNA repair associated %5BSource:HGNC%5D;gene_id=ENSG00000139618;version=17
+13\tensembl_havana\tmRNA\t32315508\t32400268\t.\t+\t.\tID=transcript:ENST00000380152;Parent=gene:ENSG00000139618;Name=BRCA2-201;biotype=protein_coding;transcript_id=ENST00000380152;version=8
+13\tensembl_havana\texon\t32315508\t32315667\t.\t+\t.\tParent=transcript:ENST00000380152;Name=ENSE00001484009;exon_id=ENSE00001484009
+13\tensembl_havana\tCDS\t32316461\t32316527\t.\t+\t0\tID=CDS:ENSP00000369497;Parent=transcript:ENST00000380152;protein_id=ENSP00000369497
+13\tensembl_havana\texon\t32316422\t32316527\t.\t+\t.\tParent=transcript:ENST00000380152;Name=ENSE00003659301;exon_id=ENSE00003659301
+13\thavana\tncRNA_gene\t32400000\t32400500\t.\t-\t.\tID=gene:ENSG00000283000;biotype=lncRNA;gene_id=ENSG00000283000;version=1
+###
+";
+
+    #[test]
+    fn test_parse_gtf() {
+        let gene_set = GeneModelParser::new()
+            .parse(GTF, GeneModelFormat::Gtf)
+            .unwrap();
+
+        assert_eq!(gene_set.genome_build.as_deref(), Some("GRCh38.p14"));
+        assert_eq!(gene_set.genes.len(), 1);
+        let gene = &gene_set.genes[0];
+        assert_eq!(gene.gene_id, "ENSG00000139618");
+        assert_eq!(gene.gene_version, Some(17));
+        assert_eq!(gene.gene_name.as_deref(), Some("BRCA2"));
+        assert_eq!(gene.biotype.as_deref(), Some("protein_coding"));
+        assert_eq!(
+            (gene.seqid.as_str(), gene.start, gene.end, gene.strand),
+            ("13", 32315508, 32400268, '+')
+        );
+
+        let transcript = &gene.transcripts[0];
+        assert_eq!(transcript.transcript_name.as_deref(), Some("BRCA2-201"));
+        assert_eq!(transcript.exons, vec![(32315508, 32315667), (32316422, 32316527)]);
+        assert_eq!(transcript.protein_id.as_deref(), Some("ENSP00000369497"));
+    }
+
+    #[test]
+    fn test_gtf_and_gff3_agree() {
+        let gtf = GeneModelParser::new()
+            .parse(GTF, GeneModelFormat::Gtf)
+            .unwrap();
+        let gff3 = GeneModelParser::new()
+            .parse(GFF3, GeneModelFormat::Gff3)
+            .unwrap();
+
+        assert_eq!(gff3.genes.len(), 2);
+        assert_eq!(gff3.genes[0], gtf.genes[0]);
+        assert_eq!(gff3.transcript_count(), 1);
+        assert_eq!(gff3.genes[1].strand, '-');
+        assert_eq!(gff3.genes[1].gene_name, None);
+    }
+
+    #[test]
+    fn test_parse_limit() {
+        let gene_set = GeneModelParser::with_limit(1)
+            .parse(GFF3, GeneModelFormat::Gff3)
+            .unwrap();
+        assert_eq!(gene_set.genes.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_errors_name_the_line() {
+        let orphan = "13\tensembl\texon\t1\t10\t.\t+\t.\tgene_id \"G\"; transcript_id \"T\";\n";
+        let error = GeneModelParser::new()
+            .parse(orphan, GeneModelFormat::Gtf)
+            .unwrap_err();
+        assert!(error.to_string().starts_with("Line 1:"));
+        assert!(format!("{:#}", error).contains("unknown transcript T"));
+
+        let reversed = "13\tensembl\tgene\t10\t1\t.\t+\t.\tgene_id \"G\";\n";
+        assert!(GeneModelParser::new()
+            .parse(reversed, GeneModelFormat::Gtf)
+            .is_err());
+    }
+}
@@ -0,0 +1,561 @@
+// Ensembl ingestion pipeline
+//
+// Ingests one Ensembl release: for each configured species, the gene set
+// (GTF, falling back to GFF3) is parsed into genes and transcripts, the
+// release files are uploaded to S3 and a new version of the species data
+// source is created with a changelog from the Ensembl bump detector.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::config::EnsemblConfig;
+use super::models::{EnsemblSpecies, GeneModelFormat};
+use super::parser::{GeneModelParser, GeneSet};
+use super::storage::{EnsemblStorage, ReleaseFile};
+use crate::ingest::common::decompression::decompress_gzip;
+use crate::ingest::common::transport::Transport;
+use crate::ingest::framework::{BatchConfig, CreateJobParams, IngestionCoordinator};
+use crate::ingest::versioning::{
+    calculate_next_version, get_latest_version, get_latest_version_id, save_changelog,
+    EnsemblBumpDetector, VersionBumpDetector,
+};
+use crate::storage::Storage;
+
+/// A file of a species release on the FTP site
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseFileSource {
+    /// Format recorded on the version file ("gtf", "gff3", "cdna", "pep")
+    pub format: String,
+    /// Upstream path (e.g., "/pub/release-110/gtf/...")
+    pub path: String,
+    pub filename: String,
+    /// Published bytes (gzipped)
+    pub data: Vec<u8>,
+}
+
+/// A species release loaded from the FTP site or mirror
+#[derive(Debug, Clone)]
+pub struct SpeciesRelease {
+    pub species: EnsemblSpecies,
+    /// Format the gene set was parsed from
+    pub format: GeneModelFormat,
+    pub gene_set: GeneSet,
+    pub files: Vec<ReleaseFileSource>,
+}
+
+/// Outcome of ingesting one species
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeciesResult {
+    pub species: String,
+    /// Internal version created, or None when the release was already ingested
+    pub version: Option<String>,
+    pub genes: usize,
+    pub transcripts: usize,
+}
+
+/// Outcome of ingesting one release
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseResult {
+    pub release: u32,
+    pub species: Vec<SpeciesResult>,
+    pub duration_seconds: f64,
+}
+
+pub struct EnsemblPipeline {
+    config: EnsemblConfig,
+    db: PgPool,
+    s3: Storage,
+    organization_id: Uuid,
+}
+
+impl EnsemblPipeline {
+    /// Create a new pipeline
+    pub fn new(config: EnsemblConfig, db: PgPool, s3: Storage, organization_id: Uuid) -> Self {
+        Self {
+            config,
+            db,
+            s3,
+            organization_id,
+        }
+    }
+
+    /// Ingest the configured species of a release
+    pub async fn run_release(&self, release: u32) -> Result<ReleaseResult> {
+        self.config
+            .validate()
+            .map_err(|e| anyhow::anyhow!("Invalid Ensembl configuration: {}", e))?;
+
+        let transport = self.config.transport();
+        let coordinator =
+            IngestionCoordinator::new(Arc::new(self.db.clone()), BatchConfig::default());
+
+        let job_id = coordinator
+            .create_job(CreateJobParams {
+                organization_id: self.organization_id,
+                job_type: "ensembl_gene_models".to_string(),
+                external_version: release.to_string(),
+                internal_version: "1.0".to_string(),
+                source_url: Some(format!(
+                    "{}{}",
+                    transport.location(),
+                    EnsemblConfig::release_dir(release)
+                )),
+                source_metadata: Some(serde_json::json!({
+                    "species": self.config.species,
+                })),
+                total_records: None,
+            })
+            .await
+            .context("Failed to create ingestion job")?;
+
+        match self
+            .execute(&coordinator, job_id, transport.as_ref(), release)
+            .await
+        {
+            Ok(result) => {
+                coordinator.complete_job(job_id).await?;
+                Ok(result)
+            },
+            Err(e) => {
+                coordinator
+                    .fail_job(job_id, &e.to_string())
+                    .await
+                    .context("Failed to mark job as failed")?;
+                Err(e)
+            },
+        }
+    }
+
+    async fn execute(
+        &self,
+        coordinator: &IngestionCoordinator,
+        job_id: Uuid,
+        transport: &dyn Transport,
+        release: u32,
+    ) -> Result<ReleaseResult> {
+        let start_time = Instant::now();
+        info!(release, species = ?self.config.species, "Starting Ensembl ingestion");
+
+        coordinator.start_download(job_id).await?;
+
+        let species_list = load_species(transport, release).await?;
+        let selected: Vec<EnsemblSpecies> = self
+            .config
+            .species
+            .iter()
+            .filter_map(|name| {
+                let species = species_list.iter().find(|s| &s.name == name).cloned();
+                if species.is_none() {
+                    warn!(release, species = %name, "Species not in Ensembl release");
+                }
+                species
+            })
+            .collect();
+        if selected.is_empty() {
+            anyhow::bail!("None of the configured species are in Ensembl release {}", release);
+        }
+
+        let storage = EnsemblStorage::new(self.db.clone(), self.organization_id, release)
+            .with_batch_size(self.config.batch_size);
+        storage.setup_citations().await?;
+
+        coordinator.start_storing(job_id).await?;
+
+        let mut results = Vec::with_capacity(selected.len());
+        for species in selected {
+            coordinator.ensure_not_cancelled(job_id).await?;
+            let result = self
+                .ingest_species(&storage, transport, release, species)
+                .await?;
+            results.push(result);
+        }
+
+        let duration = start_time.elapsed();
+        info!(
+            release,
+            species = results.len(),
+            "Ensembl ingestion complete in {:.2}s",
+            duration.as_secs_f64()
+        );
+
+        Ok(ReleaseResult {
+            release,
+            species: results,
+            duration_seconds: duration.as_secs_f64(),
+        })
+    }
+
+    async fn ingest_species(
+        &self,
+        storage: &EnsemblStorage,
+        transport: &dyn Transport,
+        release: u32,
+        species: EnsemblSpecies,
+    ) -> Result<SpeciesResult> {
+        let parser = match self.config.parse_limit {
+            Some(limit) => GeneModelParser::with_limit(limit),
+            None => GeneModelParser::new(),
+        };
+        let loaded = load_species_release(transport, &parser, release, species).await?;
+        let species = &loaded.species;
+
+        let data_source_id = storage
+            .get_or_create_species_source(species, loaded.gene_set.genome_build.as_deref())
+            .await?;
+
+        // Gene release tracking assumes releases are ingested in order
+        if let Some(latest) = latest_ingested_release(&self.db, data_source_id).await? {
+            if latest >= release {
+                info!(
+                    species = %species.name,
+                    release,
+                    latest,
+                    "Ensembl release already ingested, skipping"
+                );
+                return Ok(SpeciesResult {
+                    species: species.name.clone(),
+                    version: None,
+                    genes: 0,
+                    transcripts: 0,
+                });
+            }
+        }
+
+        let previous_version_id = get_latest_version_id(&self.db, data_source_id).await?;
+
+        storage
+            .store_gene_models(data_source_id, &loaded.gene_set.genes)
+            .await?;
+
+        let changelog = EnsemblBumpDetector::new()
+            .detect_changes(&self.db, data_source_id, previous_version_id)
+            .await
+            .context("Failed to detect version changes")?;
+
+        let version = match get_latest_version(&self.db, data_source_id).await? {
+            Some(latest) => calculate_next_version(&latest, changelog.bump_type),
+            None => "1.0".to_string(),
+        };
+
+        let slug = species.slug();
+        let mut files = Vec::with_capacity(loaded.files.len());
+        for file in &loaded.files {
+            let key = self
+                .s3
+                .build_key("ensembl", &slug, &version, &file.filename);
+            let upload = self
+                .s3
+                .upload(&key, file.data.clone(), Some("application/gzip".to_string()))
+                .await
+                .with_context(|| format!("Failed to upload {}", file.filename))?;
+            files.push(ReleaseFile {
+                format: file.format.clone(),
+                s3_key: upload.key,
+                sha256: upload.checksum,
+                size_bytes: upload.size,
+            });
+        }
+
+        let version_id = storage
+            .create_release_version(data_source_id, &version, &files)
+            .await?;
+        save_changelog(&self.db, version_id, &changelog)
+            .await
+            .context("Failed to save changelog")?;
+
+        info!(
+            species = %species.name,
+            release,
+            version = %version,
+            bump_type = ?changelog.bump_type,
+            genes = loaded.gene_set.genes.len(),
+            "Ingested Ensembl species release"
+        );
+
+        Ok(SpeciesResult {
+            species: species.name.clone(),
+            version: Some(version),
+            genes: loaded.gene_set.genes.len(),
+            transcripts: loaded.gene_set.transcript_count(),
+        })
+    }
+}
+
+/// Species list of a release
+pub async fn load_species(transport: &dyn Transport, release: u32) -> Result<Vec<EnsemblSpecies>> {
+    let path = EnsemblConfig::species_list_path(release);
+    let data = transport
+        .download(&path)
+        .await
+        .with_context(|| format!("Failed to fetch species list of Ensembl release {}", release))?
+        .data;
+    let content =
+        String::from_utf8(data).with_context(|| format!("{} is not valid UTF-8", path))?;
+    EnsemblSpecies::parse_list(&content)
+}
+
+/// Locate, fetch and parse the files of a species release
+///
+/// The gene set is parsed from the GTF file, or from the GFF3 file when the
+/// release has no GTF for the species. FASTA files are optional.
+pub async fn load_species_release(
+    transport: &dyn Transport,
+    parser: &GeneModelParser,
+    release: u32,
+    species: EnsemblSpecies,
+) -> Result<SpeciesRelease> {
+    let gtf = find_file(transport, &EnsemblConfig::gtf_dir(release, &species.name), |name| {
+        is_gene_set_file(name, release, "gtf")
+    })
+    .await?;
+    let gff3 = find_file(transport, &EnsemblConfig::gff3_dir(release, &species.name), |name| {
+        is_gene_set_file(name, release, "gff3")
+    })
+    .await?;
+    let cdna = find_file(transport, &EnsemblConfig::cdna_dir(release, &species.name), |name| {
+        name.ends_with(".cdna.all.fa.gz")
+    })
+    .await?;
+    let pep = find_file(transport, &EnsemblConfig::pep_dir(release, &species.name), |name| {
+        name.ends_with(".pep.all.fa.gz")
+    })
+    .await?;
+
+    let candidates = [("gtf", gtf), ("gff3", gff3), ("cdna", cdna), ("pep", pep)];
+    let mut files = Vec::new();
+    for (format, path) in candidates {
+        let Some(path) = path else { continue };
+        let data = transport.download(&path).await?.data;
+        let filename = path.rsplit('/').next().unwrap_or(&path).to_string();
+        files.push(ReleaseFileSource {
+            format: format.to_string(),
+            path,
+            filename,
+            data,
+        });
+    }
+
+    let (format, file) = [GeneModelFormat::Gtf, GeneModelFormat::Gff3]
+        .into_iter()
+        .find_map(|format| {
+            files
+                .iter()
+                .find(|f| f.format == format.as_str())
+                .map(|file| (format, file))
+        })
+        .with_context(|| {
+            format!("No GTF or GFF3 gene set for {} in Ensembl release {}", species.name, release)
+        })?;
+
+    let content = decompress_gzip(&file.data)
+        .with_context(|| format!("Failed to decompress {}", file.path))?;
+    let content =
+        String::from_utf8(content).with_context(|| format!("{} is not valid UTF-8", file.path))?;
+    let gene_set = parser
+        .parse(&content, format)
+        .with_context(|| format!("Failed to parse {}", file.path))?;
+
+    Ok(SpeciesRelease {
+        species,
+        format,
+        gene_set,
+        files,
+    })
+}
+
+/// Path of the first file in a directory matching a predicate, if any
+async fn find_file(
+    transport: &dyn Transport,
+    dir: &str,
+    matches: impl Fn(&str) -> bool,
+) -> Result<Option<String>> {
+    let entries = match transport.list_directory(dir).await {
+        Ok(entries) => entries,
+        Err(e) => {
+            warn!(dir = %dir, error = %e, "Ensembl directory not available");
+            return Ok(None);
+        },
+    };
+
+    Ok(entries
+        .into_iter()
+        .find(|entry| !entry.is_directory && matches(&entry.name))
+        .map(|entry| format!("{}/{}", dir, entry.name)))
+}
+
+/// Whether a file is the primary gene set of a release
+///
+/// Ensembl also publishes per-chromosome, ab initio and patch/haplotype
+/// variants next to it (e.g., `Homo_sapiens.GRCh38.110.chr.gtf.gz`).
+fn is_gene_set_file(name: &str, release: u32, extension: &str) -> bool {
+    name.ends_with(&format!(".{}.{}.gz", release, extension))
+        && ![".chr.", ".abinitio.", ".chr_patch_hapl_scaff."]
+            .iter()
+            .any(|variant| name.contains(variant))
+}
+
+/// Newest Ensembl release ingested for a species data source
+async fn latest_ingested_release(pool: &PgPool, data_source_id: Uuid) -> Result<Option<u32>> {
+    let releases: Vec<Option<String>> =
+        sqlx::query_scalar("SELECT external_version FROM versions WHERE entry_id = $1")
+            .bind(data_source_id)
+            .fetch_all(pool)
+            .await
+            .context("Failed to get ingested Ensembl releases")?;
+
+    Ok(releases
+        .into_iter()
+        .flatten()
+        .filter_map(|release| release.parse().ok())
+        .max())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::common::transport::TransportConfig;
+    use crate::ingest::ensembl::version_discovery::VersionDiscovery;
+    use std::path::PathBuf;
+
+    fn fixture_mirror() -> Arc<dyn Transport> {
+        EnsemblConfig::new()
+            .with_transport(TransportConfig::Local {
+                root: PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/ensembl"),
+            })
+            .transport()
+    }
+
+    #[test]
+    fn test_is_gene_set_file() {
+        assert!(is_gene_set_file("Homo_sapiens.GRCh38.110.gtf.gz", 110, "gtf"));
+        assert!(!is_gene_set_file("Homo_sapiens.GRCh38.110.chr.gtf.gz", 110, "gtf"));
+        assert!(!is_gene_set_file("Homo_sapiens.GRCh38.110.abinitio.gtf.gz", 110, "gtf"));
+        assert!(!is_gene_set_file(
+            "Homo_sapiens.GRCh38.110.chr_patch_hapl_scaff.gff3.gz",
+            110,
+            "gff3"
+        ));
+        assert!(!is_gene_set_file("Homo_sapiens.GRCh38.111.gtf.gz", 110, "gtf"));
+    }
+
+    #[tokio::test]
+    async fn test_load_species_release_from_mirror() {
+        let mirror = fixture_mirror();
+        let species = load_species(mirror.as_ref(), 110).await.unwrap();
+        let human = species
+            .into_iter()
+            .find(|s| s.name == "homo_sapiens")
+            .unwrap();
+
+        let loaded = load_species_release(mirror.as_ref(), &GeneModelParser::new(), 110, human)
+            .await
+            .unwrap();
+        assert_eq!(loaded.format, GeneModelFormat::Gtf);
+        assert_eq!(loaded.gene_set.genome_build.as_deref(), Some("GRCh38"));
+        assert_eq!(loaded.gene_set.genes.len(), 3);
+
+        let formats: Vec<_> = loaded.files.iter().map(|f| f.format.as_str()).collect();
+        assert_eq!(formats, vec!["gtf", "gff3", "cdna", "pep"]);
+    }
+
+    #[tokio::test]
+    async fn test_load_species_release_falls_back_to_gff3() {
+        let mirror = fixture_mirror();
+        let species = load_species(mirror.as_ref(), 110).await.unwrap();
+        let mouse = species
+            .into_iter()
+            .find(|s| s.name == "mus_musculus")
+            .unwrap();
+
+        let loaded = load_species_release(mirror.as_ref(), &GeneModelParser::new(), 110, mouse)
+            .await
+            .unwrap();
+        assert_eq!(loaded.format, GeneModelFormat::Gff3);
+        assert_eq!(loaded.gene_set.genes.len(), 1);
+        assert_eq!(loaded.files.len(), 1);
+    }
+
+    /// Serve a directory like the Ensembl FTP site's HTTPS front end
+    fn serve_fixture(root: PathBuf) -> impl Fn(&wiremock::Request) -> wiremock::ResponseTemplate {
+        move |request| {
+            let path = root.join(request.url.path().trim_matches('/'));
+            if path.is_dir() {
+                let links: String = std::fs::read_dir(&path)
+                    .unwrap()
+                    .map(|entry| {
+                        let entry = entry.unwrap();
+                        let name = entry.file_name().to_string_lossy().into_owned();
+                        let slash = if entry.path().is_dir() { "/" } else { "" };
+                        format!("<a href=\"{name}{slash}\">{name}{slash}</a>\n")
+                    })
+                    .collect();
+                wiremock::ResponseTemplate::new(200)
+                    .set_body_string(format!("<html><body>{}</body></html>", links))
+            } else if path.is_file() {
+                wiremock::ResponseTemplate::new(200).set_body_bytes(std::fs::read(&path).unwrap())
+            } else {
+                wiremock::ResponseTemplate::new(404)
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_load_species_release_from_bdp_ingest_mirror() {
+        let upstream = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("GET"))
+            .respond_with(serve_fixture(
+                PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/ensembl"),
+            ))
+            .mount(&upstream)
+            .await;
+
+        // Mirror a release the way the docs say to, then read it back
+        let output = tempfile::tempdir().unwrap();
+        let mirrored = bdp_ingest::ensembl::EnsemblIngester::new(
+            output.path(),
+            vec!["homo_sapiens".to_string()],
+        )
+        .with_base_url(format!("{}/pub", upstream.uri()))
+        .ingest(Some(110))
+        .await
+        .unwrap();
+        assert_eq!(mirrored, 110);
+
+        let config = EnsemblConfig::new().with_transport(TransportConfig::Local {
+            root: output.path().to_path_buf(),
+        });
+        let releases = VersionDiscovery::new(&config)
+            .unwrap()
+            .discover_all_versions()
+            .await
+            .unwrap();
+        assert_eq!(
+            releases
+                .iter()
+                .map(|v| v.release_number)
+                .collect::<Vec<_>>(),
+            vec![110]
+        );
+
+        let mirror = config.transport();
+        let human = load_species(mirror.as_ref(), 110)
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|s| s.name == "homo_sapiens")
+            .unwrap();
+        let loaded = load_species_release(mirror.as_ref(), &GeneModelParser::new(), 110, human)
+            .await
+            .unwrap();
+        assert_eq!(loaded.format, GeneModelFormat::Gtf);
+        assert_eq!(loaded.gene_set.genes.len(), 3);
+
+        let formats: Vec<_> = loaded.files.iter().map(|f| f.format.as_str()).collect();
+        assert_eq!(formats, vec!["gtf", "gff3", "cdna", "pep"]);
+    }
+}
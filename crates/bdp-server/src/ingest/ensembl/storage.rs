@@ -0,0 +1,601 @@
+// Storage layer for Ensembl gene sets
+//
+// Each species is a 'gene_model' data source. Genes and transcripts are
+// upserted by stable ID with the releases they appear in, so a release only
+// rewrites release bookkeeping for unchanged genes and the changes of a
+// release can be counted afterwards without keeping older rows around.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, QueryBuilder};
+use std::collections::HashMap;
+use tracing::{debug, info};
+use uuid::Uuid;
+
+use super::models::{EnsemblSpecies, GeneModel};
+use crate::ingest::citations::{ensembl_policy, setup_citation_policy};
+use crate::ingest::uniprot::taxonomy_helper::TaxonomyHelper;
+
+/// Genes and transcripts a release added, removed or changed in a species
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GeneModelChangeCounts {
+    pub genes_added: i64,
+    pub genes_removed: i64,
+    pub genes_location_modified: i64,
+    pub genes_annotation_modified: i64,
+    pub transcripts_added: i64,
+    pub transcripts_removed: i64,
+    /// Genes in the release
+    pub genes_total: i64,
+}
+
+/// A file published with a species release
+#[derive(Debug, Clone)]
+pub struct ReleaseFile {
+    /// Version file format ("gtf", "gff3", "cdna", "pep")
+    pub format: String,
+    pub s3_key: String,
+    pub sha256: String,
+    pub size_bytes: i64,
+}
+
+/// Storage handler for one Ensembl release
+pub struct EnsemblStorage {
+    db: PgPool,
+    organization_id: Uuid,
+    release: u32,
+    batch_size: usize,
+}
+
+impl EnsemblStorage {
+    /// Create a new storage handler
+    pub fn new(db: PgPool, organization_id: Uuid, release: u32) -> Self {
+        Self {
+            db,
+            organization_id,
+            release,
+            batch_size: 500,
+        }
+    }
+
+    /// Set batch size for gene and transcript upserts
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Set up citation policy for the Ensembl organization (idempotent)
+    pub async fn setup_citations(&self) -> Result<()> {
+        let policy_config = ensembl_policy(self.organization_id, None);
+        setup_citation_policy(&self.db, &policy_config).await?;
+        info!("Ensembl citation policy configured");
+        Ok(())
+    }
+
+    /// Get or create the data source of a species gene set
+    ///
+    /// The species is linked to its NCBI Taxonomy entry, which is created as a
+    /// stub when the taxonomy hasn't been ingested yet.
+    pub async fn get_or_create_species_source(
+        &self,
+        species: &EnsemblSpecies,
+        genome_build: Option<&str>,
+    ) -> Result<Uuid> {
+        let scientific_name = species.scientific_name();
+
+        let mut taxonomy_helper = TaxonomyHelper::new(self.db.clone(), self.organization_id);
+        let taxonomy_data_source_id = taxonomy_helper
+            .get_or_create_taxonomy(species.taxonomy_id, &scientific_name, &[])
+            .await
+            .context("Failed to get or create taxonomy via TaxonomyHelper")?;
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+
+        let name = format!("{} gene set", scientific_name);
+        let description = format!(
+            "Ensembl gene models, cDNA and peptide sequences of {} ({})",
+            scientific_name, species.display_name
+        );
+        let data_source_id = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            INSERT INTO registry_entries (organization_id, slug, name, description, entry_type)
+            VALUES ($1, $2, $3, $4, 'data_source')
+            ON CONFLICT (slug) DO UPDATE SET
+                name = EXCLUDED.name,
+                description = EXCLUDED.description,
+                updated_at = NOW()
+            RETURNING id
+            "#,
+        )
+        .bind(self.organization_id)
+        .bind(species.slug())
+        .bind(&name)
+        .bind(&description)
+        .fetch_one(&mut *tx)
+        .await
+        .context("Failed to create registry entry")?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO data_sources (id, source_type)
+            VALUES ($1, 'gene_model')
+            ON CONFLICT (id) DO NOTHING
+            "#,
+        )
+        .bind(data_source_id)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to create data source")?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO ensembl_species_metadata (
+                data_source_id, species, display_name, division, taxonomy_id,
+                taxonomy_data_source_id, assembly, assembly_accession, genebuild, ensembl_release
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            ON CONFLICT (data_source_id) DO UPDATE SET
+                display_name = EXCLUDED.display_name,
+                division = EXCLUDED.division,
+                taxonomy_id = EXCLUDED.taxonomy_id,
+                taxonomy_data_source_id = EXCLUDED.taxonomy_data_source_id,
+                assembly = EXCLUDED.assembly,
+                assembly_accession = EXCLUDED.assembly_accession,
+                genebuild = EXCLUDED.genebuild
+            "#,
+        )
+        .bind(data_source_id)
+        .bind(&species.name)
+        .bind(&species.display_name)
+        .bind(&species.division)
+        .bind(species.taxonomy_id)
+        .bind(taxonomy_data_source_id)
+        .bind(genome_build.unwrap_or(&species.assembly))
+        .bind(&species.assembly_accession)
+        .bind(&species.genebuild)
+        .bind(self.release as i32)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to upsert species metadata")?;
+
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        Ok(data_source_id)
+    }
+
+    /// Store the genes and transcripts of the release
+    ///
+    /// Genes missing from the release keep their rows with an older
+    /// `last_release`. Checksums of existing genes are compared to record
+    /// coordinate and annotation changes.
+    pub async fn store_gene_models(&self, data_source_id: Uuid, genes: &[GeneModel]) -> Result<()> {
+        let release = self.release as i32;
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+        let mut gene_refs: HashMap<String, Uuid> = HashMap::with_capacity(genes.len());
+
+        // 13 params per gene, well below the bind limit
+        for chunk in genes.chunks(self.batch_size) {
+            let mut query_builder = QueryBuilder::new(
+                "INSERT INTO ensembl_genes (data_source_id, gene_id, gene_version, gene_name, biotype, seqid, start_pos, end_pos, strand, location_checksum, annotation_checksum, first_release, last_release) ",
+            );
+
+            query_builder.push_values(chunk, |mut b, gene| {
+                b.push_bind(data_source_id)
+                    .push_bind(&gene.gene_id)
+                    .push_bind(gene.gene_version)
+                    .push_bind(&gene.gene_name)
+                    .push_bind(&gene.biotype)
+                    .push_bind(&gene.seqid)
+                    .push_bind(gene.start)
+                    .push_bind(gene.end)
+                    .push_bind(gene.strand.to_string())
+                    .push_bind(gene.location_checksum())
+                    .push_bind(gene.annotation_checksum())
+                    .push_bind(release)
+                    .push_bind(release);
+            });
+
+            query_builder.push(
+                r#"
+                ON CONFLICT (data_source_id, gene_id) DO UPDATE SET
+                    gene_version = EXCLUDED.gene_version,
+                    gene_name = EXCLUDED.gene_name,
+                    biotype = EXCLUDED.biotype,
+                    seqid = EXCLUDED.seqid,
+                    start_pos = EXCLUDED.start_pos,
+                    end_pos = EXCLUDED.end_pos,
+                    strand = EXCLUDED.strand,
+                    location_changed_release = CASE
+                        WHEN ensembl_genes.location_checksum <> EXCLUDED.location_checksum
+                        THEN EXCLUDED.last_release
+                        ELSE ensembl_genes.location_changed_release
+                    END,
+                    annotation_changed_release = CASE
+                        WHEN ensembl_genes.annotation_checksum <> EXCLUDED.annotation_checksum
+                        THEN EXCLUDED.last_release
+                        ELSE ensembl_genes.annotation_changed_release
+                    END,
+                    location_checksum = EXCLUDED.location_checksum,
+                    annotation_checksum = EXCLUDED.annotation_checksum,
+                    last_release = EXCLUDED.last_release
+                RETURNING gene_id, id
+                "#,
+            );
+
+            let rows = query_builder
+                .build_query_as::<(String, Uuid)>()
+                .fetch_all(&mut *tx)
+                .await
+                .context("Failed to upsert genes")?;
+            gene_refs.extend(rows);
+        }
+
+        let gene_refs = &gene_refs;
+        let transcripts: Vec<_> = genes
+            .iter()
+            .flat_map(|gene| {
+                gene.transcripts
+                    .iter()
+                    .map(move |t| (gene_refs[&gene.gene_id], t))
+            })
+            .collect();
+
+        // 12 params per transcript
+        for chunk in transcripts.chunks(self.batch_size) {
+            let mut query_builder = QueryBuilder::new(
+                "INSERT INTO ensembl_transcripts (data_source_id, gene_ref, transcript_id, transcript_version, transcript_name, biotype, start_pos, end_pos, exons, protein_id, first_release, last_release) ",
+            );
+
+            query_builder.push_values(chunk, |mut b, (gene_ref, transcript)| {
+                b.push_bind(data_source_id)
+                    .push_bind(*gene_ref)
+                    .push_bind(&transcript.transcript_id)
+                    .push_bind(transcript.transcript_version)
+                    .push_bind(&transcript.transcript_name)
+                    .push_bind(&transcript.biotype)
+                    .push_bind(transcript.start)
+                    .push_bind(transcript.end)
+                    .push_bind(serde_json::json!(transcript.exons))
+                    .push_bind(&transcript.protein_id)
+                    .push_bind(release)
+                    .push_bind(release);
+            });
+
+            query_builder.push(
+                r#"
+                ON CONFLICT (data_source_id, transcript_id) DO UPDATE SET
+                    gene_ref = EXCLUDED.gene_ref,
+                    transcript_version = EXCLUDED.transcript_version,
+                    transcript_name = EXCLUDED.transcript_name,
+                    biotype = EXCLUDED.biotype,
+                    start_pos = EXCLUDED.start_pos,
+                    end_pos = EXCLUDED.end_pos,
+                    exons = EXCLUDED.exons,
+                    protein_id = EXCLUDED.protein_id,
+                    last_release = EXCLUDED.last_release
+                "#,
+            );
+
+            query_builder
+                .build()
+                .execute(&mut *tx)
+                .await
+                .context("Failed to upsert transcripts")?;
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE ensembl_species_metadata
+            SET ensembl_release = $2, gene_count = $3, transcript_count = $4
+            WHERE data_source_id = $1
+            "#,
+        )
+        .bind(data_source_id)
+        .bind(release)
+        .bind(genes.len() as i32)
+        .bind(transcripts.len() as i32)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to update species gene counts")?;
+
+        tx.commit().await.context("Failed to commit gene models")?;
+
+        debug!(
+            data_source_id = %data_source_id,
+            genes = genes.len(),
+            transcripts = transcripts.len(),
+            "Stored gene models"
+        );
+
+        Ok(())
+    }
+
+    /// Create the version of a species release with its files
+    pub async fn create_release_version(
+        &self,
+        data_source_id: Uuid,
+        version: &str,
+        files: &[ReleaseFile],
+    ) -> Result<Uuid> {
+        let parsed = crate::ingest::versioning::SemanticVersion::parse(version).unwrap_or_default();
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+
+        let version_id = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            INSERT INTO versions (entry_id, version, external_version, version_major, version_minor, version_patch)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id
+            "#,
+        )
+        .bind(data_source_id)
+        .bind(version)
+        .bind(self.release.to_string())
+        .bind(parsed.major)
+        .bind(parsed.minor)
+        .bind(parsed.patch)
+        .fetch_one(&mut *tx)
+        .await
+        .context("Failed to create version")?;
+
+        for file in files {
+            sqlx::query(
+                r#"
+                INSERT INTO version_files (version_id, format, s3_key, checksum, size_bytes, sha256)
+                VALUES ($1, $2, $3, $4, $5, $4)
+                "#,
+            )
+            .bind(version_id)
+            .bind(&file.format)
+            .bind(&file.s3_key)
+            .bind(&file.sha256)
+            .bind(file.size_bytes)
+            .execute(&mut *tx)
+            .await
+            .with_context(|| {
+                format!("Failed to create version_file for format: {}", file.format)
+            })?;
+        }
+
+        tx.commit().await.context("Failed to commit version")?;
+
+        Ok(version_id)
+    }
+}
+
+/// Count what a release changed in a species gene set
+///
+/// Removed genes and transcripts are those last seen in `previous_release`;
+/// pass `None` for the first ingested release.
+pub async fn count_gene_model_changes(
+    pool: &PgPool,
+    data_source_id: Uuid,
+    release: u32,
+    previous_release: Option<u32>,
+) -> Result<GeneModelChangeCounts> {
+    let release = release as i32;
+    let previous_release = previous_release.map(|r| r as i32).filter(|r| *r < release);
+
+    let genes = sqlx::query_as::<_, (i64, i64, i64, i64, i64)>(
+        r#"
+        SELECT
+            COUNT(*) FILTER (WHERE last_release = $2 AND first_release = $2),
+            COUNT(*) FILTER (WHERE last_release = $3),
+            COUNT(*) FILTER (WHERE last_release = $2 AND first_release < $2 AND location_changed_release = $2),
+            COUNT(*) FILTER (WHERE last_release = $2 AND first_release < $2 AND annotation_changed_release = $2),
+            COUNT(*) FILTER (WHERE last_release = $2)
+        FROM ensembl_genes
+        WHERE data_source_id = $1
+        "#,
+    )
+    .bind(data_source_id)
+    .bind(release)
+    .bind(previous_release)
+    .fetch_one(pool)
+    .await
+    .context("Failed to count gene changes")?;
+
+    let transcripts = sqlx::query_as::<_, (i64, i64)>(
+        r#"
+        SELECT
+            COUNT(*) FILTER (WHERE last_release = $2 AND first_release = $2),
+            COUNT(*) FILTER (WHERE last_release = $3)
+        FROM ensembl_transcripts
+        WHERE data_source_id = $1
+        "#,
+    )
+    .bind(data_source_id)
+    .bind(release)
+    .bind(previous_release)
+    .fetch_one(pool)
+    .await
+    .context("Failed to count transcript changes")?;
+
+    Ok(GeneModelChangeCounts {
+        genes_added: genes.0,
+        genes_removed: genes.1,
+        genes_location_modified: genes.2,
+        genes_annotation_modified: genes.3,
+        transcripts_added: transcripts.0,
+        transcripts_removed: transcripts.1,
+        genes_total: genes.4,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::ensembl::models::Transcript;
+
+    fn species() -> EnsemblSpecies {
+        EnsemblSpecies {
+            name: "homo_sapiens".to_string(),
+            display_name: "Human".to_string(),
+            division: "EnsemblVertebrates".to_string(),
+            taxonomy_id: 9606,
+            assembly: "GRCh38.p14".to_string(),
+            assembly_accession: Some("GCA_000001405.29".to_string()),
+            genebuild: None,
+        }
+    }
+
+    fn gene(gene_id: &str, start: i64, name: &str) -> GeneModel {
+        GeneModel {
+            gene_id: gene_id.to_string(),
+            gene_version: Some(1),
+            gene_name: Some(name.to_string()),
+            biotype: Some("protein_coding".to_string()),
+            seqid: "1".to_string(),
+            start,
+            end: start + 1000,
+            strand: '+',
+            transcripts: vec![Transcript {
+                transcript_id: gene_id.replace("ENSG", "ENST"),
+                transcript_version: Some(1),
+                transcript_name: Some(format!("{}-201", name)),
+                biotype: Some("protein_coding".to_string()),
+                start,
+                end: start + 1000,
+                exons: vec![(start, start + 1000)],
+                protein_id: None,
+            }],
+        }
+    }
+
+    async fn insert_org(pool: &PgPool) -> sqlx::Result<Uuid> {
+        sqlx::query_scalar::<_, Uuid>(
+            "INSERT INTO organizations (slug, name, is_system) VALUES ('ensembl', 'Ensembl', true) RETURNING id",
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    #[sqlx::test]
+    async fn test_species_source_links_taxonomy(pool: PgPool) -> sqlx::Result<()> {
+        let org_id = insert_org(&pool).await?;
+        let storage = EnsemblStorage::new(pool.clone(), org_id, 110);
+
+        let data_source_id = storage
+            .get_or_create_species_source(&species(), Some("GRCh38.p14"))
+            .await
+            .unwrap();
+        // Idempotent
+        assert_eq!(
+            storage
+                .get_or_create_species_source(&species(), Some("GRCh38.p14"))
+                .await
+                .unwrap(),
+            data_source_id
+        );
+
+        let (slug, source_type, taxonomy_id): (String, String, i32) = sqlx::query_as(
+            r#"
+            SELECT re.slug, ds.source_type, tm.taxonomy_id
+            FROM registry_entries re
+            JOIN data_sources ds ON ds.id = re.id
+            JOIN ensembl_species_metadata esm ON esm.data_source_id = re.id
+            JOIN taxonomy_metadata tm ON tm.data_source_id = esm.taxonomy_data_source_id
+            WHERE re.id = $1
+            "#,
+        )
+        .bind(data_source_id)
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(slug, "ensembl-homo-sapiens");
+        assert_eq!(source_type, "gene_model");
+        assert_eq!(taxonomy_id, 9606);
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_release_changes_are_counted(pool: PgPool) -> sqlx::Result<()> {
+        let org_id = insert_org(&pool).await?;
+        let release_110 = EnsemblStorage::new(pool.clone(), org_id, 110).with_batch_size(2);
+        let data_source_id = release_110
+            .get_or_create_species_source(&species(), None)
+            .await
+            .unwrap();
+
+        release_110
+            .store_gene_models(
+                data_source_id,
+                &[
+                    gene("ENSG01", 100, "KEEP"),
+                    gene("ENSG02", 5000, "MOVE"),
+                    gene("ENSG03", 9000, "RENAME"),
+                    gene("ENSG04", 12000, "DROP"),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let initial = count_gene_model_changes(&pool, data_source_id, 110, None)
+            .await
+            .unwrap();
+        assert_eq!(initial.genes_added, 4);
+        assert_eq!(initial.transcripts_added, 4);
+        assert_eq!(initial.genes_total, 4);
+
+        let release_111 = EnsemblStorage::new(pool.clone(), org_id, 111);
+        release_111
+            .store_gene_models(
+                data_source_id,
+                &[
+                    gene("ENSG01", 100, "KEEP"),
+                    gene("ENSG02", 5100, "MOVE"),
+                    gene("ENSG03", 9000, "RENAMED"),
+                    gene("ENSG05", 20000, "NEW"),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let counts = count_gene_model_changes(&pool, data_source_id, 111, Some(110))
+            .await
+            .unwrap();
+        assert_eq!(
+            counts,
+            GeneModelChangeCounts {
+                genes_added: 1,
+                genes_removed: 1,
+                genes_location_modified: 1,
+                genes_annotation_modified: 1,
+                transcripts_added: 1,
+                transcripts_removed: 1,
+                genes_total: 4,
+            }
+        );
+
+        // Re-ingesting a release doesn't change its counts
+        release_111
+            .store_gene_models(
+                data_source_id,
+                &[
+                    gene("ENSG01", 100, "KEEP"),
+                    gene("ENSG02", 5100, "MOVE"),
+                    gene("ENSG03", 9000, "RENAMED"),
+                    gene("ENSG05", 20000, "NEW"),
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            count_gene_model_changes(&pool, data_source_id, 111, Some(110))
+                .await
+                .unwrap(),
+            counts
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,135 @@
+//! Ensembl version discovery
+//!
+//! Ensembl publishes each release in a `release-N` directory of its FTP site.
+//! The release number is the external version.
+
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use std::sync::Arc;
+
+use super::config::EnsemblConfig;
+use crate::ingest::common::transport::Transport;
+use crate::ingest::common::version_discovery::DiscoveredVersion as DiscoveredVersionTrait;
+
+/// Discovered Ensembl release
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoveredVersion {
+    /// External version identifier (e.g., "110")
+    pub external_version: String,
+    /// Release number
+    pub release_number: u32,
+    /// Date of the release directory (1970-01-01 when the listing has none)
+    pub release_date: NaiveDate,
+}
+
+impl DiscoveredVersionTrait for DiscoveredVersion {
+    fn external_version(&self) -> &str {
+        &self.external_version
+    }
+
+    fn release_date(&self) -> NaiveDate {
+        self.release_date
+    }
+}
+
+// Ensembl releases are ordered by number; directory dates change on re-syncs
+impl PartialOrd for DiscoveredVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DiscoveredVersion {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.release_number.cmp(&other.release_number)
+    }
+}
+
+/// Ensembl version discovery service
+pub struct VersionDiscovery {
+    transport: Arc<dyn Transport>,
+}
+
+impl VersionDiscovery {
+    pub fn new(config: &EnsemblConfig) -> Result<Self> {
+        Ok(Self {
+            transport: config.transport(),
+        })
+    }
+
+    /// All releases on the FTP site or mirror, oldest first
+    pub async fn discover_all_versions(&self) -> Result<Vec<DiscoveredVersion>> {
+        let entries = self
+            .transport
+            .list_directory(EnsemblConfig::RELEASES_DIR)
+            .await
+            .context("Failed to list Ensembl releases")?;
+
+        let mut versions: Vec<DiscoveredVersion> = entries
+            .into_iter()
+            .filter(|entry| entry.is_directory)
+            .filter_map(|entry| {
+                let release_number = parse_release_dir(&entry.name)?;
+                Some(DiscoveredVersion {
+                    external_version: release_number.to_string(),
+                    release_number,
+                    release_date: entry.modified.unwrap_or_default(),
+                })
+            })
+            .collect();
+        versions.sort();
+
+        Ok(versions)
+    }
+
+    /// The newest release
+    pub async fn discover_latest(&self) -> Result<DiscoveredVersion> {
+        self.discover_all_versions()
+            .await?
+            .pop()
+            .context("No Ensembl releases found")
+    }
+
+    /// Find a release by external version
+    pub async fn find_version(&self, external_version: &str) -> Result<DiscoveredVersion> {
+        self.discover_all_versions()
+            .await?
+            .into_iter()
+            .find(|v| v.external_version == external_version)
+            .with_context(|| format!("Ensembl release {} not found", external_version))
+    }
+}
+
+/// Release number of a `release-N` directory
+fn parse_release_dir(name: &str) -> Option<u32> {
+    name.strip_prefix("release-")?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::common::transport::TransportConfig;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_parse_release_dir() {
+        assert_eq!(parse_release_dir("release-110"), Some(110));
+        assert_eq!(parse_release_dir("release-110-beta"), None);
+        assert_eq!(parse_release_dir("current_gtf"), None);
+    }
+
+    #[tokio::test]
+    async fn test_discover_from_local_mirror() {
+        let config = EnsemblConfig::new().with_transport(TransportConfig::Local {
+            root: PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/ensembl"),
+        });
+        let discovery = VersionDiscovery::new(&config).unwrap();
+
+        let versions = discovery.discover_all_versions().await.unwrap();
+        let releases: Vec<_> = versions.iter().map(|v| v.release_number).collect();
+        assert_eq!(releases, vec![110, 111]);
+
+        assert_eq!(discovery.discover_latest().await.unwrap().external_version, "111");
+        assert!(discovery.find_version("109").await.is_err());
+    }
+}
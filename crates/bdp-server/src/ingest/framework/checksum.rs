@@ -1,9 +1,18 @@
-//! MD5 checksum utilities for file verification
+//! Checksum utilities for file verification
+//!
+//! Upstream sources publish MD5 digests, which are used to verify downloads.
+//! Files stored in the registry are identified by their SHA-256.
 
 use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
 use std::path::Path;
 use tokio::io::AsyncReadExt;
 
+/// Compute SHA-256 checksum of bytes as lowercase hex
+pub fn compute_sha256(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
 /// Compute MD5 checksum of bytes
 pub fn compute_md5(data: &[u8]) -> String {
     let digest = md5::compute(data);
@@ -48,6 +57,14 @@ mod tests {
         assert_eq!(md5, "6cd3556deb0da54bca060b4c39479839");
     }
 
+    #[test]
+    fn test_compute_sha256() {
+        assert_eq!(
+            compute_sha256(b"Hello, world!"),
+            "315f5bdb76d078c43b8ac0064e4a0164612b1fce77c869345bfc94c75894edd3"
+        );
+    }
+
     #[test]
     fn test_verify_md5() {
         let data = b"test data";
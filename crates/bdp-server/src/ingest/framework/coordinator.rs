@@ -6,12 +6,22 @@
 //! 3. Store phase: Monitor workers, handle completion
 
 use anyhow::{Context, Result};
+use serde::Serialize;
 use sqlx::PgPool;
 use std::sync::Arc;
 use uuid::Uuid;
 
+use super::record_index::RecordOffsetIndex;
 use super::types::{BatchConfig, CreateJobParams, JobStatus, WorkUnitStatus};
 
+/// A work unit to be created
+struct NewWorkUnit {
+    batch_number: i32,
+    start_offset: i64,
+    end_offset: i64,
+    record_count: Option<i32>,
+}
+
 /// Coordinates ingestion jobs and manages pipeline state
 pub struct IngestionCoordinator {
     pool: Arc<PgPool>,
@@ -57,7 +67,7 @@ impl IngestionCoordinator {
             r#"
             UPDATE ingestion_jobs
             SET status = $1, started_at = NOW()
-            WHERE id = $2
+            WHERE id = $2 AND status <> 'cancelled'
             "#,
             JobStatus::Downloading.as_str(),
             job_id
@@ -138,7 +148,7 @@ impl IngestionCoordinator {
             r#"
             UPDATE ingestion_jobs
             SET status = $1
-            WHERE id = $2
+            WHERE id = $2 AND status <> 'cancelled'
             "#,
             JobStatus::DownloadVerified.as_str(),
             job_id
@@ -164,34 +174,78 @@ impl IngestionCoordinator {
         let num_batches = (total_records + batch_size - 1) / batch_size;
 
         // Prepare all work units data first
-        let work_units: Vec<(Uuid, i32, i64, i64)> = (0..num_batches)
+        let work_units: Vec<NewWorkUnit> = (0..num_batches)
             .map(|i| {
                 let start_offset = i * batch_size;
                 let end_offset = ((i + 1) * batch_size).min(total_records) - 1;
-                (Uuid::new_v4(), i as i32, start_offset as i64, end_offset as i64)
+                NewWorkUnit {
+                    batch_number: i as i32,
+                    start_offset: start_offset as i64,
+                    end_offset: end_offset as i64,
+                    record_count: None,
+                }
             })
             .collect();
 
+        self.insert_work_units(job_id, unit_type, &work_units, total_records as i64)
+            .await?;
+        Ok(num_batches as i32)
+    }
+
+    /// Create one work unit per range of a record offset index
+    ///
+    /// The units' offsets are the ranges' offsets in the source file, and
+    /// their record counts the ranges' record counts. Workers read a unit's
+    /// records with [`super::record_index::SeekableFile::records`].
+    pub async fn create_range_work_units(
+        &self,
+        job_id: Uuid,
+        unit_type: &str,
+        index: &RecordOffsetIndex,
+    ) -> Result<i32> {
+        let work_units: Vec<NewWorkUnit> = index
+            .ranges
+            .iter()
+            .enumerate()
+            .map(|(i, range)| NewWorkUnit {
+                batch_number: i as i32,
+                start_offset: range.start as i64,
+                end_offset: range.end as i64,
+                record_count: Some(range.record_count as i32),
+            })
+            .collect();
+
+        self.insert_work_units(job_id, unit_type, &work_units, index.total_records as i64)
+            .await?;
+        Ok(work_units.len() as i32)
+    }
+
+    /// Insert pending work units and move the job to parsing
+    async fn insert_work_units(
+        &self,
+        job_id: Uuid,
+        unit_type: &str,
+        work_units: &[NewWorkUnit],
+        total_records: i64,
+    ) -> Result<()> {
         // Batch insert work units (chunks of 100 to avoid parameter limit)
         // This is much faster than sequential inserts with awaits in a loop
         for chunk in work_units.chunks(100) {
             let mut query_builder = sqlx::QueryBuilder::new(
-                "INSERT INTO ingestion_work_units (id, job_id, unit_type, batch_number, start_offset, end_offset, status, max_retries) "
+                "INSERT INTO ingestion_work_units (id, job_id, unit_type, batch_number, start_offset, end_offset, record_count, status, max_retries) "
             );
 
-            query_builder.push_values(
-                chunk,
-                |mut b, (id, batch_number, start_offset, end_offset)| {
-                    b.push_bind(*id)
-                        .push_bind(job_id)
-                        .push_bind(unit_type)
-                        .push_bind(*batch_number)
-                        .push_bind(*start_offset)
-                        .push_bind(*end_offset)
-                        .push_bind(WorkUnitStatus::Pending.as_str())
-                        .push_bind(self.config.max_retries);
-                },
-            );
+            query_builder.push_values(chunk, |mut b, unit| {
+                b.push_bind(Uuid::new_v4())
+                    .push_bind(job_id)
+                    .push_bind(unit_type)
+                    .push_bind(unit.batch_number)
+                    .push_bind(unit.start_offset)
+                    .push_bind(unit.end_offset)
+                    .push_bind(unit.record_count.unwrap_or(0))
+                    .push_bind(WorkUnitStatus::Pending.as_str())
+                    .push_bind(self.config.max_retries);
+            });
 
             query_builder
                 .build()
@@ -205,17 +259,17 @@ impl IngestionCoordinator {
             r#"
             UPDATE ingestion_jobs
             SET status = $1, total_records = $2
-            WHERE id = $3
+            WHERE id = $3 AND status <> 'cancelled'
             "#,
             JobStatus::Parsing.as_str(),
-            total_records as i64,
+            total_records,
             job_id
         )
         .execute(&*self.pool)
         .await
         .context("Failed to update job status to parsing")?;
 
-        Ok(num_batches as i32)
+        Ok(())
     }
 
     /// Reclaim stale work units from dead workers
@@ -313,7 +367,7 @@ impl IngestionCoordinator {
             r#"
             UPDATE ingestion_jobs
             SET status = $1
-            WHERE id = $2
+            WHERE id = $2 AND status <> 'cancelled'
             "#,
             JobStatus::Storing.as_str(),
             job_id
@@ -325,13 +379,30 @@ impl IngestionCoordinator {
         Ok(())
     }
 
+    /// Stop a pipeline whose job was cancelled
+    ///
+    /// Pipelines call this between phases so a cancelled job doesn't go on to
+    /// create versions or bundles.
+    pub async fn ensure_not_cancelled(&self, job_id: Uuid) -> Result<()> {
+        let status = sqlx::query_scalar!("SELECT status FROM ingestion_jobs WHERE id = $1", job_id)
+            .fetch_one(&*self.pool)
+            .await
+            .context("Failed to fetch job status")?;
+
+        if JobStatus::from(status) == JobStatus::Cancelled {
+            anyhow::bail!("Ingestion job {} was cancelled", job_id);
+        }
+
+        Ok(())
+    }
+
     /// Mark job as completed
     pub async fn complete_job(&self, job_id: Uuid) -> Result<()> {
         sqlx::query!(
             r#"
             UPDATE ingestion_jobs
             SET status = $1, completed_at = NOW()
-            WHERE id = $2
+            WHERE id = $2 AND status <> 'cancelled'
             "#,
             JobStatus::Completed.as_str(),
             job_id
@@ -350,7 +421,7 @@ impl IngestionCoordinator {
             UPDATE ingestion_jobs
             SET status = $1, completed_at = NOW(),
                 metadata = jsonb_set(COALESCE(metadata, '{}'), '{error}', to_jsonb($2::text))
-            WHERE id = $3
+            WHERE id = $3 AND status <> 'cancelled'
             "#,
             JobStatus::Failed.as_str(),
             error_message,
@@ -365,7 +436,7 @@ impl IngestionCoordinator {
 }
 
 /// Job progress snapshot
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct JobProgress {
     pub status: JobStatus,
     pub total_records: i64,
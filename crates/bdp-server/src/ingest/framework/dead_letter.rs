@@ -0,0 +1,250 @@
+//! Dead-letter store for records that fail ingestion
+//!
+//! Records a parser rejects or storage can't persist are written to
+//! `ingestion_dead_letters` with their raw text, source location and error
+//! chain instead of only being counted. Pending dead letters can be re-run
+//! once the cause is fixed.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Pipeline stage a record failed in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeadLetterStage {
+    Parse,
+    Store,
+}
+
+impl DeadLetterStage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeadLetterStage::Parse => "parse",
+            DeadLetterStage::Store => "store",
+        }
+    }
+}
+
+impl std::str::FromStr for DeadLetterStage {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "parse" => Ok(DeadLetterStage::Parse),
+            "store" => Ok(DeadLetterStage::Store),
+            _ => anyhow::bail!("Unknown dead-letter stage '{}'", s),
+        }
+    }
+}
+
+/// A record that failed ingestion
+#[derive(Debug, Clone)]
+pub struct DeadLetter {
+    pub stage: DeadLetterStage,
+    /// Record identifier, when the record got far enough to have one
+    pub record_identifier: Option<String>,
+    /// Source file the record was read from
+    pub source_file: Option<String>,
+    /// Byte offset of the record in the source file
+    pub source_offset: Option<i64>,
+    /// Raw record text
+    pub raw_record: String,
+    /// Error and its causes, outermost first
+    pub error_chain: Vec<String>,
+}
+
+impl DeadLetter {
+    pub fn new(stage: DeadLetterStage, raw_record: String, error: &anyhow::Error) -> Self {
+        Self {
+            stage,
+            record_identifier: None,
+            source_file: None,
+            source_offset: None,
+            raw_record,
+            error_chain: error.chain().map(|cause| cause.to_string()).collect(),
+        }
+    }
+
+    pub fn with_identifier(mut self, record_identifier: impl Into<String>) -> Self {
+        self.record_identifier = Some(record_identifier.into());
+        self
+    }
+
+    pub fn with_source(mut self, source_file: Option<&str>, source_offset: i64) -> Self {
+        self.source_file = source_file.map(str::to_string);
+        self.source_offset = Some(source_offset);
+        self
+    }
+
+    /// Full error message, causes separated by colons
+    pub fn error_message(&self) -> String {
+        self.error_chain.join(": ")
+    }
+}
+
+/// A dead letter waiting to be reprocessed
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct PendingDeadLetter {
+    pub id: Uuid,
+    pub stage: String,
+    pub record_identifier: Option<String>,
+    pub source_offset: Option<i64>,
+    pub raw_record: String,
+    pub attempts: i32,
+}
+
+/// Persists and resolves dead letters of ingestion jobs
+pub struct DeadLetterStore {
+    pool: Arc<PgPool>,
+}
+
+impl DeadLetterStore {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Record failed records of a job
+    pub async fn record(
+        &self,
+        job_id: Uuid,
+        work_unit_id: Option<Uuid>,
+        letters: &[DeadLetter],
+    ) -> Result<()> {
+        if letters.is_empty() {
+            return Ok(());
+        }
+
+        // 9 params per record, well below the bind limit
+        for chunk in letters.chunks(500) {
+            let mut query_builder = sqlx::QueryBuilder::new(
+                "INSERT INTO ingestion_dead_letters (job_id, work_unit_id, stage, record_identifier, source_file, source_offset, raw_record, error_message, error_chain) ",
+            );
+
+            query_builder.push_values(chunk, |mut b, letter| {
+                b.push_bind(job_id)
+                    .push_bind(work_unit_id)
+                    .push_bind(letter.stage.as_str())
+                    .push_bind(&letter.record_identifier)
+                    .push_bind(&letter.source_file)
+                    .push_bind(letter.source_offset)
+                    .push_bind(&letter.raw_record)
+                    .push_bind(letter.error_message())
+                    .push_bind(serde_json::json!(letter.error_chain));
+            });
+
+            query_builder
+                .build()
+                .execute(&*self.pool)
+                .await
+                .context("Failed to record dead letters")?;
+        }
+
+        tracing::warn!(
+            job_id = %job_id,
+            work_unit_id = ?work_unit_id,
+            count = letters.len(),
+            "Recorded dead letters"
+        );
+
+        Ok(())
+    }
+
+    /// Dead letters of a job that haven't been reprocessed yet
+    pub async fn pending(&self, job_id: Uuid) -> Result<Vec<PendingDeadLetter>> {
+        sqlx::query_as::<_, PendingDeadLetter>(
+            r#"
+            SELECT id, stage, record_identifier, source_offset, raw_record, attempts
+            FROM ingestion_dead_letters
+            WHERE job_id = $1 AND status = 'pending'
+            ORDER BY source_offset NULLS LAST, created_at
+            "#,
+        )
+        .bind(job_id)
+        .fetch_all(&*self.pool)
+        .await
+        .context("Failed to fetch pending dead letters")
+    }
+
+    /// Mark a dead letter as successfully reprocessed
+    pub async fn mark_reprocessed(&self, id: Uuid) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE ingestion_dead_letters
+            SET status = 'reprocessed', reprocessed_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .execute(&*self.pool)
+        .await
+        .context("Failed to mark dead letter as reprocessed")?;
+
+        Ok(())
+    }
+
+    /// Record another failed attempt, replacing the stage and error
+    pub async fn record_attempt(&self, id: Uuid, letter: &DeadLetter) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE ingestion_dead_letters
+            SET stage = $2,
+                record_identifier = COALESCE($3, record_identifier),
+                error_message = $4,
+                error_chain = $5,
+                attempts = attempts + 1
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(letter.stage.as_str())
+        .bind(&letter.record_identifier)
+        .bind(letter.error_message())
+        .bind(serde_json::json!(letter.error_chain))
+        .execute(&*self.pool)
+        .await
+        .context("Failed to record dead letter attempt")?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dead_letter_keeps_error_chain() {
+        let error = anyhow::anyhow!("duplicate key value")
+            .context("Failed to insert protein metadata")
+            .context("Failed to store P12345");
+
+        let letter = DeadLetter::new(DeadLetterStage::Store, "ID   TEST\n//\n".to_string(), &error)
+            .with_identifier("P12345")
+            .with_source(Some("uniprot/2025_01/uniprot_sprot.dat.gz"), 1024);
+
+        assert_eq!(
+            letter.error_chain,
+            vec![
+                "Failed to store P12345",
+                "Failed to insert protein metadata",
+                "duplicate key value"
+            ]
+        );
+        assert_eq!(
+            letter.error_message(),
+            "Failed to store P12345: Failed to insert protein metadata: duplicate key value"
+        );
+        assert_eq!(letter.source_offset, Some(1024));
+    }
+
+    #[test]
+    fn test_stage_round_trip() {
+        for stage in [DeadLetterStage::Parse, DeadLetterStage::Store] {
+            assert_eq!(stage.as_str().parse::<DeadLetterStage>().unwrap(), stage);
+        }
+        assert!("download".parse::<DeadLetterStage>().is_err());
+    }
+}
@@ -0,0 +1,1012 @@
+//! GFF3/GTF annotation parser
+//!
+//! Reads GFF3 and GTF files line by line, one `annotation` record per feature
+//! line, without holding the file's features in memory. Directives are kept
+//! on the reader and an embedded FASTA section ends the features.
+//!
+//! Features of GTF files are given the same `id`, `name` and `parents` as
+//! GFF3 features (gene → transcript → exon), so the formatter can re-emit
+//! either format, BED or JSON from any record.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write;
+
+use super::checksum::compute_md5;
+use super::parser::{DataSourceParser, RecordFormatter};
+use super::types::GenericRecord;
+
+/// Record type of parsed features
+pub const ANNOTATION_RECORD_TYPE: &str = "annotation";
+
+/// GTF feature types below transcripts, whose parent is their transcript
+const TRANSCRIPT_PARTS: &[&str] = &[
+    "exon",
+    "CDS",
+    "UTR",
+    "five_prime_utr",
+    "three_prime_utr",
+    "five_prime_UTR",
+    "three_prime_UTR",
+    "start_codon",
+    "stop_codon",
+    "Selenocysteine",
+    "intron",
+];
+
+/// Annotation file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GffFormat {
+    /// GFF3, `key=value` attributes with percent escapes
+    Gff3,
+    /// GTF (GFF2.5), `key "value";` attributes
+    Gtf,
+}
+
+impl GffFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Gff3 => "gff3",
+            Self::Gtf => "gtf",
+        }
+    }
+
+    /// Guess the format from the version directive or the first feature line
+    ///
+    /// Files without either are read as GFF3.
+    pub fn detect(data: &[u8]) -> Self {
+        let mut reader = GffReader::new(data, Self::Gff3);
+        if let Ok(Some((_, line))) = reader.next_feature_line() {
+            let attributes = line.rsplit('\t').next().unwrap_or("");
+            return match (attributes.find('='), attributes.find('"')) {
+                (Some(equals), Some(quote)) if quote < equals => Self::Gtf,
+                (Some(_), _) => Self::Gff3,
+                (None, _) => Self::Gtf,
+            };
+        }
+        for directive in reader.directives() {
+            if let GffDirective::Version(version) = directive {
+                if version.starts_with('2') {
+                    return Self::Gtf;
+                }
+            }
+        }
+        Self::Gff3
+    }
+}
+
+/// A directive (`##name value`) or Ensembl header (`#!name value`)
+///
+/// Resolution barriers (`###`) only matter for assembling feature trees and
+/// are not kept.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GffDirective {
+    /// `##gff-version 3`, `##gtf-version 2.2`
+    Version(String),
+    /// `##sequence-region seqid start end`
+    SequenceRegion {
+        seqid: String,
+        start: u64,
+        end: u64,
+    },
+    /// `#!genome-build GRCh38.p14`
+    GenomeBuild(String),
+    Other {
+        name: String,
+        value: Option<String>,
+    },
+}
+
+/// A sequence from the `##FASTA` section of a GFF3 file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmbeddedSequence {
+    /// First word of the header line
+    pub id: String,
+    pub sequence: String,
+}
+
+/// A feature line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GffFeature {
+    /// Format the feature was read from
+    pub format: GffFormat,
+    pub seqid: String,
+    pub source: String,
+    pub feature_type: String,
+    /// 1-based start, inclusive
+    pub start: u64,
+    /// 1-based end, inclusive
+    pub end: u64,
+    pub score: Option<f64>,
+    /// '+', '-', '.' (unstranded) or '?' (unknown)
+    pub strand: char,
+    pub phase: Option<u8>,
+    /// GFF3 `ID`, or the GTF `<type>_id` attribute (`gene_id` of genes)
+    pub id: Option<String>,
+    /// GFF3 `Name`, or the GTF `<type>_name` attribute
+    pub name: Option<String>,
+    /// GFF3 `Parent` values, or the GTF gene of transcripts and transcript of their parts
+    pub parents: Vec<String>,
+    /// All attributes with their decoded values
+    pub attributes: BTreeMap<String, Vec<String>>,
+}
+
+impl GffFeature {
+    /// Parse a feature line
+    pub fn parse(line: &str, format: GffFormat) -> Result<Self> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() != 9 {
+            bail!("Expected 9 tab-separated columns, found {}", fields.len());
+        }
+
+        let start: u64 = fields[3]
+            .parse()
+            .with_context(|| format!("Invalid start '{}'", fields[3]))?;
+        let end: u64 = fields[4]
+            .parse()
+            .with_context(|| format!("Invalid end '{}'", fields[4]))?;
+        if start > end {
+            bail!("Start {} is after end {}", start, end);
+        }
+        let score = match fields[5] {
+            "." => None,
+            score => Some(
+                score
+                    .parse()
+                    .with_context(|| format!("Invalid score '{}'", score))?,
+            ),
+        };
+        let strand = match fields[6] {
+            "+" => '+',
+            "-" => '-',
+            "." => '.',
+            "?" => '?',
+            other => bail!("Invalid strand '{}'", other),
+        };
+        let phase = match fields[7] {
+            "." => None,
+            "0" => Some(0),
+            "1" => Some(1),
+            "2" => Some(2),
+            other => bail!("Invalid phase '{}'", other),
+        };
+
+        let column = |value: &str| match format {
+            GffFormat::Gff3 => percent_decode(value),
+            GffFormat::Gtf => value.to_string(),
+        };
+        let mut feature = Self {
+            format,
+            seqid: column(fields[0]),
+            source: column(fields[1]),
+            feature_type: column(fields[2]),
+            start,
+            end,
+            score,
+            strand,
+            phase,
+            id: None,
+            name: None,
+            parents: Vec::new(),
+            attributes: BTreeMap::new(),
+        };
+
+        match format {
+            GffFormat::Gff3 => {
+                feature.attributes = parse_gff3_attributes(fields[8])?;
+                feature.id = feature.first_attribute("ID");
+                feature.name = feature.first_attribute("Name");
+                feature.parents = feature
+                    .attributes
+                    .get("Parent")
+                    .cloned()
+                    .unwrap_or_default();
+            },
+            GffFormat::Gtf => {
+                feature.attributes = parse_gtf_attributes(fields[8])?;
+                feature.id = feature.first_attribute(&format!("{}_id", feature.feature_type));
+                feature.name = feature.first_attribute(&format!("{}_name", feature.feature_type));
+                feature.parents = feature.gtf_parents();
+            },
+        }
+        Ok(feature)
+    }
+
+    /// First value of an attribute
+    pub fn first_attribute(&self, key: &str) -> Option<String> {
+        self.attributes.get(key)?.first().cloned()
+    }
+
+    fn gtf_parents(&self) -> Vec<String> {
+        let parent_key = match self.feature_type.as_str() {
+            "gene" => return Vec::new(),
+            "transcript" => "gene_id",
+            feature_type if TRANSCRIPT_PARTS.contains(&feature_type) => "transcript_id",
+            _ => "gene_id",
+        };
+        self.first_attribute(parent_key).into_iter().collect()
+    }
+
+    /// Identifier of the record, before making it unique within a file
+    fn record_identifier(&self) -> String {
+        match &self.id {
+            Some(id) => id.to_lowercase(),
+            None => format!("{}:{}:{}-{}", self.seqid, self.feature_type, self.start, self.end)
+                .to_lowercase(),
+        }
+    }
+
+    /// The feature as a GFF3 line, without a line break
+    ///
+    /// `ID`, `Name` and `Parent` come first; features read from GTF get
+    /// them from their `id`, `name` and `parents`.
+    pub fn to_gff3_line(&self) -> String {
+        let mut attributes: Vec<(&str, Vec<&str>)> = Vec::new();
+        let reserved = [
+            ("ID", self.id.iter().map(String::as_str).collect::<Vec<_>>()),
+            ("Name", self.name.iter().map(String::as_str).collect()),
+            ("Parent", self.parents.iter().map(String::as_str).collect()),
+        ];
+        for (key, values) in reserved {
+            let values = match self.attributes.get(key) {
+                Some(own) => own.iter().map(String::as_str).collect(),
+                None => values,
+            };
+            if !values.is_empty() {
+                attributes.push((key, values));
+            }
+        }
+        for (key, values) in &self.attributes {
+            if !matches!(key.as_str(), "ID" | "Name" | "Parent") {
+                attributes.push((key, values.iter().map(String::as_str).collect()));
+            }
+        }
+
+        let column9 = attributes
+            .iter()
+            .map(|(key, values)| {
+                let values: Vec<String> = values
+                    .iter()
+                    .map(|value| percent_encode(value, true))
+                    .collect();
+                format!("{}={}", percent_encode(key, true), values.join(","))
+            })
+            .collect::<Vec<_>>()
+            .join(";");
+
+        format!(
+            "{}\t{}\t{}\t{}",
+            percent_encode(&self.seqid, false),
+            percent_encode(&self.source, false),
+            percent_encode(&self.feature_type, false),
+            self.location_columns(if column9.is_empty() { "." } else { &column9 })
+        )
+    }
+
+    /// The feature as a GTF line, without a line break
+    ///
+    /// `gene_id` and `transcript_id` come first; features read from GFF3
+    /// get them from their own ID or their parent.
+    pub fn to_gtf_line(&self) -> String {
+        let (gene_id, transcript_id) = self.gtf_ids();
+        let mut column9 = String::new();
+        let _ = write!(column9, "gene_id \"{}\";", gtf_escape(&gene_id));
+        if let Some(transcript_id) = transcript_id {
+            let _ = write!(column9, " transcript_id \"{}\";", gtf_escape(&transcript_id));
+        }
+        for (key, values) in &self.attributes {
+            if matches!(key.as_str(), "gene_id" | "transcript_id" | "ID" | "Parent") {
+                continue;
+            }
+            for value in values {
+                let _ = write!(column9, " {} \"{}\";", key, gtf_escape(value));
+            }
+        }
+
+        format!(
+            "{}\t{}\t{}\t{}",
+            self.seqid,
+            self.source,
+            self.feature_type,
+            self.location_columns(&column9)
+        )
+    }
+
+    /// The feature as a BED6 line, without a line break
+    ///
+    /// BED starts are 0-based; scores are clamped to BED's 0-1000.
+    pub fn to_bed_line(&self) -> String {
+        let name = self
+            .name
+            .as_deref()
+            .or(self.id.as_deref())
+            .unwrap_or(&self.feature_type);
+        let score = self
+            .score
+            .map(|score| score.round().clamp(0.0, 1000.0) as i64)
+            .unwrap_or(0);
+        let strand = if self.strand == '?' { '.' } else { self.strand };
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            self.seqid,
+            self.start - 1,
+            self.end,
+            name.replace(char::is_whitespace, "_"),
+            score,
+            strand
+        )
+    }
+
+    /// Columns 4-9
+    fn location_columns(&self, attributes: &str) -> String {
+        let score = self
+            .score
+            .map(|score| score.to_string())
+            .unwrap_or_else(|| ".".to_string());
+        let phase = self
+            .phase
+            .map(|phase| phase.to_string())
+            .unwrap_or_else(|| ".".to_string());
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            self.start, self.end, score, self.strand, phase, attributes
+        )
+    }
+
+    /// GTF `gene_id` and `transcript_id` of the feature
+    fn gtf_ids(&self) -> (String, Option<String>) {
+        let own_id = self.id.clone();
+        let parent = self.parents.first().cloned();
+        let gene_id = self.first_attribute("gene_id");
+        let transcript_id = self.first_attribute("transcript_id");
+
+        match self.feature_type.as_str() {
+            "gene" => (gene_id.or(own_id).unwrap_or_default(), None),
+            feature_type if TRANSCRIPT_PARTS.contains(&feature_type) => {
+                (gene_id.unwrap_or_default(), transcript_id.or(parent))
+            },
+            // Transcripts (mRNA, ncRNA, ...) are children of genes
+            _ if parent.is_some() => {
+                (gene_id.or(parent).unwrap_or_default(), transcript_id.or(own_id))
+            },
+            _ => (gene_id.or(own_id).unwrap_or_default(), transcript_id),
+        }
+    }
+}
+
+/// Streaming reader over the lines of a GFF3 or GTF file
+pub struct GffReader<'a> {
+    format: GffFormat,
+    lines: std::iter::Enumerate<std::slice::Split<'a, u8, fn(&u8) -> bool>>,
+    directives: Vec<GffDirective>,
+    /// Header line that started the FASTA section, when it has been reached
+    fasta: Option<Option<&'a str>>,
+}
+
+impl<'a> GffReader<'a> {
+    pub fn new(data: &'a [u8], format: GffFormat) -> Self {
+        let is_newline: fn(&u8) -> bool = |byte| *byte == b'\n';
+        Self {
+            format,
+            lines: data.split(is_newline).enumerate(),
+            directives: Vec::new(),
+            fasta: None,
+        }
+    }
+
+    /// Directives read so far
+    pub fn directives(&self) -> &[GffDirective] {
+        &self.directives
+    }
+
+    /// Next feature line and its line number, handling directives and comments
+    ///
+    /// Returns `None` at the end of the file or the start of the FASTA section.
+    pub fn next_feature_line(&mut self) -> Result<Option<(usize, &'a str)>> {
+        while self.fasta.is_none() {
+            let Some((line_number, line)) = self.next_line()? else {
+                return Ok(None);
+            };
+
+            if line.starts_with("##FASTA") {
+                self.fasta = Some(None);
+            } else if line.starts_with('>') {
+                // Some tools start the FASTA section without the directive
+                self.fasta = Some(Some(line));
+            } else if line.starts_with("###") || line.trim().is_empty() {
+                continue;
+            } else if let Some(directive) = line.strip_prefix("##") {
+                let directive = parse_directive(directive)
+                    .with_context(|| format!("Line {}: {}", line_number, truncate(line)))?;
+                self.directives.push(directive);
+            } else if let Some(header) = line.strip_prefix("#!") {
+                self.directives.push(parse_header(header));
+            } else if !line.starts_with('#') {
+                return Ok(Some((line_number, line)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Next feature and its line number
+    pub fn next_feature(&mut self) -> Result<Option<(usize, GffFeature)>> {
+        let format = self.format;
+        let Some((line_number, line)) = self.next_feature_line()? else {
+            return Ok(None);
+        };
+        let feature = GffFeature::parse(line, format)
+            .with_context(|| format!("Line {}: {}", line_number, truncate(line)))?;
+        Ok(Some((line_number, feature)))
+    }
+
+    /// Sequences of the FASTA section, skipping any features left before it
+    pub fn into_sequences(mut self) -> Result<Vec<EmbeddedSequence>> {
+        while self.next_feature_line()?.is_some() {}
+
+        let mut sequences = Vec::new();
+        let mut current = self.fasta.flatten().map(|header| EmbeddedSequence {
+            id: fasta_id(header),
+            sequence: String::new(),
+        });
+        while let Some((line_number, line)) = self.next_line()? {
+            let line = line.trim();
+            if let Some(header) = line.strip_prefix('>') {
+                sequences.extend(current.take());
+                current = Some(EmbeddedSequence {
+                    id: fasta_id(header),
+                    sequence: String::new(),
+                });
+            } else if let Some(sequence) = current.as_mut() {
+                sequence.sequence.push_str(line);
+            } else if !line.is_empty() {
+                bail!("Line {}: sequence without a FASTA header", line_number);
+            }
+        }
+        sequences.extend(current);
+        Ok(sequences)
+    }
+
+    fn next_line(&mut self) -> Result<Option<(usize, &'a str)>> {
+        let Some((index, line)) = self.lines.next() else {
+            return Ok(None);
+        };
+        let line = std::str::from_utf8(line)
+            .with_context(|| format!("Line {}: invalid UTF-8", index + 1))?;
+        Ok(Some((index + 1, line.strip_suffix('\r').unwrap_or(line))))
+    }
+}
+
+impl Iterator for GffReader<'_> {
+    type Item = Result<GffFeature>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_feature()
+            .map(|feature| feature.map(|(_, feature)| feature))
+            .transpose()
+    }
+}
+
+/// Parser for GFF3 and GTF annotation files
+pub struct GffParser {
+    format: GffFormat,
+    source_file: Option<String>,
+}
+
+impl GffParser {
+    pub fn new(format: GffFormat) -> Self {
+        Self {
+            format,
+            source_file: None,
+        }
+    }
+
+    /// Record the file the records come from
+    pub fn with_source_file(mut self, source_file: impl Into<String>) -> Self {
+        self.source_file = Some(source_file.into());
+        self
+    }
+
+    /// Stream the features of a decompressed file
+    pub fn reader<'a>(&self, data: &'a [u8]) -> GffReader<'a> {
+        GffReader::new(data, self.format)
+    }
+
+    /// Sequences embedded after the features of a GFF3 file
+    pub fn embedded_sequences(&self, data: &[u8]) -> Result<Vec<EmbeddedSequence>> {
+        self.reader(data).into_sequences()
+    }
+
+    fn to_generic_record(
+        &self,
+        feature: &GffFeature,
+        record_identifier: String,
+        offset: usize,
+    ) -> Result<GenericRecord> {
+        let record_data =
+            serde_json::to_value(feature).context("Failed to serialize annotation feature")?;
+        let content_md5 = compute_md5(record_data.to_string().as_bytes());
+
+        Ok(GenericRecord {
+            record_type: ANNOTATION_RECORD_TYPE.to_string(),
+            record_identifier,
+            record_name: feature.name.as_ref().map(|name| name.to_lowercase()),
+            record_data,
+            content_md5: Some(content_md5),
+            sequence_md5: None,
+            source_file: self.source_file.clone(),
+            source_offset: Some(offset as i64),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl DataSourceParser for GffParser {
+    /// Records are numbered by feature line. Features sharing an ID (the
+    /// segments of a discontinuous CDS) or without one and at the same
+    /// location get their record index appended after the first, so
+    /// identifiers are unique within a file.
+    async fn parse_range(
+        &self,
+        data: &[u8],
+        start_offset: usize,
+        end_offset: usize,
+    ) -> Result<Vec<GenericRecord>> {
+        let mut records = Vec::new();
+        let mut identifiers = HashSet::new();
+
+        for (index, feature) in self.reader(data).enumerate() {
+            if index > end_offset {
+                break;
+            }
+            let feature = feature?;
+            let base = feature.record_identifier();
+            let identifier = if identifiers.insert(base.clone()) {
+                base
+            } else {
+                format!("{}:{}", base, index)
+            };
+            if index >= start_offset {
+                records.push(self.to_generic_record(&feature, identifier, index)?);
+            }
+        }
+
+        Ok(records)
+    }
+
+    async fn count_records(&self, data: &[u8]) -> Result<Option<usize>> {
+        let mut reader = self.reader(data);
+        let mut count = 0;
+        while reader.next_feature_line()?.is_some() {
+            count += 1;
+        }
+        Ok(Some(count))
+    }
+
+    fn record_type(&self) -> &str {
+        ANNOTATION_RECORD_TYPE
+    }
+
+    fn output_formats(&self) -> Vec<String> {
+        vec!["gff3".to_string(), "gtf".to_string(), "bed".to_string(), "json".to_string()]
+    }
+}
+
+/// Annotation record formatter for generating files
+pub struct GffFormatter;
+
+#[async_trait::async_trait]
+impl RecordFormatter for GffFormatter {
+    async fn format_record(
+        &self,
+        record: &GenericRecord,
+        format: &str,
+    ) -> Result<(Vec<u8>, String)> {
+        let feature: GffFeature = serde_json::from_value(record.record_data.clone())
+            .context("Record is not an annotation feature")?;
+
+        let content = match format {
+            "gff3" => format!("##gff-version 3\n{}\n", feature.to_gff3_line()),
+            "gtf" => format!("{}\n", feature.to_gtf_line()),
+            "bed" => format!("{}\n", feature.to_bed_line()),
+            "json" => {
+                let json = serde_json::to_string_pretty(&record.record_data)
+                    .context("Failed to serialize to JSON")?;
+                return Ok((json.into_bytes(), "application/json".to_string()));
+            },
+            _ => bail!("Unsupported format: {}", format),
+        };
+        Ok((content.into_bytes(), "text/plain".to_string()))
+    }
+}
+
+fn parse_directive(directive: &str) -> Result<GffDirective> {
+    let mut words = directive.split_whitespace();
+    let name = words.next().unwrap_or_default();
+    match name {
+        "gff-version" | "gtf-version" => {
+            Ok(GffDirective::Version(words.next().unwrap_or_default().to_string()))
+        },
+        "sequence-region" => {
+            let (Some(seqid), Some(start), Some(end)) = (words.next(), words.next(), words.next())
+            else {
+                bail!("sequence-region needs a seqid, start and end");
+            };
+            Ok(GffDirective::SequenceRegion {
+                seqid: percent_decode(seqid),
+                start: start
+                    .parse()
+                    .with_context(|| format!("Invalid sequence-region start '{}'", start))?,
+                end: end
+                    .parse()
+                    .with_context(|| format!("Invalid sequence-region end '{}'", end))?,
+            })
+        },
+        _ => Ok(other_directive(directive)),
+    }
+}
+
+fn parse_header(header: &str) -> GffDirective {
+    match header.strip_prefix("genome-build") {
+        Some(build) if build.starts_with(char::is_whitespace) => {
+            GffDirective::GenomeBuild(build.trim().to_string())
+        },
+        _ => other_directive(header),
+    }
+}
+
+fn other_directive(directive: &str) -> GffDirective {
+    let directive = directive.trim();
+    match directive.split_once(char::is_whitespace) {
+        Some((name, value)) => GffDirective::Other {
+            name: name.to_string(),
+            value: Some(value.trim().to_string()),
+        },
+        None => GffDirective::Other {
+            name: directive.to_string(),
+            value: None,
+        },
+    }
+}
+
+/// Parse GFF3 attributes (`key=value,value;key=value`), decoding percent escapes
+fn parse_gff3_attributes(column: &str) -> Result<BTreeMap<String, Vec<String>>> {
+    let mut attributes: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    if column == "." {
+        return Ok(attributes);
+    }
+    for attribute in column.split(';').map(str::trim).filter(|a| !a.is_empty()) {
+        let (key, values) = attribute
+            .split_once('=')
+            .with_context(|| format!("Attribute without a value: '{}'", attribute))?;
+        attributes
+            .entry(percent_decode(key.trim()))
+            .or_default()
+            .extend(values.split(',').map(|value| percent_decode(value.trim())));
+    }
+    Ok(attributes)
+}
+
+/// Parse GTF attributes (`key "value"; key "value";`), keeping repeated keys' values
+fn parse_gtf_attributes(column: &str) -> Result<BTreeMap<String, Vec<String>>> {
+    let mut attributes: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut rest = column.trim();
+    while !rest.is_empty() {
+        let (key, after) = rest
+            .split_once(char::is_whitespace)
+            .with_context(|| format!("Attribute without a value: '{}'", rest))?;
+        let after = after.trim_start();
+        let (value, after) = match after.strip_prefix('"') {
+            Some(quoted) => read_quoted(quoted)?,
+            None => {
+                let end = after.find(';').unwrap_or(after.len());
+                (after[..end].trim().to_string(), &after[end..])
+            },
+        };
+        attributes.entry(key.to_string()).or_default().push(value);
+
+        let after = after.trim_start();
+        rest = match after.strip_prefix(';') {
+            Some(next) => next.trim_start(),
+            None if after.is_empty() => after,
+            None => bail!("Expected ';' after attribute {}", key),
+        };
+    }
+    Ok(attributes)
+}
+
+/// Read a quoted GTF value up to its closing quote, unescaping `\"` and `\\`
+fn read_quoted(quoted: &str) -> Result<(String, &str)> {
+    let mut value = String::new();
+    let mut escaped = false;
+    for (index, c) in quoted.char_indices() {
+        match c {
+            _ if escaped => {
+                value.push(c);
+                escaped = false;
+            },
+            '\\' => escaped = true,
+            '"' => return Ok((value, &quoted[index + 1..])),
+            _ => value.push(c),
+        }
+    }
+    bail!("Unterminated quoted value")
+}
+
+fn gtf_escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Escape the characters GFF3 reserves
+///
+/// Tabs, line breaks, control characters and '%' are escaped in every
+/// column; `;=&,` also in attribute keys and values.
+fn percent_encode(value: &str, attribute: bool) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for c in value.chars() {
+        let reserved = c.is_control() || c == '%' || (attribute && ";=&,".contains(c));
+        if reserved && c.is_ascii() {
+            let _ = write!(encoded, "%{:02X}", c as u8);
+        } else {
+            encoded.push(c);
+        }
+    }
+    encoded
+}
+
+fn percent_decode(value: &str) -> String {
+    if !value.contains('%') {
+        return value.to_string();
+    }
+
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escape = (bytes[i] == b'%')
+            .then(|| value.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escape {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            },
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            },
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn fasta_id(header: &str) -> String {
+    header
+        .trim_start_matches('>')
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn truncate(line: &str) -> &str {
+    match line.char_indices().nth(120) {
+        Some((index, _)) => &line[..index],
+        None => line,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GFF3: &str = "##gff-version 3.1.26
+##sequence-region ctg123 1 1497228
+#!genome-build TEST1
+# a comment
+ctg123\t.\tgene\t1000\t9000\t.\t+\t.\tID=gene00001;Name=EDEN;Note=protein kinase%3B putative%2C see %22EDEN%22
+ctg123\t.\tmRNA\t1050\t9000\t.\t+\t.\tID=mRNA00001;Parent=gene00001;Name=EDEN.1
+ctg123\t.\tmRNA\t1300\t9000\t.\t+\t.\tID=mRNA00002;Parent=gene00001;Name=EDEN.2
+###
+ctg123\t.\texon\t1300\t1500\t.\t+\t.\tID=exon00001;Parent=mRNA00001,mRNA00002
+ctg123\t.\tCDS\t1201\t1500\t.\t+\t0\tID=cds00001;Parent=mRNA00001;Name=edenprotein.1
+ctg123\t.\tCDS\t3000\t3902\t.\t+\t0\tID=cds00001;Parent=mRNA00001;Name=edenprotein.1
+ctg123\tmotif\tTF_binding_site\t1000\t1012\t87.5\t+\t.\t.
+##FASTA
+>ctg123 test contig
+cttctgggcgtacccgattc
+tcggagaacttgccgcaccatt
+";
+
+    const GTF: &str = "#!genome-build GRCh38.p14
+13\tensembl_havana\tgene\t32315508\t32400268\t.\t+\t.\tgene_id \"ENSG00000139618\"; gene_version \"17\"; gene_name \"BRCA2\"; gene_biotype \"protein_coding\";
+13\tensembl_havana\ttranscript\t32315508\t32400268\t.\t+\t.\tgene_id \"ENSG00000139618\"; transcript_id \"ENST00000380152\"; transcript_name \"BRCA2-201\"; tag \"basic\"; tag \"Ensembl_canonical\";
+13\tensembl_havana\texon\t32315508\t32315667\t.\t+\t.\tgene_id \"ENSG00000139618\"; transcript_id \"ENST00000380152\"; exon_number 1; exon_id \"ENSE00001484009\"; note \"a \\\"quoted\\\" word; here\";
+";
+
+    #[tokio::test]
+    async fn test_parse_gff3() {
+        let parser = GffParser::new(GffFormat::Gff3).with_source_file("eden.gff3");
+        assert_eq!(parser.count_records(GFF3.as_bytes()).await.unwrap(), Some(7));
+
+        let records = parser.parse_range(GFF3.as_bytes(), 0, 100).await.unwrap();
+        assert_eq!(records.len(), 7);
+        assert!(records.iter().all(|r| r.record_type == "annotation"));
+
+        let gene: GffFeature = serde_json::from_value(records[0].record_data.clone()).unwrap();
+        assert_eq!(records[0].record_identifier, "gene00001");
+        assert_eq!(records[0].record_name.as_deref(), Some("eden"));
+        assert_eq!(records[0].source_file.as_deref(), Some("eden.gff3"));
+        assert_eq!(
+            gene.first_attribute("Note").as_deref(),
+            Some("protein kinase; putative, see \"EDEN\"")
+        );
+
+        // Multi-parent features
+        let exon: GffFeature = serde_json::from_value(records[3].record_data.clone()).unwrap();
+        assert_eq!(exon.parents, vec!["mRNA00001", "mRNA00002"]);
+
+        // Segments of a discontinuous feature share the ID
+        assert_eq!(records[4].record_identifier, "cds00001");
+        assert_eq!(records[5].record_identifier, "cds00001:5");
+        assert_eq!(records[5].record_data["phase"], 0);
+
+        let site: GffFeature = serde_json::from_value(records[6].record_data.clone()).unwrap();
+        assert_eq!(records[6].record_identifier, "ctg123:tf_binding_site:1000-1012");
+        assert_eq!(site.score, Some(87.5));
+        assert!(site.attributes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_parse_range() {
+        let parser = GffParser::new(GffFormat::Gff3);
+        let records = parser.parse_range(GFF3.as_bytes(), 4, 5).await.unwrap();
+        let identifiers: Vec<_> = records
+            .iter()
+            .map(|r| r.record_identifier.as_str())
+            .collect();
+        assert_eq!(identifiers, vec!["cds00001", "cds00001:5"]);
+        assert_eq!(records[0].source_offset, Some(4));
+
+        assert!(parser
+            .parse_range(GFF3.as_bytes(), 10, 20)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_directives_and_embedded_fasta() {
+        let mut reader = GffReader::new(GFF3.as_bytes(), GffFormat::Gff3);
+        assert_eq!(reader.by_ref().count(), 7);
+        assert_eq!(
+            reader.directives(),
+            &[
+                GffDirective::Version("3.1.26".to_string()),
+                GffDirective::SequenceRegion {
+                    seqid: "ctg123".to_string(),
+                    start: 1,
+                    end: 1497228,
+                },
+                GffDirective::GenomeBuild("TEST1".to_string()),
+            ]
+        );
+
+        let sequences = GffParser::new(GffFormat::Gff3)
+            .embedded_sequences(GFF3.as_bytes())
+            .unwrap();
+        assert_eq!(
+            sequences,
+            vec![EmbeddedSequence {
+                id: "ctg123".to_string(),
+                sequence: "cttctgggcgtacccgattctcggagaacttgccgcaccatt".to_string(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_gtf() {
+        let parser = GffParser::new(GffFormat::Gtf);
+        let records = parser.parse_range(GTF.as_bytes(), 0, 100).await.unwrap();
+        let features: Vec<GffFeature> = records
+            .iter()
+            .map(|r| serde_json::from_value(r.record_data.clone()).unwrap())
+            .collect();
+
+        assert_eq!(features[0].id.as_deref(), Some("ENSG00000139618"));
+        assert_eq!(features[0].name.as_deref(), Some("BRCA2"));
+        assert!(features[0].parents.is_empty());
+
+        assert_eq!(features[1].parents, vec!["ENSG00000139618"]);
+        assert_eq!(features[1].attributes["tag"], vec!["basic", "Ensembl_canonical"]);
+
+        assert_eq!(records[2].record_identifier, "ense00001484009");
+        assert_eq!(features[2].parents, vec!["ENST00000380152"]);
+        assert_eq!(features[2].attributes["exon_number"], vec!["1"]);
+        assert_eq!(features[2].attributes["note"], vec!["a \"quoted\" word; here"]);
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_lines() {
+        for line in [
+            "ctg123\t.\tgene\t1000\t9000\t.\t+\t.",
+            "ctg123\t.\tgene\t9000\t1000\t.\t+\t.\tID=a",
+            "ctg123\t.\tgene\t1000\t9000\t.\tx\t.\tID=a",
+            "ctg123\t.\tCDS\t1000\t9000\t.\t+\t3\tID=a",
+            "ctg123\t.\tgene\t1000\t9000\t.\t+\t.\tID",
+        ] {
+            assert!(GffFeature::parse(line, GffFormat::Gff3).is_err(), "{}", line);
+        }
+        assert!(GffFeature::parse(
+            "1\t.\tgene\t1\t2\t.\t+\t.\tgene_id \"unterminated;",
+            GffFormat::Gtf
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(GffFormat::detect(GFF3.as_bytes()), GffFormat::Gff3);
+        assert_eq!(GffFormat::detect(GTF.as_bytes()), GffFormat::Gtf);
+        assert_eq!(GffFormat::detect(b"##gff-version 2\n"), GffFormat::Gtf);
+        assert_eq!(GffFormat::detect(b""), GffFormat::Gff3);
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("a%3Bb%2Cc"), "a;b,c");
+        assert_eq!(percent_decode("100%"), "100%");
+    }
+
+    #[tokio::test]
+    async fn test_format_records() {
+        let parser = GffParser::new(GffFormat::Gff3);
+        let records = parser.parse_range(GFF3.as_bytes(), 0, 3).await.unwrap();
+        let formatter = GffFormatter;
+
+        let (gff3, content_type) = formatter.format_record(&records[0], "gff3").await.unwrap();
+        assert_eq!(content_type, "text/plain");
+        assert_eq!(
+            String::from_utf8(gff3).unwrap(),
+            "##gff-version 3\nctg123\t.\tgene\t1000\t9000\t.\t+\t.\tID=gene00001;Name=EDEN;Note=protein kinase%3B putative%2C see \"EDEN\"\n"
+        );
+
+        let (gff3, _) = formatter.format_record(&records[3], "gff3").await.unwrap();
+        assert!(String::from_utf8(gff3)
+            .unwrap()
+            .ends_with("\tID=exon00001;Parent=mRNA00001,mRNA00002\n"));
+
+        let (gtf, _) = formatter.format_record(&records[1], "gtf").await.unwrap();
+        assert_eq!(
+            String::from_utf8(gtf).unwrap(),
+            "ctg123\t.\tmRNA\t1050\t9000\t.\t+\t.\tgene_id \"gene00001\"; transcript_id \"mRNA00001\"; Name \"EDEN.1\";\n"
+        );
+
+        let (bed, _) = formatter.format_record(&records[2], "bed").await.unwrap();
+        assert_eq!(String::from_utf8(bed).unwrap(), "ctg123\t1299\t9000\tEDEN.2\t0\t+\n");
+
+        let (json, content_type) = formatter.format_record(&records[0], "json").await.unwrap();
+        assert_eq!(content_type, "application/json");
+        assert!(String::from_utf8(json)
+            .unwrap()
+            .contains("\"seqid\": \"ctg123\""));
+
+        assert!(formatter.format_record(&records[0], "fasta").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_gtf_to_gff3() {
+        let parser = GffParser::new(GffFormat::Gtf);
+        let records = parser.parse_range(GTF.as_bytes(), 1, 1).await.unwrap();
+        let (gff3, _) = GffFormatter
+            .format_record(&records[0], "gff3")
+            .await
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(gff3).unwrap(),
+            "##gff-version 3\n13\tensembl_havana\ttranscript\t32315508\t32400268\t.\t+\t.\tID=ENST00000380152;Name=BRCA2-201;Parent=ENSG00000139618;gene_id=ENSG00000139618;tag=basic,Ensembl_canonical;transcript_id=ENST00000380152;transcript_name=BRCA2-201\n"
+        );
+    }
+}
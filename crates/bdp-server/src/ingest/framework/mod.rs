@@ -5,20 +5,38 @@
 
 pub mod checksum;
 pub mod coordinator;
+pub mod dead_letter;
+pub mod gff;
 pub mod metalink;
 pub mod parser;
+pub mod record_index;
+pub mod release_changes;
 pub mod storage;
+pub mod successions;
 pub mod types;
+pub mod upstream;
 pub mod worker;
 
 // Re-export commonly used types
-pub use checksum::{compute_md5, verify_file_md5};
+pub use checksum::{compute_md5, compute_sha256, verify_file_md5};
 pub use coordinator::{IngestionCoordinator, JobProgress};
+pub use dead_letter::{DeadLetter, DeadLetterStage, DeadLetterStore, PendingDeadLetter};
+pub use gff::{GffFeature, GffFormat, GffFormatter, GffParser};
 pub use metalink::MetalinkInfo;
 pub use parser::{DataSourceParser, RecordFormatter};
+pub use record_index::{RawRecord, RecordOffsetIndex, RecordRange, SeekableFile};
+pub use release_changes::{
+    count_release_changes, record_release_change, ReleaseChange, ReleaseChangeCounts,
+    ReleaseChangeType,
+};
 pub use storage::{StorageAdapter, StorageOrchestrator};
+pub use successions::{deprecate_entries, record_successions, Succession};
 pub use types::{
     BatchConfig, CreateJobParams, FileUpload, GenericRecord, IngestionJob, IngestionWorkUnit,
     JobStatus, RecordStatus, StagedRecord, WorkUnitStatus,
 };
+pub use upstream::{
+    record_upstream_verification, record_upstream_verification_for_entries, ChecksumMismatch,
+    DigestAlgorithm, UpstreamDigest, UpstreamVerification,
+};
 pub use worker::IngestionWorker;
@@ -0,0 +1,395 @@
+//! Record offsets of seekable source files
+//!
+//! Large sources (a TrEMBL DAT file is hundreds of GB) are kept on disk
+//! rather than in memory. One pass over the file builds an index of byte
+//! ranges holding a fixed number of records each; every range becomes a work
+//! unit, and its worker seeks to the range's start and reads records up to
+//! its end, so memory use doesn't depend on the size of the file.
+//!
+//! Offsets are byte offsets in plain files and virtual offsets in BGZF
+//! files, which can be read from any block. Other gzip files can't be read
+//! from an offset and have to be decompressed to disk first.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use crate::ingest::common::bgzf::{is_bgzf, BgzfReader};
+
+/// A reader that knows the offset of the next byte it returns
+pub trait PositionedRead: BufRead + Send {
+    /// Byte offset in plain files, virtual offset in BGZF files
+    fn position(&self) -> u64;
+}
+
+impl<R: PositionedRead + ?Sized> PositionedRead for Box<R> {
+    fn position(&self) -> u64 {
+        (**self).position()
+    }
+}
+
+impl<R: Read + Seek + Send> PositionedRead for BgzfReader<R> {
+    fn position(&self) -> u64 {
+        self.virtual_offset()
+    }
+}
+
+/// Counts the bytes read from an uncompressed reader
+pub struct CountingReader<R> {
+    inner: R,
+    position: u64,
+}
+
+impl<R> CountingReader<R> {
+    /// Wrap a reader whose next byte is at `position`
+    pub fn new(inner: R, position: u64) -> Self {
+        Self { inner, position }
+    }
+}
+
+impl<R: BufRead> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.position += read as u64;
+        Ok(read)
+    }
+}
+
+impl<R: BufRead> BufRead for CountingReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.inner.consume(amount);
+        self.position += amount as u64;
+    }
+}
+
+impl<R: BufRead + Send> PositionedRead for CountingReader<R> {
+    fn position(&self) -> u64 {
+        self.position
+    }
+}
+
+/// A source file that can be read from any record offset
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SeekableFile {
+    /// Uncompressed file, addressed by byte offsets
+    Plain(PathBuf),
+    /// BGZF file, addressed by virtual offsets
+    Bgzf(PathBuf),
+}
+
+impl SeekableFile {
+    /// Open a plain or BGZF file, telling them apart by their first bytes
+    pub fn open(path: &Path) -> Result<Self> {
+        let mut header = Vec::with_capacity(18);
+        File::open(path)
+            .with_context(|| format!("Failed to open {}", path.display()))?
+            .take(18)
+            .read_to_end(&mut header)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+
+        if is_bgzf(&header) {
+            Ok(Self::Bgzf(path.to_path_buf()))
+        } else if header.starts_with(&[0x1f, 0x8b]) {
+            bail!(
+                "{} is gzip-compressed without BGZF blocks and can't be read from an offset; \
+                 decompress it to disk first",
+                path.display()
+            )
+        } else {
+            Ok(Self::Plain(path.to_path_buf()))
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Plain(path) | Self::Bgzf(path) => path,
+        }
+    }
+
+    /// Reader positioned at an offset
+    pub fn reader_at(&self, offset: u64) -> Result<Box<dyn PositionedRead>> {
+        let mut file = BufReader::new(
+            File::open(self.path())
+                .with_context(|| format!("Failed to open {}", self.path().display()))?,
+        );
+        match self {
+            Self::Plain(_) => {
+                file.seek(SeekFrom::Start(offset))
+                    .with_context(|| format!("Failed to seek to offset {}", offset))?;
+                Ok(Box::new(CountingReader::new(file, offset)))
+            },
+            Self::Bgzf(_) => {
+                let mut reader = BgzfReader::new(file);
+                reader
+                    .seek_virtual(offset)
+                    .with_context(|| format!("Failed to seek to virtual offset {}", offset))?;
+                Ok(Box::new(reader))
+            },
+        }
+    }
+
+    /// Raw records from `start` up to `end` (the whole rest of the file if `None`)
+    pub fn records<F>(
+        &self,
+        start: u64,
+        end: Option<u64>,
+        is_record_end: F,
+    ) -> Result<RecordReader<Box<dyn PositionedRead>, F>>
+    where
+        F: Fn(&[u8]) -> bool,
+    {
+        Ok(RecordReader::new(self.reader_at(start)?, end, is_record_end))
+    }
+
+    /// Index the records of the file in one pass
+    pub fn build_index<F>(
+        &self,
+        is_record_end: F,
+        records_per_range: usize,
+    ) -> Result<RecordOffsetIndex>
+    where
+        F: Fn(&[u8]) -> bool,
+    {
+        RecordOffsetIndex::build(self.reader_at(0)?, is_record_end, records_per_range)
+            .with_context(|| format!("Failed to index {}", self.path().display()))
+    }
+}
+
+/// The raw bytes of a record and the offset it starts at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawRecord {
+    pub offset: u64,
+    pub data: Vec<u8>,
+}
+
+/// Reads whole records, up to an end offset
+///
+/// A record is every line up to and including the line `is_record_end`
+/// accepts (given without its line break). Lines after the last complete
+/// record are not returned.
+pub struct RecordReader<R, F> {
+    reader: R,
+    end: Option<u64>,
+    is_record_end: F,
+}
+
+impl<R: PositionedRead, F: Fn(&[u8]) -> bool> RecordReader<R, F> {
+    pub fn new(reader: R, end: Option<u64>, is_record_end: F) -> Self {
+        Self {
+            reader,
+            end,
+            is_record_end,
+        }
+    }
+
+    fn read_record(&mut self) -> Result<Option<RawRecord>> {
+        let offset = self.reader.position();
+        if self.end.is_some_and(|end| offset >= end) {
+            return Ok(None);
+        }
+
+        let mut data = Vec::new();
+        loop {
+            let line_start = data.len();
+            if self.reader.read_until(b'\n', &mut data)? == 0 {
+                return Ok(None);
+            }
+            if (self.is_record_end)(trim_line_break(&data[line_start..])) {
+                return Ok(Some(RawRecord { offset, data }));
+            }
+        }
+    }
+}
+
+impl<R: PositionedRead, F: Fn(&[u8]) -> bool> Iterator for RecordReader<R, F> {
+    type Item = Result<RawRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record().transpose()
+    }
+}
+
+/// A range of records in a source file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordRange {
+    /// Offset of the first record
+    pub start: u64,
+    /// Offset just past the last record
+    pub end: u64,
+    /// Index of the first record in the file
+    pub first_record: u64,
+    pub record_count: u64,
+}
+
+/// Byte ranges of a file's records, a fixed number of records each
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordOffsetIndex {
+    pub ranges: Vec<RecordRange>,
+    pub total_records: u64,
+}
+
+impl RecordOffsetIndex {
+    /// Index the records of a reader in one pass, `records_per_range` per range
+    ///
+    /// Only one line is held in memory at a time.
+    pub fn build<R, F>(mut reader: R, is_record_end: F, records_per_range: usize) -> Result<Self>
+    where
+        R: PositionedRead,
+        F: Fn(&[u8]) -> bool,
+    {
+        let records_per_range = records_per_range.max(1) as u64;
+        let mut index = Self::default();
+        let mut range_start = reader.position();
+        let mut range_records = 0;
+        let mut last_record_end = range_start;
+        let mut line = Vec::new();
+
+        loop {
+            line.clear();
+            if reader.read_until(b'\n', &mut line)? == 0 {
+                break;
+            }
+            if !is_record_end(trim_line_break(&line)) {
+                continue;
+            }
+
+            index.total_records += 1;
+            range_records += 1;
+            last_record_end = reader.position();
+            if range_records == records_per_range {
+                index.push_range(range_start, last_record_end, range_records);
+                range_start = last_record_end;
+                range_records = 0;
+            }
+        }
+        if range_records > 0 {
+            // Lines after the last complete record are left out
+            index.push_range(range_start, last_record_end, range_records);
+        }
+
+        Ok(index)
+    }
+
+    fn push_range(&mut self, start: u64, end: u64, record_count: u64) {
+        let first_record = self.total_records - record_count;
+        self.ranges.push(RecordRange {
+            start,
+            end,
+            first_record,
+            record_count,
+        });
+    }
+}
+
+fn trim_line_break(line: &[u8]) -> &[u8] {
+    let line = line.strip_suffix(b"\n").unwrap_or(line);
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::common::bgzf::tests::bgzf_compress;
+    use std::io::Cursor;
+
+    fn dat(entries: usize) -> Vec<u8> {
+        (0..entries)
+            .map(|i| format!("ID   ENTRY{}\nAC   P{:05};\nSQ   SEQUENCE\n     MKV\n//\n", i, i))
+            .collect::<String>()
+            .into_bytes()
+    }
+
+    fn is_dat_end(line: &[u8]) -> bool {
+        line.starts_with(b"//")
+    }
+
+    fn read_ranges(file: &SeekableFile, index: &RecordOffsetIndex) -> Vec<Vec<RawRecord>> {
+        index
+            .ranges
+            .iter()
+            .map(|range| {
+                file.records(range.start, Some(range.end), is_dat_end)
+                    .unwrap()
+                    .collect::<Result<Vec<_>>>()
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_build_index() {
+        let mut data = dat(10);
+        data.extend_from_slice(b"ID   TRUNCATED\n");
+        let reader = CountingReader::new(Cursor::new(&data), 0);
+        let index = RecordOffsetIndex::build(reader, is_dat_end, 4).unwrap();
+
+        assert_eq!(index.total_records, 10);
+        let counts: Vec<_> = index
+            .ranges
+            .iter()
+            .map(|r| (r.first_record, r.record_count))
+            .collect();
+        assert_eq!(counts, vec![(0, 4), (4, 4), (8, 2)]);
+        assert_eq!(index.ranges[0].start, 0);
+        assert_eq!(index.ranges[1].start, index.ranges[0].end);
+        assert_eq!(index.ranges[2].end, (data.len() - 15) as u64);
+    }
+
+    #[test]
+    fn test_read_plain_ranges() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("entries.dat");
+        let data = dat(10);
+        std::fs::write(&path, &data).unwrap();
+
+        let file = SeekableFile::open(&path).unwrap();
+        assert_eq!(file, SeekableFile::Plain(path.clone()));
+        let index = file.build_index(is_dat_end, 3).unwrap();
+        let ranges = read_ranges(&file, &index);
+
+        assert_eq!(ranges.iter().map(Vec::len).collect::<Vec<_>>(), vec![3, 3, 3, 1]);
+        let first = &ranges[1][0];
+        assert!(first.data.starts_with(b"ID   ENTRY3\n"));
+        assert!(first.data.ends_with(b"//\n"));
+        assert_eq!(&data[first.offset as usize..][..first.data.len()], &first.data[..]);
+    }
+
+    #[test]
+    fn test_read_bgzf_ranges() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("entries.dat.gz");
+        let data = dat(50);
+        // Small blocks, so records span block boundaries
+        std::fs::write(&path, bgzf_compress(&data, 100)).unwrap();
+
+        let file = SeekableFile::open(&path).unwrap();
+        assert_eq!(file, SeekableFile::Bgzf(path.clone()));
+        let index = file.build_index(is_dat_end, 7).unwrap();
+        assert_eq!(index.total_records, 50);
+
+        let records: Vec<RawRecord> = read_ranges(&file, &index).into_iter().flatten().collect();
+        assert_eq!(records.len(), 50);
+        let joined: Vec<u8> = records.iter().flat_map(|r| r.data.clone()).collect();
+        assert_eq!(joined, data);
+    }
+
+    #[test]
+    fn test_rejects_plain_gzip() {
+        use flate2::write::GzEncoder;
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("entries.dat.gz");
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&dat(2)).unwrap();
+        std::fs::write(&path, encoder.finish().unwrap()).unwrap();
+
+        assert!(SeekableFile::open(&path).is_err());
+    }
+}
@@ -0,0 +1,277 @@
+//! Per-release change records for incremental ingestion
+//!
+//! Pipelines diff each entry of an upstream release against what is already
+//! stored and record only the entries that were added, modified or removed in
+//! `entry_release_changes`. Unchanged entries keep their version and files, so
+//! the records (and the work behind them) scale with release churn. Version
+//! bump detectors count these records to build the changelog.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// How an entry changed in a release
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReleaseChangeType {
+    Added,
+    SequenceModified,
+    AnnotationModified,
+    Removed,
+}
+
+impl ReleaseChangeType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReleaseChangeType::Added => "added",
+            ReleaseChangeType::SequenceModified => "sequence_modified",
+            ReleaseChangeType::AnnotationModified => "annotation_modified",
+            ReleaseChangeType::Removed => "removed",
+        }
+    }
+}
+
+impl std::str::FromStr for ReleaseChangeType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "added" => Ok(ReleaseChangeType::Added),
+            "sequence_modified" => Ok(ReleaseChangeType::SequenceModified),
+            "annotation_modified" => Ok(ReleaseChangeType::AnnotationModified),
+            "removed" => Ok(ReleaseChangeType::Removed),
+            _ => anyhow::bail!("Unknown release change type '{}'", s),
+        }
+    }
+}
+
+/// A changed entry of a release
+#[derive(Debug, Clone)]
+pub struct ReleaseChange {
+    pub entry_id: Uuid,
+    pub change_type: ReleaseChangeType,
+    /// Internal version before the release, if the entry existed
+    pub previous_version: Option<String>,
+    /// Internal version created by the release, unless the entry was removed
+    pub version: Option<String>,
+}
+
+/// Number of changed entries in a release, by change type
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseChangeCounts {
+    pub added: i64,
+    pub sequence_modified: i64,
+    pub annotation_modified: i64,
+    pub removed: i64,
+}
+
+impl ReleaseChangeCounts {
+    pub fn get(&self, change_type: ReleaseChangeType) -> i64 {
+        match change_type {
+            ReleaseChangeType::Added => self.added,
+            ReleaseChangeType::SequenceModified => self.sequence_modified,
+            ReleaseChangeType::AnnotationModified => self.annotation_modified,
+            ReleaseChangeType::Removed => self.removed,
+        }
+    }
+
+    /// Total number of changed entries
+    pub fn total(&self) -> i64 {
+        self.added + self.sequence_modified + self.annotation_modified + self.removed
+    }
+}
+
+/// Record a changed entry within the transaction that stores it
+///
+/// Re-ingesting a release replaces the entry's earlier record for that release.
+pub async fn record_release_change(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    organization_id: Uuid,
+    external_version: &str,
+    change: &ReleaseChange,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO entry_release_changes (
+            organization_id, external_version, entry_id, change_type, previous_version, version
+        )
+        VALUES ($1, $2, $3, $4, $5, $6)
+        ON CONFLICT (organization_id, external_version, entry_id) DO UPDATE
+        SET change_type = EXCLUDED.change_type,
+            previous_version = EXCLUDED.previous_version,
+            version = EXCLUDED.version
+        "#,
+    )
+    .bind(organization_id)
+    .bind(external_version)
+    .bind(change.entry_id)
+    .bind(change.change_type.as_str())
+    .bind(&change.previous_version)
+    .bind(&change.version)
+    .execute(&mut **tx)
+    .await
+    .context("Failed to record release change")?;
+
+    Ok(())
+}
+
+/// Count the changed entries of a release within a data source
+///
+/// Only the data source itself and the entries it depends on, directly or
+/// through other entries, are counted, so a bundle's changelog covers its
+/// own members rather than every entry of the organization's release.
+/// Dependencies of every version of the data source are included, so
+/// entries removed in the release still count.
+pub async fn count_release_changes(
+    pool: &PgPool,
+    data_source_id: Uuid,
+    external_version: &str,
+) -> Result<ReleaseChangeCounts> {
+    let rows = sqlx::query_as::<_, (String, i64)>(
+        r#"
+        WITH RECURSIVE closure (entry_id) AS (
+            SELECT $1::uuid
+            UNION
+            SELECT d.depends_on_entry_id
+            FROM closure c
+            JOIN versions v ON v.entry_id = c.entry_id
+            JOIN dependencies d ON d.version_id = v.id
+        )
+        SELECT rc.change_type, COUNT(*)
+        FROM entry_release_changes rc
+        JOIN closure c ON c.entry_id = rc.entry_id
+        JOIN registry_entries re ON re.id = $1
+        WHERE rc.organization_id = re.organization_id AND rc.external_version = $2
+        GROUP BY rc.change_type
+        "#,
+    )
+    .bind(data_source_id)
+    .bind(external_version)
+    .fetch_all(pool)
+    .await
+    .context("Failed to count release changes")?;
+
+    let mut counts = ReleaseChangeCounts::default();
+    for (change_type, count) in rows {
+        match change_type.parse()? {
+            ReleaseChangeType::Added => counts.added = count,
+            ReleaseChangeType::SequenceModified => counts.sequence_modified = count,
+            ReleaseChangeType::AnnotationModified => counts.annotation_modified = count,
+            ReleaseChangeType::Removed => counts.removed = count,
+        }
+    }
+
+    Ok(counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::shared::test_helpers::{
+        create_test_data_source, create_test_org, TestVersion,
+    };
+
+    #[test]
+    fn test_change_type_round_trip() {
+        for change_type in [
+            ReleaseChangeType::Added,
+            ReleaseChangeType::SequenceModified,
+            ReleaseChangeType::AnnotationModified,
+            ReleaseChangeType::Removed,
+        ] {
+            assert_eq!(change_type.as_str().parse::<ReleaseChangeType>().unwrap(), change_type);
+        }
+        assert!("unchanged".parse::<ReleaseChangeType>().is_err());
+    }
+
+    #[sqlx::test]
+    async fn test_count_release_changes(pool: PgPool) -> sqlx::Result<()> {
+        let org_id = create_test_org(&pool, "uniprot", "UniProt").await?;
+
+        let mut tx = pool.begin().await?;
+        let mut entry_ids = Vec::new();
+        for (slug, change_type) in [
+            ("P00001", ReleaseChangeType::Added),
+            ("P00002", ReleaseChangeType::SequenceModified),
+            ("P00003", ReleaseChangeType::AnnotationModified),
+            ("P00004", ReleaseChangeType::AnnotationModified),
+            ("P00005", ReleaseChangeType::Removed),
+        ] {
+            let entry_id = create_test_data_source(&pool, org_id, slug, slug, "protein").await?;
+            entry_ids.push(entry_id);
+
+            let change = ReleaseChange {
+                entry_id,
+                change_type,
+                previous_version: None,
+                version: Some("1.0".to_string()),
+            };
+            record_release_change(&mut tx, org_id, "2025_02", &change)
+                .await
+                .unwrap();
+            // Re-ingesting the release must not count an entry twice
+            record_release_change(&mut tx, org_id, "2025_02", &change)
+                .await
+                .unwrap();
+        }
+        tx.commit().await?;
+
+        // The bundle's new version no longer has the removed P00005, its
+        // previous one did; P00004 belongs to another bundle
+        let bundle_id = create_test_data_source(&pool, org_id, "human", "Human", "bundle").await?;
+        let previous = TestVersion::with_entry_id(bundle_id, "1.0")
+            .insert(&pool)
+            .await?;
+        let current = TestVersion::with_entry_id(bundle_id, "1.1")
+            .insert(&pool)
+            .await?;
+        for (version_id, entry_id) in [
+            (previous.id, entry_ids[1]),
+            (previous.id, entry_ids[2]),
+            (previous.id, entry_ids[4]),
+            (current.id, entry_ids[0]),
+            (current.id, entry_ids[1]),
+            (current.id, entry_ids[2]),
+        ] {
+            sqlx::query!(
+                "INSERT INTO dependencies (version_id, depends_on_entry_id, depends_on_version) VALUES ($1, $2, '1.0')",
+                version_id,
+                entry_id
+            )
+            .execute(&pool)
+            .await?;
+        }
+
+        let counts = count_release_changes(&pool, bundle_id, "2025_02")
+            .await
+            .unwrap();
+        assert_eq!(
+            counts,
+            ReleaseChangeCounts {
+                added: 1,
+                sequence_modified: 1,
+                annotation_modified: 1,
+                removed: 1,
+            }
+        );
+        assert_eq!(counts.total(), 4);
+
+        // A protein's own change counts for it
+        assert_eq!(
+            count_release_changes(&pool, entry_ids[3], "2025_02")
+                .await
+                .unwrap()
+                .annotation_modified,
+            1
+        );
+        assert_eq!(
+            count_release_changes(&pool, bundle_id, "2025_01")
+                .await
+                .unwrap()
+                .total(),
+            0
+        );
+        Ok(())
+    }
+}
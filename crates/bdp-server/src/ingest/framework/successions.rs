@@ -0,0 +1,248 @@
+//! Merged and deleted upstream identifiers
+//!
+//! Upstream databases retire identifiers: UniProt keeps the accessions of
+//! merged entries as secondary accessions of the surviving entry, and the
+//! NCBI taxdump lists merged and deleted taxon IDs. Pipelines record these so
+//! specs pinned to an old identifier keep resolving. The old identifier
+//! becomes a `previous_accession` alias of its successor's data source, and a
+//! registry entry still stored under it is deprecated in favour of the
+//! successor.
+
+use std::collections::HashSet;
+
+use anyhow::{Context, Result};
+use uuid::Uuid;
+
+/// An identifier replaced by another entry of the same organization
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Succession {
+    /// Retired identifier (registry entry slug)
+    pub previous: String,
+    /// Slug of the entry that replaced it
+    pub successor: String,
+}
+
+impl Succession {
+    pub fn new(previous: impl Into<String>, successor: impl Into<String>) -> Self {
+        Self {
+            previous: previous.into(),
+            successor: successor.into(),
+        }
+    }
+}
+
+/// Record that identifiers were merged into their successors
+///
+/// Successors not stored yet are skipped. An identifier keeps the first
+/// successor ever recorded for it: when it was split across several entries,
+/// later successions, in this call or a later one, leave its alias and
+/// deprecation unchanged. Returns the number of registry entries deprecated.
+pub async fn record_successions(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    organization_id: Uuid,
+    successions: &[Succession],
+) -> Result<u64> {
+    let mut seen = HashSet::new();
+    let (previous, successors): (Vec<String>, Vec<String>) = successions
+        .iter()
+        .filter(|s| s.previous != s.successor && seen.insert(s.previous.as_str()))
+        .map(|s| (s.previous.clone(), s.successor.clone()))
+        .unzip();
+    if previous.is_empty() {
+        return Ok(0);
+    }
+
+    sqlx::query!(
+        r#"
+        INSERT INTO data_source_aliases (data_source_id, alias, alias_type, notes)
+        SELECT re.id, p.previous, 'previous_accession', 'Merged into ' || p.successor
+        FROM UNNEST($2::TEXT[], $3::TEXT[]) AS p (previous, successor)
+        JOIN registry_entries re ON re.organization_id = $1 AND re.slug = p.successor
+        JOIN data_sources ds ON ds.id = re.id
+        ON CONFLICT (alias, alias_type) DO NOTHING
+        "#,
+        organization_id,
+        &previous,
+        &successors
+    )
+    .execute(&mut **tx)
+    .await
+    .context("Failed to record merged identifiers")?;
+
+    // Deprecate in favour of the recorded successor, which may not be the
+    // one listed here
+    let result = sqlx::query!(
+        r#"
+        UPDATE registry_entries re
+        SET deprecated = TRUE,
+            deprecated_at = COALESCE(re.deprecated_at, NOW()),
+            deprecated_reason = 'Merged into ' || successor.slug,
+            superseded_by_id = successor.id,
+            updated_at = NOW()
+        FROM data_source_aliases a
+        JOIN registry_entries successor ON successor.id = a.data_source_id
+        WHERE re.organization_id = $1
+          AND re.slug = ANY($2)
+          AND a.alias = re.slug
+          AND a.alias_type = 'previous_accession'
+          AND successor.organization_id = $1
+          AND re.id <> successor.id
+          AND re.superseded_by_id IS DISTINCT FROM successor.id
+        "#,
+        organization_id,
+        &previous
+    )
+    .execute(&mut **tx)
+    .await
+    .context("Failed to deprecate merged identifiers")?;
+
+    Ok(result.rows_affected())
+}
+
+/// Deprecate entries whose identifiers were withdrawn without a successor
+///
+/// Returns the number of registry entries deprecated.
+pub async fn deprecate_entries(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    organization_id: Uuid,
+    slugs: &[String],
+    reason: &str,
+) -> Result<u64> {
+    if slugs.is_empty() {
+        return Ok(0);
+    }
+
+    let result = sqlx::query!(
+        r#"
+        UPDATE registry_entries
+        SET deprecated = TRUE,
+            deprecated_at = COALESCE(deprecated_at, NOW()),
+            deprecated_reason = $3,
+            superseded_by_id = NULL,
+            updated_at = NOW()
+        WHERE organization_id = $1 AND slug = ANY($2)
+        "#,
+        organization_id,
+        slugs,
+        reason
+    )
+    .execute(&mut **tx)
+    .await
+    .context("Failed to deprecate withdrawn identifiers")?;
+
+    Ok(result.rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::PgPool;
+
+    async fn insert_entry(pool: &PgPool, org_id: Uuid, slug: &str) -> sqlx::Result<Uuid> {
+        sqlx::query_scalar(
+            r#"
+            WITH entry AS (
+                INSERT INTO registry_entries (organization_id, slug, name, entry_type)
+                VALUES ($1, $2, $2, 'data_source')
+                RETURNING id
+            )
+            INSERT INTO data_sources (id, source_type)
+            SELECT id, 'protein' FROM entry
+            RETURNING id
+            "#,
+        )
+        .bind(org_id)
+        .bind(slug)
+        .fetch_one(pool)
+        .await
+    }
+
+    #[sqlx::test]
+    async fn test_record_successions(pool: PgPool) -> sqlx::Result<()> {
+        let org_id = sqlx::query_scalar::<_, Uuid>(
+            "INSERT INTO organizations (slug, name, is_system) VALUES ('uniprot', 'UniProt', true) RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let old_id = insert_entry(&pool, org_id, "Q00001").await?;
+        let new_id = insert_entry(&pool, org_id, "P00001").await?;
+        insert_entry(&pool, org_id, "P00002").await?;
+
+        let mut tx = pool.begin().await?;
+        let deprecated = record_successions(
+            &mut tx,
+            org_id,
+            &[
+                Succession::new("Q00001", "P00001"),
+                // Split identifiers keep their first successor
+                Succession::new("Q00001", "P00002"),
+                // Never stored under the old identifier: alias only
+                Succession::new("Q00003", "P00001"),
+                // Unknown successor: ignored
+                Succession::new("Q00004", "P00009"),
+            ],
+        )
+        .await
+        .unwrap();
+        tx.commit().await?;
+        assert_eq!(deprecated, 1);
+
+        // A later batch listing another successor doesn't repoint it
+        let mut tx = pool.begin().await?;
+        let deprecated =
+            record_successions(&mut tx, org_id, &[Succession::new("Q00001", "P00002")])
+                .await
+                .unwrap();
+        tx.commit().await?;
+        assert_eq!(deprecated, 0);
+
+        let (is_deprecated, reason, superseded_by): (bool, Option<String>, Option<Uuid>) =
+            sqlx::query_as(
+                "SELECT deprecated, deprecated_reason, superseded_by_id FROM registry_entries WHERE id = $1",
+            )
+            .bind(old_id)
+            .fetch_one(&pool)
+            .await?;
+        assert!(is_deprecated);
+        assert_eq!(reason.as_deref(), Some("Merged into P00001"));
+        assert_eq!(superseded_by, Some(new_id));
+
+        let aliases: Vec<(String, Uuid)> = sqlx::query_as(
+            "SELECT alias, data_source_id FROM data_source_aliases WHERE alias_type = 'previous_accession' ORDER BY alias",
+        )
+        .fetch_all(&pool)
+        .await?;
+        assert_eq!(aliases, vec![("Q00001".to_string(), new_id), ("Q00003".to_string(), new_id)]);
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_deprecate_entries(pool: PgPool) -> sqlx::Result<()> {
+        let org_id = sqlx::query_scalar::<_, Uuid>(
+            "INSERT INTO organizations (slug, name, is_system) VALUES ('ncbi', 'NCBI', true) RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let deleted_id = insert_entry(&pool, org_id, "12345").await?;
+        insert_entry(&pool, org_id, "9606").await?;
+
+        let mut tx = pool.begin().await?;
+        let deprecated = deprecate_entries(
+            &mut tx,
+            org_id,
+            &["12345".to_string(), "99999".to_string()],
+            "Deleted from NCBI Taxonomy",
+        )
+        .await
+        .unwrap();
+        tx.commit().await?;
+        assert_eq!(deprecated, 1);
+
+        let deprecated_ids: Vec<Uuid> =
+            sqlx::query_scalar("SELECT id FROM registry_entries WHERE deprecated")
+                .fetch_all(&pool)
+                .await?;
+        assert_eq!(deprecated_ids, vec![deleted_id]);
+        Ok(())
+    }
+}
@@ -52,6 +52,11 @@ impl JobStatus {
             JobStatus::Cancelled => "cancelled",
         }
     }
+
+    /// Whether the job has finished and will not change state again
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, JobStatus::Completed | JobStatus::Failed | JobStatus::Cancelled)
+    }
 }
 
 impl From<String> for JobStatus {
@@ -0,0 +1,396 @@
+//! Upstream release verification
+//!
+//! Providers publish checksum artefacts next to their release files: `.md5`
+//! sidecars on the NCBI and EBI FTP sites, metalinks for UniProt, release
+//! notes and metadata for InterPro and GO. Pipelines check every download
+//! against them before parsing, fail the job on a mismatch, and record the
+//! verified digest and release date on the versions they created. GO
+//! publishes no checksums, so its versions get a release date but no digest.
+
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::fmt;
+use std::path::Path;
+use uuid::Uuid;
+
+use super::checksum::{compute_md5, compute_sha256};
+
+/// Digest algorithm of an upstream checksum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DigestAlgorithm {
+    Md5,
+    Sha256,
+}
+
+impl DigestAlgorithm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Md5 => "md5",
+            Self::Sha256 => "sha256",
+        }
+    }
+
+    /// Lowercase hex digest of `data`
+    pub fn compute(&self, data: &[u8]) -> String {
+        match self {
+            Self::Md5 => compute_md5(data),
+            Self::Sha256 => compute_sha256(data),
+        }
+    }
+}
+
+impl fmt::Display for DigestAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A downloaded file's bytes don't match the checksum published upstream
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error(
+    "Checksum mismatch for {artifact}: upstream {algorithm} is {expected}, downloaded {actual}"
+)]
+pub struct ChecksumMismatch {
+    pub artifact: String,
+    pub algorithm: DigestAlgorithm,
+    pub expected: String,
+    pub actual: String,
+}
+
+/// Digest of a release file as published by the provider
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpstreamDigest {
+    /// File the digest covers, e.g. `new_taxdump.tar.gz`
+    pub artifact: String,
+    pub algorithm: DigestAlgorithm,
+    /// Lowercase hex digest
+    pub value: String,
+}
+
+impl UpstreamDigest {
+    pub fn new(artifact: impl Into<String>, algorithm: DigestAlgorithm, value: &str) -> Self {
+        Self {
+            artifact: artifact.into(),
+            algorithm,
+            value: value.trim().to_lowercase(),
+        }
+    }
+
+    pub fn md5(artifact: impl Into<String>, value: &str) -> Self {
+        Self::new(artifact, DigestAlgorithm::Md5, value)
+    }
+
+    /// Digest read from a checksum file published for `artifact`
+    ///
+    /// Accepts `md5sum` output (`<hex>  <file>`, one or many lines), BSD
+    /// `MD5 (<file>) = <hex>` lines and a bare digest.
+    pub fn from_checksum_file(artifact: &str, content: &str) -> Result<Self> {
+        let value = parse_checksum_file(content, artifact)
+            .with_context(|| format!("No checksum for {} in its checksum file", artifact))?;
+        Ok(Self::md5(artifact, &value))
+    }
+
+    /// Check downloaded bytes against the digest
+    pub fn verify(&self, data: &[u8]) -> Result<(), ChecksumMismatch> {
+        let actual = self.algorithm.compute(data);
+        if actual != self.value {
+            return Err(ChecksumMismatch {
+                artifact: self.artifact.clone(),
+                algorithm: self.algorithm,
+                expected: self.value.clone(),
+                actual,
+            });
+        }
+        Ok(())
+    }
+
+    /// Check a downloaded file against the digest
+    pub async fn verify_file(&self, path: &Path) -> Result<()> {
+        let data = tokio::fs::read(path)
+            .await
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Ok(self.verify(&data)?)
+    }
+}
+
+/// Checksum of `filename` in the content of a checksum file
+///
+/// Lines naming another file are ignored; a file holding a single bare
+/// digest is taken to cover `filename`.
+pub fn parse_checksum_file(content: &str, filename: &str) -> Option<String> {
+    let basename = filename.rsplit('/').next().unwrap_or(filename);
+    let lines: Vec<&str> = content
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect();
+
+    if let [line] = lines.as_slice() {
+        if is_hex_digest(line) {
+            return Some(line.to_lowercase());
+        }
+    }
+
+    lines.iter().find_map(|line| {
+        let (digest, name) = split_checksum_line(line)?;
+        let name = name.rsplit('/').next().unwrap_or(name);
+        (name == basename && is_hex_digest(digest)).then(|| digest.to_lowercase())
+    })
+}
+
+/// `(digest, filename)` of an `md5sum` or BSD-style checksum line
+fn split_checksum_line(line: &str) -> Option<(&str, &str)> {
+    if let Some(rest) = line.strip_prefix("MD5 (") {
+        let (name, digest) = rest.split_once(") = ")?;
+        return Some((digest.trim(), name));
+    }
+    let (digest, name) = line.split_once(char::is_whitespace)?;
+    Some((digest, name.trim().trim_start_matches('*')))
+}
+
+fn is_hex_digest(s: &str) -> bool {
+    matches!(s.len(), 32 | 64) && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Outcome of verifying an upstream release
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpstreamVerification {
+    /// Digest published for the release file; None where the provider
+    /// publishes none and the release was verified from its metadata only
+    pub digest: Option<UpstreamDigest>,
+    /// Release date stated by the provider, where it publishes one
+    pub release_date: Option<NaiveDate>,
+}
+
+/// Record a verified release on the versions of an organization's entries
+///
+/// Versions are matched on their external version, or on their version for
+/// sources that use the upstream release as version. The release date only
+/// fills in dates that are unknown. Returns the number of versions updated.
+pub async fn record_upstream_verification(
+    pool: &PgPool,
+    organization_id: Uuid,
+    external_version: &str,
+    verification: &UpstreamVerification,
+) -> Result<u64> {
+    let result = sqlx::query(
+        r#"
+        UPDATE versions v
+        SET upstream_digest = $3,
+            upstream_digest_algorithm = $4,
+            upstream_verified_at = NOW(),
+            release_date = COALESCE(v.release_date, $5)
+        FROM registry_entries re
+        WHERE v.entry_id = re.id
+          AND re.organization_id = $1
+          AND COALESCE(v.external_version, v.version) = $2
+        "#,
+    )
+    .bind(organization_id)
+    .bind(external_version)
+    .bind(
+        verification
+            .digest
+            .as_ref()
+            .map(|digest| digest.value.as_str()),
+    )
+    .bind(
+        verification
+            .digest
+            .as_ref()
+            .map(|digest| digest.algorithm.as_str()),
+    )
+    .bind(verification.release_date)
+    .execute(pool)
+    .await
+    .context("Failed to record upstream verification")?;
+
+    Ok(result.rows_affected())
+}
+
+/// Record a verified release file on the versions of the entries read from it
+///
+/// For sources whose release spans many files, each with its own digest.
+/// Returns the number of versions updated.
+pub async fn record_upstream_verification_for_entries(
+    pool: &PgPool,
+    entry_ids: &[Uuid],
+    external_version: &str,
+    verification: &UpstreamVerification,
+) -> Result<u64> {
+    let result = sqlx::query(
+        r#"
+        UPDATE versions v
+        SET upstream_digest = $3,
+            upstream_digest_algorithm = $4,
+            upstream_verified_at = NOW(),
+            release_date = COALESCE(v.release_date, $5)
+        WHERE v.entry_id = ANY($1)
+          AND COALESCE(v.external_version, v.version) = $2
+        "#,
+    )
+    .bind(entry_ids)
+    .bind(external_version)
+    .bind(
+        verification
+            .digest
+            .as_ref()
+            .map(|digest| digest.value.as_str()),
+    )
+    .bind(
+        verification
+            .digest
+            .as_ref()
+            .map(|digest| digest.algorithm.as_str()),
+    )
+    .bind(verification.release_date)
+    .execute(pool)
+    .await
+    .context("Failed to record upstream verification")?;
+
+    Ok(result.rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HELLO_MD5: &str = "6cd3556deb0da54bca060b4c39479839";
+
+    #[test]
+    fn test_parse_checksum_file_formats() {
+        assert_eq!(
+            parse_checksum_file(
+                &format!("{}  new_taxdump.tar.gz\n", HELLO_MD5),
+                "new_taxdump.tar.gz"
+            ),
+            Some(HELLO_MD5.to_string())
+        );
+        assert_eq!(
+            parse_checksum_file(
+                &format!("MD5 (gbvrl1.seq.gz) = {}\n", HELLO_MD5.to_uppercase()),
+                "/genbank/gbvrl1.seq.gz"
+            ),
+            Some(HELLO_MD5.to_string())
+        );
+        assert_eq!(
+            parse_checksum_file(&format!("{}\n", HELLO_MD5), "protein2ipr.dat.gz"),
+            Some(HELLO_MD5.to_string())
+        );
+
+        let listing =
+            format!("{} *entry.list\n{}  ./protein2ipr.dat.gz\n", "0".repeat(32), HELLO_MD5);
+        assert_eq!(
+            parse_checksum_file(&listing, "protein2ipr.dat.gz"),
+            Some(HELLO_MD5.to_string())
+        );
+        assert_eq!(parse_checksum_file(&listing, "interpro.xml.gz"), None);
+        assert_eq!(parse_checksum_file("not a checksum", "entry.list"), None);
+    }
+
+    #[test]
+    fn test_verify() {
+        let digest = UpstreamDigest::from_checksum_file(
+            "hello.txt",
+            &format!("{}  hello.txt", HELLO_MD5.to_uppercase()),
+        )
+        .unwrap();
+        assert_eq!(digest.value, HELLO_MD5);
+        assert!(digest.verify(b"Hello, world!").is_ok());
+
+        let err = digest.verify(b"Hello, world?").unwrap_err();
+        assert_eq!(err.artifact, "hello.txt");
+        assert_eq!(err.expected, HELLO_MD5);
+        assert_ne!(err.actual, HELLO_MD5);
+
+        let sha256 = UpstreamDigest::new(
+            "hello.txt",
+            DigestAlgorithm::Sha256,
+            &compute_sha256(b"Hello, world!"),
+        );
+        assert!(sha256.verify(b"Hello, world!").is_ok());
+    }
+
+    #[sqlx::test]
+    async fn test_record_upstream_verification(pool: PgPool) -> sqlx::Result<()> {
+        let org_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO organizations (slug, name, is_system) VALUES ('interpro', 'InterPro', true) RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        for (slug, version, external_version) in [
+            ("ipr000001", "98.0", None),
+            ("ipr000002", "1.0", Some("98.0")),
+            ("ipr000003", "97.0", None),
+        ] {
+            sqlx::query(
+                r#"
+                WITH entry AS (
+                    INSERT INTO registry_entries (organization_id, slug, name, entry_type)
+                    VALUES ($1, $2, $2, 'data_source')
+                    RETURNING id
+                )
+                INSERT INTO versions (entry_id, version, external_version)
+                SELECT id, $3, $4 FROM entry
+                "#,
+            )
+            .bind(org_id)
+            .bind(slug)
+            .bind(version)
+            .bind(external_version)
+            .execute(&pool)
+            .await?;
+        }
+
+        let verification = UpstreamVerification {
+            digest: Some(UpstreamDigest::md5("protein2ipr.dat.gz", HELLO_MD5)),
+            release_date: NaiveDate::from_ymd_opt(2024, 1, 23),
+        };
+        let updated = record_upstream_verification(&pool, org_id, "98.0", &verification)
+            .await
+            .unwrap();
+        assert_eq!(updated, 2);
+
+        let recorded: Vec<(String, Option<String>, Option<String>, Option<NaiveDate>)> =
+            sqlx::query_as(
+                "SELECT v.version, v.upstream_digest, v.upstream_digest_algorithm, v.release_date \
+                 FROM versions v JOIN registry_entries re ON re.id = v.entry_id ORDER BY re.slug",
+            )
+            .fetch_all(&pool)
+            .await?;
+        assert_eq!(recorded[0].1.as_deref(), Some(HELLO_MD5));
+        assert_eq!(recorded[1].2.as_deref(), Some("md5"));
+        assert_eq!(recorded[1].3, NaiveDate::from_ymd_opt(2024, 1, 23));
+        assert_eq!(recorded[2].1, None);
+
+        let entry_id: Uuid =
+            sqlx::query_scalar("SELECT id FROM registry_entries WHERE slug = 'ipr000003'")
+                .fetch_one(&pool)
+                .await?;
+        let release_only = UpstreamVerification {
+            digest: None,
+            release_date: NaiveDate::from_ymd_opt(2023, 11, 2),
+        };
+        let updated =
+            record_upstream_verification_for_entries(&pool, &[entry_id], "97.0", &release_only)
+                .await
+                .unwrap();
+        assert_eq!(updated, 1);
+
+        let (digest, verified, release_date): (Option<String>, bool, Option<NaiveDate>) =
+            sqlx::query_as(
+                "SELECT upstream_digest, upstream_verified_at IS NOT NULL, release_date \
+                 FROM versions WHERE entry_id = $1",
+            )
+            .bind(entry_id)
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(digest, None);
+        assert!(verified);
+        assert_eq!(release_date, NaiveDate::from_ymd_opt(2023, 11, 2));
+        Ok(())
+    }
+}
@@ -296,12 +296,19 @@ impl IngestionWorker {
     }
 
     /// Mark work unit as failed
-    pub async fn fail_work_unit(&self, work_unit_id: Uuid, error_message: &str) -> Result<()> {
+    ///
+    /// The unit goes back to pending until it runs out of retries. Returns
+    /// whether it failed for good.
+    pub async fn fail_work_unit(&self, work_unit_id: Uuid, error_message: &str) -> Result<bool> {
         let result = sqlx::query!(
             r#"
             UPDATE ingestion_work_units
             SET status = CASE
                     WHEN retry_count + 1 >= max_retries THEN 'failed'::text
+                    WHEN EXISTS(
+                        SELECT 1 FROM ingestion_jobs j
+                        WHERE j.id = ingestion_work_units.job_id AND j.status = 'cancelled'
+                    ) THEN 'cancelled'::text
                     ELSE 'pending'::text
                 END,
                 retry_count = retry_count + 1,
@@ -319,7 +326,8 @@ impl IngestionWorker {
         .await
         .context("Failed to mark work unit as failed")?;
 
-        if result.retry_count >= result.max_retries {
+        let failed = result.retry_count >= result.max_retries;
+        if failed {
             tracing::error!(
                 work_unit_id = %work_unit_id,
                 error = %error_message,
@@ -335,7 +343,7 @@ impl IngestionWorker {
             );
         }
 
-        Ok(())
+        Ok(failed)
     }
 
     /// Run worker loop for a job
@@ -1,6 +1,10 @@
 // GenBank/RefSeq FTP configuration
 
+use std::sync::Arc;
+
 use super::models::{Division, SourceDatabase};
+use crate::ingest::common::ftp::FtpConfig;
+use crate::ingest::common::transport::{FtpTransport, Transport, TransportConfig};
 
 /// GenBank FTP configuration
 #[derive(Debug, Clone)]
@@ -34,6 +38,9 @@ pub struct GenbankFtpConfig {
 
     /// Concurrency for parallel processing
     pub concurrency: usize,
+
+    /// Transport to read upstream files through (None for FTP to `host`)
+    pub transport: Option<TransportConfig>,
 }
 
 impl Default for GenbankFtpConfig {
@@ -49,6 +56,7 @@ impl Default for GenbankFtpConfig {
             max_retries: 3,
             batch_size: 500,
             concurrency: 4,
+            transport: None,
         }
     }
 }
@@ -95,6 +103,24 @@ impl GenbankFtpConfig {
         self
     }
 
+    /// Read upstream files through another transport
+    pub fn with_transport(mut self, transport: TransportConfig) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Transport for upstream files
+    pub fn transport(&self) -> Arc<dyn Transport> {
+        match &self.transport {
+            Some(transport) => transport.build(),
+            None => Arc::new(FtpTransport::new(FtpConfig {
+                host: self.host.clone(),
+                port: self.port,
+                ..FtpConfig::default()
+            })),
+        }
+    }
+
     /// Get base path for current source database
     pub fn get_base_path(&self) -> &str {
         match self.source_database {
@@ -3,23 +3,25 @@
 use anyhow::{Context, Result};
 use flate2::read::GzDecoder;
 use std::io::{Cursor, Read};
-use std::time::Duration;
-use suppaftp::FtpStream;
+use std::sync::Arc;
 use tracing::{debug, info, warn};
 
 use super::config::GenbankFtpConfig;
-use super::models::Division;
-use crate::ingest::common::ftp::{MAX_RETRIES, RETRY_DELAY_SECS};
+use super::models::{Division, DivisionFile};
+use crate::ingest::common::transport::Transport;
+use crate::ingest::framework::{ChecksumMismatch, UpstreamDigest};
 
-/// FTP client for downloading GenBank/RefSeq data
+/// Client for downloading GenBank/RefSeq data through the configured transport
 pub struct GenbankFtp {
     config: GenbankFtpConfig,
+    transport: Arc<dyn Transport>,
 }
 
 impl GenbankFtp {
     /// Create a new FTP client
     pub fn new(config: GenbankFtpConfig) -> Self {
-        Self { config }
+        let transport = config.transport();
+        Self { config, transport }
     }
 
     /// Get current release number
@@ -45,49 +47,60 @@ impl GenbankFtp {
 
         info!("Listing files for division {} (pattern: {})", division.as_str(), pattern);
 
-        let mut ftp = self.connect().await?;
-        ftp.cwd(base_path)
-            .context("Failed to change to GenBank directory")?;
-
-        let list = ftp.list(None).context("Failed to list files")?;
-        let mut files = Vec::new();
-
-        // Parse FTP LIST output
-        // Format: "-rw-r--r--   1 ftp anonymous 12345678 Jan 15 12:00 gbvrl1.seq.gz"
-        for line in list {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() < 9 {
-                continue;
-            }
-
-            let filename = parts[8];
-            let size_str = parts[4];
-
-            // Match files for this division
-            if filename.starts_with(division.file_prefix()) && filename.ends_with(".seq.gz") {
-                if let Ok(size) = size_str.parse::<u64>() {
-                    files.push((filename.to_string(), size));
-                }
-            }
-        }
+        let files: Vec<(String, u64)> = self
+            .transport
+            .list_directory(base_path)
+            .await
+            .context("Failed to list files")?
+            .into_iter()
+            .filter(|entry| {
+                !entry.is_directory
+                    && entry.name.starts_with(division.file_prefix())
+                    && entry.name.ends_with(".seq.gz")
+            })
+            .map(|entry| (entry.name, entry.size.unwrap_or(0)))
+            .collect();
 
         info!("Found {} files for division {}", files.len(), division.as_str());
 
         Ok(files)
     }
 
-    /// Download a single GenBank file
-    pub async fn download_division_file(&self, filename: &str) -> Result<Vec<u8>> {
+    /// Download a single GenBank file, verified against its upstream digest
+    pub async fn download_division_file(
+        &self,
+        filename: &str,
+    ) -> Result<(Vec<u8>, UpstreamDigest)> {
         let base_path = self.config.get_base_path();
         let path = format!("{}/{}", base_path, filename);
 
         info!("Downloading: {}", filename);
-        self.download_file(&path).await
+        let data = self.download_file(&path).await?;
+        let digest = self.verify_download(&path, &data).await?;
+        Ok((data, digest))
+    }
+
+    /// Check a downloaded file against the `.md5` file published next to it
+    pub async fn verify_download(&self, path: &str, data: &[u8]) -> Result<UpstreamDigest> {
+        let checksum_path = format!("{}.md5", path);
+        let checksum_file = self
+            .download_file(&checksum_path)
+            .await
+            .with_context(|| format!("Failed to download checksum {}", checksum_path))?;
+        let content =
+            String::from_utf8(checksum_file).context("Checksum file is not valid UTF-8")?;
+
+        let artifact = path.rsplit('/').next().unwrap_or(path);
+        let digest = UpstreamDigest::from_checksum_file(artifact, &content)?;
+        digest.verify(data)?;
+
+        debug!("Verified {} against upstream MD5 {}", artifact, digest.value);
+        Ok(digest)
     }
 
     /// Download and decompress a GenBank file
-    pub async fn download_and_decompress(&self, filename: &str) -> Result<Vec<u8>> {
-        let compressed = self.download_division_file(filename).await?;
+    pub async fn download_and_decompress(&self, filename: &str) -> Result<DivisionFile> {
+        let (compressed, upstream_digest) = self.download_division_file(filename).await?;
         info!("Decompressing {} ({} bytes compressed)", filename, compressed.len());
 
         let cursor = Cursor::new(compressed);
@@ -99,11 +112,15 @@ impl GenbankFtp {
 
         info!("Decompressed {} ({} bytes decompressed)", filename, decompressed.len());
 
-        Ok(decompressed)
+        Ok(DivisionFile {
+            filename: filename.to_string(),
+            data: decompressed,
+            upstream_digest,
+        })
     }
 
     /// Download all files for a division
-    pub async fn download_division(&self, division: &Division) -> Result<Vec<(String, Vec<u8>)>> {
+    pub async fn download_division(&self, division: &Division) -> Result<Vec<DivisionFile>> {
         let files = self.list_division_files(division).await?;
         let mut results = Vec::new();
 
@@ -111,9 +128,11 @@ impl GenbankFtp {
             info!("Downloading {} for division {} ({} bytes)", filename, division.as_str(), size);
 
             match self.download_and_decompress(&filename).await {
-                Ok(data) => {
-                    results.push((filename, data));
+                Ok(file) => {
+                    results.push(file);
                 },
+                // Corrupt upstream data fails the release rather than thinning it
+                Err(e) if e.downcast_ref::<ChecksumMismatch>().is_some() => return Err(e),
                 Err(e) => {
                     warn!("Failed to download {}: {}", filename, e);
                 },
@@ -123,47 +142,10 @@ impl GenbankFtp {
         Ok(results)
     }
 
-    /// Download a file from FTP server (internal helper)
+    /// Download a file into memory (internal helper)
     async fn download_file(&self, path: &str) -> Result<Vec<u8>> {
-        let mut attempts = 0;
-
-        loop {
-            attempts += 1;
-
-            match self.try_download_file(path).await {
-                Ok(data) => return Ok(data),
-                Err(e) if attempts < MAX_RETRIES => {
-                    warn!(
-                        "Download attempt {}/{} failed for {}: {}",
-                        attempts, MAX_RETRIES, path, e
-                    );
-                    tokio::time::sleep(Duration::from_secs(RETRY_DELAY_SECS)).await;
-                },
-                Err(e) => {
-                    return Err(e).context(format!(
-                        "Failed to download {} after {} attempts",
-                        path, MAX_RETRIES
-                    ))
-                },
-            }
-        }
-    }
-
-    /// Single attempt to download a file
-    async fn try_download_file(&self, path: &str) -> Result<Vec<u8>> {
-        let mut ftp = self.connect().await?;
-
-        // Set binary mode
-        ftp.transfer_type(suppaftp::types::FileType::Binary)
-            .context("Failed to set binary mode")?;
-
-        // Download file
         debug!("Retrieving file: {}", path);
-        let cursor = ftp
-            .retr_as_buffer(path)
-            .context(format!("Failed to retrieve file: {}", path))?;
-
-        Ok(cursor.into_inner())
+        Ok(self.transport.download(path).await?.data)
     }
 
     /// List release directories (for historical version discovery)
@@ -174,51 +156,16 @@ impl GenbankFtp {
 
         info!("Listing release directories in: {}", base_path);
 
-        let mut ftp = self.connect().await?;
-        ftp.cwd(base_path)
-            .context("Failed to change to release directory")?;
-
-        let list = ftp.list(None).context("Failed to list directories")?;
-        let mut directories = Vec::new();
-
-        // Parse FTP LIST output to extract directory names
-        for line in list {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.is_empty() {
-                continue;
-            }
-
-            // Check if this is a directory (first char is 'd')
-            if parts[0].starts_with('d') {
-                // Directory name is typically the last part
-                if let Some(name) = parts.last() {
-                    directories.push(name.to_string());
-                }
-            }
-        }
+        let directories = self
+            .transport
+            .list_directories(base_path)
+            .await
+            .context("Failed to list directories")?;
 
         info!("Found {} directories in {}", directories.len(), base_path);
 
         Ok(directories)
     }
-
-    /// Connect to FTP server
-    async fn connect(&self) -> Result<FtpStream> {
-        debug!("Connecting to FTP server: {}:{}", self.config.host, self.config.port);
-
-        let mut ftp = FtpStream::connect(format!("{}:{}", self.config.host, self.config.port))
-            .context("Failed to connect to FTP server")?;
-
-        // Note: Timeout is configured at the TCP socket level via FTP library defaults
-        // set_read_timeout is not available in current suppaftp version
-
-        // Login anonymously
-        ftp.login("anonymous", "anonymous")
-            .context("Failed to login")?;
-
-        debug!("Successfully connected to FTP server");
-        Ok(ftp)
-    }
 }
 
 #[cfg(test)]
@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
 
+use crate::ingest::framework::UpstreamDigest;
+
 /// Source database type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -245,6 +247,22 @@ impl GenbankRecord {
     }
 }
 
+/// A downloaded and decompressed division file
+#[derive(Debug, Clone)]
+pub struct DivisionFile {
+    pub filename: String,
+    pub data: Vec<u8>,
+    /// Digest of the compressed file, as published in its `.md5` sidecar
+    pub upstream_digest: UpstreamDigest,
+}
+
+/// A verified division file and the entries stored from it
+#[derive(Debug, Clone)]
+pub struct VerifiedFile {
+    pub upstream_digest: UpstreamDigest,
+    pub entry_ids: Vec<Uuid>,
+}
+
 /// Pipeline processing result
 #[derive(Debug, Clone)]
 pub struct PipelineResult {
@@ -256,6 +274,8 @@ pub struct PipelineResult {
     pub mappings_created: usize,
     pub bytes_uploaded: u64,
     pub duration_seconds: f64,
+    /// Files the records were read from, to record their upstream digests
+    pub verified_files: Vec<VerifiedFile>,
 }
 
 /// Orchestrator summary result
@@ -18,6 +18,7 @@ use super::ftp::GenbankFtp;
 use super::models::{Division, OrchestratorResult, PipelineResult};
 use super::pipeline::GenbankPipeline;
 use super::version_discovery::VersionDiscovery;
+use crate::ingest::framework::{record_upstream_verification_for_entries, UpstreamVerification};
 use crate::storage::Storage;
 
 pub struct GenbankOrchestrator {
@@ -156,51 +157,53 @@ impl GenbankOrchestrator {
             concurrency
         );
 
-        // Create pipeline for each division
-        let results: Vec<Option<PipelineResult>> = stream::iter(divisions.iter().enumerate())
-            .map(|(index, division): (usize, &Division)| {
-                let pipeline =
-                    GenbankPipeline::new(self.config.clone(), self.db.clone(), self.s3.clone());
-                let division = division.clone();
-                let release = release.to_string();
-                let org_id = organization_id;
-
-                async move {
-                    let div_name = division.as_str();
-
-                    info!("Starting division {} ({} / {})", div_name, index + 1, divisions.len());
-
-                    match pipeline
-                        .run_division(org_id, division.clone(), &release)
-                        .await
-                    {
-                        Ok(result) => {
-                            info!(
-                                "Completed division {} ({} / {}): {} records in {:.2}s",
-                                result.division,
-                                index + 1,
-                                divisions.len(),
-                                result.records_processed,
-                                result.duration_seconds
-                            );
-                            Some(result)
-                        },
-                        Err(e) => {
-                            error!(
-                                "Failed division {} ({} / {}): {}",
-                                div_name,
-                                index + 1,
-                                divisions.len(),
-                                e
-                            );
-                            None
-                        },
+        // Create pipeline for each division. Divisions are passed by value so
+        // the resulting future stays Send for the job worker.
+        let total = divisions.len();
+        let results: Vec<Option<PipelineResult>> =
+            stream::iter(divisions.iter().cloned().enumerate())
+                .map(|(index, division): (usize, Division)| {
+                    let pipeline =
+                        GenbankPipeline::new(self.config.clone(), self.db.clone(), self.s3.clone());
+                    let release = release.to_string();
+                    let org_id = organization_id;
+
+                    async move {
+                        let div_name = division.as_str();
+
+                        info!("Starting division {} ({} / {})", div_name, index + 1, total);
+
+                        match pipeline
+                            .run_division(org_id, division.clone(), &release)
+                            .await
+                        {
+                            Ok(result) => {
+                                info!(
+                                    "Completed division {} ({} / {}): {} records in {:.2}s",
+                                    result.division,
+                                    index + 1,
+                                    total,
+                                    result.records_processed,
+                                    result.duration_seconds
+                                );
+                                Some(result)
+                            },
+                            Err(e) => {
+                                error!(
+                                    "Failed division {} ({} / {}): {}",
+                                    div_name,
+                                    index + 1,
+                                    total,
+                                    e
+                                );
+                                None
+                            },
+                        }
                     }
-                }
-            })
-            .buffer_unordered(concurrency)
-            .collect()
-            .await;
+                })
+                .buffer_unordered(concurrency)
+                .collect()
+                .await;
 
         // Filter out failures
         let successful: Vec<PipelineResult> = results.into_iter().flatten().collect();
@@ -215,9 +218,34 @@ impl GenbankOrchestrator {
             return Err(anyhow::anyhow!("All divisions failed"));
         }
 
+        for result in &successful {
+            self.record_verified_files(result).await?;
+        }
+
         Ok(successful)
     }
 
+    /// Record the upstream digest of each file on the entries stored from it
+    async fn record_verified_files(&self, result: &PipelineResult) -> Result<()> {
+        for file in &result.verified_files {
+            let verification = UpstreamVerification {
+                digest: Some(file.upstream_digest.clone()),
+                release_date: None,
+            };
+            record_upstream_verification_for_entries(
+                &self.db,
+                &file.entry_ids,
+                &result.release,
+                &verification,
+            )
+            .await
+            .with_context(|| {
+                format!("Failed to record verification of {}", file.upstream_digest.artifact)
+            })?;
+        }
+        Ok(())
+    }
+
     /// Run ingestion for a single division (convenience method)
     pub async fn run_single_division(
         &self,
@@ -232,9 +260,11 @@ impl GenbankOrchestrator {
 
         // Run pipeline
         let pipeline = GenbankPipeline::new(self.config.clone(), self.db.clone(), self.s3.clone());
-        pipeline
+        let result = pipeline
             .run_division(organization_id, division, &release)
-            .await
+            .await?;
+        self.record_verified_files(&result).await?;
+        Ok(result)
     }
 
     /// Run ingestion for test division (phage - smallest)
@@ -1,9 +1,9 @@
 // GenBank/RefSeq ingestion pipeline
 //
 // Orchestrates the complete ingestion process for a single GenBank file or division:
-// 1. Download file(s) via FTP
+// 1. Download file(s) via FTP, verified against their upstream MD5s
 // 2. Parse GenBank records
-// 3. Store metadata in PostgreSQL (batch operations)
+// 3. Store metadata in PostgreSQL (batch operations), one file at a time
 // 4. Upload sequences to S3
 // 5. Create protein mappings
 
@@ -15,7 +15,7 @@ use uuid::Uuid;
 
 use super::config::GenbankFtpConfig;
 use super::ftp::GenbankFtp;
-use super::models::{Division, PipelineResult};
+use super::models::{Division, PipelineResult, VerifiedFile};
 use super::parser::GenbankParser;
 use super::storage::GenbankStorage;
 use crate::storage::Storage;
@@ -63,39 +63,7 @@ impl GenbankPipeline {
 
         info!("Downloaded {} files for division {}", files.len(), division.as_str());
 
-        // Step 2: Parse all files
-        let parser = GenbankParser::new(self.config.source_database);
-        let mut all_records = Vec::new();
-
-        for (filename, data) in files {
-            info!("Parsing file: {} ({} bytes)", filename, data.len());
-
-            let records = if let Some(limit) = self.config.parse_limit {
-                parser.parse_with_limit(data.as_slice(), limit)?
-            } else {
-                parser.parse_all(data.as_slice())?
-            };
-
-            info!("Parsed {} records from {}", records.len(), filename);
-            all_records.extend(records);
-
-            // Check if we've hit the parse limit
-            if let Some(limit) = self.config.parse_limit {
-                if all_records.len() >= limit {
-                    info!("Reached parse limit of {}, stopping", limit);
-                    all_records.truncate(limit);
-                    break;
-                }
-            }
-        }
-
-        info!(
-            "Parsed total of {} records for division {}",
-            all_records.len(),
-            division.as_str()
-        );
-
-        // Step 3: Store records
+        // Step 2: Set up storage
         let storage = GenbankStorage::new(
             self.db.clone(),
             self.s3.clone(),
@@ -111,20 +79,55 @@ impl GenbankPipeline {
             .await
             .context("Failed to setup citation policy")?;
 
-        let stats = storage
-            .store_records(&all_records)
-            .await
-            .context("Failed to store records")?;
+        // Step 3: Parse and store each file, keeping the entries stored from it
+        let parser = GenbankParser::new(self.config.source_database);
+        let mut remaining = self.config.parse_limit;
+        let mut verified_files = Vec::new();
+        let (mut total, mut stored, mut mappings_created, mut bytes_uploaded) = (0, 0, 0, 0);
+
+        for file in files {
+            if remaining == Some(0) {
+                info!("Reached parse limit, stopping");
+                break;
+            }
+
+            info!("Parsing file: {} ({} bytes)", file.filename, file.data.len());
+
+            let records = if let Some(limit) = remaining {
+                parser.parse_with_limit(file.data.as_slice(), limit)?
+            } else {
+                parser.parse_all(file.data.as_slice())?
+            };
+            if let Some(limit) = remaining.as_mut() {
+                *limit = limit.saturating_sub(records.len());
+            }
+
+            info!("Parsed {} records from {}", records.len(), file.filename);
+
+            let stats = storage
+                .store_records(&records)
+                .await
+                .with_context(|| format!("Failed to store records of {}", file.filename))?;
+
+            total += stats.total;
+            stored += stats.stored;
+            mappings_created += stats.mappings_created;
+            bytes_uploaded += stats.bytes_uploaded;
+            verified_files.push(VerifiedFile {
+                upstream_digest: file.upstream_digest,
+                entry_ids: stats.entry_ids,
+            });
+        }
 
         let duration = start_time.elapsed();
 
         info!(
             "Pipeline complete for division {}: {} records, {} stored, {} mappings, {} bytes uploaded in {:.2}s",
             division.as_str(),
-            stats.total,
-            stats.stored,
-            stats.mappings_created,
-            stats.bytes_uploaded,
+            total,
+            stored,
+            mappings_created,
+            bytes_uploaded,
             duration.as_secs_f64()
         );
 
@@ -132,11 +135,12 @@ impl GenbankPipeline {
             data_source_id: Uuid::new_v4(), // Not used for division-level results
             release: release.to_string(),
             division: division.as_str().to_string(),
-            records_processed: stats.total,
-            sequences_inserted: stats.stored,
-            mappings_created: stats.mappings_created,
-            bytes_uploaded: stats.bytes_uploaded,
+            records_processed: total,
+            sequences_inserted: stored,
+            mappings_created,
+            bytes_uploaded,
             duration_seconds: duration.as_secs_f64(),
+            verified_files,
         })
     }
 
@@ -153,19 +157,19 @@ impl GenbankPipeline {
 
         // Step 1: Download file
         let ftp = GenbankFtp::new(self.config.clone());
-        let data = ftp
+        let file = ftp
             .download_and_decompress(filename)
             .await
             .context("Failed to download file")?;
 
-        info!("Downloaded {} ({} bytes)", filename, data.len());
+        info!("Downloaded {} ({} bytes)", filename, file.data.len());
 
         // Step 2: Parse file
         let parser = GenbankParser::new(self.config.source_database);
         let records = if let Some(limit) = self.config.parse_limit {
-            parser.parse_with_limit(data.as_slice(), limit)?
+            parser.parse_with_limit(file.data.as_slice(), limit)?
         } else {
-            parser.parse_all(data.as_slice())?
+            parser.parse_all(file.data.as_slice())?
         };
 
         info!("Parsed {} records from {}", records.len(), filename);
@@ -215,6 +219,10 @@ impl GenbankPipeline {
             mappings_created: stats.mappings_created,
             bytes_uploaded: stats.bytes_uploaded,
             duration_seconds: duration.as_secs_f64(),
+            verified_files: vec![VerifiedFile {
+                upstream_digest: file.upstream_digest,
+                entry_ids: stats.entry_ids,
+            }],
         })
     }
 
@@ -25,6 +25,8 @@ pub struct StorageStats {
     pub skipped: usize,
     pub mappings_created: usize,
     pub bytes_uploaded: u64,
+    /// Entries created for the stored records
+    pub entry_ids: Vec<Uuid>,
 }
 
 /// Storage handler for GenBank/RefSeq data
@@ -86,6 +88,7 @@ impl GenbankStorage {
                 skipped: 0,
                 mappings_created: 0,
                 bytes_uploaded: 0,
+                entry_ids: Vec::new(),
             });
         }
 
@@ -122,6 +125,7 @@ impl GenbankStorage {
         let total_chunks = (new_records.len() + CHUNK_SIZE - 1) / CHUNK_SIZE;
         let mut total_bytes_uploaded = 0u64;
         let mut total_mappings = 0usize;
+        let mut entry_ids = Vec::with_capacity(new_records.len());
 
         for (chunk_idx, chunk) in new_records.chunks(CHUNK_SIZE).enumerate() {
             info!(
@@ -147,6 +151,7 @@ impl GenbankStorage {
                 .create_protein_mappings_batch(&mut tx, chunk, &data_source_ids)
                 .await?;
             total_mappings += mappings_created;
+            entry_ids.extend(data_source_ids);
 
             info!(
                 "Chunk {} complete: {} records, {} bytes uploaded, {} mappings",
@@ -174,6 +179,7 @@ impl GenbankStorage {
             skipped: existing_hashes.len(),
             mappings_created: total_mappings,
             bytes_uploaded: total_bytes_uploaded,
+            entry_ids,
         })
     }
 
@@ -1,6 +1,9 @@
 // Gene Ontology HTTP Configuration
 
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::ingest::common::transport::{Transport, TransportConfig};
 
 /// Configuration for Gene Ontology HTTP downloads
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,6 +37,10 @@ pub struct GoHttpConfig {
 
     /// Citation text for this release
     pub citation: Option<String>,
+
+    /// Transport to read upstream files through (None for the host of each URL)
+    #[serde(default)]
+    pub transport: Option<TransportConfig>,
 }
 
 impl Default for GoHttpConfig {
@@ -50,6 +57,7 @@ impl Default for GoHttpConfig {
             local_ontology_path: None,
             zenodo_doi: None,
             citation: None,
+            transport: None,
         }
     }
 }
@@ -60,6 +68,35 @@ impl GoHttpConfig {
         GoHttpConfigBuilder::default()
     }
 
+    /// Read upstream files through another transport
+    pub fn with_transport(mut self, transport: TransportConfig) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Transport and upstream path of a download URL
+    ///
+    /// GO files live on several hosts, so each URL is read from its own host
+    /// unless a transport is configured, which then serves all of them under
+    /// their upstream paths.
+    pub fn resolve_url(&self, url: &str) -> Result<(Arc<dyn Transport>, String), String> {
+        let (scheme, rest) = url
+            .split_once("://")
+            .ok_or_else(|| format!("Invalid URL '{}'", url))?;
+        let (host, path) = match rest.split_once('/') {
+            Some((host, path)) => (host, format!("/{}", path)),
+            None => (rest, "/".to_string()),
+        };
+
+        let transport = match &self.transport {
+            Some(transport) => transport.build(),
+            None => TransportConfig::parse(&format!("{}://{}", scheme, host))
+                .map_err(|e| format!("{:#}", e))?
+                .build(),
+        };
+        Ok((transport, path))
+    }
+
     /// Get URL for GO ontology OBO file
     ///
     /// # Arguments
@@ -81,6 +118,16 @@ impl GoHttpConfig {
         }
     }
 
+    /// Get URL of the release metadata (`release-date.json`) published next
+    /// to the ontology of a release
+    pub fn release_date_url_for_version(&self, version: Option<&str>) -> String {
+        let ontology_url = self.ontology_url_for_version(version);
+        let release_url = ontology_url
+            .strip_suffix("/ontology/go-basic.obo")
+            .unwrap_or(&ontology_url);
+        format!("{}/metadata/release-date.json", release_url)
+    }
+
     /// Get URL for GO ontology OBO file (using configured version)
     pub fn ontology_url(&self) -> String {
         self.ontology_url_for_version(None)
@@ -136,6 +183,7 @@ pub struct GoHttpConfigBuilder {
     local_ontology_path: Option<String>,
     zenodo_doi: Option<String>,
     citation: Option<String>,
+    transport: Option<TransportConfig>,
 }
 
 impl GoHttpConfigBuilder {
@@ -189,6 +237,11 @@ impl GoHttpConfigBuilder {
         self
     }
 
+    pub fn transport(mut self, transport: TransportConfig) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
     pub fn build(self) -> GoHttpConfig {
         let default = GoHttpConfig::default();
 
@@ -209,6 +262,7 @@ impl GoHttpConfigBuilder {
             local_ontology_path: self.local_ontology_path,
             zenodo_doi: self.zenodo_doi,
             citation: self.citation,
+            transport: self.transport,
         }
     }
 }
@@ -231,6 +285,7 @@ impl GoHttpConfig {
             local_ontology_path: None,
             zenodo_doi: None,
             citation: None,
+            transport: None,
         }
     }
 
@@ -254,6 +309,7 @@ impl GoHttpConfig {
                 "Gene Ontology data from the {} release (DOI: {}) is made available under the terms of the Creative Commons Attribution 4.0 International license (CC BY 4.0).",
                 release_date, zenodo_doi
             )),
+            transport: None,
         }
     }
 
@@ -275,6 +331,7 @@ impl GoHttpConfig {
             local_ontology_path: None,
             zenodo_doi: None,
             citation: None,
+            transport: None,
         }
     }
 }
@@ -309,6 +366,7 @@ impl GoHttpConfig {
             local_ontology_path: std::env::var("GO_LOCAL_ONTOLOGY_PATH").ok(),
             zenodo_doi: std::env::var("GO_ZENODO_DOI").ok(),
             citation: std::env::var("GO_CITATION").ok(),
+            transport: None,
         }
     }
 }
@@ -371,6 +429,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_resolve_url() {
+        let url = "http://release.geneontology.org/2025-09-08/ontology/go-basic.obo";
+
+        let (transport, path) = GoHttpConfig::default().resolve_url(url).unwrap();
+        assert_eq!(transport.location(), "http://release.geneontology.org");
+        assert_eq!(path, "/2025-09-08/ontology/go-basic.obo");
+
+        let config = GoHttpConfig::default().with_transport(TransportConfig::Local {
+            root: "/srv/mirror/go".into(),
+        });
+        let (transport, path) = config.resolve_url(url).unwrap();
+        assert_eq!(transport.location(), "file:///srv/mirror/go");
+        assert_eq!(path, "/2025-09-08/ontology/go-basic.obo");
+
+        assert!(config.resolve_url("release.geneontology.org").is_err());
+    }
+
     #[test]
     fn test_builder_pattern() {
         let config = GoHttpConfig::builder()
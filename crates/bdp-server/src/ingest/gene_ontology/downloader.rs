@@ -1,16 +1,15 @@
 // Gene Ontology Downloader (HTTP and FTP)
 
-use crate::ingest::gene_ontology::{GoHttpConfig, Result};
+use crate::ingest::framework::UpstreamVerification;
+use crate::ingest::gene_ontology::{GoError, GoHttpConfig, Result};
+use chrono::NaiveDate;
 use flate2::read::GzDecoder;
-use reqwest::Client;
 use std::io::Read;
 use std::time::Duration;
-use suppaftp::{FtpStream, Mode};
 use tracing::{info, warn};
 
-/// HTTP client for downloading GO files
+/// Downloader for GO files over HTTP, FTP or a configured transport
 pub struct GoDownloader {
-    client: Client,
     config: GoHttpConfig,
 }
 
@@ -21,12 +20,7 @@ impl GoDownloader {
             .validate()
             .map_err(|e| crate::ingest::gene_ontology::GoError::Validation(e))?;
 
-        let client = Client::builder()
-            .timeout(Duration::from_secs(config.timeout_secs))
-            .user_agent("BDP-Gene-Ontology-Ingester/1.0")
-            .build()?;
-
-        Ok(GoDownloader { client, config })
+        Ok(GoDownloader { config })
     }
 
     /// Download GO ontology OBO file for a specific version
@@ -69,6 +63,56 @@ impl GoDownloader {
         Ok(text)
     }
 
+    /// Verify a downloaded ontology against the release metadata
+    ///
+    /// The release's `release-date.json` and the OBO `data-version` header
+    /// must both name the release the ontology was downloaded from. GO
+    /// publishes no checksum of its files, so the verification carries no
+    /// digest. Returns `None` for a local ontology file, which has no
+    /// release to check against.
+    pub async fn verify_ontology_version(
+        &self,
+        version: Option<&str>,
+        obo_content: &str,
+    ) -> Result<Option<UpstreamVerification>> {
+        if self.config.local_ontology_path.is_some() {
+            return Ok(None);
+        }
+
+        let ontology_url = self.config.ontology_url_for_version(version);
+        let release = release_from_url(&ontology_url).ok_or_else(|| {
+            GoError::Validation(format!("No release date in ontology URL {}", ontology_url))
+        })?;
+
+        let metadata_url = self.config.release_date_url_for_version(version);
+        let metadata = self.download_with_retry(&metadata_url).await?;
+        let release_date = parse_release_date_metadata(&metadata).ok_or_else(|| {
+            GoError::Validation(format!("Invalid GO release metadata at {}", metadata_url))
+        })?;
+        if release_date != release {
+            return Err(GoError::Validation(format!(
+                "GO release metadata states {} for release {}",
+                release_date, release
+            )));
+        }
+
+        let data_version = obo_data_version(obo_content);
+        if data_version != Some(release) {
+            return Err(GoError::Validation(format!(
+                "GO ontology data-version {} does not match release {}",
+                data_version.map_or("(missing)".to_string(), |d| d.to_string()),
+                release
+            )));
+        }
+
+        info!("Verified GO ontology release {}", release);
+
+        Ok(Some(UpstreamVerification {
+            digest: None,
+            release_date: Some(release_date),
+        }))
+    }
+
     /// Download GO ontology OBO file (or load from local file if configured)
     pub async fn download_ontology(&self) -> Result<String> {
         self.download_ontology_version(None).await
@@ -132,18 +176,12 @@ impl GoDownloader {
         Ok(decompressed)
     }
 
-    /// Download URL with retry logic (supports HTTP and FTP)
+    /// Download URL with retry logic
     async fn download_with_retry(&self, url: &str) -> Result<Vec<u8>> {
         let mut last_error = None;
 
         for attempt in 1..=self.config.max_retries {
-            let result = if url.starts_with("ftp://") {
-                self.download_ftp(url).await
-            } else {
-                self.download_url(url).await
-            };
-
-            match result {
+            match self.download_url(url).await {
                 Ok(content) => return Ok(content),
                 Err(e) => {
                     warn!("Download attempt {}/{} failed: {}", attempt, self.config.max_retries, e);
@@ -170,67 +208,10 @@ impl GoDownloader {
         }
     }
 
-    /// Download URL without retry
+    /// Download URL without retry, through its host or the configured transport
     async fn download_url(&self, url: &str) -> Result<Vec<u8>> {
-        let response = self.client.get(url).send().await?;
-
-        if !response.status().is_success() {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                format!("HTTP error: {}", response.status()),
-            )
-            .into());
-        }
-
-        let bytes = response.bytes().await?;
-        Ok(bytes.to_vec())
-    }
-
-    /// Download from FTP server
-    async fn download_ftp(&self, url: &str) -> Result<Vec<u8>> {
-        // Parse FTP URL: ftp://server/path/to/file
-        let url_without_protocol = url.strip_prefix("ftp://").ok_or_else(|| {
-            std::io::Error::new(std::io::ErrorKind::InvalidInput, "Invalid FTP URL")
-        })?;
-
-        let (server_and_path, _) = url_without_protocol
-            .split_once('/')
-            .unwrap_or((url_without_protocol, ""));
-        let server = server_and_path.split('/').next().unwrap_or(server_and_path);
-        let path = url.strip_prefix(&format!("ftp://{}", server)).unwrap_or("");
-
-        info!("Connecting to FTP server: {}", server);
-
-        // Run FTP operations in blocking thread
-        let server = server.to_string();
-        let path = path.to_string();
-
-        tokio::task::spawn_blocking(move || {
-            let mut ftp_stream = FtpStream::connect(format!("{}:21", server))
-                .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
-
-            ftp_stream
-                .login("anonymous", "anonymous@")
-                .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
-
-            // Enable passive mode for firewall/NAT compatibility
-            ftp_stream.set_mode(Mode::Passive);
-
-            info!("Downloading file: {}", path);
-
-            let cursor = ftp_stream
-                .retr_as_buffer(&path)
-                .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
-
-            ftp_stream
-                .quit()
-                .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
-
-            Ok::<Vec<u8>, std::io::Error>(cursor.into_inner())
-        })
-        .await
-        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?
-        .map_err(|e| crate::ingest::gene_ontology::GoError::Io(e))
+        let (transport, path) = self.config.resolve_url(url).map_err(GoError::Validation)?;
+        Ok(transport.download(&path).await?.data)
     }
 
     /// Decompress gzip data
@@ -251,6 +232,32 @@ impl GoDownloader {
     }
 }
 
+/// Release date in a release archive URL, e.g.
+/// `http://release.geneontology.org/2025-09-08/ontology/go-basic.obo`
+fn release_from_url(url: &str) -> Option<NaiveDate> {
+    url.split('/')
+        .find_map(|segment| NaiveDate::parse_from_str(segment, "%Y-%m-%d").ok())
+}
+
+/// Release date of a `release-date.json` (`{"date": "2025-09-08"}`)
+fn parse_release_date_metadata(content: &[u8]) -> Option<NaiveDate> {
+    let metadata: serde_json::Value = serde_json::from_slice(content).ok()?;
+    NaiveDate::parse_from_str(metadata.get("date")?.as_str()?, "%Y-%m-%d").ok()
+}
+
+/// Release date of the OBO `data-version: releases/2025-09-08` header
+fn obo_data_version(obo_content: &str) -> Option<NaiveDate> {
+    obo_content
+        .lines()
+        .take_while(|line| !line.starts_with('['))
+        .find_map(|line| line.strip_prefix("data-version:"))
+        .and_then(|value| {
+            let value = value.trim();
+            let date = value.strip_prefix("releases/").unwrap_or(value);
+            NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+        })
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -275,6 +282,28 @@ mod tests {
         assert!(downloader.is_err());
     }
 
+    #[test]
+    fn test_release_metadata_parsing() {
+        let release = NaiveDate::from_ymd_opt(2025, 9, 8);
+        assert_eq!(
+            release_from_url("http://release.geneontology.org/2025-09-08/ontology/go-basic.obo"),
+            release
+        );
+        assert_eq!(parse_release_date_metadata(br#"{"date": "2025-09-08"}"#), release);
+        assert_eq!(parse_release_date_metadata(b"<html>"), None);
+
+        let obo =
+            "format-version: 1.2\ndata-version: releases/2025-09-08\n\n[Term]\nid: GO:0000001\n";
+        assert_eq!(obo_data_version(obo), release);
+        assert_eq!(obo_data_version("format-version: 1.2\n\n[Term]\ndata-version: x\n"), None);
+
+        let config = GoHttpConfig::default();
+        assert_eq!(
+            config.release_date_url_for_version(Some("2025-09-08")),
+            "http://release.geneontology.org/2025-09-08/metadata/release-date.json"
+        );
+    }
+
     #[tokio::test]
     #[ignore] // Ignore by default (requires network)
     async fn test_download_ontology() {
@@ -69,6 +69,9 @@ pub enum GoError {
 
     #[error("Decompression error: {0}")]
     Decompression(String),
+
+    #[error("Transfer error: {0:#}")]
+    Transfer(#[from] anyhow::Error),
 }
 
 impl From<regex::Error> for GoError {
@@ -6,7 +6,11 @@
 // - Batch processing with PostgreSQL
 // - Version tracking and attribution
 
-use crate::ingest::gene_ontology::{GoDownloader, GoHttpConfig, GoParser, GoStorage, Result};
+use crate::ingest::framework::record_upstream_verification;
+use crate::ingest::gene_ontology::{
+    GoDownloader, GoError, GoHttpConfig, GoParser, GoStorage, Result,
+};
+use crate::ingest::version_mapping::{VersionMapper, VersionMapping};
 use crate::storage::Storage;
 use sqlx::PgPool;
 use tracing::info;
@@ -115,6 +119,9 @@ impl GoPipeline {
             obo_content.len(),
             obo_content.len() / 1024
         );
+        let verification = downloader
+            .verify_ontology_version(None, &obo_content)
+            .await?;
 
         // 2. Upload to S3
         info!("Step 2/4: Uploading ontology to S3...");
@@ -153,6 +160,37 @@ impl GoPipeline {
             )
             .await?;
 
+        let release_date = verification
+            .as_ref()
+            .and_then(|verification| verification.release_date);
+        if let Some(verification) = verification {
+            record_upstream_verification(
+                &self.db,
+                self.organization_id,
+                &config.go_release_version,
+                &verification,
+            )
+            .await
+            .map_err(|e| GoError::Validation(e.to_string()))?;
+        }
+
+        let mapper = VersionMapper::new(self.db.clone());
+        let organization_slug = mapper
+            .organization_slug(self.organization_id)
+            .await
+            .map_err(|e| GoError::Validation(format!("{:#}", e)))?;
+        mapper
+            .record(
+                &VersionMapping::organization(
+                    organization_slug,
+                    &config.go_release_version,
+                    internal_version,
+                )
+                .with_release_date(release_date),
+            )
+            .await
+            .map_err(|e| GoError::Validation(format!("{:#}", e)))?;
+
         info!("GO ontology ingestion completed");
 
         Ok(PipelineStats {
@@ -162,6 +200,19 @@ impl GoPipeline {
         })
     }
 
+    /// Internal version to publish a GO release under
+    pub async fn internal_version(&self, external_version: &str) -> Result<String> {
+        let mapper = VersionMapper::new(self.db.clone());
+        let organization_slug = mapper
+            .organization_slug(self.organization_id)
+            .await
+            .map_err(|e| GoError::Validation(format!("{:#}", e)))?;
+        mapper
+            .next_internal_version(&organization_slug, None, external_version)
+            .await
+            .map_err(|e| GoError::Validation(e.to_string()))
+    }
+
     /// Run ontology ingestion only (using configured version)
     ///
     /// Follows BDP pattern:
@@ -5,10 +5,7 @@
 
 use chrono::NaiveDate;
 use regex::Regex;
-use reqwest::Client;
-use scraper::{Html, Selector};
 use sqlx::PgPool;
-use std::time::Duration;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
@@ -48,21 +45,12 @@ impl_version_ordering!(DiscoveredVersion);
 
 /// Gene Ontology version discovery service
 pub struct VersionDiscovery {
-    _config: GoHttpConfig,
-    client: Client,
+    config: GoHttpConfig,
 }
 
 impl VersionDiscovery {
     pub fn new(config: GoHttpConfig) -> Result<Self> {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(config.timeout_secs))
-            .user_agent("BDP-Gene-Ontology-Ingester/1.0")
-            .build()?;
-
-        Ok(Self {
-            _config: config,
-            client,
-        })
+        Ok(Self { config })
     }
 
     /// Discover all available versions from HTTP release archive
@@ -74,91 +62,64 @@ impl VersionDiscovery {
 
         let base_url = "http://release.geneontology.org/";
 
-        // Fetch the directory listing HTML
-        let html = self.fetch_directory_listing(base_url).await?;
+        // List the dated release directories
+        let (transport, path) = self
+            .config
+            .resolve_url(base_url)
+            .map_err(GoError::Validation)?;
+        debug!("Listing {} through {}", path, transport.location());
+        let directories = transport.list_directories(&path).await?;
 
-        // Parse the HTML to extract dated directories
-        let versions = self.parse_directory_listing(&html, base_url)?;
+        let versions = self.parse_directory_listing(&directories, base_url)?;
 
         info!(count = versions.len(), "Discovered {} GO release versions", versions.len());
 
         Ok(versions)
     }
 
-    /// Fetch HTML directory listing from URL
-    async fn fetch_directory_listing(&self, url: &str) -> Result<String> {
-        debug!("Fetching directory listing from: {}", url);
-
-        let response = self.client.get(url).send().await?;
-
-        if !response.status().is_success() {
-            return Err(GoError::Io(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                format!("HTTP error: {}", response.status()),
-            )));
-        }
-
-        let html = response.text().await?;
-
-        Ok(html)
-    }
-
-    /// Parse HTML directory listing to extract dated releases
+    /// Extract dated releases from the directories of the release archive
     ///
-    /// Looks for links matching the YYYY-MM-DD pattern
+    /// Looks for names matching the YYYY-MM-DD pattern
     fn parse_directory_listing(
         &self,
-        html: &str,
+        directories: &[String],
         base_url: &str,
     ) -> Result<Vec<DiscoveredVersion>> {
-        let document = Html::parse_document(html);
-
-        // Try multiple selectors to handle different HTML structures
-        // AWS S3, Apache, nginx all have different HTML formats
-        #[allow(clippy::unwrap_used)]
-        let link_selector = Selector::parse("a").unwrap();
-
         // Regex for matching YYYY-MM-DD format
         let date_pattern = Regex::new(r"^(\d{4})-(\d{2})-(\d{2})/?$")?;
 
         let mut versions = Vec::new();
 
-        for element in document.select(&link_selector) {
-            // Get the href attribute
-            if let Some(href) = element.value().attr("href") {
-                // Clean up the href (remove trailing slashes)
-                let href_clean = href.trim_end_matches('/');
-
-                // Check if it matches the date pattern
-                if let Some(captures) = date_pattern.captures(href_clean) {
-                    let year: i32 = captures[1].parse()?;
-                    let month: u32 = captures[2].parse()?;
-                    let day: u32 = captures[3].parse()?;
-
-                    // Validate the date
-                    if let Some(release_date) = NaiveDate::from_ymd_opt(year, month, day) {
-                        let external_version = format!("{:04}-{:02}-{:02}", year, month, day);
-                        let release_url = format!("{}{}/", base_url, external_version);
-
-                        versions.push(DiscoveredVersion {
-                            external_version: external_version.clone(),
-                            release_date,
-                            release_url,
-                        });
-
-                        debug!(
-                            version = %external_version,
-                            date = %release_date,
-                            "Discovered GO version"
-                        );
-                    } else {
-                        warn!(
-                            year = year,
-                            month = month,
-                            day = day,
-                            "Invalid date in directory listing"
-                        );
-                    }
+        for name in directories {
+            // Check if it matches the date pattern
+            if let Some(captures) = date_pattern.captures(name.trim_end_matches('/')) {
+                let year: i32 = captures[1].parse()?;
+                let month: u32 = captures[2].parse()?;
+                let day: u32 = captures[3].parse()?;
+
+                // Validate the date
+                if let Some(release_date) = NaiveDate::from_ymd_opt(year, month, day) {
+                    let external_version = format!("{:04}-{:02}-{:02}", year, month, day);
+                    let release_url = format!("{}{}/", base_url, external_version);
+
+                    versions.push(DiscoveredVersion {
+                        external_version: external_version.clone(),
+                        release_date,
+                        release_url,
+                    });
+
+                    debug!(
+                        version = %external_version,
+                        date = %release_date,
+                        "Discovered GO version"
+                    );
+                } else {
+                    warn!(
+                        year = year,
+                        month = month,
+                        day = day,
+                        "Invalid date in directory listing"
+                    );
                 }
             }
         }
@@ -437,19 +398,11 @@ mod tests {
         let config = GoHttpConfig::default();
         let discovery = VersionDiscovery::new(config).unwrap();
 
-        let html = r#"
-            <html>
-            <body>
-                <a href="2024-11-01/">2024-11-01/</a>
-                <a href="2024-12-01/">2024-12-01/</a>
-                <a href="2025-01-01/">2025-01-01/</a>
-                <a href="other-file.txt">other-file.txt</a>
-            </body>
-            </html>
-        "#;
+        let directories =
+            ["2025-01-01", "2024-11-01", "2024-12-01", "2024-13-01", "current"].map(str::to_string);
 
         let versions = discovery
-            .parse_directory_listing(html, "http://release.geneontology.org/")
+            .parse_directory_listing(&directories, "http://release.geneontology.org/")
             .unwrap();
 
         assert_eq!(versions.len(), 3);
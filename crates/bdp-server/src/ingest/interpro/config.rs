@@ -4,6 +4,10 @@
 
 use serde::{Deserialize, Serialize};
 use std::env;
+use std::sync::Arc;
+
+use crate::ingest::common::ftp::FtpConfig;
+use crate::ingest::common::transport::{FtpTransport, Transport, TransportConfig};
 
 /// Configuration for InterPro FTP connection and ingestion
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +29,10 @@ pub struct InterProConfig {
 
     /// Cron schedule for automatic ingestion (e.g., "0 2 * * *" for daily at 2 AM)
     pub schedule: String,
+
+    /// Transport to read upstream files through (None for FTP to `ftp_host`)
+    #[serde(default)]
+    pub transport: Option<TransportConfig>,
 }
 
 impl Default for InterProConfig {
@@ -36,6 +44,7 @@ impl Default for InterProConfig {
             batch_size: 500,
             auto_enabled: false,
             schedule: "0 2 * * *".to_string(), // Daily at 2 AM
+            transport: None,
         }
     }
 }
@@ -75,6 +84,25 @@ impl InterProConfig {
 
             schedule: env::var("INGEST_INTERPRO_SCHEDULE")
                 .unwrap_or_else(|_| "0 2 * * *".to_string()),
+
+            transport: None,
+        }
+    }
+
+    /// Read upstream files through another transport
+    pub fn with_transport(mut self, transport: TransportConfig) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Transport for upstream files
+    pub fn transport(&self) -> Arc<dyn Transport> {
+        match &self.transport {
+            Some(transport) => transport.build(),
+            None => Arc::new(FtpTransport::new(FtpConfig {
+                host: self.ftp_host.clone(),
+                ..FtpConfig::default()
+            })),
         }
     }
 
@@ -88,6 +116,11 @@ impl InterProConfig {
         format!("{}{}/entry.list", self.ftp_path, version)
     }
 
+    /// Get release_notes.txt file path for a release
+    pub fn get_release_notes_path(&self, version: &str) -> String {
+        format!("{}{}/release_notes.txt", self.ftp_path, version)
+    }
+
     /// Get current release version path
     pub fn get_current_release_path(&self) -> String {
         format!("{}current_release/", self.ftp_path)
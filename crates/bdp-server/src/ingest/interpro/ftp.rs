@@ -1,11 +1,15 @@
 // InterPro FTP Downloader
 //
-// Downloads protein2ipr.dat.gz and entry.list files from InterPro FTP server
+// Downloads protein2ipr.dat.gz and entry.list files from the InterPro FTP
+// server, or the transport configured in its place
 
 use crate::error::Error;
+use crate::ingest::common::transport::Transport;
+use crate::ingest::framework::{UpstreamDigest, UpstreamVerification};
 use crate::ingest::interpro::config::InterProConfig;
+use chrono::NaiveDate;
 use std::path::{Path, PathBuf};
-use suppaftp::FtpStream;
+use std::sync::Arc;
 use tracing::{debug, info};
 
 // ============================================================================
@@ -24,66 +28,62 @@ fn is_version_format(s: &str) -> bool {
     parts[0].chars().all(|c| c.is_ascii_digit()) && parts[1].chars().all(|c| c.is_ascii_digit())
 }
 
+/// Release number and date stated by a release_notes.txt
+///
+/// The notes open with the release (`InterPro 98.0` or `Release 98.0`) and
+/// state its date as `23 January 2024` or `2024-01-23`.
+pub fn parse_release_notes(content: &str) -> (Option<String>, Option<NaiveDate>) {
+    let words: Vec<&str> = content
+        .split_whitespace()
+        .map(|w| w.trim_matches(|c: char| matches!(c, ',' | ';' | ':' | '(' | ')')))
+        .collect();
+
+    let version = words.windows(2).find_map(|pair| {
+        let is_label =
+            pair[0].eq_ignore_ascii_case("interpro") || pair[0].eq_ignore_ascii_case("release");
+        let number = pair[1].trim_end_matches('.');
+        (is_label && is_version_format(number)).then(|| number.to_string())
+    });
+
+    let release_date = words.iter().enumerate().find_map(|(i, word)| {
+        let word = word.trim_end_matches('.');
+        if let Ok(date) = NaiveDate::parse_from_str(word, "%Y-%m-%d") {
+            return Some(date);
+        }
+        let text = words.get(i..i + 3)?.join(" ");
+        NaiveDate::parse_from_str(text.trim_end_matches('.'), "%d %B %Y").ok()
+    });
+
+    (version, release_date)
+}
+
 // ============================================================================
 // FTP Downloader
 // ============================================================================
 
-/// InterPro FTP downloader
+/// InterPro downloader, reading the FTP layout through the configured transport
 pub struct InterProFtpDownloader {
     config: InterProConfig,
-    ftp: Option<FtpStream>,
+    transport: Arc<dyn Transport>,
 }
 
 impl InterProFtpDownloader {
-    /// Create a new FTP downloader
+    /// Create a new downloader
     pub fn new(config: InterProConfig) -> Self {
-        Self { config, ftp: None }
-    }
-
-    /// Connect to FTP server
-    pub fn connect(&mut self) -> Result<(), Error> {
-        info!("Connecting to InterPro FTP server: {}", self.config.ftp_host);
-
-        let mut ftp = FtpStream::connect(format!("{}:21", self.config.ftp_host))
-            .map_err(|e| Error::Other(format!("FTP connection failed: {}", e)))?;
-
-        // Anonymous login
-        ftp.login("anonymous", "anonymous")
-            .map_err(|e| Error::Other(format!("FTP login failed: {}", e)))?;
-
-        info!("Successfully connected to InterPro FTP");
-
-        self.ftp = Some(ftp);
-        Ok(())
-    }
-
-    /// Disconnect from FTP server
-    pub fn disconnect(&mut self) -> Result<(), Error> {
-        if let Some(mut ftp) = self.ftp.take() {
-            ftp.quit()
-                .map_err(|e| Error::Other(format!("FTP disconnect failed: {}", e)))?;
-            info!("Disconnected from InterPro FTP");
-        }
-        Ok(())
+        let transport = config.transport();
+        Self { config, transport }
     }
 
     /// Get current release version from FTP
-    pub fn get_current_version(&mut self) -> Result<String, Error> {
-        let ftp = self
-            .ftp
-            .as_mut()
-            .ok_or_else(|| Error::Other("Not connected to FTP".to_string()))?;
-
+    pub async fn get_current_version(&self) -> Result<String, Error> {
         // Read current release version from release_notes.txt or directory listing
         let release_path = self.config.get_current_release_path();
 
-        ftp.cwd(&release_path)
-            .map_err(|e| Error::Other(format!("Failed to change to release directory: {}", e)))?;
-
-        // List files to find version
-        let list = ftp
-            .list(None)
-            .map_err(|e| Error::Other(format!("Failed to list files: {}", e)))?;
+        let list = self
+            .transport
+            .list_directory(&release_path)
+            .await
+            .map_err(|e| Error::Other(format!("Failed to list files: {:#}", e)))?;
 
         debug!("Release directory contents: {:?}", list);
 
@@ -92,90 +92,107 @@ impl InterProFtpDownloader {
     }
 
     /// Download protein2ipr.dat.gz file
-    pub fn download_protein2ipr(
-        &mut self,
+    pub async fn download_protein2ipr(
+        &self,
         version: &str,
         output_dir: &Path,
     ) -> Result<PathBuf, Error> {
-        let ftp = self
-            .ftp
-            .as_mut()
-            .ok_or_else(|| Error::Other("Not connected to FTP".to_string()))?;
-
         let remote_path = self.config.get_protein2ipr_path(version);
         let local_path = output_dir.join(format!("protein2ipr_{}.dat.gz", version));
 
         info!("Downloading protein2ipr.dat.gz from {} to {:?}", remote_path, local_path);
 
-        // Change to directory
-        let dir_path = format!("{}{}", self.config.ftp_path, version);
-        ftp.cwd(&dir_path)
-            .map_err(|e| Error::Other(format!("Failed to change directory: {}", e)))?;
-
-        // Download file
-        let mut reader = ftp
-            .retr_as_buffer("protein2ipr.dat.gz")
-            .map_err(|e| Error::Other(format!("Failed to download file: {}", e)))?;
-
-        // Write to local file
-        let mut file = std::fs::File::create(&local_path)
-            .map_err(|e| Error::Other(format!("Failed to create output file: {}", e)))?;
+        let size = self
+            .transport
+            .download_to(&remote_path, &local_path)
+            .await
+            .map_err(|e| Error::Other(format!("Failed to download file: {:#}", e)))?;
 
-        std::io::copy(&mut reader, &mut file)
-            .map_err(|e| Error::Other(format!("Failed to write file: {}", e)))?;
-
-        info!(
-            "Successfully downloaded protein2ipr.dat.gz ({} bytes)",
-            file.metadata().map(|m| m.len()).unwrap_or(0)
-        );
+        info!("Successfully downloaded protein2ipr.dat.gz ({} bytes)", size);
 
         Ok(local_path)
     }
 
     /// Download entry.list file
-    pub fn download_entry_list(
-        &mut self,
+    pub async fn download_entry_list(
+        &self,
         version: &str,
         output_dir: &Path,
     ) -> Result<PathBuf, Error> {
-        let ftp = self
-            .ftp
-            .as_mut()
-            .ok_or_else(|| Error::Other("Not connected to FTP".to_string()))?;
-
         let remote_path = self.config.get_entry_list_path(version);
         let local_path = output_dir.join(format!("entry_list_{}.txt", version));
 
         info!("Downloading entry.list from {} to {:?}", remote_path, local_path);
 
-        // Change to directory
-        let dir_path = format!("{}{}", self.config.ftp_path, version);
-        ftp.cwd(&dir_path)
-            .map_err(|e| Error::Other(format!("Failed to change directory: {}", e)))?;
+        let size = self
+            .transport
+            .download_to(&remote_path, &local_path)
+            .await
+            .map_err(|e| Error::Other(format!("Failed to download file: {:#}", e)))?;
 
-        // Download file
-        let mut reader = ftp
-            .retr_as_buffer("entry.list")
-            .map_err(|e| Error::Other(format!("Failed to download file: {}", e)))?;
+        info!("Successfully downloaded entry.list ({} bytes)", size);
 
-        // Write to local file
-        let mut file = std::fs::File::create(&local_path)
-            .map_err(|e| Error::Other(format!("Failed to create output file: {}", e)))?;
+        Ok(local_path)
+    }
 
-        std::io::copy(&mut reader, &mut file)
-            .map_err(|e| Error::Other(format!("Failed to write file: {}", e)))?;
+    /// Download a small file of a release into memory
+    async fn download_release_file(&self, version: &str, filename: &str) -> Result<Vec<u8>, Error> {
+        let path = format!("{}{}/{}", self.config.ftp_path, version, filename);
+        let result = self
+            .transport
+            .download(&path)
+            .await
+            .map_err(|e| Error::Other(format!("Failed to download {}: {:#}", filename, e)))?;
+        Ok(result.data)
+    }
+
+    /// Verify a downloaded release against its release notes and checksum
+    ///
+    /// The release notes must describe `version`; protein2ipr.dat.gz must
+    /// match the MD5 published next to it.
+    pub async fn verify_release(
+        &self,
+        version: &str,
+        protein2ipr_path: &Path,
+    ) -> Result<UpstreamVerification, Error> {
+        let notes = self
+            .download_release_file(version, "release_notes.txt")
+            .await?;
+        let (notes_version, release_date) = parse_release_notes(&String::from_utf8_lossy(&notes));
+        if notes_version.as_deref() != Some(version) {
+            return Err(Error::Other(format!(
+                "Release notes of InterPro {} describe release {}",
+                version,
+                notes_version.as_deref().unwrap_or("(unknown)")
+            )));
+        }
+
+        let checksum = self
+            .download_release_file(version, "protein2ipr.dat.gz.md5")
+            .await?;
+        let digest = UpstreamDigest::from_checksum_file(
+            "protein2ipr.dat.gz",
+            &String::from_utf8_lossy(&checksum),
+        )
+        .map_err(|e| Error::Other(e.to_string()))?;
+        let data = std::fs::read(protein2ipr_path)?;
+        digest
+            .verify(&data)
+            .map_err(|e| Error::Other(e.to_string()))?;
 
         info!(
-            "Successfully downloaded entry.list ({} bytes)",
-            file.metadata().map(|m| m.len()).unwrap_or(0)
+            "Verified InterPro {} (released {:?}) against upstream MD5 {}",
+            version, release_date, digest.value
         );
-
-        Ok(local_path)
+        Ok(UpstreamVerification {
+            digest: Some(digest),
+            release_date,
+        })
     }
 
     /// Download both required files for a version
-    pub fn download_all(
-        &mut self,
+    pub async fn download_all(
+        &self,
         version: &str,
         output_dir: &Path,
     ) -> Result<(PathBuf, PathBuf), Error> {
@@ -185,8 +202,8 @@ impl InterProFtpDownloader {
         std::fs::create_dir_all(output_dir)
             .map_err(|e| Error::Other(format!("Failed to create output directory: {}", e)))?;
 
-        let protein2ipr_path = self.download_protein2ipr(version, output_dir)?;
-        let entry_list_path = self.download_entry_list(version, output_dir)?;
+        let protein2ipr_path = self.download_protein2ipr(version, output_dir).await?;
+        let entry_list_path = self.download_entry_list(version, output_dir).await?;
 
         info!("Successfully downloaded all files for version {}", version);
 
@@ -197,48 +214,23 @@ impl InterProFtpDownloader {
     ///
     /// Returns a list of version directory names (e.g., ["96.0", "97.0", "98.0"]).
     /// Filters out non-version directories like "current", "tools", etc.
-    pub fn list_versions(&mut self) -> Result<Vec<String>, Error> {
-        let ftp = self
-            .ftp
-            .as_mut()
-            .ok_or_else(|| Error::Other("Not connected to FTP".to_string()))?;
-
-        // Change to base InterPro directory
-        ftp.cwd(&self.config.ftp_path)
-            .map_err(|e| Error::Other(format!("Failed to change directory: {}", e)))?;
-
-        // Use LIST command to get detailed directory listing
-        let list = ftp
-            .list(None)
-            .map_err(|e| Error::Other(format!("Failed to list directories: {}", e)))?;
+    pub async fn list_versions(&self) -> Result<Vec<String>, Error> {
+        let directories = self
+            .transport
+            .list_directories(&self.config.ftp_path)
+            .await
+            .map_err(|e| Error::Other(format!("Failed to list directories: {:#}", e)))?;
 
-        debug!("FTP LIST returned {} entries", list.len());
+        debug!("Listing returned {} directories", directories.len());
 
-        // Parse directory names from LIST output
         let mut versions = Vec::new();
-
-        for entry in list {
-            // FTP LIST format: drwxr-xr-x   2 ftp      ftp          4096 Jan 15 12:00 96.0
-            // We need to extract directory names (entries starting with 'd')
-            let parts: Vec<&str> = entry.split_whitespace().collect();
-
-            if parts.is_empty() {
-                continue;
-            }
-
-            // Check if this is a directory (first char is 'd')
-            if parts[0].starts_with('d') {
-                // Directory name is typically the last part
-                if let Some(name) = parts.last() {
-                    // Filter for version directories only (e.g., "96.0", "97.0")
-                    // Simple check: must be all digits and dots
-                    if is_version_format(name) {
-                        versions.push(name.to_string());
-                        debug!("Found version directory: {}", name);
-                    } else {
-                        debug!("Skipping non-version directory: {}", name);
-                    }
-                }
+        for name in directories {
+            // Filter for version directories only (e.g., "96.0", "97.0")
+            if is_version_format(&name) {
+                debug!("Found version directory: {}", name);
+                versions.push(name);
+            } else {
+                debug!("Skipping non-version directory: {}", name);
             }
         }
 
@@ -251,40 +243,55 @@ impl InterProFtpDownloader {
     }
 }
 
-impl Drop for InterProFtpDownloader {
-    fn drop(&mut self) {
-        let _ = self.disconnect();
-    }
-}
-
 // ============================================================================
 // Tests
 // ============================================================================
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
     use super::*;
+    use crate::ingest::common::transport::TransportConfig;
 
     #[test]
     fn test_downloader_creation() {
         let config = InterProConfig::default();
         let downloader = InterProFtpDownloader::new(config);
 
-        assert!(downloader.ftp.is_none());
+        assert_eq!(downloader.transport.location(), "ftp://ftp.ebi.ac.uk:21");
+    }
+
+    #[tokio::test]
+    async fn test_list_versions_from_mirror() {
+        let mirror = tempfile::tempdir().unwrap();
+        for dir in ["97.0", "98.0", "current_release", "tools"] {
+            std::fs::create_dir_all(mirror.path().join("pub/databases/interpro").join(dir))
+                .unwrap();
+        }
+
+        let config = InterProConfig::default().with_transport(TransportConfig::Local {
+            root: mirror.path().to_path_buf(),
+        });
+        let downloader = InterProFtpDownloader::new(config);
+
+        assert_eq!(downloader.list_versions().await.unwrap(), vec!["97.0", "98.0"]);
     }
 
-    // Note: Connection tests are commented out as they require actual FTP access
-    // They can be enabled for manual testing
-
-    // #[test]
-    // fn test_connect_to_ftp() {
-    //     let config = InterProConfig::default();
-    //     let mut downloader = InterProFtpDownloader::new(config);
-    //
-    //     let result = downloader.connect();
-    //     assert!(result.is_ok());
-    //
-    //     let disconnect_result = downloader.disconnect();
-    //     assert!(disconnect_result.is_ok());
-    // }
+    #[test]
+    fn test_parse_release_notes() {
+        let notes =
+            "Release Notes for InterPro 98.0\n\nInterPro 98.0 was released on 23 January 2024.\n";
+        assert_eq!(
+            parse_release_notes(notes),
+            (Some("98.0".to_string()), NaiveDate::from_ymd_opt(2024, 1, 23))
+        );
+
+        let notes = "Release 97.0, 2023-11-02\nContents: 40,000 entries\n";
+        assert_eq!(
+            parse_release_notes(notes),
+            (Some("97.0".to_string()), NaiveDate::from_ymd_opt(2023, 11, 2))
+        );
+
+        assert_eq!(parse_release_notes("no release here"), (None, None));
+    }
 }
@@ -3,6 +3,7 @@
 // End-to-end pipeline for ingesting InterPro data
 
 use crate::error::Error;
+use crate::ingest::framework::UpstreamVerification;
 use crate::ingest::interpro::{
     config::InterProConfig,
     ftp::InterProFtpDownloader,
@@ -41,7 +42,8 @@ impl InterProPipeline {
 
         // Step 1: Download files
         info!("Step 1: Downloading files from FTP");
-        let (protein2ipr_path, entry_list_path) = self.download_files(version).await?;
+        let (protein2ipr_path, entry_list_path, verification) =
+            self.download_files(version).await?;
         stats.files_downloaded = 2;
 
         // Step 2: Parse entry list
@@ -86,22 +88,30 @@ impl InterProPipeline {
         stats.matches_stored = matches_stored;
         info!("Stored {} protein matches", matches_stored);
 
+        // Step 7: Record the verified upstream release on the stored versions
+        record_release_verification(&self.pool, version, &verification).await?;
+        record_release_mapping(&self.pool, version, verification.release_date).await?;
+
         info!("InterPro ingestion complete! Stats: {:?}", stats);
 
         Ok(stats)
     }
 
-    /// Download files from FTP
-    async fn download_files(&self, version: &str) -> Result<(PathBuf, PathBuf), Error> {
-        let mut downloader = InterProFtpDownloader::new(self.config.clone());
-
-        downloader.connect()?;
-
-        let result = downloader.download_all(version, &self.download_dir);
+    /// Download files from upstream and verify them against the release notes and
+    /// published checksum
+    async fn download_files(
+        &self,
+        version: &str,
+    ) -> Result<(PathBuf, PathBuf, UpstreamVerification), Error> {
+        let downloader = InterProFtpDownloader::new(self.config.clone());
 
-        downloader.disconnect()?;
+        let (protein2ipr_path, entry_list_path) =
+            downloader.download_all(version, &self.download_dir).await?;
+        let verification = downloader
+            .verify_release(version, &protein2ipr_path)
+            .await?;
 
-        result
+        Ok((protein2ipr_path, entry_list_path, verification))
     }
 
     /// Parse entry list file
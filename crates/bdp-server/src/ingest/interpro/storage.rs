@@ -13,6 +13,7 @@
 // 4. Minimize round trips - combine operations where possible
 
 use crate::error::Error;
+use crate::ingest::framework::{record_upstream_verification, UpstreamVerification};
 use crate::ingest::interpro::helpers::{
     GoTermLookupHelper, InterProEntryLookupHelper, ProteinLookupHelper, SignatureLookupHelper,
 };
@@ -20,6 +21,8 @@ use crate::ingest::interpro::models::{
     ExternalReferenceData, GoMappingData, InterProEntry, InterProMetadata, MemberSignatureData,
     ProteinMatch,
 };
+use crate::ingest::version_mapping::{VersionMapper, VersionMapping};
+use chrono::NaiveDate;
 use sqlx::PgPool;
 use std::collections::{HashMap, HashSet};
 use tracing::{debug, info, warn};
@@ -616,6 +619,49 @@ pub async fn store_interpro_metadata(
     Ok((interpro_ds_id, interpro_ver_id))
 }
 
+// ============================================================================
+// Upstream Verification
+// ============================================================================
+
+/// Record the verified upstream checksum and release date on the versions of
+/// an InterPro release
+///
+/// Returns the number of versions updated.
+pub async fn record_release_verification(
+    pool: &PgPool,
+    version: &str,
+    verification: &UpstreamVerification,
+) -> Result<u64, Error> {
+    let org_id: Option<Uuid> = sqlx::query_scalar("SELECT id FROM organizations WHERE slug = $1")
+        .bind(INTERPRO_ORGANIZATION.to_lowercase())
+        .fetch_optional(pool)
+        .await?;
+    let Some(org_id) = org_id else {
+        warn!("InterPro organization not found, release {} not recorded", version);
+        return Ok(0);
+    };
+
+    record_upstream_verification(pool, org_id, version, verification)
+        .await
+        .map_err(|e| Error::Other(e.to_string()))
+}
+
+/// Record that the versions of an InterPro release are published under the
+/// release number itself
+pub async fn record_release_mapping(
+    pool: &PgPool,
+    version: &str,
+    release_date: Option<NaiveDate>,
+) -> Result<(), Error> {
+    let mapping =
+        VersionMapping::organization(INTERPRO_ORGANIZATION.to_lowercase(), version, version)
+            .with_release_date(release_date);
+    VersionMapper::new(pool.clone())
+        .record(&mapping)
+        .await
+        .map_err(|e| Error::Other(format!("{:#}", e)))
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
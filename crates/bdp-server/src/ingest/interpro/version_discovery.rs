@@ -193,16 +193,14 @@ impl VersionDiscovery {
     /// The current release is in the /current/ or /current_release/ directory.
     /// We need to determine the version number from the directory listing or metadata.
     async fn discover_current_version(&self) -> Result<DiscoveredVersion> {
-        let mut downloader = InterProFtpDownloader::new(self.config.clone());
-        downloader.connect()?;
+        let downloader = InterProFtpDownloader::new(self.config.clone());
 
         // Get current version (this reads from FTP)
         let version_str = downloader
             .get_current_version()
+            .await
             .context("Failed to get current version from FTP")?;
 
-        downloader.disconnect()?;
-
         let (major, minor) = DiscoveredVersion::parse_version(&version_str)?;
         let release_date = DiscoveredVersion::estimate_release_date(major, minor);
 
@@ -223,16 +221,14 @@ impl VersionDiscovery {
     /// - /pub/databases/interpro/97.0/
     /// - /pub/databases/interpro/98.0/
     async fn discover_historical_versions(&self) -> Result<Vec<DiscoveredVersion>> {
-        let mut downloader = InterProFtpDownloader::new(self.config.clone());
-        downloader.connect()?;
+        let downloader = InterProFtpDownloader::new(self.config.clone());
 
         // List all version directories
         let version_dirs = downloader
             .list_versions()
+            .await
             .context("Failed to list version directories from FTP")?;
 
-        downloader.disconnect()?;
-
         tracing::info!(
             count = version_dirs.len(),
             "Found {} version directories on FTP",
@@ -6,6 +6,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::sources::IngestSource;
+
 /// UniProt ingestion job payload
 ///
 /// This job is responsible for syncing UniProt data from their FTP server.
@@ -53,6 +55,74 @@ impl UniProtIngestJob {
     }
 }
 
+/// Ingestion job for one upstream version of a source pipeline
+///
+/// Enqueued by the scheduler and processed by the ingest worker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceIngestJob {
+    /// Pipeline to run
+    pub source: IngestSource,
+    /// Organization the source ingests under
+    pub organization_id: Uuid,
+    /// Upstream version to ingest
+    pub external_version: String,
+    /// Triggered by (user ID or system)
+    pub triggered_by: Option<Uuid>,
+    /// Ingestion job whose pending work units should be re-run instead of
+    /// starting a new ingestion
+    #[serde(default)]
+    pub retry_of: Option<Uuid>,
+    /// Ingestion job whose pending dead letters should be reprocessed
+    /// instead of starting a new ingestion
+    #[serde(default)]
+    pub dead_letters_of: Option<Uuid>,
+    /// Ingest the version even if it was already ingested
+    #[serde(default)]
+    pub force: bool,
+    /// Timestamp when job was created
+    pub created_at: DateTime<Utc>,
+}
+
+impl SourceIngestJob {
+    /// Create a new job for an upstream version
+    pub fn new(source: IngestSource, organization_id: Uuid, external_version: String) -> Self {
+        Self {
+            source,
+            organization_id,
+            external_version,
+            triggered_by: None,
+            retry_of: None,
+            dead_letters_of: None,
+            force: false,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Set the user who triggered this job
+    pub fn with_triggered_by(mut self, user_id: Uuid) -> Self {
+        self.triggered_by = Some(user_id);
+        self
+    }
+
+    /// Re-run the pending work units of an existing ingestion job
+    pub fn with_retry_of(mut self, job_id: Uuid) -> Self {
+        self.retry_of = Some(job_id);
+        self
+    }
+
+    /// Reprocess the pending dead letters of an existing ingestion job
+    pub fn with_dead_letters_of(mut self, job_id: Uuid) -> Self {
+        self.dead_letters_of = Some(job_id);
+        self
+    }
+
+    /// Re-ingest the version even if it was already ingested
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+}
+
 /// Statistics collected during ingestion
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct IngestStats {
@@ -213,6 +283,21 @@ mod tests {
         assert_eq!(job.triggered_by, Some(user_id));
     }
 
+    #[test]
+    fn test_source_ingest_job_new() {
+        let org_id = Uuid::new_v4();
+        let job =
+            SourceIngestJob::new(IngestSource::GeneOntology, org_id, "2025-01-01".to_string());
+
+        assert_eq!(job.source, IngestSource::GeneOntology);
+        assert_eq!(job.organization_id, org_id);
+        assert_eq!(job.external_version, "2025-01-01");
+        assert!(job.triggered_by.is_none());
+        assert!(job.retry_of.is_none());
+        assert!(job.dead_letters_of.is_none());
+        assert!(!job.force);
+    }
+
     #[test]
     fn test_ingest_stats_new() {
         let stats = IngestStats::new();
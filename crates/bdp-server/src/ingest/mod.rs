@@ -5,10 +5,11 @@
 //! # Architecture
 //!
 //! - **config**: Configuration for ingestion jobs (INGEST_* environment variables)
-//! - **jobs**: Job definitions for apalis queue (UniProtIngestJob, IngestStats)
+//! - **jobs**: Job definitions for apalis queue (SourceIngestJob, IngestStats)
 //! - **models**: Database models for sync status tracking
-//! - **scheduler**: Apalis scheduler setup and worker management
-//! - **version_mapping**: Version mapping utilities (Agent 3)
+//! - **scheduler**: Cron-driven scheduler, apalis queue setup and worker management
+//! - **sources**: Version discovery and ingestion runs for each scheduled source
+//! - **version_mapping**: Mapping between upstream releases and internal versions
 //! - **uniprot**: UniProt-specific ingestion logic (Agent 4)
 //!
 //! # Public API
@@ -24,6 +25,7 @@
 pub mod citations;
 pub mod common;
 pub mod config;
+pub mod ensembl;
 pub mod framework;
 pub mod genbank;
 pub mod gene_ontology;
@@ -31,19 +33,24 @@ pub mod interpro;
 pub mod jobs;
 pub mod models;
 pub mod ncbi_taxonomy;
-pub mod orchestrator;
+pub mod refseq;
 pub mod scheduler;
+pub mod sources;
+pub mod structure;
 pub mod uniprot;
 pub mod version_mapping;
 pub mod versioning;
 
-pub use config::{IngestConfig, UniProtConfig};
+pub use config::{IngestConfig, SourceScheduleConfig, UniProtConfig};
+pub use ensembl::{EnsemblConfig, EnsemblPipeline};
 pub use genbank::{GenbankFtpConfig, GenbankOrchestrator, GenbankPipeline};
 pub use gene_ontology::{GoHttpConfig, GoPipeline, GoStorage};
-pub use jobs::{IngestStats, UniProtIngestJob};
+pub use jobs::{IngestStats, SourceIngestJob, UniProtIngestJob};
 pub use models::{OrganizationSyncStatus, SyncStatus};
 pub use ncbi_taxonomy::{NcbiTaxonomyFtpConfig, NcbiTaxonomyOrchestrator, NcbiTaxonomyPipeline};
-pub use orchestrator::IngestOrchestrator;
+pub use refseq::{RefseqAssemblyConfig, RefseqAssemblyPipeline};
 pub use scheduler::JobScheduler;
+pub use sources::{IngestContext, IngestSource};
+pub use structure::{StructureConfig, StructurePipeline};
 pub use uniprot::UniProtPipeline;
-pub use version_mapping::VersionMapper;
+pub use version_mapping::{VersionMapper, VersionMapping};
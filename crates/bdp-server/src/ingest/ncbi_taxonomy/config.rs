@@ -1,6 +1,10 @@
 //! NCBI Taxonomy data source configuration
 
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::ingest::common::ftp::FtpConfig;
+use crate::ingest::common::transport::{FtpTransport, Transport, TransportConfig};
 
 /// Configuration for NCBI Taxonomy data source
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +25,9 @@ pub struct NcbiTaxonomyFtpConfig {
     pub read_timeout_secs: u64,
     /// Maximum number of entries to parse (None for unlimited)
     pub parse_limit: Option<usize>,
+    /// Transport to read upstream files through (None for FTP to `ftp_host`)
+    #[serde(default)]
+    pub transport: Option<TransportConfig>,
 }
 
 impl Default for NcbiTaxonomyFtpConfig {
@@ -34,6 +41,7 @@ impl Default for NcbiTaxonomyFtpConfig {
             connection_timeout_secs: 30,
             read_timeout_secs: 1800, // 30 minutes for large files (140 MB tar.gz archive)
             parse_limit: None,
+            transport: None,
         }
     }
 }
@@ -56,6 +64,12 @@ impl NcbiTaxonomyFtpConfig {
         self
     }
 
+    /// Read upstream files through another transport
+    pub fn with_transport(mut self, transport: TransportConfig) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
     /// Set connection timeout
     pub fn with_connection_timeout(mut self, timeout_secs: u64) -> Self {
         self.connection_timeout_secs = timeout_secs;
@@ -68,6 +82,19 @@ impl NcbiTaxonomyFtpConfig {
         self
     }
 
+    /// Transport for upstream files
+    pub fn transport(&self) -> Arc<dyn Transport> {
+        match &self.transport {
+            Some(transport) => transport.build(),
+            None => Arc::new(FtpTransport::new(FtpConfig {
+                host: self.ftp_host.clone(),
+                port: self.ftp_port,
+                username: self.ftp_username.clone(),
+                password: self.ftp_password.clone(),
+            })),
+        }
+    }
+
     /// Get the full FTP path for new_taxdump.tar.gz (current version)
     pub fn taxdump_path(&self) -> String {
         format!("{}/new_taxdump.tar.gz", self.ftp_base_path)
@@ -1,27 +1,28 @@
-//! NCBI Taxonomy FTP download functionality
+//! NCBI Taxonomy download functionality
 
 use anyhow::{Context, Result};
-use chrono::Datelike;
 use flate2::read::GzDecoder;
 use std::io::{Cursor, Read};
-use std::time::Duration;
-use suppaftp::FtpStream;
+use std::sync::Arc;
 use tar::Archive;
-use tracing::{debug, info, warn};
+use tracing::{debug, info};
 use zip::ZipArchive;
 
 use super::config::NcbiTaxonomyFtpConfig;
-use crate::ingest::common::ftp::{MAX_RETRIES, RETRY_DELAY_SECS};
+use crate::ingest::common::transport::Transport;
+use crate::ingest::framework::UpstreamDigest;
 
-/// FTP client for downloading NCBI Taxonomy data
+/// Client for downloading NCBI Taxonomy data through the configured transport
 pub struct NcbiTaxonomyFtp {
     config: NcbiTaxonomyFtpConfig,
+    transport: Arc<dyn Transport>,
 }
 
 impl NcbiTaxonomyFtp {
-    /// Create a new FTP client
+    /// Create a new client
     pub fn new(config: NcbiTaxonomyFtpConfig) -> Self {
-        Self { config }
+        let transport = config.transport();
+        Self { config, transport }
     }
 
     /// Download and extract taxdump files (current version)
@@ -55,17 +56,24 @@ impl NcbiTaxonomyFtp {
         };
 
         info!("Downloading taxdump from: {}", path);
-        let (compressed, file_timestamp) = self.download_file_with_timestamp(&path).await?;
+        let download = self.transport.download(&path).await?;
+        let compressed = download.data;
 
         // For historical archives, use the version from filename, not file timestamp
-        let external_version = if let Some(ver) = version {
-            ver.to_string()
-        } else {
-            file_timestamp
+        let external_version = match version {
+            Some(ver) => ver.to_string(),
+            None => download
+                .timestamp
+                .map(|timestamp| timestamp.format("%Y-%m-%d").to_string())
+                .with_context(|| {
+                    format!("{} has no modification time for {}", self.transport.location(), path)
+                })?,
         };
 
         info!("Downloaded taxdump version {} ({} bytes)", external_version, compressed.len());
 
+        let upstream_digest = self.verify_download(&path, &compressed).await?;
+
         info!("Decompressing and extracting taxdump archive");
         let taxdump_files = if is_zip {
             self.extract_taxdump_zip(&compressed)?
@@ -78,9 +86,29 @@ impl NcbiTaxonomyFtp {
             merged: taxdump_files.merged,
             delnodes: taxdump_files.delnodes,
             external_version,
+            upstream_digest,
         })
     }
 
+    /// Check a downloaded archive against the `.md5` file NCBI publishes next to it
+    async fn verify_download(&self, path: &str, data: &[u8]) -> Result<UpstreamDigest> {
+        let checksum_path = format!("{}.md5", path);
+        let checksum_file = self
+            .transport
+            .download(&checksum_path)
+            .await
+            .with_context(|| format!("Failed to download checksum {}", checksum_path))?;
+        let content =
+            String::from_utf8(checksum_file.data).context("Checksum file is not valid UTF-8")?;
+
+        let artifact = path.rsplit('/').next().unwrap_or(path);
+        let digest = UpstreamDigest::from_checksum_file(artifact, &content)?;
+        digest.verify(data)?;
+
+        info!("Verified {} against upstream MD5 {}", artifact, digest.value);
+        Ok(digest)
+    }
+
     /// List all available taxdump archive versions
     ///
     /// Returns a sorted list of archive dates (oldest to newest)
@@ -89,7 +117,7 @@ impl NcbiTaxonomyFtp {
         let archive_dir = "/pub/taxonomy/taxdump_archive";
 
         info!("Listing available taxdump archives from: {}", archive_dir);
-        let files = self.list_directory_files(archive_dir).await?;
+        let files = self.transport.list_files(archive_dir).await?;
 
         // Parse archive filenames: new_taxdump_YYYY-MM-DD.zip
         let mut versions: Vec<String> = files
@@ -116,96 +144,6 @@ impl NcbiTaxonomyFtp {
         Ok(versions)
     }
 
-    /// List files in an FTP directory
-    ///
-    /// # Arguments
-    /// * `path` - Directory path to list
-    ///
-    /// # Returns
-    /// Vector of filenames (not full paths)
-    async fn list_directory_files(&self, path: &str) -> Result<Vec<String>> {
-        let config = self.config.clone();
-        let path = path.to_string();
-
-        // Retry loop for FTP listing
-        for attempt in 1..=MAX_RETRIES {
-            match tokio::task::spawn_blocking({
-                let config = config.clone();
-                let path = path.clone();
-                move || Self::list_directory_sync(&config, &path)
-            })
-            .await
-            {
-                Ok(Ok(files)) => {
-                    info!("Successfully listed directory {} ({} files)", path, files.len());
-                    return Ok(files);
-                },
-                Ok(Err(e)) => {
-                    if attempt < MAX_RETRIES {
-                        warn!(
-                            "List attempt {}/{} failed: {}. Retrying in {}s...",
-                            attempt, MAX_RETRIES, e, RETRY_DELAY_SECS
-                        );
-                        tokio::time::sleep(Duration::from_secs(RETRY_DELAY_SECS * attempt as u64))
-                            .await;
-                    } else {
-                        return Err(e).with_context(|| {
-                            format!("Failed to list {} after {} attempts", path, MAX_RETRIES)
-                        });
-                    }
-                },
-                Err(e) => {
-                    return Err(anyhow::anyhow!("FTP list task panicked: {}", e));
-                },
-            }
-        }
-
-        unreachable!("Retry loop should always return")
-    }
-
-    /// Synchronous FTP directory listing
-    fn list_directory_sync(config: &NcbiTaxonomyFtpConfig, path: &str) -> Result<Vec<String>> {
-        debug!("Connecting to FTP server: {}:{}", config.ftp_host, config.ftp_port);
-
-        // Connect to FTP server
-        let mut ftp_stream = FtpStream::connect(format!("{}:{}", config.ftp_host, config.ftp_port))
-            .context("Failed to connect to FTP server")?;
-
-        // Use Extended Passive Mode (EPSV)
-        ftp_stream.set_mode(suppaftp::Mode::ExtendedPassive);
-
-        debug!("Logging in as: {}", config.ftp_username);
-
-        // Login
-        ftp_stream
-            .login(&config.ftp_username, &config.ftp_password)
-            .context("FTP login failed")?;
-
-        debug!("Listing directory: {}", path);
-
-        // List directory
-        let entries = ftp_stream
-            .list(Some(path))
-            .with_context(|| format!("Failed to list directory: {}", path))?;
-
-        // Parse filenames from listing
-        // FTP LIST format: "-rw-r--r--   1 ftp  anonymous  134217728 Jan 01 12:00 taxdmp_2024-01-01.tar.gz"
-        let files: Vec<String> = entries
-            .iter()
-            .filter_map(|entry| {
-                // Split by whitespace and take last field (filename)
-                entry.split_whitespace().last().map(|s| s.to_string())
-            })
-            .collect();
-
-        // Logout
-        let _ = ftp_stream.quit();
-
-        debug!("Listed {} files from {}", files.len(), path);
-
-        Ok(files)
-    }
-
     /// Extract required files from taxdump tar.gz archive
     fn extract_taxdump_targz(&self, compressed: &[u8]) -> Result<ExtractedFiles> {
         // Decompress gzip
@@ -337,99 +275,6 @@ impl NcbiTaxonomyFtp {
             delnodes: delnodes.context("delnodes.dmp not found in archive")?,
         })
     }
-
-    /// Download a file and get its modification timestamp
-    async fn download_file_with_timestamp(&self, path: &str) -> Result<(Vec<u8>, String)> {
-        let config = self.config.clone();
-        let path = path.to_string();
-
-        // Retry loop
-        for attempt in 1..=MAX_RETRIES {
-            debug!("Download attempt {}/{} for: {}", attempt, MAX_RETRIES, path);
-
-            match tokio::task::spawn_blocking({
-                let config = config.clone();
-                let path = path.clone();
-                move || Self::download_file_with_timestamp_sync(&config, &path)
-            })
-            .await
-            {
-                Ok(Ok(result)) => {
-                    info!("Successfully downloaded {} ({} bytes)", path, result.0.len());
-                    return Ok(result);
-                },
-                Ok(Err(e)) => {
-                    if attempt < MAX_RETRIES {
-                        warn!(
-                            "Download attempt {}/{} failed: {}. Retrying in {}s...",
-                            attempt, MAX_RETRIES, e, RETRY_DELAY_SECS
-                        );
-                        tokio::time::sleep(Duration::from_secs(RETRY_DELAY_SECS * attempt as u64))
-                            .await;
-                    } else {
-                        return Err(e).with_context(|| {
-                            format!("Failed to download {} after {} attempts", path, MAX_RETRIES)
-                        });
-                    }
-                },
-                Err(e) => {
-                    return Err(anyhow::anyhow!("FTP download task panicked: {}", e));
-                },
-            }
-        }
-
-        unreachable!("Retry loop should always return")
-    }
-
-    /// Synchronous FTP download with timestamp retrieval
-    fn download_file_with_timestamp_sync(
-        config: &NcbiTaxonomyFtpConfig,
-        path: &str,
-    ) -> Result<(Vec<u8>, String)> {
-        debug!("Connecting to FTP server: {}:{}", config.ftp_host, config.ftp_port);
-
-        // Connect to FTP server
-        let mut ftp_stream = FtpStream::connect(format!("{}:{}", config.ftp_host, config.ftp_port))
-            .context("Failed to connect to FTP server")?;
-
-        // Use Extended Passive Mode (EPSV) - better for NAT/Docker environments
-        ftp_stream.set_mode(suppaftp::Mode::ExtendedPassive);
-
-        debug!("FTP connection established with Extended Passive Mode (EPSV)");
-        debug!("Logging in as: {}", config.ftp_username);
-
-        // Login
-        ftp_stream
-            .login(&config.ftp_username, &config.ftp_password)
-            .context("FTP login failed")?;
-
-        debug!("FTP login successful");
-
-        // Get file modification timestamp (MDTM command)
-        let timestamp = ftp_stream
-            .mdtm(path)
-            .context("Failed to get file modification time")?;
-
-        // Format timestamp as YYYY-MM-DD
-        let external_version =
-            format!("{:04}-{:02}-{:02}", timestamp.year(), timestamp.month(), timestamp.day());
-
-        debug!("File modification time: {}", external_version);
-
-        // Download file
-        debug!("Downloading file: {}", path);
-        let cursor = ftp_stream
-            .retr_as_buffer(path)
-            .with_context(|| format!("Failed to download file: {}", path))?;
-
-        let data = cursor.into_inner();
-        debug!("Downloaded {} bytes from {}", data.len(), path);
-
-        // Logout
-        let _ = ftp_stream.quit();
-
-        Ok((data, external_version))
-    }
 }
 
 /// Extracted taxdump files with external version
@@ -439,6 +284,8 @@ pub struct TaxdumpFiles {
     pub merged: String,
     pub delnodes: String,
     pub external_version: String,
+    /// MD5 of the downloaded archive, verified against NCBI's `.md5` file
+    pub upstream_digest: UpstreamDigest,
 }
 
 /// Internal struct for extracted files during processing
@@ -452,6 +299,8 @@ struct ExtractedFiles {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ingest::common::transport::TransportConfig;
+    use crate::ingest::framework::ChecksumMismatch;
 
     #[test]
     fn test_extract_taxdump_format() {
@@ -462,4 +311,61 @@ mod tests {
         // Verify paths are constructed correctly
         assert!(config.taxdump_path().contains("new_taxdump.tar.gz"));
     }
+
+    fn write_archive(dir: &std::path::Path, date: &str) {
+        use std::io::Write;
+
+        let file = std::fs::File::create(dir.join(format!("new_taxdump_{}.zip", date))).unwrap();
+        let mut zip = zip::ZipWriter::new(file);
+        let options = zip::write::SimpleFileOptions::default();
+        for (name, content) in [
+            ("rankedlineage.dmp", "9606\t|\tHomo sapiens\t|\n"),
+            ("merged.dmp", ""),
+            ("delnodes.dmp", ""),
+        ] {
+            zip.start_file(name, options).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let path = dir.join(format!("new_taxdump_{}.zip", date));
+        let md5 = crate::ingest::framework::compute_md5(&std::fs::read(&path).unwrap());
+        std::fs::write(
+            dir.join(format!("new_taxdump_{}.zip.md5", date)),
+            format!("{}  new_taxdump_{}.zip\n", md5, date),
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_local_mirror() {
+        let mirror = tempfile::tempdir().unwrap();
+        let archive_dir = mirror.path().join("pub/taxonomy/taxdump_archive");
+        std::fs::create_dir_all(&archive_dir).unwrap();
+        write_archive(&archive_dir, "2024-02-01");
+        write_archive(&archive_dir, "2024-01-01");
+        std::fs::write(archive_dir.join("README"), "").unwrap();
+
+        let transport =
+            TransportConfig::parse(&format!("file://{}", mirror.path().display())).unwrap();
+        let ftp = NcbiTaxonomyFtp::new(NcbiTaxonomyFtpConfig::new().with_transport(transport));
+
+        assert_eq!(ftp.list_available_versions().await.unwrap(), vec!["2024-01-01", "2024-02-01"]);
+
+        let files = ftp
+            .download_taxdump_version(Some("2024-01-01"))
+            .await
+            .unwrap();
+        assert_eq!(files.external_version, "2024-01-01");
+        assert!(files.rankedlineage.starts_with("9606"));
+        assert_eq!(files.upstream_digest.artifact, "new_taxdump_2024-01-01.zip");
+
+        // A corrupted download fails verification
+        std::fs::write(archive_dir.join("new_taxdump_2024-02-01.zip.md5"), "0".repeat(32)).unwrap();
+        let err = ftp
+            .download_taxdump_version(Some("2024-02-01"))
+            .await
+            .unwrap_err();
+        assert!(err.downcast_ref::<ChecksumMismatch>().is_some());
+    }
 }
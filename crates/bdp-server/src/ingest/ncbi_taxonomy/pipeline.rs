@@ -3,6 +3,7 @@
 //! Orchestrates the full ingestion process from FTP download to database storage.
 
 use anyhow::{Context, Result};
+use chrono::NaiveDate;
 use sqlx::PgPool;
 use tracing::{info, warn};
 use uuid::Uuid;
@@ -12,6 +13,7 @@ use super::ftp::NcbiTaxonomyFtp;
 use super::parser::TaxdumpParser;
 use super::storage::{NcbiTaxonomyStorage, StorageStats};
 use super::version_discovery::TaxonomyVersionDiscovery;
+use crate::ingest::framework::{record_upstream_verification, UpstreamVerification};
 use crate::storage::Storage;
 
 /// NCBI Taxonomy ingestion pipeline
@@ -150,10 +152,17 @@ impl NcbiTaxonomyPipeline {
         info!("Phase 4: Determining internal version");
         let version_discovery = TaxonomyVersionDiscovery::new(self.config.clone(), self.db.clone());
         let has_major_changes = !taxdump_data.merged.is_empty() || !taxdump_data.deleted.is_empty();
-        let internal_version = version_discovery
-            .determine_next_version(has_major_changes)
-            .await
-            .context("Failed to determine next version")?;
+        // A re-ingested release keeps the internal version it was mapped to
+        let internal_version = match version_discovery
+            .get_internal_version(&external_version)
+            .await?
+        {
+            Some(internal_version) => internal_version,
+            None => version_discovery
+                .determine_next_version(has_major_changes)
+                .await
+                .context("Failed to determine next version")?,
+        };
 
         info!(
             internal_version = %internal_version,
@@ -205,6 +214,13 @@ impl NcbiTaxonomyPipeline {
             .await
             .context("Failed to record version mapping")?;
 
+        let verification = UpstreamVerification {
+            digest: Some(taxdump_files.upstream_digest.clone()),
+            release_date: NaiveDate::parse_from_str(&external_version, "%Y-%m-%d").ok(),
+        };
+        record_upstream_verification(&self.db, organization_id, &external_version, &verification)
+            .await?;
+
         info!(
             external_version = %external_version,
             internal_version = %internal_version,
@@ -10,6 +10,7 @@ use uuid::Uuid;
 
 use super::models::{DeletedTaxon, MergedTaxon, TaxdumpData, TaxonomyEntry};
 use crate::ingest::citations::{ncbi_taxonomy_policy, setup_citation_policy};
+use crate::ingest::framework::{compute_sha256, deprecate_entries, record_successions, Succession};
 use crate::storage::Storage;
 
 /// Storage handler for NCBI Taxonomy data
@@ -517,12 +518,12 @@ impl NcbiTaxonomyStorage {
             // Generate JSON content
             let json_content = entry.to_json()?;
             let json_size = json_content.len() as i64;
-            let json_checksum = format!("{:x}", md5::compute(&json_content));
+            let json_checksum = compute_sha256(json_content.as_bytes());
 
             // Generate TSV content
             let tsv_content = format!("{}\n{}", TaxonomyEntry::tsv_header(), entry.to_tsv());
             let tsv_size = tsv_content.len() as i64;
-            let tsv_checksum = format!("{:x}", md5::compute(&tsv_content));
+            let tsv_checksum = compute_sha256(tsv_content.as_bytes());
 
             // S3 keys
             let s3_key_json =
@@ -553,9 +554,10 @@ impl NcbiTaxonomyStorage {
             file_data.push((*version_id, "tsv", s3_key_tsv, tsv_checksum, tsv_size));
         }
 
-        // Batch insert version_files
+        // Batch insert version_files. The files are generated here rather than
+        // published upstream, so they have no upstream MD5.
         let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new(
-            "INSERT INTO version_files (version_id, format, s3_key, checksum, size_bytes) ",
+            "INSERT INTO version_files (version_id, format, s3_key, checksum, sha256, size_bytes) ",
         );
 
         query_builder.push_values(
@@ -565,15 +567,15 @@ impl NcbiTaxonomyStorage {
                     .push_bind(format)
                     .push_bind(s3_key)
                     .push_bind(checksum)
+                    .push_bind(checksum)
                     .push_bind(size);
             },
         );
 
+        // Files of existing versions are sealed; only fill in a missing digest
         query_builder.push(
             " ON CONFLICT (version_id, format) DO UPDATE SET \
-             s3_key = EXCLUDED.s3_key, \
-             checksum = EXCLUDED.checksum, \
-             size_bytes = EXCLUDED.size_bytes",
+             sha256 = COALESCE(version_files.sha256, EXCLUDED.sha256)",
         );
 
         query_builder.build().execute(&mut **tx).await?;
@@ -636,6 +638,17 @@ impl NcbiTaxonomyStorage {
             }
         }
 
+        // Old taxon IDs resolve to the taxon they were merged into
+        let successions: Vec<Succession> = merged
+            .iter()
+            .map(|m| Succession::new(m.old_taxonomy_id.to_string(), m.new_taxonomy_id.to_string()))
+            .collect();
+        let mut deprecated = 0;
+        for chunk in successions.chunks(self.chunk_size) {
+            deprecated += record_successions(tx, self.organization_id, chunk).await?;
+        }
+        debug!(deprecated, "Deprecated merged taxa");
+
         Ok(())
     }
 
@@ -687,6 +700,12 @@ impl NcbiTaxonomyStorage {
             }
         }
 
+        let slugs: Vec<String> = deleted.iter().map(|d| d.taxonomy_id.to_string()).collect();
+        for chunk in slugs.chunks(self.chunk_size) {
+            deprecate_entries(tx, self.organization_id, chunk, "Deleted from NCBI Taxonomy")
+                .await?;
+        }
+
         Ok(())
     }
 }
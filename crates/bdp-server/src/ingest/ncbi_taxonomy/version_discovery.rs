@@ -11,6 +11,7 @@ use super::config::NcbiTaxonomyFtpConfig;
 use super::ftp::NcbiTaxonomyFtp;
 use crate::impl_version_ordering;
 use crate::ingest::common::version_discovery::DiscoveredVersion as DiscoveredVersionTrait;
+use crate::ingest::version_mapping::{VersionMapper, VersionMapping};
 
 /// Discovered NCBI Taxonomy version from FTP
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -288,34 +289,31 @@ impl TaxonomyVersionDiscovery {
         Ok(next_version)
     }
 
+    /// Internal version a taxdump release was already mapped to
+    pub async fn get_internal_version(&self, external_version: &str) -> Result<Option<String>> {
+        VersionMapper::new(self.db.clone())
+            .external_to_internal("ncbi", None, external_version)
+            .await
+            .context("Failed to look up version mapping")
+    }
+
     /// Record version mapping in the database
     ///
-    /// Creates an entry in version_mappings linking external → internal version
+    /// Maps the taxdump release to its internal version for the whole ncbi
+    /// organization, with the release date the taxdump is named after
     pub async fn record_version_mapping(
         &self,
         external_version: &str,
         internal_version: &str,
     ) -> Result<()> {
-        sqlx::query(
-            r#"
-            INSERT INTO version_mappings (organization_slug, external_version, internal_version)
-            VALUES ('ncbi', $1, $2)
-            ON CONFLICT (organization_slug, external_version) DO NOTHING
-            "#,
-        )
-        .bind(external_version)
-        .bind(internal_version)
-        .execute(&self.db)
-        .await
-        .context("Failed to record version mapping")?;
-
-        info!(
-            external_version = %external_version,
-            internal_version = %internal_version,
-            "Recorded version mapping"
-        );
-
-        Ok(())
+        let release_date = NaiveDate::parse_from_str(external_version, "%Y-%m-%d").ok();
+        VersionMapper::new(self.db.clone())
+            .record(
+                &VersionMapping::organization("ncbi", external_version, internal_version)
+                    .with_release_date(release_date),
+            )
+            .await
+            .context("Failed to record version mapping")
     }
 
     // ========================================================================
@@ -0,0 +1,218 @@
+// RefSeq assembly configuration
+//
+// Environment-based configuration for RefSeq assembly ingestion. Files are
+// read from the NCBI FTP site over HTTPS, or through the configured transport
+// with the same directory layout (/genomes/refseq/<group>/assembly_summary.txt,
+// /genomes/all/GCF/000/005/845/<assembly>/...).
+
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::sync::Arc;
+
+use crate::ingest::common::transport::{HttpsTransport, Transport, TransportConfig};
+
+/// Configuration for RefSeq assembly downloads and ingestion
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefseqAssemblyConfig {
+    /// Base URL of the NCBI FTP site (default: https://ftp.ncbi.nlm.nih.gov)
+    pub base_url: String,
+
+    /// Transport to read upstream files through (None for HTTPS to `base_url`)
+    #[serde(default)]
+    pub transport: Option<TransportConfig>,
+
+    /// RefSeq groups whose assembly summaries are read (e.g., "bacteria")
+    pub groups: Vec<String>,
+
+    /// RefSeq categories to ingest (e.g., "reference genome"); empty = all
+    pub refseq_categories: Vec<String>,
+
+    /// Taxonomy IDs to ingest (default: human and mouse); empty = all
+    pub taxonomy_ids: Vec<i32>,
+
+    /// Batch size for database operations
+    pub batch_size: usize,
+
+    /// Maximum number of assemblies to ingest per run (None = all)
+    pub assembly_limit: Option<usize>,
+}
+
+impl Default for RefseqAssemblyConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "https://ftp.ncbi.nlm.nih.gov".to_string(),
+            transport: None,
+            groups: vec!["vertebrate_mammalian".to_string()],
+            refseq_categories: vec!["reference genome".to_string()],
+            taxonomy_ids: vec![9606, 10090],
+            batch_size: 500,
+            assembly_limit: None,
+        }
+    }
+}
+
+impl RefseqAssemblyConfig {
+    /// Create new configuration with defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load configuration from environment variables
+    ///
+    /// Environment variables:
+    /// - INGEST_REFSEQ_ASSEMBLY_BASE_URL
+    /// - INGEST_REFSEQ_ASSEMBLY_GROUPS (comma-separated, e.g. "bacteria,vertebrate_mammalian")
+    /// - INGEST_REFSEQ_ASSEMBLY_CATEGORIES (comma-separated, "all" for every category)
+    /// - INGEST_REFSEQ_ASSEMBLY_TAXONOMY_IDS (comma-separated, "all" for every organism)
+    /// - INGEST_REFSEQ_ASSEMBLY_BATCH_SIZE
+    /// - INGEST_REFSEQ_ASSEMBLY_LIMIT
+    ///
+    /// The transport (including `INGEST_REFSEQ_ASSEMBLY_LOCAL_MIRROR`) is read
+    /// by [`IngestConfig`](crate::ingest::config::IngestConfig).
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+
+        Self {
+            base_url: env::var("INGEST_REFSEQ_ASSEMBLY_BASE_URL").unwrap_or(defaults.base_url),
+
+            transport: None,
+
+            groups: env::var("INGEST_REFSEQ_ASSEMBLY_GROUPS")
+                .ok()
+                .map(|list| parse_list(&list))
+                .filter(|groups| !groups.is_empty())
+                .unwrap_or(defaults.groups),
+
+            refseq_categories: match env::var("INGEST_REFSEQ_ASSEMBLY_CATEGORIES") {
+                Ok(list) if list.trim().eq_ignore_ascii_case("all") => Vec::new(),
+                Ok(list) => parse_list(&list),
+                Err(_) => defaults.refseq_categories,
+            },
+
+            taxonomy_ids: match env::var("INGEST_REFSEQ_ASSEMBLY_TAXONOMY_IDS") {
+                Ok(list) if list.trim().eq_ignore_ascii_case("all") => Vec::new(),
+                Ok(list) => parse_list(&list)
+                    .iter()
+                    .filter_map(|id| id.parse().ok())
+                    .collect(),
+                Err(_) => defaults.taxonomy_ids,
+            },
+
+            batch_size: env::var("INGEST_REFSEQ_ASSEMBLY_BATCH_SIZE")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.batch_size),
+
+            assembly_limit: env::var("INGEST_REFSEQ_ASSEMBLY_LIMIT")
+                .ok()
+                .and_then(|s| s.parse().ok()),
+        }
+    }
+
+    /// Read upstream files through another transport
+    pub fn with_transport(mut self, transport: TransportConfig) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Transport for upstream files
+    pub fn transport(&self) -> Arc<dyn Transport> {
+        match &self.transport {
+            Some(transport) => transport.build(),
+            None => Arc::new(HttpsTransport::new(self.base_url.clone())),
+        }
+    }
+
+    /// Set the RefSeq groups to read
+    pub fn with_groups<S: Into<String>>(mut self, groups: impl IntoIterator<Item = S>) -> Self {
+        self.groups = groups.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the RefSeq categories to ingest (empty = all)
+    pub fn with_refseq_categories<S: Into<String>>(
+        mut self,
+        categories: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.refseq_categories = categories.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the taxonomy IDs to ingest (empty = all)
+    pub fn with_taxonomy_ids(mut self, taxonomy_ids: impl IntoIterator<Item = i32>) -> Self {
+        self.taxonomy_ids = taxonomy_ids.into_iter().collect();
+        self
+    }
+
+    /// Set the maximum number of assemblies per run
+    pub fn with_assembly_limit(mut self, limit: usize) -> Self {
+        self.assembly_limit = Some(limit);
+        self
+    }
+
+    /// Set batch size
+    pub fn with_batch_size(mut self, size: usize) -> Self {
+        self.batch_size = size;
+        self
+    }
+
+    /// Assembly summary of a RefSeq group
+    pub fn assembly_summary_path(group: &str) -> String {
+        format!("/genomes/refseq/{}/assembly_summary.txt", group)
+    }
+
+    /// Validate configuration
+    pub fn validate(&self) -> Result<(), String> {
+        if self.transport.is_none() && self.base_url.is_empty() {
+            return Err("Base URL cannot be empty without a transport".to_string());
+        }
+
+        if self.groups.is_empty() {
+            return Err("At least one RefSeq group must be configured".to_string());
+        }
+
+        if self.batch_size == 0 {
+            return Err("Batch size must be greater than 0".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_list(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config() {
+        let config = RefseqAssemblyConfig::default();
+        assert_eq!(config.base_url, "https://ftp.ncbi.nlm.nih.gov");
+        assert_eq!(config.refseq_categories, vec!["reference genome"]);
+        assert_eq!(config.taxonomy_ids, vec![9606, 10090]);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_assembly_summary_path() {
+        assert_eq!(
+            RefseqAssemblyConfig::assembly_summary_path("bacteria"),
+            "/genomes/refseq/bacteria/assembly_summary.txt"
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let config = RefseqAssemblyConfig::new().with_groups(Vec::<String>::new());
+        assert!(config.validate().is_err());
+
+        let config = RefseqAssemblyConfig::new().with_batch_size(0);
+        assert!(config.validate().is_err());
+    }
+}
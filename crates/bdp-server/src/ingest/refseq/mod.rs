@@ -0,0 +1,18 @@
+// RefSeq assembly ingestion module
+//
+// This module handles ingestion of RefSeq genome assemblies, one data source per
+// assembly (GCF accession) linked to its organism. Each assembly version becomes
+// a version of the assembly; its proteins are cross-linked to UniProt entries.
+// Storage: S3 for assembly files (genomic FASTA, GFF3, protein FASTA),
+// PostgreSQL for assembly metadata, stats and protein mappings
+
+pub mod config;
+pub mod models;
+pub mod parser;
+pub mod pipeline;
+pub mod storage;
+
+pub use config::RefseqAssemblyConfig;
+pub use models::{AssemblyFileKind, AssemblyStats, AssemblySummary, RefseqProtein};
+pub use pipeline::RefseqAssemblyPipeline;
+pub use storage::RefseqAssemblyStorage;
@@ -0,0 +1,287 @@
+// Data models for RefSeq genome assemblies
+
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// An assembly row of a RefSeq `assembly_summary.txt`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssemblySummary {
+    /// Versioned GCF accession (e.g., "GCF_000005845.2")
+    pub accession_version: String,
+    /// RefSeq category ("reference genome", "na")
+    pub refseq_category: Option<String>,
+    /// NCBI Taxonomy ID of the organism
+    pub taxonomy_id: i32,
+    /// NCBI Taxonomy ID of the species
+    pub species_taxonomy_id: Option<i32>,
+    pub organism_name: String,
+    /// Strain, breed or cultivar (e.g., "strain=K-12 substr. MG1655")
+    pub infraspecific_name: Option<String>,
+    /// "latest", "replaced" or "suppressed"
+    pub version_status: Option<String>,
+    /// "Complete Genome", "Chromosome", "Scaffold" or "Contig"
+    pub assembly_level: Option<String>,
+    /// "Full" or "Partial"
+    pub genome_rep: Option<String>,
+    pub release_date: Option<NaiveDate>,
+    /// Assembly name (e.g., "ASM584v2")
+    pub assembly_name: String,
+    pub submitter: Option<String>,
+    /// Paired GenBank assembly (e.g., "GCA_000005845.2")
+    pub paired_accession: Option<String>,
+    /// Assembly directory URL on the FTP site
+    pub ftp_path: String,
+    /// Date of the RefSeq annotation, when the summary has one
+    pub annotation_date: Option<NaiveDate>,
+}
+
+impl AssemblySummary {
+    /// Accession without its version ("GCF_000005845.2" → "GCF_000005845")
+    pub fn accession(&self) -> &str {
+        self.accession_version
+            .split_once('.')
+            .map(|(accession, _)| accession)
+            .unwrap_or(&self.accession_version)
+    }
+
+    /// Registry slug of the assembly ("GCF_000005845.2" → "refseq-gcf-000005845")
+    pub fn slug(&self) -> String {
+        format!("refseq-{}", self.accession().to_lowercase().replace('_', "-"))
+    }
+
+    /// Whether this is the reference genome of its organism
+    pub fn is_reference(&self) -> bool {
+        self.refseq_category.as_deref() == Some("reference genome")
+    }
+
+    /// Whether this is the current version of the assembly
+    pub fn is_latest(&self) -> bool {
+        self.version_status
+            .as_deref()
+            .is_none_or(|status| status == "latest")
+    }
+
+    /// Upstream path of the assembly directory
+    /// ("https://ftp.ncbi.nlm.nih.gov/genomes/all/GCF/000/005/845/GCF_000005845.2_ASM584v2"
+    /// → "/genomes/all/GCF/000/005/845/GCF_000005845.2_ASM584v2")
+    pub fn directory(&self) -> &str {
+        let path = self.ftp_path.trim_end_matches('/');
+        match path.find("/genomes/") {
+            Some(index) => &path[index..],
+            None => path,
+        }
+    }
+
+    /// Name shared by the files of the assembly ("GCF_000005845.2_ASM584v2")
+    pub fn file_prefix(&self) -> &str {
+        let directory = self.directory();
+        directory.rsplit('/').next().unwrap_or(directory)
+    }
+
+    /// Upstream path of an assembly file
+    pub fn file_path(&self, kind: AssemblyFileKind) -> String {
+        format!("{}/{}", self.directory(), self.file_name(kind))
+    }
+
+    /// File name of an assembly file
+    pub fn file_name(&self, kind: AssemblyFileKind) -> String {
+        format!("{}{}", self.file_prefix(), kind.suffix())
+    }
+
+    /// Parse a tab-separated `assembly_summary.txt`
+    ///
+    /// Columns are located by the names of the header line
+    /// (`#assembly_accession` or `# assembly_accession`), since NCBI has
+    /// appended columns over time. Assemblies without an FTP path are skipped.
+    pub fn parse_summary(content: &str) -> Result<Vec<AssemblySummary>> {
+        let header = content
+            .lines()
+            .find(|line| {
+                line.starts_with('#')
+                    && line
+                        .trim_start_matches(['#', ' '])
+                        .starts_with("assembly_accession")
+            })
+            .context("Assembly summary has no header line")?;
+        let columns: HashMap<&str, usize> = header
+            .trim_start_matches(['#', ' '])
+            .split('\t')
+            .enumerate()
+            .map(|(index, name)| (name.trim(), index))
+            .collect();
+        let column = |name: &str| {
+            columns
+                .get(name)
+                .copied()
+                .with_context(|| format!("Assembly summary has no '{}' column", name))
+        };
+
+        let accession_col = column("assembly_accession")?;
+        let taxid_col = column("taxid")?;
+        let organism_col = column("organism_name")?;
+        let asm_name_col = column("asm_name")?;
+        let ftp_path_col = column("ftp_path")?;
+
+        content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+            .filter_map(|(index, line)| {
+                let fields: Vec<&str> = line.split('\t').collect();
+                let optional = |name: &str| {
+                    columns
+                        .get(name)
+                        .and_then(|i| fields.get(*i))
+                        .map(|value| value.trim())
+                        .filter(|value| !value.is_empty() && *value != "na")
+                        .map(str::to_string)
+                };
+                let required = |i: usize| fields.get(i).map(|value| value.trim()).unwrap_or("");
+
+                let ftp_path = required(ftp_path_col);
+                if ftp_path.is_empty() || ftp_path == "na" {
+                    return None;
+                }
+
+                let taxonomy_id = match required(taxid_col).parse() {
+                    Ok(taxonomy_id) => taxonomy_id,
+                    Err(_) => {
+                        return Some(Err(anyhow::anyhow!(
+                            "Line {}: invalid taxonomy ID '{}'",
+                            index + 1,
+                            required(taxid_col)
+                        )))
+                    },
+                };
+                let date = |name: &str| {
+                    optional(name)
+                        .and_then(|date| NaiveDate::parse_from_str(&date, "%Y/%m/%d").ok())
+                };
+
+                Some(Ok(AssemblySummary {
+                    accession_version: required(accession_col).to_string(),
+                    refseq_category: optional("refseq_category"),
+                    taxonomy_id,
+                    species_taxonomy_id: optional("species_taxid").and_then(|id| id.parse().ok()),
+                    organism_name: required(organism_col).to_string(),
+                    infraspecific_name: optional("infraspecific_name"),
+                    version_status: optional("version_status"),
+                    assembly_level: optional("assembly_level"),
+                    genome_rep: optional("genome_rep"),
+                    release_date: date("seq_rel_date"),
+                    assembly_name: required(asm_name_col).to_string(),
+                    submitter: optional("asm_submitter"),
+                    paired_accession: optional("gbrs_paired_asm"),
+                    ftp_path: ftp_path.to_string(),
+                    annotation_date: date("annotation_date"),
+                }))
+            })
+            .collect()
+    }
+}
+
+/// A file published in an assembly directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AssemblyFileKind {
+    /// Genomic sequences (`_genomic.fna.gz`)
+    GenomicFasta,
+    /// Genome annotation (`_genomic.gff.gz`)
+    Gff,
+    /// Annotated proteins (`_protein.faa.gz`)
+    ProteinFasta,
+}
+
+impl AssemblyFileKind {
+    pub const ALL: [AssemblyFileKind; 3] = [Self::GenomicFasta, Self::Gff, Self::ProteinFasta];
+
+    /// Format recorded on the version file
+    pub fn format(&self) -> &'static str {
+        match self {
+            Self::GenomicFasta => "fna",
+            Self::Gff => "gff",
+            Self::ProteinFasta => "faa",
+        }
+    }
+
+    /// Suffix after the assembly file prefix
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            Self::GenomicFasta => "_genomic.fna.gz",
+            Self::Gff => "_genomic.gff.gz",
+            Self::ProteinFasta => "_protein.faa.gz",
+        }
+    }
+}
+
+/// Assembly statistics computed from the assembly files
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AssemblyStats {
+    /// Total sequence length in bases
+    pub total_length: i64,
+    /// Number of sequences (chromosomes, plasmids, scaffolds, ...)
+    pub sequence_count: i32,
+    /// GC content over unambiguous bases
+    pub gc_percent: Option<f64>,
+    /// Scaffold N50 in bases
+    pub scaffold_n50: i64,
+    /// Genes annotated in the GFF file
+    pub gene_count: Option<i32>,
+    /// Proteins in the protein FASTA file
+    pub protein_count: Option<i32>,
+}
+
+/// A RefSeq protein from an assembly's protein FASTA
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RefseqProtein {
+    /// Versioned protein accession (e.g., "NP_414542.1")
+    pub accession: String,
+    pub description: Option<String>,
+    pub length: usize,
+    /// SHA256 of the sequence, as stored in `protein_sequences.sequence_hash`
+    pub sequence_hash: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUMMARY: &str = "##  See ftp://ftp.ncbi.nlm.nih.gov/genomes/README_assembly_summary.txt for a description of the columns in this file.\n\
+#assembly_accession\tbioproject\tbiosample\twgs_master\trefseq_category\ttaxid\tspecies_taxid\torganism_name\tinfraspecific_name\tisolate\tversion_status\tassembly_level\trelease_type\tgenome_rep\tseq_rel_date\tasm_name\tasm_submitter\tgbrs_paired_asm\tpaired_asm_comp\tftp_path\n\
+GCF_000005845.2\tPRJNA57779\tSAMN02604091\t\treference genome\t511145\t562\tEscherichia coli str. K-12 substr. MG1655\tstrain=K-12 substr. MG1655\t\tlatest\tComplete Genome\tMajor\tFull\t2013/09/26\tASM584v2\tUniversity of Wisconsin\tGCA_000005845.2\tidentical\thttps://ftp.ncbi.nlm.nih.gov/genomes/all/GCF/000/005/845/GCF_000005845.2_ASM584v2\n\
+GCF_000001.1\tPRJNA1\tSAMN1\t\tna\t562\t562\tEscherichia coli\t\t\tlatest\tContig\tMajor\tFull\t2020/01/01\tASM1v1\tSomeone\tna\tna\tna\n";
+
+    #[test]
+    fn test_parse_summary() {
+        let assemblies = AssemblySummary::parse_summary(SUMMARY).unwrap();
+        assert_eq!(assemblies.len(), 1);
+
+        let ecoli = &assemblies[0];
+        assert_eq!(ecoli.accession_version, "GCF_000005845.2");
+        assert_eq!(ecoli.accession(), "GCF_000005845");
+        assert_eq!(ecoli.slug(), "refseq-gcf-000005845");
+        assert_eq!(ecoli.taxonomy_id, 511145);
+        assert_eq!(ecoli.species_taxonomy_id, Some(562));
+        assert!(ecoli.is_reference());
+        assert!(ecoli.is_latest());
+        assert_eq!(ecoli.release_date, NaiveDate::from_ymd_opt(2013, 9, 26));
+        assert_eq!(ecoli.paired_accession.as_deref(), Some("GCA_000005845.2"));
+        assert_eq!(ecoli.annotation_date, None);
+    }
+
+    #[test]
+    fn test_file_paths() {
+        let ecoli = AssemblySummary::parse_summary(SUMMARY).unwrap().remove(0);
+        assert_eq!(ecoli.directory(), "/genomes/all/GCF/000/005/845/GCF_000005845.2_ASM584v2");
+        assert_eq!(
+            ecoli.file_path(AssemblyFileKind::ProteinFasta),
+            "/genomes/all/GCF/000/005/845/GCF_000005845.2_ASM584v2/GCF_000005845.2_ASM584v2_protein.faa.gz"
+        );
+    }
+
+    #[test]
+    fn test_parse_summary_requires_header() {
+        assert!(AssemblySummary::parse_summary("GCF_000005845.2\tPRJNA57779\n").is_err());
+    }
+}
@@ -0,0 +1,204 @@
+// RefSeq assembly file parsers
+//
+// Genomic FASTA files of large assemblies run to gigabytes, so the FASTA parsers
+// read line by line from a (decompressing) reader and only keep the numbers
+// and protein hashes they need.
+
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::io::BufRead;
+
+use super::models::{AssemblyStats, RefseqProtein};
+use crate::ingest::framework::gff::GffReader;
+use crate::ingest::framework::GffFormat;
+
+/// Sequence statistics of a genomic FASTA file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FastaStats {
+    pub total_length: i64,
+    pub sequence_count: i32,
+    /// GC content over unambiguous bases (A, C, G, T)
+    pub gc_percent: Option<f64>,
+    pub n50: i64,
+}
+
+impl FastaStats {
+    /// Combine with annotation counts into assembly stats
+    pub fn into_assembly_stats(
+        self,
+        gene_count: Option<i32>,
+        protein_count: Option<i32>,
+    ) -> AssemblyStats {
+        AssemblyStats {
+            total_length: self.total_length,
+            sequence_count: self.sequence_count,
+            gc_percent: self.gc_percent,
+            scaffold_n50: self.n50,
+            gene_count,
+            protein_count,
+        }
+    }
+}
+
+/// Compute length, count, GC content and N50 of a genomic FASTA file
+pub fn fasta_stats(reader: impl BufRead) -> Result<FastaStats> {
+    let mut lengths: Vec<i64> = Vec::new();
+    let mut gc: i64 = 0;
+    let mut at: i64 = 0;
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.with_context(|| format!("Failed to read FASTA line {}", index + 1))?;
+        let line = line.trim_end();
+        if line.starts_with('>') {
+            lengths.push(0);
+            continue;
+        }
+        let Some(length) = lengths.last_mut() else {
+            if line.is_empty() {
+                continue;
+            }
+            anyhow::bail!("Line {}: sequence data before the first FASTA header", index + 1);
+        };
+        *length += line.len() as i64;
+        for base in line.bytes() {
+            match base {
+                b'G' | b'C' | b'g' | b'c' => gc += 1,
+                b'A' | b'T' | b'a' | b't' => at += 1,
+                _ => {},
+            }
+        }
+    }
+
+    let total_length: i64 = lengths.iter().sum();
+    let gc_percent = (gc + at > 0).then(|| {
+        let percent = gc as f64 * 100.0 / (gc + at) as f64;
+        (percent * 100.0).round() / 100.0
+    });
+
+    Ok(FastaStats {
+        total_length,
+        sequence_count: lengths.len() as i32,
+        gc_percent,
+        n50: n50(lengths, total_length),
+    })
+}
+
+/// Length of the shortest sequence among the longest covering half the assembly
+fn n50(mut lengths: Vec<i64>, total_length: i64) -> i64 {
+    lengths.sort_unstable_by(|a, b| b.cmp(a));
+    let mut covered = 0;
+    for length in lengths {
+        covered += length;
+        if covered * 2 >= total_length {
+            return length;
+        }
+    }
+    0
+}
+
+/// Count the genes of a decompressed GFF3 annotation
+///
+/// Counts `gene` features; pseudogenes are typed `pseudogene` in RefSeq
+/// annotations and are not included.
+pub fn count_gff_genes(data: &[u8]) -> Result<i32> {
+    let mut reader = GffReader::new(data, GffFormat::Gff3);
+    let mut genes = 0;
+    while let Some((_, feature)) = reader.next_feature()? {
+        if feature.feature_type == "gene" {
+            genes += 1;
+        }
+    }
+    Ok(genes)
+}
+
+/// Parse a RefSeq protein FASTA file
+///
+/// Headers look like `>NP_414542.1 thr operon leader peptide [Escherichia coli ...]`.
+/// Sequences are hashed the way UniProt sequences are stored, so identical
+/// sequences can be matched to UniProt entries.
+pub fn parse_protein_fasta(reader: impl BufRead) -> Result<Vec<RefseqProtein>> {
+    let mut proteins = Vec::new();
+    let mut current: Option<(String, Option<String>)> = None;
+    let mut sequence = String::new();
+
+    let mut finish = |current: Option<(String, Option<String>)>, sequence: &mut String| {
+        if let Some((accession, description)) = current {
+            proteins.push(RefseqProtein {
+                accession,
+                description,
+                length: sequence.len(),
+                sequence_hash: format!("{:x}", Sha256::digest(sequence.as_bytes())),
+            });
+        }
+        sequence.clear();
+    };
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.with_context(|| format!("Failed to read FASTA line {}", index + 1))?;
+        let line = line.trim();
+        if let Some(header) = line.strip_prefix('>') {
+            finish(current.take(), &mut sequence);
+            let (accession, description) = match header.split_once(char::is_whitespace) {
+                Some((accession, description)) => (accession, Some(description.trim().to_string())),
+                None => (header, None),
+            };
+            if accession.is_empty() {
+                anyhow::bail!("Line {}: FASTA header without an accession", index + 1);
+            }
+            current = Some((accession.to_string(), description));
+        } else if current.is_some() {
+            sequence.push_str(&line.to_ascii_uppercase());
+        } else if !line.is_empty() {
+            anyhow::bail!("Line {}: sequence data before the first FASTA header", index + 1);
+        }
+    }
+    finish(current, &mut sequence);
+
+    Ok(proteins)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fasta_stats() {
+        let fasta = ">chr1 test\nGGCC\nAATT\n>chr2\nGCNN\n>plasmid\nAT\n";
+        let stats = fasta_stats(fasta.as_bytes()).unwrap();
+        assert_eq!(stats.total_length, 14);
+        assert_eq!(stats.sequence_count, 3);
+        // 6 G/C out of 12 unambiguous bases
+        assert_eq!(stats.gc_percent, Some(50.0));
+        assert_eq!(stats.n50, 8);
+    }
+
+    #[test]
+    fn test_fasta_stats_rejects_headerless_sequence() {
+        assert!(fasta_stats("ACGT\n>chr1\nACGT\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_count_gff_genes() {
+        let gff = "##gff-version 3\n\
+NC_000913.3\tRefSeq\tregion\t1\t4641652\t.\t+\t.\tID=NC_000913.3:1..4641652\n\
+NC_000913.3\tRefSeq\tgene\t190\t255\t.\t+\t.\tID=gene-b0001;Name=thrL\n\
+NC_000913.3\tRefSeq\tCDS\t190\t255\t.\t+\t0\tID=cds-NP_414542.1;Parent=gene-b0001\n\
+NC_000913.3\tRefSeq\tpseudogene\t300\t400\t.\t+\t.\tID=gene-b4587\n";
+        assert_eq!(count_gff_genes(gff.as_bytes()).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_parse_protein_fasta() {
+        let faa = ">NP_414542.1 thr operon leader peptide [Escherichia coli]\nMKRISTTITT\ntikfsg\n>WP_000000001.1\nMA\n";
+        let proteins = parse_protein_fasta(faa.as_bytes()).unwrap();
+        assert_eq!(proteins.len(), 2);
+        assert_eq!(proteins[0].accession, "NP_414542.1");
+        assert_eq!(
+            proteins[0].description.as_deref(),
+            Some("thr operon leader peptide [Escherichia coli]")
+        );
+        assert_eq!(proteins[0].length, 16);
+        assert_eq!(proteins[0].sequence_hash, format!("{:x}", Sha256::digest(b"MKRISTTITTTIKFSG")));
+        assert_eq!(proteins[1].description, None);
+    }
+}
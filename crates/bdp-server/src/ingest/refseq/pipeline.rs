@@ -0,0 +1,463 @@
+// RefSeq assembly ingestion pipeline
+//
+// Reads the assembly summaries of the configured RefSeq groups, selects the
+// latest version of the configured assemblies and, for every assembly whose
+// version or annotation changed since the last run, computes assembly stats
+// from its files, links its proteins to UniProt, uploads the files to S3 and
+// creates a new version of the assembly data source with a changelog.
+
+use anyhow::{Context, Result};
+use flate2::read::MultiGzDecoder;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::io::{BufReader, Read};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::info;
+use uuid::Uuid;
+
+use super::config::RefseqAssemblyConfig;
+use super::models::{AssemblyFileKind, AssemblyStats, AssemblySummary, RefseqProtein};
+use super::parser::{count_gff_genes, fasta_stats, parse_protein_fasta};
+use super::storage::{
+    version_assembly_snapshot, AssemblyFile, AssemblySnapshot, RefseqAssemblyStorage,
+};
+use crate::ingest::common::transport::Transport;
+use crate::ingest::framework::{BatchConfig, CreateJobParams, IngestionCoordinator};
+use crate::ingest::versioning::{
+    calculate_next_version, get_latest_version, get_latest_version_id, save_changelog,
+    RefseqAssemblyBumpDetector, VersionBumpDetector,
+};
+use crate::storage::Storage;
+
+/// A file of an assembly on the FTP site
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssemblyFileSource {
+    pub kind: AssemblyFileKind,
+    /// Upstream path (e.g., "/genomes/all/GCF/...")
+    pub path: String,
+    pub filename: String,
+    /// Published bytes (gzipped)
+    pub data: Vec<u8>,
+}
+
+/// An assembly loaded from the FTP site or mirror
+#[derive(Debug, Clone)]
+pub struct LoadedAssembly {
+    pub summary: AssemblySummary,
+    pub stats: AssemblyStats,
+    pub proteins: Vec<RefseqProtein>,
+    pub files: Vec<AssemblyFileSource>,
+}
+
+/// Outcome of ingesting one assembly
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssemblyResult {
+    pub accession_version: String,
+    /// Internal version created, or None when the assembly was already ingested
+    pub version: Option<String>,
+    pub proteins: usize,
+    pub mapped_proteins: usize,
+}
+
+/// Outcome of one run over the assembly summaries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssemblyRunResult {
+    pub assemblies: Vec<AssemblyResult>,
+    pub duration_seconds: f64,
+}
+
+pub struct RefseqAssemblyPipeline {
+    config: RefseqAssemblyConfig,
+    db: PgPool,
+    s3: Storage,
+    organization_id: Uuid,
+}
+
+impl RefseqAssemblyPipeline {
+    /// Create a new pipeline
+    pub fn new(
+        config: RefseqAssemblyConfig,
+        db: PgPool,
+        s3: Storage,
+        organization_id: Uuid,
+    ) -> Self {
+        Self {
+            config,
+            db,
+            s3,
+            organization_id,
+        }
+    }
+
+    /// Ingest the configured assemblies
+    ///
+    /// `release` is the RefSeq release the run belongs to; assemblies are
+    /// versioned by their own accession versions.
+    pub async fn run(&self, release: &str) -> Result<AssemblyRunResult> {
+        self.config
+            .validate()
+            .map_err(|e| anyhow::anyhow!("Invalid RefSeq assembly configuration: {}", e))?;
+
+        let transport = self.config.transport();
+        let coordinator =
+            IngestionCoordinator::new(Arc::new(self.db.clone()), BatchConfig::default());
+
+        let job_id = coordinator
+            .create_job(CreateJobParams {
+                organization_id: self.organization_id,
+                job_type: "refseq_assemblies".to_string(),
+                external_version: release.to_string(),
+                internal_version: "1.0".to_string(),
+                source_url: Some(format!("{}/genomes/refseq", transport.location())),
+                source_metadata: Some(serde_json::json!({
+                    "groups": self.config.groups,
+                    "refseq_categories": self.config.refseq_categories,
+                    "taxonomy_ids": self.config.taxonomy_ids,
+                })),
+                total_records: None,
+            })
+            .await
+            .context("Failed to create ingestion job")?;
+
+        match self.execute(&coordinator, job_id, transport.as_ref()).await {
+            Ok(result) => {
+                coordinator.complete_job(job_id).await?;
+                Ok(result)
+            },
+            Err(e) => {
+                coordinator
+                    .fail_job(job_id, &e.to_string())
+                    .await
+                    .context("Failed to mark job as failed")?;
+                Err(e)
+            },
+        }
+    }
+
+    async fn execute(
+        &self,
+        coordinator: &IngestionCoordinator,
+        job_id: Uuid,
+        transport: &dyn Transport,
+    ) -> Result<AssemblyRunResult> {
+        let start_time = Instant::now();
+        info!(groups = ?self.config.groups, "Starting RefSeq assembly ingestion");
+
+        coordinator.start_download(job_id).await?;
+
+        let assemblies = load_assemblies(transport, &self.config).await?;
+        info!(assemblies = assemblies.len(), "Selected RefSeq assemblies");
+
+        let storage = RefseqAssemblyStorage::new(self.db.clone(), self.organization_id)
+            .with_batch_size(self.config.batch_size);
+        storage.setup_citations().await?;
+
+        coordinator.start_storing(job_id).await?;
+
+        let mut results = Vec::with_capacity(assemblies.len());
+        for summary in assemblies {
+            coordinator.ensure_not_cancelled(job_id).await?;
+            let result = self.ingest_assembly(&storage, transport, summary).await?;
+            results.push(result);
+        }
+
+        let duration = start_time.elapsed();
+        info!(
+            assemblies = results.len(),
+            "RefSeq assembly ingestion complete in {:.2}s",
+            duration.as_secs_f64()
+        );
+
+        Ok(AssemblyRunResult {
+            assemblies: results,
+            duration_seconds: duration.as_secs_f64(),
+        })
+    }
+
+    async fn ingest_assembly(
+        &self,
+        storage: &RefseqAssemblyStorage,
+        transport: &dyn Transport,
+        summary: AssemblySummary,
+    ) -> Result<AssemblyResult> {
+        let data_source_id = storage.get_or_create_assembly_source(&summary).await?;
+        let previous_version_id = get_latest_version_id(&self.db, data_source_id).await?;
+
+        if let Some(version_id) = previous_version_id {
+            if let Some(previous) = version_assembly_snapshot(&self.db, version_id).await? {
+                if previous.accession_version == summary.accession_version
+                    && previous.annotation_date == summary.annotation_date
+                {
+                    info!(
+                        accession = %summary.accession_version,
+                        "RefSeq assembly already ingested, skipping"
+                    );
+                    return Ok(AssemblyResult {
+                        accession_version: summary.accession_version,
+                        version: None,
+                        proteins: 0,
+                        mapped_proteins: 0,
+                    });
+                }
+            }
+        }
+
+        let loaded = load_assembly(transport, summary).await?;
+        let summary = &loaded.summary;
+
+        let mapped_proteins = storage
+            .map_proteins_to_uniprot(data_source_id, &loaded.proteins)
+            .await?;
+        let snapshot = AssemblySnapshot {
+            accession_version: summary.accession_version.clone(),
+            annotation_date: summary.annotation_date,
+            stats: loaded.stats.clone(),
+            mapped_protein_count: mapped_proteins as i32,
+        };
+        storage.update_assembly(data_source_id, &snapshot).await?;
+
+        let changelog = RefseqAssemblyBumpDetector::new()
+            .detect_changes(&self.db, data_source_id, previous_version_id)
+            .await
+            .context("Failed to detect version changes")?;
+
+        let version = match get_latest_version(&self.db, data_source_id).await? {
+            Some(latest) => calculate_next_version(&latest, changelog.bump_type),
+            None => "1.0".to_string(),
+        };
+
+        let slug = summary.slug();
+        let mut files = Vec::with_capacity(loaded.files.len());
+        for file in &loaded.files {
+            let key = self.s3.build_key("refseq", &slug, &version, &file.filename);
+            let upload = self
+                .s3
+                .upload(&key, file.data.clone(), Some("application/gzip".to_string()))
+                .await
+                .with_context(|| format!("Failed to upload {}", file.filename))?;
+            files.push(AssemblyFile {
+                format: file.kind.format().to_string(),
+                s3_key: upload.key,
+                sha256: upload.checksum,
+                size_bytes: upload.size,
+            });
+        }
+
+        let version_id = storage
+            .create_assembly_version(
+                data_source_id,
+                &version,
+                &snapshot,
+                summary.release_date,
+                &files,
+            )
+            .await?;
+        save_changelog(&self.db, version_id, &changelog)
+            .await
+            .context("Failed to save changelog")?;
+
+        info!(
+            accession = %summary.accession_version,
+            version = %version,
+            bump_type = ?changelog.bump_type,
+            proteins = loaded.proteins.len(),
+            mapped_proteins,
+            "Ingested RefSeq assembly"
+        );
+
+        Ok(AssemblyResult {
+            accession_version: summary.accession_version.clone(),
+            version: Some(version),
+            proteins: loaded.proteins.len(),
+            mapped_proteins,
+        })
+    }
+}
+
+/// Latest versions of the configured assemblies, from the group summaries
+pub async fn load_assemblies(
+    transport: &dyn Transport,
+    config: &RefseqAssemblyConfig,
+) -> Result<Vec<AssemblySummary>> {
+    let mut selected = Vec::new();
+    for group in &config.groups {
+        let path = RefseqAssemblyConfig::assembly_summary_path(group);
+        let data = transport.download(&path).await.with_context(|| {
+            format!("Failed to fetch assembly summary of RefSeq group {}", group)
+        })?;
+        let content =
+            String::from_utf8(data.data).with_context(|| format!("{} is not valid UTF-8", path))?;
+        let assemblies = AssemblySummary::parse_summary(&content)
+            .with_context(|| format!("Failed to parse {}", path))?;
+
+        selected.extend(
+            assemblies
+                .into_iter()
+                .filter(|summary| is_selected(summary, config)),
+        );
+    }
+
+    if let Some(limit) = config.assembly_limit {
+        selected.truncate(limit);
+    }
+    Ok(selected)
+}
+
+/// Whether the configuration selects an assembly
+fn is_selected(summary: &AssemblySummary, config: &RefseqAssemblyConfig) -> bool {
+    summary.is_latest()
+        && (config.refseq_categories.is_empty()
+            || summary
+                .refseq_category
+                .as_ref()
+                .is_some_and(|category| config.refseq_categories.contains(category)))
+        && (config.taxonomy_ids.is_empty()
+            || config.taxonomy_ids.contains(&summary.taxonomy_id)
+            || summary
+                .species_taxonomy_id
+                .is_some_and(|id| config.taxonomy_ids.contains(&id)))
+}
+
+/// Fetch the files of an assembly and compute its stats and proteins
+///
+/// The genomic FASTA is required; annotation and protein files are only
+/// published for annotated assemblies.
+pub async fn load_assembly(
+    transport: &dyn Transport,
+    summary: AssemblySummary,
+) -> Result<LoadedAssembly> {
+    let mut files = Vec::new();
+    for kind in AssemblyFileKind::ALL {
+        let path = summary.file_path(kind);
+        let Some(data) = transport.download_if_exists(&path).await? else {
+            if kind == AssemblyFileKind::GenomicFasta {
+                anyhow::bail!("{}{} not found", transport.location(), path);
+            }
+            continue;
+        };
+        files.push(AssemblyFileSource {
+            kind,
+            filename: summary.file_name(kind),
+            path,
+            data: data.data,
+        });
+    }
+
+    let mut fasta = None;
+    let mut gene_count = None;
+    let mut proteins = Vec::new();
+    let mut has_proteins = false;
+    for file in &files {
+        let reader = BufReader::new(MultiGzDecoder::new(file.data.as_slice()));
+        let context = || format!("Failed to parse {}", file.path);
+        match file.kind {
+            AssemblyFileKind::GenomicFasta => {
+                fasta = Some(fasta_stats(reader).with_context(context)?);
+            },
+            AssemblyFileKind::Gff => {
+                let mut data = Vec::new();
+                MultiGzDecoder::new(file.data.as_slice())
+                    .read_to_end(&mut data)
+                    .with_context(context)?;
+                gene_count = Some(count_gff_genes(&data).with_context(context)?);
+            },
+            AssemblyFileKind::ProteinFasta => {
+                proteins = parse_protein_fasta(reader).with_context(context)?;
+                has_proteins = true;
+            },
+        }
+    }
+
+    let protein_count = has_proteins.then_some(proteins.len() as i32);
+    let stats = fasta
+        .unwrap_or_default()
+        .into_assembly_stats(gene_count, protein_count);
+
+    Ok(LoadedAssembly {
+        summary,
+        stats,
+        proteins,
+        files,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::common::transport::TransportConfig;
+    use std::path::PathBuf;
+
+    fn fixture_config() -> RefseqAssemblyConfig {
+        RefseqAssemblyConfig::new()
+            .with_transport(TransportConfig::Local {
+                root: PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/refseq"),
+            })
+            .with_groups(["bacteria"])
+    }
+
+    #[tokio::test]
+    async fn test_load_assemblies_applies_filters() {
+        let config = fixture_config().with_taxonomy_ids([562]);
+        let mirror = config.transport();
+
+        // The replaced version and the non-reference assembly are skipped
+        let assemblies = load_assemblies(mirror.as_ref(), &config).await.unwrap();
+        let accessions: Vec<_> = assemblies
+            .iter()
+            .map(|a| a.accession_version.as_str())
+            .collect();
+        assert_eq!(accessions, vec!["GCF_000005845.2"]);
+
+        let config = config.with_refseq_categories(Vec::<String>::new());
+        let assemblies = load_assemblies(mirror.as_ref(), &config).await.unwrap();
+        assert_eq!(assemblies.len(), 2);
+
+        let config = config.with_taxonomy_ids([9606]);
+        assert!(load_assemblies(mirror.as_ref(), &config)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_load_assembly_from_mirror() {
+        let config = fixture_config().with_taxonomy_ids([562]);
+        let mirror = config.transport();
+        let summary = load_assemblies(mirror.as_ref(), &config)
+            .await
+            .unwrap()
+            .remove(0);
+
+        let loaded = load_assembly(mirror.as_ref(), summary).await.unwrap();
+        assert_eq!(loaded.stats.sequence_count, 2);
+        assert_eq!(loaded.stats.total_length, 180);
+        assert_eq!(loaded.stats.scaffold_n50, 120);
+        assert_eq!(loaded.stats.gene_count, Some(3));
+        assert_eq!(loaded.stats.protein_count, Some(3));
+        assert_eq!(loaded.proteins[0].accession, "NP_414542.1");
+
+        let formats: Vec<_> = loaded.files.iter().map(|f| f.kind.format()).collect();
+        assert_eq!(formats, vec!["fna", "gff", "faa"]);
+    }
+
+    #[tokio::test]
+    async fn test_load_assembly_without_annotation() {
+        let config = fixture_config()
+            .with_taxonomy_ids([562])
+            .with_refseq_categories(Vec::<String>::new());
+        let mirror = config.transport();
+        let summary = load_assemblies(mirror.as_ref(), &config)
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|a| a.accession_version == "GCF_000008865.2")
+            .unwrap();
+
+        let loaded = load_assembly(mirror.as_ref(), summary).await.unwrap();
+        assert_eq!(loaded.files.len(), 1);
+        assert_eq!(loaded.stats.gene_count, None);
+        assert_eq!(loaded.stats.protein_count, None);
+        assert!(loaded.proteins.is_empty());
+    }
+}
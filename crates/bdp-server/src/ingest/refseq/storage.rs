@@ -0,0 +1,680 @@
+// Storage layer for RefSeq genome assemblies
+//
+// Each assembly is a 'genome' data source keyed by its unversioned GCF
+// accession and linked to its organism's taxonomy entry. Reference genomes
+// also become the organism's genome assembly. Proteins of the assembly are
+// linked to UniProt entries through sequence_protein_mappings, either by the
+// RefSeq cross-references of UniProt entries or by identical sequences.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, QueryBuilder};
+use std::collections::{HashMap, HashSet};
+use tracing::{debug, info};
+use uuid::Uuid;
+
+use super::models::{AssemblyStats, AssemblySummary, RefseqProtein};
+use crate::ingest::citations::{ncbi_refseq_policy, setup_citation_policy};
+use crate::ingest::uniprot::taxonomy_helper::TaxonomyHelper;
+
+/// A file published with an assembly version
+#[derive(Debug, Clone)]
+pub struct AssemblyFile {
+    /// Version file format ("fna", "gff", "faa")
+    pub format: String,
+    pub s3_key: String,
+    pub sha256: String,
+    pub size_bytes: i64,
+}
+
+/// What an assembly version was built from, kept in the version's
+/// `additional_metadata` so later versions can be compared against it
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AssemblySnapshot {
+    pub accession_version: String,
+    pub annotation_date: Option<chrono::NaiveDate>,
+    pub stats: AssemblyStats,
+    pub mapped_protein_count: i32,
+}
+
+/// Storage handler for RefSeq assemblies
+pub struct RefseqAssemblyStorage {
+    db: PgPool,
+    organization_id: Uuid,
+    batch_size: usize,
+}
+
+impl RefseqAssemblyStorage {
+    /// Create a new storage handler
+    pub fn new(db: PgPool, organization_id: Uuid) -> Self {
+        Self {
+            db,
+            organization_id,
+            batch_size: 500,
+        }
+    }
+
+    /// Set batch size for protein lookups and mapping inserts
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Set up citation policy for the NCBI organization (idempotent)
+    pub async fn setup_citations(&self) -> Result<()> {
+        let policy_config = ncbi_refseq_policy(self.organization_id, None);
+        setup_citation_policy(&self.db, &policy_config).await?;
+        info!("RefSeq citation policy configured");
+        Ok(())
+    }
+
+    /// Get or create the data source of an assembly
+    ///
+    /// The assembly is linked to its NCBI Taxonomy entry, which is created as
+    /// a stub when the taxonomy hasn't been ingested yet. Reference genomes
+    /// are also recorded as the genome assembly of that taxonomy entry.
+    pub async fn get_or_create_assembly_source(&self, summary: &AssemblySummary) -> Result<Uuid> {
+        let mut taxonomy_helper = TaxonomyHelper::new(self.db.clone(), self.organization_id);
+        let taxonomy_data_source_id = taxonomy_helper
+            .get_or_create_taxonomy(summary.taxonomy_id, &summary.organism_name, &[])
+            .await
+            .context("Failed to get or create taxonomy via TaxonomyHelper")?;
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+
+        let name = format!("{} genome assembly {}", summary.organism_name, summary.assembly_name);
+        let description = match &summary.infraspecific_name {
+            Some(infraspecific) => format!(
+                "RefSeq genome assembly {} of {} ({}): genomic sequences, annotation and proteins",
+                summary.accession(),
+                summary.organism_name,
+                infraspecific
+            ),
+            None => format!(
+                "RefSeq genome assembly {} of {}: genomic sequences, annotation and proteins",
+                summary.accession(),
+                summary.organism_name
+            ),
+        };
+        let data_source_id = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            INSERT INTO registry_entries (organization_id, slug, name, description, entry_type)
+            VALUES ($1, $2, $3, $4, 'data_source')
+            ON CONFLICT (slug) DO UPDATE SET
+                name = EXCLUDED.name,
+                description = EXCLUDED.description,
+                updated_at = NOW()
+            RETURNING id
+            "#,
+        )
+        .bind(self.organization_id)
+        .bind(summary.slug())
+        .bind(&name)
+        .bind(&description)
+        .fetch_one(&mut *tx)
+        .await
+        .context("Failed to create registry entry")?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO data_sources (id, source_type)
+            VALUES ($1, 'genome')
+            ON CONFLICT (id) DO NOTHING
+            "#,
+        )
+        .bind(data_source_id)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to create data source")?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO genome_assembly_metadata (
+                data_source_id, accession, accession_version, assembly_name, organism_name,
+                infraspecific_name, taxonomy_id, species_taxonomy_id, taxonomy_data_source_id,
+                refseq_category, assembly_level, genome_rep, release_date, submitter,
+                paired_accession, ftp_path
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
+            ON CONFLICT (data_source_id) DO UPDATE SET
+                assembly_name = EXCLUDED.assembly_name,
+                organism_name = EXCLUDED.organism_name,
+                infraspecific_name = EXCLUDED.infraspecific_name,
+                taxonomy_id = EXCLUDED.taxonomy_id,
+                species_taxonomy_id = EXCLUDED.species_taxonomy_id,
+                taxonomy_data_source_id = EXCLUDED.taxonomy_data_source_id,
+                refseq_category = EXCLUDED.refseq_category,
+                assembly_level = EXCLUDED.assembly_level,
+                genome_rep = EXCLUDED.genome_rep,
+                release_date = EXCLUDED.release_date,
+                submitter = EXCLUDED.submitter,
+                paired_accession = EXCLUDED.paired_accession,
+                ftp_path = EXCLUDED.ftp_path
+            "#,
+        )
+        .bind(data_source_id)
+        .bind(summary.accession())
+        .bind(&summary.accession_version)
+        .bind(&summary.assembly_name)
+        .bind(&summary.organism_name)
+        .bind(&summary.infraspecific_name)
+        .bind(summary.taxonomy_id)
+        .bind(summary.species_taxonomy_id)
+        .bind(taxonomy_data_source_id)
+        .bind(&summary.refseq_category)
+        .bind(&summary.assembly_level)
+        .bind(&summary.genome_rep)
+        .bind(summary.release_date)
+        .bind(&summary.submitter)
+        .bind(&summary.paired_accession)
+        .bind(&summary.ftp_path)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to upsert assembly metadata")?;
+
+        if summary.is_reference() {
+            sqlx::query(
+                "UPDATE taxonomy_metadata SET genome_assembly_id = $2 WHERE data_source_id = $1",
+            )
+            .bind(taxonomy_data_source_id)
+            .bind(data_source_id)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to link reference genome to taxonomy")?;
+        }
+
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        Ok(data_source_id)
+    }
+
+    /// Record the assembly version, annotation and stats the files were ingested from
+    pub async fn update_assembly(
+        &self,
+        data_source_id: Uuid,
+        snapshot: &AssemblySnapshot,
+    ) -> Result<()> {
+        let stats = &snapshot.stats;
+        sqlx::query(
+            r#"
+            UPDATE genome_assembly_metadata SET
+                accession_version = $2,
+                annotation_date = $3,
+                total_length = $4,
+                sequence_count = $5,
+                gc_percent = $6,
+                scaffold_n50 = $7,
+                gene_count = $8,
+                protein_count = $9,
+                mapped_protein_count = $10
+            WHERE data_source_id = $1
+            "#,
+        )
+        .bind(data_source_id)
+        .bind(&snapshot.accession_version)
+        .bind(snapshot.annotation_date)
+        .bind(stats.total_length)
+        .bind(stats.sequence_count)
+        .bind(stats.gc_percent)
+        .bind(stats.scaffold_n50)
+        .bind(stats.gene_count)
+        .bind(stats.protein_count)
+        .bind(snapshot.mapped_protein_count)
+        .execute(&self.db)
+        .await
+        .context("Failed to update assembly stats")?;
+
+        Ok(())
+    }
+
+    /// Link the proteins of an assembly to UniProt entries
+    ///
+    /// Proteins are matched by the RefSeq cross-references of UniProt entries
+    /// ('db_xref'), then by identical sequences ('translation'). Mappings of
+    /// earlier versions of the assembly are replaced. Returns the number of
+    /// RefSeq proteins that were linked.
+    pub async fn map_proteins_to_uniprot(
+        &self,
+        data_source_id: Uuid,
+        proteins: &[RefseqProtein],
+    ) -> Result<usize> {
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+
+        sqlx::query("DELETE FROM sequence_protein_mappings WHERE sequence_data_source_id = $1")
+            .bind(data_source_id)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to clear previous protein mappings")?;
+
+        // (UniProt data source, mapping type, RefSeq protein accession)
+        let mut mappings: Vec<(Uuid, &'static str, &str)> = Vec::new();
+        let mut mapped: HashSet<&str> = HashSet::new();
+
+        for chunk in proteins.chunks(self.batch_size) {
+            let accessions: Vec<&str> = chunk.iter().map(|p| p.accession.as_str()).collect();
+            let rows = sqlx::query_as::<_, (String, Uuid)>(
+                r#"
+                SELECT database_id, protein_id
+                FROM protein_cross_references
+                WHERE database = 'RefSeq' AND database_id = ANY($1)
+                "#,
+            )
+            .bind(&accessions)
+            .fetch_all(&mut *tx)
+            .await
+            .context("Failed to look up RefSeq cross-references")?;
+
+            for (accession, protein_data_source_id) in rows {
+                if let Some(protein) = chunk.iter().find(|p| p.accession == accession) {
+                    mappings.push((protein_data_source_id, "db_xref", &protein.accession));
+                    mapped.insert(&protein.accession);
+                }
+            }
+        }
+
+        let unmatched: Vec<&RefseqProtein> = proteins
+            .iter()
+            .filter(|p| !mapped.contains(p.accession.as_str()))
+            .collect();
+        for chunk in unmatched.chunks(self.batch_size) {
+            let mut by_hash: HashMap<&str, Vec<&str>> = HashMap::new();
+            for protein in chunk {
+                by_hash
+                    .entry(protein.sequence_hash.as_str())
+                    .or_default()
+                    .push(&protein.accession);
+            }
+            let hashes: Vec<&str> = by_hash.keys().copied().collect();
+            let rows = sqlx::query_as::<_, (String, Uuid)>(
+                r#"
+                SELECT ps.sequence_hash, pm.data_source_id
+                FROM protein_sequences ps
+                JOIN protein_metadata pm ON pm.sequence_id = ps.id
+                WHERE ps.sequence_hash = ANY($1)
+                "#,
+            )
+            .bind(&hashes)
+            .fetch_all(&mut *tx)
+            .await
+            .context("Failed to look up UniProt sequences")?;
+
+            for (hash, protein_data_source_id) in rows {
+                for &accession in by_hash.get(hash.as_str()).into_iter().flatten() {
+                    mappings.push((protein_data_source_id, "translation", accession));
+                    mapped.insert(accession);
+                }
+            }
+        }
+
+        for chunk in mappings.chunks(self.batch_size) {
+            let mut query_builder = QueryBuilder::new(
+                "INSERT INTO sequence_protein_mappings (sequence_data_source_id, protein_data_source_id, mapping_type, protein_accession) ",
+            );
+            query_builder.push_values(
+                chunk,
+                |mut b, (protein_data_source_id, mapping_type, accession)| {
+                    b.push_bind(data_source_id)
+                        .push_bind(*protein_data_source_id)
+                        .push_bind(*mapping_type)
+                        .push_bind(*accession);
+                },
+            );
+            // One mapping per UniProt entry and type, even when several RefSeq proteins match it
+            query_builder.push(" ON CONFLICT DO NOTHING");
+
+            query_builder
+                .build()
+                .execute(&mut *tx)
+                .await
+                .context("Failed to insert protein mappings")?;
+        }
+
+        tx.commit()
+            .await
+            .context("Failed to commit protein mappings")?;
+
+        debug!(
+            data_source_id = %data_source_id,
+            proteins = proteins.len(),
+            mapped = mapped.len(),
+            "Mapped RefSeq proteins to UniProt"
+        );
+
+        Ok(mapped.len())
+    }
+
+    /// Create an assembly version with its files
+    pub async fn create_assembly_version(
+        &self,
+        data_source_id: Uuid,
+        version: &str,
+        snapshot: &AssemblySnapshot,
+        release_date: Option<chrono::NaiveDate>,
+        files: &[AssemblyFile],
+    ) -> Result<Uuid> {
+        let parsed = crate::ingest::versioning::SemanticVersion::parse(version).unwrap_or_default();
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+
+        let version_id = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            INSERT INTO versions (
+                entry_id, version, external_version, release_date, additional_metadata,
+                version_major, version_minor, version_patch
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id
+            "#,
+        )
+        .bind(data_source_id)
+        .bind(version)
+        .bind(&snapshot.accession_version)
+        .bind(release_date)
+        .bind(serde_json::to_value(snapshot)?)
+        .bind(parsed.major)
+        .bind(parsed.minor)
+        .bind(parsed.patch)
+        .fetch_one(&mut *tx)
+        .await
+        .context("Failed to create version")?;
+
+        for file in files {
+            sqlx::query(
+                r#"
+                INSERT INTO version_files (version_id, format, s3_key, checksum, size_bytes, sha256)
+                VALUES ($1, $2, $3, $4, $5, $4)
+                "#,
+            )
+            .bind(version_id)
+            .bind(&file.format)
+            .bind(&file.s3_key)
+            .bind(&file.sha256)
+            .bind(file.size_bytes)
+            .execute(&mut *tx)
+            .await
+            .with_context(|| {
+                format!("Failed to create version_file for format: {}", file.format)
+            })?;
+        }
+
+        tx.commit().await.context("Failed to commit version")?;
+
+        Ok(version_id)
+    }
+}
+
+/// Snapshot of an assembly as currently stored
+pub async fn current_assembly_snapshot(
+    pool: &PgPool,
+    data_source_id: Uuid,
+) -> Result<AssemblySnapshot> {
+    let row = sqlx::query_as::<
+        _,
+        (
+            String,
+            Option<chrono::NaiveDate>,
+            Option<i64>,
+            Option<i32>,
+            Option<f64>,
+            Option<i64>,
+            Option<i32>,
+            Option<i32>,
+            Option<i32>,
+        ),
+    >(
+        r#"
+        SELECT accession_version, annotation_date, total_length, sequence_count, gc_percent::FLOAT8,
+               scaffold_n50, gene_count, protein_count, mapped_protein_count
+        FROM genome_assembly_metadata
+        WHERE data_source_id = $1
+        "#,
+    )
+    .bind(data_source_id)
+    .fetch_one(pool)
+    .await
+    .context("Failed to get assembly metadata")?;
+
+    Ok(AssemblySnapshot {
+        accession_version: row.0,
+        annotation_date: row.1,
+        stats: AssemblyStats {
+            total_length: row.2.unwrap_or_default(),
+            sequence_count: row.3.unwrap_or_default(),
+            gc_percent: row.4,
+            scaffold_n50: row.5.unwrap_or_default(),
+            gene_count: row.6,
+            protein_count: row.7,
+        },
+        mapped_protein_count: row.8.unwrap_or_default(),
+    })
+}
+
+/// Snapshot an assembly version was created from, if it recorded one
+pub async fn version_assembly_snapshot(
+    pool: &PgPool,
+    version_id: Uuid,
+) -> Result<Option<AssemblySnapshot>> {
+    let metadata: Option<serde_json::Value> =
+        sqlx::query_scalar("SELECT additional_metadata FROM versions WHERE id = $1")
+            .bind(version_id)
+            .fetch_one(pool)
+            .await
+            .context("Failed to get version metadata")?;
+
+    Ok(metadata.and_then(|value| serde_json::from_value(value).ok()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sha2::{Digest, Sha256};
+
+    fn summary(category: &str) -> AssemblySummary {
+        AssemblySummary {
+            accession_version: "GCF_000005845.2".to_string(),
+            refseq_category: Some(category.to_string()),
+            taxonomy_id: 511145,
+            species_taxonomy_id: Some(562),
+            organism_name: "Escherichia coli str. K-12 substr. MG1655".to_string(),
+            infraspecific_name: Some("strain=K-12 substr. MG1655".to_string()),
+            version_status: Some("latest".to_string()),
+            assembly_level: Some("Complete Genome".to_string()),
+            genome_rep: Some("Full".to_string()),
+            release_date: chrono::NaiveDate::from_ymd_opt(2013, 9, 26),
+            assembly_name: "ASM584v2".to_string(),
+            submitter: Some("University of Wisconsin".to_string()),
+            paired_accession: Some("GCA_000005845.2".to_string()),
+            ftp_path:
+                "https://ftp.ncbi.nlm.nih.gov/genomes/all/GCF/000/005/845/GCF_000005845.2_ASM584v2"
+                    .to_string(),
+            annotation_date: None,
+        }
+    }
+
+    fn protein(accession: &str, sequence: &str) -> RefseqProtein {
+        RefseqProtein {
+            accession: accession.to_string(),
+            description: None,
+            length: sequence.len(),
+            sequence_hash: format!("{:x}", Sha256::digest(sequence.as_bytes())),
+        }
+    }
+
+    async fn insert_org(pool: &PgPool) -> sqlx::Result<Uuid> {
+        sqlx::query_scalar::<_, Uuid>(
+            "INSERT INTO organizations (slug, name, is_system) VALUES ('ncbi', 'NCBI', true) RETURNING id",
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Insert a UniProt entry with its sequence and optional RefSeq cross-reference
+    async fn insert_uniprot(
+        pool: &PgPool,
+        org_id: Uuid,
+        accession: &str,
+        sequence: &str,
+        refseq: Option<&str>,
+    ) -> sqlx::Result<Uuid> {
+        let id = sqlx::query_scalar::<_, Uuid>(
+            "INSERT INTO registry_entries (organization_id, slug, name, entry_type) VALUES ($1, $2, $2, 'data_source') RETURNING id",
+        )
+        .bind(org_id)
+        .bind(accession.to_lowercase())
+        .fetch_one(pool)
+        .await?;
+        sqlx::query("INSERT INTO data_sources (id, source_type) VALUES ($1, 'protein')")
+            .bind(id)
+            .execute(pool)
+            .await?;
+        let sequence_id = sqlx::query_scalar::<_, Uuid>(
+            "INSERT INTO protein_sequences (sequence, sequence_hash, sequence_length, sequence_md5) VALUES ($1, $2, $3, '') RETURNING id",
+        )
+        .bind(sequence)
+        .bind(format!("{:x}", Sha256::digest(sequence.as_bytes())))
+        .bind(sequence.len() as i32)
+        .fetch_one(pool)
+        .await?;
+        sqlx::query(
+            "INSERT INTO protein_metadata (data_source_id, accession, sequence_id) VALUES ($1, $2, $3)",
+        )
+        .bind(id)
+        .bind(accession)
+        .bind(sequence_id)
+        .execute(pool)
+        .await?;
+        if let Some(refseq) = refseq {
+            sqlx::query(
+                "INSERT INTO protein_cross_references (protein_id, database, database_id) VALUES ($1, 'RefSeq', $2)",
+            )
+            .bind(id)
+            .bind(refseq)
+            .execute(pool)
+            .await?;
+        }
+        Ok(id)
+    }
+
+    #[sqlx::test]
+    async fn test_assembly_source_links_organism(pool: PgPool) -> sqlx::Result<()> {
+        let org_id = insert_org(&pool).await?;
+        let storage = RefseqAssemblyStorage::new(pool.clone(), org_id);
+
+        let data_source_id = storage
+            .get_or_create_assembly_source(&summary("reference genome"))
+            .await
+            .unwrap();
+        // Idempotent
+        assert_eq!(
+            storage
+                .get_or_create_assembly_source(&summary("reference genome"))
+                .await
+                .unwrap(),
+            data_source_id
+        );
+
+        let (slug, source_type, taxonomy_id, genome_assembly_id): (
+            String,
+            String,
+            i32,
+            Option<Uuid>,
+        ) = sqlx::query_as(
+            r#"
+                SELECT re.slug, ds.source_type, tm.taxonomy_id, tm.genome_assembly_id
+                FROM registry_entries re
+                JOIN data_sources ds ON ds.id = re.id
+                JOIN genome_assembly_metadata gam ON gam.data_source_id = re.id
+                JOIN taxonomy_metadata tm ON tm.data_source_id = gam.taxonomy_data_source_id
+                WHERE re.id = $1
+                "#,
+        )
+        .bind(data_source_id)
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(slug, "refseq-gcf-000005845");
+        assert_eq!(source_type, "genome");
+        assert_eq!(taxonomy_id, 511145);
+        assert_eq!(genome_assembly_id, Some(data_source_id));
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_proteins_are_mapped_to_uniprot(pool: PgPool) -> sqlx::Result<()> {
+        let org_id = insert_org(&pool).await?;
+        let by_xref =
+            insert_uniprot(&pool, org_id, "P0AD86", "MKRISTTITTTITITTGNGAG", Some("NP_414542.1"))
+                .await?;
+        let by_sequence =
+            insert_uniprot(&pool, org_id, "P00561", "MRVLKFGGTSVANAERFLRV", None).await?;
+
+        let storage = RefseqAssemblyStorage::new(pool.clone(), org_id).with_batch_size(1);
+        let data_source_id = storage
+            .get_or_create_assembly_source(&summary("na"))
+            .await
+            .unwrap();
+
+        let proteins = vec![
+            protein("NP_414542.1", "MKRISTTITTTITITTGNGAG"),
+            protein("NP_414543.1", "MRVLKFGGTSVANAERFLRV"),
+            protein("NP_414544.1", "MVKVYAPASSANMSVGFDVL"),
+        ];
+        let mapped = storage
+            .map_proteins_to_uniprot(data_source_id, &proteins)
+            .await
+            .unwrap();
+        assert_eq!(mapped, 2);
+
+        let mut mappings: Vec<(Uuid, String, Option<String>)> = sqlx::query_as(
+            r#"
+            SELECT protein_data_source_id, mapping_type, protein_accession
+            FROM sequence_protein_mappings
+            WHERE sequence_data_source_id = $1
+            "#,
+        )
+        .bind(data_source_id)
+        .fetch_all(&pool)
+        .await?;
+        mappings.sort_by(|a, b| a.1.cmp(&b.1));
+        assert_eq!(
+            mappings,
+            vec![
+                (by_xref, "db_xref".to_string(), Some("NP_414542.1".to_string())),
+                (by_sequence, "translation".to_string(), Some("NP_414543.1".to_string())),
+            ]
+        );
+
+        // Re-mapping replaces the previous mappings
+        let mapped = storage
+            .map_proteins_to_uniprot(data_source_id, &proteins[..1])
+            .await
+            .unwrap();
+        assert_eq!(mapped, 1);
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM sequence_protein_mappings WHERE sequence_data_source_id = $1",
+        )
+        .bind(data_source_id)
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(count, 1);
+
+        // The reference genome link is only set for reference genomes
+        let genome_assembly_id: Option<Uuid> = sqlx::query_scalar(
+            "SELECT genome_assembly_id FROM taxonomy_metadata WHERE taxonomy_id = 511145",
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(genome_assembly_id, None);
+        Ok(())
+    }
+}
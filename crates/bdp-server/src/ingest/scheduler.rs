@@ -1,73 +1,195 @@
 //! Job scheduler
 //!
-//! Sets up and manages the apalis job queue with PostgreSQL storage.
+//! Sets up the apalis job queue with PostgreSQL storage and drives every
+//! source pipeline from its cron schedule. When a schedule is due the
+//! scheduler runs version discovery for its source and enqueues one
+//! [`SourceIngestJob`] per new upstream version; the ingest worker then runs
+//! the pipeline for each job.
+//!
+//! Schedule state lives in `ingestion_schedules`. A replica claims a due
+//! schedule by moving `next_run_at` forward with a compare-and-swap, so each
+//! tick fires on exactly one server. `scheduled_ingestions` keeps a version
+//! from being enqueued twice.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use apalis::prelude::*;
 use apalis_postgres::PostgresStorage;
+use chrono::{DateTime, Utc};
+use cron::Schedule;
+use futures::FutureExt;
 use sqlx::PgPool;
+use std::str::FromStr;
+use std::time::Duration;
 use tokio::task::JoinHandle;
-use tracing::{info, warn};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use super::config::{IngestConfig, SourceScheduleConfig};
+use super::jobs::SourceIngestJob;
+use super::sources::{self, IngestContext, IngestSource};
+use crate::storage::Storage;
+
+/// How often the scheduler checks for due schedules
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Parse a cron expression
+///
+/// Accepts the standard five fields (`0 2 * * *`) as well as the six- and
+/// seven-field forms with leading seconds understood by the `cron` crate.
+pub fn parse_schedule(expression: &str) -> Result<Schedule> {
+    let expression = expression.trim();
+    let normalized = if expression.split_whitespace().count() == 5 {
+        format!("0 {}", expression)
+    } else {
+        expression.to_string()
+    };
+
+    Schedule::from_str(&normalized)
+        .with_context(|| format!("Invalid cron expression '{}'", expression))
+}
+
+/// The tick that follows `previous`
+///
+/// The next tick is at least `check_interval` after `previous` and never in
+/// the past, so missed ticks after downtime collapse into a single run.
+pub fn next_run_after(
+    schedule: &Schedule,
+    previous: DateTime<Utc>,
+    now: DateTime<Utc>,
+    check_interval: Duration,
+) -> Option<DateTime<Utc>> {
+    let one_second = chrono::Duration::seconds(1);
+    let interval = chrono::Duration::from_std(check_interval)
+        .unwrap_or(one_second)
+        .max(one_second);
+    let earliest = (previous + interval).max(now + one_second);
+
+    schedule.after(&(earliest - one_second)).next()
+}
+
+/// Claim the due tick of a schedule
+///
+/// Returns the claimed tick, or `None` when the schedule is not due or
+/// another replica claimed it first.
+pub async fn claim_due_schedule(
+    pool: &PgPool,
+    config: &SourceScheduleConfig,
+    schedule: &Schedule,
+    instance_id: &str,
+) -> Result<Option<DateTime<Utc>>> {
+    let now = Utc::now();
+    let due = sqlx::query_scalar!(
+        "SELECT next_run_at FROM ingestion_schedules WHERE source = $1",
+        config.source.as_str()
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let Some(tick) = due.filter(|tick| *tick <= now) else {
+        return Ok(None);
+    };
 
-use super::{config::IngestConfig, jobs::UniProtIngestJob};
+    let next = next_run_after(schedule, tick, now, config.check_interval())
+        .context("Cron schedule has no upcoming runs")?;
+
+    let claimed = sqlx::query!(
+        r#"
+        UPDATE ingestion_schedules
+        SET next_run_at = $3, claimed_by = $4, last_run_at = NOW(), last_status = 'running'
+        WHERE source = $1 AND next_run_at = $2
+        "#,
+        config.source.as_str(),
+        tick,
+        next,
+        instance_id
+    )
+    .execute(pool)
+    .await?
+    .rows_affected()
+        == 1;
+
+    Ok(claimed.then_some(tick))
+}
 
 /// Job scheduler
 pub struct JobScheduler {
     config: IngestConfig,
     db: PgPool,
+    storage: Storage,
 }
 
 impl JobScheduler {
     /// Create a new job scheduler
-    pub fn new(config: IngestConfig, db: PgPool) -> Self {
-        Self { config, db }
+    pub fn new(config: IngestConfig, db: PgPool, storage: Storage) -> Self {
+        Self {
+            config,
+            db,
+            storage,
+        }
     }
 
     /// Start the scheduler
     ///
     /// This will:
     /// 1. Setup PostgreSQL storage for apalis
-    /// 2. Start worker threads to process jobs
-    /// 3. Setup cron jobs if enabled
+    /// 2. Register the cron schedule of every enabled source
+    /// 3. Start the worker that processes ingestion jobs
+    /// 4. Start the loop that enqueues jobs when schedules are due
     pub async fn start(self) -> Result<JoinHandle<()>> {
         info!("Starting job scheduler");
 
         // Setup PostgreSQL storage for apalis
-        let storage = self.setup_storage().await?;
+        let backend = self.setup_storage().await?;
+
+        let schedules = self.register_schedules().await?;
+        if schedules.is_empty() {
+            info!("No ingestion sources enabled, no cron jobs scheduled");
+        }
 
         info!("Job scheduler initialized with {} workers", self.config.worker_threads);
 
-        // Setup cron jobs if auto-ingest is enabled
-        if self.config.uniprot.auto_ingest_enabled {
-            info!("Auto-ingest is enabled");
-            warn!("Cron job scheduling not yet implemented");
-        } else {
-            info!("Auto-ingest is disabled, no cron jobs scheduled");
-        }
+        let ctx = IngestContext {
+            db: self.db.clone(),
+            storage: self.storage,
+            config: self.config,
+        };
 
         // Spawn the worker in a separate task
         // Monitor::register expects a factory closure that creates workers
-        let handle = tokio::spawn(async move {
+        let worker_backend = backend.clone();
+        let worker_ctx = ctx.clone();
+        let worker = tokio::spawn(async move {
             info!("Job worker started");
             if let Err(e) = Monitor::new()
                 .register(move |_index| {
                     WorkerBuilder::new("bdp-ingest-worker")
-                        .backend(storage.clone())
-                        .build(process_uniprot_job)
+                        .backend(worker_backend.clone())
+                        .data(worker_ctx.clone())
+                        .build(process_ingest_job)
                 })
                 .run()
                 .await
             {
-                tracing::error!("Job worker error: {:?}", e);
+                error!("Job worker error: {:?}", e);
             }
             info!("Job worker stopped");
         });
 
-        Ok(handle)
+        let ticker = ScheduleRunner {
+            ctx,
+            backend,
+            schedules,
+            instance_id: std::env::var("HOSTNAME").unwrap_or_else(|_| Uuid::new_v4().to_string()),
+        };
+        let ticker = tokio::spawn(ticker.run());
+
+        Ok(tokio::spawn(async move {
+            let _ = tokio::join!(worker, ticker);
+        }))
     }
 
     /// Setup PostgreSQL storage for apalis
-    async fn setup_storage(&self) -> Result<PostgresStorage<UniProtIngestJob>> {
+    async fn setup_storage(&self) -> Result<PostgresStorage<SourceIngestJob>> {
         info!("Setting up PostgreSQL storage for apalis");
 
         // Create storage with reference to pool
@@ -78,60 +200,451 @@ impl JobScheduler {
 
         Ok(storage)
     }
+
+    /// Persist the schedule of every enabled source
+    ///
+    /// A schedule whose cron expression is unchanged keeps its stored next
+    /// run, so restarts and additional replicas don't reset it.
+    async fn register_schedules(&self) -> Result<Vec<(SourceScheduleConfig, Schedule)>> {
+        let mut registered = Vec::new();
+
+        for config in self.config.enabled_schedules() {
+            let schedule = parse_schedule(&config.schedule)?;
+            let next = schedule
+                .upcoming(Utc)
+                .next()
+                .context("Cron schedule has no upcoming runs")?;
+
+            let next_run_at = sqlx::query_scalar!(
+                r#"
+                INSERT INTO ingestion_schedules (source, cron_expression, next_run_at)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (source) DO UPDATE SET
+                    cron_expression = EXCLUDED.cron_expression,
+                    next_run_at = CASE
+                        WHEN ingestion_schedules.cron_expression = EXCLUDED.cron_expression
+                        THEN ingestion_schedules.next_run_at
+                        ELSE EXCLUDED.next_run_at
+                    END
+                RETURNING next_run_at
+                "#,
+                config.source.as_str(),
+                config.schedule,
+                next
+            )
+            .fetch_one(&self.db)
+            .await?;
+
+            info!(
+                source = %config.source,
+                schedule = %config.schedule,
+                next_run_at = %next_run_at,
+                "Scheduled ingestion source"
+            );
+            registered.push((config.clone(), schedule));
+        }
+
+        Ok(registered)
+    }
+}
+
+/// Enqueues ingestion jobs when schedules are due
+struct ScheduleRunner {
+    ctx: IngestContext,
+    backend: PostgresStorage<SourceIngestJob>,
+    schedules: Vec<(SourceScheduleConfig, Schedule)>,
+    instance_id: String,
+}
+
+impl ScheduleRunner {
+    async fn run(mut self) {
+        if self.schedules.is_empty() {
+            return;
+        }
+
+        loop {
+            for i in 0..self.schedules.len() {
+                let (config, schedule) = self.schedules[i].clone();
+                match claim_due_schedule(&self.ctx.db, &config, &schedule, &self.instance_id).await
+                {
+                    Ok(Some(tick)) => {
+                        info!(source = %config.source, tick = %tick, "Running scheduled discovery");
+                        let result = self.enqueue_new_versions(config.source).await;
+                        if let Err(e) = self.record_result(config.source, &result).await {
+                            error!(source = %config.source, "Failed to record schedule result: {}", e);
+                        }
+                    },
+                    Ok(None) => {},
+                    Err(e) => error!(source = %config.source, "Failed to claim schedule: {}", e),
+                }
+            }
+
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    /// Discover new versions of a source and enqueue a job for each
+    async fn enqueue_new_versions(&mut self, source: IngestSource) -> Result<usize> {
+        let organization_id = sources::ensure_organization(&self.ctx.db, source).await?;
+        let versions = sources::discover_versions(&self.ctx, source).await?;
+
+        let mut enqueued = 0;
+        for version in versions {
+            if !needs_ingestion(&self.ctx.db, source, organization_id, &version).await? {
+                continue;
+            }
+
+            self.backend
+                .push(SourceIngestJob::new(source, organization_id, version.clone()))
+                .await
+                .map_err(|e| {
+                    anyhow::anyhow!("Failed to enqueue {} {}: {:?}", source, version, e)
+                })?;
+
+            sqlx::query!(
+                r#"
+                INSERT INTO scheduled_ingestions (source, external_version)
+                VALUES ($1, $2)
+                ON CONFLICT DO NOTHING
+                "#,
+                source.as_str(),
+                version
+            )
+            .execute(&self.ctx.db)
+            .await?;
+
+            info!(source = %source, version = %version, "Enqueued ingestion job");
+            enqueued += 1;
+        }
+
+        Ok(enqueued)
+    }
+
+    async fn record_result(&self, source: IngestSource, result: &Result<usize>) -> Result<()> {
+        let (status, error, enqueued) = match result {
+            Ok(enqueued) => ("completed", None, *enqueued as i32),
+            Err(e) => {
+                warn!(source = %source, "Scheduled discovery failed: {:#}", e);
+                ("failed", Some(format!("{:#}", e)), 0)
+            },
+        };
+
+        sqlx::query!(
+            r#"
+            UPDATE ingestion_schedules
+            SET last_status = $2, last_error = $3, last_versions_enqueued = $4, claimed_by = NULL
+            WHERE source = $1
+            "#,
+            source.as_str(),
+            status,
+            error,
+            enqueued
+        )
+        .execute(&self.ctx.db)
+        .await?;
+
+        Ok(())
+    }
 }
 
-/// Process a UniProt ingest job
+/// Whether the scheduler should enqueue an upstream version
 ///
-/// This function is called by the apalis worker to execute a UniProt ingestion job.
-/// It creates a UniProt pipeline instance and runs the ingestion for the specified version.
+/// Versions with a queued or running job, and versions already ingested, are
+/// skipped.
+async fn needs_ingestion(
+    pool: &PgPool,
+    source: IngestSource,
+    organization_id: Uuid,
+    version: &str,
+) -> Result<bool> {
+    let already_scheduled = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM scheduled_ingestions
+            WHERE source = $1 AND external_version = $2
+        ) as "exists!"
+        "#,
+        source.as_str(),
+        version
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(!already_scheduled && !sources::is_ingested(pool, organization_id, version).await?)
+}
+
+/// Forget that a version was enqueued, so the next scheduled run enqueues
+/// it again
+async fn release_scheduled_version(
+    pool: &PgPool,
+    source: IngestSource,
+    version: &str,
+) -> Result<()> {
+    sqlx::query!(
+        "DELETE FROM scheduled_ingestions WHERE source = $1 AND external_version = $2",
+        source.as_str(),
+        version
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Process an ingestion job
+///
+/// Called by the apalis worker for each enqueued [`SourceIngestJob`]. When
+/// the job fails, its version is released so the scheduler retries it on
+/// the next run.
+async fn process_ingest_job(job: SourceIngestJob, ctx: Data<IngestContext>) -> Result<()> {
+    let result = run_ingest_job(&job, &ctx).await;
+
+    if let Err(e) = &result {
+        warn!(
+            source = %job.source,
+            version = %job.external_version,
+            "Ingestion job failed, releasing version for the next scheduled run: {:#}",
+            e
+        );
+        if let Err(e) = release_scheduled_version(&ctx.db, job.source, &job.external_version).await
+        {
+            error!(source = %job.source, "Failed to release scheduled version: {}", e);
+        }
+    }
+
+    result
+}
+
+/// Run an ingestion job
 ///
-/// NOTE: This implementation is ready but currently disabled due to apalis-postgres compilation issues.
-/// Once apalis is re-enabled, this will work out of the box.
-async fn process_uniprot_job(job: UniProtIngestJob) -> Result<()> {
-    info!("Processing UniProt ingest job for organization: {}", job.organization_id);
-
-    // NOTE: The following implementation is ready but commented out because
-    // we need access to the database pool and S3 storage which should be
-    // passed through the job context when apalis is re-enabled.
-    //
-    // When apalis is working again, this function should be updated to:
-    // 1. Accept PgPool and Storage from job context
-    // 2. Create UniProtPipeline with those dependencies
-    // 3. Run the pipeline with the target version
-    //
-    // Example implementation (to be uncommented when apalis is re-enabled):
-    //
-    // use super::uniprot::{UniProtPipeline, UniProtFtpConfig};
-    //
-    // let config = UniProtFtpConfig::default();
-    // let pipeline = UniProtPipeline::new(pool, job.organization_id, config);
-    //
-    // let stats = pipeline.run(job.target_version.as_deref()).await?;
-    //
-    // info!(
-    //     "UniProt ingestion completed: {} entries inserted, {} failed",
-    //     stats.entries_inserted,
-    //     stats.entries_failed
-    // );
-
-    warn!("Job processing is disabled due to apalis-postgres compilation issues");
+/// Versions that were ingested since the job was enqueued are skipped unless
+/// the job is forced; retries and dead-letter reprocessing resume the
+/// existing ingestion job instead.
+async fn run_ingest_job(job: &SourceIngestJob, ctx: &IngestContext) -> Result<()> {
     info!(
-        "Job details: full_sync={}, target_version={:?}",
-        job.full_sync, job.target_version
+        source = %job.source,
+        version = %job.external_version,
+        "Processing ingestion job"
     );
 
-    Ok(())
+    // The pipelines are boxed so their futures are proven Send here rather
+    // than inside the worker's generic bounds, which rustc can't do for them
+    if let Some(job_id) = job.retry_of {
+        return sources::retry_job(
+            ctx,
+            job.source,
+            job.organization_id,
+            &job.external_version,
+            job_id,
+        )
+        .boxed()
+        .await;
+    }
+
+    if let Some(job_id) = job.dead_letters_of {
+        return sources::reprocess_dead_letters(
+            ctx,
+            job.source,
+            job.organization_id,
+            &job.external_version,
+            job_id,
+        )
+        .boxed()
+        .await;
+    }
+
+    if !job.force
+        && sources::is_ingested(&ctx.db, job.organization_id, &job.external_version).await?
+    {
+        info!(
+            source = %job.source,
+            version = %job.external_version,
+            "Version already ingested, skipping"
+        );
+        return Ok(());
+    }
+
+    sources::run_version(ctx, job.source, job.organization_id, &job.external_version)
+        .boxed()
+        .await
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::TimeZone;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_parse_schedule() {
+        assert!(parse_schedule(super::super::config::DEFAULT_INGESTION_SCHEDULE).is_ok());
+        assert!(parse_schedule("30 0 2 * * *").is_ok());
+        assert!(parse_schedule("every day").is_err());
+        assert!(parse_schedule("").is_err());
+    }
+
+    #[test]
+    fn test_next_run_after_follows_cron() {
+        let schedule = parse_schedule("0 2 * * *").unwrap();
+        let tick = utc("2026-01-10T02:00:00Z");
+        let now = utc("2026-01-10T02:00:05Z");
+
+        let next = next_run_after(&schedule, tick, now, Duration::ZERO).unwrap();
+        assert_eq!(next, utc("2026-01-11T02:00:00Z"));
+    }
 
     #[test]
-    fn test_job_scheduler_new() {
+    fn test_next_run_after_honours_check_interval() {
+        let schedule = parse_schedule("0 * * * *").unwrap();
+        let tick = utc("2026-01-10T02:00:00Z");
+        let now = utc("2026-01-10T02:00:05Z");
+
+        let next = next_run_after(&schedule, tick, now, Duration::from_secs(86400)).unwrap();
+        assert_eq!(next, utc("2026-01-11T02:00:00Z"));
+    }
+
+    #[test]
+    fn test_next_run_after_collapses_missed_ticks() {
+        let schedule = parse_schedule("0 2 * * *").unwrap();
+        let tick = utc("2026-01-01T02:00:00Z");
+        let now = utc("2026-01-10T12:00:00Z");
+
+        let next = next_run_after(&schedule, tick, now, Duration::ZERO).unwrap();
+        assert_eq!(next, Utc.with_ymd_and_hms(2026, 1, 11, 2, 0, 0).unwrap());
+    }
+
+    #[sqlx::test]
+    async fn test_claim_due_schedule_once(pool: PgPool) -> sqlx::Result<()> {
+        let config = SourceScheduleConfig {
+            enabled: true,
+            ..SourceScheduleConfig::new(IngestSource::GeneOntology)
+        };
+        let schedule = parse_schedule(&config.schedule).unwrap();
+        let tick = Utc::now() - chrono::Duration::minutes(5);
+
+        sqlx::query!(
+            "INSERT INTO ingestion_schedules (source, cron_expression, next_run_at) VALUES ($1, $2, $3)",
+            config.source.as_str(),
+            config.schedule,
+            tick
+        )
+        .execute(&pool)
+        .await?;
+
+        let first = claim_due_schedule(&pool, &config, &schedule, "replica-a")
+            .await
+            .unwrap();
+        let second = claim_due_schedule(&pool, &config, &schedule, "replica-b")
+            .await
+            .unwrap();
+
+        assert!(first.is_some());
+        assert!(second.is_none());
+
+        let row = sqlx::query!(
+            "SELECT next_run_at, claimed_by, last_status FROM ingestion_schedules WHERE source = $1",
+            config.source.as_str()
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert!(row.next_run_at > Utc::now());
+        assert_eq!(row.claimed_by.as_deref(), Some("replica-a"));
+        assert_eq!(row.last_status.as_deref(), Some("running"));
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_failed_job_is_enqueued_again(pool: PgPool) -> sqlx::Result<()> {
+        let source = IngestSource::GeneOntology;
+        let organization_id = sources::ensure_organization(&pool, source).await.unwrap();
+
+        sqlx::query!(
+            "INSERT INTO scheduled_ingestions (source, external_version) VALUES ($1, $2)",
+            source.as_str(),
+            "2026-01-01"
+        )
+        .execute(&pool)
+        .await?;
+        assert!(!needs_ingestion(&pool, source, organization_id, "2026-01-01")
+            .await
+            .unwrap());
+
+        // Gene Ontology jobs have no work units, so a retry fails straight away
+        let job = SourceIngestJob::new(source, organization_id, "2026-01-01".to_string())
+            .with_retry_of(Uuid::new_v4());
+        let ctx = IngestContext {
+            db: pool.clone(),
+            storage: Storage::new(crate::storage::config::StorageConfig::for_minio(
+                "http://localhost:9000",
+                "test-bucket",
+            ))
+            .await
+            .unwrap(),
+            config: IngestConfig::default(),
+        };
+        assert!(process_ingest_job(job, Data::new(ctx)).await.is_err());
+
+        // The next scheduled run enqueues the version again
+        assert!(needs_ingestion(&pool, source, organization_id, "2026-01-01")
+            .await
+            .unwrap());
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_forced_job_runs_ingested_version(pool: PgPool) -> sqlx::Result<()> {
+        let source = IngestSource::Ensembl;
+        let organization_id = sources::ensure_organization(&pool, source).await.unwrap();
+
+        // An unparseable release makes the pipeline fail as soon as it is reached
+        sqlx::query!(
+            r#"
+            INSERT INTO ingestion_jobs (organization_id, job_type, external_version, internal_version, status)
+            VALUES ($1, 'ensembl', 'current', '1.0', 'completed')
+            "#,
+            organization_id
+        )
+        .execute(&pool)
+        .await?;
+
+        let ctx = IngestContext {
+            db: pool.clone(),
+            storage: Storage::new(crate::storage::config::StorageConfig::for_minio(
+                "http://localhost:9000",
+                "test-bucket",
+            ))
+            .await
+            .unwrap(),
+            config: IngestConfig::default(),
+        };
+        let job = SourceIngestJob::new(source, organization_id, "current".to_string());
+
+        assert!(run_ingest_job(&job, &ctx).await.is_ok());
+
+        let error = run_ingest_job(&job.with_force(true), &ctx)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("Invalid Ensembl release"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_job_scheduler_new() {
         let config = IngestConfig::default();
         let db = PgPool::connect_lazy("postgresql://localhost/test").unwrap();
-        let scheduler = JobScheduler::new(config.clone(), db);
+        let storage = Storage::new(crate::storage::config::StorageConfig::for_minio(
+            "http://localhost:9000",
+            "test-bucket",
+        ))
+        .await
+        .unwrap();
+        let scheduler = JobScheduler::new(config.clone(), db, storage);
 
         assert_eq!(scheduler.config.worker_threads, config.worker_threads);
     }
@@ -0,0 +1,632 @@
+//! Ingestion sources
+//!
+//! The upstream pipelines driven by the scheduler, with the glue to discover
+//! new upstream versions and to run an ingestion for one of them. Each source
+//! ingests under its own system organization.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::info;
+use uuid::Uuid;
+
+use super::config::{IngestConfig, IngestionMode};
+use super::ensembl::{EnsemblConfig, EnsemblPipeline};
+use super::framework::BatchConfig;
+use super::genbank::{GenbankFtpConfig, GenbankOrchestrator};
+use super::gene_ontology::{GoHttpConfig, GoPipeline};
+use super::interpro::{config::InterProConfig, pipeline::InterProPipeline};
+use super::ncbi_taxonomy::{NcbiTaxonomyFtpConfig, NcbiTaxonomyPipeline, TaxonomyVersionDiscovery};
+use super::refseq::{RefseqAssemblyConfig, RefseqAssemblyPipeline};
+use super::structure::{pipeline::pdb_weekly_release, StructureConfig, StructurePipeline};
+use super::uniprot::{DiscoveredVersion, UniProtFtpConfig, UniProtPipeline};
+use crate::storage::Storage;
+
+/// A source pipeline the scheduler can run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IngestSource {
+    Uniprot,
+    NcbiTaxonomy,
+    GeneOntology,
+    Interpro,
+    Genbank,
+    Refseq,
+    Ensembl,
+    Pdb,
+    Alphafold,
+}
+
+impl IngestSource {
+    pub const ALL: [IngestSource; 9] = [
+        IngestSource::Uniprot,
+        IngestSource::NcbiTaxonomy,
+        IngestSource::GeneOntology,
+        IngestSource::Interpro,
+        IngestSource::Genbank,
+        IngestSource::Refseq,
+        IngestSource::Ensembl,
+        IngestSource::Pdb,
+        IngestSource::Alphafold,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IngestSource::Uniprot => "uniprot",
+            IngestSource::NcbiTaxonomy => "ncbi_taxonomy",
+            IngestSource::GeneOntology => "gene_ontology",
+            IngestSource::Interpro => "interpro",
+            IngestSource::Genbank => "genbank",
+            IngestSource::Refseq => "refseq",
+            IngestSource::Ensembl => "ensembl",
+            IngestSource::Pdb => "pdb",
+            IngestSource::Alphafold => "alphafold",
+        }
+    }
+
+    /// Prefix of the `INGEST_<PREFIX>_*` environment variables for this source
+    pub fn env_prefix(&self) -> &'static str {
+        match self {
+            IngestSource::Uniprot => "UNIPROT",
+            IngestSource::NcbiTaxonomy => "NCBI_TAXONOMY",
+            IngestSource::GeneOntology => "GO",
+            IngestSource::Interpro => "INTERPRO",
+            IngestSource::Genbank => "GENBANK",
+            IngestSource::Refseq => "REFSEQ",
+            IngestSource::Ensembl => "ENSEMBL",
+            IngestSource::Pdb => "PDB",
+            IngestSource::Alphafold => "ALPHAFOLD",
+        }
+    }
+
+    /// The source that ingests under the organization with this slug
+    pub fn from_organization_slug(slug: &str) -> Option<Self> {
+        IngestSource::ALL
+            .into_iter()
+            .find(|source| source.organization().0.eq_ignore_ascii_case(slug))
+    }
+
+    /// Slug, name and description of the organization the source ingests under
+    fn organization(&self) -> (&'static str, &'static str, &'static str) {
+        match self {
+            IngestSource::Uniprot => (
+                "uniprot",
+                "Universal Protein Resource",
+                "UniProt Knowledgebase - Protein sequences and functional information",
+            ),
+            IngestSource::NcbiTaxonomy => (
+                "ncbi",
+                "National Center for Biotechnology Information",
+                "NCBI Taxonomy - Classification of organisms",
+            ),
+            IngestSource::GeneOntology => (
+                "gene-ontology",
+                "Gene Ontology Consortium",
+                "Gene Ontology - Ontology of gene function and GO annotations",
+            ),
+            IngestSource::Interpro => (
+                "interpro",
+                "InterPro",
+                "InterPro - Protein families, domains and functional sites",
+            ),
+            IngestSource::Genbank => (
+                "genbank",
+                "GenBank",
+                "GenBank - Annotated collection of publicly available DNA sequences",
+            ),
+            IngestSource::Refseq => (
+                "refseq",
+                "RefSeq",
+                "NCBI Reference Sequence Database - Curated non-redundant sequences",
+            ),
+            IngestSource::Ensembl => (
+                "ensembl",
+                "Ensembl",
+                "Ensembl - Genome annotation, gene models and comparative genomics",
+            ),
+            IngestSource::Pdb => (
+                "pdb",
+                "Protein Data Bank",
+                "Protein Data Bank - Experimentally determined 3D structures of biological macromolecules",
+            ),
+            IngestSource::Alphafold => (
+                "alphafold",
+                "AlphaFold Protein Structure Database",
+                "AlphaFold DB - Predicted protein structures for UniProt entries",
+            ),
+        }
+    }
+}
+
+impl fmt::Display for IngestSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for IngestSource {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        IngestSource::ALL
+            .into_iter()
+            .find(|source| source.as_str() == s)
+            .with_context(|| format!("Unknown ingestion source: {}", s))
+    }
+}
+
+/// Shared dependencies for discovering and running ingestions
+#[derive(Clone)]
+pub struct IngestContext {
+    pub db: PgPool,
+    pub storage: Storage,
+    pub config: IngestConfig,
+}
+
+/// Get the organization a source ingests under, creating it on first use
+pub async fn ensure_organization(pool: &PgPool, source: IngestSource) -> Result<Uuid> {
+    let (slug, name, description) = source.organization();
+
+    sqlx::query!(
+        r#"
+        INSERT INTO organizations (name, slug, description, is_system)
+        VALUES ($1, $2, $3, true)
+        ON CONFLICT (slug) DO NOTHING
+        "#,
+        name,
+        slug,
+        description
+    )
+    .execute(pool)
+    .await?;
+
+    // Fetch the ID in case another process created it concurrently
+    let id = sqlx::query_scalar!("SELECT id FROM organizations WHERE slug = $1", slug)
+        .fetch_one(pool)
+        .await?;
+
+    Ok(id)
+}
+
+/// Whether an upstream version of a source has already been ingested
+pub async fn is_ingested(
+    pool: &PgPool,
+    organization_id: Uuid,
+    external_version: &str,
+) -> Result<bool> {
+    let exists = sqlx::query_scalar!(
+        r#"
+        SELECT (
+            EXISTS(
+                SELECT 1 FROM ingestion_jobs
+                WHERE organization_id = $1 AND external_version = $2 AND status = 'completed'
+            )
+            OR EXISTS(
+                SELECT 1 FROM versions v
+                JOIN registry_entries re ON v.entry_id = re.id
+                WHERE re.organization_id = $1 AND v.external_version = $2
+            )
+        ) as "exists!"
+        "#,
+        organization_id,
+        external_version
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(exists)
+}
+
+/// All upstream versions of a source, oldest first
+pub async fn available_versions(
+    pool: &PgPool,
+    config: &IngestConfig,
+    source: IngestSource,
+) -> Result<Vec<String>> {
+    let versions = match source {
+        IngestSource::Uniprot => {
+            let discovery = super::uniprot::VersionDiscovery::new(uniprot_ftp_config(config));
+            let mut versions = discovery.discover_all_versions().await?;
+            versions.sort();
+            versions.into_iter().map(|v| v.external_version).collect()
+        },
+        IngestSource::NcbiTaxonomy => {
+            let discovery =
+                TaxonomyVersionDiscovery::new(ncbi_taxonomy_config(config), pool.clone());
+            discovery
+                .discover_all_versions()
+                .await?
+                .into_iter()
+                .map(|v| v.external_version)
+                .collect()
+        },
+        IngestSource::GeneOntology => {
+            let discovery = super::gene_ontology::VersionDiscovery::new(go_config(config))?;
+            let mut versions = discovery.discover_all_versions().await?;
+            versions.sort();
+            versions.into_iter().map(|v| v.external_version).collect()
+        },
+        IngestSource::Interpro => {
+            let discovery =
+                super::interpro::version_discovery::VersionDiscovery::new(interpro_config(config));
+            let mut versions = discovery.discover_all_versions().await?;
+            versions.sort_by_key(|v| (v.major, v.minor));
+            versions.into_iter().map(|v| v.external_version).collect()
+        },
+        IngestSource::Genbank | IngestSource::Refseq => {
+            let discovery = super::genbank::VersionDiscovery::new(genbank_config(config, source));
+            let mut versions = discovery.discover_all_versions().await?;
+            versions.sort_by_key(|v| v.release_number);
+            versions.into_iter().map(|v| v.external_version).collect()
+        },
+        IngestSource::Ensembl => {
+            let discovery = super::ensembl::VersionDiscovery::new(&ensembl_config(config))?;
+            discovery
+                .discover_all_versions()
+                .await?
+                .into_iter()
+                .map(|v| v.external_version)
+                .collect()
+        },
+        // Entries are released weekly; only the current release can be fetched
+        IngestSource::Pdb => {
+            vec![pdb_weekly_release(chrono::Utc::now().date_naive()).to_string()]
+        },
+        // Models are published per model version; only the configured one is fetched
+        IngestSource::Alphafold => vec![structure_config(config, source).model_version],
+    };
+
+    Ok(versions)
+}
+
+/// Discover the upstream versions of a source that should be ingested
+///
+/// Returns external version identifiers, oldest first. UniProt backfills every
+/// release since `INGEST_START_FROM_VERSION` when that is set; every other
+/// source tracks its newest release.
+pub async fn discover_versions(ctx: &IngestContext, source: IngestSource) -> Result<Vec<String>> {
+    let versions = match source {
+        IngestSource::Uniprot => {
+            let mut versions = available_versions(&ctx.db, &ctx.config, source).await?;
+
+            let uniprot = &ctx.config.uniprot;
+            if let IngestionMode::Latest(latest) = &uniprot.ingestion_mode {
+                if let Some(ignore_before) = &latest.ignore_before {
+                    versions.retain(|v| v >= ignore_before);
+                }
+            }
+
+            if uniprot.start_from_version.is_empty() {
+                versions.pop().into_iter().collect()
+            } else {
+                versions
+                    .into_iter()
+                    .filter(|v| *v >= uniprot.start_from_version)
+                    .collect()
+            }
+        },
+        IngestSource::NcbiTaxonomy => {
+            let discovery =
+                TaxonomyVersionDiscovery::new(ncbi_taxonomy_config(&ctx.config), ctx.db.clone());
+            discovery
+                .check_for_newer_version()
+                .await?
+                .into_iter()
+                .map(|v| v.external_version)
+                .collect()
+        },
+        _ => available_versions(&ctx.db, &ctx.config, source)
+            .await?
+            .pop()
+            .into_iter()
+            .collect(),
+    };
+
+    Ok(versions)
+}
+
+/// Run the ingestion of one upstream version of a source
+pub async fn run_version(
+    ctx: &IngestContext,
+    source: IngestSource,
+    organization_id: Uuid,
+    external_version: &str,
+) -> Result<()> {
+    info!(source = %source, version = %external_version, "Running ingestion");
+
+    match source {
+        IngestSource::Uniprot => {
+            let ftp_config = uniprot_ftp_config(&ctx.config);
+            let discovery = super::uniprot::VersionDiscovery::new(ftp_config.clone());
+            let version = discovery
+                .discover_all_versions()
+                .await?
+                .into_iter()
+                .find(|v| v.external_version == external_version)
+                .with_context(|| format!("UniProt release {} not found", external_version))?;
+
+            let pipeline = UniProtPipeline::new(
+                Arc::new(ctx.db.clone()),
+                organization_id,
+                ftp_config,
+                BatchConfig::default(),
+                ctx.storage.clone(),
+                ctx.config.uniprot.cache_dir.clone(),
+            );
+            pipeline.ingest_version(&version).await?;
+        },
+        IngestSource::NcbiTaxonomy => {
+            // The current taxdump is ingested without a version; older ones
+            // come from the archive
+            let current = available_versions(&ctx.db, &ctx.config, source)
+                .await?
+                .pop();
+            let version =
+                (current.as_deref() != Some(external_version)).then_some(external_version);
+
+            let pipeline = NcbiTaxonomyPipeline::with_s3(
+                ncbi_taxonomy_config(&ctx.config),
+                ctx.db.clone(),
+                ctx.storage.clone(),
+            );
+            pipeline.run_version(organization_id, version).await?;
+        },
+        IngestSource::GeneOntology => {
+            let pipeline = GoPipeline::new(
+                go_config(&ctx.config),
+                ctx.db.clone(),
+                ctx.storage.clone(),
+                organization_id,
+            );
+            let internal_version = pipeline.internal_version(external_version).await?;
+            pipeline
+                .run_ontology_version(&internal_version, Some(external_version))
+                .await?;
+        },
+        IngestSource::Interpro => {
+            let download_dir = interpro_download_dir(&ctx.config);
+            let pipeline =
+                InterProPipeline::new(ctx.db.clone(), interpro_config(&ctx.config), download_dir);
+            pipeline
+                .run(external_version)
+                .await
+                .map_err(|e| anyhow::anyhow!("InterPro ingestion failed: {}", e))?;
+        },
+        IngestSource::Genbank | IngestSource::Refseq => {
+            // Releases are only published for the current release number
+            let orchestrator = GenbankOrchestrator::new(
+                genbank_config(&ctx.config, source),
+                ctx.db.clone(),
+                ctx.storage.clone(),
+            );
+            orchestrator.run_release(organization_id).await?;
+
+            if source == IngestSource::Refseq {
+                let pipeline = RefseqAssemblyPipeline::new(
+                    refseq_assembly_config(&ctx.config),
+                    ctx.db.clone(),
+                    ctx.storage.clone(),
+                    organization_id,
+                );
+                pipeline.run(external_version).await?;
+            }
+        },
+        IngestSource::Ensembl => {
+            let release = external_version
+                .parse()
+                .with_context(|| format!("Invalid Ensembl release: {}", external_version))?;
+            let pipeline = EnsemblPipeline::new(
+                ensembl_config(&ctx.config),
+                ctx.db.clone(),
+                ctx.storage.clone(),
+                organization_id,
+            );
+            pipeline.run_release(release).await?;
+        },
+        IngestSource::Pdb => {
+            let pipeline = StructurePipeline::new(
+                structure_config(&ctx.config, source),
+                ctx.db.clone(),
+                ctx.storage.clone(),
+                organization_id,
+            );
+            pipeline.run_pdb(external_version).await?;
+        },
+        IngestSource::Alphafold => {
+            let config = structure_config(&ctx.config, source);
+            if config.model_version != external_version {
+                bail!(
+                    "AlphaFold DB model version {} is not the configured version {}",
+                    external_version,
+                    config.model_version
+                );
+            }
+            let pipeline = StructurePipeline::new(
+                config,
+                ctx.db.clone(),
+                ctx.storage.clone(),
+                organization_id,
+            );
+            pipeline.run_alphafold().await?;
+        },
+    }
+
+    Ok(())
+}
+
+/// Re-run the pending work units of an existing ingestion job
+///
+/// Only the UniProt pipeline splits its work into units; the other sources
+/// have nothing to retry.
+pub async fn retry_job(
+    ctx: &IngestContext,
+    source: IngestSource,
+    organization_id: Uuid,
+    external_version: &str,
+    job_id: Uuid,
+) -> Result<()> {
+    info!(source = %source, version = %external_version, job_id = %job_id, "Retrying ingestion job");
+
+    match source {
+        IngestSource::Uniprot => {
+            let (pipeline, version) =
+                uniprot_pipeline_for(ctx, organization_id, external_version).await?;
+            pipeline.retry_work_units(job_id, &version).await
+        },
+        _ => bail!("{} ingestion jobs have no work units to retry", source),
+    }
+}
+
+/// Reprocess the pending dead letters of an existing ingestion job
+///
+/// Only pipelines that record dead letters support this.
+pub async fn reprocess_dead_letters(
+    ctx: &IngestContext,
+    source: IngestSource,
+    organization_id: Uuid,
+    external_version: &str,
+    job_id: Uuid,
+) -> Result<()> {
+    info!(source = %source, version = %external_version, job_id = %job_id, "Reprocessing dead letters");
+
+    match source {
+        IngestSource::Uniprot => {
+            let (pipeline, version) =
+                uniprot_pipeline_for(ctx, organization_id, external_version).await?;
+            let (recovered, still_failing) =
+                pipeline.reprocess_dead_letters(job_id, &version).await?;
+            info!(job_id = %job_id, recovered, still_failing, "Dead letters reprocessed");
+            Ok(())
+        },
+        _ => bail!("{} ingestion jobs have no dead letters to reprocess", source),
+    }
+}
+
+/// Build the UniProt pipeline and find the release an existing job ingested
+async fn uniprot_pipeline_for(
+    ctx: &IngestContext,
+    organization_id: Uuid,
+    external_version: &str,
+) -> Result<(UniProtPipeline, DiscoveredVersion)> {
+    let ftp_config = uniprot_ftp_config(&ctx.config);
+    let discovery = super::uniprot::VersionDiscovery::new(ftp_config.clone());
+    let version = discovery
+        .discover_all_versions()
+        .await?
+        .into_iter()
+        .find(|v| v.external_version == external_version)
+        .with_context(|| format!("UniProt release {} not found", external_version))?;
+
+    let pipeline = UniProtPipeline::new(
+        Arc::new(ctx.db.clone()),
+        organization_id,
+        ftp_config,
+        BatchConfig::default(),
+        ctx.storage.clone(),
+        ctx.config.uniprot.cache_dir.clone(),
+    );
+    Ok((pipeline, version))
+}
+
+/// UniProt download settings, with the source's configured transport
+fn uniprot_ftp_config(config: &IngestConfig) -> UniProtFtpConfig {
+    UniProtFtpConfig {
+        transport: config.transport(IngestSource::Uniprot).cloned(),
+        ..UniProtFtpConfig::default()
+    }
+}
+
+/// NCBI Taxonomy download settings, with the source's configured transport
+fn ncbi_taxonomy_config(config: &IngestConfig) -> NcbiTaxonomyFtpConfig {
+    NcbiTaxonomyFtpConfig {
+        transport: config.transport(IngestSource::NcbiTaxonomy).cloned(),
+        ..NcbiTaxonomyFtpConfig::new()
+    }
+}
+
+fn go_config(config: &IngestConfig) -> GoHttpConfig {
+    GoHttpConfig {
+        transport: config.transport(IngestSource::GeneOntology).cloned(),
+        ..GoHttpConfig::from_env()
+    }
+}
+
+fn interpro_config(config: &IngestConfig) -> InterProConfig {
+    InterProConfig {
+        transport: config.transport(IngestSource::Interpro).cloned(),
+        ..InterProConfig::from_env()
+    }
+}
+
+fn genbank_config(config: &IngestConfig, source: IngestSource) -> GenbankFtpConfig {
+    let genbank = GenbankFtpConfig {
+        transport: config.transport(source).cloned(),
+        ..GenbankFtpConfig::new()
+    };
+    match source {
+        IngestSource::Refseq => genbank.with_refseq(),
+        _ => genbank.with_genbank(),
+    }
+}
+
+fn refseq_assembly_config(config: &IngestConfig) -> RefseqAssemblyConfig {
+    RefseqAssemblyConfig {
+        transport: config.transport(IngestSource::Refseq).cloned(),
+        ..RefseqAssemblyConfig::from_env()
+    }
+}
+
+fn ensembl_config(config: &IngestConfig) -> EnsemblConfig {
+    EnsemblConfig {
+        transport: config.transport(IngestSource::Ensembl).cloned(),
+        ..EnsemblConfig::from_env()
+    }
+}
+
+/// Structure settings; PDB entries and AlphaFold models each have their own transport
+fn structure_config(config: &IngestConfig, source: IngestSource) -> StructureConfig {
+    StructureConfig {
+        transport: config.transport(source).cloned(),
+        ..StructureConfig::from_env()
+    }
+}
+
+fn interpro_download_dir(config: &IngestConfig) -> PathBuf {
+    config.uniprot.cache_dir.join("interpro")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_round_trip() {
+        for source in IngestSource::ALL {
+            assert_eq!(source.as_str().parse::<IngestSource>().unwrap(), source);
+            assert_eq!(serde_json::to_value(source).unwrap(), serde_json::json!(source.as_str()));
+        }
+        assert!("ddbj".parse::<IngestSource>().is_err());
+    }
+
+    #[test]
+    fn test_organization_slugs_are_unique() {
+        let mut slugs: Vec<_> = IngestSource::ALL
+            .iter()
+            .map(|s| s.organization().0)
+            .collect();
+        slugs.sort();
+        slugs.dedup();
+        assert_eq!(slugs.len(), IngestSource::ALL.len());
+    }
+
+    #[test]
+    fn test_from_organization_slug() {
+        assert_eq!(IngestSource::from_organization_slug("uniprot"), Some(IngestSource::Uniprot));
+        assert_eq!(IngestSource::from_organization_slug("NCBI"), Some(IngestSource::NcbiTaxonomy));
+        assert_eq!(IngestSource::from_organization_slug("my-lab"), None);
+    }
+}
@@ -0,0 +1,356 @@
+// PDBx/mmCIF reader
+//
+// Reads the first data block of an mmCIF file into categories of rows, for
+// the metadata categories the structure parser needs. Coordinate categories
+// (atom_site and friends) make up most of a file; their values are tokenized
+// but not kept.
+
+use anyhow::{bail, Result};
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// Categories whose values are skipped while reading
+const SKIPPED_CATEGORIES: &[&str] = &[
+    "atom_site",
+    "atom_site_anisotrop",
+    "ma_qa_metric_local",
+    "pdbx_poly_seq_scheme",
+    "pdbx_nonpoly_scheme",
+];
+
+#[derive(Debug, Default)]
+struct Category {
+    items: Vec<String>,
+    rows: Vec<Vec<Option<String>>>,
+}
+
+/// A data block of an mmCIF file
+#[derive(Debug, Default)]
+pub struct CifBlock {
+    /// Block name, from `data_<name>`
+    pub name: String,
+    categories: HashMap<String, Category>,
+}
+
+/// A row of a category
+#[derive(Debug, Clone, Copy)]
+pub struct CifRow<'a> {
+    items: &'a [String],
+    values: &'a [Option<String>],
+}
+
+impl<'a> CifRow<'a> {
+    /// Value of an item; `None` when it is missing, `.` or `?`
+    pub fn get(&self, item: &str) -> Option<&'a str> {
+        let index = self.items.iter().position(|name| name == item)?;
+        self.values.get(index)?.as_deref()
+    }
+}
+
+impl CifBlock {
+    /// Read the first data block of an mmCIF file
+    pub fn parse(content: &str) -> Result<CifBlock> {
+        let mut tokens = Tokenizer::new(content);
+        let mut block = CifBlock::default();
+        let mut in_block = false;
+        let mut pending = None;
+
+        loop {
+            let token = match pending.take() {
+                Some(token) => token,
+                None => match tokens.next_token()? {
+                    Some(token) => token,
+                    None => break,
+                },
+            };
+
+            match token {
+                Token::Data(name) => {
+                    if in_block {
+                        break;
+                    }
+                    in_block = true;
+                    block.name = name.to_string();
+                },
+                Token::Tag(tag) => {
+                    let value = match tokens.next_token()? {
+                        Some(Token::Value(value)) => value,
+                        _ => bail!("Line {}: {} has no value", tokens.line_number, tag),
+                    };
+                    let (category, item) = split_tag(tag, tokens.line_number)?;
+                    if SKIPPED_CATEGORIES.contains(&category) {
+                        continue;
+                    }
+                    let category = block.categories.entry(category.to_string()).or_default();
+                    if category.rows.is_empty() {
+                        category.rows.push(Vec::new());
+                    }
+                    category.items.push(item.to_string());
+                    category.rows[0].push(value.map(Cow::into_owned));
+                },
+                Token::Loop => {
+                    let mut tags = Vec::new();
+                    let mut next = loop {
+                        match tokens.next_token()? {
+                            Some(Token::Tag(tag)) => tags.push(tag),
+                            other => break other,
+                        }
+                    };
+                    let Some(first) = tags.first() else {
+                        bail!("Line {}: loop_ without tags", tokens.line_number);
+                    };
+                    let (category, _) = split_tag(first, tokens.line_number)?;
+                    let keep = !SKIPPED_CATEGORIES.contains(&category);
+
+                    let mut items = Vec::with_capacity(tags.len());
+                    for tag in &tags {
+                        items.push(split_tag(tag, tokens.line_number)?.1.to_string());
+                    }
+                    let mut rows = Vec::new();
+                    let mut row = Vec::with_capacity(items.len());
+                    while let Some(Token::Value(value)) = next {
+                        if keep {
+                            row.push(value.map(Cow::into_owned));
+                        }
+                        if keep && row.len() == items.len() {
+                            rows.push(std::mem::replace(&mut row, Vec::with_capacity(items.len())));
+                        }
+                        next = tokens.next_token()?;
+                    }
+                    if !row.is_empty() {
+                        bail!(
+                            "Line {}: loop of {} ends with an incomplete row",
+                            tokens.line_number,
+                            category
+                        );
+                    }
+                    if keep {
+                        block
+                            .categories
+                            .insert(category.to_string(), Category { items, rows });
+                    }
+                    pending = next;
+                },
+                Token::Value(_) => {
+                    bail!("Line {}: value without a tag", tokens.line_number);
+                },
+            }
+        }
+
+        if !in_block {
+            bail!("No data block found");
+        }
+        Ok(block)
+    }
+
+    /// Whether the block has a category (without the leading underscore)
+    pub fn has_category(&self, category: &str) -> bool {
+        self.categories.contains_key(category)
+    }
+
+    /// Rows of a category, empty when the block doesn't have it
+    pub fn rows(&self, category: &str) -> impl Iterator<Item = CifRow<'_>> {
+        self.categories
+            .get(category)
+            .into_iter()
+            .flat_map(|category| {
+                category.rows.iter().map(|values| CifRow {
+                    items: &category.items,
+                    values,
+                })
+            })
+    }
+
+    /// Value of an item in the first row of a category
+    pub fn value(&self, category: &str, item: &str) -> Option<&str> {
+        self.rows(category).next()?.get(item)
+    }
+}
+
+/// Split `_category.item` into its parts
+fn split_tag(tag: &str, line_number: usize) -> Result<(&str, &str)> {
+    match tag.trim_start_matches('_').split_once('.') {
+        Some((category, item)) if !category.is_empty() && !item.is_empty() => Ok((category, item)),
+        _ => bail!("Line {}: invalid tag {}", line_number, tag),
+    }
+}
+
+#[derive(Debug)]
+enum Token<'a> {
+    Data(&'a str),
+    Loop,
+    Tag(&'a str),
+    /// `None` for the `.` (inapplicable) and `?` (unknown) placeholders
+    Value(Option<Cow<'a, str>>),
+}
+
+struct Tokenizer<'a> {
+    lines: std::iter::Enumerate<std::str::Lines<'a>>,
+    /// Unread rest of the current line
+    line: &'a str,
+    line_number: usize,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn new(content: &'a str) -> Self {
+        Self {
+            lines: content.lines().enumerate(),
+            line: "",
+            line_number: 0,
+        }
+    }
+
+    /// Read the lines of a text field up to its closing semicolon
+    fn read_text_field(&mut self, first: &str) -> Result<String> {
+        let start = self.line_number;
+        let mut text = first.to_string();
+        loop {
+            let Some((index, line)) = self.lines.next() else {
+                bail!("Line {}: unterminated text field", start);
+            };
+            self.line_number = index + 1;
+            if let Some(after) = line.strip_prefix(';') {
+                self.line = after;
+                return Ok(text.trim().to_string());
+            }
+            if !text.is_empty() {
+                text.push('\n');
+            }
+            text.push_str(line);
+        }
+    }
+
+    fn next_token(&mut self) -> Result<Option<Token<'a>>> {
+        loop {
+            let rest = self.line.trim_start();
+            if rest.is_empty() || rest.starts_with('#') {
+                let Some((index, line)) = self.lines.next() else {
+                    return Ok(None);
+                };
+                self.line_number = index + 1;
+
+                // Text fields start and end with a semicolon at the start of a line
+                if let Some(first) = line.strip_prefix(';') {
+                    let text = self.read_text_field(first)?;
+                    return Ok(Some(Token::Value(Some(Cow::Owned(text)))));
+                }
+
+                self.line = line;
+                continue;
+            }
+
+            if let Some(quote) = rest.chars().next().filter(|c| *c == '\'' || *c == '"') {
+                // A quote only closes a value when followed by whitespace
+                let bytes = rest.as_bytes();
+                let end = (1..bytes.len()).find(|&i| {
+                    bytes[i] == quote as u8
+                        && bytes.get(i + 1).is_none_or(|b| b.is_ascii_whitespace())
+                });
+                let Some(end) = end else {
+                    bail!("Line {}: unterminated quoted value", self.line_number);
+                };
+                self.line = &rest[end + 1..];
+                return Ok(Some(Token::Value(Some(Cow::Borrowed(&rest[1..end])))));
+            }
+
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            let word = &rest[..end];
+            self.line = &rest[end..];
+
+            if word.starts_with('_') {
+                return Ok(Some(Token::Tag(word)));
+            }
+            let lower = word.to_ascii_lowercase();
+            if lower == "loop_" {
+                return Ok(Some(Token::Loop));
+            }
+            if lower.starts_with("data_") {
+                return Ok(Some(Token::Data(&word[5..])));
+            }
+            if lower.starts_with("save_") || lower == "global_" || lower == "stop_" {
+                continue;
+            }
+            return Ok(Some(Token::Value(match word {
+                "." | "?" => None,
+                _ => Some(Cow::Borrowed(word)),
+            })));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CIF: &str = "data_4INS
+#
+_entry.id   4INS
+#
+_struct.entry_id  4INS
+_struct.title     'THE STRUCTURE OF 2ZN PIG INSULIN CRYSTALS AT 1.5 A'S RESOLUTION'
+_struct.pdbx_descriptor ?
+#
+loop_
+_entity.id
+_entity.type
+_entity.pdbx_description
+1 polymer     'INSULIN (CHAIN A)'
+2 polymer     \"INSULIN (CHAIN B)\"
+3 non-polymer 'ZINC ION'
+#
+_entity_poly.entity_id 1
+_entity_poly.pdbx_seq_one_letter_code_can
+;GIVEQCCTSI
+CSLYQLENYCN
+;
+_entity_poly.pdbx_strand_id A,C
+#
+loop_
+_atom_site.group_PDB
+_atom_site.id
+ATOM 1
+ATOM 2
+data_other
+_entry.id OTHER
+";
+
+    #[test]
+    fn test_parse_block() {
+        let block = CifBlock::parse(CIF).unwrap();
+        assert_eq!(block.name, "4INS");
+        assert_eq!(block.value("entry", "id"), Some("4INS"));
+        // Quotes inside a quoted value don't end it unless followed by whitespace
+        assert_eq!(
+            block.value("struct", "title"),
+            Some("THE STRUCTURE OF 2ZN PIG INSULIN CRYSTALS AT 1.5 A'S RESOLUTION")
+        );
+        assert_eq!(block.value("struct", "pdbx_descriptor"), None);
+
+        let entities: Vec<_> = block
+            .rows("entity")
+            .map(|row| row.get("pdbx_description").unwrap())
+            .collect();
+        assert_eq!(entities, vec!["INSULIN (CHAIN A)", "INSULIN (CHAIN B)", "ZINC ION"]);
+
+        assert_eq!(
+            block.value("entity_poly", "pdbx_seq_one_letter_code_can"),
+            Some("GIVEQCCTSI\nCSLYQLENYCN")
+        );
+        assert_eq!(block.value("entity_poly", "pdbx_strand_id"), Some("A,C"));
+
+        // Coordinates are skipped, and only the first block is read
+        assert!(!block.has_category("atom_site"));
+        assert_eq!(block.rows("missing").count(), 0);
+    }
+
+    #[test]
+    fn test_parse_rejects_incomplete_loop() {
+        let cif = "data_X\nloop_\n_entity.id\n_entity.type\n1 polymer\n2\n";
+        assert!(CifBlock::parse(cif).is_err());
+    }
+
+    #[test]
+    fn test_parse_requires_data_block() {
+        assert!(CifBlock::parse("_entry.id 4INS\n").is_err());
+    }
+}
@@ -0,0 +1,242 @@
+// Structure ingestion configuration
+//
+// Environment-based configuration for PDB and AlphaFold DB ingestion. Files
+// are downloaded from the RCSB PDB and AlphaFold DB file services, or through
+// the configured transport with their layout
+// (/download/4INS.cif.gz, /files/AF-P01308-F1-model_v4.cif).
+
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::sync::Arc;
+
+use super::models::StructureArchive;
+use crate::ingest::common::transport::{HttpsTransport, Transport, TransportConfig};
+
+/// Configuration for structure downloads and ingestion
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StructureConfig {
+    /// Base URL of the RCSB PDB file service (default: https://files.rcsb.org)
+    pub pdb_base_url: String,
+
+    /// Base URL of the AlphaFold DB file service (default: https://alphafold.ebi.ac.uk)
+    pub alphafold_base_url: String,
+
+    /// Transport to read upstream files through (None for HTTPS to the base URLs)
+    #[serde(default)]
+    pub transport: Option<TransportConfig>,
+
+    /// PDB IDs to ingest; empty = entries cross-referenced by ingested UniProt entries
+    pub pdb_ids: Vec<String>,
+
+    /// UniProt accessions whose AlphaFold models are ingested; empty =
+    /// ingested UniProt entries with an AlphaFold DB cross-reference
+    pub uniprot_accessions: Vec<String>,
+
+    /// AlphaFold DB model version (e.g., "v4")
+    pub model_version: String,
+
+    /// Batch size for database operations
+    pub batch_size: usize,
+
+    /// Maximum number of structures to ingest per run (None = all)
+    pub structure_limit: Option<usize>,
+}
+
+impl Default for StructureConfig {
+    fn default() -> Self {
+        Self {
+            pdb_base_url: "https://files.rcsb.org".to_string(),
+            alphafold_base_url: "https://alphafold.ebi.ac.uk".to_string(),
+            transport: None,
+            pdb_ids: Vec::new(),
+            uniprot_accessions: Vec::new(),
+            model_version: "v4".to_string(),
+            batch_size: 500,
+            structure_limit: None,
+        }
+    }
+}
+
+impl StructureConfig {
+    /// Create new configuration with defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load configuration from environment variables
+    ///
+    /// Environment variables:
+    /// - INGEST_PDB_BASE_URL
+    /// - INGEST_PDB_IDS (comma-separated, e.g. "4INS,1ZNI")
+    /// - INGEST_ALPHAFOLD_BASE_URL
+    /// - INGEST_ALPHAFOLD_ACCESSIONS (comma-separated UniProt accessions)
+    /// - INGEST_ALPHAFOLD_MODEL_VERSION
+    /// - INGEST_STRUCTURE_BATCH_SIZE
+    /// - INGEST_STRUCTURE_LIMIT
+    ///
+    /// The transport (including `INGEST_STRUCTURE_LOCAL_MIRROR`) is read by
+    /// [`IngestConfig`](crate::ingest::config::IngestConfig).
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+
+        Self {
+            pdb_base_url: env::var("INGEST_PDB_BASE_URL").unwrap_or(defaults.pdb_base_url),
+
+            alphafold_base_url: env::var("INGEST_ALPHAFOLD_BASE_URL")
+                .unwrap_or(defaults.alphafold_base_url),
+
+            transport: None,
+
+            pdb_ids: env::var("INGEST_PDB_IDS")
+                .map(|list| parse_list(&list))
+                .unwrap_or_default(),
+
+            uniprot_accessions: env::var("INGEST_ALPHAFOLD_ACCESSIONS")
+                .map(|list| parse_list(&list))
+                .unwrap_or_default(),
+
+            model_version: env::var("INGEST_ALPHAFOLD_MODEL_VERSION")
+                .ok()
+                .filter(|version| !version.is_empty())
+                .unwrap_or(defaults.model_version),
+
+            batch_size: env::var("INGEST_STRUCTURE_BATCH_SIZE")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.batch_size),
+
+            structure_limit: env::var("INGEST_STRUCTURE_LIMIT")
+                .ok()
+                .and_then(|s| s.parse().ok()),
+        }
+    }
+
+    /// Read upstream files through another transport
+    pub fn with_transport(mut self, transport: TransportConfig) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Transport for the upstream files of an archive
+    pub fn transport(&self, archive: StructureArchive) -> Arc<dyn Transport> {
+        match (&self.transport, archive) {
+            (Some(transport), _) => transport.build(),
+            (None, StructureArchive::Pdb) => {
+                Arc::new(HttpsTransport::new(self.pdb_base_url.clone()))
+            },
+            (None, StructureArchive::Alphafold) => {
+                Arc::new(HttpsTransport::new(self.alphafold_base_url.clone()))
+            },
+        }
+    }
+
+    /// Upstream directory of an archive's files
+    pub fn files_dir(archive: StructureArchive) -> &'static str {
+        match archive {
+            StructureArchive::Pdb => "/download",
+            StructureArchive::Alphafold => "/files",
+        }
+    }
+
+    /// Upstream path of a structure file
+    pub fn file_path(archive: StructureArchive, file_name: &str) -> String {
+        format!("{}/{}", Self::files_dir(archive), file_name)
+    }
+
+    /// Set the PDB IDs to ingest (empty = cross-referenced entries)
+    pub fn with_pdb_ids<S: Into<String>>(mut self, ids: impl IntoIterator<Item = S>) -> Self {
+        self.pdb_ids = ids.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the UniProt accessions whose AlphaFold models are ingested
+    pub fn with_uniprot_accessions<S: Into<String>>(
+        mut self,
+        accessions: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.uniprot_accessions = accessions.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the AlphaFold DB model version
+    pub fn with_model_version(mut self, version: impl Into<String>) -> Self {
+        self.model_version = version.into();
+        self
+    }
+
+    /// Set the maximum number of structures per run
+    pub fn with_structure_limit(mut self, limit: usize) -> Self {
+        self.structure_limit = Some(limit);
+        self
+    }
+
+    /// Set batch size
+    pub fn with_batch_size(mut self, size: usize) -> Self {
+        self.batch_size = size;
+        self
+    }
+
+    /// Validate configuration
+    pub fn validate(&self) -> Result<(), String> {
+        if self.transport.is_none()
+            && (self.pdb_base_url.is_empty() || self.alphafold_base_url.is_empty())
+        {
+            return Err("Base URLs cannot be empty without a transport".to_string());
+        }
+
+        if !self.model_version.starts_with('v') {
+            return Err(format!(
+                "Invalid AlphaFold DB model version '{}', expected e.g. 'v4'",
+                self.model_version
+            ));
+        }
+
+        if self.batch_size == 0 {
+            return Err("Batch size must be greater than 0".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_list(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config() {
+        let config = StructureConfig::default();
+        assert_eq!(config.pdb_base_url, "https://files.rcsb.org");
+        assert_eq!(config.model_version, "v4");
+        assert!(config.pdb_ids.is_empty());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_file_path() {
+        assert_eq!(
+            StructureConfig::file_path(StructureArchive::Pdb, "4INS.cif.gz"),
+            "/download/4INS.cif.gz"
+        );
+        assert_eq!(
+            StructureConfig::file_path(StructureArchive::Alphafold, "AF-P01308-F1-model_v4.cif"),
+            "/files/AF-P01308-F1-model_v4.cif"
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let config = StructureConfig::new().with_model_version("4");
+        assert!(config.validate().is_err());
+
+        let config = StructureConfig::new().with_batch_size(0);
+        assert!(config.validate().is_err());
+    }
+}
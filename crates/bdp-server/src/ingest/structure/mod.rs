@@ -0,0 +1,23 @@
+// Structure ingestion module
+//
+// This module handles ingestion of macromolecular structures: PDB entries and
+// AlphaFold DB models, one data source per structure. PDB entries are
+// versioned by their revision history, AlphaFold models by model version.
+// Polymer chains are cross-linked to the UniProt entries they were built from.
+// Storage: S3 for structure files (mmCIF, PDB format),
+// PostgreSQL for structure metadata, chains and ligands
+
+pub mod cif;
+pub mod config;
+pub mod models;
+pub mod parser;
+pub mod pipeline;
+pub mod storage;
+
+pub use config::StructureConfig;
+pub use models::{
+    Ligand, SourceOrganism, Structure, StructureArchive, StructureChain, StructureFileKind,
+    StructureTarget,
+};
+pub use pipeline::StructurePipeline;
+pub use storage::StructureStorage;
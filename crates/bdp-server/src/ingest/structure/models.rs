@@ -0,0 +1,328 @@
+// Data models for PDB entries and AlphaFold DB models
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Archive a structure comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StructureArchive {
+    /// Experimentally determined structures of the Protein Data Bank
+    Pdb,
+    /// Predicted models of the AlphaFold Protein Structure Database
+    Alphafold,
+}
+
+impl StructureArchive {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pdb => "pdb",
+            Self::Alphafold => "alphafold",
+        }
+    }
+
+    /// Cross-reference database of UniProt entries that points at the archive
+    pub fn uniprot_database(&self) -> &'static str {
+        match self {
+            Self::Pdb => "PDB",
+            Self::Alphafold => "AlphaFoldDB",
+        }
+    }
+}
+
+/// A file published for a structure
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StructureFileKind {
+    /// PDBx/mmCIF coordinates and metadata
+    Mmcif,
+    /// Legacy PDB format, not published for very large structures
+    Pdb,
+}
+
+impl StructureFileKind {
+    pub const ALL: [StructureFileKind; 2] = [Self::Mmcif, Self::Pdb];
+
+    /// Format recorded on the version file
+    pub fn format(&self) -> &'static str {
+        match self {
+            Self::Mmcif => "mmcif",
+            Self::Pdb => "pdb",
+        }
+    }
+}
+
+/// A structure to ingest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StructureTarget {
+    pub archive: StructureArchive,
+    /// PDB ID ("4INS") or AlphaFold DB model ID ("AF-P01308-F1")
+    pub structure_id: String,
+}
+
+impl StructureTarget {
+    /// A PDB entry
+    pub fn pdb(pdb_id: &str) -> Self {
+        Self {
+            archive: StructureArchive::Pdb,
+            structure_id: pdb_id.trim().to_uppercase(),
+        }
+    }
+
+    /// The AlphaFold DB model of a UniProt entry
+    ///
+    /// Only the first fragment is addressed; AlphaFold DB splits just the
+    /// longest human proteins into several fragments.
+    pub fn alphafold(accession: &str) -> Self {
+        Self {
+            archive: StructureArchive::Alphafold,
+            structure_id: format!("AF-{}-F1", accession.trim().to_uppercase()),
+        }
+    }
+
+    /// Registry slug ("4INS" → "pdb-4ins", "AF-P01308-F1" → "af-p01308-f1")
+    pub fn slug(&self) -> String {
+        match self.archive {
+            StructureArchive::Pdb => format!("pdb-{}", self.structure_id.to_lowercase()),
+            StructureArchive::Alphafold => self.structure_id.to_lowercase(),
+        }
+    }
+
+    /// Name of a published file
+    ///
+    /// PDB files are gzipped ("4INS.cif.gz"); AlphaFold DB files are not
+    /// ("AF-P01308-F1-model_v4.cif").
+    pub fn file_name(&self, kind: StructureFileKind, model_version: &str) -> String {
+        let extension = match kind {
+            StructureFileKind::Mmcif => "cif",
+            StructureFileKind::Pdb => "pdb",
+        };
+        match self.archive {
+            StructureArchive::Pdb => format!("{}.{}.gz", self.structure_id, extension),
+            StructureArchive::Alphafold => {
+                format!("{}-model_{}.{}", self.structure_id, model_version, extension)
+            },
+        }
+    }
+}
+
+/// A polymer chain of a structure
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StructureChain {
+    /// Author chain ID ("A")
+    pub chain_id: String,
+    pub entity_id: String,
+    /// Entity description ("INSULIN A CHAIN")
+    pub description: Option<String>,
+    /// Polymer type ("polypeptide(L)")
+    pub polymer_type: Option<String>,
+    pub sequence_length: Option<i32>,
+    /// UniProt accession of the reference sequence
+    pub uniprot_accession: Option<String>,
+}
+
+/// A non-polymer entity bound in a structure
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ligand {
+    /// Chemical component ID ("ZN")
+    pub comp_id: String,
+    pub name: Option<String>,
+    /// Number of instances in the structure
+    pub count: i32,
+}
+
+/// Source organism of a structure
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceOrganism {
+    pub taxonomy_id: i32,
+    pub scientific_name: String,
+}
+
+/// Metadata of a structure, from the categories of its mmCIF file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Structure {
+    pub archive: StructureArchive,
+    pub structure_id: String,
+    pub title: Option<String>,
+    /// Experimental method ("X-RAY DIFFRACTION"), "PREDICTED" for AlphaFold models
+    pub method: Option<String>,
+    /// Resolution in Angstrom
+    pub resolution: Option<f64>,
+    /// Initial release date
+    pub release_date: Option<NaiveDate>,
+    /// Latest PDB revision ("1.3"), or the AlphaFold DB model version ("v4")
+    pub revision: Option<String>,
+    pub revision_date: Option<NaiveDate>,
+    /// Global pLDDT of AlphaFold models
+    pub mean_plddt: Option<f64>,
+    pub organism: Option<SourceOrganism>,
+    pub chains: Vec<StructureChain>,
+    pub ligands: Vec<Ligand>,
+}
+
+impl Structure {
+    /// Target the structure was loaded for
+    pub fn target(&self) -> StructureTarget {
+        StructureTarget {
+            archive: self.archive,
+            structure_id: self.structure_id.clone(),
+        }
+    }
+
+    /// UniProt accessions of the chains, without duplicates
+    pub fn uniprot_accessions(&self) -> Vec<&str> {
+        let mut accessions: Vec<&str> = self
+            .chains
+            .iter()
+            .filter_map(|chain| chain.uniprot_accession.as_deref())
+            .collect();
+        accessions.sort_unstable();
+        accessions.dedup();
+        accessions
+    }
+
+    /// Registry entry name ("PDB 4INS: The structure of 2Zn pig insulin ...")
+    pub fn display_name(&self) -> String {
+        let name = match self.archive {
+            StructureArchive::Pdb => match &self.title {
+                Some(title) => format!("PDB {}: {}", self.structure_id, title),
+                None => format!("PDB {}", self.structure_id),
+            },
+            StructureArchive::Alphafold => {
+                let protein = self.chains.first().and_then(|c| c.description.as_deref());
+                match (protein, self.uniprot_accessions().first()) {
+                    (Some(protein), Some(accession)) => {
+                        format!("AlphaFold model of {} ({})", protein, accession)
+                    },
+                    _ => format!("AlphaFold model {}", self.structure_id),
+                }
+            },
+        };
+        // Registry entry names are limited to 255 characters
+        match name.char_indices().nth(255) {
+            Some((index, _)) => name[..index].to_string(),
+            None => name,
+        }
+    }
+
+    /// Registry entry description, naming the method, organism, chains and ligands
+    pub fn summary(&self) -> String {
+        let mut summary = match self.archive {
+            StructureArchive::Pdb => {
+                let method = self.method.as_deref().unwrap_or("Experimental");
+                match self.resolution {
+                    Some(resolution) => format!(
+                        "PDB entry {} ({} structure at {:.2} Å)",
+                        self.structure_id, method, resolution
+                    ),
+                    None => format!("PDB entry {} ({} structure)", self.structure_id, method),
+                }
+            },
+            StructureArchive::Alphafold => {
+                let version = self.revision.as_deref().unwrap_or("");
+                match self.mean_plddt {
+                    Some(plddt) => format!(
+                        "AlphaFold DB {} predicted structure {} (mean pLDDT {:.1})",
+                        version, self.structure_id, plddt
+                    ),
+                    None => format!(
+                        "AlphaFold DB {} predicted structure {}",
+                        version, self.structure_id
+                    ),
+                }
+            },
+        };
+        if let Some(organism) = &self.organism {
+            summary.push_str(&format!(" of {}", organism.scientific_name));
+        }
+
+        let mut entities: Vec<&str> = Vec::new();
+        for chain in &self.chains {
+            if let Some(description) = chain.description.as_deref() {
+                if !entities.contains(&description) {
+                    entities.push(description);
+                }
+            }
+        }
+        if !entities.is_empty() {
+            summary.push_str(&format!(": {}", entities.join(", ")));
+        }
+
+        let accessions = self.uniprot_accessions();
+        if !accessions.is_empty() {
+            summary.push_str(&format!("; UniProt {}", accessions.join(", ")));
+        }
+        if !self.ligands.is_empty() {
+            let ligands: Vec<&str> = self.ligands.iter().map(|l| l.comp_id.as_str()).collect();
+            summary.push_str(&format!("; ligands {}", ligands.join(", ")));
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain(chain_id: &str, description: &str, accession: &str) -> StructureChain {
+        StructureChain {
+            chain_id: chain_id.to_string(),
+            entity_id: "1".to_string(),
+            description: Some(description.to_string()),
+            polymer_type: Some("polypeptide(L)".to_string()),
+            sequence_length: Some(21),
+            uniprot_accession: Some(accession.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_target_files() {
+        let pdb = StructureTarget::pdb("4ins");
+        assert_eq!(pdb.structure_id, "4INS");
+        assert_eq!(pdb.slug(), "pdb-4ins");
+        assert_eq!(pdb.file_name(StructureFileKind::Mmcif, "v4"), "4INS.cif.gz");
+
+        let model = StructureTarget::alphafold("P01308");
+        assert_eq!(model.structure_id, "AF-P01308-F1");
+        assert_eq!(model.slug(), "af-p01308-f1");
+        assert_eq!(model.file_name(StructureFileKind::Pdb, "v4"), "AF-P01308-F1-model_v4.pdb");
+    }
+
+    #[test]
+    fn test_display_name_and_summary() {
+        let structure = Structure {
+            archive: StructureArchive::Pdb,
+            structure_id: "4INS".to_string(),
+            title: Some("THE STRUCTURE OF 2ZN PIG INSULIN CRYSTALS".to_string()),
+            method: Some("X-RAY DIFFRACTION".to_string()),
+            resolution: Some(1.5),
+            release_date: None,
+            revision: Some("1.3".to_string()),
+            revision_date: None,
+            mean_plddt: None,
+            organism: Some(SourceOrganism {
+                taxonomy_id: 9823,
+                scientific_name: "Sus scrofa".to_string(),
+            }),
+            chains: vec![
+                chain("A", "INSULIN (CHAIN A)", "P01315"),
+                chain("C", "INSULIN (CHAIN A)", "P01315"),
+            ],
+            ligands: vec![Ligand {
+                comp_id: "ZN".to_string(),
+                name: Some("ZINC ION".to_string()),
+                count: 2,
+            }],
+        };
+        assert_eq!(structure.display_name(), "PDB 4INS: THE STRUCTURE OF 2ZN PIG INSULIN CRYSTALS");
+        assert_eq!(
+            structure.summary(),
+            "PDB entry 4INS (X-RAY DIFFRACTION structure at 1.50 Å) of Sus scrofa: INSULIN (CHAIN A); UniProt P01315; ligands ZN"
+        );
+
+        let long = Structure {
+            title: Some("A".repeat(300)),
+            ..structure
+        };
+        assert_eq!(long.display_name().chars().count(), 255);
+    }
+}
@@ -0,0 +1,315 @@
+// Structure metadata parser
+//
+// Extracts the metadata of a PDB entry or AlphaFold DB model from the
+// categories of its mmCIF file:
+//   _struct.title, _exptl.method                       title and method
+//   _refine, _em_3d_reconstruction, _reflns            resolution
+//   _pdbx_audit_revision_history                       release date and revision
+//   _entity, _entity_poly, _struct_ref                 chains and their UniProt entries
+//   _pdbx_entity_nonpoly, _struct_asym                 ligands
+//   _entity_src_gen, _entity_src_nat, ...              source organism
+//   _ma_qa_metric_global, _ma_target_ref_db_details    AlphaFold confidence and target
+
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use std::collections::HashMap;
+
+use super::cif::CifBlock;
+use super::models::{Ligand, SourceOrganism, Structure, StructureArchive, StructureChain};
+
+/// Solvent components that are not reported as ligands
+const SOLVENTS: &[&str] = &["HOH", "DOD", "H2O"];
+
+/// Method recorded for AlphaFold models, which have no `_exptl` category
+pub const PREDICTED_METHOD: &str = "PREDICTED";
+
+/// Parse the metadata of a structure from its mmCIF file
+pub fn parse_structure(content: &str, archive: StructureArchive) -> Result<Structure> {
+    let block = CifBlock::parse(content).context("Failed to read mmCIF file")?;
+
+    let structure_id = block
+        .value("entry", "id")
+        .unwrap_or(&block.name)
+        .to_uppercase();
+    if structure_id.is_empty() {
+        anyhow::bail!("mmCIF file has no entry ID");
+    }
+
+    let methods: Vec<&str> = block
+        .rows("exptl")
+        .filter_map(|row| row.get("method"))
+        .collect();
+    let method = if !methods.is_empty() {
+        Some(methods.join(", "))
+    } else if archive == StructureArchive::Alphafold {
+        Some(PREDICTED_METHOD.to_string())
+    } else {
+        None
+    };
+
+    let resolution = [
+        ("refine", "ls_d_res_high"),
+        ("em_3d_reconstruction", "resolution"),
+        ("reflns", "d_resolution_high"),
+    ]
+    .iter()
+    .find_map(|(category, item)| block.value(category, item)?.parse::<f64>().ok())
+    .map(round2);
+
+    let (release_date, revision, revision_date) = revision_history(&block);
+
+    let mean_plddt = block
+        .value("ma_qa_metric_global", "metric_value")
+        .and_then(|value| value.parse::<f64>().ok())
+        .map(round2);
+
+    Ok(Structure {
+        archive,
+        structure_id,
+        title: block.value("struct", "title").map(str::to_string),
+        method,
+        resolution,
+        release_date,
+        revision,
+        revision_date,
+        mean_plddt,
+        organism: source_organism(&block),
+        chains: chains(&block),
+        ligands: ligands(&block),
+    })
+}
+
+/// Initial release date, latest revision ("major.minor") and its date
+fn revision_history(block: &CifBlock) -> (Option<NaiveDate>, Option<String>, Option<NaiveDate>) {
+    let revisions: Vec<_> = block
+        .rows("pdbx_audit_revision_history")
+        .filter(|row| {
+            row.get("data_content_type")
+                .is_none_or(|content| content == "Structure model")
+        })
+        .collect();
+
+    let date = |value: Option<&str>| {
+        value.and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+    };
+    let release_date = revisions
+        .first()
+        .and_then(|row| date(row.get("revision_date")));
+    let Some(latest) = revisions.last() else {
+        return (release_date, None, None);
+    };
+    let revision = match (latest.get("major_revision"), latest.get("minor_revision")) {
+        (Some(major), Some(minor)) => Some(format!("{}.{}", major, minor)),
+        (Some(major), None) => Some(format!("{}.0", major)),
+        _ => None,
+    };
+
+    (release_date, revision, date(latest.get("revision_date")))
+}
+
+/// Polymer chains, one per author chain ID of each polymer entity
+fn chains(block: &CifBlock) -> Vec<StructureChain> {
+    let descriptions: HashMap<&str, &str> = block
+        .rows("entity")
+        .filter_map(|row| Some((row.get("id")?, row.get("pdbx_description")?)))
+        .collect();
+
+    let mut accessions: HashMap<&str, &str> = HashMap::new();
+    for row in block.rows("struct_ref") {
+        if let (Some("UNP"), Some(entity_id), Some(accession)) =
+            (row.get("db_name"), row.get("entity_id"), row.get("pdbx_db_accession"))
+        {
+            accessions.entry(entity_id).or_insert(accession);
+        }
+    }
+    // AlphaFold models name their UniProt target here as well
+    let target = block
+        .rows("ma_target_ref_db_details")
+        .find(|row| row.get("db_name") == Some("UNP"))
+        .and_then(|row| row.get("db_accession"));
+
+    let mut chains = Vec::new();
+    for row in block.rows("entity_poly") {
+        let Some(entity_id) = row.get("entity_id") else {
+            continue;
+        };
+        let sequence_length = row
+            .get("pdbx_seq_one_letter_code_can")
+            .map(|sequence| sequence.chars().filter(|c| !c.is_whitespace()).count() as i32);
+        let uniprot_accession = accessions.get(entity_id).copied().or(target);
+
+        for chain_id in row
+            .get("pdbx_strand_id")
+            .unwrap_or("")
+            .split(',')
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+        {
+            chains.push(StructureChain {
+                chain_id: chain_id.to_string(),
+                entity_id: entity_id.to_string(),
+                description: descriptions.get(entity_id).map(|d| d.to_string()),
+                polymer_type: row.get("type").map(str::to_string),
+                sequence_length,
+                uniprot_accession: uniprot_accession.map(str::to_string),
+            });
+        }
+    }
+    chains
+}
+
+/// Non-polymer entities other than solvent, with their instance counts
+fn ligands(block: &CifBlock) -> Vec<Ligand> {
+    let mut instances: HashMap<&str, i32> = HashMap::new();
+    for entity_id in block
+        .rows("struct_asym")
+        .filter_map(|row| row.get("entity_id"))
+    {
+        *instances.entry(entity_id).or_default() += 1;
+    }
+
+    block
+        .rows("pdbx_entity_nonpoly")
+        .filter_map(|row| {
+            let comp_id = row.get("comp_id")?;
+            if SOLVENTS.contains(&comp_id) {
+                return None;
+            }
+            let count = row
+                .get("entity_id")
+                .and_then(|id| instances.get(id).copied())
+                .unwrap_or(1);
+            Some(Ligand {
+                comp_id: comp_id.to_string(),
+                name: row.get("name").map(str::to_string),
+                count,
+            })
+        })
+        .collect()
+}
+
+/// Organism of the first polymer entity that names one
+fn source_organism(block: &CifBlock) -> Option<SourceOrganism> {
+    const SOURCES: [(&str, &str, &str); 4] = [
+        (
+            "entity_src_gen",
+            "pdbx_gene_src_ncbi_taxonomy_id",
+            "pdbx_gene_src_scientific_name",
+        ),
+        ("entity_src_nat", "pdbx_ncbi_taxonomy_id", "pdbx_organism_scientific"),
+        ("pdbx_entity_src_syn", "ncbi_taxonomy_id", "organism_scientific"),
+        ("ma_target_ref_db_details", "ncbi_taxonomy_id", "organism_scientific"),
+    ];
+
+    SOURCES
+        .iter()
+        .find_map(|(category, taxonomy_item, name_item)| {
+            block.rows(category).find_map(|row| {
+                // Chimeric entities list several taxonomy IDs
+                let taxonomy_id = row
+                    .get(taxonomy_item)?
+                    .split(',')
+                    .next()?
+                    .trim()
+                    .parse()
+                    .ok()?;
+                Some(SourceOrganism {
+                    taxonomy_id,
+                    scientific_name: row.get(name_item).unwrap_or("unknown").trim().to_string(),
+                })
+            })
+        })
+}
+
+/// Chain IDs of a UniProt PDB cross-reference
+///
+/// The last field of `DR   PDB; 4INS; X-ray; 1.50 A; A/C=1-21, B/D=25-54.`
+/// lists the chains built from the UniProt sequence.
+pub fn uniprot_pdb_chains(metadata: &[String]) -> Vec<String> {
+    let Some(chains) = metadata.last() else {
+        return Vec::new();
+    };
+    chains
+        .trim_end_matches('.')
+        .split(',')
+        .filter_map(|segment| segment.split('=').next())
+        .flat_map(|ids| ids.split('/'))
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty() && id != "-")
+        .collect()
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PDB_CIF_GZ: &[u8] =
+        include_bytes!("../../../tests/fixtures/structure/download/4INS.cif.gz");
+    const ALPHAFOLD_CIF: &str =
+        include_str!("../../../tests/fixtures/structure/files/AF-P01308-F1-model_v4.cif");
+
+    #[test]
+    fn test_parse_pdb_entry() {
+        let cif = crate::ingest::common::decompression::decompress_gzip(PDB_CIF_GZ).unwrap();
+        let structure =
+            parse_structure(std::str::from_utf8(&cif).unwrap(), StructureArchive::Pdb).unwrap();
+        assert_eq!(structure.structure_id, "4INS");
+        assert_eq!(structure.method.as_deref(), Some("X-RAY DIFFRACTION"));
+        assert_eq!(structure.resolution, Some(1.5));
+        assert_eq!(structure.release_date, NaiveDate::from_ymd_opt(1989, 7, 12));
+        assert_eq!(structure.revision.as_deref(), Some("1.4"));
+        assert_eq!(structure.revision_date, NaiveDate::from_ymd_opt(2024, 2, 28));
+        assert_eq!(structure.organism.as_ref().unwrap().taxonomy_id, 9823);
+
+        let chains: Vec<_> = structure
+            .chains
+            .iter()
+            .map(|c| (c.chain_id.as_str(), c.uniprot_accession.as_deref(), c.sequence_length))
+            .collect();
+        assert_eq!(
+            chains,
+            vec![
+                ("A", Some("P01315"), Some(21)),
+                ("C", Some("P01315"), Some(21)),
+                ("B", Some("P01315"), Some(30)),
+                ("D", Some("P01315"), Some(30)),
+            ]
+        );
+
+        // Water is not a ligand
+        assert_eq!(
+            structure.ligands,
+            vec![Ligand {
+                comp_id: "ZN".to_string(),
+                name: Some("ZINC ION".to_string()),
+                count: 2,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_alphafold_model() {
+        let structure = parse_structure(ALPHAFOLD_CIF, StructureArchive::Alphafold).unwrap();
+        assert_eq!(structure.structure_id, "AF-P01308-F1");
+        assert_eq!(structure.method.as_deref(), Some(PREDICTED_METHOD));
+        assert_eq!(structure.resolution, None);
+        assert_eq!(structure.mean_plddt, Some(67.39));
+        assert_eq!(structure.organism.as_ref().unwrap().scientific_name, "Homo sapiens");
+        assert_eq!(structure.chains.len(), 1);
+        assert_eq!(structure.chains[0].uniprot_accession.as_deref(), Some("P01308"));
+        assert_eq!(structure.chains[0].sequence_length, Some(110));
+        assert!(structure.ligands.is_empty());
+    }
+
+    #[test]
+    fn test_uniprot_pdb_chains() {
+        let metadata =
+            vec!["X-ray".to_string(), "1.50 A".to_string(), "A/C=1-21, B/D=25-54.".to_string()];
+        assert_eq!(uniprot_pdb_chains(&metadata), vec!["A", "C", "B", "D"]);
+        assert!(uniprot_pdb_chains(&[]).is_empty());
+    }
+}
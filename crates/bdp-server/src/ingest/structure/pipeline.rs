@@ -0,0 +1,465 @@
+// Structure ingestion pipeline
+//
+// Selects the configured PDB entries or AlphaFold DB models (by default the
+// ones cross-referenced by ingested UniProt entries), reads their metadata
+// from their mmCIF files and links their chains to UniProt. For every
+// structure whose revision changed since the last run, the mmCIF and PDB
+// format files are uploaded to S3 and a new version of the structure data
+// source is created with a changelog.
+
+use anyhow::{Context, Result};
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::config::StructureConfig;
+use super::models::{Structure, StructureArchive, StructureFileKind, StructureTarget};
+use super::parser::parse_structure;
+use super::storage::{
+    version_structure_snapshot, StructureFile, StructureSnapshot, StructureStorage,
+};
+use crate::ingest::common::decompression::decompress_gzip;
+use crate::ingest::common::transport::Transport;
+use crate::ingest::framework::{BatchConfig, CreateJobParams, IngestionCoordinator};
+use crate::ingest::versioning::{
+    calculate_next_version, get_latest_version, get_latest_version_id, save_changelog,
+    StructureBumpDetector, VersionBumpDetector,
+};
+use crate::storage::Storage;
+
+/// A published file of a structure
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructureFileSource {
+    pub kind: StructureFileKind,
+    pub filename: String,
+    /// Published bytes (gzipped for PDB entries)
+    pub data: Vec<u8>,
+}
+
+/// A structure loaded from the file services or mirror
+#[derive(Debug, Clone)]
+pub struct LoadedStructure {
+    pub structure: Structure,
+    pub files: Vec<StructureFileSource>,
+}
+
+/// Outcome of ingesting one structure
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StructureResult {
+    pub structure_id: String,
+    /// Internal version created, or None when the revision was already ingested
+    pub version: Option<String>,
+    pub chains: usize,
+    pub linked_chains: usize,
+}
+
+/// Outcome of one run over the selected structures
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StructureRunResult {
+    pub structures: Vec<StructureResult>,
+    /// Selected structures without published files (obsolete entries, no model)
+    pub missing: Vec<String>,
+    pub duration_seconds: f64,
+}
+
+pub struct StructurePipeline {
+    config: StructureConfig,
+    db: PgPool,
+    s3: Storage,
+    organization_id: Uuid,
+}
+
+impl StructurePipeline {
+    /// Create a new pipeline
+    pub fn new(config: StructureConfig, db: PgPool, s3: Storage, organization_id: Uuid) -> Self {
+        Self {
+            config,
+            db,
+            s3,
+            organization_id,
+        }
+    }
+
+    /// Ingest the configured PDB entries
+    ///
+    /// `release` is the weekly PDB release the run belongs to; entries are
+    /// versioned by their own revisions.
+    pub async fn run_pdb(&self, release: &str) -> Result<StructureRunResult> {
+        self.run(StructureArchive::Pdb, release).await
+    }
+
+    /// Ingest the configured AlphaFold DB models of the configured model version
+    pub async fn run_alphafold(&self) -> Result<StructureRunResult> {
+        self.run(StructureArchive::Alphafold, &self.config.model_version)
+            .await
+    }
+
+    async fn run(
+        &self,
+        archive: StructureArchive,
+        external_version: &str,
+    ) -> Result<StructureRunResult> {
+        self.config
+            .validate()
+            .map_err(|e| anyhow::anyhow!("Invalid structure configuration: {}", e))?;
+
+        let transport = self.config.transport(archive);
+        let coordinator =
+            IngestionCoordinator::new(Arc::new(self.db.clone()), BatchConfig::default());
+
+        let job_type = match archive {
+            StructureArchive::Pdb => "pdb_structures",
+            StructureArchive::Alphafold => "alphafold_models",
+        };
+        let job_id = coordinator
+            .create_job(CreateJobParams {
+                organization_id: self.organization_id,
+                job_type: job_type.to_string(),
+                external_version: external_version.to_string(),
+                internal_version: "1.0".to_string(),
+                source_url: Some(format!(
+                    "{}{}",
+                    transport.location(),
+                    StructureConfig::files_dir(archive)
+                )),
+                source_metadata: Some(serde_json::json!({
+                    "pdb_ids": self.config.pdb_ids,
+                    "uniprot_accessions": self.config.uniprot_accessions,
+                    "model_version": self.config.model_version,
+                })),
+                total_records: None,
+            })
+            .await
+            .context("Failed to create ingestion job")?;
+
+        match self
+            .execute(&coordinator, job_id, transport.as_ref(), archive)
+            .await
+        {
+            Ok(result) => {
+                coordinator.complete_job(job_id).await?;
+                Ok(result)
+            },
+            Err(e) => {
+                coordinator
+                    .fail_job(job_id, &e.to_string())
+                    .await
+                    .context("Failed to mark job as failed")?;
+                Err(e)
+            },
+        }
+    }
+
+    async fn execute(
+        &self,
+        coordinator: &IngestionCoordinator,
+        job_id: Uuid,
+        transport: &dyn Transport,
+        archive: StructureArchive,
+    ) -> Result<StructureRunResult> {
+        let start_time = Instant::now();
+        info!(archive = archive.as_str(), "Starting structure ingestion");
+
+        let storage = StructureStorage::new(self.db.clone(), self.organization_id)
+            .with_batch_size(self.config.batch_size);
+        storage.setup_citations(archive).await?;
+
+        coordinator.start_download(job_id).await?;
+
+        let targets = self.select_targets(&storage, archive).await?;
+        info!(structures = targets.len(), "Selected structures");
+
+        coordinator.start_storing(job_id).await?;
+
+        let mut results = Vec::with_capacity(targets.len());
+        let mut missing = Vec::new();
+        for target in targets {
+            coordinator.ensure_not_cancelled(job_id).await?;
+            match self.ingest_structure(&storage, transport, &target).await? {
+                Some(result) => results.push(result),
+                None => {
+                    warn!(structure = %target.structure_id, "No files published for structure");
+                    missing.push(target.structure_id);
+                },
+            }
+        }
+
+        let duration = start_time.elapsed();
+        info!(
+            structures = results.len(),
+            missing = missing.len(),
+            "Structure ingestion complete in {:.2}s",
+            duration.as_secs_f64()
+        );
+
+        Ok(StructureRunResult {
+            structures: results,
+            missing,
+            duration_seconds: duration.as_secs_f64(),
+        })
+    }
+
+    /// Configured structures, or those cross-referenced by ingested UniProt entries
+    async fn select_targets(
+        &self,
+        storage: &StructureStorage,
+        archive: StructureArchive,
+    ) -> Result<Vec<StructureTarget>> {
+        let configured = match archive {
+            StructureArchive::Pdb => &self.config.pdb_ids,
+            StructureArchive::Alphafold => &self.config.uniprot_accessions,
+        };
+        let ids = if configured.is_empty() {
+            storage
+                .cross_referenced_ids(archive, self.config.structure_limit)
+                .await?
+        } else {
+            configured.clone()
+        };
+
+        let mut targets: Vec<StructureTarget> = Vec::with_capacity(ids.len());
+        for id in &ids {
+            let target = match archive {
+                StructureArchive::Pdb => StructureTarget::pdb(id),
+                StructureArchive::Alphafold => StructureTarget::alphafold(id),
+            };
+            if !targets.contains(&target) {
+                targets.push(target);
+            }
+        }
+        if let Some(limit) = self.config.structure_limit {
+            targets.truncate(limit);
+        }
+        Ok(targets)
+    }
+
+    async fn ingest_structure(
+        &self,
+        storage: &StructureStorage,
+        transport: &dyn Transport,
+        target: &StructureTarget,
+    ) -> Result<Option<StructureResult>> {
+        let Some(loaded) = load_structure(transport, target, &self.config.model_version).await?
+        else {
+            return Ok(None);
+        };
+        let structure = &loaded.structure;
+
+        let data_source_id = storage.get_or_create_structure_source(structure).await?;
+        // Chains are relinked on every run, to pick up newly ingested UniProt entries
+        let linked_chains = storage.store_chains(data_source_id, structure).await?;
+        let previous_version_id = get_latest_version_id(&self.db, data_source_id).await?;
+
+        if let Some(version_id) = previous_version_id {
+            if let Some(previous) = version_structure_snapshot(&self.db, version_id).await? {
+                if previous.revision == structure.revision {
+                    info!(
+                        structure = %structure.structure_id,
+                        revision = ?structure.revision,
+                        "Structure revision already ingested, skipping"
+                    );
+                    return Ok(Some(StructureResult {
+                        structure_id: structure.structure_id.clone(),
+                        version: None,
+                        chains: structure.chains.len(),
+                        linked_chains,
+                    }));
+                }
+            }
+        }
+
+        let snapshot = StructureSnapshot {
+            revision: structure.revision.clone(),
+            revision_date: structure.revision_date,
+            chain_count: structure.chains.len() as i32,
+            ligands: structure
+                .ligands
+                .iter()
+                .map(|l| l.comp_id.clone())
+                .collect(),
+            linked_chain_count: linked_chains as i32,
+        };
+
+        let changelog = StructureBumpDetector::new()
+            .detect_changes(&self.db, data_source_id, previous_version_id)
+            .await
+            .context("Failed to detect version changes")?;
+
+        let version = match get_latest_version(&self.db, data_source_id).await? {
+            Some(latest) => calculate_next_version(&latest, changelog.bump_type),
+            None => "1.0".to_string(),
+        };
+
+        let slug = target.slug();
+        let mut files = Vec::with_capacity(loaded.files.len());
+        for file in &loaded.files {
+            let key = self
+                .s3
+                .build_key(target.archive.as_str(), &slug, &version, &file.filename);
+            let content_type = if file.filename.ends_with(".gz") {
+                "application/gzip"
+            } else {
+                "text/plain"
+            };
+            let upload = self
+                .s3
+                .upload(&key, file.data.clone(), Some(content_type.to_string()))
+                .await
+                .with_context(|| format!("Failed to upload {}", file.filename))?;
+            files.push(StructureFile {
+                format: file.kind.format().to_string(),
+                s3_key: upload.key,
+                sha256: upload.checksum,
+                size_bytes: upload.size,
+            });
+        }
+
+        let version_id = storage
+            .create_structure_version(
+                data_source_id,
+                &version,
+                &snapshot,
+                structure.revision_date.or(structure.release_date),
+                &files,
+            )
+            .await?;
+        save_changelog(&self.db, version_id, &changelog)
+            .await
+            .context("Failed to save changelog")?;
+
+        info!(
+            structure = %structure.structure_id,
+            version = %version,
+            revision = ?structure.revision,
+            bump_type = ?changelog.bump_type,
+            chains = structure.chains.len(),
+            linked_chains,
+            "Ingested structure"
+        );
+
+        Ok(Some(StructureResult {
+            structure_id: structure.structure_id.clone(),
+            version: Some(version),
+            chains: structure.chains.len(),
+            linked_chains,
+        }))
+    }
+}
+
+/// Fetch the files of a structure and parse its mmCIF metadata
+///
+/// Returns `None` when no mmCIF file is published for the structure. The PDB
+/// format file is optional. AlphaFold models are versioned by the AlphaFold
+/// DB model version rather than their revision history.
+pub async fn load_structure(
+    transport: &dyn Transport,
+    target: &StructureTarget,
+    model_version: &str,
+) -> Result<Option<LoadedStructure>> {
+    let mut files = Vec::new();
+    for kind in StructureFileKind::ALL {
+        let filename = target.file_name(kind, model_version);
+        let path = StructureConfig::file_path(target.archive, &filename);
+        let Some(data) = transport.download_if_exists(&path).await? else {
+            if kind == StructureFileKind::Mmcif {
+                return Ok(None);
+            }
+            continue;
+        };
+        files.push(StructureFileSource {
+            kind,
+            filename,
+            data: data.data,
+        });
+    }
+
+    let mmcif = &files[0];
+    let content = if mmcif.filename.ends_with(".gz") {
+        decompress_gzip(&mmcif.data)
+            .with_context(|| format!("Failed to decompress {}", mmcif.filename))?
+    } else {
+        mmcif.data.clone()
+    };
+    let content = String::from_utf8(content)
+        .with_context(|| format!("{} is not valid UTF-8", mmcif.filename))?;
+
+    let mut structure = parse_structure(&content, target.archive)
+        .with_context(|| format!("Failed to parse {}", mmcif.filename))?;
+    if structure.structure_id != target.structure_id {
+        anyhow::bail!(
+            "{} describes {} instead of {}",
+            mmcif.filename,
+            structure.structure_id,
+            target.structure_id
+        );
+    }
+    if target.archive == StructureArchive::Alphafold {
+        structure.revision = Some(model_version.to_string());
+    }
+
+    Ok(Some(LoadedStructure { structure, files }))
+}
+
+/// Weekly PDB release a date belongs to
+///
+/// New and updated entries are released every Wednesday at 00:00 UTC.
+pub fn pdb_weekly_release(date: NaiveDate) -> NaiveDate {
+    let days_since_wednesday = (date.weekday().num_days_from_monday() + 7 - 2) % 7;
+    date - chrono::Duration::days(days_since_wednesday as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::common::transport::TransportConfig;
+    use std::path::PathBuf;
+
+    fn fixture_mirror() -> Arc<dyn Transport> {
+        TransportConfig::Local {
+            root: PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/structure"),
+        }
+        .build()
+    }
+
+    #[tokio::test]
+    async fn test_load_pdb_entry() {
+        let loaded = load_structure(fixture_mirror().as_ref(), &StructureTarget::pdb("4ins"), "v4")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded.structure.revision.as_deref(), Some("1.4"));
+        assert_eq!(loaded.structure.chains.len(), 4);
+
+        let formats: Vec<_> = loaded.files.iter().map(|f| f.kind.format()).collect();
+        assert_eq!(formats, vec!["mmcif", "pdb"]);
+    }
+
+    #[tokio::test]
+    async fn test_load_alphafold_model() {
+        let mirror = fixture_mirror();
+        let loaded = load_structure(mirror.as_ref(), &StructureTarget::alphafold("P01308"), "v4")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded.structure.revision.as_deref(), Some("v4"));
+        assert_eq!(loaded.files[0].filename, "AF-P01308-F1-model_v4.cif");
+
+        // No model of another version
+        assert!(load_structure(mirror.as_ref(), &StructureTarget::alphafold("P01308"), "v3")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_pdb_weekly_release() {
+        let wednesday = NaiveDate::from_ymd_opt(2026, 10, 14).unwrap();
+        assert_eq!(pdb_weekly_release(wednesday), wednesday);
+        assert_eq!(pdb_weekly_release(NaiveDate::from_ymd_opt(2026, 10, 18).unwrap()), wednesday);
+        assert_eq!(pdb_weekly_release(NaiveDate::from_ymd_opt(2026, 10, 20).unwrap()), wednesday);
+    }
+}
@@ -0,0 +1,627 @@
+// Storage layer for PDB entries and AlphaFold DB models
+//
+// Each structure is a 'structure' data source keyed by its PDB ID or
+// AlphaFold DB model ID and linked to the taxonomy entry of its source
+// organism. Polymer chains are stored in structure_chains and linked to the
+// UniProt entries of their reference sequences; chains without one in the
+// mmCIF file are linked through the PDB cross-references of UniProt entries.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, QueryBuilder};
+use std::collections::HashMap;
+use tracing::{debug, info};
+use uuid::Uuid;
+
+use super::models::{Structure, StructureArchive};
+use super::parser::uniprot_pdb_chains;
+use crate::ingest::citations::{alphafold_policy, pdb_policy, setup_citation_policy};
+use crate::ingest::uniprot::taxonomy_helper::TaxonomyHelper;
+
+/// A file published with a structure version
+#[derive(Debug, Clone)]
+pub struct StructureFile {
+    /// Version file format ("mmcif", "pdb")
+    pub format: String,
+    pub s3_key: String,
+    pub sha256: String,
+    pub size_bytes: i64,
+}
+
+/// What a structure version was built from, kept in the version's
+/// `additional_metadata` so later versions can be compared against it
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StructureSnapshot {
+    /// PDB revision ("1.3") or AlphaFold DB model version ("v4")
+    pub revision: Option<String>,
+    pub revision_date: Option<chrono::NaiveDate>,
+    pub chain_count: i32,
+    /// Chemical component IDs of the ligands
+    pub ligands: Vec<String>,
+    /// Chains linked to an ingested UniProt entry
+    pub linked_chain_count: i32,
+}
+
+impl StructureSnapshot {
+    /// Major part of the revision
+    ///
+    /// The PDB increments the major revision when coordinates change; every
+    /// AlphaFold DB model version is a new prediction.
+    pub fn major_revision(&self) -> Option<&str> {
+        self.revision.as_deref()?.split('.').next()
+    }
+}
+
+/// Storage handler for structures
+pub struct StructureStorage {
+    db: PgPool,
+    organization_id: Uuid,
+    batch_size: usize,
+}
+
+impl StructureStorage {
+    /// Create a new storage handler
+    pub fn new(db: PgPool, organization_id: Uuid) -> Self {
+        Self {
+            db,
+            organization_id,
+            batch_size: 500,
+        }
+    }
+
+    /// Set batch size for chain inserts
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Set up the citation policy of the archive's organization (idempotent)
+    pub async fn setup_citations(&self, archive: StructureArchive) -> Result<()> {
+        let policy_config = match archive {
+            StructureArchive::Pdb => pdb_policy(self.organization_id, None),
+            StructureArchive::Alphafold => alphafold_policy(self.organization_id, None),
+        };
+        setup_citation_policy(&self.db, &policy_config).await?;
+        info!(archive = archive.as_str(), "Structure citation policy configured");
+        Ok(())
+    }
+
+    /// IDs that ingested UniProt entries cross-reference in an archive
+    ///
+    /// PDB IDs for the PDB, UniProt accessions for AlphaFold DB.
+    pub async fn cross_referenced_ids(
+        &self,
+        archive: StructureArchive,
+        limit: Option<usize>,
+    ) -> Result<Vec<String>> {
+        let ids = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT DISTINCT database_id
+            FROM protein_cross_references
+            WHERE database = $1
+            ORDER BY database_id
+            LIMIT $2
+            "#,
+        )
+        .bind(archive.uniprot_database())
+        .bind(limit.map(|limit| limit as i64))
+        .fetch_all(&self.db)
+        .await
+        .context("Failed to look up structure cross-references")?;
+
+        Ok(ids)
+    }
+
+    /// Get or create the data source of a structure and store its metadata
+    ///
+    /// The structure is linked to the NCBI Taxonomy entry of its source
+    /// organism, which is created as a stub when the taxonomy hasn't been
+    /// ingested yet.
+    pub async fn get_or_create_structure_source(&self, structure: &Structure) -> Result<Uuid> {
+        let taxonomy_data_source_id = match &structure.organism {
+            Some(organism) => {
+                let mut taxonomy_helper =
+                    TaxonomyHelper::new(self.db.clone(), self.organization_id);
+                Some(
+                    taxonomy_helper
+                        .get_or_create_taxonomy(
+                            organism.taxonomy_id,
+                            &organism.scientific_name,
+                            &[],
+                        )
+                        .await
+                        .context("Failed to get or create taxonomy via TaxonomyHelper")?,
+                )
+            },
+            None => None,
+        };
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+
+        let data_source_id = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            INSERT INTO registry_entries (organization_id, slug, name, description, entry_type)
+            VALUES ($1, $2, $3, $4, 'data_source')
+            ON CONFLICT (slug) DO UPDATE SET
+                name = EXCLUDED.name,
+                description = EXCLUDED.description,
+                updated_at = NOW()
+            RETURNING id
+            "#,
+        )
+        .bind(self.organization_id)
+        .bind(structure.target().slug())
+        .bind(structure.display_name())
+        .bind(structure.summary())
+        .fetch_one(&mut *tx)
+        .await
+        .context("Failed to create registry entry")?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO data_sources (id, source_type, external_id)
+            VALUES ($1, 'structure', $2)
+            ON CONFLICT (id) DO NOTHING
+            "#,
+        )
+        .bind(data_source_id)
+        .bind(&structure.structure_id)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to create data source")?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO structure_metadata (
+                data_source_id, structure_id, archive, title, method, resolution,
+                release_date, revision, revision_date, mean_plddt, taxonomy_id,
+                taxonomy_data_source_id, chain_count, ligands
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+            ON CONFLICT (data_source_id) DO UPDATE SET
+                title = EXCLUDED.title,
+                method = EXCLUDED.method,
+                resolution = EXCLUDED.resolution,
+                release_date = EXCLUDED.release_date,
+                revision = EXCLUDED.revision,
+                revision_date = EXCLUDED.revision_date,
+                mean_plddt = EXCLUDED.mean_plddt,
+                taxonomy_id = EXCLUDED.taxonomy_id,
+                taxonomy_data_source_id = EXCLUDED.taxonomy_data_source_id,
+                chain_count = EXCLUDED.chain_count,
+                ligands = EXCLUDED.ligands
+            "#,
+        )
+        .bind(data_source_id)
+        .bind(&structure.structure_id)
+        .bind(structure.archive.as_str())
+        .bind(&structure.title)
+        .bind(&structure.method)
+        .bind(structure.resolution)
+        .bind(structure.release_date)
+        .bind(&structure.revision)
+        .bind(structure.revision_date)
+        .bind(structure.mean_plddt)
+        .bind(structure.organism.as_ref().map(|o| o.taxonomy_id))
+        .bind(taxonomy_data_source_id)
+        .bind(structure.chains.len() as i32)
+        .bind(serde_json::to_value(&structure.ligands)?)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to upsert structure metadata")?;
+
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        Ok(data_source_id)
+    }
+
+    /// Replace the chains of a structure and link them to UniProt entries
+    ///
+    /// Returns the number of chains linked to an ingested UniProt entry.
+    pub async fn store_chains(&self, data_source_id: Uuid, structure: &Structure) -> Result<usize> {
+        let mut chains = structure.chains.clone();
+
+        if structure.archive == StructureArchive::Pdb
+            && chains.iter().any(|chain| chain.uniprot_accession.is_none())
+        {
+            let xrefs = sqlx::query_as::<_, (String, Option<serde_json::Value>)>(
+                r#"
+                SELECT pm.accession, pcr.metadata
+                FROM protein_cross_references pcr
+                JOIN protein_metadata pm ON pm.data_source_id = pcr.protein_id
+                WHERE pcr.database = 'PDB' AND pcr.database_id = $1
+                "#,
+            )
+            .bind(&structure.structure_id)
+            .fetch_all(&self.db)
+            .await
+            .context("Failed to look up UniProt PDB cross-references")?;
+
+            for (accession, metadata) in xrefs {
+                let metadata: Vec<String> = metadata
+                    .and_then(|value| serde_json::from_value(value).ok())
+                    .unwrap_or_default();
+                for chain_id in uniprot_pdb_chains(&metadata) {
+                    if let Some(chain) = chains.iter_mut().find(|chain| {
+                        chain.chain_id == chain_id && chain.uniprot_accession.is_none()
+                    }) {
+                        chain.uniprot_accession = Some(accession.clone());
+                    }
+                }
+            }
+        }
+
+        let mut accessions: Vec<&str> = chains
+            .iter()
+            .filter_map(|chain| chain.uniprot_accession.as_deref())
+            .collect();
+        accessions.sort_unstable();
+        accessions.dedup();
+        let proteins: HashMap<String, Uuid> = sqlx::query_as::<_, (String, Uuid)>(
+            "SELECT accession, data_source_id FROM protein_metadata WHERE accession = ANY($1)",
+        )
+        .bind(&accessions)
+        .fetch_all(&self.db)
+        .await
+        .context("Failed to look up UniProt entries")?
+        .into_iter()
+        .collect();
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+
+        sqlx::query("DELETE FROM structure_chains WHERE structure_data_source_id = $1")
+            .bind(data_source_id)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to clear previous chains")?;
+
+        for chunk in chains.chunks(self.batch_size) {
+            let mut query_builder = QueryBuilder::new(
+                "INSERT INTO structure_chains (structure_data_source_id, chain_id, entity_id, description, polymer_type, sequence_length, uniprot_accession, protein_data_source_id) ",
+            );
+            query_builder.push_values(chunk, |mut b, chain| {
+                let protein_data_source_id = chain
+                    .uniprot_accession
+                    .as_ref()
+                    .and_then(|accession| proteins.get(accession).copied());
+                b.push_bind(data_source_id)
+                    .push_bind(&chain.chain_id)
+                    .push_bind(&chain.entity_id)
+                    .push_bind(&chain.description)
+                    .push_bind(&chain.polymer_type)
+                    .push_bind(chain.sequence_length)
+                    .push_bind(&chain.uniprot_accession)
+                    .push_bind(protein_data_source_id);
+            });
+            query_builder
+                .build()
+                .execute(&mut *tx)
+                .await
+                .context("Failed to insert chains")?;
+        }
+
+        tx.commit().await.context("Failed to commit chains")?;
+
+        let linked = chains
+            .iter()
+            .filter(|chain| {
+                chain
+                    .uniprot_accession
+                    .as_ref()
+                    .is_some_and(|accession| proteins.contains_key(accession))
+            })
+            .count();
+        debug!(
+            structure = %structure.structure_id,
+            chains = chains.len(),
+            linked,
+            "Stored structure chains"
+        );
+
+        Ok(linked)
+    }
+
+    /// Create a structure version with its files
+    pub async fn create_structure_version(
+        &self,
+        data_source_id: Uuid,
+        version: &str,
+        snapshot: &StructureSnapshot,
+        release_date: Option<chrono::NaiveDate>,
+        files: &[StructureFile],
+    ) -> Result<Uuid> {
+        let parsed = crate::ingest::versioning::SemanticVersion::parse(version).unwrap_or_default();
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+
+        let version_id = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            INSERT INTO versions (
+                entry_id, version, external_version, release_date, additional_metadata,
+                version_major, version_minor, version_patch
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id
+            "#,
+        )
+        .bind(data_source_id)
+        .bind(version)
+        .bind(&snapshot.revision)
+        .bind(release_date)
+        .bind(serde_json::to_value(snapshot)?)
+        .bind(parsed.major)
+        .bind(parsed.minor)
+        .bind(parsed.patch)
+        .fetch_one(&mut *tx)
+        .await
+        .context("Failed to create version")?;
+
+        for file in files {
+            sqlx::query(
+                r#"
+                INSERT INTO version_files (version_id, format, s3_key, checksum, size_bytes, sha256)
+                VALUES ($1, $2, $3, $4, $5, $4)
+                "#,
+            )
+            .bind(version_id)
+            .bind(&file.format)
+            .bind(&file.s3_key)
+            .bind(&file.sha256)
+            .bind(file.size_bytes)
+            .execute(&mut *tx)
+            .await
+            .with_context(|| {
+                format!("Failed to create version_file for format: {}", file.format)
+            })?;
+        }
+
+        tx.commit().await.context("Failed to commit version")?;
+
+        Ok(version_id)
+    }
+}
+
+/// Snapshot of a structure as currently stored
+pub async fn current_structure_snapshot(
+    pool: &PgPool,
+    data_source_id: Uuid,
+) -> Result<StructureSnapshot> {
+    let (revision, revision_date, chain_count, ligands, linked_chain_count) = sqlx::query_as::<
+        _,
+        (Option<String>, Option<chrono::NaiveDate>, i32, serde_json::Value, i64),
+    >(
+        r#"
+        SELECT sm.revision, sm.revision_date, sm.chain_count, sm.ligands,
+               (SELECT COUNT(*) FROM structure_chains sc
+                WHERE sc.structure_data_source_id = sm.data_source_id
+                  AND sc.protein_data_source_id IS NOT NULL)
+        FROM structure_metadata sm
+        WHERE sm.data_source_id = $1
+        "#,
+    )
+    .bind(data_source_id)
+    .fetch_one(pool)
+    .await
+    .context("Failed to get structure metadata")?;
+
+    let ligands: Vec<super::models::Ligand> = serde_json::from_value(ligands).unwrap_or_default();
+
+    Ok(StructureSnapshot {
+        revision,
+        revision_date,
+        chain_count,
+        ligands: ligands.into_iter().map(|ligand| ligand.comp_id).collect(),
+        linked_chain_count: linked_chain_count as i32,
+    })
+}
+
+/// Snapshot a structure version was created from, if it recorded one
+pub async fn version_structure_snapshot(
+    pool: &PgPool,
+    version_id: Uuid,
+) -> Result<Option<StructureSnapshot>> {
+    let metadata: Option<serde_json::Value> =
+        sqlx::query_scalar("SELECT additional_metadata FROM versions WHERE id = $1")
+            .bind(version_id)
+            .fetch_one(pool)
+            .await
+            .context("Failed to get version metadata")?;
+
+    Ok(metadata.and_then(|value| serde_json::from_value(value).ok()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::structure::models::{SourceOrganism, StructureChain};
+
+    fn structure() -> Structure {
+        let chain = |chain_id: &str, entity_id: &str, accession: Option<&str>| StructureChain {
+            chain_id: chain_id.to_string(),
+            entity_id: entity_id.to_string(),
+            description: Some("INSULIN".to_string()),
+            polymer_type: Some("polypeptide(L)".to_string()),
+            sequence_length: Some(21),
+            uniprot_accession: accession.map(str::to_string),
+        };
+        Structure {
+            archive: StructureArchive::Pdb,
+            structure_id: "4INS".to_string(),
+            title: Some("THE STRUCTURE OF 2ZN PIG INSULIN CRYSTALS".to_string()),
+            method: Some("X-RAY DIFFRACTION".to_string()),
+            resolution: Some(1.5),
+            release_date: chrono::NaiveDate::from_ymd_opt(1989, 7, 12),
+            revision: Some("1.4".to_string()),
+            revision_date: chrono::NaiveDate::from_ymd_opt(2024, 2, 28),
+            mean_plddt: None,
+            organism: Some(SourceOrganism {
+                taxonomy_id: 9823,
+                scientific_name: "Sus scrofa".to_string(),
+            }),
+            chains: vec![
+                chain("A", "1", Some("P01315")),
+                chain("B", "2", None),
+                chain("C", "3", None),
+            ],
+            ligands: Vec::new(),
+        }
+    }
+
+    async fn insert_org(pool: &PgPool) -> sqlx::Result<Uuid> {
+        sqlx::query_scalar::<_, Uuid>(
+            "INSERT INTO organizations (slug, name, is_system) VALUES ('pdb', 'Protein Data Bank', true) RETURNING id",
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Insert a UniProt entry with optional PDB cross-reference metadata
+    async fn insert_uniprot(
+        pool: &PgPool,
+        org_id: Uuid,
+        accession: &str,
+        pdb: Option<(&str, serde_json::Value)>,
+    ) -> sqlx::Result<Uuid> {
+        let id = sqlx::query_scalar::<_, Uuid>(
+            "INSERT INTO registry_entries (organization_id, slug, name, entry_type) VALUES ($1, $2, $2, 'data_source') RETURNING id",
+        )
+        .bind(org_id)
+        .bind(accession.to_lowercase())
+        .fetch_one(pool)
+        .await?;
+        sqlx::query("INSERT INTO data_sources (id, source_type) VALUES ($1, 'protein')")
+            .bind(id)
+            .execute(pool)
+            .await?;
+        sqlx::query("INSERT INTO protein_metadata (data_source_id, accession) VALUES ($1, $2)")
+            .bind(id)
+            .bind(accession)
+            .execute(pool)
+            .await?;
+        if let Some((pdb_id, metadata)) = pdb {
+            sqlx::query(
+                "INSERT INTO protein_cross_references (protein_id, database, database_id, metadata) VALUES ($1, 'PDB', $2, $3)",
+            )
+            .bind(id)
+            .bind(pdb_id)
+            .bind(metadata)
+            .execute(pool)
+            .await?;
+        }
+        Ok(id)
+    }
+
+    #[sqlx::test]
+    async fn test_structure_source_links_organism(pool: PgPool) -> sqlx::Result<()> {
+        let org_id = insert_org(&pool).await?;
+        let storage = StructureStorage::new(pool.clone(), org_id);
+
+        let data_source_id = storage
+            .get_or_create_structure_source(&structure())
+            .await
+            .unwrap();
+        // Idempotent
+        assert_eq!(
+            storage
+                .get_or_create_structure_source(&structure())
+                .await
+                .unwrap(),
+            data_source_id
+        );
+
+        let (slug, source_type, taxonomy_id, resolution): (String, String, i32, f64) =
+            sqlx::query_as(
+                r#"
+                SELECT re.slug, ds.source_type, tm.taxonomy_id, sm.resolution::FLOAT8
+                FROM registry_entries re
+                JOIN data_sources ds ON ds.id = re.id
+                JOIN structure_metadata sm ON sm.data_source_id = re.id
+                JOIN taxonomy_metadata tm ON tm.data_source_id = sm.taxonomy_data_source_id
+                WHERE re.id = $1
+                "#,
+            )
+            .bind(data_source_id)
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(slug, "pdb-4ins");
+        assert_eq!(source_type, "structure");
+        assert_eq!(taxonomy_id, 9823);
+        assert_eq!(resolution, 1.5);
+
+        let snapshot = current_structure_snapshot(&pool, data_source_id)
+            .await
+            .unwrap();
+        assert_eq!(snapshot.revision.as_deref(), Some("1.4"));
+        assert_eq!(snapshot.major_revision(), Some("1"));
+        assert_eq!(snapshot.chain_count, 3);
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_store_chains_links_uniprot(pool: PgPool) -> sqlx::Result<()> {
+        let org_id = insert_org(&pool).await?;
+        let storage = StructureStorage::new(pool.clone(), org_id).with_batch_size(2);
+        let structure = structure();
+        let data_source_id = storage
+            .get_or_create_structure_source(&structure)
+            .await
+            .unwrap();
+
+        // Chain A by its mmCIF reference, chain B by UniProt's PDB
+        // cross-reference, chain C not at all
+        let p01315 = insert_uniprot(&pool, org_id, "P01315", None).await?;
+        let q00000 = insert_uniprot(
+            &pool,
+            org_id,
+            "Q00000",
+            Some(("4INS", serde_json::json!(["X-ray", "1.50 A", "B=1-30."]))),
+        )
+        .await?;
+
+        let linked = storage
+            .store_chains(data_source_id, &structure)
+            .await
+            .unwrap();
+        assert_eq!(linked, 2);
+        // Replaced, not duplicated
+        storage
+            .store_chains(data_source_id, &structure)
+            .await
+            .unwrap();
+
+        let chains: Vec<(String, Option<String>, Option<Uuid>)> = sqlx::query_as(
+            "SELECT chain_id, uniprot_accession, protein_data_source_id FROM structure_chains WHERE structure_data_source_id = $1 ORDER BY chain_id",
+        )
+        .bind(data_source_id)
+        .fetch_all(&pool)
+        .await?;
+        assert_eq!(
+            chains,
+            vec![
+                ("A".to_string(), Some("P01315".to_string()), Some(p01315)),
+                ("B".to_string(), Some("Q00000".to_string()), Some(q00000)),
+                ("C".to_string(), None, None),
+            ]
+        );
+
+        let ids = storage
+            .cross_referenced_ids(StructureArchive::Pdb, None)
+            .await
+            .unwrap();
+        assert_eq!(ids, vec!["4INS"]);
+
+        Ok(())
+    }
+}
@@ -2,6 +2,10 @@
 
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::sync::Arc;
+
+use crate::ingest::common::ftp::FtpConfig;
+use crate::ingest::common::transport::{FtpTransport, Transport, TransportConfig};
 
 /// Error type for UniProt configuration operations
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -54,6 +58,9 @@ pub struct UniProtFtpConfig {
     pub parse_limit: Option<usize>,
     /// Release type (Current or Previous)
     pub release_type: ReleaseType,
+    /// Transport to read upstream files through (None for FTP to `ftp_host`)
+    #[serde(default)]
+    pub transport: Option<TransportConfig>,
 }
 
 impl Default for UniProtFtpConfig {
@@ -68,6 +75,7 @@ impl Default for UniProtFtpConfig {
             read_timeout_secs: 1800, // 30 minutes for large files (multi-GB tar.gz archives)
             parse_limit: None,
             release_type: ReleaseType::Current,
+            transport: None,
         }
     }
 }
@@ -90,6 +98,12 @@ impl UniProtFtpConfig {
         self
     }
 
+    /// Read upstream files through another transport
+    pub fn with_transport(mut self, transport: TransportConfig) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
     /// Set connection timeout
     pub fn with_connection_timeout(mut self, timeout_secs: u64) -> Self {
         self.connection_timeout_secs = timeout_secs;
@@ -102,6 +116,19 @@ impl UniProtFtpConfig {
         self
     }
 
+    /// Transport for upstream files
+    pub fn transport(&self) -> Arc<dyn Transport> {
+        match &self.transport {
+            Some(transport) => transport.build(),
+            None => Arc::new(FtpTransport::new(FtpConfig {
+                host: self.ftp_host.clone(),
+                port: self.ftp_port,
+                username: self.ftp_username.clone(),
+                password: self.ftp_password.clone(),
+            })),
+        }
+    }
+
     /// Set release type
     pub fn with_release_type(mut self, release_type: ReleaseType) -> Self {
         self.release_type = release_type;
@@ -1,26 +1,28 @@
-//! UniProt FTP download functionality
+//! UniProt download functionality
 
 use anyhow::{Context, Result};
 use chrono::NaiveDate;
 use flate2::read::GzDecoder;
 use std::io::Read;
-use std::time::Duration;
-use suppaftp::FtpStream;
-use tracing::{debug, info, warn};
+use std::path::Path;
+use std::sync::Arc;
+use tracing::info;
 
 use super::config::UniProtFtpConfig;
 use super::models::ReleaseInfo;
-use crate::ingest::common::ftp::{MAX_RETRIES, RETRY_DELAY_SECS};
+use crate::ingest::common::transport::Transport;
 
-/// FTP client for downloading UniProt data
+/// Client for downloading UniProt data through the configured transport
 pub struct UniProtFtp {
     config: UniProtFtpConfig,
+    transport: Arc<dyn Transport>,
 }
 
 impl UniProtFtp {
-    /// Create a new FTP client
+    /// Create a new client
     pub fn new(config: UniProtFtpConfig) -> Self {
-        Self { config }
+        let transport = config.transport();
+        Self { config, transport }
     }
 
     /// Download release notes
@@ -126,7 +128,27 @@ impl UniProtFtp {
         Ok(decompressed)
     }
 
-    /// Check if a release exists on the FTP server
+    /// Download a DAT file and decompress it to `dest` without buffering it
+    ///
+    /// Takes the same arguments as [`Self::download_dat_file`]; used for
+    /// releases too large to hold in memory, such as TrEMBL.
+    ///
+    /// # Returns
+    /// Size of the decompressed DAT file
+    pub async fn download_dat_file_to(
+        &self,
+        version: Option<&str>,
+        dataset: Option<&str>,
+        dest: &Path,
+    ) -> Result<u64> {
+        let path = self
+            .config
+            .dat_file_path(version, dataset)
+            .context("Failed to build DAT file path")?;
+        self.download_gzip_file_to(&path, dest).await
+    }
+
+    /// Check if a release exists upstream
     ///
     /// # Arguments
     /// * `version` - Optional release version (e.g., "2024_01")
@@ -145,209 +167,29 @@ impl UniProtFtp {
         }
     }
 
-    /// Download a file from the FTP server using synchronous FTP with retry logic
+    /// Download a file into memory
     async fn download_file(&self, path: &str) -> Result<Vec<u8>> {
-        let config = self.config.clone();
-        let path = path.to_string();
-
-        // Retry loop
-        for attempt in 1..=MAX_RETRIES {
-            debug!("Download attempt {}/{} for: {}", attempt, MAX_RETRIES, path);
-
-            match tokio::task::spawn_blocking({
-                let config = config.clone();
-                let path = path.clone();
-                move || Self::download_file_sync(&config, &path)
-            })
-            .await
-            {
-                Ok(Ok(data)) => {
-                    info!("Successfully downloaded {} ({} bytes)", path, data.len());
-                    return Ok(data);
-                },
-                Ok(Err(e)) => {
-                    if attempt < MAX_RETRIES {
-                        warn!(
-                            "Download attempt {}/{} failed: {}. Retrying in {}s...",
-                            attempt, MAX_RETRIES, e, RETRY_DELAY_SECS
-                        );
-                        tokio::time::sleep(Duration::from_secs(RETRY_DELAY_SECS * attempt as u64))
-                            .await;
-                    } else {
-                        return Err(e).with_context(|| {
-                            format!("Failed to download {} after {} attempts", path, MAX_RETRIES)
-                        });
-                    }
-                },
-                Err(e) => {
-                    return Err(anyhow::anyhow!("FTP download task panicked: {}", e));
-                },
-            }
-        }
-
-        unreachable!("Retry loop should always return")
+        Ok(self.transport.download(path).await?.data)
     }
 
-    /// Synchronous FTP download implementation
-    ///
-    /// Note: suppaftp doesn't support timeout configuration directly.
-    /// The tokio task timeout wrapper in download_file() provides overall timeout protection.
-    fn download_file_sync(config: &UniProtFtpConfig, path: &str) -> Result<Vec<u8>> {
-        debug!("Connecting to FTP server: {}:{}", config.ftp_host, config.ftp_port);
-
-        // Connect to FTP server
-        let mut ftp_stream = FtpStream::connect(format!("{}:{}", config.ftp_host, config.ftp_port))
-            .context("Failed to connect to FTP server")?;
-
-        // Use Extended Passive Mode (EPSV) - better for NAT/Docker environments
-        // EPSV is defined in RFC 2428 and works better through firewalls/NAT than standard PASV
-        ftp_stream.set_mode(suppaftp::Mode::ExtendedPassive);
-
-        debug!("FTP connection established with Extended Passive Mode (EPSV)");
-        debug!("Logging in as: {}", config.ftp_username);
-
-        // Login
-        ftp_stream
-            .login(&config.ftp_username, &config.ftp_password)
-            .context("Failed to login to FTP server")?;
-
-        debug!("Setting binary transfer mode");
-
-        // Set binary mode
-        ftp_stream
-            .transfer_type(suppaftp::types::FileType::Binary)
-            .context("Failed to set binary mode")?;
-
-        debug!("Downloading file: {}", path);
-
-        // Download file
-        let mut reader = ftp_stream
-            .retr_as_buffer(path)
-            .with_context(|| format!("Failed to download file: {}", path))?;
-
-        let mut data = Vec::new();
-        let bytes_read = reader
-            .read_to_end(&mut data)
-            .context("Failed to read file data")?;
-
-        debug!("Downloaded {} bytes from {}", bytes_read, path);
-
-        // Quit gracefully
-        if let Err(e) = ftp_stream.quit() {
-            warn!("Failed to quit FTP session gracefully: {}", e);
-        }
-
-        Ok(data)
+    /// Download a gzipped file, decompressing it to `dest`
+    async fn download_gzip_file_to(&self, path: &str, dest: &Path) -> Result<u64> {
+        let written = self.transport.download_gzip_to(path, dest).await?;
+        info!("Successfully downloaded {} ({} bytes decompressed)", path, written);
+        Ok(written)
     }
 
-    /// List directories in a given FTP path
+    /// List directories in a given path
     ///
     /// # Arguments
-    /// * `path` - The FTP directory path to list
+    /// * `path` - The directory path to list
     ///
     /// # Returns
     /// Vector of directory names (not full paths, just the names)
     pub async fn list_directories(&self, path: &str) -> Result<Vec<String>> {
-        let config = self.config.clone();
-        let path = path.to_string();
-
-        // Retry loop for FTP listing
-        for attempt in 1..=MAX_RETRIES {
-            debug!("FTP LIST attempt {}/{} for: {}", attempt, MAX_RETRIES, path);
-
-            match tokio::task::spawn_blocking({
-                let config = config.clone();
-                let path = path.clone();
-                move || Self::list_directories_sync(&config, &path)
-            })
-            .await
-            {
-                Ok(Ok(dirs)) => {
-                    info!("Successfully listed {} directories in {}", dirs.len(), path);
-                    return Ok(dirs);
-                },
-                Ok(Err(e)) => {
-                    if attempt < MAX_RETRIES {
-                        warn!(
-                            "FTP LIST attempt {}/{} failed: {}. Retrying in {}s...",
-                            attempt, MAX_RETRIES, e, RETRY_DELAY_SECS
-                        );
-                        tokio::time::sleep(Duration::from_secs(RETRY_DELAY_SECS * attempt as u64))
-                            .await;
-                    } else {
-                        return Err(e).with_context(|| {
-                            format!("Failed to list {} after {} attempts", path, MAX_RETRIES)
-                        });
-                    }
-                },
-                Err(e) => {
-                    return Err(anyhow::anyhow!("FTP LIST task panicked: {}", e));
-                },
-            }
-        }
-
-        unreachable!("Retry loop should always return")
-    }
-
-    /// Synchronous FTP directory listing implementation
-    fn list_directories_sync(config: &UniProtFtpConfig, path: &str) -> Result<Vec<String>> {
-        debug!("Connecting to FTP server: {}:{}", config.ftp_host, config.ftp_port);
-
-        // Connect to FTP server
-        let mut ftp_stream = FtpStream::connect(format!("{}:{}", config.ftp_host, config.ftp_port))
-            .context("Failed to connect to FTP server")?;
-
-        // Use Extended Passive Mode (EPSV) - better for NAT/Docker environments
-        ftp_stream.set_mode(suppaftp::Mode::ExtendedPassive);
-
-        debug!("FTP connection established with Extended Passive Mode (EPSV)");
-        debug!("Logging in as: {}", config.ftp_username);
-
-        // Login
-        ftp_stream
-            .login(&config.ftp_username, &config.ftp_password)
-            .context("Failed to login to FTP server")?;
-
-        debug!("Listing directory: {}", path);
-
-        // List directory contents
-        let entries = ftp_stream
-            .list(Some(path))
-            .with_context(|| format!("Failed to list directory: {}", path))?;
-
-        debug!("Received {} entries from FTP LIST", entries.len());
-
-        // Parse directory names from LIST output
-        let mut directories = Vec::new();
-        for entry in entries {
-            debug!("Parsing FTP LIST entry: {}", entry);
-
-            // FTP LIST format varies, but typically:
-            // drwxr-xr-x   2 ftp      ftp          4096 Jan 15 12:00 release-2025_01
-            // We need to extract directory names (entries starting with 'd')
-            let parts: Vec<&str> = entry.split_whitespace().collect();
-
-            if parts.is_empty() {
-                continue;
-            }
-
-            // Check if this is a directory (first char is 'd')
-            if parts[0].starts_with('d') {
-                // Directory name is typically the last part
-                if let Some(name) = parts.last() {
-                    directories.push(name.to_string());
-                }
-            }
-        }
-
-        debug!("Extracted {} directories from listing", directories.len());
-
-        // Quit gracefully
-        if let Err(e) = ftp_stream.quit() {
-            warn!("Failed to quit FTP session gracefully: {}", e);
-        }
-
-        Ok(directories)
+        let dirs = self.transport.list_directories(path).await?;
+        info!("Successfully listed {} directories in {}", dirs.len(), path);
+        Ok(dirs)
     }
 
     /// Parse release date in format "15-Jan-2024"
@@ -33,6 +33,9 @@ pub struct UniProtEntry {
     pub release_date: NaiveDate,
 
     // === Extended Metadata (Phase 2) ===
+    /// Secondary accessions, from entries merged into this one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub secondary_accessions: Vec<String>,
     /// Alternative protein names (AltName, SubName)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub alternative_names: Vec<String>,
@@ -181,6 +184,33 @@ impl UniProtEntry {
         format!("{:x}", hasher.finalize())
     }
 
+    /// Calculate SHA-256 checksum of the annotations
+    ///
+    /// Covers every field except the sequence and the release dates, so it
+    /// only changes when the annotation content does.
+    pub fn annotation_checksum(&self) -> Result<String> {
+        let mut annotation =
+            serde_json::to_value(self).context("Failed to serialize UniProtEntry annotations")?;
+        if let Some(fields) = annotation.as_object_mut() {
+            for field in [
+                "sequence",
+                "sequence_length",
+                "mass_da",
+                "release_date",
+                "secondary_accessions",
+                "entry_created",
+                "sequence_updated",
+                "annotation_updated",
+            ] {
+                fields.remove(field);
+            }
+        }
+
+        let mut hasher = Sha256::new();
+        hasher.update(annotation.to_string().as_bytes());
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
     /// Validate the entry for consistency
     pub fn validate(&self) -> Result<()> {
         anyhow::ensure!(!self.accession.is_empty(), "Accession cannot be empty");
@@ -325,6 +355,7 @@ mod tests {
             sequence_length: 25,
             mass_da: 2897,
             release_date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            secondary_accessions: vec![],
             alternative_names: vec![],
             ec_numbers: vec![],
             features: vec![],
@@ -405,6 +436,25 @@ mod tests {
         assert_ne!(checksum, entry3.sequence_checksum());
     }
 
+    #[test]
+    fn test_annotation_checksum() {
+        let entry = sample_entry();
+        let checksum = entry.annotation_checksum().unwrap();
+        assert_eq!(checksum.len(), 64);
+
+        // Sequence, accession history and release dates are not annotations
+        let mut resequenced = sample_entry();
+        resequenced.sequence = "DIFFERENT".to_string();
+        resequenced.sequence_length = 9;
+        resequenced.secondary_accessions.push("Q99999".to_string());
+        resequenced.annotation_updated = NaiveDate::from_ymd_opt(2025, 2, 5);
+        assert_eq!(checksum, resequenced.annotation_checksum().unwrap());
+
+        let mut reannotated = sample_entry();
+        reannotated.keywords.push("Glycoprotein".to_string());
+        assert_ne!(checksum, reannotated.annotation_checksum().unwrap());
+    }
+
     #[test]
     fn test_validate_success() {
         let entry = sample_entry();
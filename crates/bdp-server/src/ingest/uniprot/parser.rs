@@ -44,11 +44,31 @@ use anyhow::{anyhow, Context, Result};
 use chrono::NaiveDate;
 use flate2::read::GzDecoder;
 use std::io::{BufRead, BufReader, Read};
+use std::ops::Range;
 use std::path::Path;
 use tar::Archive;
 
 use super::models::{Comment, CrossReference, ProteinFeature, Publication, UniProtEntry};
 
+/// Entries parsed from a range of a DAT file, with those that failed
+#[derive(Debug, Default)]
+pub struct ParsedRange {
+    /// Parsed entries with the byte range of their raw text
+    pub entries: Vec<(UniProtEntry, Range<usize>)>,
+    /// Entries that could not be parsed
+    pub failures: Vec<ParseFailure>,
+}
+
+/// An entry that could not be parsed
+#[derive(Debug)]
+pub struct ParseFailure {
+    /// Index of the entry in the file (0-based)
+    pub entry_index: usize,
+    /// Byte range of the raw entry text
+    pub span: Range<usize>,
+    pub error: anyhow::Error,
+}
+
 /// Parser for UniProt DAT files
 pub struct DatParser {
     /// Maximum number of entries to parse (None for unlimited)
@@ -289,6 +309,8 @@ impl DatParser {
     ///
     /// This method skips the decompression step and assumes data is already in plain DAT format.
     /// Use this when reading from the cache to avoid redundant decompression.
+    /// Entries that fail to parse are logged and skipped; use
+    /// [`Self::parse_range_with_failures`] to keep them.
     ///
     /// # Arguments
     /// * `dat_data` - Already decompressed DAT file data (plain text format)
@@ -300,11 +322,40 @@ impl DatParser {
         start_offset: usize,
         end_offset: usize,
     ) -> Result<Vec<UniProtEntry>> {
-        tracing::info!(
+        let parsed = self.parse_range_with_failures(dat_data, start_offset, end_offset)?;
+
+        for failure in parsed.failures.iter().take(3) {
+            tracing::warn!(
+                entry_index = failure.entry_index,
+                error = %format!("{:#}", failure.error),
+                "Entry skipped"
+            );
+        }
+
+        Ok(parsed.entries.into_iter().map(|(entry, _)| entry).collect())
+    }
+
+    /// Parse a range of entries from pre-decompressed DAT data, keeping failures
+    ///
+    /// Every entry in the range ends up either parsed or in `failures`, each
+    /// with the byte range of its raw text in `dat_data`. Entries with lines
+    /// that can't be parsed or without the required fields are failures.
+    ///
+    /// # Arguments
+    /// * `dat_data` - Already decompressed DAT file data (plain text format)
+    /// * `start_offset` - Start parsing from this entry index (0-based)
+    /// * `end_offset` - Stop parsing at this entry index (inclusive)
+    pub fn parse_range_with_failures(
+        &self,
+        dat_data: &[u8],
+        start_offset: usize,
+        end_offset: usize,
+    ) -> Result<ParsedRange> {
+        tracing::debug!(
             start_offset,
             end_offset,
             input_size = dat_data.len(),
-            "parse_range_predecompressed called (skipping extraction)"
+            "parse_range_with_failures called (skipping extraction)"
         );
 
         // Validate that this is actually DAT format
@@ -314,51 +365,39 @@ impl DatParser {
             );
         }
 
-        // Parse the requested range directly (no extraction needed)
-        let buf_reader = BufReader::new(dat_data);
-        let mut entries = Vec::new();
+        let mut parsed = ParsedRange::default();
         let mut current_entry = EntryBuilder::new();
+        let mut entry_error: Option<anyhow::Error> = None;
         let mut in_sequence = false;
         let mut entry_index = 0;
-        let mut lines_processed = 0;
-        let mut entries_skipped_no_build = 0;
+        let mut entry_start = 0;
+        let mut position = 0;
 
-        for line in buf_reader.lines() {
-            let line = line.context("Failed to read line")?;
+        for raw_line in dat_data.split_inclusive(|&b| b == b'\n') {
+            position += raw_line.len();
+            let in_range = entry_index >= start_offset;
+            let line = raw_line.strip_suffix(b"\n").unwrap_or(raw_line);
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
 
             // End of entry
-            if line.starts_with("//") {
-                // Check if the entry we just finished is in our range
-                if entry_index >= start_offset && entry_index <= end_offset {
-                    if entry_index == start_offset {
-                        tracing::info!(
+            if line.starts_with(b"//") {
+                if in_range {
+                    let span = entry_start..position;
+                    let builder = std::mem::take(&mut current_entry);
+                    let built = match entry_error.take() {
+                        Some(e) => Err(e),
+                        None => builder
+                            .build()
+                            .and_then(|entry| entry.context("Entry is missing required fields")),
+                    };
+
+                    match built {
+                        Ok(entry) => parsed.entries.push((entry, span)),
+                        Err(error) => parsed.failures.push(ParseFailure {
                             entry_index,
-                            lines_processed,
-                            "Processing first entry in range"
-                        );
-                    }
-
-                    #[allow(clippy::excessive_nesting)]
-                    match current_entry.build()? {
-                        Some(entry) => {
-                            entries.push(entry);
-                            let is_first_entry = entries.len() == 1;
-                            if is_first_entry {
-                                tracing::info!(entry_index, "Successfully built first entry!");
-                            }
-                        },
-                        None => {
-                            entries_skipped_no_build += 1;
-                            let should_log =
-                                entries_skipped_no_build <= 3 || entry_index == start_offset;
-                            if should_log {
-                                tracing::warn!(
-                                    entry_index,
-                                    lines_processed_for_entry = lines_processed,
-                                    "Entry skipped - build() returned None (missing required fields)"
-                                );
-                            }
-                        },
+                            span,
+                            error,
+                        }),
                     }
                 }
 
@@ -370,28 +409,67 @@ impl DatParser {
                 }
 
                 current_entry = EntryBuilder::new();
+                entry_error = None;
                 in_sequence = false;
-                lines_processed = 0;
+                entry_start = position;
                 continue;
             }
 
-            // Only process lines for entries we care about
-            if entry_index >= start_offset && entry_index <= end_offset {
-                self.process_line(&line, &mut current_entry, &mut in_sequence)?;
-                lines_processed += 1;
+            // Only process lines for entries we care about, up to the first error
+            if in_range && entry_error.is_none() {
+                let result = std::str::from_utf8(line)
+                    .context("Line is not valid UTF-8")
+                    .and_then(|line| {
+                        self.process_line(line, &mut current_entry, &mut in_sequence)
+                            .with_context(|| format!("Failed to parse line '{}'", line))
+                    });
+                entry_error = result.err();
             }
         }
 
-        if entries.is_empty() && entries_skipped_no_build > 0 {
+        if parsed.entries.is_empty() && !parsed.failures.is_empty() {
             tracing::warn!(
                 start_offset,
                 end_offset,
-                entries_skipped_no_build,
-                "No entries parsed - all entries skipped due to missing required fields"
+                failed = parsed.failures.len(),
+                "No entries parsed - all entries in range failed"
             );
         }
 
-        Ok(entries)
+        Ok(parsed)
+    }
+
+    /// Whether a line ends a DAT entry
+    pub fn is_entry_end(line: &[u8]) -> bool {
+        line.starts_with(b"//")
+    }
+
+    /// Primary accession of a raw DAT entry, read from its first AC line
+    ///
+    /// Works on entries the parser rejects, so their dead letters can still
+    /// be looked up by accession.
+    pub fn primary_accession(raw_entry: &[u8]) -> Option<String> {
+        raw_entry
+            .split(|b| *b == b'\n')
+            .find_map(|line| line.strip_prefix(b"AC   "))
+            .and_then(|rest| rest.split(|b| *b == b';').next())
+            .map(|accession| String::from_utf8_lossy(accession).trim().to_string())
+            .filter(|accession| !accession.is_empty())
+    }
+
+    /// Parse a single raw DAT entry, as kept by the dead-letter store or read
+    /// from a range of a DAT file
+    pub fn parse_entry(&self, raw_entry: &[u8]) -> Result<UniProtEntry> {
+        let mut parsed = self.parse_range_with_failures(raw_entry, 0, 0)?;
+
+        if let Some(failure) = parsed.failures.pop() {
+            return Err(failure.error);
+        }
+        parsed
+            .entries
+            .pop()
+            .map(|(entry, _)| entry)
+            .context("No complete entry found (missing '//' terminator)")
     }
 
     /// Count total entries from pre-decompressed DAT data (for efficiency)
@@ -749,6 +827,7 @@ struct EntryBuilder {
     sequence: String,
 
     // Extended metadata
+    secondary_accessions: Vec<String>,
     alternative_names: Vec<String>,
     ec_numbers: Vec<String>,
     features: Vec<ProteinFeature>,
@@ -788,11 +867,20 @@ impl EntryBuilder {
     }
 
     /// Parse AC line: AC   P12345; P67890;
+    ///
+    /// The first accession is the primary one; the rest, which may continue
+    /// on further AC lines, are secondary accessions.
     fn parse_ac_line(&mut self, line: &str) -> Result<()> {
-        if self.accession.is_none() {
-            let ac_part = line.trim_start_matches("AC   ");
-            if let Some(first_ac) = ac_part.split(';').next() {
-                self.accession = Some(first_ac.trim().to_string());
+        let ac_part = line.trim_start_matches("AC   ");
+        for ac in ac_part
+            .split(';')
+            .map(str::trim)
+            .filter(|ac| !ac.is_empty())
+        {
+            if self.accession.is_none() {
+                self.accession = Some(ac.to_string());
+            } else {
+                self.secondary_accessions.push(ac.to_string());
             }
         }
         Ok(())
@@ -1349,6 +1437,7 @@ impl EntryBuilder {
             sequence_length,
             mass_da,
             release_date,
+            secondary_accessions: self.secondary_accessions,
             alternative_names: self.alternative_names,
             ec_numbers: self.ec_numbers,
             features: self.features,
@@ -1428,7 +1517,9 @@ mod tests {
     fn test_entry_builder_accession() {
         let mut builder = EntryBuilder::new();
         builder.parse_ac_line("AC   P12345; P67890;").unwrap();
+        builder.parse_ac_line("AC   Q11111;").unwrap();
         assert_eq!(builder.accession, Some("P12345".to_string()));
+        assert_eq!(builder.secondary_accessions, vec!["P67890", "Q11111"]);
     }
 
     #[test]
@@ -1551,4 +1642,104 @@ mod tests {
             .unwrap();
         assert_eq!(builder.taxonomy_lineage, vec!["Archaea", "Euryarchaeota", "Methanomicrobia"]);
     }
+
+    const SAMPLE_ENTRY: &str = "ID   104K_THEPA              Reviewed;         104 AA.
+AC   P15711;
+DT   01-APR-1990, integrated into UniProtKB/Swiss-Prot.
+DE   RecName: Full=104 kDa microneme-rhoptry antigen;
+OS   Theileria parva.
+OX   NCBI_TaxID=5875;
+SQ   SEQUENCE   104 AA;  11937 MW;  B4840739BF7D4121 CRC64;
+     MVKKLVVVDG GTGDGTTSAG IAADRNAIKR ALMDAKAEMP TDPVGGNIDG DIDALVNEAA
+//
+";
+
+    #[test]
+    fn test_parse_range_with_failures() {
+        // The second entry has no organism, so it can't be built
+        let incomplete =
+            "ID   108_SOLLC               Reviewed;         102 AA.\nAC   P04284;\n//\n";
+        let data = format!("{}{}{}", SAMPLE_ENTRY, incomplete, SAMPLE_ENTRY);
+
+        let parsed = DatParser::new()
+            .parse_range_with_failures(data.as_bytes(), 0, 2)
+            .unwrap();
+
+        assert_eq!(parsed.entries.len(), 2);
+        assert_eq!(parsed.entries[0].0.accession, "P15711");
+        assert_eq!(parsed.entries[0].1, 0..SAMPLE_ENTRY.len());
+
+        assert_eq!(parsed.failures.len(), 1);
+        let failure = &parsed.failures[0];
+        assert_eq!(failure.entry_index, 1);
+        assert_eq!(&data[failure.span.clone()], incomplete);
+        assert!(failure
+            .error
+            .to_string()
+            .contains("missing required fields"));
+    }
+
+    #[test]
+    fn test_parse_range_with_failures_respects_range() {
+        let data = SAMPLE_ENTRY.repeat(3);
+
+        let parsed = DatParser::new()
+            .parse_range_with_failures(data.as_bytes(), 1, 1)
+            .unwrap();
+
+        assert_eq!(parsed.entries.len(), 1);
+        assert_eq!(parsed.entries[0].1, SAMPLE_ENTRY.len()..2 * SAMPLE_ENTRY.len());
+        assert!(parsed.failures.is_empty());
+    }
+
+    #[test]
+    fn test_parse_entry() {
+        let parser = DatParser::new();
+
+        let entry = parser.parse_entry(SAMPLE_ENTRY.as_bytes()).unwrap();
+        assert_eq!(entry.accession, "P15711");
+        assert_eq!(entry.taxonomy_id, 5875);
+
+        let truncated = &SAMPLE_ENTRY[..SAMPLE_ENTRY.len() - 3];
+        assert!(parser.parse_entry(truncated.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_primary_accession_of_unparseable_entry() {
+        let truncated = &SAMPLE_ENTRY[..SAMPLE_ENTRY.len() - 3];
+        assert_eq!(DatParser::primary_accession(truncated.as_bytes()).as_deref(), Some("P15711"));
+        assert_eq!(DatParser::primary_accession(b"ID   BROKEN\n//\n"), None);
+    }
+
+    #[test]
+    fn test_parse_entries_by_range() {
+        let path = std::path::Path::new(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/uniprot_sample_10.dat"
+        ));
+        let source = crate::ingest::framework::SeekableFile::open(path).unwrap();
+        let index = source.build_index(DatParser::is_entry_end, 4).unwrap();
+        assert_eq!(index.total_records, 10);
+        assert_eq!(index.ranges.len(), 3);
+
+        let parser = DatParser::new();
+        let mut accessions = Vec::new();
+        for range in &index.ranges {
+            for raw in source
+                .records(range.start, Some(range.end), DatParser::is_entry_end)
+                .unwrap()
+            {
+                let raw = raw.unwrap();
+                accessions.push(parser.parse_entry(&raw.data).unwrap().accession);
+            }
+        }
+
+        let expected: Vec<_> = parser
+            .parse_file(path)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.accession)
+            .collect();
+        assert_eq!(accessions, expected);
+    }
 }
@@ -36,13 +36,15 @@ use super::version_discovery::{DiscoveredVersion, VersionDiscovery};
 use crate::audit::{create_audit_entry, AuditAction, CreateAuditEntry, ResourceType};
 use crate::ingest::config::{HistoricalConfig, IngestionMode, LatestConfig, UniProtConfig};
 use crate::ingest::framework::{
-    BatchConfig, CreateJobParams, IngestionCoordinator, IngestionWorker,
+    BatchConfig, CreateJobParams, DeadLetter, DeadLetterStage, DeadLetterStore,
+    IngestionCoordinator, IngestionWorker, RawRecord, SeekableFile,
 };
 use crate::ingest::jobs::IngestStats;
+use crate::ingest::version_mapping::{VersionMapper, VersionMapping};
 use crate::ingest::versioning::{
     calculate_next_version, cascade_version_bump, create_version, get_latest_version,
-    get_latest_version_id, save_changelog, BumpType, UniProtBumpDetector, VersionBumpDetector,
-    VersionChangelog,
+    get_latest_version_id, get_version_details, save_changelog, BumpType, UniProtBumpDetector,
+    VersionBumpDetector, VersionChangelog,
 };
 use crate::storage::Storage;
 
@@ -58,15 +60,17 @@ use crate::storage::Storage;
 ///
 /// The pipeline follows a multi-phase approach:
 /// 1. Download phase: Fetch .dat.gz files from UniProt FTP (with local caching)
-/// 2. Parse phase: Count entries and create work units for parallel processing
-/// 3. Storage phase: Parallel workers parse and store proteins (streaming)
+/// 2. Parse phase: Index entry offsets and create a work unit per byte range
+/// 3. Storage phase: Parallel workers read their range, parse and store proteins (streaming)
 /// 4. Bundle phase: Create organism-specific and Swiss-Prot bundles
 /// 5. Versioning phase: Detect changes and create semantic version changelog
 ///
 /// # Caching
 ///
-/// Downloaded and decompressed DAT files are cached locally to avoid repeated
-/// FTP downloads. Cache cleanup occurs automatically for files older than 7 days.
+/// DAT files are decompressed to a local cache while they download, so they
+/// are never held in memory and repeated FTP downloads are avoided. Workers
+/// read their entries from the cached file. Cache cleanup occurs automatically
+/// for files older than 7 days.
 ///
 /// # Idempotency
 ///
@@ -134,12 +138,10 @@ impl UniProtPipeline {
         cache_path.exists() && cache_path.is_file()
     }
 
-    /// Write decompressed DAT data to cache atomically
-    async fn write_to_cache(&self, version: &str, dat_data: &[u8]) -> Result<()> {
-        use tokio::io::AsyncWriteExt;
-
-        let cache_path = self.get_cache_path(version);
-        let lock_path = self.get_lock_path(version);
+    /// Download a version's DAT file and decompress it into the cache atomically
+    async fn download_to_cache(&self, version: &DiscoveredVersion) -> Result<std::path::PathBuf> {
+        let cache_path = self.get_cache_path(&version.external_version);
+        let lock_path = self.get_lock_path(&version.external_version);
 
         // Create cache directory
         if let Some(parent) = cache_path.parent() {
@@ -153,19 +155,30 @@ impl UniProtPipeline {
             .await
             .context("Failed to create lock file")?;
 
-        // Write to temporary file
+        // Stream the download into a temporary file
         let temp_path = cache_path.with_extension("tmp");
-        let mut file = tokio::fs::File::create(&temp_path)
-            .await
-            .context("Failed to create temp cache file")?;
-
-        file.write_all(dat_data)
+        let ftp = UniProtFtp::new(self.config.clone());
+        let result = ftp
+            .download_dat_file_to(
+                if version.is_current {
+                    None
+                } else {
+                    Some(&version.external_version)
+                },
+                Some("sprot"), // Swiss-Prot filename is "sprot" not "swissprot"
+                &temp_path,
+            )
             .await
-            .context("Failed to write to temp cache file")?;
+            .context("Failed to download DAT file from FTP");
 
-        file.flush()
-            .await
-            .context("Failed to flush temp cache file")?;
+        let size_bytes = match result {
+            Ok(size_bytes) => size_bytes,
+            Err(e) => {
+                let _ = tokio::fs::remove_file(&temp_path).await;
+                let _ = tokio::fs::remove_file(&lock_path).await;
+                return Err(e);
+            },
+        };
 
         // Atomic rename
         tokio::fs::rename(&temp_path, &cache_path)
@@ -176,31 +189,13 @@ impl UniProtPipeline {
         let _ = tokio::fs::remove_file(&lock_path).await;
 
         tracing::info!(
-            version = version,
+            version = %version.external_version,
             cache_path = ?cache_path,
-            size_bytes = dat_data.len(),
+            size_bytes = size_bytes,
             "Successfully wrote decompressed DAT to cache"
         );
 
-        Ok(())
-    }
-
-    /// Read decompressed DAT data from cache
-    async fn read_from_cache(&self, version: &str) -> Result<Vec<u8>> {
-        let cache_path = self.get_cache_path(version);
-
-        let data = tokio::fs::read(&cache_path)
-            .await
-            .with_context(|| format!("Failed to read cache file: {:?}", cache_path))?;
-
-        tracing::info!(
-            version = version,
-            cache_path = ?cache_path,
-            size_bytes = data.len(),
-            "Successfully read decompressed DAT from cache (CACHE HIT)"
-        );
-
-        Ok(data)
+        Ok(cache_path)
     }
 
     /// Clean up cache files older than the specified number of days
@@ -841,6 +836,135 @@ impl UniProtPipeline {
         }
     }
 
+    /// Re-run the pending work units of an existing ingestion job
+    ///
+    /// Used after failed work units were reset to pending. Only those units
+    /// are processed; bundles are rebuilt to include the newly stored
+    /// proteins, and the version created by the original run is kept.
+    pub async fn retry_work_units(&self, job_id: Uuid, version: &DiscoveredVersion) -> Result<()> {
+        let coordinator = IngestionCoordinator::new(self.pool.clone(), self.batch_config.clone());
+
+        let source = self.fetch_dat_file(job_id, version).await?;
+        let total_records: i64 = sqlx::query_scalar(
+            "SELECT COALESCE(SUM(record_count), 0)::BIGINT FROM ingestion_work_units \
+             WHERE job_id = $1 AND status = 'pending'",
+        )
+        .bind(job_id)
+        .fetch_one(&*self.pool)
+        .await
+        .context("Failed to count records of pending work units")?;
+
+        let result = async {
+            self.storage_phase(&coordinator, job_id, "", total_records as usize, source, version)
+                .await?;
+            coordinator.ensure_not_cancelled(job_id).await?;
+            self.bundle_phase(&coordinator, job_id, version).await?;
+            coordinator.complete_job(job_id).await
+        }
+        .await;
+
+        if let Err(e) = &result {
+            tracing::error!(job_id = %job_id, error = %e, "Work unit retry failed");
+            coordinator
+                .fail_job(job_id, &e.to_string())
+                .await
+                .context("Failed to mark job as failed")?;
+        }
+
+        result
+    }
+
+    /// Re-run the pending dead letters of an existing ingestion job
+    ///
+    /// Each dead letter's raw entry is parsed and stored again, so records
+    /// rejected by a since-fixed parser or storage bug can be recovered
+    /// without re-ingesting the release. Records that fail again stay pending
+    /// with the new error. Bundles are rebuilt if any record was recovered.
+    ///
+    /// Returns the number of records recovered and the number still failing.
+    pub async fn reprocess_dead_letters(
+        &self,
+        job_id: Uuid,
+        version: &DiscoveredVersion,
+    ) -> Result<(usize, usize)> {
+        let coordinator = IngestionCoordinator::new(self.pool.clone(), self.batch_config.clone());
+        let dead_letters = DeadLetterStore::new(self.pool.clone());
+        let parser = DatParser::new();
+        let storage = UniProtStorage::with_s3(
+            (*self.pool).clone(),
+            self.storage.clone(),
+            self.organization_id,
+            "1.0".to_string(),
+            version.external_version.clone(),
+        );
+
+        let pending = dead_letters.pending(job_id).await?;
+        tracing::info!(job_id = %job_id, pending = pending.len(), "Reprocessing dead letters");
+
+        let mut recovered = 0;
+        let mut still_failing = 0;
+
+        for letter in pending {
+            let parsed = parser
+                .parse_entry(letter.raw_record.as_bytes())
+                .map_err(|e| (DeadLetterStage::Parse, e));
+            let stored = match parsed {
+                Ok(entry) => storage
+                    .store_entries_with_failures(std::slice::from_ref(&entry))
+                    .await?
+                    .failures
+                    .pop()
+                    .map(|failure| (DeadLetterStage::Store, failure.error)),
+                Err(failure) => Some(failure),
+            };
+
+            match stored {
+                None => {
+                    dead_letters.mark_reprocessed(letter.id).await?;
+                    recovered += 1;
+                },
+                Some((stage, error)) => {
+                    tracing::warn!(
+                        dead_letter_id = %letter.id,
+                        stage = stage.as_str(),
+                        error = %format!("{:#}", error),
+                        "Dead letter failed again"
+                    );
+                    let failure = DeadLetter::new(stage, letter.raw_record, &error);
+                    dead_letters.record_attempt(letter.id, &failure).await?;
+                    still_failing += 1;
+                },
+            }
+        }
+
+        sqlx::query!(
+            r#"
+            UPDATE ingestion_jobs
+            SET records_stored = COALESCE(records_stored, 0) + $1,
+                records_failed = GREATEST(COALESCE(records_failed, 0) - $1, 0)
+            WHERE id = $2
+            "#,
+            recovered as i64,
+            job_id
+        )
+        .execute(&*self.pool)
+        .await
+        .context("Failed to update job record counts")?;
+
+        if recovered > 0 {
+            self.bundle_phase(&coordinator, job_id, version).await?;
+        }
+
+        tracing::info!(
+            job_id = %job_id,
+            recovered = recovered,
+            still_failing = still_failing,
+            "Dead letter reprocessing completed"
+        );
+
+        Ok((recovered, still_failing))
+    }
+
     /// Execute the full ingestion pipeline for a version
     async fn execute_pipeline(
         &self,
@@ -856,11 +980,18 @@ impl UniProtPipeline {
         // Phase 0: Determine version bump type based on previous version (if exists)
         let previous_version_id = get_latest_version_id(&self.pool, data_source_entry_id).await?;
         let previous_version_string = get_latest_version(&self.pool, data_source_entry_id).await?;
+        let previous_release = match previous_version_id {
+            Some(id) => get_version_details(&self.pool, id)
+                .await?
+                .and_then(|details| details.external_version),
+            None => None,
+        };
 
         tracing::info!(
             job_id = %job_id,
             previous_version = ?previous_version_string,
             previous_version_id = ?previous_version_id,
+            previous_release = ?previous_release,
             "Checking for previous version"
         );
 
@@ -889,16 +1020,34 @@ impl UniProtPipeline {
             .context("Failed to setup citation policy")?;
 
         // Phase 1: Download from FTP and upload to S3
-        let (s3_key, dat_data) = self.download_phase(coordinator, job_id, version).await?;
+        let (s3_key, source) = self.download_phase(coordinator, job_id, version).await?;
 
-        // Phase 2: Count entries and create work units for parallel processing
+        // Phase 2: Index entry offsets and create work units for parallel processing
         let total_records = self
-            .parse_phase(coordinator, job_id, &s3_key, &dat_data)
+            .parse_phase(coordinator, job_id, &s3_key, &source)
             .await?;
 
         // Phase 3: Process work units in parallel (spawn multiple workers, streaming parse+store)
-        self.storage_phase(coordinator, job_id, &s3_key, total_records, dat_data, version)
+        // Only added and modified entries are written; unchanged ones are carried forward
+        self.storage_phase(coordinator, job_id, &s3_key, total_records, source, version)
             .await?;
+        coordinator.ensure_not_cancelled(job_id).await?;
+
+        // Phase 3b: Record proteins of the previous release missing from this one
+        if let Some(previous_release) = previous_release
+            .as_deref()
+            .filter(|release| *release != version.external_version)
+        {
+            let removed = storage_setup
+                .record_removed_entries(previous_release, job_id)
+                .await?;
+            tracing::info!(
+                job_id = %job_id,
+                previous_release = %previous_release,
+                removed = removed,
+                "Recorded removed proteins"
+            );
+        }
 
         // Phase 4: Create bundles after all proteins stored
         self.bundle_phase(coordinator, job_id, version).await?;
@@ -915,6 +1064,22 @@ impl UniProtPipeline {
             "Version bump detected"
         );
 
+        // Phase 5b: Record the release the new version was published from
+        let mapper = VersionMapper::new((*self.pool).clone());
+        let organization_slug = mapper.organization_slug(self.organization_id).await?;
+        mapper
+            .record(
+                &VersionMapping::data_source(
+                    organization_slug,
+                    "swissprot",
+                    &version.external_version,
+                    &new_version,
+                )
+                .with_release_date(Some(version.release_date)),
+            )
+            .await
+            .context("Failed to record version mapping")?;
+
         // Phase 6: Cascade version bump to dependents
         if let Some(_prev_id) = previous_version_id {
             // Only cascade if there was a previous version
@@ -996,8 +1161,8 @@ impl UniProtPipeline {
             "Starting versioning phase"
         );
 
-        // Detect changes using UniProt-specific detector
-        let detector = UniProtBumpDetector::new();
+        // Detect changes from the entry changes recorded for this release
+        let detector = UniProtBumpDetector::for_release(external_version);
         let changelog = detector
             .detect_changes(&self.pool, data_source_id, previous_version_id)
             .await
@@ -1055,86 +1220,21 @@ impl UniProtPipeline {
         coordinator: &IngestionCoordinator,
         job_id: Uuid,
         version: &DiscoveredVersion,
-    ) -> Result<(String, Vec<u8>)> {
+    ) -> Result<(String, SeekableFile)> {
         tracing::info!(job_id = %job_id, "Starting download phase");
         coordinator.start_download(job_id).await?;
 
-        // Run cache cleanup (delete files older than configured days)
-        let cache_max_age_days = std::env::var("UNIPROT_CACHE_MAX_AGE_DAYS")
-            .ok()
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(DEFAULT_CACHE_MAX_AGE_DAYS);
-        if let Err(e) = self.cleanup_cache(cache_max_age_days).await {
-            tracing::warn!(error = %e, "Cache cleanup failed, continuing");
-        }
-
-        // Check if we have a cached decompressed DAT file
-        let dat_data = if self.is_cached(&version.external_version) {
-            // CACHE HIT - Read from disk cache
-            tracing::info!(
-                job_id = %job_id,
-                version = %version.external_version,
-                "Cache hit - reading decompressed DAT from cache"
-            );
-            self.read_from_cache(&version.external_version).await?
-        } else {
-            // CACHE MISS - Download from FTP and decompress
-            tracing::info!(
-                job_id = %job_id,
-                version = %version.external_version,
-                "Cache miss - downloading from FTP"
-            );
-
-            let ftp = UniProtFtp::new(self.config.clone());
-            let compressed_data = ftp
-                .download_dat_file(
-                    if version.is_current {
-                        None
-                    } else {
-                        Some(&version.external_version)
-                    },
-                    Some("sprot"), // Swiss-Prot filename is "sprot" not "swissprot"
-                )
-                .await
-                .context("Failed to download DAT file from FTP")?;
-
-            tracing::info!(
-                job_id = %job_id,
-                compressed_size = compressed_data.len(),
-                "Downloaded compressed DAT file from FTP"
-            );
-
-            // Decompress the data
-            let parser = DatParser::new();
-            let decompressed_data = parser
-                .extract_dat_data(&compressed_data)
-                .context("Failed to decompress DAT file")?;
-
-            tracing::info!(
-                job_id = %job_id,
-                decompressed_size = decompressed_data.len(),
-                "Decompressed DAT file"
-            );
-
-            // Write to cache for future use
-            if let Err(e) = self
-                .write_to_cache(&version.external_version, &decompressed_data)
-                .await
-            {
-                tracing::warn!(
-                    job_id = %job_id,
-                    error = %e,
-                    "Failed to write to cache, continuing"
-                );
-            }
-
-            decompressed_data
-        };
+        let source = self.fetch_dat_file(job_id, version).await?;
+        let size_bytes = tokio::fs::metadata(source.path())
+            .await
+            .with_context(|| format!("Failed to read metadata of {}", source.path().display()))?
+            .len();
 
         tracing::info!(
             job_id = %job_id,
-            size_bytes = dat_data.len(),
-            "DAT data ready (decompressed)"
+            path = ?source.path(),
+            size_bytes = size_bytes,
+            "DAT file ready (decompressed)"
         );
 
         // Note: We no longer upload the compressed file to S3 since we're caching the decompressed version
@@ -1143,7 +1243,7 @@ impl UniProtPipeline {
             format!("ingest/uniprot/{}/{}_swissprot.dat.gz", job_id, version.external_version);
 
         let (s3_uploaded, file_size, _checksum): (bool, i64, Option<String>) =
-            (false, dat_data.len() as i64, None);
+            (false, size_bytes as i64, None);
 
         // Register the raw file in database (only if S3 upload succeeded)
         if s3_uploaded {
@@ -1164,38 +1264,81 @@ impl UniProtPipeline {
         coordinator.complete_download(job_id).await?;
         tracing::info!(job_id = %job_id, s3_key = %s3_key, "Download phase completed");
 
-        Ok((s3_key, dat_data))
+        Ok((s3_key, source))
+    }
+
+    /// Locate the decompressed DAT file of a version, downloading it on a cache miss
+    async fn fetch_dat_file(
+        &self,
+        job_id: Uuid,
+        version: &DiscoveredVersion,
+    ) -> Result<SeekableFile> {
+        // Run cache cleanup (delete files older than configured days)
+        let cache_max_age_days = std::env::var("UNIPROT_CACHE_MAX_AGE_DAYS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(DEFAULT_CACHE_MAX_AGE_DAYS);
+        if let Err(e) = self.cleanup_cache(cache_max_age_days).await {
+            tracing::warn!(error = %e, "Cache cleanup failed, continuing");
+        }
+
+        // Check if we have a cached decompressed DAT file
+        let cache_path = if self.is_cached(&version.external_version) {
+            // CACHE HIT - Read from disk cache
+            tracing::info!(
+                job_id = %job_id,
+                version = %version.external_version,
+                "Cache hit - reading decompressed DAT from cache"
+            );
+            self.get_cache_path(&version.external_version)
+        } else {
+            // CACHE MISS - Download from FTP, decompressing into the cache
+            tracing::info!(
+                job_id = %job_id,
+                version = %version.external_version,
+                "Cache miss - downloading from FTP"
+            );
+            self.download_to_cache(version).await?
+        };
+
+        SeekableFile::open(&cache_path)
     }
 
-    /// Phase 2: Count records and create work units for parallel processing
+    /// Phase 2: Index entry offsets and create a work unit per range of entries
     async fn parse_phase(
         &self,
         coordinator: &IngestionCoordinator,
         job_id: Uuid,
         s3_key: &str,
-        dat_data: &[u8],
+        source: &SeekableFile,
     ) -> Result<usize> {
         tracing::info!(
             job_id = %job_id,
             s3_key = %s3_key,
-            size_bytes = dat_data.len(),
-            "Starting parse phase - counting records"
+            path = ?source.path(),
+            "Starting parse phase - indexing entry offsets"
         );
 
-        // Count total records efficiently (just count "//" markers)
-        // Use count_entries_predecompressed since dat_data is already decompressed from cache
-        let parser = DatParser::new();
-        let total_records = parser.count_entries_predecompressed(dat_data)?;
+        // One pass over the file, noting where every parse_batch_size entries start
+        let index = {
+            let source = source.clone();
+            let entries_per_range = self.batch_config.parse_batch_size;
+            tokio::task::spawn_blocking(move || {
+                source.build_index(DatParser::is_entry_end, entries_per_range)
+            })
+            .await
+            .context("Indexing task panicked")??
+        };
 
         tracing::info!(
             job_id = %job_id,
-            total_records = total_records,
-            "Counted protein entries"
+            total_records = index.total_records,
+            "Indexed protein entries"
         );
 
         // Create work units for parallel processing
         let num_work_units = coordinator
-            .create_work_units(job_id, "parse_store", total_records)
+            .create_range_work_units(job_id, "parse_store", &index)
             .await
             .context("Failed to create work units")?;
 
@@ -1206,20 +1349,20 @@ impl UniProtPipeline {
             "Created work units for parallel processing"
         );
 
-        Ok(total_records)
+        Ok(index.total_records as usize)
     }
 
     /// Phase 3: Parallel batch processing with multiple workers
     ///
     /// Spawns worker tasks that compete for work units using SKIP LOCKED.
-    /// Each worker parses its range on-demand and stores proteins (streaming).
+    /// Each worker reads its range of the DAT file, parses and stores proteins (streaming).
     async fn storage_phase(
         &self,
         _coordinator: &IngestionCoordinator,
         job_id: Uuid,
-        _s3_key: &str,
+        s3_key: &str,
         total_records: usize,
-        dat_data: Vec<u8>,
+        source: SeekableFile,
         version: &DiscoveredVersion,
     ) -> Result<()> {
         tracing::info!(
@@ -1233,7 +1376,7 @@ impl UniProtPipeline {
             r#"
             UPDATE ingestion_jobs
             SET status = 'storing'
-            WHERE id = $1
+            WHERE id = $1 AND status <> 'cancelled'
             "#,
             job_id
         )
@@ -1241,12 +1384,14 @@ impl UniProtPipeline {
         .await
         .context("Failed to update job status to storing")?;
 
-        // Share raw bytes across workers (they parse their range on-demand)
-        let dat_data = Arc::new(dat_data);
+        // Workers open the file themselves and read only their range
+        let source = Arc::new(source);
+        // Retries have no S3 key at hand; dead letters then only carry offsets
+        let source_file = Some(s3_key.to_string()).filter(|key| !key.is_empty());
 
         tracing::info!(
             job_id = %job_id,
-            size_bytes = dat_data.len(),
+            path = ?source.path(),
             "Prepared data for streaming parallel parse+store"
         );
 
@@ -1270,10 +1415,11 @@ impl UniProtPipeline {
         for worker_num in 0..num_workers {
             let pool = self.pool.clone();
             let batch_config = self.batch_config.clone();
-            let dat_data_clone = dat_data.clone();
+            let source = source.clone();
             let org_id = self.organization_id;
             let storage = self.storage.clone();
             let external_version = version.external_version.clone();
+            let source_file = source_file.clone();
 
             let handle = tokio::spawn(async move {
                 Self::worker_task(
@@ -1281,7 +1427,8 @@ impl UniProtPipeline {
                     job_id,
                     pool,
                     batch_config,
-                    dat_data_clone,
+                    source,
+                    source_file,
                     org_id,
                     storage,
                     external_version,
@@ -1311,11 +1458,9 @@ impl UniProtPipeline {
                 },
                 Ok(Err(e)) => {
                     tracing::error!(job_id = %job_id, worker = idx, error = %e, "Worker failed");
-                    total_failed += 1;
                 },
                 Err(e) => {
                     tracing::error!(job_id = %job_id, worker = idx, error = %e, "Worker panicked");
-                    total_failed += 1;
                 },
             }
         }
@@ -1327,13 +1472,13 @@ impl UniProtPipeline {
             "All workers completed"
         );
 
-        // Update final counts
+        // Add this run's counts to the job totals (retries run on top of earlier runs)
         sqlx::query!(
             r#"
             UPDATE ingestion_jobs
-            SET records_processed = $1,
-                records_stored = $2,
-                records_failed = $3
+            SET records_processed = COALESCE(records_processed, 0) + $1,
+                records_stored = COALESCE(records_stored, 0) + $2,
+                records_failed = COALESCE(records_failed, 0) + $3
             WHERE id = $4
             "#,
             total_processed as i64,
@@ -1406,6 +1551,7 @@ impl UniProtPipeline {
                 mass_da: 0,
                 release_date: chrono::NaiveDate::from_ymd_opt(2024, 1, 1)
                     .unwrap_or_else(|| chrono::NaiveDate::MIN),
+                secondary_accessions: Vec::new(),
                 alternative_names: Vec::new(),
                 ec_numbers: Vec::new(),
                 features: Vec::new(),
@@ -1444,13 +1590,17 @@ impl UniProtPipeline {
     ///
     /// Uses SKIP LOCKED to atomically claim work units without blocking.
     /// Processes batches idempotently - can be run by multiple workers in parallel.
-    /// Each worker parses its range on-demand (streaming parse+store).
+    /// Each worker reads and parses its range on-demand (streaming parse+store).
+    ///
+    /// Returns the number of records stored and the number of records that
+    /// failed, counting every record of a work unit that failed for good.
     async fn worker_task(
         worker_num: usize,
         job_id: Uuid,
         pool: Arc<PgPool>,
         batch_config: BatchConfig,
-        dat_data: Arc<Vec<u8>>,
+        source: Arc<SeekableFile>,
+        source_file: Option<String>,
         org_id: Uuid,
         storage: Storage,
         external_version: String,
@@ -1468,8 +1618,9 @@ impl UniProtPipeline {
         let mut total_processed = 0;
         let mut total_failed = 0;
 
-        // Create parser for this worker
+        // Create parser and dead-letter store for this worker
         let parser = DatParser::new();
+        let dead_letters = DeadLetterStore::new(pool.clone());
 
         // Loop until no more work units available
         loop {
@@ -1502,9 +1653,12 @@ impl UniProtPipeline {
             // Process the batch (parse + store streaming)
             let result = Self::process_work_unit(
                 &worker,
+                job_id,
                 &work_unit,
-                &dat_data,
+                &source,
+                source_file.as_deref(),
                 &parser,
+                &dead_letters,
                 &pool,
                 org_id,
                 &storage,
@@ -1516,18 +1670,19 @@ impl UniProtPipeline {
             heartbeat_handle.abort();
 
             match result {
-                Ok(count) => {
+                Ok((count, dead_lettered)) => {
                     total_processed += count;
+                    total_failed += dead_lettered;
                     tracing::info!(
                         job_id = %job_id,
                         worker_id = %worker_id,
                         work_unit_id = %work_unit.id,
                         records_processed = count,
+                        records_dead_lettered = dead_lettered,
                         "Work unit completed successfully"
                     );
                 },
                 Err(e) => {
-                    total_failed += 1;
                     tracing::error!(
                         job_id = %job_id,
                         worker_id = %worker_id,
@@ -1536,8 +1691,10 @@ impl UniProtPipeline {
                         "Work unit failed"
                     );
 
-                    // Mark work unit as failed
-                    worker.fail_work_unit(work_unit.id, &e.to_string()).await?;
+                    // A unit that will be retried hasn't lost its records yet
+                    if worker.fail_work_unit(work_unit.id, &e.to_string()).await? {
+                        total_failed += work_unit.record_count.unwrap_or(0) as usize;
+                    }
                 },
             }
         }
@@ -1553,63 +1710,116 @@ impl UniProtPipeline {
         Ok((total_processed, total_failed))
     }
 
-    /// Process a single work unit - parse range on-demand and insert proteins (streaming)
+    /// Process a single work unit - read and parse its range, insert proteins (streaming)
+    ///
+    /// Entries that fail parsing or storage are written to the dead-letter
+    /// store with their raw text. Returns the number of entries stored and the
+    /// number dead-lettered.
     async fn process_work_unit(
         worker: &IngestionWorker,
+        job_id: Uuid,
         work_unit: &super::super::framework::types::ClaimedWorkUnit,
-        dat_data: &[u8],
+        source: &Arc<SeekableFile>,
+        source_file: Option<&str>,
         parser: &DatParser,
+        dead_letters: &DeadLetterStore,
         pool: &PgPool,
         org_id: Uuid,
         storage_backend: &Storage,
         external_version: &str,
-    ) -> Result<usize> {
-        let start = work_unit.start_offset as usize;
-        let end = work_unit.end_offset as usize;
+    ) -> Result<(usize, usize)> {
+        let start = work_unit.start_offset as u64;
+        let end = work_unit.end_offset as u64;
 
         tracing::debug!(
             work_unit_id = %work_unit.id,
             start_offset = start,
             end_offset = end,
-            "Parsing range on-demand"
+            "Reading range on-demand"
         );
 
-        // Parse only this worker's range (streaming parse)
-        // Use parse_range_predecompressed since dat_data is already decompressed from cache
-        let entries = parser.parse_range_predecompressed(dat_data, start, end)?;
+        // Read only this worker's range of the file
+        let raw_records = {
+            let source = source.clone();
+            tokio::task::spawn_blocking(move || {
+                source
+                    .records(start, Some(end), DatParser::is_entry_end)?
+                    .collect::<Result<Vec<RawRecord>>>()
+            })
+            .await
+            .context("Read task panicked")??
+        };
 
-        if entries.is_empty() {
+        let raw_record = |raw: &RawRecord| String::from_utf8_lossy(&raw.data).into_owned();
+
+        let mut failed: Vec<DeadLetter> = Vec::new();
+        let mut entries: Vec<UniProtEntry> = Vec::with_capacity(raw_records.len());
+        let mut parsed_records: Vec<&RawRecord> = Vec::with_capacity(raw_records.len());
+        for raw in &raw_records {
+            match parser.parse_entry(&raw.data) {
+                Ok(entry) => {
+                    entries.push(entry);
+                    parsed_records.push(raw);
+                },
+                Err(error) => {
+                    let mut letter =
+                        DeadLetter::new(DeadLetterStage::Parse, raw_record(raw), &error)
+                            .with_source(source_file, raw.offset as i64);
+                    letter.record_identifier = DatParser::primary_accession(&raw.data);
+                    failed.push(letter);
+                },
+            }
+        }
+
+        let inserted = if entries.is_empty() {
             tracing::warn!(
                 work_unit_id = %work_unit.id,
                 start = start,
                 end = end,
                 "No entries parsed in range"
             );
-            worker.complete_work_unit(work_unit.id).await?;
-            return Ok(0);
-        }
+            0
+        } else {
+            tracing::debug!(
+                work_unit_id = %work_unit.id,
+                parsed_count = entries.len(),
+                "Parsed entries, starting storage"
+            );
 
-        tracing::debug!(
-            work_unit_id = %work_unit.id,
-            parsed_count = entries.len(),
-            "Parsed entries, starting storage"
-        );
+            // Calculate internal version from external version
+            // For now, use "1.0" as default - TODO: implement proper version mapping
+            let internal_version = "1.0".to_string();
 
-        // Calculate internal version from external version
-        // For now, use "1.0" as default - TODO: implement proper version mapping
-        let internal_version = "1.0".to_string();
+            // Create storage handler with S3
+            let storage = UniProtStorage::with_s3(
+                pool.clone(),
+                storage_backend.clone(),
+                org_id,
+                internal_version,
+                external_version.to_string(),
+            );
 
-        // Create storage handler with S3
-        let storage = UniProtStorage::with_s3(
-            pool.clone(),
-            storage_backend.clone(),
-            org_id,
-            internal_version,
-            external_version.to_string(),
-        );
+            // Store entries using new schema (registry_entries → data_sources → protein_metadata)
+            let outcome = storage.store_entries_with_failures(&entries).await?;
+            tracing::debug!(
+                work_unit_id = %work_unit.id,
+                unchanged = outcome.unchanged,
+                "Carried unchanged entries forward"
+            );
+
+            failed.extend(outcome.failures.iter().map(|failure| {
+                let raw = parsed_records[failure.index];
+                DeadLetter::new(DeadLetterStage::Store, raw_record(raw), &failure.error)
+                    .with_identifier(failure.accession.clone())
+                    .with_source(source_file, raw.offset as i64)
+            }));
 
-        // Store entries using new schema (registry_entries → data_sources → protein_metadata)
-        let inserted = storage.store_entries(&entries).await?;
+            outcome.stored
+        };
+
+        dead_letters
+            .record(job_id, Some(work_unit.id), &failed)
+            .await?;
 
         // Mark work unit as completed
         worker.complete_work_unit(work_unit.id).await?;
@@ -1618,10 +1828,11 @@ impl UniProtPipeline {
             work_unit_id = %work_unit.id,
             inserted = inserted,
             parsed_count = entries.len(),
+            dead_lettered = failed.len(),
             "Batch parsed and inserted successfully (streaming)"
         );
 
-        Ok(inserted)
+        Ok((inserted, failed.len()))
     }
 
     /// Check if a specific version has been ingested
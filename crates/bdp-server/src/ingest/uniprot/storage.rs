@@ -1,8 +1,13 @@
 //! Storage layer for UniProt parsed data
 //!
 //! Creates individual data sources for each protein with proper schema structure.
+//!
+//! Ingestion is incremental: each entry is diffed against the stored one by
+//! sequence and annotation checksum. Only added and modified entries get a new
+//! version and files; unchanged entries are carried forward by reference.
 
 use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use sqlx::PgPool;
 use tracing::{debug, error, info, warn};
@@ -11,6 +16,10 @@ use uuid::Uuid;
 use super::models::UniProtEntry;
 use super::taxonomy_helper::TaxonomyHelper;
 use crate::ingest::citations::{setup_citation_policy, uniprot_policy};
+use crate::ingest::framework::{
+    record_release_change, record_successions, ReleaseChange, ReleaseChangeType, Succession,
+};
+use crate::ingest::versioning::{calculate_next_version, BumpType};
 use crate::storage::Storage;
 use std::collections::HashMap;
 
@@ -40,6 +49,190 @@ pub const DEPENDENCY_BATCH_SIZE: usize = 1000;
 /// Maximum slug length for taxonomy entries.
 pub const MAX_SLUG_LENGTH: usize = 100;
 
+/// Result of storing a batch of entries
+#[derive(Debug, Default)]
+pub struct StoreOutcome {
+    /// Number of entries stored, including unchanged entries carried forward
+    pub stored: usize,
+    /// Entries identical to the stored ones, for which nothing was written
+    pub unchanged: usize,
+    /// Entries that could not be stored
+    pub failures: Vec<StoreFailure>,
+}
+
+/// An entry that could not be stored
+#[derive(Debug)]
+pub struct StoreFailure {
+    /// Index of the entry in the stored batch
+    pub index: usize,
+    pub accession: String,
+    pub error: anyhow::Error,
+}
+
+/// Checksums of a stored protein, used to diff it against a new release
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct StoredEntry {
+    pub accession: String,
+    pub sequence_checksum: Option<String>,
+    pub annotation_checksum: Option<String>,
+    /// Latest internal version of the protein
+    pub latest_version: Option<String>,
+}
+
+/// Checksums of a parsed entry
+#[derive(Debug, Clone)]
+pub struct EntryChecksums {
+    pub sequence: String,
+    pub annotation: String,
+}
+
+impl EntryChecksums {
+    pub fn of(entry: &UniProtEntry) -> Result<Self> {
+        Ok(Self {
+            sequence: entry.sequence_checksum(),
+            annotation: entry.annotation_checksum()?,
+        })
+    }
+}
+
+/// What a protein version was built from, kept in the version's
+/// `additional_metadata` so releases can be diffed field by field
+///
+/// Features and cross-references are flattened to sorted strings so that a
+/// diff reports them as added or removed items.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProteinSnapshot {
+    pub entry_name: String,
+    pub protein_name: String,
+    pub gene_name: Option<String>,
+    pub sequence_length: i32,
+    pub sequence_checksum: String,
+    pub alternative_names: Vec<String>,
+    pub ec_numbers: Vec<String>,
+    pub keywords: Vec<String>,
+    /// `TYPE start..end description`
+    pub features: Vec<String>,
+    /// GO identifiers from the GO cross-references
+    pub go_terms: Vec<String>,
+    /// `DATABASE:id` for every other cross-reference
+    pub cross_references: Vec<String>,
+}
+
+impl ProteinSnapshot {
+    pub fn of(entry: &UniProtEntry, checksums: &EntryChecksums) -> Self {
+        let position = |pos: Option<i32>| pos.map(|p| p.to_string()).unwrap_or_else(|| "?".into());
+        let sorted = |mut items: Vec<String>| {
+            items.sort();
+            items.dedup();
+            items
+        };
+
+        Self {
+            entry_name: entry.entry_name.clone(),
+            protein_name: entry.protein_name.clone(),
+            gene_name: entry.gene_name.clone(),
+            sequence_length: entry.sequence_length,
+            sequence_checksum: checksums.sequence.clone(),
+            alternative_names: sorted(entry.alternative_names.clone()),
+            ec_numbers: sorted(entry.ec_numbers.clone()),
+            keywords: sorted(entry.keywords.clone()),
+            features: sorted(
+                entry
+                    .features
+                    .iter()
+                    .map(|f| {
+                        format!(
+                            "{} {}..{} {}",
+                            f.feature_type,
+                            position(f.start_pos),
+                            position(f.end_pos),
+                            f.description
+                        )
+                        .trim_end()
+                        .to_string()
+                    })
+                    .collect(),
+            ),
+            go_terms: sorted(
+                entry
+                    .cross_references
+                    .iter()
+                    .filter(|x| x.database == "GO")
+                    .map(|x| x.database_id.clone())
+                    .collect(),
+            ),
+            cross_references: sorted(
+                entry
+                    .cross_references
+                    .iter()
+                    .filter(|x| x.database != "GO")
+                    .map(|x| format!("{}:{}", x.database, x.database_id))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// A new version to write for an added or modified entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedVersion {
+    pub change_type: ReleaseChangeType,
+    pub previous_version: Option<String>,
+    pub version: String,
+}
+
+/// How a parsed entry compares with the stored one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntryDiff {
+    /// Identical to the stored entry; its version and files are reused
+    Unchanged,
+    /// Added or modified; a new version is written
+    Changed(PlannedVersion),
+}
+
+/// Diff a parsed entry against the stored one
+///
+/// Sequence changes bump the major version and annotation-only changes the
+/// minor version of the protein. Added proteins start at `initial_version`.
+/// Entries stored before annotation checksums existed are treated as
+/// unchanged when their sequence matches; their checksum is filled in when
+/// they are carried forward.
+pub fn diff_entry(
+    checksums: &EntryChecksums,
+    stored: Option<&StoredEntry>,
+    initial_version: &str,
+) -> EntryDiff {
+    let Some(stored) = stored else {
+        return EntryDiff::Changed(PlannedVersion {
+            change_type: ReleaseChangeType::Added,
+            previous_version: None,
+            version: initial_version.to_string(),
+        });
+    };
+
+    let (change_type, bump) = if stored.sequence_checksum.as_deref() != Some(&checksums.sequence) {
+        (ReleaseChangeType::SequenceModified, BumpType::Major)
+    } else if stored
+        .annotation_checksum
+        .as_deref()
+        .is_some_and(|annotation| annotation != checksums.annotation)
+    {
+        (ReleaseChangeType::AnnotationModified, BumpType::Minor)
+    } else {
+        return EntryDiff::Unchanged;
+    };
+
+    EntryDiff::Changed(PlannedVersion {
+        change_type,
+        previous_version: stored.latest_version.clone(),
+        version: stored
+            .latest_version
+            .as_deref()
+            .map(|latest| calculate_next_version(latest, bump))
+            .unwrap_or_else(|| initial_version.to_string()),
+    })
+}
+
 /// Convert organism taxonomy to human-readable slug
 ///
 /// Examples:
@@ -183,9 +376,11 @@ impl UniProtStorage {
 
     /// Store a batch of parsed entries
     ///
-    /// Processes entries in two phases:
-    /// 1. Upload FASTA files to S3 (if configured) in parallel batches
-    /// 2. Store metadata in the database using micro-transactions
+    /// Processes entries in four phases:
+    /// 1. Diff entries against the stored ones
+    /// 2. Upload files of added and modified entries to S3 (if configured) in parallel batches
+    /// 3. Store added and modified entries in the database using micro-transactions
+    /// 4. Carry unchanged entries forward to this release
     ///
     /// # Arguments
     ///
@@ -199,30 +394,66 @@ impl UniProtStorage {
     ///
     /// Returns an error if S3 uploads or database transactions fail.
     pub async fn store_entries(&self, entries: &[UniProtEntry]) -> Result<usize> {
+        Ok(self.store_entries_with_failures(entries).await?.stored)
+    }
+
+    /// Store a batch of parsed entries, returning the entries that failed
+    ///
+    /// Like [`Self::store_entries`], but every entry that could not be stored
+    /// is returned with its error instead of only being logged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database transaction can't be started or committed.
+    pub async fn store_entries_with_failures(
+        &self,
+        entries: &[UniProtEntry],
+    ) -> Result<StoreOutcome> {
         info!("Storing {} UniProt entries", entries.len());
 
-        // STEP 1: Upload ALL entries to S3 FIRST (before any database transaction!)
+        // STEP 1: Diff against the stored entries; only added and modified
+        // entries are written, unchanged ones keep their version and files
+        let stored_entries = self.fetch_stored_entries(entries).await?;
+        let mut changed = Vec::new();
+        let mut unchanged = Vec::new();
+        for (index, entry) in entries.iter().enumerate() {
+            let checksums = EntryChecksums::of(entry)?;
+            match diff_entry(
+                &checksums,
+                stored_entries.get(&entry.accession),
+                &self.internal_version,
+            ) {
+                EntryDiff::Unchanged => unchanged.push((entry.accession.clone(), checksums)),
+                EntryDiff::Changed(planned) => changed.push((index, checksums, planned)),
+            }
+        }
+
+        debug!(
+            changed = changed.len(),
+            unchanged = unchanged.len(),
+            "Diffed entries against stored release"
+        );
+
+        // STEP 2: Upload changed entries to S3 FIRST (before any database transaction!)
         // Use parallel uploads with batching for 10-20x speedup
         if let Some(ref s3) = self.s3 {
-            let internal_ver = self.internal_version.clone();
             let org_id = self.organization_id;
 
             // Process in batches of 50 concurrent uploads
-            let chunks: Vec<_> = entries.chunks(S3_UPLOAD_BATCH_SIZE).collect();
-            for chunk in chunks {
+            for chunk in changed.chunks(S3_UPLOAD_BATCH_SIZE) {
                 let mut upload_futures = Vec::new();
 
-                for entry in chunk {
+                for (index, _, planned) in chunk {
                     let s3_clone = s3.clone();
-                    let int_ver_clone = internal_ver.clone();
-                    let entry_clone = entry.clone();
+                    let version = planned.version.clone();
+                    let entry_clone = entries[*index].clone();
 
                     let upload_future = async move {
                         let result = Self::upload_entry_to_s3_static(
                             &s3_clone,
                             &entry_clone,
                             org_id,
-                            &int_ver_clone,
+                            &version,
                             "",
                         )
                         .await;
@@ -244,15 +475,14 @@ impl UniProtStorage {
             }
         }
 
-        // STEP 2: Now do database work - process in smaller sub-batches
+        // STEP 3: Now do database work - process in smaller sub-batches
         // Use mini-transactions instead of savepoints for better performance
         let mut stored_count = 0;
-        let mut error_count = 0;
-        let mut errors = Vec::new();
+        let mut failures = Vec::new();
 
         // Process entries in micro-batches (10 at a time) with separate transactions
         // This balances between transaction overhead and failure isolation
-        for chunk in entries.chunks(DB_MICRO_BATCH_SIZE) {
+        for chunk in changed.chunks(DB_MICRO_BATCH_SIZE) {
             let mut tx = self
                 .db
                 .begin()
@@ -260,10 +490,15 @@ impl UniProtStorage {
                 .context("Failed to begin transaction")?;
 
             let mut chunk_success = true;
-            for entry in chunk {
-                match self.store_entry_tx(&mut tx, entry).await {
+            let mut chunk_stored = 0;
+            for (index, checksums, planned) in chunk {
+                let entry = &entries[*index];
+                match self
+                    .store_entry_tx(&mut tx, entry, checksums, planned)
+                    .await
+                {
                     Ok(_) => {
-                        stored_count += 1;
+                        chunk_stored += 1;
                     },
                     Err(e) => {
                         // On error, rollback this transaction and retry entries individually
@@ -283,17 +518,22 @@ impl UniProtStorage {
                 tx.commit()
                     .await
                     .context("Failed to commit batch transaction")?;
+                stored_count += chunk_stored;
             } else {
                 // Rollback and retry failed entries one-by-one
                 drop(tx); // Explicit rollback by dropping
 
-                for entry in chunk {
+                for (index, checksums, planned) in chunk {
+                    let entry = &entries[*index];
                     let mut retry_tx = self
                         .db
                         .begin()
                         .await
                         .context("Failed to begin retry transaction")?;
-                    match self.store_entry_tx(&mut retry_tx, entry).await {
+                    match self
+                        .store_entry_tx(&mut retry_tx, entry, checksums, planned)
+                        .await
+                    {
                         Ok(_) => {
                             retry_tx
                                 .commit()
@@ -302,7 +542,6 @@ impl UniProtStorage {
                             stored_count += 1;
                         },
                         Err(e) => {
-                            error_count += 1;
                             error!(
                                 accession = %entry.accession,
                                 error = %e,
@@ -310,51 +549,228 @@ impl UniProtStorage {
                                 "Failed to store entry after retry"
                             );
 
-                            // Collect first 5 errors for debugging
-                            const MAX_ERRORS_TO_COLLECT: usize = 5;
-                            #[allow(clippy::excessive_nesting)]
-                            if errors.len() < MAX_ERRORS_TO_COLLECT {
-                                errors.push((entry.accession.clone(), format!("{:#}", e)));
-                            }
+                            failures.push(StoreFailure {
+                                index: *index,
+                                accession: entry.accession.clone(),
+                                error: e,
+                            });
                         },
                     }
                 }
             }
         }
 
+        // STEP 4: Carry unchanged entries forward to this release
+        self.carry_forward_tx(&unchanged).await?;
+        stored_count += unchanged.len();
+
+        // STEP 5: Point the secondary accessions of added and modified entries
+        // at the entries they were merged into; an unchanged entry's were
+        // recorded when it last changed
+        self.record_merged_accessions(changed.iter().map(|(index, ..)| &entries[*index]))
+            .await?;
+
         // Log error summary
-        if error_count > 0 {
+        if !failures.is_empty() {
             error!(
                 stored = stored_count,
-                failed = error_count,
+                failed = failures.len(),
                 total = entries.len(),
                 "Storage completed with errors"
             );
 
-            for (accession, err) in &errors {
+            for failure in failures.iter().take(5) {
                 error!(
-                    accession = %accession,
-                    error = %err,
+                    accession = %failure.accession,
+                    error = %format!("{:#}", failure.error),
                     "Sample error"
                 );
             }
 
-            if error_count > 5 {
-                error!(additional_errors = error_count - 5, "Additional errors not shown");
+            if failures.len() > 5 {
+                error!(additional_errors = failures.len() - 5, "Additional errors not shown");
             }
         }
 
-        info!("Successfully stored {}/{} entries", stored_count, entries.len());
-        Ok(stored_count)
+        info!(
+            "Successfully stored {}/{} entries ({} unchanged)",
+            stored_count,
+            entries.len(),
+            unchanged.len()
+        );
+        Ok(StoreOutcome {
+            stored: stored_count,
+            unchanged: unchanged.len(),
+            failures,
+        })
+    }
+
+    /// Fetch the checksums and latest version of already stored entries
+    async fn fetch_stored_entries(
+        &self,
+        entries: &[UniProtEntry],
+    ) -> Result<HashMap<String, StoredEntry>> {
+        let accessions: Vec<&str> = entries.iter().map(|e| e.accession.as_str()).collect();
+
+        let stored = sqlx::query_as::<_, StoredEntry>(
+            r#"
+            SELECT
+                pm.accession,
+                pm.sequence_checksum,
+                pm.annotation_checksum,
+                (
+                    SELECT v.version
+                    FROM versions v
+                    WHERE v.entry_id = pm.data_source_id
+                    ORDER BY v.version_major DESC, v.version_minor DESC, v.version_patch DESC
+                    LIMIT 1
+                ) AS latest_version
+            FROM protein_metadata pm
+            WHERE pm.accession = ANY($1)
+            "#,
+        )
+        .bind(&accessions)
+        .fetch_all(&self.db)
+        .await
+        .context("Failed to fetch stored entries")?;
+
+        Ok(stored
+            .into_iter()
+            .map(|entry| (entry.accession.clone(), entry))
+            .collect())
     }
 
-    /// Store a single entry within a transaction
+    /// Mark unchanged entries as part of this release
+    ///
+    /// Their version and files are reused as they are. Entries stored before
+    /// annotation checksums existed get theirs filled in here.
+    async fn carry_forward_tx(&self, unchanged: &[(String, EntryChecksums)]) -> Result<()> {
+        if unchanged.is_empty() {
+            return Ok(());
+        }
+
+        let (accessions, annotation_checksums): (Vec<&str>, Vec<&str>) = unchanged
+            .iter()
+            .map(|(accession, checksums)| (accession.as_str(), checksums.annotation.as_str()))
+            .unzip();
+
+        sqlx::query(
+            r#"
+            UPDATE protein_metadata pm
+            SET uniprot_version = $1,
+                annotation_checksum = COALESCE(pm.annotation_checksum, u.annotation_checksum)
+            FROM UNNEST($2::text[], $3::text[]) AS u(accession, annotation_checksum)
+            WHERE pm.accession = u.accession
+            "#,
+        )
+        .bind(&self.external_version)
+        .bind(&accessions)
+        .bind(&annotation_checksums)
+        .execute(&self.db)
+        .await
+        .context("Failed to carry unchanged entries forward")?;
+
+        Ok(())
+    }
+
+    /// Record the proteins of the previous release that are missing from this one
+    ///
+    /// Must run after every entry of the release was stored or carried
+    /// forward. Entries that are dead-lettered by `job_id` are not counted as
+    /// removed. Returns the number of removed proteins.
+    pub async fn record_removed_entries(
+        &self,
+        previous_release: &str,
+        job_id: Uuid,
+    ) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO entry_release_changes (
+                organization_id, external_version, entry_id, change_type, previous_version
+            )
+            SELECT
+                re.organization_id,
+                $2,
+                pm.data_source_id,
+                'removed',
+                (
+                    SELECT v.version
+                    FROM versions v
+                    WHERE v.entry_id = pm.data_source_id
+                    ORDER BY v.version_major DESC, v.version_minor DESC, v.version_patch DESC
+                    LIMIT 1
+                )
+            FROM protein_metadata pm
+            JOIN registry_entries re ON re.id = pm.data_source_id
+            WHERE re.organization_id = $1
+              AND pm.uniprot_version = $3
+              AND NOT EXISTS (
+                  SELECT 1 FROM ingestion_dead_letters dl
+                  WHERE dl.job_id = $4
+                    AND dl.status = 'pending'
+                    AND dl.record_identifier = pm.accession
+              )
+            ON CONFLICT (organization_id, external_version, entry_id) DO NOTHING
+            "#,
+        )
+        .bind(self.organization_id)
+        .bind(&self.external_version)
+        .bind(previous_release)
+        .bind(job_id)
+        .execute(&self.db)
+        .await
+        .context("Failed to record removed entries")?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Record the secondary accessions of entries as merged into their entry
+    ///
+    /// Specs pinned to a secondary accession then resolve to the entry, and
+    /// a protein stored under one earlier is deprecated in its favour.
+    async fn record_merged_accessions<'a>(
+        &self,
+        entries: impl Iterator<Item = &'a UniProtEntry>,
+    ) -> Result<()> {
+        let successions: Vec<Succession> = entries
+            .flat_map(|entry| {
+                entry
+                    .secondary_accessions
+                    .iter()
+                    .map(|previous| Succession::new(previous.as_str(), entry.accession.as_str()))
+            })
+            .collect();
+        if successions.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+        let deprecated = record_successions(&mut tx, self.organization_id, &successions).await?;
+        tx.commit()
+            .await
+            .context("Failed to commit merged accessions")?;
+
+        debug!(
+            secondary_accessions = successions.len(),
+            deprecated, "Recorded merged accessions"
+        );
+        Ok(())
+    }
+
+    /// Store a single added or modified entry within a transaction
     ///
     /// Creates: registry_entry -> data_source -> protein_metadata -> version -> version_file
+    /// and records the change for this release.
     async fn store_entry_tx(
         &self,
         tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
         entry: &UniProtEntry,
+        checksums: &EntryChecksums,
+        planned: &PlannedVersion,
     ) -> Result<()> {
         debug!("Storing protein: {}", entry.accession);
 
@@ -369,14 +785,36 @@ impl UniProtStorage {
             .await?;
 
         // 4. Create protein metadata (with sequence deduplication and organism reference)
-        self.create_protein_metadata_tx(tx, entry_id, entry, organism_id)
+        self.create_protein_metadata_tx(tx, entry_id, entry, organism_id, checksums)
             .await?;
 
         // 5. Create version with semantic versioning
-        let version_id = self.create_version_tx(tx, entry_id).await?;
+        let version_id = self
+            .create_version_tx(
+                tx,
+                entry_id,
+                &planned.version,
+                &ProteinSnapshot::of(entry, checksums),
+            )
+            .await?;
 
         // 6. Create version files for multiple formats (DAT, FASTA, JSON)
-        self.create_version_files_tx(tx, entry, version_id).await?;
+        self.create_version_files_tx(tx, entry, version_id, &planned.version)
+            .await?;
+
+        // 7. Record what changed in this release
+        record_release_change(
+            tx,
+            self.organization_id,
+            &self.external_version,
+            &ReleaseChange {
+                entry_id,
+                change_type: planned.change_type,
+                previous_version: planned.previous_version.clone(),
+                version: Some(planned.version.clone()),
+            },
+        )
+        .await?;
 
         debug!("Successfully stored protein: {}", entry.accession);
         Ok(())
@@ -498,16 +936,12 @@ impl UniProtStorage {
         data_source_id: Uuid,
         entry: &UniProtEntry,
         taxonomy_id: Uuid,
+        checksums: &EntryChecksums,
     ) -> Result<()> {
         // 1. Get or create deduplicated sequence
         let sequence_id = self.get_or_create_sequence_tx(tx, entry).await?;
 
-        // 2. Compute sequence checksum for metadata
-        let mut hasher = Sha256::new();
-        hasher.update(entry.sequence.as_bytes());
-        let sequence_checksum = format!("{:x}", hasher.finalize());
-
-        // 3. Insert protein metadata (with extended metadata and dates)
+        // 2. Insert protein metadata (with extended metadata and dates)
         sqlx::query(
             r#"
             INSERT INTO protein_metadata (
@@ -516,10 +950,21 @@ impl UniProtStorage {
                 sequence_id, taxonomy_id, uniprot_version,
                 alternative_names, ec_numbers, protein_existence, keywords,
                 organelle, organism_hosts,
-                entry_created, sequence_updated, annotation_updated
+                entry_created, sequence_updated, annotation_updated,
+                annotation_checksum
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21)
             ON CONFLICT (data_source_id) DO UPDATE SET
+                entry_name = EXCLUDED.entry_name,
+                protein_name = EXCLUDED.protein_name,
+                gene_name = EXCLUDED.gene_name,
+                sequence_length = EXCLUDED.sequence_length,
+                mass_da = EXCLUDED.mass_da,
+                sequence_checksum = EXCLUDED.sequence_checksum,
+                sequence_id = EXCLUDED.sequence_id,
+                taxonomy_id = EXCLUDED.taxonomy_id,
+                uniprot_version = EXCLUDED.uniprot_version,
+                annotation_checksum = EXCLUDED.annotation_checksum,
                 alternative_names = EXCLUDED.alternative_names,
                 ec_numbers = EXCLUDED.ec_numbers,
                 protein_existence = EXCLUDED.protein_existence,
@@ -538,7 +983,7 @@ impl UniProtStorage {
         .bind(&entry.gene_name)
         .bind(entry.sequence_length)
         .bind(entry.mass_da)
-        .bind(&sequence_checksum)
+        .bind(&checksums.sequence)
         .bind(sequence_id)
         .bind(taxonomy_id)
         .bind(&self.external_version)
@@ -551,21 +996,22 @@ impl UniProtStorage {
         .bind(entry.entry_created)
         .bind(entry.sequence_updated)
         .bind(entry.annotation_updated)
+        .bind(&checksums.annotation)
         .execute(&mut **tx)
         .await
         .context("Failed to create protein_metadata")?;
 
-        // 4. Insert protein features
+        // 3. Insert protein features
         self.store_features_tx(tx, data_source_id, entry).await?;
 
-        // 5. Insert cross-references
+        // 4. Insert cross-references
         self.store_cross_references_tx(tx, data_source_id, entry)
             .await?;
 
-        // 6. Insert comments
+        // 5. Insert comments
         self.store_comments_tx(tx, data_source_id, entry).await?;
 
-        // 7. Insert publications
+        // 6. Insert publications
         self.store_publications_tx(tx, data_source_id, entry)
             .await?;
 
@@ -579,16 +1025,16 @@ impl UniProtStorage {
         protein_id: Uuid,
         entry: &UniProtEntry,
     ) -> Result<()> {
-        if entry.features.is_empty() {
-            return Ok(());
-        }
-
         // Delete existing features for this protein (for updates)
         sqlx::query("DELETE FROM protein_features WHERE protein_id = $1")
             .bind(protein_id)
             .execute(&mut **tx)
             .await?;
 
+        if entry.features.is_empty() {
+            return Ok(());
+        }
+
         // Batch insert features (max 100 at a time to avoid parameter limit)
         for chunk in entry.features.chunks(MAX_INSERT_BATCH_SIZE) {
             let mut query_builder = sqlx::QueryBuilder::new(
@@ -616,16 +1062,16 @@ impl UniProtStorage {
         protein_id: Uuid,
         entry: &UniProtEntry,
     ) -> Result<()> {
-        if entry.cross_references.is_empty() {
-            return Ok(());
-        }
-
         // Delete existing cross-references for this protein
         sqlx::query("DELETE FROM protein_cross_references WHERE protein_id = $1")
             .bind(protein_id)
             .execute(&mut **tx)
             .await?;
 
+        if entry.cross_references.is_empty() {
+            return Ok(());
+        }
+
         // Batch insert cross-references (max 100 at a time)
         for chunk in entry.cross_references.chunks(MAX_INSERT_BATCH_SIZE) {
             let mut query_builder = sqlx::QueryBuilder::new(
@@ -654,16 +1100,16 @@ impl UniProtStorage {
         protein_id: Uuid,
         entry: &UniProtEntry,
     ) -> Result<()> {
-        if entry.comments.is_empty() {
-            return Ok(());
-        }
-
         // Delete existing comments for this protein
         sqlx::query("DELETE FROM protein_comments WHERE protein_id = $1")
             .bind(protein_id)
             .execute(&mut **tx)
             .await?;
 
+        if entry.comments.is_empty() {
+            return Ok(());
+        }
+
         // Batch insert comments (max 100 at a time)
         for chunk in entry.comments.chunks(MAX_INSERT_BATCH_SIZE) {
             let mut query_builder =
@@ -688,16 +1134,16 @@ impl UniProtStorage {
         protein_id: Uuid,
         entry: &UniProtEntry,
     ) -> Result<()> {
-        if entry.publications.is_empty() {
-            return Ok(());
-        }
-
         // Delete existing publications for this protein
         sqlx::query("DELETE FROM protein_publications WHERE protein_id = $1")
             .bind(protein_id)
             .execute(&mut **tx)
             .await?;
 
+        if entry.publications.is_empty() {
+            return Ok(());
+        }
+
         // Batch insert publications (max 50 at a time due to many columns)
         for chunk in entry.publications.chunks(MAX_PUBLICATION_BATCH_SIZE) {
             let mut query_builder = sqlx::QueryBuilder::new(
@@ -776,9 +1222,11 @@ impl UniProtStorage {
         &self,
         tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
         entry_id: Uuid,
+        version: &str,
+        snapshot: &ProteinSnapshot,
     ) -> Result<Uuid> {
         // Parse internal version (e.g., "1.0" → major=1, minor=0, patch=0)
-        let version_parts: Vec<&str> = self.internal_version.split('.').collect();
+        let version_parts: Vec<&str> = version.split('.').collect();
         let version_major = version_parts
             .first()
             .and_then(|v| v.parse::<i32>().ok())
@@ -795,12 +1243,13 @@ impl UniProtStorage {
         let version_id = sqlx::query_scalar::<_, Uuid>(
             r#"
             INSERT INTO versions (
-                entry_id, version, external_version,
+                entry_id, version, external_version, additional_metadata,
                 version_major, version_minor, version_patch
             )
-            VALUES ($1, $2, $3, $4, $5, $6)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
             ON CONFLICT (entry_id, version) DO UPDATE
             SET external_version = EXCLUDED.external_version,
+                additional_metadata = EXCLUDED.additional_metadata,
                 version_major = EXCLUDED.version_major,
                 version_minor = EXCLUDED.version_minor,
                 version_patch = EXCLUDED.version_patch,
@@ -809,8 +1258,9 @@ impl UniProtStorage {
             "#,
         )
         .bind(entry_id)
-        .bind(&self.internal_version)
+        .bind(version)
         .bind(&self.external_version)
+        .bind(serde_json::to_value(snapshot).context("Failed to serialize protein snapshot")?)
         .bind(version_major)
         .bind(version_minor)
         .bind(version_patch)
@@ -895,8 +1345,9 @@ impl UniProtStorage {
         tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
         entry: &UniProtEntry,
         version_id: Uuid,
+        version: &str,
     ) -> Result<()> {
-        let base_path = format!("proteins/uniprot/{}/{}", entry.accession, self.internal_version);
+        let base_path = format!("proteins/uniprot/{}/{}", entry.accession, version);
 
         // 1. DAT format - just create DB record (S3 upload already done)
         let dat_content = entry.sequence.as_bytes().to_vec();
@@ -958,8 +1409,8 @@ impl UniProtStorage {
     ) -> Result<()> {
         sqlx::query(
             r#"
-            INSERT INTO version_files (version_id, format, s3_key, checksum, size_bytes)
-            VALUES ($1, $2, $3, $4, $5)
+            INSERT INTO version_files (version_id, format, s3_key, checksum, size_bytes, sha256)
+            VALUES ($1, $2, $3, $4, $5, $4)
             ON CONFLICT (version_id, format) DO NOTHING
             "#,
         )
@@ -1420,4 +1871,120 @@ mod tests {
         let lineage = vec!["UnknownDomain".to_string()];
         assert_eq!(classify_source_type(&lineage), "organism");
     }
+
+    fn checksums(sequence: &str, annotation: &str) -> EntryChecksums {
+        EntryChecksums {
+            sequence: sequence.to_string(),
+            annotation: annotation.to_string(),
+        }
+    }
+
+    fn stored(annotation: Option<&str>) -> StoredEntry {
+        StoredEntry {
+            accession: "P12345".to_string(),
+            sequence_checksum: Some("seq".to_string()),
+            annotation_checksum: annotation.map(str::to_string),
+            latest_version: Some("1.2".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_diff_entry_added() {
+        assert_eq!(
+            diff_entry(&checksums("seq", "ann"), None, "1.0"),
+            EntryDiff::Changed(PlannedVersion {
+                change_type: ReleaseChangeType::Added,
+                previous_version: None,
+                version: "1.0".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_diff_entry_unchanged() {
+        assert_eq!(
+            diff_entry(&checksums("seq", "ann"), Some(&stored(Some("ann"))), "1.0"),
+            EntryDiff::Unchanged
+        );
+        // Stored before annotation checksums existed: carried forward, not modified
+        assert_eq!(
+            diff_entry(&checksums("seq", "ann"), Some(&stored(None)), "1.0"),
+            EntryDiff::Unchanged
+        );
+    }
+
+    #[test]
+    fn test_diff_entry_modified() {
+        assert_eq!(
+            diff_entry(&checksums("seq", "new"), Some(&stored(Some("ann"))), "1.0"),
+            EntryDiff::Changed(PlannedVersion {
+                change_type: ReleaseChangeType::AnnotationModified,
+                previous_version: Some("1.2".to_string()),
+                version: "1.3".to_string(),
+            })
+        );
+        // A sequence change wins over an annotation change
+        assert_eq!(
+            diff_entry(&checksums("new", "new"), Some(&stored(Some("ann"))), "1.0"),
+            EntryDiff::Changed(PlannedVersion {
+                change_type: ReleaseChangeType::SequenceModified,
+                previous_version: Some("1.2".to_string()),
+                version: "2.0".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_protein_snapshot() {
+        use crate::ingest::uniprot::models::{CrossReference, ProteinFeature};
+
+        let xref = |database: &str, id: &str| CrossReference {
+            database: database.to_string(),
+            database_id: id.to_string(),
+            metadata: vec![],
+        };
+        let entry = UniProtEntry {
+            accession: "P01308".to_string(),
+            entry_name: "INS_HUMAN".to_string(),
+            protein_name: "Insulin".to_string(),
+            gene_name: Some("INS".to_string()),
+            organism_name: "Homo sapiens".to_string(),
+            taxonomy_id: 9606,
+            taxonomy_lineage: vec!["Eukaryota".to_string()],
+            sequence: "MALWMRLLPLL".to_string(),
+            sequence_length: 11,
+            mass_da: 1300,
+            release_date: chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            secondary_accessions: vec![],
+            alternative_names: vec![],
+            ec_numbers: vec![],
+            features: vec![ProteinFeature {
+                feature_type: "SIGNAL".to_string(),
+                start_pos: Some(1),
+                end_pos: Some(24),
+                description: String::new(),
+            }],
+            cross_references: vec![
+                xref("PDB", "4INS"),
+                xref("GO", "GO:0005179"),
+                xref("GO", "GO:0005179"),
+            ],
+            comments: vec![],
+            protein_existence: Some(1),
+            keywords: vec!["Hormone".to_string(), "Diabetes mellitus".to_string()],
+            organelle: None,
+            organism_hosts: vec![],
+            publications: vec![],
+            entry_created: None,
+            sequence_updated: None,
+            annotation_updated: None,
+        };
+
+        let snapshot = ProteinSnapshot::of(&entry, &checksums("seq", "ann"));
+        assert_eq!(snapshot.features, vec!["SIGNAL 1..24"]);
+        assert_eq!(snapshot.go_terms, vec!["GO:0005179"]);
+        assert_eq!(snapshot.cross_references, vec!["PDB:4INS"]);
+        assert_eq!(snapshot.keywords, vec!["Diabetes mellitus", "Hormone"]);
+        assert_eq!(snapshot.sequence_checksum, "seq");
+    }
 }
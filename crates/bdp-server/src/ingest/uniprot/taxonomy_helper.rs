@@ -153,8 +153,7 @@ impl TaxonomyHelper {
             VALUES ($1, $2, $3, $4, $5, $6, $7)
             ON CONFLICT (data_source_id) DO UPDATE SET
                 scientific_name = EXCLUDED.scientific_name,
-                lineage = EXCLUDED.lineage,
-                parsed_at = NOW()
+                lineage = EXCLUDED.lineage
             "#,
         )
         .bind(registry_entry_id)
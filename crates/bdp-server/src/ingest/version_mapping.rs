@@ -1,64 +1,328 @@
-//! Version mapping utilities
+//! Version mapping between upstream releases and internal versions
 //!
-//! Utilities for mapping between internal and external version identifiers.
-//! Currently uses a simple mapping strategy where internal version is always "1.0"
-//! and external versions are preserved as-is (e.g., "2024_01", "2025_06").
-
-/// Version mapper for converting between internal and external version formats
-///
-/// Current strategy:
-/// - Internal version: Always "1.0" (our schema version)
-/// - External version: Preserved as-is from source (e.g., "2024_01" for UniProt)
-///
-/// This simple approach allows us to version our data schema independently
-/// from the upstream source versioning.
-pub struct VersionMapper;
+//! Data sources are published under internal semantic versions (`1.4.0`)
+//! calculated from the changes between upstream releases, which keep their
+//! provider's naming (`2025_01` for UniProt, `2025-03-16` for GO, `98.0` for
+//! InterPro, `2025-06-01` for the NCBI taxdump). The mapper records which
+//! internal version corresponds to which release and looks mappings up in
+//! both directions.
+//!
+//! A mapping covers either one data source or, for releases shared by every
+//! entry of an organization, the whole organization. Lookups for a data
+//! source check, in order:
+//!
+//! 1. its versions, whose `external_version` records the release they were
+//!    ingested from
+//! 2. mappings recorded for the data source
+//! 3. mappings recorded for its organization
+//!
+//! The candidates come from the `version_mapping_candidates` SQL function,
+//! which tags each one with its lookup priority.
+
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::features::shared::error_helpers::is_unique_violation;
+use crate::ingest::versioning::{BumpType, SemanticVersion};
+
+/// An upstream release and the internal version it is published under
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionMapping {
+    pub organization_slug: String,
+    /// Data source the mapping is specific to, `None` for a release shared
+    /// by the whole organization
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_source_slug: Option<String>,
+    pub internal_version: String,
+    pub external_version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub release_date: Option<NaiveDate>,
+}
+
+impl VersionMapping {
+    /// Mapping of a release shared by every data source of an organization
+    pub fn organization(
+        organization_slug: impl Into<String>,
+        external_version: impl Into<String>,
+        internal_version: impl Into<String>,
+    ) -> Self {
+        Self {
+            organization_slug: organization_slug.into(),
+            data_source_slug: None,
+            internal_version: internal_version.into(),
+            external_version: external_version.into(),
+            release_date: None,
+        }
+    }
+
+    /// Mapping of a release of a single data source
+    pub fn data_source(
+        organization_slug: impl Into<String>,
+        data_source_slug: impl Into<String>,
+        external_version: impl Into<String>,
+        internal_version: impl Into<String>,
+    ) -> Self {
+        Self {
+            data_source_slug: Some(data_source_slug.into()),
+            ..Self::organization(organization_slug, external_version, internal_version)
+        }
+    }
+
+    pub fn with_release_date(mut self, release_date: Option<NaiveDate>) -> Self {
+        self.release_date = release_date;
+        self
+    }
+}
+
+/// Persistent mapping between upstream releases and internal versions
+#[derive(Clone)]
+pub struct VersionMapper {
+    pool: PgPool,
+}
 
 impl VersionMapper {
-    /// Create a new version mapper
-    pub fn new() -> Self {
-        Self
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
     }
 
-    /// Convert external version to internal format
+    /// Record that an upstream release is published under an internal version
     ///
-    /// Currently returns "1.0" for all external versions as we're using
-    /// a single internal schema version.
-    ///
-    /// # Arguments
-    /// * `external_version` - External version string (e.g., "2024_01")
-    ///
-    /// # Returns
-    /// Internal version string (currently always "1.0")
-    pub fn external_to_internal(&self, _external_version: &str) -> anyhow::Result<String> {
-        Ok("1.0".to_string())
+    /// Recording the same mapping again only fills in an unknown release
+    /// date. Fails when the release or the internal version is already mapped
+    /// to something else, as pinned manifests would silently change meaning.
+    pub async fn record(&self, mapping: &VersionMapping) -> Result<()> {
+        if SemanticVersion::parse(&mapping.internal_version).is_none() {
+            anyhow::bail!(
+                "Internal version '{}' is not a semantic version",
+                mapping.internal_version
+            );
+        }
+
+        let entry_id = match &mapping.data_source_slug {
+            Some(slug) => Some(
+                sqlx::query_scalar!(
+                    r#"
+                    SELECT re.id
+                    FROM registry_entries re
+                    JOIN organizations o ON o.id = re.organization_id
+                    WHERE LOWER(o.slug) = LOWER($1) AND LOWER(re.slug) = LOWER($2)
+                    "#,
+                    mapping.organization_slug,
+                    slug
+                )
+                .fetch_optional(&self.pool)
+                .await?
+                .with_context(|| {
+                    format!("Data source '{}:{}' not found", mapping.organization_slug, slug)
+                })?,
+            ),
+            None => None,
+        };
+
+        let mapped_to = sqlx::query_scalar!(
+            r#"
+            INSERT INTO version_mappings
+                (organization_slug, entry_id, external_version, internal_version, release_date)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT ON CONSTRAINT version_mappings_external_key DO UPDATE
+            SET release_date = COALESCE(version_mappings.release_date, EXCLUDED.release_date)
+            RETURNING internal_version
+            "#,
+            mapping.organization_slug,
+            entry_id,
+            mapping.external_version,
+            mapping.internal_version,
+            mapping.release_date
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            // The release itself is upserted, so a unique violation can only
+            // come from the internal version being taken
+            if is_unique_violation(&e) {
+                anyhow::Error::new(e).context(format!(
+                    "Internal version '{}' of {} is already mapped to another release",
+                    mapping.internal_version,
+                    scope(mapping)
+                ))
+            } else {
+                anyhow::Error::new(e).context("Failed to record version mapping")
+            }
+        })?;
+
+        if mapped_to != mapping.internal_version {
+            anyhow::bail!(
+                "Release '{}' of {} is already mapped to internal version '{}'",
+                mapping.external_version,
+                scope(mapping),
+                mapped_to
+            );
+        }
+
+        info!(
+            organization = %mapping.organization_slug,
+            data_source = ?mapping.data_source_slug,
+            external_version = %mapping.external_version,
+            internal_version = %mapping.internal_version,
+            "Recorded version mapping"
+        );
+        Ok(())
     }
 
-    /// Convert internal version to external format
-    ///
-    /// This is the inverse operation, but since our internal version is generic ("1.0"),
-    /// we cannot reliably reconstruct the external version from it alone.
-    /// This method is provided for API completeness but should be used with caution.
+    /// Internal version to publish an upstream release under
     ///
-    /// # Arguments
-    /// * `internal_version` - Internal version string (expected to be "1.0")
-    ///
-    /// # Returns
-    /// Error indicating this operation is not supported in the current implementation
-    pub fn internal_to_external(&self, internal_version: &str) -> anyhow::Result<String> {
-        // Since internal version is always "1.0" and doesn't encode external version info,
-        // we cannot convert back without additional context
-        anyhow::bail!(
-            "Cannot convert internal version '{}' to external format without additional context. \
-             Internal version '1.0' represents our schema version, not the source version.",
+    /// The version the release is already mapped to, otherwise a minor bump
+    /// of the highest mapped version, or 1.0 for the first release.
+    pub async fn next_internal_version(
+        &self,
+        organization_slug: &str,
+        data_source_slug: Option<&str>,
+        external_version: &str,
+    ) -> sqlx::Result<String> {
+        if let Some(internal) = self
+            .external_to_internal(organization_slug, data_source_slug, external_version)
+            .await?
+        {
+            return Ok(internal);
+        }
+
+        let highest = self
+            .list(organization_slug, data_source_slug)
+            .await?
+            .iter()
+            .filter_map(|mapping| SemanticVersion::parse(&mapping.internal_version))
+            .reduce(|highest, version| {
+                if version.is_greater_than(&highest) {
+                    version
+                } else {
+                    highest
+                }
+            });
+        Ok(match highest {
+            Some(version) => version.bump(BumpType::Minor).to_string_short(),
+            None => "1.0".to_string(),
+        })
+    }
+
+    /// Slug of an organization, for pipelines that only know its ID
+    pub async fn organization_slug(&self, organization_id: Uuid) -> Result<String> {
+        sqlx::query_scalar!("SELECT slug FROM organizations WHERE id = $1", organization_id)
+            .fetch_optional(&self.pool)
+            .await?
+            .with_context(|| format!("Organization {} not found", organization_id))
+    }
+
+    /// Internal version an upstream release is published under
+    pub async fn external_to_internal(
+        &self,
+        organization_slug: &str,
+        data_source_slug: Option<&str>,
+        external_version: &str,
+    ) -> sqlx::Result<Option<String>> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT internal_version as "internal_version!"
+            FROM version_mapping_candidates($1, $2)
+            WHERE external_version = $3
+            ORDER BY priority
+            LIMIT 1
+            "#,
+            organization_slug,
+            data_source_slug,
+            external_version
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Upstream release an internal version was published from
+    pub async fn internal_to_external(
+        &self,
+        organization_slug: &str,
+        data_source_slug: Option<&str>,
+        internal_version: &str,
+    ) -> sqlx::Result<Option<String>> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT external_version as "external_version!"
+            FROM version_mapping_candidates($1, $2)
+            WHERE internal_version = $3
+            ORDER BY priority
+            LIMIT 1
+            "#,
+            organization_slug,
+            data_source_slug,
             internal_version
         )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Mapping of a version given in either form
+    ///
+    /// An internal version wins over a release of the same name, so `@98.0`
+    /// keeps meaning the published version when a release is also called so.
+    pub async fn resolve(
+        &self,
+        organization_slug: &str,
+        data_source_slug: Option<&str>,
+        version: &str,
+    ) -> sqlx::Result<Option<VersionMapping>> {
+        sqlx::query_as!(
+            VersionMapping,
+            r#"
+            SELECT organization_slug as "organization_slug!", data_source_slug,
+                   internal_version as "internal_version!",
+                   external_version as "external_version!", release_date
+            FROM version_mapping_candidates($1, $2)
+            WHERE internal_version = $3 OR external_version = $3
+            ORDER BY (internal_version = $3) DESC, priority
+            LIMIT 1
+            "#,
+            organization_slug,
+            data_source_slug,
+            version
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Every release mapped for a data source, or for the organization when
+    /// no data source is given, newest first
+    pub async fn list(
+        &self,
+        organization_slug: &str,
+        data_source_slug: Option<&str>,
+    ) -> sqlx::Result<Vec<VersionMapping>> {
+        sqlx::query_as!(
+            VersionMapping,
+            r#"
+            SELECT organization_slug as "organization_slug!", data_source_slug,
+                   internal_version as "internal_version!",
+                   external_version as "external_version!", release_date
+            FROM (
+                SELECT DISTINCT ON (external_version) *
+                FROM version_mapping_candidates($1, $2)
+                ORDER BY external_version, priority
+            ) mappings
+            ORDER BY release_date DESC NULLS LAST, external_version DESC
+            "#,
+            organization_slug,
+            data_source_slug
+        )
+        .fetch_all(&self.pool)
+        .await
     }
 }
 
-impl Default for VersionMapper {
-    fn default() -> Self {
-        Self::new()
+fn scope(mapping: &VersionMapping) -> String {
+    match &mapping.data_source_slug {
+        Some(slug) => format!("'{}:{}'", mapping.organization_slug, slug),
+        None => format!("'{}'", mapping.organization_slug),
     }
 }
 
@@ -66,8 +330,133 @@ impl Default for VersionMapper {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_version_mapper_new() {
-        let _mapper = VersionMapper::new();
+    async fn setup(pool: &PgPool) -> sqlx::Result<()> {
+        let org_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO organizations (slug, name, is_system) VALUES ('uniprot', 'UniProt', true) RETURNING id",
+        )
+        .fetch_one(pool)
+        .await?;
+        sqlx::query(
+            r#"
+            WITH entry AS (
+                INSERT INTO registry_entries (organization_id, slug, name, entry_type)
+                VALUES ($1, 'P01308', 'Insulin', 'data_source')
+                RETURNING id
+            )
+            INSERT INTO versions (entry_id, version, external_version)
+            SELECT id, v.version, v.external_version
+            FROM entry, (VALUES ('1.3.0', '2024_06'), ('1.4.0', '2025_01')) AS v (version, external_version)
+            "#,
+        )
+        .bind(org_id)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_lookups_use_versions_of_the_data_source(pool: PgPool) -> sqlx::Result<()> {
+        setup(&pool).await?;
+        let mapper = VersionMapper::new(pool);
+
+        let internal = mapper
+            .external_to_internal("uniprot", Some("P01308"), "2025_01")
+            .await?;
+        assert_eq!(internal.as_deref(), Some("1.4.0"));
+
+        let external = mapper
+            .internal_to_external("UniProt", Some("p01308"), "1.3.0")
+            .await?;
+        assert_eq!(external.as_deref(), Some("2024_06"));
+
+        assert_eq!(
+            mapper
+                .external_to_internal("uniprot", Some("P01308"), "2023_01")
+                .await?,
+            None
+        );
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_record_and_resolve(pool: PgPool) -> sqlx::Result<()> {
+        setup(&pool).await?;
+        let mapper = VersionMapper::new(pool);
+
+        mapper
+            .record(
+                &VersionMapping::organization("uniprot", "2025_02", "1.5.0")
+                    .with_release_date(NaiveDate::from_ymd_opt(2025, 2, 5)),
+            )
+            .await
+            .unwrap();
+
+        // Organization-wide mappings apply to every data source
+        let mapping = mapper
+            .resolve("uniprot", Some("P01308"), "2025_02")
+            .await?
+            .unwrap();
+        assert_eq!(mapping.internal_version, "1.5.0");
+        assert_eq!(mapping.data_source_slug, None);
+
+        let mapping = mapper
+            .resolve("uniprot", Some("P01308"), "1.4.0")
+            .await?
+            .unwrap();
+        assert_eq!(mapping.external_version, "2025_01");
+        assert_eq!(mapping.data_source_slug.as_deref(), Some("P01308"));
+
+        let releases: Vec<String> = mapper
+            .list("uniprot", Some("P01308"))
+            .await?
+            .into_iter()
+            .map(|m| m.external_version)
+            .collect();
+        assert_eq!(releases, vec!["2025_02", "2025_01", "2024_06"]);
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_next_internal_version(pool: PgPool) -> sqlx::Result<()> {
+        setup(&pool).await?;
+        let mapper = VersionMapper::new(pool);
+
+        let next = |release| mapper.next_internal_version("uniprot", Some("P01308"), release);
+        assert_eq!(next("2025_01").await?, "1.4.0");
+        assert_eq!(next("2025_02").await?, "1.5");
+        assert_eq!(
+            mapper
+                .next_internal_version("uniprot", None, "2025_02")
+                .await?,
+            "1.0"
+        );
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_record_rejects_conflicting_mappings(pool: PgPool) -> sqlx::Result<()> {
+        setup(&pool).await?;
+        let mapper = VersionMapper::new(pool);
+
+        let mapping = VersionMapping::data_source("uniprot", "P01308", "2025_02", "1.5.0");
+        mapper.record(&mapping).await.unwrap();
+        // Recording the same mapping again is a no-op
+        mapper.record(&mapping).await.unwrap();
+
+        let remapped = VersionMapping::data_source("uniprot", "P01308", "2025_02", "1.6.0");
+        assert!(mapper.record(&remapped).await.is_err());
+
+        let reused = VersionMapping::data_source("uniprot", "P01308", "2025_03", "1.5.0");
+        let error = mapper.record(&reused).await.unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("already mapped to another release"));
+
+        let not_semver = VersionMapping::organization("uniprot", "2025_03", "2025_03");
+        assert!(mapper.record(&not_semver).await.is_err());
+
+        let unknown = VersionMapping::data_source("uniprot", "P99999", "2025_03", "1.6.0");
+        assert!(mapper.record(&unknown).await.is_err());
+        Ok(())
     }
 }
@@ -3,6 +3,8 @@
 //! This module handles cascading version bumps to dependent data sources
 //! when an upstream dependency is updated.
 
+use std::collections::HashSet;
+
 use anyhow::{Context, Result};
 use sqlx::{PgPool, Row};
 use tracing::{debug, info, instrument, warn};
@@ -11,8 +13,8 @@ use uuid::Uuid;
 use super::calculator::{calculate_next_version, create_version};
 use super::storage::save_changelog;
 use super::types::{
-    BumpType, CascadeResult, ChangelogEntry, ChangelogSummary, DataSourceDependent, TriggerReason,
-    VersionChangelog,
+    BumpType, CascadePreview, CascadeResult, ChangelogEntry, ChangelogSummary, DataSourceDependent,
+    TriggerReason, VersionChangelog,
 };
 
 /// Find all data sources that depend on the given version
@@ -175,6 +177,16 @@ pub async fn cascade_version_bump(
     pool: &PgPool,
     source_version_id: Uuid,
     source_changelog: &VersionChangelog,
+) -> Result<Vec<CascadeResult>> {
+    cascade_version_bump_excluding(pool, source_version_id, source_changelog, &HashSet::new()).await
+}
+
+/// Cascade to direct dependents, skipping the excluded registry entries
+async fn cascade_version_bump_excluding(
+    pool: &PgPool,
+    source_version_id: Uuid,
+    source_changelog: &VersionChangelog,
+    excluded_entries: &HashSet<Uuid>,
 ) -> Result<Vec<CascadeResult>> {
     info!(
         source_version_id = %source_version_id,
@@ -183,7 +195,8 @@ pub async fn cascade_version_bump(
     );
 
     // Find all dependents of this version
-    let dependents = find_dependents(pool, source_version_id).await?;
+    let mut dependents = find_dependents(pool, source_version_id).await?;
+    dependents.retain(|d| !excluded_entries.contains(&d.entry_id));
 
     if dependents.is_empty() {
         debug!("No dependents found, cascade complete");
@@ -193,27 +206,7 @@ pub async fn cascade_version_bump(
     info!(dependent_count = dependents.len(), "Found dependents to cascade");
 
     // Get source version info for changelog
-    let source_row = sqlx::query(
-        r#"
-        SELECT re.slug, v.version
-        FROM versions v
-        JOIN registry_entries re ON re.id = v.entry_id
-        WHERE v.id = $1
-        "#,
-    )
-    .bind(source_version_id)
-    .fetch_one(pool)
-    .await
-    .context("Failed to fetch source version info")?;
-
-    struct SourceInfo {
-        slug: String,
-        version: String,
-    }
-    let source_info = SourceInfo {
-        slug: source_row.get("slug"),
-        version: source_row.get("version"),
-    };
+    let source_info = fetch_source_info(pool, source_version_id).await?;
 
     let mut cascade_results = Vec::new();
 
@@ -252,6 +245,60 @@ pub async fn cascade_version_bump(
     Ok(cascade_results)
 }
 
+/// Slug and version string of the version that triggers a cascade
+struct SourceInfo {
+    slug: String,
+    version: String,
+}
+
+async fn fetch_source_info(pool: &PgPool, source_version_id: Uuid) -> Result<SourceInfo> {
+    let source_row = sqlx::query(
+        r#"
+        SELECT re.slug, v.version
+        FROM versions v
+        JOIN registry_entries re ON re.id = v.entry_id
+        WHERE v.id = $1
+        "#,
+    )
+    .bind(source_version_id)
+    .fetch_one(pool)
+    .await
+    .context("Failed to fetch source version info")?;
+
+    Ok(SourceInfo {
+        slug: source_row.get("slug"),
+        version: source_row.get("version"),
+    })
+}
+
+/// Bump a dependent gets from its upstream changelog
+///
+/// If the source had breaking changes, the dependent also gets a major bump.
+fn cascade_bump_type(source_changelog: &VersionChangelog) -> BumpType {
+    if source_changelog.has_breaking_changes() {
+        BumpType::Major
+    } else {
+        BumpType::Minor
+    }
+}
+
+/// Changelog `cascade_recursive` passes down from a cascaded dependent
+fn nested_cascade_changelog(
+    source_changelog: &VersionChangelog,
+    dependent_slug: &str,
+) -> VersionChangelog {
+    VersionChangelog::new(
+        source_changelog.bump_type,
+        vec![ChangelogEntry::dependency(
+            "dependencies",
+            format!("Cascaded from {}", dependent_slug),
+            source_changelog.has_breaking_changes(),
+        )],
+        ChangelogSummary::default(),
+        "Cascaded dependency update",
+    )
+}
+
 /// Cascade version bump to a single dependent
 async fn cascade_single_dependent(
     pool: &PgPool,
@@ -262,12 +309,7 @@ async fn cascade_single_dependent(
     source_version: &str,
 ) -> Result<CascadeResult> {
     // Calculate new version for dependent
-    // If source had breaking changes, dependent also gets major bump
-    let bump_type = if source_changelog.has_breaking_changes() {
-        BumpType::Major
-    } else {
-        BumpType::Minor
-    };
+    let bump_type = cascade_bump_type(source_changelog);
 
     let new_version = calculate_next_version(&dependent.current_version, bump_type);
 
@@ -436,6 +478,30 @@ pub async fn cascade_recursive(
     source_version_id: Uuid,
     source_changelog: &VersionChangelog,
     max_depth: usize,
+) -> Result<Vec<CascadeResult>> {
+    cascade_recursive_excluding(
+        pool,
+        source_version_id,
+        source_changelog,
+        max_depth,
+        &HashSet::new(),
+    )
+    .await
+}
+
+/// Recursively cascade version bumps, skipping the excluded registry entries
+///
+/// An excluded dependent keeps its current version, and nothing is cascaded
+/// through it; its own dependents are only bumped if they are reached through
+/// another dependency. Used to publish a cascade after reviewing it with
+/// [`preview_cascade`].
+#[instrument(skip(pool, source_changelog, excluded_entries))]
+pub async fn cascade_recursive_excluding(
+    pool: &PgPool,
+    source_version_id: Uuid,
+    source_changelog: &VersionChangelog,
+    max_depth: usize,
+    excluded_entries: &HashSet<Uuid>,
 ) -> Result<Vec<CascadeResult>> {
     if max_depth == 0 {
         warn!("Max cascade depth reached, stopping recursion");
@@ -445,27 +511,21 @@ pub async fn cascade_recursive(
     let mut all_results = Vec::new();
 
     // Cascade to direct dependents
-    let direct_results = cascade_version_bump(pool, source_version_id, source_changelog).await?;
+    let direct_results =
+        cascade_version_bump_excluding(pool, source_version_id, source_changelog, excluded_entries)
+            .await?;
 
     // For each cascaded version, recursively cascade to their dependents
     for result in &direct_results {
         // Create a minimal changelog for the recursive cascade
-        let cascade_changelog = VersionChangelog::new(
-            source_changelog.bump_type,
-            vec![ChangelogEntry::dependency(
-                "dependencies",
-                format!("Cascaded from {}", result.entry_slug),
-                source_changelog.has_breaking_changes(),
-            )],
-            ChangelogSummary::default(),
-            "Cascaded dependency update",
-        );
+        let cascade_changelog = nested_cascade_changelog(source_changelog, &result.entry_slug);
 
-        let nested_results = Box::pin(cascade_recursive(
+        let nested_results = Box::pin(cascade_recursive_excluding(
             pool,
             result.new_version_id,
             &cascade_changelog,
             max_depth - 1,
+            excluded_entries,
         ))
         .await?;
 
@@ -476,6 +536,129 @@ pub async fn cascade_recursive(
     Ok(all_results)
 }
 
+/// Compute the cascade `cascade_recursive_excluding` would perform, without writing
+///
+/// Walks the same dependents to the same depth and returns, for each, the
+/// version it would be bumped to and the changelog that would be saved with
+/// it, nested under the dependent that triggers it.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `source_version_id` - The version whose bump would trigger the cascade
+/// * `source_changelog` - The changelog of that version
+/// * `max_depth` - Maximum depth to cascade
+/// * `excluded_entries` - Registry entries to leave out, with everything
+///   reached only through them
+///
+/// # Returns
+/// The direct dependents that would be bumped, each with its own dependents
+#[instrument(skip(pool, source_changelog, excluded_entries))]
+pub async fn preview_cascade(
+    pool: &PgPool,
+    source_version_id: Uuid,
+    source_changelog: &VersionChangelog,
+    max_depth: usize,
+    excluded_entries: &HashSet<Uuid>,
+) -> Result<Vec<CascadePreview>> {
+    if max_depth == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut dependents = find_dependents(pool, source_version_id).await?;
+    dependents.retain(|d| !excluded_entries.contains(&d.entry_id));
+    if dependents.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let source_info = fetch_source_info(pool, source_version_id).await?;
+
+    let mut previews = Vec::with_capacity(dependents.len());
+    for dependent in dependents {
+        previews.push(
+            preview_dependent(
+                pool,
+                dependent,
+                &source_info,
+                Some(source_version_id),
+                source_changelog,
+                max_depth - 1,
+                excluded_entries,
+            )
+            .await?,
+        );
+    }
+
+    Ok(previews)
+}
+
+/// Preview the bump of one dependent and, depth permitting, its dependents
+///
+/// `source_version_id` is `None` when the triggering version would itself be
+/// created by the cascade.
+async fn preview_dependent(
+    pool: &PgPool,
+    dependent: DataSourceDependent,
+    source_info: &SourceInfo,
+    source_version_id: Option<Uuid>,
+    source_changelog: &VersionChangelog,
+    remaining_depth: usize,
+    excluded_entries: &HashSet<Uuid>,
+) -> Result<CascadePreview> {
+    let bump_type = cascade_bump_type(source_changelog);
+    let new_version = calculate_next_version(&dependent.current_version, bump_type);
+
+    let mut changelog = create_cascade_changelog(
+        bump_type,
+        &source_info.slug,
+        &source_info.version,
+        source_version_id.unwrap_or_else(Uuid::nil),
+        source_changelog,
+    );
+    changelog.triggered_by_version_id = source_version_id;
+
+    let mut nested = Vec::new();
+    if remaining_depth > 0 {
+        // The cascade continues from the version it creates for this
+        // dependent, which shares the dependent's registry entry
+        let nested_source = SourceInfo {
+            slug: dependent.slug.clone(),
+            version: new_version.clone(),
+        };
+        let nested_changelog = nested_cascade_changelog(source_changelog, &dependent.slug);
+
+        let mut next_dependents = find_dependents_by_entry(pool, dependent.entry_id).await?;
+        next_dependents.retain(|d| !excluded_entries.contains(&d.entry_id));
+
+        for next in next_dependents {
+            nested.push(
+                Box::pin(preview_dependent(
+                    pool,
+                    next,
+                    &nested_source,
+                    None,
+                    &nested_changelog,
+                    remaining_depth - 1,
+                    excluded_entries,
+                ))
+                .await?,
+            );
+        }
+    }
+
+    Ok(CascadePreview {
+        entry_id: dependent.entry_id,
+        entry_slug: dependent.slug,
+        name: dependent.name,
+        organization_slug: dependent.organization_slug,
+        current_version_id: dependent.version_id,
+        current_version: dependent.current_version,
+        new_version,
+        bump_type,
+        changelog,
+        dependents: nested,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
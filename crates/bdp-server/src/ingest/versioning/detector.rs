@@ -1,7 +1,8 @@
 //! Version bump detection trait and implementations
 //!
 //! This module provides the trait and implementations for detecting version bumps
-//! for different data sources (UniProt, NCBI Taxonomy, Gene Ontology, GenBank/RefSeq).
+//! for different data sources (UniProt, NCBI Taxonomy, Gene Ontology, GenBank/RefSeq,
+//! RefSeq assemblies, Ensembl, PDB/AlphaFold structures).
 //!
 //! Detectors can optionally use an organization's custom versioning strategy if one
 //! is defined in the database. If no strategy is defined, the detector falls back to
@@ -13,6 +14,11 @@ use sqlx::PgPool;
 use tracing::{debug, info, instrument, warn};
 use uuid::Uuid;
 
+use crate::ingest::ensembl::storage::count_gene_model_changes;
+use crate::ingest::framework::{count_release_changes, ReleaseChangeType};
+use crate::ingest::refseq::storage::{current_assembly_snapshot, version_assembly_snapshot};
+use crate::ingest::structure::storage::{current_structure_snapshot, version_structure_snapshot};
+
 use super::types::{
     BumpType, ChangelogEntry, ChangelogSummary, TriggerReason, VersionChangelog, VersioningStrategy,
 };
@@ -151,13 +157,26 @@ pub trait VersionBumpDetector: Send + Sync {
 /// Detects changes in proteins between versions:
 /// - MAJOR if: proteins removed, accessions changed, sequences modified
 /// - MINOR if: proteins added, annotations updated, metadata changes
+///
+/// When the release is known, counts come from the per-entry changes the
+/// incremental pipeline recorded for it, limited to the data source and the
+/// entries it depends on.
 #[derive(Debug, Clone, Default)]
-pub struct UniProtBumpDetector;
+pub struct UniProtBumpDetector {
+    release: Option<String>,
+}
 
 impl UniProtBumpDetector {
     /// Create a new UniProt bump detector
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Create a detector that counts the recorded changes of a release
+    pub fn for_release(external_version: impl Into<String>) -> Self {
+        Self {
+            release: Some(external_version.into()),
+        }
     }
 }
 
@@ -278,6 +297,22 @@ impl VersionBumpDetector for UniProtBumpDetector {
 }
 
 impl UniProtBumpDetector {
+    /// Count the recorded changes of the detector's release among the data
+    /// source's entries, if it has a release
+    async fn count_release_changes(
+        &self,
+        pool: &PgPool,
+        data_source_id: Uuid,
+        change_type: ReleaseChangeType,
+    ) -> Result<Option<i64>> {
+        let Some(release) = &self.release else {
+            return Ok(None);
+        };
+
+        let counts = count_release_changes(pool, data_source_id, release).await?;
+        Ok(Some(counts.get(change_type)))
+    }
+
     /// Create initial changelog for first version
     async fn create_initial_changelog(
         &self,
@@ -342,9 +377,16 @@ impl UniProtBumpDetector {
     async fn detect_removed_proteins(
         &self,
         pool: &PgPool,
-        _data_source_id: Uuid,
+        data_source_id: Uuid,
         prev_version_id: Uuid,
     ) -> Result<i64> {
+        if let Some(count) = self
+            .count_release_changes(pool, data_source_id, ReleaseChangeType::Removed)
+            .await?
+        {
+            return Ok(count);
+        }
+
         // Count proteins that were in previous version but not in current
         // This is a simplified implementation - in production you'd compare actual accessions
         let count: Option<i64> = sqlx::query_scalar(
@@ -373,6 +415,13 @@ impl UniProtBumpDetector {
         data_source_id: Uuid,
         prev_version_id: Uuid,
     ) -> Result<i64> {
+        if let Some(count) = self
+            .count_release_changes(pool, data_source_id, ReleaseChangeType::Added)
+            .await?
+        {
+            return Ok(count);
+        }
+
         // Count proteins in current that weren't in previous version
         let count: Option<i64> = sqlx::query_scalar(
             r#"
@@ -399,29 +448,41 @@ impl UniProtBumpDetector {
     }
 
     /// Detect modified sequences between versions
+    ///
+    /// Sequence checksums are compared during ingestion, so this needs the
+    /// detector's release; without one nothing is counted.
     async fn detect_modified_sequences(
         &self,
-        _pool: &PgPool,
-        _data_source_id: Uuid,
+        pool: &PgPool,
+        data_source_id: Uuid,
         _prev_version_id: Uuid,
     ) -> Result<i64> {
-        // In a full implementation, this would compare sequence checksums
-        // between versions. For now, return 0 as we'd need sequence version tracking.
-        debug!("Sequence modification detection not yet implemented");
-        Ok(0)
+        let count = self
+            .count_release_changes(pool, data_source_id, ReleaseChangeType::SequenceModified)
+            .await?;
+        if count.is_none() {
+            debug!("No release to count modified sequences for");
+        }
+        Ok(count.unwrap_or(0))
     }
 
     /// Detect modified annotations between versions
+    ///
+    /// Annotation checksums are compared during ingestion, so this needs the
+    /// detector's release; without one nothing is counted.
     async fn detect_modified_annotations(
         &self,
-        _pool: &PgPool,
-        _data_source_id: Uuid,
+        pool: &PgPool,
+        data_source_id: Uuid,
         _prev_version_id: Uuid,
     ) -> Result<i64> {
-        // In a full implementation, this would compare annotation hashes
-        // between versions. For now, return 0.
-        debug!("Annotation modification detection not yet implemented");
-        Ok(0)
+        let count = self
+            .count_release_changes(pool, data_source_id, ReleaseChangeType::AnnotationModified)
+            .await?;
+        if count.is_none() {
+            debug!("No release to count modified annotations for");
+        }
+        Ok(count.unwrap_or(0))
     }
 }
 
@@ -1030,13 +1091,385 @@ impl GenbankBumpDetector {
     }
 }
 
+/// Ensembl gene set version bump detector
+///
+/// Counts the genes and transcripts the latest ingested release of a species
+/// added, removed or changed:
+/// - MAJOR if: genes or transcripts removed, gene coordinates changed
+/// - MINOR if: genes or transcripts added, annotations updated
+#[derive(Debug, Clone, Default)]
+pub struct EnsemblBumpDetector;
+
+impl EnsemblBumpDetector {
+    /// Create a new Ensembl bump detector
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl VersionBumpDetector for EnsemblBumpDetector {
+    fn name(&self) -> &'static str {
+        "Ensembl"
+    }
+
+    fn default_strategy(&self) -> VersioningStrategy {
+        VersioningStrategy::ensembl()
+    }
+
+    #[instrument(skip(self, pool), fields(detector = self.name()))]
+    async fn detect_changes(
+        &self,
+        pool: &PgPool,
+        data_source_id: Uuid,
+        previous_version_id: Option<Uuid>,
+    ) -> Result<VersionChangelog> {
+        info!(
+            data_source_id = %data_source_id,
+            previous_version_id = ?previous_version_id,
+            "Detecting Ensembl version changes"
+        );
+
+        let release: i32 = sqlx::query_scalar(
+            "SELECT ensembl_release FROM ensembl_species_metadata WHERE data_source_id = $1",
+        )
+        .bind(data_source_id)
+        .fetch_one(pool)
+        .await
+        .context("Failed to get the ingested Ensembl release")?;
+
+        let previous_release = match previous_version_id {
+            Some(version_id) => {
+                let external_version: Option<String> =
+                    sqlx::query_scalar("SELECT external_version FROM versions WHERE id = $1")
+                        .bind(version_id)
+                        .fetch_one(pool)
+                        .await
+                        .context("Failed to get previous Ensembl release")?;
+                external_version.and_then(|v| v.parse::<u32>().ok())
+            },
+            None => None,
+        };
+
+        let counts =
+            count_gene_model_changes(pool, data_source_id, release as u32, previous_release)
+                .await?;
+
+        // If no previous version, this is the initial release
+        if previous_version_id.is_none() {
+            let entries = vec![
+                ChangelogEntry::added(
+                    "genes",
+                    counts.genes_total,
+                    format!("Initial gene set from Ensembl release {}", release),
+                ),
+                ChangelogEntry::added(
+                    "transcripts",
+                    counts.transcripts_added,
+                    "Initial transcripts",
+                ),
+            ];
+            let summary = ChangelogSummary::initial(counts.genes_total);
+            let summary_text = format!(
+                "Initial release with {} genes and {} transcripts",
+                counts.genes_total, counts.transcripts_added
+            );
+            return Ok(VersionChangelog::new(BumpType::Minor, entries, summary, summary_text));
+        }
+
+        let mut entries = Vec::new();
+        if counts.genes_removed > 0 {
+            entries.push(ChangelogEntry::removed(
+                "genes",
+                counts.genes_removed,
+                "Genes removed or deprecated",
+            ));
+        }
+        if counts.transcripts_removed > 0 {
+            entries.push(ChangelogEntry::removed(
+                "transcripts",
+                counts.transcripts_removed,
+                "Transcripts removed or deprecated",
+            ));
+        }
+        if counts.genes_location_modified > 0 {
+            entries.push(ChangelogEntry::modified(
+                "coordinates",
+                counts.genes_location_modified,
+                "Genomic coordinates changed",
+                true, // Coordinate changes are breaking
+            ));
+        }
+        if counts.genes_added > 0 {
+            entries.push(ChangelogEntry::added("genes", counts.genes_added, "New genes added"));
+        }
+        if counts.transcripts_added > 0 {
+            entries.push(ChangelogEntry::added(
+                "transcripts",
+                counts.transcripts_added,
+                "New transcripts added",
+            ));
+        }
+        if counts.genes_annotation_modified > 0 {
+            entries.push(ChangelogEntry::modified(
+                "annotations",
+                counts.genes_annotation_modified,
+                "Gene annotations updated",
+                false, // Annotation changes are non-breaking
+            ));
+        }
+
+        let summary = ChangelogSummary::new(
+            counts.genes_total - counts.genes_added + counts.genes_removed,
+            counts.genes_total,
+            counts.genes_added,
+            counts.genes_removed,
+            counts.genes_location_modified + counts.genes_annotation_modified,
+            TriggerReason::NewRelease,
+        );
+
+        let bump_type = VersionChangelog::determine_bump_type(&entries);
+
+        let changelog = VersionChangelog::new(bump_type, entries, summary, "");
+        let summary_text = changelog.generate_summary_text();
+
+        Ok(VersionChangelog {
+            summary_text,
+            ..changelog
+        })
+    }
+}
+
+/// RefSeq genome assembly version bump detector
+///
+/// Compares the stored assembly against the snapshot recorded with the
+/// previous version:
+/// - MAJOR if: a new assembly version replaced the genomic sequences
+/// - MINOR if: the assembly was re-annotated (genes, proteins, annotation date)
+#[derive(Debug, Clone, Default)]
+pub struct RefseqAssemblyBumpDetector;
+
+impl RefseqAssemblyBumpDetector {
+    /// Create a new RefSeq assembly bump detector
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl VersionBumpDetector for RefseqAssemblyBumpDetector {
+    fn name(&self) -> &'static str {
+        "RefSeq Assembly"
+    }
+
+    fn default_strategy(&self) -> VersioningStrategy {
+        VersioningStrategy::genbank()
+    }
+
+    #[instrument(skip(self, pool), fields(detector = self.name()))]
+    async fn detect_changes(
+        &self,
+        pool: &PgPool,
+        data_source_id: Uuid,
+        previous_version_id: Option<Uuid>,
+    ) -> Result<VersionChangelog> {
+        info!(
+            data_source_id = %data_source_id,
+            previous_version_id = ?previous_version_id,
+            "Detecting RefSeq assembly version changes"
+        );
+
+        let current = current_assembly_snapshot(pool, data_source_id).await?;
+        let sequence_count = current.stats.sequence_count as i64;
+
+        // If no previous version, this is the initial release
+        let Some(prev_version_id) = previous_version_id else {
+            let mut entries = vec![ChangelogEntry::added(
+                "sequences",
+                sequence_count,
+                format!("Initial assembly {}", current.accession_version),
+            )];
+            if let Some(gene_count) = current.stats.gene_count {
+                entries.push(ChangelogEntry::added(
+                    "genes",
+                    gene_count as i64,
+                    "Initial annotation",
+                ));
+            }
+            let summary = ChangelogSummary::initial(sequence_count);
+            let summary_text = format!(
+                "Initial release of assembly {} with {} sequences",
+                current.accession_version, sequence_count
+            );
+            return Ok(VersionChangelog::new(BumpType::Minor, entries, summary, summary_text));
+        };
+
+        let previous = version_assembly_snapshot(pool, prev_version_id)
+            .await?
+            .unwrap_or_default();
+        let previous_count = previous.stats.sequence_count as i64;
+
+        let mut entries = Vec::new();
+        let mut modified = 0;
+        if current.accession_version != previous.accession_version {
+            entries.push(ChangelogEntry::modified(
+                "sequences",
+                sequence_count,
+                format!(
+                    "Assembly updated from {} to {}",
+                    previous.accession_version, current.accession_version
+                ),
+                true, // A new assembly version replaces the sequences
+            ));
+            modified = previous_count.min(sequence_count);
+        }
+        if current.annotation_date != previous.annotation_date
+            || current.stats.gene_count != previous.stats.gene_count
+            || current.stats.protein_count != previous.stats.protein_count
+        {
+            entries.push(ChangelogEntry::modified(
+                "annotations",
+                current.stats.gene_count.unwrap_or_default() as i64,
+                "Genome annotation updated",
+                false, // Re-annotation keeps the sequences
+            ));
+        }
+
+        let summary = ChangelogSummary::new(
+            previous_count,
+            sequence_count,
+            (sequence_count - previous_count).max(0),
+            (previous_count - sequence_count).max(0),
+            modified,
+            TriggerReason::NewRelease,
+        );
+
+        let bump_type = VersionChangelog::determine_bump_type(&entries);
+
+        let changelog = VersionChangelog::new(bump_type, entries, summary, "");
+        let summary_text = changelog.generate_summary_text();
+
+        Ok(VersionChangelog {
+            summary_text,
+            ..changelog
+        })
+    }
+}
+
+/// Structure version bump detector (PDB entries and AlphaFold DB models)
+///
+/// Compares the stored structure against the snapshot recorded with the
+/// previous version:
+/// - MAJOR if: the coordinates changed (a new major PDB revision or a new
+///   AlphaFold DB model version)
+/// - MINOR if: a minor PDB revision updated the metadata
+#[derive(Debug, Clone, Default)]
+pub struct StructureBumpDetector;
+
+impl StructureBumpDetector {
+    /// Create a new structure bump detector
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl VersionBumpDetector for StructureBumpDetector {
+    fn name(&self) -> &'static str {
+        "Structure"
+    }
+
+    fn default_strategy(&self) -> VersioningStrategy {
+        VersioningStrategy::pdb()
+    }
+
+    #[instrument(skip(self, pool), fields(detector = self.name()))]
+    async fn detect_changes(
+        &self,
+        pool: &PgPool,
+        data_source_id: Uuid,
+        previous_version_id: Option<Uuid>,
+    ) -> Result<VersionChangelog> {
+        info!(
+            data_source_id = %data_source_id,
+            previous_version_id = ?previous_version_id,
+            "Detecting structure version changes"
+        );
+
+        let current = current_structure_snapshot(pool, data_source_id).await?;
+        let revision = current.revision.as_deref().unwrap_or("unknown");
+
+        // If no previous version, this is the initial release
+        let Some(prev_version_id) = previous_version_id else {
+            let entries = vec![ChangelogEntry::added(
+                "structures",
+                1,
+                format!("Initial structure, revision {}", revision),
+            )];
+            let summary = ChangelogSummary::initial(1);
+            let summary_text = format!(
+                "Initial release of revision {} with {} chains",
+                revision, current.chain_count
+            );
+            return Ok(VersionChangelog::new(BumpType::Minor, entries, summary, summary_text));
+        };
+
+        let previous = version_structure_snapshot(pool, prev_version_id)
+            .await?
+            .unwrap_or_default();
+        let previous_revision = previous.revision.as_deref().unwrap_or("unknown");
+
+        let mut entries = Vec::new();
+        if current.major_revision() != previous.major_revision() {
+            entries.push(ChangelogEntry::modified(
+                "coordinates",
+                current.chain_count as i64,
+                format!(
+                    "Coordinates replaced in revision {} (was {})",
+                    revision, previous_revision
+                ),
+                true, // Coordinates of the previous revision no longer apply
+            ));
+        } else if current.revision != previous.revision {
+            entries.push(ChangelogEntry::modified(
+                "metadata",
+                1,
+                format!("Metadata updated in revision {} (was {})", revision, previous_revision),
+                false,
+            ));
+        }
+
+        let summary = ChangelogSummary::new(
+            1,
+            1,
+            0,
+            0,
+            if entries.is_empty() { 0 } else { 1 },
+            TriggerReason::NewRelease,
+        );
+
+        let bump_type = VersionChangelog::determine_bump_type(&entries);
+
+        let changelog = VersionChangelog::new(bump_type, entries, summary, "");
+        let summary_text = changelog.generate_summary_text();
+
+        Ok(VersionChangelog {
+            summary_text,
+            ..changelog
+        })
+    }
+}
+
 /// Get the appropriate detector for a data source type
 pub fn get_detector(source_type: &str) -> Option<Box<dyn VersionBumpDetector>> {
     match source_type {
         "protein" | "bundle" => Some(Box::new(UniProtBumpDetector::new())),
         "taxonomy" => Some(Box::new(NcbiTaxonomyBumpDetector::new())),
         "ontology" => Some(Box::new(GeneOntologyBumpDetector::new())),
-        "genome" | "sequence" => Some(Box::new(GenbankBumpDetector::new())),
+        "genome" => Some(Box::new(RefseqAssemblyBumpDetector::new())),
+        "sequence" => Some(Box::new(GenbankBumpDetector::new())),
+        "gene_model" => Some(Box::new(EnsemblBumpDetector::new())),
+        "structure" => Some(Box::new(StructureBumpDetector::new())),
         _ => {
             warn!(source_type = %source_type, "No version bump detector for source type");
             None
@@ -1054,6 +1487,9 @@ mod tests {
         assert!(get_detector("taxonomy").is_some());
         assert!(get_detector("ontology").is_some());
         assert!(get_detector("genome").is_some());
+        assert!(get_detector("sequence").is_some());
+        assert!(get_detector("gene_model").is_some());
+        assert!(get_detector("structure").is_some());
         assert!(get_detector("unknown").is_none());
     }
 
@@ -1063,5 +1499,8 @@ mod tests {
         assert_eq!(NcbiTaxonomyBumpDetector::new().name(), "NCBI Taxonomy");
         assert_eq!(GeneOntologyBumpDetector::new().name(), "Gene Ontology");
         assert_eq!(GenbankBumpDetector::new().name(), "GenBank/RefSeq");
+        assert_eq!(EnsemblBumpDetector::new().name(), "Ensembl");
+        assert_eq!(RefseqAssemblyBumpDetector::new().name(), "RefSeq Assembly");
+        assert_eq!(StructureBumpDetector::new().name(), "Structure");
     }
 }
@@ -28,20 +28,22 @@ mod types;
 
 // Re-export types
 pub use types::{
-    BumpType, CascadeResult, ChangeType, ChangelogEntry, ChangelogSummary, DataSourceDependent,
-    TriggerReason, TriggerType, VersionChangeType, VersionChangelog, VersionInfo, VersionTrigger,
-    VersioningStrategy,
+    BumpType, CascadePreview, CascadeResult, ChangeType, ChangelogEntry, ChangelogSummary,
+    DataSourceDependent, StrategyValidationError, TriggerReason, TriggerType, VersionChangeType,
+    VersionChangelog, VersionInfo, VersionTrigger, VersioningStrategy,
 };
 
 // Re-export detector trait and implementations
 pub use detector::{
-    get_detector, get_organization_versioning_strategy, GenbankBumpDetector,
-    GeneOntologyBumpDetector, NcbiTaxonomyBumpDetector, UniProtBumpDetector, VersionBumpDetector,
+    get_detector, get_organization_versioning_strategy, EnsemblBumpDetector, GenbankBumpDetector,
+    GeneOntologyBumpDetector, NcbiTaxonomyBumpDetector, RefseqAssemblyBumpDetector,
+    StructureBumpDetector, UniProtBumpDetector, VersionBumpDetector,
 };
 
 // Re-export cascade functions
 pub use cascade::{
-    cascade_recursive, cascade_version_bump, find_dependents, find_dependents_by_entry,
+    cascade_recursive, cascade_recursive_excluding, cascade_version_bump, find_dependents,
+    find_dependents_by_entry, preview_cascade,
 };
 
 // Re-export calculator functions and types
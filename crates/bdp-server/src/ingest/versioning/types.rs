@@ -119,31 +119,72 @@ impl VersioningStrategy {
     /// Checks if the change matches any major triggers first, then minor triggers,
     /// and falls back to the default bump type.
     pub fn determine_bump(&self, change_type: &ChangeType, category: &str) -> BumpType {
-        // Convert ChangeType to VersionChangeType for comparison
-        let version_change_type = match change_type {
-            ChangeType::Added => VersionChangeType::Added,
-            ChangeType::Removed => VersionChangeType::Removed,
-            ChangeType::Modified => VersionChangeType::Modified,
-            ChangeType::Schema => VersionChangeType::Schema,
-            ChangeType::Dependency => VersionChangeType::Dependency,
-        };
+        self.matching_trigger(change_type, category)
+            .map(|(bump, _)| bump)
+            .unwrap_or(self.default_bump)
+    }
 
-        // Check major triggers first
-        for trigger in &self.major_triggers {
-            if trigger.matches(&version_change_type, category) {
-                return BumpType::Major;
-            }
+    /// Find the trigger that decides the bump for a given change
+    ///
+    /// Major triggers take precedence over minor triggers. Returns `None` when
+    /// the change falls through to the default bump type.
+    pub fn matching_trigger(
+        &self,
+        change_type: &ChangeType,
+        category: &str,
+    ) -> Option<(BumpType, &VersionTrigger)> {
+        let version_change_type = VersionChangeType::from(*change_type);
+
+        if let Some(trigger) = self
+            .major_triggers
+            .iter()
+            .find(|t| t.matches(&version_change_type, category))
+        {
+            return Some((BumpType::Major, trigger));
         }
 
-        // Check minor triggers
-        for trigger in &self.minor_triggers {
-            if trigger.matches(&version_change_type, category) {
-                return BumpType::Minor;
+        self.minor_triggers
+            .iter()
+            .find(|t| t.matches(&version_change_type, category))
+            .map(|trigger| (BumpType::Minor, trigger))
+    }
+
+    /// Check that the strategy is internally consistent
+    ///
+    /// Every trigger needs a category (`*` matches all of them), may only be
+    /// listed once, and may not appear as both a major and a minor trigger since
+    /// the minor one would never apply.
+    pub fn validate(&self) -> Result<(), StrategyValidationError> {
+        let mut seen: Vec<(VersionChangeType, &str)> = Vec::new();
+
+        for trigger in self.major_triggers.iter().chain(&self.minor_triggers) {
+            if trigger.category.trim().is_empty() {
+                return Err(StrategyValidationError::EmptyCategory(trigger.change_type));
             }
+            if seen.contains(&(trigger.change_type, trigger.category.as_str())) {
+                let in_major = self.major_triggers.iter().any(|t| {
+                    t.change_type == trigger.change_type && t.category == trigger.category
+                });
+                let in_minor = self.minor_triggers.iter().any(|t| {
+                    t.change_type == trigger.change_type && t.category == trigger.category
+                });
+                let (change_type, category) = (trigger.change_type, trigger.category.clone());
+                return Err(if in_major && in_minor {
+                    StrategyValidationError::ConflictingTrigger {
+                        change_type,
+                        category,
+                    }
+                } else {
+                    StrategyValidationError::DuplicateTrigger {
+                        change_type,
+                        category,
+                    }
+                });
+            }
+            seen.push((trigger.change_type, trigger.category.as_str()));
         }
 
-        // Fall back to default
-        self.default_bump
+        Ok(())
     }
 
     /// Determine bump type from a list of changelog entries
@@ -298,6 +339,82 @@ impl VersioningStrategy {
             cascade_on_minor: true,
         }
     }
+
+    /// Create a PDB-specific versioning strategy
+    pub fn pdb() -> Self {
+        Self {
+            major_triggers: vec![
+                VersionTrigger {
+                    change_type: VersionChangeType::Removed,
+                    category: "structures".to_string(),
+                    description: "Structures withdrawn or obsoleted".to_string(),
+                },
+                VersionTrigger {
+                    change_type: VersionChangeType::Modified,
+                    category: "coordinates".to_string(),
+                    description: "Atomic coordinates corrected".to_string(),
+                },
+            ],
+            minor_triggers: vec![
+                VersionTrigger {
+                    change_type: VersionChangeType::Added,
+                    category: "structures".to_string(),
+                    description: "New structures released".to_string(),
+                },
+                VersionTrigger {
+                    change_type: VersionChangeType::Modified,
+                    category: "metadata".to_string(),
+                    description: "Structure metadata updated".to_string(),
+                },
+            ],
+            default_bump: BumpType::Minor,
+            cascade_on_major: true,
+            cascade_on_minor: false,
+        }
+    }
+
+    /// Create an Ensembl-specific versioning strategy
+    pub fn ensembl() -> Self {
+        Self {
+            major_triggers: vec![
+                VersionTrigger {
+                    change_type: VersionChangeType::Removed,
+                    category: "genes".to_string(),
+                    description: "Genes removed or deprecated".to_string(),
+                },
+                VersionTrigger {
+                    change_type: VersionChangeType::Removed,
+                    category: "transcripts".to_string(),
+                    description: "Transcripts removed or deprecated".to_string(),
+                },
+                VersionTrigger {
+                    change_type: VersionChangeType::Modified,
+                    category: "coordinates".to_string(),
+                    description: "Genomic coordinates changed".to_string(),
+                },
+            ],
+            minor_triggers: vec![
+                VersionTrigger {
+                    change_type: VersionChangeType::Added,
+                    category: "genes".to_string(),
+                    description: "New genes added".to_string(),
+                },
+                VersionTrigger {
+                    change_type: VersionChangeType::Added,
+                    category: "transcripts".to_string(),
+                    description: "New transcripts added".to_string(),
+                },
+                VersionTrigger {
+                    change_type: VersionChangeType::Modified,
+                    category: "annotations".to_string(),
+                    description: "Gene annotations updated".to_string(),
+                },
+            ],
+            default_bump: BumpType::Minor,
+            cascade_on_major: true,
+            cascade_on_minor: true,
+        }
+    }
 }
 
 /// A trigger condition for version bumps
@@ -367,6 +484,38 @@ impl std::fmt::Display for VersionChangeType {
     }
 }
 
+impl From<ChangeType> for VersionChangeType {
+    fn from(change_type: ChangeType) -> Self {
+        match change_type {
+            ChangeType::Added => VersionChangeType::Added,
+            ChangeType::Removed => VersionChangeType::Removed,
+            ChangeType::Modified => VersionChangeType::Modified,
+            ChangeType::Schema => VersionChangeType::Schema,
+            ChangeType::Dependency => VersionChangeType::Dependency,
+        }
+    }
+}
+
+/// Reasons a versioning strategy is rejected
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum StrategyValidationError {
+    #[error("Trigger for '{0}' changes has an empty category; use '*' to match every category")]
+    EmptyCategory(VersionChangeType),
+    #[error("Trigger '{change_type}' on '{category}' is listed more than once")]
+    DuplicateTrigger {
+        change_type: VersionChangeType,
+        category: String,
+    },
+    #[error(
+        "Trigger '{change_type}' on '{category}' is both a major and a minor trigger; \
+         the minor trigger would never apply"
+    )]
+    ConflictingTrigger {
+        change_type: VersionChangeType,
+        category: String,
+    },
+}
+
 // ============================================================================
 // Existing Types
 // ============================================================================
@@ -835,7 +984,7 @@ impl VersionInfo {
 }
 
 /// Result of a version cascade operation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CascadeResult {
     /// ID of the dependent data source entry
     pub entry_id: Uuid,
@@ -849,10 +998,118 @@ pub struct CascadeResult {
     pub changelog_id: Uuid,
 }
 
+/// A dependent that a version cascade would bump, computed without writing
+///
+/// Mirrors what `cascade_recursive` would create for the same source version:
+/// the new version number and the changelog saved with it. `dependents` holds
+/// the data sources that would in turn be bumped because of this one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CascadePreview {
+    /// ID of the dependent data source entry
+    pub entry_id: Uuid,
+    /// Slug of the dependent data source
+    pub entry_slug: String,
+    /// Name of the dependent data source
+    pub name: String,
+    /// Organization slug
+    pub organization_slug: String,
+    /// Version that would be superseded
+    pub current_version_id: Uuid,
+    /// Current version string
+    pub current_version: String,
+    /// Version string that would be created
+    pub new_version: String,
+    /// Type of bump the dependent would get
+    pub bump_type: BumpType,
+    /// Changelog that would be saved with the new version
+    ///
+    /// `triggered_by_version_id` is unset below the first level, where the
+    /// triggering version does not exist yet.
+    pub changelog: VersionChangelog,
+    /// Dependents bumped in turn by this one
+    pub dependents: Vec<CascadePreview>,
+}
+
+impl CascadePreview {
+    /// Number of versions this node and its dependents would create
+    pub fn total(&self) -> usize {
+        1 + self
+            .dependents
+            .iter()
+            .map(CascadePreview::total)
+            .sum::<usize>()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_strategy_validation() {
+        for strategy in [
+            VersioningStrategy::default(),
+            VersioningStrategy::uniprot(),
+            VersioningStrategy::ncbi_taxonomy(),
+            VersioningStrategy::gene_ontology(),
+            VersioningStrategy::genbank(),
+        ] {
+            assert_eq!(strategy.validate(), Ok(()));
+        }
+
+        let empty = VersioningStrategy::new(
+            vec![VersionTrigger::new(VersionChangeType::Removed, " ", "Removed")],
+            vec![],
+        );
+        assert_eq!(
+            empty.validate(),
+            Err(StrategyValidationError::EmptyCategory(VersionChangeType::Removed))
+        );
+
+        let duplicate = VersioningStrategy::new(
+            vec![],
+            vec![
+                VersionTrigger::new(VersionChangeType::Added, "terms", "Terms added"),
+                VersionTrigger::new(VersionChangeType::Added, "terms", "More terms"),
+            ],
+        );
+        assert!(matches!(
+            duplicate.validate(),
+            Err(StrategyValidationError::DuplicateTrigger { .. })
+        ));
+
+        let conflicting = VersioningStrategy::new(
+            vec![VersionTrigger::new(VersionChangeType::Modified, "terms", "Terms changed")],
+            vec![VersionTrigger::new(VersionChangeType::Modified, "terms", "Terms changed")],
+        );
+        assert!(matches!(
+            conflicting.validate(),
+            Err(StrategyValidationError::ConflictingTrigger { .. })
+        ));
+    }
+
+    #[test]
+    fn test_matching_trigger_prefers_major() {
+        let strategy = VersioningStrategy::new(
+            vec![VersionTrigger::new(VersionChangeType::Removed, "*", "Anything removed")],
+            vec![VersionTrigger::new(VersionChangeType::Added, "taxa", "Taxa added")],
+        );
+
+        let (bump, trigger) = strategy
+            .matching_trigger(&ChangeType::Removed, "taxa")
+            .unwrap();
+        assert_eq!(bump, BumpType::Major);
+        assert_eq!(trigger.category, "*");
+
+        let (bump, _) = strategy
+            .matching_trigger(&ChangeType::Added, "taxa")
+            .unwrap();
+        assert_eq!(bump, BumpType::Minor);
+        assert!(strategy
+            .matching_trigger(&ChangeType::Schema, "taxa")
+            .is_none());
+    }
+
     #[test]
     fn test_bump_type_display() {
         assert_eq!(BumpType::Major.to_string(), "major");
@@ -1021,6 +1278,23 @@ mod tests {
             .major_triggers
             .iter()
             .any(|t| t.category == "sequences"));
+
+        // Ensembl
+        let ensembl = VersioningStrategy::ensembl();
+        assert!(ensembl
+            .major_triggers
+            .iter()
+            .any(|t| t.category == "coordinates"));
+        assert!(ensembl
+            .minor_triggers
+            .iter()
+            .any(|t| t.category == "transcripts"));
+
+        // PDB
+        let pdb = VersioningStrategy::pdb();
+        assert_eq!(pdb.determine_bump(&ChangeType::Modified, "coordinates"), BumpType::Major);
+        assert_eq!(pdb.determine_bump(&ChangeType::Modified, "metadata"), BumpType::Minor);
+        assert!(!pdb.cascade_on_minor);
     }
 
     #[test]
@@ -91,6 +91,7 @@ pub mod error;
 pub mod features;
 pub mod ingest;
 pub mod middleware;
+pub mod mirror;
 pub mod models;
 pub mod storage;
 
@@ -0,0 +1,148 @@
+//! Administrator authentication
+//!
+//! Administrative operations (overriding sealed versions, managing ingestion
+//! jobs) require a bearer token matching the `BDP_ADMIN_TOKEN` environment
+//! variable. When the variable is unset, every admin request is refused.
+//!
+//! Handlers opt in by taking [`AdminAuth`] as an extractor, or
+//! `Option<AdminAuth>` when admin access only unlocks part of the behavior.
+
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header, request::Parts, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use crate::api::response::ErrorResponse;
+
+/// Environment variable holding the administrator token
+pub const ADMIN_TOKEN_ENV: &str = "BDP_ADMIN_TOKEN";
+
+/// Proof that the request carried a valid administrator token
+#[derive(Debug, Clone, Copy)]
+pub struct AdminAuth;
+
+/// Reasons an admin request was refused
+#[derive(Debug, thiserror::Error)]
+pub enum AdminAuthError {
+    #[error("Admin operations are disabled: {ADMIN_TOKEN_ENV} is not configured")]
+    NotConfigured,
+    #[error("Admin token required: send 'Authorization: Bearer <token>'")]
+    MissingToken,
+    #[error("Invalid admin token")]
+    InvalidToken,
+}
+
+impl AdminAuth {
+    /// Check a presented token against the configured one
+    pub fn verify(
+        presented: Option<&str>,
+        configured: Option<&str>,
+    ) -> Result<Self, AdminAuthError> {
+        let configured = configured
+            .filter(|t| !t.is_empty())
+            .ok_or(AdminAuthError::NotConfigured)?;
+        let presented = presented.ok_or(AdminAuthError::MissingToken)?;
+
+        if constant_time_eq(presented.as_bytes(), configured.as_bytes()) {
+            Ok(Self)
+        } else {
+            Err(AdminAuthError::InvalidToken)
+        }
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AdminAuth
+where
+    S: Send + Sync,
+{
+    type Rejection = AdminAuthError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let presented = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .map(str::trim);
+        let configured = std::env::var(ADMIN_TOKEN_ENV).ok();
+
+        Self::verify(presented, configured.as_deref()).inspect_err(|e| {
+            tracing::warn!(error = %e, path = %parts.uri.path(), "Admin request refused");
+        })
+    }
+}
+
+impl IntoResponse for AdminAuthError {
+    fn into_response(self) -> Response {
+        let (status, code) = match self {
+            AdminAuthError::MissingToken => (StatusCode::UNAUTHORIZED, "UNAUTHORIZED"),
+            AdminAuthError::NotConfigured | AdminAuthError::InvalidToken => {
+                (StatusCode::FORBIDDEN, "FORBIDDEN")
+            },
+        };
+        let error = ErrorResponse::new(code, self.to_string());
+        (status, Json(error)).into_response()
+    }
+}
+
+/// Compare two byte strings without short-circuiting on the first difference
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify() {
+        assert!(AdminAuth::verify(Some("secret"), Some("secret")).is_ok());
+        assert!(matches!(
+            AdminAuth::verify(Some("secret"), None),
+            Err(AdminAuthError::NotConfigured)
+        ));
+        assert!(matches!(
+            AdminAuth::verify(Some(""), Some("")),
+            Err(AdminAuthError::NotConfigured)
+        ));
+        assert!(matches!(
+            AdminAuth::verify(None, Some("secret")),
+            Err(AdminAuthError::MissingToken)
+        ));
+        assert!(matches!(
+            AdminAuth::verify(Some("secreT"), Some("secret")),
+            Err(AdminAuthError::InvalidToken)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_error_codes() {
+        for (error, status, code) in [
+            (AdminAuthError::MissingToken, StatusCode::UNAUTHORIZED, "UNAUTHORIZED"),
+            (AdminAuthError::InvalidToken, StatusCode::FORBIDDEN, "FORBIDDEN"),
+            (AdminAuthError::NotConfigured, StatusCode::FORBIDDEN, "FORBIDDEN"),
+        ] {
+            let response = error.into_response();
+            assert_eq!(response.status(), status);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(json["error"]["code"], code);
+        }
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"abc", b"abc"));
+        assert!(!constant_time_eq(b"abc", b"abd"));
+        assert!(!constant_time_eq(b"abc", b"abcd"));
+    }
+}
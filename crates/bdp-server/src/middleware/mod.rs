@@ -5,6 +5,7 @@
 //! - Request logging with tracing
 //! - Rate limiting
 //! - Audit logging
+//! - Administrator authentication
 
 use axum::http::{header, HeaderName, Method};
 use std::time::Duration;
@@ -25,6 +26,7 @@ use crate::config::CorsConfig;
 /// Can be configured via CORS_MAX_AGE_SECS environment variable.
 pub const DEFAULT_CORS_MAX_AGE_SECS: u64 = 3600;
 
+pub mod admin;
 pub mod audit;
 pub mod rate_limit;
 
@@ -0,0 +1,234 @@
+//! HTTP client for the upstream registry
+//!
+//! Talks to the public API of another BDP server and decodes its responses
+//! into the same types this server produces.
+
+use anyhow::{bail, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use uuid::Uuid;
+
+use crate::features::data_sources::queries::{
+    DataSourceListItem, DependencyItem, GetDataSourceResponse, GetVersionResponse,
+    ListDependenciesResponse,
+};
+use crate::features::organizations::GetOrganizationResponse;
+
+/// Page size used when walking paginated upstream listings
+const PAGE_SIZE: i64 = 100;
+
+/// Page size for dependency listings, which allow larger pages
+const DEPENDENCY_PAGE_SIZE: i64 = 1000;
+
+#[derive(Debug, Deserialize)]
+struct Envelope<T> {
+    data: T,
+    #[serde(default)]
+    meta: Option<serde_json::Value>,
+}
+
+/// Digests and size of a downloaded file, computed while it was written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadedFile {
+    pub sha256: String,
+    pub md5: String,
+    pub size: i64,
+}
+
+/// Client for an upstream BDP API
+#[derive(Debug, Clone)]
+pub struct UpstreamClient {
+    base_url: String,
+    http: reqwest::Client,
+}
+
+impl UpstreamClient {
+    /// Create a client for the registry at `base_url` (e.g. `https://bdp.example.org`)
+    pub fn new(base_url: impl Into<String>) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(600))
+            .user_agent(concat!("bdp-mirror/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .context("Failed to build HTTP client")?;
+
+        Ok(Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            http,
+        })
+    }
+
+    /// Base URL of the upstream registry
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub async fn get_organization(&self, slug: &str) -> Result<GetOrganizationResponse> {
+        self.get_json(&format!("/api/v1/organizations/{}", slug))
+            .await
+            .map(|e| e.data)
+    }
+
+    /// All data sources of an organization
+    pub async fn list_data_sources(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Vec<DataSourceListItem>> {
+        let mut items = Vec::new();
+        let mut page = 1;
+
+        loop {
+            let envelope: Envelope<Vec<DataSourceListItem>> = self
+                .get_json(&format!(
+                    "/api/v1/data-sources?organization_id={}&page={}&per_page={}",
+                    organization_id, page, PAGE_SIZE
+                ))
+                .await?;
+
+            let has_next = envelope
+                .meta
+                .as_ref()
+                .and_then(|m| m.pointer("/pagination/has_next"))
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+
+            items.extend(envelope.data);
+            if !has_next {
+                return Ok(items);
+            }
+            page += 1;
+        }
+    }
+
+    pub async fn get_data_source(&self, org: &str, slug: &str) -> Result<GetDataSourceResponse> {
+        self.get_json(&format!("/api/v1/data-sources/{}/{}", org, slug))
+            .await
+            .map(|e| e.data)
+    }
+
+    pub async fn get_version(
+        &self,
+        org: &str,
+        slug: &str,
+        version: &str,
+    ) -> Result<GetVersionResponse> {
+        self.get_json(&format!("/api/v1/data-sources/{}/{}/{}", org, slug, version))
+            .await
+            .map(|e| e.data)
+    }
+
+    /// All dependencies of a version
+    pub async fn list_dependencies(
+        &self,
+        org: &str,
+        slug: &str,
+        version: &str,
+    ) -> Result<Vec<DependencyItem>> {
+        let mut items = Vec::new();
+        let mut page = 1;
+
+        loop {
+            let response: ListDependenciesResponse = self
+                .get_json(&format!(
+                    "/api/v1/data-sources/{}/{}/{}/dependencies?page={}&per_page={}",
+                    org, slug, version, page, DEPENDENCY_PAGE_SIZE
+                ))
+                .await?
+                .data;
+
+            items.extend(response.dependencies);
+            if !response.pagination.has_next {
+                return Ok(items);
+            }
+            page += 1;
+        }
+    }
+
+    /// Download one file of a version to `dest`, hashing it on the way
+    pub async fn download_file(
+        &self,
+        org: &str,
+        slug: &str,
+        version: &str,
+        format: &str,
+        dest: &Path,
+    ) -> Result<DownloadedFile> {
+        let url = format!(
+            "{}/api/v1/data-sources/{}/{}/{}/download?format={}",
+            self.base_url, org, slug, version, format
+        );
+        let mut response = self
+            .http
+            .get(&url)
+            .send()
+            .await
+            .with_context(|| format!("Failed to request {}", url))?;
+
+        if !response.status().is_success() {
+            bail!("Upstream returned {} for {}", response.status(), url);
+        }
+
+        let mut file = tokio::fs::File::create(dest)
+            .await
+            .with_context(|| format!("Failed to create {}", dest.display()))?;
+        let mut sha256 = Sha256::new();
+        let mut md5 = md5::Context::new();
+        let mut size = 0;
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .with_context(|| format!("Failed to read {}", url))?
+        {
+            sha256.update(&chunk);
+            md5.consume(&chunk);
+            size += chunk.len() as i64;
+            file.write_all(&chunk).await?;
+        }
+        file.flush().await?;
+
+        Ok(DownloadedFile {
+            sha256: format!("{:x}", sha256.finalize()),
+            md5: format!("{:x}", md5.compute()),
+            size,
+        })
+    }
+
+    async fn get_json<T: DeserializeOwned>(&self, path: &str) -> Result<Envelope<T>> {
+        let url = format!("{}{}", self.base_url, path);
+        let response = self
+            .http
+            .get(&url)
+            .send()
+            .await
+            .with_context(|| format!("Failed to request {}", url))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            let message = body
+                .pointer("/error/message")
+                .and_then(|m| m.as_str())
+                .unwrap_or("no error message");
+            bail!("Upstream returned {} for {}: {}", status, url, message);
+        }
+
+        response
+            .json()
+            .await
+            .with_context(|| format!("Unexpected response from {}", url))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base_url_is_normalized() {
+        let client = UpstreamClient::new("https://bdp.example.org/").unwrap();
+        assert_eq!(client.base_url(), "https://bdp.example.org");
+    }
+}
@@ -0,0 +1,25 @@
+//! Registry mirroring
+//!
+//! Syncs a subset of a remote BDP registry into this server so institutions
+//! can run an internal copy behind a firewall. A mirror run takes selectors
+//! (`org`, `org:slug`, `org:slug@range`), walks the matching organizations,
+//! data sources and versions through the upstream public API, verifies every
+//! downloaded file against its published digests and writes it to local
+//! storage under the same key.
+//!
+//! Runs are incremental: versions already present with matching files are
+//! not transferred again. Each run is recorded in `mirror_runs`, and
+//! `mirrored_versions` links every mirrored version to the run and upstream
+//! that last synced it.
+//!
+//! Type-specific metadata (protein, taxonomy, GO and InterPro tables) is not
+//! mirrored; mirrored versions carry their registered files, citations and
+//! dependency links.
+
+pub mod client;
+pub mod selector;
+pub mod sync;
+
+pub use client::UpstreamClient;
+pub use selector::{MirrorSelector, SelectorError, VersionRange};
+pub use sync::{Mirror, MirrorReport};
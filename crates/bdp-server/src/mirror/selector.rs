@@ -0,0 +1,270 @@
+//! Mirror selectors
+//!
+//! A selector names what to mirror: `org`, `org:slug` or `org:slug@range`.
+//! A range is `*` (the default), `latest`, an exact version, or comparisons
+//! joined by commas such as `>=1.0,<2.0`.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// What to mirror from one organization
+///
+/// Without a data source the range applies to every data source of the
+/// organization.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MirrorSelector {
+    pub organization: String,
+    /// A single data source, or every data source of the organization
+    pub data_source: Option<String>,
+    pub range: VersionRange,
+}
+
+/// Which versions of a data source to mirror
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionRange {
+    Any,
+    Latest,
+    Constraints(Vec<Constraint>),
+}
+
+/// A single version comparison, e.g. `>=1.0`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Constraint {
+    pub op: Op,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Eq,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SelectorError {
+    #[error("Selector cannot be empty")]
+    Empty,
+    #[error("Invalid selector '{0}': expected org[:slug][@range]")]
+    Invalid(String),
+    #[error("Invalid version range '{0}'")]
+    InvalidRange(String),
+}
+
+impl FromStr for MirrorSelector {
+    type Err = SelectorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(SelectorError::Empty);
+        }
+
+        let (target, range) = match s.split_once('@') {
+            Some((target, range)) => (target, range.parse()?),
+            None => (s, VersionRange::Any),
+        };
+
+        let (organization, data_source) = match target.split_once(':') {
+            Some((org, slug)) => (org, Some(slug)),
+            None => (target, None),
+        };
+
+        if organization.is_empty() || data_source.is_some_and(str::is_empty) {
+            return Err(SelectorError::Invalid(s.to_string()));
+        }
+
+        Ok(Self {
+            organization: organization.to_string(),
+            data_source: data_source.map(str::to_string),
+            range,
+        })
+    }
+}
+
+impl FromStr for VersionRange {
+    type Err = SelectorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        match s {
+            "" => return Err(SelectorError::InvalidRange(s.to_string())),
+            "*" => return Ok(Self::Any),
+            "latest" => return Ok(Self::Latest),
+            _ => {},
+        }
+
+        let constraints = s
+            .split(',')
+            .map(|part| {
+                let part = part.trim();
+                let (op, version) =
+                    [(">=", Op::Ge), ("<=", Op::Le), (">", Op::Gt), ("<", Op::Lt), ("=", Op::Eq)]
+                        .iter()
+                        .find_map(|(prefix, op)| part.strip_prefix(prefix).map(|v| (*op, v)))
+                        .unwrap_or((Op::Eq, part));
+                let version = version.trim();
+                if version.is_empty() {
+                    return Err(SelectorError::InvalidRange(s.to_string()));
+                }
+                Ok(Constraint {
+                    op,
+                    version: version.to_string(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self::Constraints(constraints))
+    }
+}
+
+impl fmt::Display for MirrorSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.organization)?;
+        if let Some(data_source) = &self.data_source {
+            write!(f, ":{}", data_source)?;
+        }
+        write!(f, "@{}", self.range)
+    }
+}
+
+impl fmt::Display for VersionRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Any => write!(f, "*"),
+            Self::Latest => write!(f, "latest"),
+            Self::Constraints(constraints) => {
+                let parts: Vec<String> = constraints
+                    .iter()
+                    .map(|c| {
+                        let op = match c.op {
+                            Op::Eq => "=",
+                            Op::Gt => ">",
+                            Op::Ge => ">=",
+                            Op::Lt => "<",
+                            Op::Le => "<=",
+                        };
+                        format!("{}{}", op, c.version)
+                    })
+                    .collect();
+                write!(f, "{}", parts.join(","))
+            },
+        }
+    }
+}
+
+impl VersionRange {
+    /// Whether `version` falls in the range; `latest` is the upstream latest version
+    pub fn matches(&self, version: &str, latest: Option<&str>) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Latest => latest == Some(version),
+            Self::Constraints(constraints) => constraints.iter().all(|c| {
+                let ord = compare_versions(version, &c.version);
+                match c.op {
+                    Op::Eq => ord == Ordering::Equal,
+                    Op::Gt => ord == Ordering::Greater,
+                    Op::Ge => ord != Ordering::Less,
+                    Op::Lt => ord == Ordering::Less,
+                    Op::Le => ord != Ordering::Greater,
+                }
+            }),
+        }
+    }
+}
+
+/// Compare version strings segment by segment
+///
+/// Versions are split on `.`, `_` and `-`; numeric segments compare as
+/// numbers and everything else lexically, so `1.10 > 1.9` and
+/// `2025_01 < 2025_02`.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    const SEPARATORS: [char; 3] = ['.', '_', '-'];
+    let a: Vec<&str> = a.split(SEPARATORS).collect();
+    let b: Vec<&str> = b.split(SEPARATORS).collect();
+
+    for (x, y) in a.iter().zip(&b) {
+        let ord = match (x.parse::<u64>(), y.parse::<u64>()) {
+            (Ok(x), Ok(y)) => x.cmp(&y),
+            _ => x.cmp(y),
+        };
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+
+    a.len().cmp(&b.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_selector() {
+        let selector: MirrorSelector = "uniprot".parse().unwrap();
+        assert_eq!(selector.organization, "uniprot");
+        assert_eq!(selector.data_source, None);
+        assert_eq!(selector.range, VersionRange::Any);
+
+        let selector: MirrorSelector = "ncbi:taxonomy@>=2024.01,<2025".parse().unwrap();
+        assert_eq!(selector.data_source.as_deref(), Some("taxonomy"));
+        assert_eq!(
+            selector.range,
+            VersionRange::Constraints(vec![
+                Constraint {
+                    op: Op::Ge,
+                    version: "2024.01".to_string()
+                },
+                Constraint {
+                    op: Op::Lt,
+                    version: "2025".to_string()
+                },
+            ])
+        );
+
+        let selector: MirrorSelector = "uniprot@latest".parse().unwrap();
+        assert_eq!(selector.range, VersionRange::Latest);
+
+        assert!("".parse::<MirrorSelector>().is_err());
+        assert!(":taxonomy".parse::<MirrorSelector>().is_err());
+        assert!("ncbi:".parse::<MirrorSelector>().is_err());
+        assert!("ncbi:taxonomy@".parse::<MirrorSelector>().is_err());
+    }
+
+    #[test]
+    fn test_selector_round_trip() {
+        for s in ["uniprot@*", "ncbi:taxonomy@latest", "ncbi:taxonomy@>=2024.01,<2025"] {
+            let selector: MirrorSelector = s.parse().unwrap();
+            assert_eq!(selector.to_string(), s);
+        }
+    }
+
+    #[test]
+    fn test_range_matches() {
+        let range: VersionRange = ">=1.2,<2.0".parse().unwrap();
+        assert!(range.matches("1.2", None));
+        assert!(range.matches("1.10", None));
+        assert!(!range.matches("1.1", None));
+        assert!(!range.matches("2.0", None));
+
+        let exact: VersionRange = "1.0".parse().unwrap();
+        assert!(exact.matches("1.0", None));
+        assert!(!exact.matches("1.0.1", None));
+
+        assert!(VersionRange::Latest.matches("3.0", Some("3.0")));
+        assert!(!VersionRange::Latest.matches("2.0", Some("3.0")));
+        assert!(VersionRange::Any.matches("anything", None));
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("1.10", "1.9"), Ordering::Greater);
+        assert_eq!(compare_versions("2025_01", "2025_02"), Ordering::Less);
+        assert_eq!(compare_versions("1.0", "1.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.0", "1.0.1"), Ordering::Less);
+    }
+}
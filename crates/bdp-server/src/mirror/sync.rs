@@ -0,0 +1,756 @@
+//! Mirror runner
+//!
+//! Copies the selected part of an upstream registry into the local database
+//! and storage. Versions are immutable once published, so a version whose
+//! local files already match upstream is skipped; re-running the mirror only
+//! transfers what is new. Yank state, citations and dependency links are
+//! refreshed on every run.
+
+use anyhow::{bail, Context, Result};
+use aws_sdk_s3::primitives::ByteStream;
+use serde::Serialize;
+use sqlx::PgPool;
+use std::path::Path;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::client::{DownloadedFile, UpstreamClient};
+use super::selector::MirrorSelector;
+use crate::features::data_sources::queries::{
+    FileInfo, GetDataSourceResponse, GetVersionResponse, VersionInfo,
+};
+use crate::features::organizations::GetOrganizationResponse;
+use crate::features::version_files::checksums;
+use crate::storage::Storage;
+
+/// Totals for one mirror run
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MirrorReport {
+    pub run_id: Uuid,
+    pub organizations: usize,
+    pub data_sources: usize,
+    pub versions_mirrored: usize,
+    pub versions_unchanged: usize,
+    pub files_mirrored: usize,
+    pub bytes_mirrored: i64,
+    pub dependencies_linked: usize,
+    /// Upstream dependencies whose target is not present locally
+    pub dependencies_missing: usize,
+}
+
+/// A local file of a version, as compared against upstream
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LocalFile {
+    format: String,
+    checksum: String,
+}
+
+/// Mirrors selected organizations and data sources from an upstream registry
+pub struct Mirror {
+    pool: PgPool,
+    storage: Storage,
+    client: UpstreamClient,
+}
+
+impl Mirror {
+    pub fn new(pool: PgPool, storage: Storage, client: UpstreamClient) -> Self {
+        Self {
+            pool,
+            storage,
+            client,
+        }
+    }
+
+    /// Mirror everything matched by `selectors`, recording the run
+    pub async fn run(&self, selectors: &[MirrorSelector]) -> Result<MirrorReport> {
+        let selector_strings: Vec<String> = selectors.iter().map(ToString::to_string).collect();
+
+        let run_id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO mirror_runs (upstream_url, selectors)
+            VALUES ($1, $2)
+            RETURNING id
+            "#,
+            self.client.base_url(),
+            serde_json::json!(selector_strings)
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to record mirror run")?;
+
+        let mut report = MirrorReport {
+            run_id,
+            ..Default::default()
+        };
+
+        info!(run_id = %run_id, upstream = self.client.base_url(), "Starting mirror run");
+
+        let mut result = Ok(());
+        for selector in selectors {
+            result = self.sync_selector(selector, &mut report).await;
+            if result.is_err() {
+                break;
+            }
+        }
+
+        let (status, error) = match &result {
+            Ok(()) => ("completed", None),
+            Err(e) => ("failed", Some(format!("{:#}", e))),
+        };
+
+        sqlx::query!(
+            r#"
+            UPDATE mirror_runs
+            SET status = $2, stats = $3, error = $4, finished_at = NOW()
+            WHERE id = $1
+            "#,
+            run_id,
+            status,
+            serde_json::to_value(&report)?,
+            error
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to record mirror run result")?;
+
+        result.map(|()| report)
+    }
+
+    async fn sync_selector(
+        &self,
+        selector: &MirrorSelector,
+        report: &mut MirrorReport,
+    ) -> Result<()> {
+        let organization = self
+            .client
+            .get_organization(&selector.organization)
+            .await
+            .with_context(|| format!("Failed to fetch organization '{}'", selector.organization))?;
+
+        let organization_id = self.upsert_organization(&organization).await?;
+        report.organizations += 1;
+
+        let slugs = match &selector.data_source {
+            Some(slug) => vec![slug.clone()],
+            None => self
+                .client
+                .list_data_sources(organization.id)
+                .await?
+                .into_iter()
+                .map(|ds| ds.slug)
+                .collect(),
+        };
+
+        for slug in slugs {
+            let data_source = self
+                .client
+                .get_data_source(&organization.slug, &slug)
+                .await
+                .with_context(|| {
+                    format!("Failed to fetch data source '{}:{}'", organization.slug, slug)
+                })?;
+
+            let entry_id = self
+                .upsert_data_source(organization_id, &data_source)
+                .await?;
+            report.data_sources += 1;
+
+            let latest = data_source.latest_version.as_deref();
+            for version in data_source
+                .versions
+                .iter()
+                .filter(|v| selector.range.matches(&v.version, latest))
+            {
+                self.sync_version(&data_source, entry_id, version, report)
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "Failed to mirror '{}:{}@{}'",
+                            organization.slug, data_source.slug, version.version
+                        )
+                    })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn upsert_organization(&self, organization: &GetOrganizationResponse) -> Result<Uuid> {
+        let id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO organizations (
+                slug, name, website, description, logo_url, is_system,
+                license, license_url, citation, citation_url,
+                version_strategy, version_description, data_source_url,
+                documentation_url, contact_email, versioning_rules
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
+            ON CONFLICT (slug) DO UPDATE SET
+                name = EXCLUDED.name,
+                website = EXCLUDED.website,
+                description = EXCLUDED.description,
+                logo_url = EXCLUDED.logo_url,
+                license = EXCLUDED.license,
+                license_url = EXCLUDED.license_url,
+                citation = EXCLUDED.citation,
+                citation_url = EXCLUDED.citation_url,
+                version_strategy = EXCLUDED.version_strategy,
+                version_description = EXCLUDED.version_description,
+                data_source_url = EXCLUDED.data_source_url,
+                documentation_url = EXCLUDED.documentation_url,
+                contact_email = EXCLUDED.contact_email,
+                versioning_rules = EXCLUDED.versioning_rules
+            RETURNING id
+            "#,
+            organization.slug,
+            organization.name,
+            organization.website,
+            organization.description,
+            organization.logo_url,
+            organization.is_system,
+            organization.license,
+            organization.license_url,
+            organization.citation,
+            organization.citation_url,
+            organization.version_strategy,
+            organization.version_description,
+            organization.data_source_url,
+            organization.documentation_url,
+            organization.contact_email,
+            organization.versioning_rules
+        )
+        .fetch_one(&self.pool)
+        .await
+        .with_context(|| format!("Failed to upsert organization '{}'", organization.slug))?;
+
+        Ok(id)
+    }
+
+    /// Create or update the local entry of a data source
+    ///
+    /// Entry slugs are unique across organizations, so a local entry with the
+    /// same slug under another organization is a conflict.
+    async fn upsert_data_source(
+        &self,
+        organization_id: Uuid,
+        data_source: &GetDataSourceResponse,
+    ) -> Result<Uuid> {
+        let mut tx = self.pool.begin().await?;
+
+        let existing = sqlx::query!(
+            "SELECT id, organization_id FROM registry_entries WHERE slug = $1",
+            data_source.slug
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let entry_id = match existing {
+            Some(entry) if entry.organization_id != organization_id => {
+                bail!("Local entry '{}' belongs to a different organization", data_source.slug)
+            },
+            Some(entry) => {
+                sqlx::query!(
+                    "UPDATE registry_entries SET name = $2, description = $3 WHERE id = $1",
+                    entry.id,
+                    data_source.name,
+                    data_source.description
+                )
+                .execute(&mut *tx)
+                .await?;
+                entry.id
+            },
+            None => {
+                let id = sqlx::query_scalar!(
+                    r#"
+                    INSERT INTO registry_entries (organization_id, slug, name, description, entry_type)
+                    VALUES ($1, $2, $3, $4, 'data_source')
+                    RETURNING id
+                    "#,
+                    organization_id,
+                    data_source.slug,
+                    data_source.name,
+                    data_source.description
+                )
+                .fetch_one(&mut *tx)
+                .await?;
+
+                sqlx::query!(
+                    "INSERT INTO data_sources (id, source_type, external_id) VALUES ($1, $2, $3)",
+                    id,
+                    data_source.source_type,
+                    data_source.external_id
+                )
+                .execute(&mut *tx)
+                .await?;
+                id
+            },
+        };
+
+        tx.commit().await?;
+        Ok(entry_id)
+    }
+
+    async fn sync_version(
+        &self,
+        data_source: &GetDataSourceResponse,
+        entry_id: Uuid,
+        info: &VersionInfo,
+        report: &mut MirrorReport,
+    ) -> Result<()> {
+        let org = &data_source.organization.slug;
+        let upstream = self
+            .client
+            .get_version(org, &data_source.slug, &info.version)
+            .await?;
+
+        let local_version_id = sqlx::query_scalar!(
+            "SELECT id FROM versions WHERE entry_id = $1 AND version = $2",
+            entry_id,
+            upstream.version
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let local_files = match local_version_id {
+            Some(id) => {
+                sqlx::query_as!(
+                    LocalFile,
+                    "SELECT format, checksum FROM version_files WHERE version_id = $1",
+                    id
+                )
+                .fetch_all(&self.pool)
+                .await?
+            },
+            None => Vec::new(),
+        };
+
+        let pending = pending_files(&upstream.files, &local_files)?;
+
+        let version_id = match local_version_id {
+            Some(id) if pending.is_empty() => {
+                report.versions_unchanged += 1;
+                id
+            },
+            _ => {
+                let (version_id, bytes) = self
+                    .copy_version(entry_id, &upstream, &pending, report)
+                    .await?;
+                info!(
+                    data_source = %data_source.slug,
+                    version = %upstream.version,
+                    files = pending.len(),
+                    bytes,
+                    "Mirrored version"
+                );
+                version_id
+            },
+        };
+
+        sqlx::query!(
+            r#"
+            UPDATE versions
+            SET yanked_at = $2, yanked_reason = $3
+            WHERE id = $1 AND yanked_at IS DISTINCT FROM $2
+            "#,
+            version_id,
+            upstream.yanked_at,
+            upstream.yanked_reason
+        )
+        .execute(&self.pool)
+        .await?;
+
+        self.sync_dependencies(data_source, &upstream, version_id, report)
+            .await
+    }
+
+    /// Download, verify and register the pending files of a version
+    async fn copy_version(
+        &self,
+        entry_id: Uuid,
+        upstream: &GetVersionResponse,
+        pending: &[&FileInfo],
+        report: &mut MirrorReport,
+    ) -> Result<(Uuid, i64)> {
+        let mut verified = Vec::with_capacity(pending.len());
+        for file in pending {
+            let spool = std::env::temp_dir().join(format!("bdp-mirror-{}", Uuid::new_v4()));
+            let copied = self.copy_file(upstream, file, &spool).await;
+            if let Err(e) = tokio::fs::remove_file(&spool).await {
+                warn!(path = %spool.display(), error = %e, "Failed to remove spooled file");
+            }
+            verified.push((file, copied?));
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        let version_id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO versions (entry_id, version, external_version, release_date, size_bytes, published_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (entry_id, version) DO UPDATE SET updated_at = NOW()
+            RETURNING id
+            "#,
+            entry_id,
+            upstream.version,
+            upstream.external_version,
+            upstream.release_date,
+            upstream.size_bytes,
+            upstream.published_at
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let mut bytes = 0;
+        for (file, downloaded) in &verified {
+            sqlx::query!(
+                r#"
+                INSERT INTO version_files (version_id, format, s3_key, checksum, size_bytes, compression, sha256, md5)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                "#,
+                version_id,
+                file.format,
+                file.s3_key,
+                file.checksum,
+                downloaded.size,
+                file.compression,
+                downloaded.sha256,
+                downloaded.md5
+            )
+            .execute(&mut *tx)
+            .await?;
+            bytes += downloaded.size;
+        }
+
+        let has_citations = sqlx::query_scalar!(
+            r#"SELECT EXISTS(SELECT 1 FROM citations WHERE version_id = $1) as "exists!""#,
+            version_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if !has_citations {
+            for citation in &upstream.citations {
+                sqlx::query!(
+                    r#"
+                    INSERT INTO citations (
+                        version_id, citation_type, doi, pubmed_id, title, journal,
+                        publication_date, authors
+                    )
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                    "#,
+                    version_id,
+                    citation.citation_type,
+                    citation.doi,
+                    citation.pubmed_id,
+                    citation.title,
+                    citation.journal,
+                    citation.publication_date,
+                    citation.authors
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+
+        if !verified.is_empty() {
+            checksums::store_manifest(&mut tx, version_id).await?;
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO mirrored_versions (
+                version_id, run_id, upstream_url, upstream_version_id, files_mirrored, bytes_mirrored
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (version_id) DO UPDATE SET
+                run_id = EXCLUDED.run_id,
+                upstream_url = EXCLUDED.upstream_url,
+                upstream_version_id = EXCLUDED.upstream_version_id,
+                files_mirrored = mirrored_versions.files_mirrored + EXCLUDED.files_mirrored,
+                bytes_mirrored = mirrored_versions.bytes_mirrored + EXCLUDED.bytes_mirrored,
+                mirrored_at = NOW()
+            "#,
+            version_id,
+            report.run_id,
+            self.client.base_url(),
+            upstream.id,
+            verified.len() as i32,
+            bytes
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        report.versions_mirrored += 1;
+        report.files_mirrored += verified.len();
+        report.bytes_mirrored += bytes;
+
+        Ok((version_id, bytes))
+    }
+
+    /// Spool one upstream file to `spool`, verify it and stream it to storage
+    async fn copy_file(
+        &self,
+        upstream: &GetVersionResponse,
+        file: &FileInfo,
+        spool: &Path,
+    ) -> Result<DownloadedFile> {
+        let downloaded = self
+            .client
+            .download_file(
+                &upstream.organization_slug,
+                &upstream.data_source_slug,
+                &upstream.version,
+                &file.format,
+                spool,
+            )
+            .await?;
+        verify_download(file, &downloaded)?;
+
+        let stream = ByteStream::from_path(spool)
+            .await
+            .context("Failed to read spooled file")?;
+        self.storage
+            .upload_stream(&file.s3_key, stream, None, Some(downloaded.size))
+            .await
+            .with_context(|| format!("Failed to store '{}'", file.s3_key))?;
+
+        Ok(downloaded)
+    }
+
+    /// Link the dependencies of a version to local entries
+    ///
+    /// Dependencies on entries that have not been mirrored are skipped with a
+    /// warning; a later run picks them up once their targets exist.
+    async fn sync_dependencies(
+        &self,
+        data_source: &GetDataSourceResponse,
+        upstream: &GetVersionResponse,
+        version_id: Uuid,
+        report: &mut MirrorReport,
+    ) -> Result<()> {
+        if !upstream.has_dependencies {
+            return Ok(());
+        }
+
+        let dependencies = self
+            .client
+            .list_dependencies(&data_source.organization.slug, &data_source.slug, &upstream.version)
+            .await?;
+
+        let mut tx = self.pool.begin().await?;
+
+        for dependency in &dependencies {
+            let target = sqlx::query!(
+                r#"
+                SELECT re.id as entry_id, v.id as "version_id?"
+                FROM registry_entries re
+                JOIN organizations o ON re.organization_id = o.id
+                LEFT JOIN versions v ON v.entry_id = re.id AND v.version = $3
+                WHERE LOWER(o.slug) = LOWER($1) AND LOWER(re.slug) = LOWER($2)
+                "#,
+                dependency.organization_slug,
+                dependency.entry_slug,
+                dependency.required_version
+            )
+            .fetch_optional(&mut *tx)
+            .await?;
+
+            let Some(target) = target else {
+                warn!(
+                    version = %upstream.version,
+                    dependency = %format!("{}:{}@{}", dependency.organization_slug, dependency.entry_slug, dependency.required_version),
+                    "Dependency target not mirrored, skipping"
+                );
+                report.dependencies_missing += 1;
+                continue;
+            };
+
+            sqlx::query!(
+                r#"
+                INSERT INTO dependencies (
+                    version_id, depends_on_entry_id, depends_on_version,
+                    dependency_version_id, dependency_type
+                )
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (version_id, depends_on_entry_id) DO UPDATE SET
+                    depends_on_version = EXCLUDED.depends_on_version,
+                    dependency_version_id = EXCLUDED.dependency_version_id,
+                    dependency_type = EXCLUDED.dependency_type
+                "#,
+                version_id,
+                target.entry_id,
+                dependency.required_version,
+                target.version_id,
+                dependency.dependency_type
+            )
+            .execute(&mut *tx)
+            .await?;
+            report.dependencies_linked += 1;
+        }
+
+        sqlx::query!(
+            r#"
+            UPDATE versions
+            SET dependency_count = (SELECT COUNT(*) FROM dependencies WHERE version_id = $1)
+            WHERE id = $1
+            "#,
+            version_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+/// Upstream files that still need to be copied
+///
+/// A local file with the same format but a different checksum cannot be
+/// replaced, since published versions are immutable; that is an error.
+fn pending_files<'a>(upstream: &'a [FileInfo], local: &[LocalFile]) -> Result<Vec<&'a FileInfo>> {
+    let mut pending = Vec::new();
+
+    for file in upstream {
+        match local.iter().find(|l| l.format == file.format) {
+            Some(l) if l.checksum.eq_ignore_ascii_case(&file.checksum) => {},
+            Some(l) => bail!(
+                "Local '{}' file has checksum {} but upstream has {}",
+                file.format,
+                l.checksum,
+                file.checksum
+            ),
+            None => pending.push(file),
+        }
+    }
+
+    Ok(pending)
+}
+
+/// Check a download against the digests published upstream
+fn verify_download(file: &FileInfo, downloaded: &DownloadedFile) -> Result<()> {
+    let expected_sha256 = file
+        .sha256
+        .clone()
+        .or_else(|| checksums::is_sha256(&file.checksum).then(|| file.checksum.clone()));
+    let expected_md5 = file
+        .md5
+        .clone()
+        .or_else(|| checksums::is_md5(&file.checksum).then(|| file.checksum.clone()));
+
+    if expected_sha256.is_none() && expected_md5.is_none() {
+        bail!("Upstream '{}' file has no verifiable digest", file.format);
+    }
+    if let Some(expected) = expected_sha256 {
+        if !expected.eq_ignore_ascii_case(&downloaded.sha256) {
+            bail!(
+                "SHA-256 mismatch for '{}': expected {}, got {}",
+                file.format,
+                expected,
+                downloaded.sha256
+            );
+        }
+    }
+    if let Some(expected) = expected_md5 {
+        if !expected.eq_ignore_ascii_case(&downloaded.md5) {
+            bail!(
+                "MD5 mismatch for '{}': expected {}, got {}",
+                file.format,
+                expected,
+                downloaded.md5
+            );
+        }
+    }
+    if downloaded.size != file.size_bytes {
+        bail!(
+            "Size mismatch for '{}': expected {} bytes, got {}",
+            file.format,
+            file.size_bytes,
+            downloaded.size
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::framework::{compute_md5, compute_sha256};
+
+    fn file(format: &str, content: &[u8]) -> FileInfo {
+        FileInfo {
+            id: Uuid::new_v4(),
+            format: format.to_string(),
+            s3_key: format!("data-sources/my-lab/proteome/1.0/proteome.{}", format),
+            checksum: compute_sha256(content),
+            size_bytes: content.len() as i64,
+            compression: None,
+            sha256: Some(compute_sha256(content)),
+            md5: None,
+        }
+    }
+
+    fn downloaded(content: &[u8]) -> DownloadedFile {
+        DownloadedFile {
+            sha256: compute_sha256(content),
+            md5: compute_md5(content),
+            size: content.len() as i64,
+        }
+    }
+
+    #[test]
+    fn test_verify_download() {
+        let content = b">sp|P01308|INS_HUMAN\nMALWMRLLPLL\n";
+        let info = file("fasta", content);
+        assert!(verify_download(&info, &downloaded(content)).is_ok());
+
+        assert!(verify_download(&info, &downloaded(b"tampered")).is_err());
+
+        let truncated = DownloadedFile {
+            size: 3,
+            ..downloaded(content)
+        };
+        assert!(verify_download(&info, &truncated).is_err());
+
+        let md5_only = FileInfo {
+            checksum: compute_md5(content),
+            sha256: None,
+            ..info.clone()
+        };
+        assert!(verify_download(&md5_only, &downloaded(content)).is_ok());
+
+        let unverifiable = FileInfo {
+            checksum: "abc".to_string(),
+            sha256: None,
+            ..info
+        };
+        assert!(verify_download(&unverifiable, &downloaded(content)).is_err());
+    }
+
+    #[test]
+    fn test_pending_files() {
+        let fasta = file("fasta", b"fasta");
+        let json = file("json", b"json");
+        let upstream = vec![fasta.clone(), json.clone()];
+
+        let pending = pending_files(&upstream, &[]).unwrap();
+        assert_eq!(pending.len(), 2);
+
+        let local = vec![LocalFile {
+            format: "fasta".to_string(),
+            checksum: fasta.checksum.to_uppercase(),
+        }];
+        let pending = pending_files(&upstream, &local).unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].format, "json");
+
+        let conflicting = vec![LocalFile {
+            format: "fasta".to_string(),
+            checksum: json.checksum,
+        }];
+        assert!(pending_files(&upstream, &conflicting).is_err());
+    }
+}
@@ -0,0 +1,13 @@
+-- Version yanking
+-- A yanked version stays downloadable for existing lockfiles but is hidden
+-- from resolution of new specs and from "latest version" lookups.
+
+ALTER TABLE versions
+    ADD COLUMN yanked_at TIMESTAMPTZ,
+    ADD COLUMN yanked_reason TEXT;
+
+-- Most lookups only care about non-yanked versions
+CREATE INDEX versions_not_yanked_idx ON versions(entry_id, published_at DESC) WHERE yanked_at IS NULL;
+
+COMMENT ON COLUMN versions.yanked_at IS 'When the version was yanked (NULL if not yanked)';
+COMMENT ON COLUMN versions.yanked_reason IS 'Why the version was yanked (corrupt file, wrong organism, ...)';
@@ -0,0 +1,89 @@
+-- Sealed versions
+-- A version is sealed as soon as its first file is registered. After that the
+-- checksum, storage key and size of its files can no longer change, so a
+-- lockfile pinned to a checksum always gets the bytes it was created with.
+-- Administrators can override the seal for a single transaction with
+-- SET LOCAL bdp.allow_sealed_overwrite = 'on'; the API audits every override.
+
+ALTER TABLE versions ADD COLUMN sealed_at TIMESTAMPTZ;
+
+COMMENT ON COLUMN versions.sealed_at IS 'When the version files were sealed (NULL while the version is still being assembled)';
+
+-- Everything that already has files is considered published
+UPDATE versions v
+SET sealed_at = COALESCE(v.published_at, NOW())
+WHERE EXISTS (SELECT 1 FROM version_files vf WHERE vf.version_id = v.id);
+
+-- ============================================================================
+-- Seal a version when its first file is registered
+-- ============================================================================
+
+CREATE OR REPLACE FUNCTION seal_version_on_file_insert()
+RETURNS TRIGGER AS $$
+BEGIN
+    UPDATE versions
+    SET sealed_at = NOW()
+    WHERE id = NEW.version_id AND sealed_at IS NULL;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_seal_version_on_file_insert
+AFTER INSERT ON version_files
+FOR EACH ROW EXECUTE FUNCTION seal_version_on_file_insert();
+
+-- ============================================================================
+-- Reject changes to files of sealed versions
+-- ============================================================================
+-- Cascading deletes from versions are unaffected: by the time they run the
+-- parent version row is gone, so the version no longer counts as sealed.
+
+CREATE OR REPLACE FUNCTION reject_sealed_version_file_changes()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF COALESCE(current_setting('bdp.allow_sealed_overwrite', true), '') = 'on' THEN
+        RETURN COALESCE(NEW, OLD);
+    END IF;
+
+    IF NOT EXISTS (SELECT 1 FROM versions WHERE id = OLD.version_id AND sealed_at IS NOT NULL) THEN
+        RETURN COALESCE(NEW, OLD);
+    END IF;
+
+    IF TG_OP = 'DELETE' THEN
+        RAISE EXCEPTION 'cannot delete % file of sealed version %', OLD.format, OLD.version_id
+            USING ERRCODE = 'integrity_constraint_violation';
+    END IF;
+
+    IF NEW.checksum IS DISTINCT FROM OLD.checksum
+        OR NEW.s3_key IS DISTINCT FROM OLD.s3_key
+        OR NEW.size_bytes IS DISTINCT FROM OLD.size_bytes
+        OR NEW.version_id IS DISTINCT FROM OLD.version_id THEN
+        RAISE EXCEPTION 'cannot modify % file of sealed version %', OLD.format, OLD.version_id
+            USING ERRCODE = 'integrity_constraint_violation';
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_reject_sealed_version_file_changes
+BEFORE UPDATE OR DELETE ON version_files
+FOR EACH ROW EXECUTE FUNCTION reject_sealed_version_file_changes();
+
+-- ============================================================================
+-- Audit actions for sealed writes
+-- ============================================================================
+
+ALTER TABLE audit_log DROP CONSTRAINT IF EXISTS action_check;
+
+ALTER TABLE audit_log
+ADD CONSTRAINT action_check CHECK (action IN (
+    'create', 'update', 'delete', 'read',
+    'login', 'logout', 'register',
+    'publish', 'unpublish', 'archive',
+    'upload', 'download',
+    'grant', 'revoke',
+    'ingest',
+    'overwrite_rejected', 'seal_override',
+    'other'
+));
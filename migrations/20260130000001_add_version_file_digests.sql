@@ -0,0 +1,54 @@
+-- Version file digests
+-- Every version file carries its SHA-256 plus the MD5 published upstream where
+-- known, and each version keeps a SHA256SUMS-style manifest generated when its
+-- files are published.
+
+ALTER TABLE version_files
+    ADD COLUMN sha256 VARCHAR(64),
+    ADD COLUMN md5 VARCHAR(32);
+
+COMMENT ON COLUMN version_files.sha256 IS 'Lowercase hex SHA-256 of the stored file';
+COMMENT ON COLUMN version_files.md5 IS 'Lowercase hex MD5 of the file, as published upstream where available';
+
+-- The checksum column has held a mix of SHA-256 and MD5 digests
+UPDATE version_files SET sha256 = LOWER(checksum) WHERE checksum ~ '^[0-9a-fA-F]{64}$';
+UPDATE version_files SET md5 = LOWER(checksum) WHERE checksum ~ '^[0-9a-fA-F]{32}$';
+
+ALTER TABLE versions ADD COLUMN checksum_manifest TEXT;
+
+COMMENT ON COLUMN versions.checksum_manifest IS 'SHA256SUMS manifest ("<sha256>  <filename>" per line) generated at publish time';
+
+-- ============================================================================
+-- Digests of sealed files are as immutable as their checksums
+-- ============================================================================
+-- Filling in a digest that was unknown is allowed; changing a known one is not.
+
+CREATE OR REPLACE FUNCTION reject_sealed_version_file_changes()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF COALESCE(current_setting('bdp.allow_sealed_overwrite', true), '') = 'on' THEN
+        RETURN COALESCE(NEW, OLD);
+    END IF;
+
+    IF NOT EXISTS (SELECT 1 FROM versions WHERE id = OLD.version_id AND sealed_at IS NOT NULL) THEN
+        RETURN COALESCE(NEW, OLD);
+    END IF;
+
+    IF TG_OP = 'DELETE' THEN
+        RAISE EXCEPTION 'cannot delete % file of sealed version %', OLD.format, OLD.version_id
+            USING ERRCODE = 'integrity_constraint_violation';
+    END IF;
+
+    IF NEW.checksum IS DISTINCT FROM OLD.checksum
+        OR NEW.s3_key IS DISTINCT FROM OLD.s3_key
+        OR NEW.size_bytes IS DISTINCT FROM OLD.size_bytes
+        OR NEW.version_id IS DISTINCT FROM OLD.version_id
+        OR (OLD.sha256 IS NOT NULL AND NEW.sha256 IS DISTINCT FROM OLD.sha256)
+        OR (OLD.md5 IS NOT NULL AND NEW.md5 IS DISTINCT FROM OLD.md5) THEN
+        RAISE EXCEPTION 'cannot modify % file of sealed version %', OLD.format, OLD.version_id
+            USING ERRCODE = 'integrity_constraint_violation';
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,37 @@
+-- Registry mirroring provenance
+--
+-- Each invocation of the mirror records a run with the upstream registry and
+-- the selectors it synced. Every version copied from upstream is linked to the
+-- run that brought it in, so operators can tell which local versions are
+-- mirrored, where they came from and when.
+
+CREATE TABLE mirror_runs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    upstream_url TEXT NOT NULL,
+    selectors JSONB NOT NULL DEFAULT '[]'::jsonb,
+    status VARCHAR(20) NOT NULL DEFAULT 'running',
+    stats JSONB,
+    error TEXT,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    finished_at TIMESTAMPTZ,
+    CONSTRAINT mirror_runs_status_check CHECK (status IN ('running', 'completed', 'failed'))
+);
+
+CREATE INDEX mirror_runs_started_at_idx ON mirror_runs (started_at DESC);
+
+-- Latest mirror state of each version
+CREATE TABLE mirrored_versions (
+    version_id UUID PRIMARY KEY REFERENCES versions(id) ON DELETE CASCADE,
+    run_id UUID NOT NULL REFERENCES mirror_runs(id) ON DELETE CASCADE,
+    upstream_url TEXT NOT NULL,
+    upstream_version_id UUID NOT NULL,
+    files_mirrored INTEGER NOT NULL DEFAULT 0,
+    bytes_mirrored BIGINT NOT NULL DEFAULT 0,
+    first_mirrored_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    mirrored_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX mirrored_versions_run_id_idx ON mirrored_versions (run_id);
+
+COMMENT ON TABLE mirror_runs IS 'Runs of the registry mirror against an upstream BDP server';
+COMMENT ON TABLE mirrored_versions IS 'Versions copied from an upstream registry and the run that last synced them';
@@ -0,0 +1,36 @@
+-- Ingestion scheduler state
+--
+-- One row per source pipeline. Replicas claim a due schedule with a
+-- compare-and-swap on next_run_at, so each cron tick fires on exactly one
+-- server even when several are running.
+
+CREATE TABLE ingestion_schedules (
+    source VARCHAR(50) PRIMARY KEY,
+    cron_expression TEXT NOT NULL,
+    next_run_at TIMESTAMPTZ NOT NULL,
+    last_run_at TIMESTAMPTZ,
+    last_status VARCHAR(20),
+    last_error TEXT,
+    last_versions_enqueued INTEGER NOT NULL DEFAULT 0,
+    claimed_by TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT ingestion_schedules_last_status_check
+        CHECK (last_status IS NULL OR last_status IN ('running', 'completed', 'failed'))
+);
+
+CREATE TRIGGER ingestion_schedules_updated_at
+    BEFORE UPDATE ON ingestion_schedules
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at();
+
+-- Versions the scheduler has enqueued, so a version is only queued once
+CREATE TABLE scheduled_ingestions (
+    source VARCHAR(50) NOT NULL,
+    external_version VARCHAR(64) NOT NULL,
+    enqueued_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (source, external_version)
+);
+
+COMMENT ON TABLE ingestion_schedules IS 'Cron schedule and last-run state of each ingestion source';
+COMMENT ON TABLE scheduled_ingestions IS 'Upstream versions enqueued for ingestion by the scheduler';
@@ -0,0 +1,40 @@
+-- Dead-letter store for ingestion records
+--
+-- Records that fail parsing or storage are kept here with their raw text, so
+-- they can be inspected per job and re-run after a parser fix without
+-- re-ingesting the whole release.
+
+CREATE TABLE ingestion_dead_letters (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    job_id UUID NOT NULL REFERENCES ingestion_jobs(id) ON DELETE CASCADE,
+    work_unit_id UUID REFERENCES ingestion_work_units(id) ON DELETE SET NULL,
+    stage VARCHAR(20) NOT NULL,
+    record_identifier TEXT,
+    source_file TEXT,
+    source_offset BIGINT,
+    raw_record TEXT NOT NULL,
+    error_message TEXT NOT NULL,
+    error_chain JSONB NOT NULL DEFAULT '[]'::jsonb,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 1,
+    reprocessed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT ingestion_dead_letters_stage_check
+        CHECK (stage IN ('parse', 'store')),
+    CONSTRAINT ingestion_dead_letters_status_check
+        CHECK (status IN ('pending', 'reprocessed'))
+);
+
+CREATE INDEX idx_ingestion_dead_letters_job ON ingestion_dead_letters(job_id, created_at);
+CREATE INDEX idx_ingestion_dead_letters_pending ON ingestion_dead_letters(job_id)
+    WHERE status = 'pending';
+
+CREATE TRIGGER ingestion_dead_letters_updated_at
+    BEFORE UPDATE ON ingestion_dead_letters
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at();
+
+COMMENT ON TABLE ingestion_dead_letters IS 'Records that failed parsing or storage during ingestion, kept for inspection and reprocessing';
+COMMENT ON COLUMN ingestion_dead_letters.raw_record IS 'Raw record text as read from the source file';
+COMMENT ON COLUMN ingestion_dead_letters.error_chain IS 'Error and its causes, outermost first';
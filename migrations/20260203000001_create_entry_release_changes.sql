@@ -0,0 +1,33 @@
+-- Incremental release ingestion
+--
+-- Entries are diffed against the previous release by sequence and annotation
+-- checksum. Only added, modified and removed entries are recorded here, so the
+-- table grows with release churn rather than with the size of the database.
+-- Unchanged entries keep their existing version and files.
+
+ALTER TABLE protein_metadata ADD COLUMN annotation_checksum VARCHAR(64);
+
+COMMENT ON COLUMN protein_metadata.annotation_checksum IS 'SHA-256 of the entry annotations (everything but the sequence), used to detect annotation-only changes between releases';
+COMMENT ON COLUMN protein_metadata.uniprot_version IS 'Latest UniProt release (e.g., 2025_01) that contains this entry';
+
+CREATE TABLE entry_release_changes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    external_version VARCHAR(64) NOT NULL,
+    entry_id UUID NOT NULL REFERENCES registry_entries(id) ON DELETE CASCADE,
+    change_type VARCHAR(30) NOT NULL,
+    previous_version VARCHAR(64),
+    version VARCHAR(64),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT entry_release_changes_type_check
+        CHECK (change_type IN ('added', 'sequence_modified', 'annotation_modified', 'removed')),
+    UNIQUE (organization_id, external_version, entry_id)
+);
+
+CREATE INDEX idx_entry_release_changes_release
+    ON entry_release_changes(organization_id, external_version, change_type);
+CREATE INDEX idx_entry_release_changes_entry ON entry_release_changes(entry_id);
+
+COMMENT ON TABLE entry_release_changes IS 'Entries added, modified or removed by an upstream release; unchanged entries are not recorded';
+COMMENT ON COLUMN entry_release_changes.previous_version IS 'Internal version of the entry before the release (NULL for added entries)';
+COMMENT ON COLUMN entry_release_changes.version IS 'Internal version created for the entry (NULL for removed entries)';
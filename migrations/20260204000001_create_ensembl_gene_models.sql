@@ -0,0 +1,139 @@
+-- Ensembl gene models
+--
+-- Each species of an Ensembl release is a data source ('gene_model') whose
+-- versions carry the release's GTF/GFF3 and cDNA/peptide FASTA files. Genes
+-- and transcripts are stored relationally, keyed by their stable IDs, and
+-- track the releases they appear in so version bump detection can count what
+-- a release added, removed or changed without keeping every release's rows.
+
+-- ============================================================================
+-- 1. Allow 'gene_model' data sources
+-- ============================================================================
+-- Two overlapping constraints guard source_type: source_type_check (types of
+-- the original schema) and check_source_type (types added by the GO and
+-- InterPro pipelines). A row must pass both, so only their intersection was
+-- accepted. Replace them with a single constraint allowing every type.
+
+ALTER TABLE data_sources DROP CONSTRAINT IF EXISTS source_type_check;
+ALTER TABLE data_sources DROP CONSTRAINT IF EXISTS check_source_type;
+
+ALTER TABLE data_sources
+ADD CONSTRAINT check_source_type CHECK (
+    source_type IN (
+        'protein',
+        'genome',
+        'organism',
+        'taxonomy',
+        'bundle',
+        'transcript',
+        'annotation',
+        'structure',
+        'pathway',
+        'genomic_sequence',
+        'go_term',
+        'interpro_entry',
+        'gene_model',
+        'other'
+    )
+);
+
+-- ============================================================================
+-- 2. Species metadata
+-- ============================================================================
+
+CREATE TABLE ensembl_species_metadata (
+    data_source_id UUID PRIMARY KEY REFERENCES data_sources(id) ON DELETE CASCADE,
+    species VARCHAR(255) NOT NULL UNIQUE,          -- homo_sapiens
+    display_name VARCHAR(255) NOT NULL,            -- Human
+    division VARCHAR(50) NOT NULL,                 -- EnsemblVertebrates
+    taxonomy_id INTEGER NOT NULL,                  -- NCBI Taxonomy: 9606
+    taxonomy_data_source_id UUID REFERENCES data_sources(id) ON DELETE SET NULL,
+    assembly VARCHAR(100) NOT NULL,                -- GRCh38.p14
+    assembly_accession VARCHAR(50),                -- GCA_000001405.29
+    genebuild VARCHAR(100),
+    ensembl_release INTEGER NOT NULL,              -- Latest ingested release
+    gene_count INTEGER NOT NULL DEFAULT 0,
+    transcript_count INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_ensembl_species_taxonomy ON ensembl_species_metadata(taxonomy_id);
+
+CREATE TRIGGER ensembl_species_metadata_updated_at
+    BEFORE UPDATE ON ensembl_species_metadata
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at();
+
+-- ============================================================================
+-- 3. Genes and transcripts
+-- ============================================================================
+
+CREATE TABLE ensembl_genes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    data_source_id UUID NOT NULL REFERENCES data_sources(id) ON DELETE CASCADE,
+    gene_id VARCHAR(50) NOT NULL,                  -- ENSG00000139618
+    gene_version INTEGER,
+    gene_name VARCHAR(255),                        -- BRCA2
+    biotype VARCHAR(100),                          -- protein_coding
+    seqid VARCHAR(255) NOT NULL,                   -- 13
+    start_pos BIGINT NOT NULL,
+    end_pos BIGINT NOT NULL,
+    strand CHAR(1) NOT NULL,
+    location_checksum VARCHAR(64) NOT NULL,
+    annotation_checksum VARCHAR(64) NOT NULL,
+    first_release INTEGER NOT NULL,
+    last_release INTEGER NOT NULL,
+    location_changed_release INTEGER,
+    annotation_changed_release INTEGER,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT ensembl_genes_strand_check CHECK (strand IN ('+', '-', '.')),
+    UNIQUE (data_source_id, gene_id)
+);
+
+CREATE INDEX idx_ensembl_genes_last_release ON ensembl_genes(data_source_id, last_release);
+CREATE INDEX idx_ensembl_genes_gene_name ON ensembl_genes(gene_name);
+CREATE INDEX idx_ensembl_genes_location ON ensembl_genes(data_source_id, seqid, start_pos);
+
+CREATE TRIGGER ensembl_genes_updated_at
+    BEFORE UPDATE ON ensembl_genes
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at();
+
+CREATE TABLE ensembl_transcripts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    data_source_id UUID NOT NULL REFERENCES data_sources(id) ON DELETE CASCADE,
+    gene_ref UUID NOT NULL REFERENCES ensembl_genes(id) ON DELETE CASCADE,
+    transcript_id VARCHAR(50) NOT NULL,            -- ENST00000380152
+    transcript_version INTEGER,
+    transcript_name VARCHAR(255),                  -- BRCA2-201
+    biotype VARCHAR(100),
+    start_pos BIGINT NOT NULL,
+    end_pos BIGINT NOT NULL,
+    exons JSONB NOT NULL DEFAULT '[]'::jsonb,      -- [[start, end], ...] in transcript order
+    protein_id VARCHAR(50),                        -- ENSP00000369497
+    first_release INTEGER NOT NULL,
+    last_release INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (data_source_id, transcript_id)
+);
+
+CREATE INDEX idx_ensembl_transcripts_gene ON ensembl_transcripts(gene_ref);
+CREATE INDEX idx_ensembl_transcripts_last_release ON ensembl_transcripts(data_source_id, last_release);
+CREATE INDEX idx_ensembl_transcripts_protein ON ensembl_transcripts(protein_id);
+
+CREATE TRIGGER ensembl_transcripts_updated_at
+    BEFORE UPDATE ON ensembl_transcripts
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at();
+
+COMMENT ON TABLE ensembl_species_metadata IS 'Ensembl species gene sets, one gene_model data source per species';
+COMMENT ON TABLE ensembl_genes IS 'Ensembl genes by stable ID; rows of removed genes are kept with their last release';
+COMMENT ON TABLE ensembl_transcripts IS 'Ensembl transcripts by stable ID; rows of removed transcripts are kept with their last release';
+COMMENT ON COLUMN ensembl_genes.location_checksum IS 'SHA-256 of the gene and transcript coordinates, used to detect coordinate changes between releases';
+COMMENT ON COLUMN ensembl_genes.annotation_checksum IS 'SHA-256 of the gene and transcript names, biotypes and versions, used to detect annotation changes between releases';
+COMMENT ON COLUMN ensembl_genes.last_release IS 'Latest Ensembl release containing the gene';
+COMMENT ON COLUMN ensembl_genes.location_changed_release IS 'Latest release that changed the gene coordinates';
+COMMENT ON COLUMN ensembl_genes.annotation_changed_release IS 'Latest release that changed the gene annotations';
@@ -0,0 +1,71 @@
+-- RefSeq genome assemblies
+--
+-- Each RefSeq assembly (GCF accession) is a 'genome' data source keyed by its
+-- unversioned accession; every assembly version (GCF_000005845.1, .2, ...)
+-- becomes a version carrying the genomic FASTA, GFF3 annotation and protein
+-- FASTA files. RefSeq proteins are cross-linked to UniProt entries through
+-- sequence_protein_mappings.
+
+-- ============================================================================
+-- 1. Assembly metadata
+-- ============================================================================
+
+CREATE TABLE genome_assembly_metadata (
+    data_source_id UUID PRIMARY KEY REFERENCES data_sources(id) ON DELETE CASCADE,
+    accession VARCHAR(50) NOT NULL UNIQUE,           -- GCF_000005845 (unversioned)
+    accession_version VARCHAR(50) NOT NULL,          -- GCF_000005845.2 (latest ingested)
+    assembly_name VARCHAR(255) NOT NULL,             -- ASM584v2
+    organism_name VARCHAR(255) NOT NULL,
+    infraspecific_name VARCHAR(255),                 -- strain=K-12 substr. MG1655
+    taxonomy_id INTEGER NOT NULL,
+    species_taxonomy_id INTEGER,
+    taxonomy_data_source_id UUID REFERENCES data_sources(id) ON DELETE SET NULL,
+    refseq_category VARCHAR(50),                     -- reference genome, na
+    assembly_level VARCHAR(50),                      -- Complete Genome, Chromosome, Scaffold, Contig
+    genome_rep VARCHAR(20),                          -- Full, Partial
+    release_date DATE,
+    annotation_date DATE,                            -- date of the current RefSeq annotation
+    submitter VARCHAR(255),
+    paired_accession VARCHAR(50),                    -- GCA_000005845.2
+    ftp_path TEXT NOT NULL,
+
+    -- Assembly stats, computed from the ingested files
+    total_length BIGINT,
+    sequence_count INTEGER,
+    gc_percent NUMERIC(5,2),
+    scaffold_n50 BIGINT,
+    gene_count INTEGER,
+    protein_count INTEGER,
+    mapped_protein_count INTEGER,
+
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX idx_genome_assembly_taxonomy ON genome_assembly_metadata(taxonomy_id);
+CREATE INDEX idx_genome_assembly_category ON genome_assembly_metadata(refseq_category);
+CREATE INDEX idx_genome_assembly_level ON genome_assembly_metadata(assembly_level);
+
+CREATE TRIGGER update_genome_assembly_metadata_updated_at
+    BEFORE UPDATE ON genome_assembly_metadata
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at();
+
+COMMENT ON TABLE genome_assembly_metadata IS 'RefSeq genome assemblies from assembly_summary.txt, with stats computed from their files';
+COMMENT ON COLUMN genome_assembly_metadata.accession_version IS 'Versioned GCF accession of the latest ingested assembly version';
+COMMENT ON COLUMN genome_assembly_metadata.mapped_protein_count IS 'RefSeq proteins cross-linked to a UniProt entry';
+
+-- ============================================================================
+-- 2. RefSeq protein accessions on protein mappings
+-- ============================================================================
+-- Assembly mappings link the assembly to UniProt entries; the RefSeq protein
+-- behind each link is kept alongside it.
+
+ALTER TABLE sequence_protein_mappings
+ADD COLUMN protein_accession VARCHAR(50);
+
+CREATE INDEX idx_sequence_protein_accession
+    ON sequence_protein_mappings(protein_accession)
+    WHERE protein_accession IS NOT NULL;
+
+COMMENT ON COLUMN sequence_protein_mappings.protein_accession IS 'Versioned RefSeq protein accession (e.g., NP_414542.1) for assembly mappings';
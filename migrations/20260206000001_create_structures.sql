@@ -0,0 +1,72 @@
+-- Macromolecular structures (PDB entries and AlphaFold DB models)
+--
+-- Each PDB entry (4INS) and AlphaFold DB model (AF-P01308-F1) is a
+-- 'structure' data source whose versions carry the mmCIF and, where one is
+-- published, PDB format files. PDB entries are versioned by their revision
+-- history, AlphaFold models by the AlphaFold DB model version. Polymer chains
+-- are linked to the UniProt entries they were built from through the
+-- reference sequence cross-references of the mmCIF file.
+
+-- ============================================================================
+-- 1. Structure metadata
+-- ============================================================================
+
+CREATE TABLE structure_metadata (
+    data_source_id UUID PRIMARY KEY REFERENCES data_sources(id) ON DELETE CASCADE,
+    structure_id VARCHAR(50) NOT NULL UNIQUE,        -- 4INS, AF-P01308-F1
+    archive VARCHAR(20) NOT NULL,                    -- pdb, alphafold
+    title TEXT,
+    method VARCHAR(100),                             -- X-RAY DIFFRACTION, ELECTRON MICROSCOPY, PREDICTED
+    resolution NUMERIC(6,2),                         -- Angstrom, experimental structures only
+    release_date DATE,                               -- initial release
+    revision VARCHAR(20),                            -- PDB revision (1.3) or AlphaFold DB model version (v4)
+    revision_date DATE,
+    mean_plddt NUMERIC(5,2),                         -- AlphaFold global confidence (0-100)
+    taxonomy_id INTEGER,
+    taxonomy_data_source_id UUID REFERENCES data_sources(id) ON DELETE SET NULL,
+    chain_count INTEGER NOT NULL DEFAULT 0,
+    ligands JSONB NOT NULL DEFAULT '[]'::jsonb,      -- [{"comp_id": "ZN", "name": "ZINC ION", "count": 2}]
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT structure_archive_check CHECK (archive IN ('pdb', 'alphafold'))
+);
+
+CREATE INDEX idx_structure_archive ON structure_metadata(archive);
+CREATE INDEX idx_structure_method ON structure_metadata(method);
+CREATE INDEX idx_structure_taxonomy ON structure_metadata(taxonomy_id);
+CREATE INDEX idx_structure_ligands ON structure_metadata USING GIN (ligands);
+
+CREATE TRIGGER update_structure_metadata_updated_at
+    BEFORE UPDATE ON structure_metadata
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at();
+
+COMMENT ON TABLE structure_metadata IS 'PDB entries and AlphaFold DB models, from the metadata categories of their mmCIF files';
+COMMENT ON COLUMN structure_metadata.revision IS 'PDB revision (major.minor) or AlphaFold DB model version of the latest ingested files';
+COMMENT ON COLUMN structure_metadata.ligands IS 'Non-polymer entities other than water, with their instance counts';
+
+-- ============================================================================
+-- 2. Polymer chains
+-- ============================================================================
+
+CREATE TABLE structure_chains (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    structure_data_source_id UUID NOT NULL REFERENCES data_sources(id) ON DELETE CASCADE,
+    chain_id VARCHAR(10) NOT NULL,                   -- author chain ID
+    entity_id VARCHAR(10) NOT NULL,
+    description TEXT,                                -- INSULIN A CHAIN
+    polymer_type VARCHAR(50),                        -- polypeptide(L), polyribonucleotide
+    sequence_length INTEGER,
+    uniprot_accession VARCHAR(20),                   -- P01308
+    protein_data_source_id UUID REFERENCES data_sources(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    UNIQUE (structure_data_source_id, chain_id)
+);
+
+CREATE INDEX idx_structure_chains_uniprot ON structure_chains(uniprot_accession);
+CREATE INDEX idx_structure_chains_protein ON structure_chains(protein_data_source_id);
+
+COMMENT ON TABLE structure_chains IS 'Polymer chains of structures, linked to the UniProt entries of their reference sequences';
+COMMENT ON COLUMN structure_chains.protein_data_source_id IS 'UniProt entry of uniprot_accession, when it has been ingested';
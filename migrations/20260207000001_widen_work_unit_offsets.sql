@@ -0,0 +1,12 @@
+-- Byte-range work units
+--
+-- Work units of large source files address byte ranges (or BGZF virtual
+-- offsets) of the file instead of record indices, so workers can seek to
+-- their range. Offsets into files of hundreds of GB don't fit in INTEGER.
+
+ALTER TABLE ingestion_work_units
+    ALTER COLUMN start_offset TYPE BIGINT,
+    ALTER COLUMN end_offset TYPE BIGINT;
+
+COMMENT ON COLUMN ingestion_work_units.start_offset IS 'First record index, or offset of the first record in the source file';
+COMMENT ON COLUMN ingestion_work_units.end_offset IS 'Last record index (inclusive), or offset just past the last record';
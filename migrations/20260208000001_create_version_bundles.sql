@@ -0,0 +1,29 @@
+-- Aggregate bundles
+--
+-- An aggregate version (a proteome, an organism bundle) is resolved to
+-- thousands of small member files. A bundle concatenates the files of every
+-- member in its dependency closure into one artifact per format:
+-- multi-FASTA for 'fasta', JSON Lines for 'json' and a tar archive for
+-- anything else ('dat'). The member index records where each member's bytes
+-- sit inside the bundle, so clients can split it back into members.
+
+CREATE TABLE version_bundles (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    version_id UUID NOT NULL REFERENCES versions(id) ON DELETE CASCADE,
+    format VARCHAR(50) NOT NULL,                     -- member format: fasta, json, dat
+    bundle_format VARCHAR(20) NOT NULL,              -- multifasta, jsonl, tar
+    s3_key TEXT NOT NULL,
+    sha256 VARCHAR(64) NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    member_count INTEGER NOT NULL,
+    members JSONB NOT NULL DEFAULT '[]'::jsonb,      -- [{"source": "uniprot:P01308@1.0", "offset": 0, "size": 412, "sha256": "..."}]
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT version_bundles_format_unique UNIQUE (version_id, format),
+    CONSTRAINT version_bundles_bundle_format_check CHECK (bundle_format IN ('multifasta', 'jsonl', 'tar'))
+);
+
+CREATE INDEX idx_version_bundles_version ON version_bundles(version_id);
+
+COMMENT ON TABLE version_bundles IS 'Concatenated member files of aggregate versions, one per member format';
+COMMENT ON COLUMN version_bundles.members IS 'Member index: byte range and SHA-256 of each member inside the bundle';
@@ -0,0 +1,16 @@
+-- Upstream digests
+-- Pipelines verify every release file against the checksum its provider
+-- publishes (.md5 sidecars, metalinks, release metadata) and fail the job on
+-- a mismatch. The verified digest is recorded on each version created from
+-- the release, next to the provider's release date.
+
+ALTER TABLE versions
+    ADD COLUMN upstream_digest VARCHAR(128),
+    ADD COLUMN upstream_digest_algorithm VARCHAR(10),
+    ADD COLUMN upstream_verified_at TIMESTAMPTZ,
+    ADD CONSTRAINT versions_upstream_digest_algorithm_check
+        CHECK (upstream_digest_algorithm IN ('md5', 'sha256'));
+
+COMMENT ON COLUMN versions.upstream_digest IS 'Lowercase hex digest of the release file, as published upstream and verified at ingestion';
+COMMENT ON COLUMN versions.upstream_digest_algorithm IS 'Algorithm of upstream_digest: md5 or sha256';
+COMMENT ON COLUMN versions.upstream_verified_at IS 'When the release file was verified against upstream_digest';
@@ -0,0 +1,20 @@
+-- Per-data-source version mappings
+-- A mapping either covers every entry of an organization (entry_id NULL, for
+-- releases such as the NCBI taxdump) or a single data source. Within that
+-- scope each upstream release maps to one internal version and back.
+
+ALTER TABLE version_mappings
+    ADD COLUMN entry_id UUID REFERENCES registry_entries(id) ON DELETE CASCADE;
+
+ALTER TABLE version_mappings
+    DROP CONSTRAINT version_mappings_organization_slug_external_version_key;
+
+ALTER TABLE version_mappings
+    ADD CONSTRAINT version_mappings_external_key
+        UNIQUE NULLS NOT DISTINCT (organization_slug, entry_id, external_version),
+    ADD CONSTRAINT version_mappings_internal_key
+        UNIQUE NULLS NOT DISTINCT (organization_slug, entry_id, internal_version);
+
+CREATE INDEX version_mappings_entry_idx ON version_mappings(entry_id) WHERE entry_id IS NOT NULL;
+
+COMMENT ON COLUMN version_mappings.entry_id IS 'Data source the mapping applies to; NULL for organization-wide releases';
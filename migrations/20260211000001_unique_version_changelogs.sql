@@ -0,0 +1,12 @@
+-- One changelog per version
+-- save_changelog upserts on version_id, which needs a unique index to
+-- conflict on. Keep the most recent changelog where a version has several.
+
+DELETE FROM version_changelogs vc
+USING version_changelogs newer
+WHERE newer.version_id = vc.version_id
+  AND (newer.created_at, newer.id) > (vc.created_at, vc.id);
+
+DROP INDEX IF EXISTS idx_version_changelogs_version_id;
+
+CREATE UNIQUE INDEX idx_version_changelogs_version_id ON version_changelogs(version_id);
@@ -0,0 +1,16 @@
+-- Look up pending dead letters by record
+--
+-- Release diffs check whether a missing entry was dead-lettered rather than
+-- removed upstream. Records that failed parsing are now keyed by the
+-- accession on their AC line too; key the existing ones the same way so the
+-- check is an index lookup instead of a scan of the raw text.
+
+UPDATE ingestion_dead_letters
+SET record_identifier = substring(raw_record FROM '\nAC +([^;[:space:]]+);')
+WHERE record_identifier IS NULL;
+
+CREATE INDEX idx_ingestion_dead_letters_pending_record
+    ON ingestion_dead_letters(job_id, record_identifier)
+    WHERE status = 'pending';
+
+COMMENT ON COLUMN ingestion_dead_letters.record_identifier IS 'Key of the record in its source (e.g. UniProt accession), when it could be read';
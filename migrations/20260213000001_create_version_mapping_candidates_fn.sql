@@ -0,0 +1,42 @@
+-- Version mapping lookup candidates
+-- Every release-to-version mapping visible to a data source of an
+-- organization, by lookup priority: the data source's own versions (0), its
+-- recorded mappings (1), then the organization-wide mappings (2). Passing a
+-- NULL data source returns only the organization-wide mappings.
+
+CREATE FUNCTION version_mapping_candidates(
+    p_organization_slug TEXT,
+    p_data_source_slug TEXT
+)
+RETURNS TABLE (
+    organization_slug TEXT,
+    data_source_slug TEXT,
+    internal_version TEXT,
+    external_version TEXT,
+    release_date DATE,
+    priority INT
+) AS $$
+    SELECT o.slug::TEXT, re.slug::TEXT, v.version::TEXT, v.external_version::TEXT,
+           v.release_date, 0
+    FROM versions v
+    JOIN registry_entries re ON re.id = v.entry_id
+    JOIN organizations o ON o.id = re.organization_id
+    WHERE LOWER(o.slug) = LOWER(p_organization_slug)
+      AND LOWER(re.slug) = LOWER(p_data_source_slug)
+      AND v.external_version IS NOT NULL
+    UNION ALL
+    SELECT vm.organization_slug::TEXT, re.slug::TEXT, vm.internal_version::TEXT,
+           vm.external_version::TEXT, vm.release_date, 1
+    FROM version_mappings vm
+    JOIN registry_entries re ON re.id = vm.entry_id
+    WHERE LOWER(vm.organization_slug) = LOWER(p_organization_slug)
+      AND LOWER(re.slug) = LOWER(p_data_source_slug)
+    UNION ALL
+    SELECT vm.organization_slug::TEXT, NULL, vm.internal_version::TEXT,
+           vm.external_version::TEXT, vm.release_date, 2
+    FROM version_mappings vm
+    WHERE LOWER(vm.organization_slug) = LOWER(p_organization_slug)
+      AND vm.entry_id IS NULL
+$$ LANGUAGE sql STABLE;
+
+COMMENT ON FUNCTION version_mapping_candidates(TEXT, TEXT) IS 'Release-to-version mappings visible to a data source, with lookup priority (0 = its versions, 1 = its mappings, 2 = organization-wide)';
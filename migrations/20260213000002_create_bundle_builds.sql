@@ -0,0 +1,26 @@
+-- Aggregate bundle builds
+--
+-- Every bundle (re)build is queued as an apalis job; this table tracks its
+-- status so that a failed build is visible to the API instead of only being
+-- logged. At most one build of a bundle is pending or running at a time.
+
+CREATE TABLE bundle_builds (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    version_id UUID NOT NULL REFERENCES versions(id) ON DELETE CASCADE,
+    format VARCHAR(50) NOT NULL,                     -- member format: fasta, json, dat
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',   -- 'pending' → 'running' → 'completed' | 'failed'
+    member_count INTEGER NOT NULL,
+    error_message TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    started_at TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ,
+
+    CONSTRAINT bundle_builds_status_check CHECK (status IN ('pending', 'running', 'completed', 'failed'))
+);
+
+CREATE INDEX idx_bundle_builds_version ON bundle_builds(version_id, format, created_at DESC);
+
+CREATE UNIQUE INDEX idx_bundle_builds_active ON bundle_builds(version_id, format)
+    WHERE status IN ('pending', 'running');
+
+COMMENT ON TABLE bundle_builds IS 'Queued and finished builds of aggregate bundles';